@@ -0,0 +1,375 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn classes(el: &web_sys::Element) -> Vec<String> {
+    let list = el.class_list();
+    (0..list.length())
+        .filter_map(|idx| list.item(idx))
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn class_toggles_leave_the_other_classes_alone() {
+    let test_wrapper = test_wrapper();
+    let cx = signal_scope();
+    let (active, set_active) = create_signal(cx, false);
+    let (big, set_big) = create_signal(cx, false);
+
+    mount_to(test_wrapper.clone(), move |cx| {
+        view! { cx,
+            <button
+                class="btn"
+                class:active=move || active.get()
+                class=("big", move || big.get())
+            >
+                "Click me"
+            </button>
+        }
+    });
+    let button = find(&test_wrapper, "button");
+    assert_eq!(classes(&button), ["btn"]);
+
+    set_active.set(true);
+    assert_eq!(classes(&button), ["btn", "active"]);
+
+    // a class another script adds survives both toggles
+    button.class_list().add_1("tooltip").unwrap();
+    set_big.set(true);
+    assert_eq!(classes(&button), ["btn", "active", "tooltip", "big"]);
+    set_active.set(false);
+    assert_eq!(classes(&button), ["btn", "tooltip", "big"]);
+    set_big.set(false);
+    assert_eq!(classes(&button), ["btn", "tooltip"]);
+}
+
+#[wasm_bindgen_test]
+fn hydrating_class_toggles_keeps_the_server_classes() {
+    // what a debug build renders from `HydrationKey::root("class")`, plus a
+    // class another script added before hydration
+    let test_wrapper = server_rendered(
+        r#"<button id="_class-1" class="btn active tooltip">Click me</button>"#,
+    );
+    let cx = signal_scope();
+    let (active, set_active) = create_signal(cx, true);
+    let (big, set_big) = create_signal(cx, false);
+
+    hydrate_to(&test_wrapper, "class", move |cx| {
+        view! { cx,
+            <button
+                class="btn"
+                class:active=move || active.get()
+                class:big=move || big.get()
+            >
+                "Click me"
+            </button>
+        }
+    });
+    let button = find(&test_wrapper, "button");
+    assert!(!button.has_attribute("id"));
+    assert_eq!(classes(&button), ["btn", "active", "tooltip"]);
+
+    set_big.set(true);
+    set_active.set(false);
+    assert_eq!(classes(&button), ["btn", "tooltip", "big"]);
+}
+
+fn style_of(el: &web_sys::HtmlElement, name: &str) -> String {
+    el.style().get_property_value(name).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn style_properties_and_the_style_attribute_leave_each_other_alone() {
+    let test_wrapper = test_wrapper();
+    let cx = signal_scope();
+    let (style, set_style) =
+        create_signal(cx, "margin: 1px; padding: 2px".to_string());
+    let (color, set_color) = create_signal(cx, Some("red"));
+    let (gap, set_gap) = create_signal(cx, "4px".to_string());
+
+    mount_to(test_wrapper.clone(), move |cx| {
+        view! { cx,
+            <div
+                style=move || style.get()
+                style:color=move || color.get()
+                style=("--gap", move || gap.get())
+            />
+        }
+    });
+    let div = find(&test_wrapper, "div");
+    assert_eq!(style_of(&div, "margin"), "1px");
+    assert_eq!(style_of(&div, "padding"), "2px");
+    assert_eq!(style_of(&div, "color"), "red");
+    assert_eq!(style_of(&div, "--gap"), "4px");
+
+    // updating the whole attribute only touches what it declares, and
+    // leaves the properties and what an animation set alone
+    div.style().set_property("opacity", "0.5").unwrap();
+    set_style.set("margin: 3px".to_string());
+    assert_eq!(style_of(&div, "margin"), "3px");
+    assert_eq!(style_of(&div, "padding"), "");
+    assert_eq!(style_of(&div, "color"), "red");
+    assert_eq!(style_of(&div, "--gap"), "4px");
+    assert_eq!(style_of(&div, "opacity"), "0.5");
+
+    // and the properties leave the attribute's declarations alone
+    set_gap.set("8px".to_string());
+    set_color.set(Some("blue"));
+    assert_eq!(style_of(&div, "--gap"), "8px");
+    assert_eq!(style_of(&div, "color"), "blue");
+    assert_eq!(style_of(&div, "margin"), "3px");
+
+    // `None` removes a property
+    set_color.set(None);
+    assert_eq!(style_of(&div, "color"), "");
+    assert_eq!(style_of(&div, "margin"), "3px");
+    assert_eq!(style_of(&div, "opacity"), "0.5");
+}
+
+#[wasm_bindgen_test]
+fn prop_value_sets_what_the_input_shows() {
+    let test_wrapper = test_wrapper();
+    let cx = signal_scope();
+    let (value, set_value) = create_signal(cx, "initial".to_string());
+
+    mount_to(test_wrapper.clone(), move |cx| {
+        view! { cx,
+            <input
+                value="initial"
+                prop:value=move || value.get()
+                on:input=move |ev| set_value.set(event_target_value(&ev))
+            />
+        }
+    });
+    let input: web_sys::HtmlInputElement =
+        find(&test_wrapper, "input").unchecked_into();
+
+    // the attribute is only the initial value
+    set_value.set("changed".to_string());
+    assert_eq!(input.value(), "changed");
+    assert_eq!(input.get_attribute("value").as_deref(), Some("initial"));
+
+    // what the user types comes back through the signal without moving
+    // their cursor
+    input.set_value("changed!");
+    input.set_selection_range(3, 3).unwrap();
+    input
+        .dispatch_event(&web_sys::Event::new("input").unwrap())
+        .unwrap();
+    assert_eq!(value.get(), "changed!");
+    assert_eq!(input.selection_start().unwrap(), Some(3));
+
+    // and the signal still wins over what was typed
+    set_value.set("reset".to_string());
+    assert_eq!(input.value(), "reset");
+}
+
+#[wasm_bindgen_test]
+fn prop_sets_a_custom_element_property_only_when_it_changes() {
+    js_sys::Function::new_no_args(
+        r#"customElements.define("prop-counter", class extends HTMLElement {
+            get level() { return this._level; }
+            set level(level) { this._level = level; this.writes = (this.writes || 0) + 1; }
+        })"#,
+    )
+    .call0(&wasm_bindgen::JsValue::NULL)
+    .unwrap();
+    let test_wrapper = test_wrapper();
+    let cx = signal_scope();
+    let (level, set_level) = create_signal(cx, 1);
+
+    mount_to(test_wrapper.clone(), move |cx| {
+        view! { cx, <prop-counter prop:level=move || level.get()/> }
+    });
+    let el = test_wrapper
+        .query_selector("prop-counter")
+        .unwrap()
+        .unwrap();
+    let get = |name: &str| {
+        js_sys::Reflect::get(&el, &name.into())
+            .unwrap()
+            .as_f64()
+            .unwrap()
+    };
+    assert_eq!((get("level"), get("writes")), (1.0, 1.0));
+
+    set_level.set(2);
+    assert_eq!((get("level"), get("writes")), (2.0, 2.0));
+    set_level.set(2);
+    assert_eq!((get("level"), get("writes")), (2.0, 2.0));
+}
+
+#[wasm_bindgen_test]
+fn hydrated_inner_html_is_kept_until_it_changes() {
+    // what a debug build renders from `HydrationKey::root("inner")`
+    let test_wrapper = server_rendered(
+        r#"<div id="_inner-1" class="content"><b>Bold</b> text</div>"#,
+    );
+    let bold = find(&test_wrapper, "b");
+    let cx = signal_scope();
+    let (html, set_html) = create_signal(cx, "<b>Bold</b> text".to_string());
+
+    hydrate_to(&test_wrapper, "inner", move |cx| {
+        view! { cx, <div class="content" inner_html=move || html.get()/> }
+    });
+    let div = find(&test_wrapper, "div");
+    assert!(!div.has_attribute("id"));
+    assert_eq!(div.inner_html(), "<b>Bold</b> text");
+    assert!(find(&div, "b").is_same_node(Some(&bold)));
+
+    set_html.set("<i>Italic</i>".to_string());
+    assert_eq!(div.inner_html(), "<i>Italic</i>");
+}
+
+#[wasm_bindgen_test]
+fn reactive_inner_html_is_replaced() {
+    let test_wrapper = test_wrapper();
+    let cx = signal_scope();
+    let (html, set_html) = create_signal(cx, "<b>Bold</b>".to_string());
+
+    mount_to(test_wrapper.clone(), move |cx| {
+        html::div(cx).dangerously_set_inner_html(move || html.get())
+    });
+    let div = find(&test_wrapper, "div");
+    assert_eq!(div.inner_html(), "<b>Bold</b>");
+
+    set_html.set("<i>Italic</i> <u>text</u>".to_string());
+    assert_eq!(div.inner_html(), "<i>Italic</i> <u>text</u>");
+    assert_eq!(div.child_element_count(), 2);
+}
+
+#[wasm_bindgen_test]
+fn boolean_attributes_are_removed_when_false() {
+    let test_wrapper = test_wrapper();
+    let cx = signal_scope();
+    let (busy, set_busy) = create_signal(cx, true);
+    let (open, set_open) = create_signal(cx, "false");
+
+    mount_to(test_wrapper.clone(), move |cx| {
+        view! { cx,
+            <button disabled=move || busy.get()>"Save"</button>
+            <details open=move || open.get()><summary>"More"</summary></details>
+        }
+    });
+    let button: web_sys::HtmlButtonElement =
+        find(&test_wrapper, "button").unchecked_into();
+    let details: web_sys::HtmlDetailsElement =
+        find(&test_wrapper, "details").unchecked_into();
+    assert_eq!(button.get_attribute("disabled").as_deref(), Some(""));
+    assert!(button.disabled());
+    // "false" is false too
+    assert!(!details.has_attribute("open"));
+    assert!(!details.open());
+
+    set_busy.set(false);
+    set_open.set("true");
+    assert!(!button.has_attribute("disabled"));
+    assert!(!button.disabled());
+    assert!(details.open());
+
+    set_busy.set(true);
+    set_open.set("false");
+    assert!(button.disabled());
+    assert!(!details.has_attribute("open"));
+}
+
+#[wasm_bindgen_test]
+fn optional_attributes_are_only_there_when_some() {
+    let test_wrapper = test_wrapper();
+    let cx = signal_scope();
+    let (described_by, set_described_by) = create_signal(cx, None);
+    let (pressed, set_pressed) = create_signal(cx, None);
+
+    mount_to(test_wrapper.clone(), move |cx| {
+        view! { cx,
+            <button
+                aria-describedby=move || described_by.get()
+                aria-pressed=move || pressed.get()
+            >
+                "Bold"
+            </button>
+        }
+    });
+    let button = find(&test_wrapper, "button");
+    assert!(!button.has_attribute("aria-describedby"));
+    assert!(!button.has_attribute("aria-pressed"));
+
+    set_described_by.set(Some("hint"));
+    set_pressed.set(Some(false));
+    assert_eq!(
+        button.get_attribute("aria-describedby").as_deref(),
+        Some("hint")
+    );
+    assert_eq!(
+        button.get_attribute("aria-pressed").as_deref(),
+        Some("false")
+    );
+
+    set_pressed.set(Some(true));
+    assert_eq!(
+        button.get_attribute("aria-pressed").as_deref(),
+        Some("true")
+    );
+
+    set_described_by.set(None);
+    set_pressed.set(None);
+    assert!(!button.has_attribute("aria-describedby"));
+    assert!(!button.has_attribute("aria-pressed"));
+}
+
+// records the classList calls until it's restored
+const SPY_ON_CLASS_LIST: &str = r#"
+    var proto = DOMTokenList.prototype;
+    var add = proto.add, remove = proto.remove;
+    var calls = [];
+    proto.add = function(...names) { calls.push("add " + names.join(" ")); return add.apply(this, names); };
+    proto.remove = function(...names) { calls.push("remove " + names.join(" ")); return remove.apply(this, names); };
+    globalThis.__classListCalls = function() { return calls.splice(0, calls.length); };
+    globalThis.__restoreClassList = function() { proto.add = add; proto.remove = remove; };
+"#;
+
+fn class_list_calls() -> Vec<String> {
+    let calls: js_sys::Function =
+        js_sys::Reflect::get(&js_sys::global(), &"__classListCalls".into())
+            .unwrap()
+            .unchecked_into();
+    let calls: js_sys::Array = calls
+        .call0(&wasm_bindgen::JsValue::NULL)
+        .unwrap()
+        .unchecked_into();
+    calls.iter().filter_map(|call| call.as_string()).collect()
+}
+
+#[wasm_bindgen_test]
+fn a_list_of_classes_only_adds_and_removes_what_changed() {
+    js_sys::Function::new_no_args(SPY_ON_CLASS_LIST)
+        .call0(&wasm_bindgen::JsValue::NULL)
+        .unwrap();
+    let test_wrapper = test_wrapper();
+    let cx = signal_scope();
+    let (list, set_list) = create_signal(cx, vec!["card", "wide"]);
+
+    mount_to(test_wrapper.clone(), move |cx| {
+        view! { cx, <div class=move || list.get()/> }
+    });
+    let div = find(&test_wrapper, "div");
+    assert_eq!(classes(&div), ["card", "wide"]);
+    div.class_list().add_1("tooltip").unwrap();
+    _ = class_list_calls();
+
+    set_list.set(vec!["card", "tall", "card"]);
+    assert_eq!(class_list_calls(), ["remove wide", "add tall"]);
+    assert_eq!(classes(&div), ["card", "tooltip", "tall"]);
+
+    set_list.set(vec!["card", "tall"]);
+    assert!(class_list_calls().is_empty());
+
+    js_sys::Function::new_no_args("globalThis.__restoreClassList()")
+        .call0(&wasm_bindgen::JsValue::NULL)
+        .unwrap();
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn fire(el: &web_sys::Element, event: &str) {
+    let init = web_sys::EventInit::new();
+    init.set_bubbles(true);
+    let event = web_sys::Event::new_with_event_init_dict(event, &init).unwrap();
+    el.dispatch_event(&event).unwrap();
+}
+
+fn input(
+    test_wrapper: &web_sys::Element,
+    selector: &str,
+) -> web_sys::HtmlInputElement {
+    test_wrapper
+        .query_selector(selector)
+        .unwrap()
+        .unwrap()
+        .unchecked_into()
+}
+
+#[wasm_bindgen_test]
+fn bind_value() {
+    let test_wrapper = test_wrapper();
+    let cx = signal_scope();
+    let name = create_rw_signal(cx, String::new());
+    let age = create_rw_signal(cx, 30u8);
+
+    let handle = mount_to(test_wrapper.clone(), move |cx| {
+        view! { cx,
+            <input id="name" bind:value=name/>
+            <input id="age" type="text" bind:value=age/>
+        }
+    });
+    let name_input = input(&test_wrapper, "#name");
+    let age_input = input(&test_wrapper, "#age");
+    assert_eq!(age_input.value(), "30");
+
+    // typing updates the signal
+    name_input.set_value("Alice");
+    fire(&name_input, "input");
+    assert_eq!(name.get(), "Alice");
+
+    // setting the signal updates the input
+    name.set("Bob".to_string());
+    assert_eq!(name_input.value(), "Bob");
+
+    // a number is only updated while what's typed parses, and is written
+    // back when the input changes
+    age_input.set_value("31");
+    fire(&age_input, "input");
+    assert_eq!(age.get(), 31);
+    age_input.set_value("31x");
+    fire(&age_input, "input");
+    assert_eq!(age.get(), 31);
+    fire(&age_input, "change");
+    assert_eq!(age_input.value(), "31");
+
+    // nothing is updated while text is being composed
+    name_input.set_value("Bo");
+    fire(&name_input, "compositionstart");
+    fire(&name_input, "input");
+    assert_eq!(name.get(), "Bob");
+    name_input.set_value("Bo日");
+    fire(&name_input, "compositionend");
+    assert_eq!(name.get(), "Bo日");
+
+    handle.unmount();
+}
+
+#[wasm_bindgen_test]
+fn bind_checked_and_group() {
+    let test_wrapper = test_wrapper();
+    let cx = signal_scope();
+    let subscribed = create_rw_signal(cx, false);
+    let plan = create_rw_signal(cx, "free");
+    let toppings = create_rw_signal(cx, vec!["cheese"]);
+
+    let handle = mount_to(test_wrapper.clone(), move |cx| {
+        view! { cx,
+            <input id="subscribed" type="checkbox" bind:checked=subscribed/>
+            <input id="free" type="radio" name="plan" bind:group=(plan, "free")/>
+            <input id="pro" type="radio" name="plan" bind:group=(plan, "pro")/>
+            <input id="cheese" type="checkbox" bind:group=(toppings, "cheese")/>
+            <input id="olives" type="checkbox" bind:group=(toppings, "olives")/>
+        }
+    });
+
+    let subscribed_input = input(&test_wrapper, "#subscribed");
+    assert!(!subscribed_input.checked());
+    subscribed_input.click();
+    assert!(subscribed.get());
+    subscribed.set(false);
+    assert!(!subscribed_input.checked());
+
+    let free = input(&test_wrapper, "#free");
+    let pro = input(&test_wrapper, "#pro");
+    assert!(free.checked());
+    pro.click();
+    assert_eq!(plan.get(), "pro");
+    assert!(!free.checked());
+    plan.set("free");
+    assert!(free.checked());
+    assert!(!pro.checked());
+
+    let cheese = input(&test_wrapper, "#cheese");
+    let olives = input(&test_wrapper, "#olives");
+    assert!(cheese.checked());
+    assert!(!olives.checked());
+    olives.click();
+    assert_eq!(toppings.get(), vec!["cheese", "olives"]);
+    cheese.click();
+    assert_eq!(toppings.get(), vec!["olives"]);
+    toppings.set(vec!["cheese"]);
+    assert!(cheese.checked());
+    assert!(!olives.checked());
+
+    handle.unmount();
+}
@@ -0,0 +1,47 @@
+//! What the tests of the example share; each of them uses some of it.
+#![allow(dead_code)]
+
+use leptos::*;
+use wasm_bindgen::JsCast;
+
+pub async fn sleep(ms: i32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        leptos::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+// signals that outlive the mounted view
+pub fn signal_scope() -> Scope {
+    let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+    std::mem::forget(disposer);
+    cx
+}
+
+// an element of its own for each test to mount to
+pub fn test_wrapper() -> web_sys::Element {
+    let document = leptos::document();
+    let test_wrapper = document.create_element("section").unwrap();
+    document
+        .body()
+        .unwrap()
+        .append_child(&test_wrapper)
+        .unwrap();
+    test_wrapper
+}
+
+// a test wrapper with what the server rendered in it, to be hydrated
+pub fn server_rendered(html: &str) -> web_sys::Element {
+    let test_wrapper = test_wrapper();
+    test_wrapper.set_inner_html(html);
+    test_wrapper
+}
+
+pub fn find(root: &web_sys::Element, selector: &str) -> web_sys::HtmlElement {
+    root.query_selector(selector)
+        .unwrap()
+        .unwrap_or_else(|| panic!("nothing matches {selector}"))
+        .unchecked_into()
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn custom_element() {
+    let mounted = Rc::new(Cell::new(0));
+    let cleaned_up = Rc::new(Cell::new(0));
+
+    define_custom_element_with(
+        "test-counter",
+        CustomElementOptions {
+            observed_attributes: vec!["count"],
+            styles: vec!["span { color: red; }".into()],
+        },
+        {
+            let mounted = Rc::clone(&mounted);
+            let cleaned_up = Rc::clone(&cleaned_up);
+            move |cx, el| {
+                mounted.set(mounted.get() + 1);
+                on_cleanup(cx, {
+                    let cleaned_up = Rc::clone(&cleaned_up);
+                    move || cleaned_up.set(cleaned_up.get() + 1)
+                });
+
+                let count = el.attribute("count");
+                view! { cx,
+                    <span>"Count: " {move || count.get().unwrap_or_default()}</span>
+                }
+            }
+        },
+    );
+
+    // elements created by the parser are connected right away
+    let test_wrapper = test_wrapper();
+    test_wrapper.set_inner_html(r#"<test-counter count="1"></test-counter>"#);
+
+    let host = test_wrapper
+        .query_selector("test-counter")
+        .unwrap()
+        .unwrap();
+    let shadow_root = host.shadow_root().expect("an open shadow root");
+    let text = |selector| {
+        shadow_root
+            .query_selector(selector)
+            .unwrap()
+            .and_then(|el| el.text_content())
+    };
+    assert_eq!(mounted.get(), 1);
+    assert_eq!(text("style").as_deref(), Some("span { color: red; }"));
+    assert_eq!(text("span").as_deref(), Some("Count: 1"));
+
+    // observed attributes are reflected into signals
+    host.set_attribute("count", "2").unwrap();
+    assert_eq!(text("span").as_deref(), Some("Count: 2"));
+
+    // events from the view reach listeners outside the shadow root
+    let detail = Rc::new(Cell::new(None));
+    let listener =
+        wasm_bindgen::closure::Closure::<dyn Fn(web_sys::Event)>::new({
+            let detail = Rc::clone(&detail);
+            move |ev: web_sys::Event| {
+                let ev = wasm_bindgen::JsCast::unchecked_into::<
+                    web_sys::CustomEvent,
+                >(ev);
+                detail.set(ev.detail().as_f64());
+            }
+        })
+        .into_js_value();
+    test_wrapper
+        .add_event_listener_with_callback(
+            "ping",
+            wasm_bindgen::JsCast::unchecked_ref(&listener),
+        )
+        .unwrap();
+    dispatch_custom_event(
+        wasm_bindgen::JsCast::unchecked_ref(
+            &shadow_root.query_selector("span").unwrap().unwrap(),
+        ),
+        "ping",
+        &3,
+    );
+    assert_eq!(detail.get(), Some(3.0));
+
+    // removing it disposes of its scope
+    test_wrapper.set_inner_html("");
+    assert_eq!(cleaned_up.get(), 1);
+    assert_eq!(shadow_root.inner_html(), "");
+}
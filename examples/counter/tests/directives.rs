@@ -0,0 +1,57 @@
+mod common;
+
+use common::*;
+use leptos::{directives::click_outside, *};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+// logs when it runs and when it's cleaned up
+fn traced(cx: Scope, el: web_sys::Element, log: Log) {
+    log.borrow_mut()
+        .push(format!("run, connected: {}", el.is_connected()));
+    on_cleanup(cx, move || log.borrow_mut().push("cleanup".to_string()));
+}
+
+#[wasm_bindgen_test]
+async fn directives_run_after_mount_and_clean_up_on_dispose() {
+    let test_wrapper = test_wrapper();
+    let log = Log::default();
+
+    let handle = mount_to(test_wrapper.clone(), {
+        let log = Rc::clone(&log);
+        move |cx| {
+            let outside = Rc::clone(&log);
+            view! { cx,
+                <menu
+                    use:traced=Rc::clone(&log)
+                    use:click_outside=move |_: ev::MouseEvent| {
+                        outside.borrow_mut().push("outside".to_string())
+                    }
+                >
+                    <li>"Copy"</li>
+                </menu>
+                <p>"Elsewhere"</p>
+            }
+        }
+    });
+    assert!(log.borrow().is_empty());
+    sleep(50).await;
+    assert_eq!(*log.borrow(), ["run, connected: true"]);
+
+    // the click_outside directive only sees clicks outside its element
+    find(&test_wrapper, "li").click();
+    assert_eq!(log.borrow().len(), 1);
+    find(&test_wrapper, "p").click();
+    assert_eq!(*log.borrow(), ["run, connected: true", "outside"]);
+
+    // and both are cleaned up with their scope
+    handle.unmount();
+    assert_eq!(log.borrow().last().map(String::as_str), Some("cleanup"));
+    let clicked = log.borrow().len();
+    leptos::document().body().unwrap().click();
+    assert_eq!(log.borrow().len(), clicked);
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+// logs when it's created and disposed, and owns a signal, a memo and an
+// effect that should be disposed along with it
+fn tracked(cx: Scope, log: &Log, name: &'static str) {
+    log.borrow_mut().push(format!("+{name}"));
+    let value = create_rw_signal(cx, name);
+    let upper = create_memo(cx, move |_| value.get().to_uppercase());
+    create_effect(cx, move |_| _ = upper.get());
+    let log = Rc::clone(log);
+    on_cleanup(cx, move || log.borrow_mut().push(format!("-{name}")));
+}
+
+#[wasm_bindgen_test]
+fn dynamic_disposes_the_component_it_replaces() {
+    let test_wrapper = test_wrapper();
+
+    let cx = signal_scope();
+    // in the browser there's only one runtime, which the view is mounted in
+    let runtime = cx.runtime;
+    let log = Log::default();
+
+    // components with one, three and no root nodes
+    let one = DynComponent::new({
+        let log = Rc::clone(&log);
+        move |cx| {
+            tracked(cx, &log, "one");
+            view! { cx, <p>"One"</p> }
+        }
+    });
+    let three = DynComponent::new({
+        let log = Rc::clone(&log);
+        move |cx| {
+            tracked(cx, &log, "three");
+            view! { cx, <dt>"Term"</dt><dd>"Definition"</dd>"!" }
+        }
+    });
+    let none = DynComponent::new({
+        let log = Rc::clone(&log);
+        move |cx| {
+            tracked(cx, &log, "none");
+        }
+    });
+    let shown = create_rw_signal(cx, one.clone());
+
+    let handle = mount_to(test_wrapper.clone(), move |cx| {
+        view! { cx, <span>"["</span><Dynamic component=move || shown.get()/><span>"]"</span> }
+    });
+    let text = || test_wrapper.text_content().unwrap_or_default();
+    assert_eq!(text(), "[One]");
+    let stats = runtime.stats();
+
+    shown.set(three.clone());
+    assert_eq!(text(), "[TermDefinition!]");
+    shown.set(none.clone());
+    assert_eq!(text(), "[]");
+    shown.set(one.clone());
+    assert_eq!(text(), "[One]");
+    // nothing is left over from the instances that were replaced
+    assert_eq!(runtime.stats(), stats);
+
+    // swapping several times in a row leaves only the last one
+    for component in [&three, &none, &one, &three] {
+        shown.set(component.clone());
+    }
+    assert_eq!(text(), "[TermDefinition!]");
+    assert_eq!(test_wrapper.query_selector_all("dt").unwrap().length(), 1);
+    // setting the same component again doesn't recreate it
+    let created = log.borrow().len();
+    shown.set(three.clone());
+    assert_eq!(log.borrow().len(), created);
+
+    // each instance is disposed before the next one is created
+    assert_eq!(
+        *log.borrow(),
+        [
+            "+one", "-one", "+three", "-three", "+none", "-none", "+one",
+            "-one", "+three", "-three", "+none", "-none", "+one", "-one",
+            "+three"
+        ]
+    );
+
+    handle.unmount();
+    assert_eq!(log.borrow().last().map(String::as_str), Some("-three"));
+}
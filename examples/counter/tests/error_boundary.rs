@@ -0,0 +1,68 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+async fn error_boundary_recovers_when_a_refetch_succeeds() {
+    let test_wrapper = test_wrapper();
+
+    let attempts = Rc::new(Cell::new(0));
+    let resource = Rc::new(Cell::new(None));
+
+    let handle = mount_to(test_wrapper.clone(), {
+        let attempts = Rc::clone(&attempts);
+        let resource = Rc::clone(&resource);
+        move |cx| {
+            let count = create_resource(
+                cx,
+                || (),
+                move |_| {
+                    let attempt = attempts.get();
+                    attempts.set(attempt + 1);
+                    async move {
+                        if attempt == 0 {
+                            Err::<_, ServerFnError>(ServerFnError::ServerError(
+                                "no count".into(),
+                            ))
+                        } else {
+                            Ok(42)
+                        }
+                    }
+                },
+            );
+            resource.set(Some(count));
+
+            view! { cx,
+                <ErrorBoundary fallback=|cx, errors| view! { cx,
+                    <p id="error">
+                        {move || errors.with(|errors| {
+                            errors.iter().map(|(_, e)| e.to_string()).collect::<String>()
+                        })}
+                    </p>
+                }>
+                    <p id="count">"Count: " {move || count.read(cx)}</p>
+                </ErrorBoundary>
+            }
+        }
+    });
+
+    sleep(10).await;
+    let error = test_wrapper.query_selector("#error").unwrap().unwrap();
+    assert!(error.text_content().unwrap().contains("no count"));
+
+    // once the resource loads successfully, the error is removed and the
+    // children are shown again
+    resource.get().unwrap().refetch();
+    sleep(10).await;
+    assert!(test_wrapper.query_selector("#error").unwrap().is_none());
+    let count = test_wrapper.query_selector("#count").unwrap().unwrap();
+    assert_eq!(count.text_content().as_deref(), Some("Count: 42"));
+    assert_eq!(attempts.get(), 2);
+
+    handle.unmount();
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn submit_event() -> web_sys::Event {
+    let init = web_sys::EventInit::new();
+    init.set_bubbles(true);
+    init.set_cancelable(true);
+    web_sys::Event::new_with_event_init_dict("submit", &init).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn modifiers_run_before_the_handler() {
+    let test_wrapper = test_wrapper();
+    let outer_clicks = Rc::new(Cell::new(0));
+    let seen = Rc::new(Cell::new(None));
+
+    mount_to(test_wrapper.clone().unchecked_into(), {
+        let outer_clicks = Rc::clone(&outer_clicks);
+        let seen = Rc::clone(&seen);
+        move |cx| {
+            view! { cx,
+                <div on:click=move |_| outer_clicks.set(outer_clicks.get() + 1)>
+                    <button on:click:prevent:stop=move |ev: ev::MouseEvent| {
+                        seen.set(Some(ev.default_prevented()));
+                    }>
+                        "Click me"
+                    </button>
+                </div>
+            }
+        }
+    });
+
+    let button = test_wrapper
+        .query_selector("button")
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>();
+    button.click();
+
+    // the default was prevented by the time the handler ran, and the
+    // delegated listener on the <div> never saw it
+    assert_eq!(seen.get(), Some(true));
+    assert_eq!(outer_clicks.get(), 0);
+}
+
+#[wasm_bindgen_test]
+fn default_is_prevented_when_the_handler_panics() {
+    let test_wrapper = test_wrapper();
+
+    mount_to(test_wrapper.clone().unchecked_into(), |cx| {
+        view! { cx,
+            <form on:submit:prevent=|_| panic!("the handler panicked")>
+                <button type="submit">"Send"</button>
+            </form>
+        }
+    });
+
+    let form = test_wrapper.query_selector("form").unwrap().unwrap();
+    // `dispatch_event` returns `false` if the default was prevented; the
+    // panic is only reported, as it happens in a listener
+    assert!(!form.dispatch_event(&submit_event()).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn modifiers_apply_to_builder_listeners() {
+    let test_wrapper = test_wrapper();
+
+    mount_to(test_wrapper.clone().unchecked_into(), |cx| {
+        html::form(cx).on(
+            ev::modified(
+                ev::undelegated(ev::submit),
+                ev::EventModifiers::PREVENT_DEFAULT,
+            ),
+            |_| {},
+        )
+    });
+
+    let form = test_wrapper.query_selector("form").unwrap().unwrap();
+    assert!(!form.dispatch_event(&submit_event()).unwrap());
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// what `ssr::event_replay_script` renders inside the <script> for
+// `ssr::DEFAULT_REPLAYED_EVENTS`
+const RECORD_EVENTS: &str = r#"(function(){var q=[],t=["click","input","submit"];function r(e){q.push({target:e.target,type:e.type});if(e.type=="submit")e.preventDefault()}t.forEach(function(n){document.addEventListener(n,r,true)});__LEPTOS_CAPTURED_EVENTS=function(){t.forEach(function(n){document.removeEventListener(n,r,true)});return q}})()"#;
+
+#[component]
+fn Clicker(cx: Scope, clicks: Rc<Cell<usize>>) -> impl IntoView {
+    view! { cx,
+        <button on:click=move |_| clicks.set(clicks.get() + 1)>"Click me"</button>
+    }
+}
+
+// what a debug build renders for `<div><Clicker/></div>` from
+// `HydrationKey::root(root)`
+fn clicker_html(root: &str) -> String {
+    format!(
+        r#"<div id="_{root}-1"><!--hk=_{root}-1-0o|leptos-clicker-start--><button id="_{root}-1-1">Click me</button><!--hk=_{root}-1-0c|leptos-clicker-end--></div>"#
+    )
+}
+
+fn hydrate_clicker(root: &web_sys::Element, name: &str) -> Rc<Cell<usize>> {
+    let clicks = Rc::new(Cell::new(0));
+    hydrate_to(root, name, {
+        let clicks = Rc::clone(&clicks);
+        move |cx| view! { cx, <div><Clicker clicks/></div> }
+    });
+    clicks
+}
+
+#[wasm_bindgen_test]
+async fn events_before_hydration_are_replayed_once_it_is_done() {
+    js_sys::Function::new_no_args(RECORD_EVENTS)
+        .call0(&JsValue::NULL)
+        .unwrap();
+    let first = server_rendered(&clicker_html("replay_first"));
+    let second = server_rendered(&clicker_html("replay_second"));
+
+    // clicked before there are any listeners
+    find(&first, "button").click();
+    find(&second, "button").click();
+    find(&second, "button").click();
+
+    // the clicks in the root that's hydrated are dispatched again once its
+    // view is mounted
+    let first_clicks = hydrate_clicker(&first, "replay_first");
+    assert_eq!(first_clicks.get(), 0);
+    sleep(0).await;
+    assert_eq!(first_clicks.get(), 1);
+
+    // and the others wait for the root they happened in
+    let second_clicks = hydrate_clicker(&second, "replay_second");
+    sleep(0).await;
+    assert_eq!(second_clicks.get(), 2);
+
+    // each of them only once
+    sleep(10).await;
+    assert_eq!((first_clicks.get(), second_clicks.get()), (1, 2));
+    find(&first, "button").click();
+    assert_eq!(first_clicks.get(), 2);
+}
@@ -0,0 +1,293 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+type Log = Rc<RefCell<Vec<&'static str>>>;
+
+fn button_in(test_wrapper: &web_sys::Element) -> web_sys::HtmlElement {
+    test_wrapper
+        .query_selector("button")
+        .unwrap()
+        .unwrap()
+        .unchecked_into()
+}
+
+#[wasm_bindgen_test]
+fn nested_handlers_run_from_the_inside_out() {
+    let test_wrapper = test_wrapper();
+    let log = Log::default();
+
+    mount_to(test_wrapper.clone(), {
+        let log = Rc::clone(&log);
+        move |cx| {
+            let (div, p, button) = (log.clone(), log.clone(), log);
+            view! { cx,
+                <div on:click=move |_| div.borrow_mut().push("div")>
+                    <p on:click=move |_| p.borrow_mut().push("p")>
+                        <button on:click=move |_| button.borrow_mut().push("button")>
+                            "Click me"
+                        </button>
+                    </p>
+                </div>
+            }
+        }
+    });
+
+    button_in(&test_wrapper).click();
+    assert_eq!(*log.borrow(), ["button", "p", "div"]);
+
+    // a click on the <p> itself doesn't reach the <button>
+    log.borrow_mut().clear();
+    test_wrapper
+        .query_selector("p")
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>()
+        .click();
+    assert_eq!(*log.borrow(), ["p", "div"]);
+}
+
+#[wasm_bindgen_test]
+fn stop_propagation_keeps_outer_handlers_from_running() {
+    let test_wrapper = test_wrapper();
+    let log = Log::default();
+
+    mount_to(test_wrapper.clone(), {
+        let log = Rc::clone(&log);
+        move |cx| {
+            let (div, p, button) = (log.clone(), log.clone(), log);
+            view! { cx,
+                <div on:click=move |_| div.borrow_mut().push("div")>
+                    <p on:click=move |ev: ev::MouseEvent| {
+                        p.borrow_mut().push("p");
+                        ev.stop_propagation();
+                    }>
+                        <button on:click=move |_| button.borrow_mut().push("button")>
+                            "Click me"
+                        </button>
+                    </p>
+                </div>
+            }
+        }
+    });
+
+    button_in(&test_wrapper).click();
+    assert_eq!(*log.borrow(), ["button", "p"]);
+}
+
+#[wasm_bindgen_test]
+fn handlers_are_removed_when_their_scope_is_disposed() {
+    let test_wrapper = test_wrapper();
+    let clicks = Rc::new(Cell::new(0));
+    let direct_clicks = Rc::new(Cell::new(0));
+    let focuses = Rc::new(Cell::new(0));
+
+    let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+    let el = html::button(cx)
+        .on(ev::click, {
+            let clicks = Rc::clone(&clicks);
+            move |_| clicks.set(clicks.get() + 1)
+        })
+        .on(ev::undelegated(ev::click), {
+            let direct_clicks = Rc::clone(&direct_clicks);
+            move |_| direct_clicks.set(direct_clicks.get() + 1)
+        })
+        // doesn't bubble, so it's attached to the element
+        .on(ev::focus, {
+            let focuses = Rc::clone(&focuses);
+            move |_| focuses.set(focuses.get() + 1)
+        });
+    let el: web_sys::HtmlElement = (*el).clone().into();
+    test_wrapper.append_child(&el).unwrap();
+
+    let focus = || {
+        el.dispatch_event(&web_sys::FocusEvent::new("focus").unwrap())
+            .unwrap();
+    };
+    el.click();
+    focus();
+    assert_eq!(
+        (clicks.get(), direct_clicks.get(), focuses.get()),
+        (1, 1, 1)
+    );
+
+    // the element outlives the scope, but its handlers don't
+    disposer.dispose();
+    el.click();
+    focus();
+    assert_eq!(
+        (clicks.get(), direct_clicks.get(), focuses.get()),
+        (1, 1, 1)
+    );
+
+    // and the delegated listener still serves elements that are around
+    let log = Log::default();
+    mount_to(test_wrapper.clone(), {
+        let log = Rc::clone(&log);
+        move |cx| {
+            view! { cx,
+                <button on:click=move |_| log.borrow_mut().push("button")>
+                    "Click me"
+                </button>
+            }
+        }
+    });
+    test_wrapper
+        .query_selector("button:last-child")
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>()
+        .click();
+    assert_eq!(*log.borrow(), ["button"]);
+}
+
+fn cancelable_click() -> web_sys::MouseEvent {
+    let init = web_sys::MouseEventInit::new();
+    init.set_bubbles(true);
+    init.set_cancelable(true);
+    web_sys::MouseEvent::new_with_mouse_event_init_dict("click", &init).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn passive_handlers_cannot_prevent_the_default() {
+    let test_wrapper = test_wrapper();
+
+    mount_to(test_wrapper.clone(), |cx| {
+        view! { cx,
+            <button on:click:passive=|ev: ev::MouseEvent| ev.prevent_default()>
+                "Passive"
+            </button>
+            <a on:click=|ev: ev::MouseEvent| ev.prevent_default()>"Active"</a>
+        }
+    });
+
+    let click = cancelable_click();
+    assert!(button_in(&test_wrapper).dispatch_event(&click).unwrap());
+    assert!(!click.default_prevented());
+
+    // unlike a handler that isn't passive
+    let click = cancelable_click();
+    let a = test_wrapper.query_selector("a").unwrap().unwrap();
+    assert!(!a.dispatch_event(&click).unwrap());
+    assert!(click.default_prevented());
+}
+
+#[wasm_bindgen_test]
+fn once_handlers_run_a_single_time() {
+    let test_wrapper = test_wrapper();
+    let log = Log::default();
+
+    mount_to(test_wrapper.clone(), {
+        let log = Rc::clone(&log);
+        move |cx| {
+            let (once, every) = (log.clone(), log);
+            view! { cx,
+                <button
+                    on:click:once=move |_| once.borrow_mut().push("once")
+                    on:click:undelegated=move |_| every.borrow_mut().push("every")
+                >
+                    "Click me"
+                </button>
+            }
+        }
+    });
+
+    let button = button_in(&test_wrapper);
+    button.click();
+    button.click();
+    button.click();
+    assert_eq!(*log.borrow(), ["once", "every", "every", "every"]);
+}
+
+#[component]
+fn Picker(cx: Scope) -> impl IntoView {
+    view! { cx,
+        <button on:click=|ev: ev::MouseEvent| {
+            let target = ev.target().unwrap();
+            dispatch_custom_event(&target, "picked", &(3, "three".to_string()));
+        }>
+            "Pick"
+        </button>
+    }
+}
+
+#[wasm_bindgen_test]
+fn a_parent_receives_the_typed_detail_of_a_custom_event() {
+    let test_wrapper = test_wrapper();
+    let picked = Rc::new(RefCell::new(Vec::new()));
+
+    mount_to(test_wrapper.clone(), {
+        let picked = Rc::clone(&picked);
+        move |cx| {
+            view! { cx,
+                <div on:picked={move |ev: ev::CustomEvent<(u32, String)>| {
+                    picked.borrow_mut().push(ev.detail());
+                }}>
+                    <Picker/>
+                </div>
+            }
+        }
+    });
+
+    button_in(&test_wrapper).click();
+    assert_eq!(*picked.borrow(), [Some((3, "three".to_string()))]);
+}
+
+#[component]
+fn Shortcuts(cx: Scope, log: Log) -> impl IntoView {
+    let (window, document) = (log.clone(), log);
+    window_event_listener(cx, ev::keydown, move |_| {
+        window.borrow_mut().push("window")
+    });
+    document_event_listener(cx, ev::keydown, move |_| {
+        document.borrow_mut().push("document")
+    });
+    view! { cx, <p>"Press a key"</p> }
+}
+
+fn keydown(target: &web_sys::EventTarget) {
+    let init = web_sys::EventInit::new();
+    init.set_bubbles(true);
+    let keydown = web_sys::Event::new_with_event_init_dict("keydown", &init);
+    target.dispatch_event(&keydown.unwrap()).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn window_and_document_listeners_are_removed_with_their_scope() {
+    let log = Log::default();
+    let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+    // the listeners don't need the view to be in the page
+    _ = view! { cx, <Shortcuts log=Rc::clone(&log)/> };
+
+    keydown(&leptos::document());
+    assert_eq!(*log.borrow(), ["document", "window"]);
+
+    disposer.dispose();
+    keydown(&leptos::document());
+    keydown(&leptos::window());
+    assert_eq!(*log.borrow(), ["document", "window"]);
+}
+
+#[wasm_bindgen_test]
+fn a_removed_listener_no_longer_runs() {
+    let log = Log::default();
+    let cx = signal_scope();
+    let handle = window_event_listener(cx, ev::keydown, {
+        let log = Rc::clone(&log);
+        move |_| log.borrow_mut().push("keydown")
+    });
+
+    keydown(&leptos::window());
+    handle.remove();
+    keydown(&leptos::window());
+    assert_eq!(*log.borrow(), ["keydown"]);
+}
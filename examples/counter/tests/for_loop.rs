@@ -0,0 +1,358 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn rows(test_wrapper: &web_sys::Element) -> Vec<web_sys::Node> {
+    let rows = test_wrapper.query_selector_all("li").unwrap();
+    (0..rows.length())
+        .map(|idx| rows.get(idx).unwrap())
+        .collect()
+}
+
+fn texts(test_wrapper: &web_sys::Element) -> Vec<String> {
+    rows(test_wrapper)
+        .iter()
+        .map(|row| row.text_content().unwrap())
+        .collect()
+}
+
+fn assert_same_nodes(a: &[web_sys::Node], b: &[web_sys::Node]) {
+    assert_eq!(a.len(), b.len());
+    for (a, b) in a.iter().zip(b) {
+        assert!(a.is_same_node(Some(b)));
+    }
+}
+
+#[wasm_bindgen_test]
+fn keyed_rows_are_moved_not_recreated() {
+    let test_wrapper = test_wrapper();
+
+    let cx = signal_scope();
+    let items = create_rw_signal(cx, vec![(1, "a"), (2, "b"), (3, "c")]);
+    let created = Rc::new(Cell::new(0));
+    let disposed = Rc::new(Cell::new(0));
+
+    let handle = mount_to(test_wrapper.clone(), {
+        let created = Rc::clone(&created);
+        let disposed = Rc::clone(&disposed);
+        move |cx| {
+            view! { cx,
+                <ul>
+                    <ForEach
+                        each=move || items.get()
+                        key=|item| item.0
+                        children=move |cx, item| {
+                            created.set(created.get() + 1);
+                            on_cleanup(cx, {
+                                let disposed = Rc::clone(&disposed);
+                                move || disposed.set(disposed.get() + 1)
+                            });
+                            view! { cx, <li>{move || item.with(|item| item.1)}</li> }
+                        }
+                    />
+                </ul>
+            }
+        }
+    });
+    assert_eq!(texts(&test_wrapper), ["a", "b", "c"]);
+    assert_eq!(created.get(), 3);
+
+    // append
+    let before = rows(&test_wrapper);
+    items.update(|items| items.push((4, "d")));
+    let after = rows(&test_wrapper);
+    assert_eq!(texts(&test_wrapper), ["a", "b", "c", "d"]);
+    assert_same_nodes(&before, &after[..3]);
+    assert_eq!(created.get(), 4);
+
+    // prepend
+    let before = after;
+    items.update(|items| items.insert(0, (0, "z")));
+    let after = rows(&test_wrapper);
+    assert_eq!(texts(&test_wrapper), ["z", "a", "b", "c", "d"]);
+    assert_same_nodes(&before, &after[1..]);
+    assert_eq!(created.get(), 5);
+
+    // remove from the middle
+    let before = after;
+    items.update(|items| {
+        items.remove(2);
+    });
+    let after = rows(&test_wrapper);
+    assert_eq!(texts(&test_wrapper), ["z", "a", "c", "d"]);
+    assert_same_nodes(&[&before[..2], &before[3..]].concat(), &after);
+    assert_eq!(disposed.get(), 1);
+
+    // reverse
+    let before = after;
+    items.update(|items| items.reverse());
+    let after = rows(&test_wrapper);
+    assert_eq!(texts(&test_wrapper), ["d", "c", "a", "z"]);
+    assert_same_nodes(
+        &before.iter().rev().cloned().collect::<Vec<_>>(),
+        &after,
+    );
+
+    // an item that changes but keeps its key updates its row in place
+    let before = after;
+    items.update(|items| items[1].1 = "C");
+    let after = rows(&test_wrapper);
+    assert_eq!(texts(&test_wrapper), ["d", "C", "a", "z"]);
+    assert_same_nodes(&before, &after);
+
+    // no rows were created or disposed by the moves and updates
+    assert_eq!(created.get(), 5);
+    assert_eq!(disposed.get(), 1);
+
+    handle.unmount();
+    assert_eq!(disposed.get(), 5);
+}
+
+#[wasm_bindgen_test]
+fn index_rows_follow_the_length() {
+    let test_wrapper = test_wrapper();
+
+    let cx = signal_scope();
+    let lines = create_rw_signal(cx, vec!["a", "b", "c", "d"]);
+    // every time a row's effect runs, and every row that is disposed
+    let ran = Rc::new(RefCell::new(Vec::new()));
+    let disposed = Rc::new(RefCell::new(Vec::new()));
+
+    let handle = mount_to(test_wrapper.clone(), {
+        let ran = Rc::clone(&ran);
+        let disposed = Rc::clone(&disposed);
+        move |cx| {
+            view! { cx,
+                <ol>
+                    <EachIndex
+                        each=lines
+                        children=move |cx, line| {
+                            let ran = Rc::clone(&ran);
+                            create_effect(cx, move |_| {
+                                ran.borrow_mut().push(line.get())
+                            });
+                            on_cleanup(cx, {
+                                let disposed = Rc::clone(&disposed);
+                                move || {
+                                    disposed
+                                        .borrow_mut()
+                                        .push(line.get_untracked())
+                                }
+                            });
+                            view! { cx, <li>{move || line.get()}</li> }
+                        }
+                    />
+                </ol>
+            }
+        }
+    });
+    assert_eq!(texts(&test_wrapper), ["a", "b", "c", "d"]);
+    assert_eq!(*ran.borrow(), ["a", "b", "c", "d"]);
+
+    // changing one item only re-runs that row
+    let before = rows(&test_wrapper);
+    ran.borrow_mut().clear();
+    lines.update(|lines| lines[1] = "B");
+    assert_eq!(*ran.borrow(), ["B"]);
+    assert_eq!(texts(&test_wrapper), ["a", "B", "c", "d"]);
+    assert_same_nodes(&before, &rows(&test_wrapper));
+
+    // shrinking disposes exactly the rows at the end
+    ran.borrow_mut().clear();
+    lines.update(|lines| lines.truncate(2));
+    assert_eq!(*disposed.borrow(), ["c", "d"]);
+    assert!(ran.borrow().is_empty());
+    assert_eq!(texts(&test_wrapper), ["a", "B"]);
+    assert_same_nodes(&before[..2], &rows(&test_wrapper));
+
+    // growing adds rows at the end
+    lines.update(|lines| lines.push("e"));
+    assert_eq!(*ran.borrow(), ["e"]);
+    assert_eq!(texts(&test_wrapper), ["a", "B", "e"]);
+    assert_same_nodes(&before[..2], &rows(&test_wrapper)[..2]);
+
+    handle.unmount();
+    assert_eq!(disposed.borrow().len(), 5);
+}
+
+#[wasm_bindgen_test]
+fn moved_rows_are_animated_from_where_they_were() {
+    let test_wrapper = test_wrapper();
+
+    let cx = signal_scope();
+    let items = create_rw_signal(cx, vec!["a", "b", "c"]);
+    // the text of each row that's animated, and where it moved from and to
+    let moves = Rc::new(RefCell::new(Vec::new()));
+    let entered = Rc::new(RefCell::new(Vec::new()));
+
+    let animation = EachAnimation::default()
+        .animate({
+            let moves = Rc::clone(&moves);
+            move |el, from, to| {
+                moves.borrow_mut().push((
+                    el.text_content().unwrap(),
+                    *from,
+                    *to,
+                ))
+            }
+        })
+        .on_enter({
+            let entered = Rc::clone(&entered);
+            move |el| entered.borrow_mut().push(el.text_content().unwrap())
+        });
+
+    let handle = mount_to(test_wrapper.clone(), move |cx| {
+        view! { cx,
+            <ul>
+                <For
+                    each=move || items.get()
+                    key=|item| *item
+                    view=|cx, item| view! { cx, <li>{item}</li> }
+                    animation
+                />
+            </ul>
+        }
+    });
+    // the rows rendered with the list don't enter
+    assert!(entered.borrow().is_empty());
+    let tops = rows(&test_wrapper)
+        .iter()
+        .map(|row| {
+            row.unchecked_ref::<web_sys::Element>()
+                .get_bounding_client_rect()
+                .top()
+        })
+        .collect::<Vec<_>>();
+
+    items.update(|items| items.reverse());
+    assert_eq!(texts(&test_wrapper), ["c", "b", "a"]);
+
+    // the first and last rows swapped places, and the middle one didn't move
+    let moves = moves.borrow();
+    let mut moved = moves
+        .iter()
+        .map(|(text, from, to)| (text.as_str(), from.top, to.top))
+        .collect::<Vec<_>>();
+    moved.sort_by(|a, b| a.0.cmp(b.0));
+    assert_eq!(moved, [("a", tops[0], tops[2]), ("c", tops[2], tops[0])]);
+    assert!(moves.iter().all(|(_, from, to)| from.height == to.height));
+
+    items.update(|items| items.push("d"));
+    assert_eq!(*entered.borrow(), ["d"]);
+
+    handle.unmount();
+}
+
+// an exit animation that lasts until the test ends it
+fn delayed_exit() -> (EachAnimation, Rc<RefCell<Vec<js_sys::Function>>>) {
+    let finish = Rc::new(RefCell::new(Vec::new()));
+    let animation = EachAnimation::default().on_exit({
+        let finish = Rc::clone(&finish);
+        move |_| {
+            Some(js_sys::Promise::new(&mut |resolve, _| {
+                finish.borrow_mut().push(resolve)
+            }))
+        }
+    });
+    (animation, finish)
+}
+
+async fn next_tick() {
+    _ = wasm_bindgen_futures::JsFuture::from(js_sys::Promise::resolve(
+        &wasm_bindgen::JsValue::UNDEFINED,
+    ))
+    .await;
+}
+
+#[wasm_bindgen_test]
+async fn exiting_rows_are_removed_once_their_animation_ends() {
+    let test_wrapper = test_wrapper();
+
+    let cx = signal_scope();
+    let items = create_rw_signal(cx, vec!["a", "b", "c"]);
+    let disposed = Rc::new(Cell::new(0));
+    let (animation, finish) = delayed_exit();
+
+    let handle = mount_to(test_wrapper.clone(), {
+        let disposed = Rc::clone(&disposed);
+        move |cx| {
+            view! { cx,
+                <ul>
+                    <For
+                        each=move || items.get()
+                        key=|item| *item
+                        view=move |cx, item| {
+                            let disposed = Rc::clone(&disposed);
+                            on_cleanup(cx, move || disposed.set(disposed.get() + 1));
+                            view! { cx, <li>{item}</li> }
+                        }
+                        animation
+                    />
+                </ul>
+            }
+        }
+    });
+
+    items.update(|items| {
+        items.remove(1);
+    });
+    // the row's scope is gone, but it stays until its exit finishes
+    assert_eq!(disposed.get(), 1);
+    assert_eq!(texts(&test_wrapper), ["a", "b", "c"]);
+
+    // rows that are added in the meantime go before the next row that's
+    // still in the list
+    items.update(|items| items.insert(1, "d"));
+    assert_eq!(texts(&test_wrapper), ["a", "b", "d", "c"]);
+
+    for resolve in finish.take() {
+        resolve.call0(&wasm_bindgen::JsValue::NULL).unwrap();
+    }
+    next_tick().await;
+    next_tick().await;
+    assert_eq!(texts(&test_wrapper), ["a", "d", "c"]);
+
+    handle.unmount();
+}
+
+#[wasm_bindgen_test]
+fn disposing_the_list_removes_exiting_rows() {
+    let test_wrapper = test_wrapper();
+
+    let cx = signal_scope();
+    let items = create_rw_signal(cx, vec!["a", "b"]);
+    let (animation, finish) = delayed_exit();
+
+    let handle = mount_to(test_wrapper.clone(), move |cx| {
+        view! { cx,
+            <ul>
+                <For
+                    each=move || items.get()
+                    key=|item| *item
+                    view=|cx, item| view! { cx, <li>{item}</li> }
+                    animation
+                />
+            </ul>
+        }
+    });
+    let exiting = rows(&test_wrapper).pop().unwrap();
+
+    // clearing the list exits every row
+    items.set(vec![]);
+    assert_eq!(finish.borrow().len(), 2);
+    assert_eq!(texts(&test_wrapper), ["a", "b"]);
+
+    // the exit never finishes, but disposing the list removes the row
+    // from its parent, not just the parent from the page
+    handle.unmount();
+    assert!(exiting.parent_node().is_none());
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[component]
+fn Term(cx: Scope, count: ReadSignal<i32>) -> impl IntoView {
+    view! { cx,
+        <dt>"Count"</dt>
+        <dd>{move || count.get()}</dd>
+        <dd>{move || count.get() * 2}</dd>
+    }
+}
+
+fn children_of(el: &web_sys::Element) -> Vec<(String, String)> {
+    let children = el.children();
+    (0..children.length())
+        .filter_map(|idx| children.item(idx))
+        .map(|child| (child.tag_name(), child.text_content().unwrap()))
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn a_component_with_three_roots_is_mounted_as_siblings() {
+    let test_wrapper = test_wrapper();
+    let cx = signal_scope();
+    let (count, set_count) = create_signal(cx, 1);
+    let (shown, set_shown) = create_signal(cx, true);
+
+    mount_to(test_wrapper.clone(), move |cx| {
+        view! { cx,
+            <dl>
+                <dt>"Title"</dt>
+                {move || shown.get().then(|| view! { cx, <Term count/> })}
+            </dl>
+        }
+    });
+    let dl = test_wrapper.query_selector("dl").unwrap().unwrap();
+    assert_eq!(
+        children_of(&dl),
+        [
+            ("DT".to_string(), "Title".to_string()),
+            ("DT".to_string(), "Count".to_string()),
+            ("DD".to_string(), "1".to_string()),
+            ("DD".to_string(), "2".to_string()),
+        ]
+    );
+
+    // its dynamic children update in place
+    let first_dd = find(&dl, "dd");
+    set_count.set(5);
+    assert!(find(&dl, "dd").is_same_node(Some(&first_dd)));
+    assert_eq!(
+        children_of(&dl)[1..],
+        [
+            ("DT".to_string(), "Count".to_string()),
+            ("DD".to_string(), "5".to_string()),
+            ("DD".to_string(), "10".to_string()),
+        ]
+    );
+
+    // and it's removed and inserted again as a unit
+    set_shown.set(false);
+    assert_eq!(children_of(&dl), [("DT".to_string(), "Title".to_string())]);
+    set_shown.set(true);
+    assert_eq!(children_of(&dl).len(), 4);
+    assert_eq!(
+        dl.last_element_child().unwrap().text_content().as_deref(),
+        Some("10")
+    );
+}
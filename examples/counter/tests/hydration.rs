@@ -0,0 +1,339 @@
+mod common;
+
+use common::*;
+use leptos::{leptos_dom::HydrationCtx, *};
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// The server-rendered HTML in these tests is what a debug build of the
+// server renders for the same views, with keys continuing from
+// `HydrationKey::root` with the name each test hydrates with.
+
+#[wasm_bindgen_test]
+fn mismatched_elements_are_counted_and_the_rest_is_hydrated() {
+    let test_wrapper = server_rendered(
+        r#"<div id="_mismatch-1"><p id="_mismatch-2">Server</p><button id="_mismatch-3">Click me</button></div>"#,
+    );
+    let button = find(&test_wrapper, "button");
+    let mismatches = HydrationCtx::mismatches();
+    let clicks = Rc::new(Cell::new(0));
+
+    hydrate_to(&test_wrapper, "mismatch", {
+        let clicks = Rc::clone(&clicks);
+        move |cx| {
+            view! { cx,
+                <div>
+                    // the server rendered a <p> here
+                    <span>"Client"</span>
+                    <button on:click=move |_| clicks.set(clicks.get() + 1)>
+                        "Click me"
+                    </button>
+                </div>
+            }
+        }
+    });
+
+    assert_eq!(HydrationCtx::mismatches() - mismatches, 1);
+    // the button after it was still hydrated, not created again
+    button.click();
+    assert_eq!(clicks.get(), 1);
+    assert_eq!(
+        test_wrapper.query_selector_all("button").unwrap().length(),
+        1
+    );
+}
+
+#[component]
+fn Banner(cx: Scope) -> impl IntoView {
+    view! { cx, <p class="banner">"Welcome"</p> }
+}
+
+#[component]
+fn Clicker(cx: Scope, clicks: Rc<Cell<usize>>) -> impl IntoView {
+    view! { cx,
+        <button on:click=move |_| clicks.set(clicks.get() + 1)>"Click me"</button>
+    }
+}
+
+#[wasm_bindgen_test]
+fn a_component_the_server_did_not_render_is_rendered_on_the_client() {
+    // `<main><Banner/><Clicker/></main>`, without the <Banner/>
+    let test_wrapper = server_rendered(
+        r#"<main id="_missing-1"><!--hk=_missing-1R2-0o|leptos-clicker-start--><button id="_missing-1R2-1">Click me</button><!--hk=_missing-1R2-0c|leptos-clicker-end--></main>"#,
+    );
+    let button = find(&test_wrapper, "button");
+    let mismatches = HydrationCtx::mismatches();
+    let clicks = Rc::new(Cell::new(0));
+
+    hydrate_to(&test_wrapper, "missing", {
+        let clicks = Rc::clone(&clicks);
+        move |cx| view! { cx, <main><Banner/><Clicker clicks/></main> }
+    });
+
+    // one warning for the whole <Banner/>, whose <p> didn't go looking for
+    // the <Clicker/>'s nodes
+    assert_eq!(HydrationCtx::mismatches() - mismatches, 1);
+    let banner = find(&test_wrapper, "main > p.banner");
+    assert_eq!(banner.text_content().as_deref(), Some("Welcome"));
+
+    // and the <Clicker/> was still hydrated
+    button.click();
+    assert_eq!(clicks.get(), 1);
+    assert_eq!(
+        test_wrapper.query_selector_all("button").unwrap().length(),
+        1
+    );
+}
+
+#[component]
+fn Word(cx: Scope) -> impl IntoView {
+    _ = cx;
+    "word"
+}
+
+#[wasm_bindgen_test]
+fn dynamic_text_is_hydrated_next_to_other_text_and_comments() {
+    let test_wrapper = server_rendered(
+        r#"<div id="_text-1"><p id="_text-2">Hello, <!--hk=_text-3o|leptos-dyn-child-start-->World<!--hk=_text-3c|leptos-dyn-child-end--><!--hk=_text-4o|leptos-dyn-child-start--><!--hk=_text-4c|leptos-dyn-child-end-->!</p><p id="_text-5"><!--hk=_text-5-0o|leptos-word-start-->word<!--hk=_text-5-0c|leptos-word-end--><!--hk=_text-5-1o|leptos-dyn-child-start-->World<!--hk=_text-5-1c|leptos-dyn-child-end--></p></div>"#,
+    );
+    let (greeting, word) = {
+        let p = test_wrapper.query_selector_all("p").unwrap();
+        (p.get(0).unwrap(), p.get(1).unwrap())
+    };
+    let (name, set_name) = create_signal(signal_scope(), "World".to_string());
+    let (empty, set_empty) = create_signal(signal_scope(), String::new());
+
+    hydrate_to(&test_wrapper, "text", move |cx| {
+        view! { cx,
+            <div>
+                <p>"Hello, " {move || name.get()} {move || empty.get()} "!"</p>
+                <p><Word/>{move || name.get()}</p>
+            </div>
+        }
+    });
+
+    // the empty text has no node of its own, and the nodes around it are
+    // all still there
+    assert_eq!(greeting.text_content().as_deref(), Some("Hello, World!"));
+    assert_eq!(greeting.child_nodes().length(), 7);
+    assert_eq!(word.text_content().as_deref(), Some("wordWorld"));
+
+    set_name.set("Leptos".to_string());
+    set_empty.set("?".to_string());
+    assert_eq!(greeting.text_content().as_deref(), Some("Hello, Leptos?!"));
+    assert_eq!(word.text_content().as_deref(), Some("wordLeptos"));
+
+    set_empty.set(String::new());
+    assert_eq!(greeting.text_content().as_deref(), Some("Hello, Leptos!"));
+}
+
+#[wasm_bindgen_test]
+fn sibling_roots_and_a_shadow_root_are_hydrated_separately() {
+    fn clicker_html(root: &str) -> String {
+        format!(
+            r#"<div id="_{root}-1"><!--hk=_{root}-1-0o|leptos-clicker-start--><button id="_{root}-1-1">Click me</button><!--hk=_{root}-1-0c|leptos-clicker-end--></div>"#
+        )
+    }
+
+    fn hydrate_clicker(root: &web_sys::Node, name: &str) -> Rc<Cell<usize>> {
+        let clicks = Rc::new(Cell::new(0));
+        hydrate_to(root, name, {
+            let clicks = Rc::clone(&clicks);
+            move |cx| view! { cx, <div><Clicker clicks/></div> }
+        });
+        clicks
+    }
+
+    let mismatches = HydrationCtx::mismatches();
+
+    // two roots side by side, whose keys differ only by their names
+    let left = server_rendered(&clicker_html("left"));
+    let right = server_rendered(&clicker_html("right"));
+    let left_clicks = hydrate_clicker(&left, "left");
+    let right_clicks = hydrate_clicker(&right, "right");
+
+    // and one in a shadow root, which the scan of the <body> doesn't reach
+    let host = document().create_element("div").unwrap();
+    test_wrapper().append_child(&host).unwrap();
+    let shadow = host
+        .attach_shadow(&web_sys::ShadowRootInit::new(
+            web_sys::ShadowRootMode::Open,
+        ))
+        .unwrap();
+    shadow.set_inner_html(&clicker_html("shadow"));
+    let shadow_button = shadow
+        .query_selector("button")
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>();
+    let shadow_clicks = hydrate_clicker(&shadow, "shadow");
+
+    assert_eq!(HydrationCtx::mismatches(), mismatches);
+
+    find(&left, "button").click();
+    find(&right, "button").click();
+    find(&right, "button").click();
+    shadow_button.click();
+    assert_eq!(
+        (left_clicks.get(), right_clicks.get(), shadow_clicks.get()),
+        (1, 2, 1)
+    );
+    for root in [&left, &right] {
+        assert_eq!(root.query_selector_all("button").unwrap().length(), 1);
+    }
+    assert_eq!(shadow.query_selector_all("button").unwrap().length(), 1);
+}
+
+#[wasm_bindgen_test]
+fn server_rendered_nodes_no_view_claimed_are_reported_and_removed() {
+    // `<main><Clicker/><Banner/></main>`, of which the client only renders
+    // the <Clicker/>
+    let test_wrapper = server_rendered(
+        r#"<main id="_unclaimed-1"><!--hk=_unclaimed-1-0o|leptos-clicker-start--><button id="_unclaimed-1-1">Click me</button><!--hk=_unclaimed-1-0c|leptos-clicker-end--><!--hk=_unclaimed-1R2-0o|leptos-banner-start--><p id="_unclaimed-1R2-1" class="banner">Welcome</p><!--hk=_unclaimed-1R2-0c|leptos-banner-end--></main>"#,
+    );
+    let main = find(&test_wrapper, "main");
+    let clicks = Rc::new(Cell::new(0));
+
+    HydrationCtx::remove_unclaimed(true);
+    hydrate_to(&test_wrapper, "unclaimed", {
+        let clicks = Rc::clone(&clicks);
+        move |cx| view! { cx, <main><Clicker clicks/></main> }
+    });
+    HydrationCtx::remove_unclaimed(false);
+
+    // the <Banner/>, and the <p> inside it
+    assert_eq!(HydrationCtx::unclaimed_count(), 2);
+    assert!(main.query_selector("p").unwrap().is_none());
+    assert_eq!(main.text_content().as_deref(), Some("Click me"));
+
+    find(&test_wrapper, "button").click();
+    assert_eq!(clicks.get(), 1);
+
+    // hydrating what the server rendered in full leaves nothing over
+    let test_wrapper = server_rendered(
+        r#"<main id="_claimed-1"><!--hk=_claimed-1-0o|leptos-clicker-start--><button id="_claimed-1-1">Click me</button><!--hk=_claimed-1-0c|leptos-clicker-end--></main>"#,
+    );
+    hydrate_to(&test_wrapper, "claimed", move |cx| {
+        let clicks = Rc::new(Cell::new(0));
+        view! { cx, <main><Clicker clicks/></main> }
+    });
+    assert_eq!(HydrationCtx::unclaimed_count(), 0);
+}
+
+#[component]
+fn Streamed(cx: Scope) -> impl IntoView {
+    let message = create_resource(
+        cx,
+        || (),
+        |_| async {
+            sleep(50).await;
+            "Streamed".to_string()
+        },
+    );
+    view! { cx,
+        <Suspense fallback=move || view! { cx, <p class="loading">"Loading..."</p> }>
+            <p class="message">{move || message.read(cx)}</p>
+        </Suspense>
+    }
+}
+
+// the chunk streamed in for the <Suspense/> in `<main><Streamed/></main>`,
+// rendered with the app ID `chunk`, and the script that swaps it in
+const STREAMED_CHUNK: &str = r#"<template id="chunk-1R2-0f"><!--hk=_chunk-1R2-1o|leptos-dyn-child-start--><!--hk=_chunk-1R2-2o|leptos--start--><p class="message" id="_chunk-1R2-3"><!--hk=_chunk-1R2-4o|leptos-dyn-child-start-->Streamed<!--hk=_chunk-1R2-4c|leptos-dyn-child-end--></p><!--hk=_chunk-1R2-2c|leptos--end--><!--hk=_chunk-1R2-1c|leptos-dyn-child-end--></template>"#;
+const SWAP_CHUNK: &str = r#"
+    var id = "chunk-1R2-0";
+    var open;
+    var close;
+    var walker = document.createTreeWalker(document.body, NodeFilter.SHOW_COMMENT);
+    while(walker.nextNode()) {
+         if(walker.currentNode.textContent == `suspense-open-${id}`) {
+           open = walker.currentNode;
+         } else if(walker.currentNode.textContent == `suspense-close-${id}`) {
+           close = walker.currentNode;
+         }
+      }
+    var range = new Range();
+    range.setStartAfter(open);
+    range.setEndBefore(close);
+    range.deleteContents();
+    var tpl = document.getElementById("chunk-1R2-0f");
+    close.parentNode.insertBefore(tpl.content.cloneNode(true), close);
+    document.dispatchEvent(new CustomEvent("leptos:streamed-chunk", { detail: id }));
+"#;
+
+#[wasm_bindgen_test]
+async fn a_chunk_streamed_in_after_hydration_started_is_hydrated() {
+    // the shell, with the fallback
+    let test_wrapper = server_rendered(
+        r#"<main id="_chunk-1"><!--hk=_chunk-1-0o|leptos-streamed-start--><!--suspense-open-chunk-1R2-0--><!--hk=_chunk-1R2-1o|leptos-dyn-child-start--><p id="_chunk-1R2-7" class="loading">Loading...</p><!--hk=_chunk-1R2-1c|leptos-dyn-child-end--><!--suspense-close-chunk-1R2-0--><!--hk=_chunk-1-0c|leptos-streamed-end--></main>"#,
+    );
+
+    hydrate_to(&test_wrapper, "chunk", |cx| {
+        view! { cx, <main><Streamed/></main> }
+    });
+
+    // the chunk lands while the resource is still loading in the browser
+    test_wrapper
+        .insert_adjacent_html("beforeend", STREAMED_CHUNK)
+        .unwrap();
+    js_sys::Function::new_no_args(SWAP_CHUNK)
+        .call0(&wasm_bindgen::JsValue::NULL)
+        .unwrap();
+    let streamed = find(&test_wrapper, "p.message");
+    assert!(streamed.has_attribute("id"));
+
+    // once it has loaded, the children are hydrated from the chunk instead
+    // of being created again
+    sleep(150).await;
+    let message = find(&test_wrapper, "main p.message");
+    assert!(message.is_same_node(Some(&streamed)));
+    assert!(!message.has_attribute("id"));
+    assert_eq!(message.text_content().as_deref(), Some("Streamed"));
+    assert_eq!(
+        test_wrapper.query_selector_all("main p").unwrap().length(),
+        1
+    );
+}
+
+#[wasm_bindgen_test]
+fn a_dynamic_child_that_does_not_match_is_rendered_on_the_client() {
+    // the server rendered a <span> where the client has the <div>
+    let test_wrapper = server_rendered(
+        r#"<section id="_recover-1"><h2 id="_recover-2">Title</h2><!--hk=_recover-3o|leptos-dyn-child-start--><span id="_recover-4" class="panel"><!--hk=_recover-4-0o|leptos-clicker-start--><button id="_recover-4-1">Click me</button><!--hk=_recover-4-0c|leptos-clicker-end--></span><!--hk=_recover-3c|leptos-dyn-child-end--></section>"#,
+    );
+    let recoveries = HydrationCtx::recoveries();
+    let clicks = Rc::new(Cell::new(0));
+
+    hydrate_to(&test_wrapper, "recover", {
+        let clicks = Rc::clone(&clicks);
+        move |cx| {
+            view! { cx,
+                <section>
+                    <h2>"Title"</h2>
+                    {move || {
+                        let clicks = Rc::clone(&clicks);
+                        view! { cx, <div class="panel"><Clicker clicks/></div> }
+                    }}
+                </section>
+            }
+        }
+    });
+
+    // only the dynamic child was rendered again, once
+    assert_eq!(HydrationCtx::recoveries(), recoveries + 1);
+    assert!(!find(&test_wrapper, "h2").has_attribute("id"));
+    assert!(test_wrapper.query_selector("span").unwrap().is_none());
+    assert_eq!(
+        test_wrapper.query_selector_all("button").unwrap().length(),
+        1
+    );
+
+    // and the page works
+    let button = find(&test_wrapper, "section > div.panel > button");
+    button.click();
+    button.click();
+    assert_eq!(clicks.get(), 2);
+}
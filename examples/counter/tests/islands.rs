@@ -0,0 +1,161 @@
+mod common;
+
+use common::*;
+use leptos::{leptos_dom::HydrationCtx, *};
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// The pages in these tests are what a debug build of the server renders for
+// the same views. `hydrate_islands` looks for islands in the whole document,
+// so each test takes its page out again once it's done.
+
+#[component]
+fn Counter(cx: Scope) -> impl IntoView {
+    let (count, set_count) = create_signal(cx, 0);
+    view! { cx,
+        <button on:click=move |_| set_count.update(|n| *n += 1)>
+            {move || count.get()}
+        </button>
+    }
+}
+
+fn islands(cx: Scope, name: &str) -> Option<View> {
+    match name {
+        "counter" => Some(view! { cx, <Counter/> }.into_view(cx)),
+        _ => None,
+    }
+}
+
+#[wasm_bindgen_test]
+fn only_the_island_in_a_static_page_is_hydrated() {
+    // `<main><h1/><p/>{island(cx, "counter", ..)}</main>`
+    let page = server_rendered(
+        r#"<main id="_1-1"><h1 id="_1-2">Static</h1><p id="_1-3">Lots of static text.</p><leptos-island data-island="counter" data-key="island_1_4" data-resources="[]" id="_1-5"><!--hk=_island_1_4-0-0o|leptos-counter-start--><button id="_island_1_4-0-1"><!--hk=_island_1_4-0-2o|leptos-dyn-child-start-->0<!--hk=_island_1_4-0-2c|leptos-dyn-child-end--></button><!--hk=_island_1_4-0-0c|leptos-counter-end--></leptos-island></main>"#,
+    );
+    let button = find(&page, "leptos-island button");
+    let mismatches = HydrationCtx::mismatches();
+
+    _ = hydrate_islands(islands);
+
+    assert_eq!(HydrationCtx::mismatches(), mismatches);
+    button.click();
+    button.click();
+    assert_eq!(button.text_content().as_deref(), Some("2"));
+
+    // nothing outside the island was hydrated, so the static elements still
+    // have the keys the server gave them
+    assert_eq!(find(&page, "main").id(), "_1-1");
+    assert_eq!(find(&page, "h1").id(), "_1-2");
+    assert_eq!(find(&page, "p").id(), "_1-3");
+    assert_eq!(page.query_selector_all("button").unwrap().length(), 1);
+
+    page.remove();
+}
+
+#[component]
+fn Pressable(cx: Scope, presses: Rc<Cell<usize>>) -> impl IntoView {
+    view! { cx,
+        <button on:pointerdown=move |_| presses.set(presses.get() + 1)>
+            "Press me"
+        </button>
+    }
+}
+
+// `<section><h2/>` and islands hydrated on interaction, once visible, and
+// when idle
+const LAZY_PAGE: &str = r#"<section id="_1-1"><h2 id="_1-2">Later</h2><leptos-island data-island="pressable" data-key="island_1_3" data-resources="[]" data-hydrate="interaction" id="_1-4"><!--hk=_island_1_3-0-0o|leptos-pressable-start--><button id="_island_1_3-0-1">Press me</button><!--hk=_island_1_3-0-0c|leptos-pressable-end--></leptos-island><leptos-island data-island="counter" data-key="island_1_5" data-resources="[]" data-hydrate="visible" id="_1-6"><!--hk=_island_1_5-0-0o|leptos-counter-start--><button id="_island_1_5-0-1"><!--hk=_island_1_5-0-2o|leptos-dyn-child-start-->0<!--hk=_island_1_5-0-2c|leptos-dyn-child-end--></button><!--hk=_island_1_5-0-0c|leptos-counter-end--></leptos-island><leptos-island data-island="counter" data-key="island_1_7" data-resources="[]" data-hydrate="idle" id="_1-8"><!--hk=_island_1_7-0-0o|leptos-counter-start--><button id="_island_1_7-0-1"><!--hk=_island_1_7-0-2o|leptos-dyn-child-start-->0<!--hk=_island_1_7-0-2c|leptos-dyn-child-end--></button><!--hk=_island_1_7-0-0c|leptos-counter-end--></leptos-island></section>"#;
+
+fn lazy_islands(
+    presses: &Rc<Cell<usize>>,
+) -> impl Fn(Scope, &str) -> Option<View> + Clone + 'static {
+    let presses = Rc::clone(presses);
+    move |cx, name| match name {
+        "pressable" => {
+            let presses = Rc::clone(&presses);
+            Some(view! { cx, <Pressable presses/> }.into_view(cx))
+        }
+        name => islands(cx, name),
+    }
+}
+
+fn pointerdown() -> web_sys::Event {
+    let init = web_sys::EventInit::new();
+    init.set_bubbles(true);
+    web_sys::Event::new_with_event_init_dict("pointerdown", &init).unwrap()
+}
+
+// a hydrated element has given up its key
+fn is_hydrated(el: &web_sys::HtmlElement) -> bool {
+    !el.has_attribute("id")
+}
+
+#[wasm_bindgen_test]
+async fn islands_are_hydrated_when_their_strategy_triggers() {
+    let page = server_rendered(LAZY_PAGE);
+    let pressable = find(&page, "[data-hydrate=interaction] button");
+    let visible_island = find(&page, "[data-hydrate=visible]");
+    let visible = find(&page, "[data-hydrate=visible] button");
+    let idle = find(&page, "[data-hydrate=idle] button");
+    // kept out of view until the end
+    visible_island
+        .style()
+        .set_property("display", "none")
+        .unwrap();
+    let presses = Rc::new(Cell::new(0));
+
+    _ = hydrate_islands(lazy_islands(&presses));
+    assert!(!is_hydrated(&pressable));
+    assert!(!is_hydrated(&visible));
+
+    sleep(200).await;
+    assert!(is_hydrated(&idle));
+    idle.click();
+    assert_eq!(idle.text_content().as_deref(), Some("1"));
+
+    // the event that hydrates the island reaches its listener too
+    assert!(!is_hydrated(&pressable));
+    assert_eq!(presses.get(), 0);
+    pressable.dispatch_event(&pointerdown()).unwrap();
+    assert!(is_hydrated(&pressable));
+    assert_eq!(presses.get(), 1);
+    pressable.dispatch_event(&pointerdown()).unwrap();
+    assert_eq!(presses.get(), 2);
+
+    // until the island is shown, it's left as the server rendered it
+    assert!(!is_hydrated(&visible));
+    visible.click();
+    assert_eq!(visible.text_content().as_deref(), Some("0"));
+    visible_island.style().remove_property("display").unwrap();
+    sleep(200).await;
+    assert!(is_hydrated(&visible));
+    visible.click();
+    assert_eq!(visible.text_content().as_deref(), Some("1"));
+
+    page.remove();
+}
+
+#[wasm_bindgen_test]
+async fn disposed_islands_are_never_hydrated() {
+    let page = server_rendered(LAZY_PAGE);
+    let buttons = page.query_selector_all("button").unwrap();
+    let presses = Rc::new(Cell::new(0));
+
+    hydrate_islands(lazy_islands(&presses)).dispose();
+
+    find(&page, "[data-hydrate=interaction] button")
+        .dispatch_event(&pointerdown())
+        .unwrap();
+    sleep(200).await;
+    assert_eq!(presses.get(), 0);
+    for idx in 0..buttons.length() {
+        let button = buttons.get(idx).unwrap();
+        assert!(button
+            .unchecked_ref::<web_sys::Element>()
+            .has_attribute("id"));
+    }
+
+    page.remove();
+}
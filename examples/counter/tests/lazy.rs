@@ -0,0 +1,89 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+async fn lazy_component_suspends_until_it_has_loaded_once() {
+    let test_wrapper = test_wrapper();
+
+    let loads = Rc::new(Cell::new(0));
+    let settings = lazy_component({
+        let loads = Rc::clone(&loads);
+        move || {
+            loads.set(loads.get() + 1);
+            async {
+                // e.g., fetching the chunk the component is in
+                sleep(10).await;
+                Ok::<_, ServerFnError>(DynComponent::new(|cx| {
+                    view! { cx, <p>"Settings"</p> }
+                }))
+            }
+        }
+    });
+
+    let handle = mount_to(test_wrapper.clone(), {
+        let settings = settings.clone();
+        move |cx| {
+            view! { cx,
+                <Suspense fallback=|| "Loading...">
+                    {settings.clone()}
+                    {settings.clone()}
+                </Suspense>
+            }
+        }
+    });
+    let text = || test_wrapper.text_content().unwrap_or_default();
+    assert_eq!(text(), "Loading...");
+
+    sleep(30).await;
+    assert_eq!(text(), "SettingsSettings");
+    assert_eq!(loads.get(), 1);
+    handle.unmount();
+
+    // once it has loaded, it's rendered right away
+    let handle = mount_to(test_wrapper.clone(), move |cx| {
+        view! { cx,
+            <Suspense fallback=|| "Loading...">{settings.clone()}</Suspense>
+        }
+    });
+    assert_eq!(text(), "Settings");
+    assert_eq!(loads.get(), 1);
+    handle.unmount();
+}
+
+#[wasm_bindgen_test]
+async fn lazy_component_reports_a_failed_load_to_the_error_boundary() {
+    let test_wrapper = test_wrapper();
+
+    let broken = lazy_component(|| async {
+        sleep(10).await;
+        Err::<DynComponent, ServerFnError>(ServerFnError::ServerError(
+            "chunk failed".into(),
+        ))
+    });
+
+    let handle = mount_to(test_wrapper.clone(), move |cx| {
+        view! { cx,
+            <ErrorBoundary fallback=|cx, errors| view! { cx,
+                <p id="error">
+                    {move || errors.with(|errors| {
+                        errors.iter().map(|(_, e)| e.to_string()).collect::<String>()
+                    })}
+                </p>
+            }>
+                <Suspense fallback=|| "Loading...">{broken.clone()}</Suspense>
+            </ErrorBoundary>
+        }
+    });
+
+    sleep(30).await;
+    let error = test_wrapper.query_selector("#error").unwrap().unwrap();
+    assert!(error.text_content().unwrap().contains("chunk failed"));
+
+    handle.unmount();
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+fn record(log: &Log, entry: String, connected: bool) {
+    let state = if connected { "connected" } else { "detached" };
+    log.borrow_mut().push(format!("{entry} {state}"));
+}
+
+#[component]
+fn Label(cx: Scope, id: u32, log: Log) -> impl IntoView {
+    let el = view! { cx, <span>{id}</span> };
+    on_unmount(cx, el.clone(), move |el| {
+        record(&log, format!("unmount label {id}"), el.is_connected())
+    });
+    el
+}
+
+#[component]
+fn Item(cx: Scope, id: u32, log: Log) -> impl IntoView {
+    let el = view! { cx, <li><Label id log=Rc::clone(&log)/></li> };
+    on_mount(cx, el.clone(), {
+        let log = Rc::clone(&log);
+        move |el| record(&log, format!("mount {id}"), el.is_connected())
+    });
+    on_unmount(cx, el.clone(), move |el| {
+        record(&log, format!("unmount {id}"), el.is_connected())
+    });
+    el
+}
+
+#[wasm_bindgen_test]
+async fn mount_and_unmount_callbacks_follow_the_element_in_a_keyed_list() {
+    let test_wrapper = test_wrapper();
+    let log = Log::default();
+    let cx = signal_scope();
+    let (items, set_items) = create_signal(cx, vec![1, 2]);
+
+    mount_to(test_wrapper.clone(), {
+        let log = Rc::clone(&log);
+        move |cx| {
+            view! { cx,
+                <ul>
+                    <For
+                        each=move || items.get()
+                        key=|id| *id
+                        view=move |cx, id| {
+                            view! { cx, <Item id log=Rc::clone(&log)/> }
+                        }
+                    />
+                </ul>
+            }
+        }
+    });
+    // the elements aren't in the document yet while the view is built
+    assert!(log.borrow().is_empty());
+    sleep(50).await;
+    assert_eq!(*log.borrow(), ["mount 1 connected", "mount 2 connected"]);
+
+    // an insertion mounts only the new element
+    log.borrow_mut().clear();
+    set_items.set(vec![3, 1, 2]);
+    sleep(50).await;
+    assert_eq!(*log.borrow(), ["mount 3 connected"]);
+
+    // moving an element neither mounts nor unmounts it
+    log.borrow_mut().clear();
+    set_items.set(vec![2, 1, 3]);
+    sleep(50).await;
+    assert!(log.borrow().is_empty());
+
+    // a removal unmounts the children first, while they're still connected
+    set_items.set(vec![2, 3]);
+    assert_eq!(
+        *log.borrow(),
+        ["unmount label 1 connected", "unmount 1 connected"]
+    );
+    sleep(50).await;
+    assert_eq!(log.borrow().len(), 2);
+}
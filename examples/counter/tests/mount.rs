@@ -0,0 +1,122 @@
+mod common;
+
+use common::*;
+use counter::*;
+use leptos::*;
+use std::{cell::Cell, rc::Rc, time::Duration};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+async fn unmount_and_mount_again() {
+    let test_wrapper = test_wrapper();
+    // anything that was there before is left alone
+    test_wrapper.set_inner_html("<p>Not rendered by Leptos</p>");
+
+    let ticks = Rc::new(Cell::new(0));
+    let resizes = Rc::new(Cell::new(0));
+    let handle = mount_to(test_wrapper.clone(), {
+        let ticks = Rc::clone(&ticks);
+        let resizes = Rc::clone(&resizes);
+        move |cx| {
+            set_interval(cx, Duration::from_millis(5), move || {
+                ticks.set(ticks.get() + 1)
+            });
+            window_event_listener(cx, ev::resize, move |_| {
+                resizes.set(resizes.get() + 1)
+            });
+            view! { cx, <SimpleCounter initial_value=0 step=1/> }
+        }
+    });
+    assert!(handle.is_mounted());
+
+    let span = test_wrapper.query_selector("span").unwrap().unwrap();
+    let increment = test_wrapper
+        .query_selector("button:last-child")
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>();
+    increment.click();
+    assert_eq!(span.text_content().as_deref(), Some("Value: 1!"));
+
+    let resize = web_sys::Event::new("resize").unwrap();
+    leptos::window().dispatch_event(&resize).unwrap();
+    assert_eq!(resizes.get(), 1);
+
+    handle.unmount();
+    assert!(!handle.is_mounted());
+    assert_eq!(test_wrapper.inner_html(), "<p>Not rendered by Leptos</p>");
+
+    // the listener and the interval were removed along with the view
+    leptos::window().dispatch_event(&resize).unwrap();
+    assert_eq!(resizes.get(), 1);
+    let ticks_when_unmounted = ticks.get();
+    sleep(30).await;
+    assert_eq!(ticks.get(), ticks_when_unmounted);
+
+    // and the element can be mounted to again
+    let handle = mount_to(test_wrapper.clone(), |cx| {
+        view! { cx, <SimpleCounter initial_value=5 step=1/> }
+    });
+    assert!(handle.is_mounted());
+    let span = test_wrapper.query_selector("span").unwrap().unwrap();
+    assert_eq!(span.text_content().as_deref(), Some("Value: 5!"));
+    handle.unmount();
+}
+
+#[wasm_bindgen_test]
+fn mounting_again_replaces_the_previous_mount() {
+    let test_wrapper = test_wrapper();
+
+    let first = mount_to(test_wrapper.clone(), |cx| {
+        view! { cx, <SimpleCounter initial_value=1 step=1/> }
+    });
+    let second = mount_to(test_wrapper.clone(), |cx| {
+        view! { cx, <SimpleCounter initial_value=2 step=1/> }
+    });
+
+    assert!(!first.is_mounted());
+    assert!(second.is_mounted());
+    assert_eq!(test_wrapper.query_selector_all("span").unwrap().length(), 1);
+    let span = test_wrapper.query_selector("span").unwrap().unwrap();
+    assert_eq!(span.text_content().as_deref(), Some("Value: 2!"));
+
+    second.unmount();
+    assert_eq!(test_wrapper.inner_html(), "");
+}
+
+#[wasm_bindgen_test]
+fn unmounting_removes_what_the_view_added_after_it_was_mounted() {
+    let test_wrapper = test_wrapper();
+    test_wrapper.set_inner_html("<p>Not rendered by Leptos</p>");
+
+    let set_count = Rc::new(Cell::new(None));
+    let handle = mount_to(test_wrapper.clone(), {
+        let set_count = Rc::clone(&set_count);
+        move |cx| {
+            let (count, set) = create_signal(cx, 1);
+            set_count.set(Some(set));
+            let items = move || (0..count.get()).collect::<Vec<usize>>();
+            view! { cx,
+                <>
+                    <For
+                        each=items
+                        key=|n| *n
+                        view=|cx, n| view! { cx, <li>{n}</li> }
+                    />
+                    {move || (count.get() > 2).then(|| view! { cx, <p>"Many"</p> })}
+                </>
+            }
+        }
+    });
+
+    // the view grows at its root once it's mounted
+    set_count.get().unwrap().set(3);
+    assert_eq!(test_wrapper.query_selector_all("li").unwrap().length(), 3);
+    assert!(test_wrapper.inner_html().contains("<p>Many</p>"));
+
+    handle.unmount();
+    assert_eq!(test_wrapper.inner_html(), "<p>Not rendered by Leptos</p>");
+}
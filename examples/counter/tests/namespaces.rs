@@ -0,0 +1,52 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const HTML: &str = "http://www.w3.org/1999/xhtml";
+const SVG: &str = "http://www.w3.org/2000/svg";
+const XLINK: &str = "http://www.w3.org/1999/xlink";
+
+#[wasm_bindgen_test]
+fn an_inline_svg_icon_is_in_the_svg_namespace() {
+    let test_wrapper = test_wrapper();
+    let cx = signal_scope();
+    let (fill, set_fill) = create_signal(cx, "red");
+
+    mount_to(test_wrapper.clone(), move |cx| {
+        view! { cx,
+            <svg viewBox="0 0 10 10">
+                <title>"Icon"</title>
+                <path d="M0 0h10v10z" fill=move || fill.get()/>
+                <image xlink:href="#shape"/>
+                <a href="#shape">"Shape"</a>
+                <foreignObject>
+                    <a href="#top">"Top"</a>
+                </foreignObject>
+            </svg>
+        }
+    });
+    let namespace =
+        |selector: &str| find(&test_wrapper, selector).namespace_uri();
+    assert_eq!(namespace("svg").as_deref(), Some(SVG));
+    assert_eq!(namespace("svg > title").as_deref(), Some(SVG));
+    assert_eq!(namespace("path").as_deref(), Some(SVG));
+    assert_eq!(namespace("svg > a").as_deref(), Some(SVG));
+    // the children of a <foreignObject> are HTML again
+    assert_eq!(namespace("foreignObject").as_deref(), Some(SVG));
+    assert_eq!(namespace("foreignObject > a").as_deref(), Some(HTML));
+
+    let image = find(&test_wrapper, "image");
+    assert_eq!(
+        image.get_attribute_ns(Some(XLINK), "href").as_deref(),
+        Some("#shape")
+    );
+
+    let path = find(&test_wrapper, "path");
+    assert_eq!(path.get_attribute("fill").as_deref(), Some("red"));
+    set_fill.set("blue");
+    assert_eq!(path.get_attribute("fill").as_deref(), Some("blue"));
+}
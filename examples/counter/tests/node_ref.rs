@@ -0,0 +1,91 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[component]
+fn Search(cx: Scope, input_ref: NodeRef<html::Input>) -> impl IntoView {
+    view! { cx, <form><input name="search" node_ref=input_ref/></form> }
+}
+
+// focuses the input once the ref is loaded with it
+fn focus_when_loaded(cx: Scope, input_ref: NodeRef<html::Input>) {
+    create_effect(cx, move |_| {
+        if let Some(input) = input_ref.get() {
+            input.focus().unwrap();
+        }
+    });
+}
+
+fn is_focused(el: &web_sys::HtmlElement) -> bool {
+    el.is_same_node(leptos::document().active_element().as_deref())
+}
+
+#[wasm_bindgen_test]
+fn an_effect_focuses_the_input_through_its_ref() {
+    let test_wrapper = test_wrapper();
+    let cx = signal_scope();
+    let input_ref = create_node_ref::<html::Input>(cx);
+
+    let handle = mount_to(test_wrapper.clone(), move |cx| {
+        view! { cx, <Search input_ref/> }
+    });
+    focus_when_loaded(cx, input_ref);
+    assert!(is_focused(&find(&test_wrapper, "input")));
+
+    // the ref is emptied along with the view
+    handle.unmount();
+    assert!(input_ref.get().is_none());
+}
+
+#[wasm_bindgen_test]
+fn a_hydrated_ref_holds_the_server_rendered_input() {
+    // what a debug build renders from `HydrationKey::root("focus")`
+    let test_wrapper = server_rendered(
+        r#"<form id="_focus-1"><input name="search" id="_focus-2"/></form>"#,
+    );
+    let input = find(&test_wrapper, "input");
+    let cx = signal_scope();
+    let input_ref = create_node_ref::<html::Input>(cx);
+
+    hydrate_to(&test_wrapper, "focus", move |cx| {
+        view! { cx, <Search input_ref/> }
+    });
+    focus_when_loaded(cx, input_ref);
+    assert!(is_focused(&input));
+    assert!(!input.has_attribute("id"));
+}
+
+#[wasm_bindgen_test]
+fn a_ref_loaded_again_is_left_alone_by_the_first_element() {
+    let cx = signal_scope();
+    let input_ref = create_node_ref::<html::Input>(cx);
+    let runs = Rc::new(Cell::new(0));
+    create_effect(cx, {
+        let runs = Rc::clone(&runs);
+        move |_| {
+            _ = input_ref.get();
+            runs.set(runs.get() + 1);
+        }
+    });
+
+    let first = mount_to(test_wrapper(), move |cx| {
+        view! { cx, <Search input_ref/> }
+    });
+    let second_wrapper = test_wrapper();
+    let _second = mount_to(second_wrapper.clone(), move |cx| {
+        view! { cx, <Search input_ref/> }
+    });
+    let ran = runs.get();
+
+    // the ref now holds the second input, which the first view's cleanup
+    // neither empties nor reports as a change
+    first.unmount();
+    assert_eq!(runs.get(), ran);
+    let input = input_ref.get().unwrap();
+    assert!(input.is_same_node(Some(&find(&second_wrapper, "input"))));
+}
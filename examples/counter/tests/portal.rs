@@ -0,0 +1,107 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Clone)]
+struct Theme(&'static str);
+
+#[wasm_bindgen_test]
+fn portal_children_use_the_owners_context() {
+    let document = leptos::document();
+    let test_wrapper = document.create_element("section").unwrap();
+    let target = document.create_element("aside").unwrap();
+    let body = document.body().unwrap();
+    body.append_child(&test_wrapper).unwrap();
+    body.append_child(&target).unwrap();
+
+    let handle = mount_to(test_wrapper.clone(), {
+        let target = target.clone();
+        move |cx| {
+            provide_context(cx, Theme("dark"));
+
+            view! { cx,
+                <p>"In place"</p>
+                <Portal mount=target>
+                    <p id="themed">
+                        {move || use_context::<Theme>(cx).map(|theme| theme.0)}
+                    </p>
+                </Portal>
+            }
+        }
+    });
+
+    // the children are rendered into the target, not where the portal is
+    assert_eq!(test_wrapper.text_content().as_deref(), Some("In place"));
+    let themed = target.query_selector("#themed").unwrap().unwrap();
+    assert_eq!(themed.text_content().as_deref(), Some("dark"));
+
+    handle.unmount();
+    assert!(target.query_selector("#themed").unwrap().is_none());
+}
+
+#[wasm_bindgen_test]
+fn portal_children_are_removed_with_their_owner() {
+    let test_wrapper = test_wrapper();
+
+    let cx = signal_scope();
+    let open = create_rw_signal(cx, true);
+    let count = create_rw_signal(cx, 0);
+    let cleanups = Rc::new(Cell::new(0));
+
+    let handle = mount_to(test_wrapper.clone(), {
+        let cleanups = Rc::clone(&cleanups);
+        move |cx| {
+            view! { cx,
+                <Show when=move || open.get() fallback=|_| ()>
+                    {
+                        let cleanups = Rc::clone(&cleanups);
+                        view! { cx,
+                            <Portal>
+                                {
+                                    on_cleanup(cx, move || {
+                                        cleanups.set(cleanups.get() + 1)
+                                    });
+                                    view! { cx,
+                                        <dialog id="portal-dialog" open>
+                                            {move || count.get()}
+                                        </dialog>
+                                    }
+                                }
+                            </Portal>
+                        }
+                    }
+                </Show>
+            }
+        }
+    });
+    let dialog = || {
+        leptos::document()
+            .body()
+            .unwrap()
+            .query_selector("#portal-dialog")
+            .unwrap()
+    };
+
+    // the children are rendered into the body by default, and stay reactive
+    let rendered = dialog().unwrap();
+    assert!(!test_wrapper.contains(Some(&rendered)));
+    count.set(1);
+    assert_eq!(rendered.text_content().as_deref(), Some("1"));
+
+    // disposing the branch the portal is in removes what it rendered
+    open.set(false);
+    assert!(dialog().is_none());
+    assert_eq!(cleanups.get(), 1);
+
+    open.set(true);
+    assert!(dialog().is_some());
+
+    handle.unmount();
+    assert!(dialog().is_none());
+    assert_eq!(cleanups.get(), 2);
+}
@@ -0,0 +1,38 @@
+mod common;
+
+use common::*;
+use leptos::{leptos_dom::HydrationCtx, *};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// Whether the page looks server-rendered is only checked once, so this test
+// has a binary of its own, and sets up the page before anything is mounted.
+
+#[wasm_bindgen_test]
+fn a_page_that_looks_server_rendered_only_hydrates_builds_made_to() {
+    let server_rendered =
+        server_rendered(r#"<p id="_1-0-0" class="server">Server</p>"#);
+
+    // a build made to hydrate picks up on the key of the server's first root
+    let hydrating = RenderMode::default() == RenderMode::Hydrate;
+    assert_eq!(HydrationCtx::is_hydrating(), hydrating);
+    if hydrating {
+        return;
+    }
+
+    // and a client-rendered one creates its view from scratch, leaving the
+    // server's HTML alone
+    let test_wrapper = test_wrapper();
+    mount_to(test_wrapper.clone(), |cx| {
+        view! { cx, <p class="client">"Client"</p> }
+    });
+    assert!(!HydrationCtx::is_hydrating());
+    assert_eq!(
+        find(&test_wrapper, "p.client").text_content().as_deref(),
+        Some("Client")
+    );
+    let server = find(&server_rendered, "p.server");
+    assert_eq!(server.id(), "_1-0-0");
+    assert_eq!(server.text_content().as_deref(), Some("Server"));
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Default)]
+struct Counts {
+    shown: Cell<usize>,
+    fallbacks: Cell<usize>,
+    disposed: Cell<usize>,
+}
+
+fn toggle_five_times(keep_alive: bool) -> Rc<Counts> {
+    let test_wrapper = test_wrapper();
+
+    let cx = signal_scope();
+    let when = create_rw_signal(cx, true);
+    let counts = Rc::new(Counts::default());
+
+    let handle = mount_to(test_wrapper.clone(), {
+        let counts = Rc::clone(&counts);
+        move |cx| {
+            let on_dispose = {
+                let counts = Rc::clone(&counts);
+                move |cx| {
+                    let counts = Rc::clone(&counts);
+                    on_cleanup(cx, move || {
+                        counts.disposed.set(counts.disposed.get() + 1)
+                    });
+                }
+            };
+            let fallback = {
+                let counts = Rc::clone(&counts);
+                let on_dispose = on_dispose.clone();
+                move |cx| {
+                    counts.fallbacks.set(counts.fallbacks.get() + 1);
+                    on_dispose(cx);
+                    view! { cx, <p id="fallback">"Hidden"</p> }
+                }
+            };
+
+            view! { cx,
+                <Show when=move || when.get() fallback keep_alive>
+                    {
+                        counts.shown.set(counts.shown.get() + 1);
+                        on_dispose(cx);
+                        view! { cx, <input id="shown"/> }
+                    }
+                </Show>
+            }
+        }
+    });
+
+    let shown = test_wrapper.query_selector("#shown").unwrap().unwrap();
+    assert_eq!(counts.fallbacks.get(), 0);
+
+    for _ in 0..5 {
+        when.update(|when| *when = !*when);
+    }
+    // an odd number of toggles ends on the fallback
+    assert!(test_wrapper.query_selector("#shown").unwrap().is_none());
+    assert!(test_wrapper.query_selector("#fallback").unwrap().is_some());
+
+    when.set(true);
+    let shown_again = test_wrapper.query_selector("#shown").unwrap().unwrap();
+    assert_eq!(shown.is_same_node(Some(&shown_again)), keep_alive);
+
+    handle.unmount();
+    counts
+}
+
+#[wasm_bindgen_test]
+fn show_disposes_the_hidden_branch() {
+    let counts = toggle_five_times(false);
+
+    // true, false, true, false, true, false, then true again
+    assert_eq!(counts.shown.get(), 4);
+    assert_eq!(counts.fallbacks.get(), 3);
+    assert_eq!(counts.disposed.get(), 7);
+}
+
+#[wasm_bindgen_test]
+fn show_keeps_the_hidden_branch_alive() {
+    let counts = toggle_five_times(true);
+
+    // each branch is only created once, and disposed along with the view
+    assert_eq!(counts.shown.get(), 1);
+    assert_eq!(counts.fallbacks.get(), 1);
+    assert_eq!(counts.disposed.get(), 2);
+}
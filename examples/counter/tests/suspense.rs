@@ -0,0 +1,89 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use std::{cell::RefCell, rc::Rc, time::Duration};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// mounts a `<Suspense/>` whose resource takes `load_ms` to load, and records
+// every text it shows
+fn mount_timed(
+    load_ms: i32,
+    delay_ms: u64,
+    min_duration_ms: u64,
+) -> (web_sys::Element, Rc<RefCell<Vec<String>>>, impl FnOnce()) {
+    let test_wrapper = test_wrapper();
+
+    let handle = mount_to(test_wrapper.clone(), move |cx| {
+        let value = create_resource(
+            cx,
+            || (),
+            move |_| async move {
+                sleep(load_ms).await;
+                42
+            },
+        );
+
+        view! { cx,
+            <Suspense
+                fallback=|| "Loading..."
+                fallback_delay=Duration::from_millis(delay_ms)
+                fallback_min_duration=Duration::from_millis(min_duration_ms)
+            >
+                "Value: " {move || value.read(cx)}
+            </Suspense>
+        }
+    });
+
+    // a mutation observer would miss nothing, but sampling every few
+    // milliseconds is enough to see what stays on screen
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    leptos::spawn_local({
+        let seen = Rc::clone(&seen);
+        let test_wrapper = test_wrapper.clone();
+        async move {
+            for _ in 0..100 {
+                let text = test_wrapper.text_content().unwrap_or_default();
+                let mut seen = seen.borrow_mut();
+                if seen.last() != Some(&text) {
+                    seen.push(text);
+                }
+                drop(seen);
+                sleep(2).await;
+            }
+        }
+    });
+
+    (test_wrapper, seen, move || handle.unmount())
+}
+
+#[wasm_bindgen_test]
+async fn fast_resources_never_show_a_delayed_fallback() {
+    let (test_wrapper, seen, unmount) = mount_timed(10, 100, 0);
+
+    sleep(50).await;
+    assert_eq!(test_wrapper.text_content().unwrap(), "Value: 42");
+    assert!(!seen.borrow().iter().any(|text| text == "Loading..."));
+    unmount();
+}
+
+#[wasm_bindgen_test]
+async fn a_shown_fallback_is_held_for_the_minimum_duration() {
+    let (test_wrapper, seen, unmount) = mount_timed(40, 20, 100);
+
+    // shown after 20ms, and kept until 120ms even though it loads at 40ms
+    sleep(60).await;
+    assert_eq!(test_wrapper.text_content().unwrap(), "Loading...");
+    sleep(100).await;
+    assert_eq!(test_wrapper.text_content().unwrap(), "Value: 42");
+    assert_eq!(
+        seen.borrow()
+            .iter()
+            .filter(|text| *text == "Loading...")
+            .count(),
+        1
+    );
+    unmount();
+}
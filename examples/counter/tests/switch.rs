@@ -0,0 +1,101 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    Loading,
+    Error,
+    Empty,
+    Data(usize),
+}
+
+#[wasm_bindgen_test]
+fn switch_keeps_one_branch_alive() {
+    let test_wrapper = test_wrapper();
+
+    let cx = signal_scope();
+    let state = create_rw_signal(cx, State::Loading);
+    // every branch that's created or disposed, in order
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    let handle = mount_to(test_wrapper.clone(), {
+        let log = Rc::clone(&log);
+        move |cx| {
+            let lifecycle = move |cx, name: &'static str| {
+                log.borrow_mut().push(format!("+{name}"));
+                let log = Rc::clone(&log);
+                on_cleanup(cx, move || {
+                    log.borrow_mut().push(format!("-{name}"))
+                });
+            };
+            let fallback = {
+                let lifecycle = lifecycle.clone();
+                move |cx| {
+                    lifecycle(cx, "loading");
+                    view! { cx, <p>"Loading..."</p> }
+                }
+            };
+            let error = lifecycle.clone();
+            let empty = lifecycle.clone();
+
+            view! { cx,
+                <Switch fallback>
+                    <Match when=move || state.get() == State::Error>
+                        {error(cx, "error"); view! { cx, <p>"Error"</p> }}
+                    </Match>
+                    <Match when=move || state.get() == State::Empty>
+                        {empty(cx, "empty"); view! { cx, <p>"Empty"</p> }}
+                    </Match>
+                    <Match when=move || matches!(state.get(), State::Data(_))>
+                        {
+                            lifecycle(cx, "data");
+                            view! { cx,
+                                <p>
+                                    {move || match state.get() {
+                                        State::Data(n) => n,
+                                        _ => 0,
+                                    }}
+                                    " items"
+                                </p>
+                            }
+                        }
+                    </Match>
+                </Switch>
+            }
+        }
+    });
+
+    let text = || test_wrapper.text_content().unwrap_or_default();
+    assert_eq!(text(), "Loading...");
+
+    state.set(State::Error);
+    assert_eq!(text(), "Error");
+    state.set(State::Empty);
+    assert_eq!(text(), "Empty");
+    state.set(State::Data(1));
+    assert_eq!(text(), "1 items");
+    let paragraph = test_wrapper.query_selector("p").unwrap().unwrap();
+
+    // changing the state without changing the branch updates it in place
+    state.set(State::Data(2));
+    assert_eq!(text(), "2 items");
+    assert!(paragraph
+        .is_same_node(test_wrapper.query_selector("p").unwrap().as_deref()));
+
+    assert_eq!(
+        *log.borrow(),
+        [
+            "+loading", "-loading", "+error", "-error", "+empty", "-empty",
+            "+data"
+        ]
+    );
+
+    handle.unmount();
+    assert_eq!(log.borrow().last().map(String::as_str), Some("-data"));
+}
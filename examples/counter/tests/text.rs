@@ -0,0 +1,62 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// the text node between the markers of the dynamic child in `<p>`
+fn dynamic_text(p: &web_sys::Element) -> web_sys::Node {
+    let nodes = p.child_nodes();
+    let text = (0..nodes.length())
+        .filter_map(|idx| nodes.item(idx))
+        .filter(|node| node.node_type() == web_sys::Node::TEXT_NODE)
+        .collect::<Vec<_>>();
+    assert_eq!(text.len(), 2, "{}", p.inner_html());
+    text[1].clone()
+}
+
+#[wasm_bindgen_test]
+fn dynamic_text_keeps_its_text_node() {
+    let test_wrapper = test_wrapper();
+    let cx = signal_scope();
+    let (count, set_count) = create_signal(cx, 0);
+
+    mount_to(test_wrapper.clone(), move |cx| {
+        view! { cx, <p>"Count: "{move || count.get()}</p> }
+    });
+    let p = find(&test_wrapper, "p");
+    let text = dynamic_text(&p);
+
+    for n in 1..=100 {
+        set_count.set(n);
+        let now = dynamic_text(&p);
+        assert!(now.is_same_node(Some(&text)));
+        assert_eq!(now.text_content(), Some(n.to_string()));
+    }
+    assert_eq!(p.text_content().as_deref(), Some("Count: 100"));
+}
+
+#[wasm_bindgen_test]
+fn hydrated_dynamic_text_keeps_the_server_text_node() {
+    // what a debug build renders from `HydrationKey::root("count")`
+    let test_wrapper = server_rendered(
+        r#"<p id="_count-1">Count: <!--hk=_count-2o|leptos-dyn-child-start-->0<!--hk=_count-2c|leptos-dyn-child-end--></p>"#,
+    );
+    let p = find(&test_wrapper, "p");
+    let text = dynamic_text(&p);
+    let cx = signal_scope();
+    let (count, set_count) = create_signal(cx, 0);
+
+    hydrate_to(&test_wrapper, "count", move |cx| {
+        view! { cx, <p>"Count: "{move || count.get()}</p> }
+    });
+    assert!(dynamic_text(&p).is_same_node(Some(&text)));
+
+    for n in 1..=100 {
+        set_count.set(n);
+        assert!(dynamic_text(&p).is_same_node(Some(&text)));
+    }
+    assert_eq!(p.text_content().as_deref(), Some("Count: 100"));
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use std::{cell::Cell, rc::Rc, time::Duration};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn counter() -> (Rc<Cell<usize>>, impl Fn() + Clone + 'static) {
+    let count = Rc::new(Cell::new(0));
+    let increment = {
+        let count = Rc::clone(&count);
+        move || count.set(count.get() + 1)
+    };
+    (count, increment)
+}
+
+#[wasm_bindgen_test]
+async fn timers_are_cancelled_when_their_scope_is_disposed() {
+    let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+    let (timeouts, timeout) = counter();
+    let (ticks, tick) = counter();
+    let (frames, frame) = counter();
+
+    set_timeout(cx, Duration::from_millis(20), timeout);
+    set_interval(cx, Duration::from_millis(5), tick);
+    request_animation_frame(cx, frame);
+    sleep(30).await;
+    assert_eq!((timeouts.get(), frames.get()), (1, 1));
+    assert!(ticks.get() > 0);
+
+    // none of them run once the scope is gone, even when they're due
+    let (later, later_timeout) = counter();
+    let (later_frames, later_frame) = counter();
+    set_timeout(cx, Duration::from_millis(20), later_timeout);
+    request_animation_frame(cx, later_frame);
+    disposer.dispose();
+    let ticked = ticks.get();
+    sleep(60).await;
+    assert_eq!(ticks.get(), ticked);
+    assert_eq!((later.get(), later_frames.get()), (0, 0));
+}
+
+#[wasm_bindgen_test]
+async fn clearing_an_interval_stops_it() {
+    let cx = signal_scope();
+    let (ticks, tick) = counter();
+
+    let handle = set_interval(cx, Duration::from_millis(5), tick);
+    sleep(30).await;
+    assert!(ticks.get() > 0);
+
+    handle.clear();
+    let ticked = ticks.get();
+    sleep(30).await;
+    assert_eq!(ticks.get(), ticked);
+
+    // and so do the other handles
+    let (timeouts, timeout) = counter();
+    let (frames, frame) = counter();
+    set_timeout(cx, Duration::from_millis(5), timeout).clear();
+    request_animation_frame(cx, frame).clear();
+    sleep(30).await;
+    assert_eq!((timeouts.get(), frames.get()), (0, 0));
+}
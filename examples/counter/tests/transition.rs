@@ -0,0 +1,70 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+async fn transition_keeps_stale_children_while_refetching() {
+    let test_wrapper = test_wrapper();
+
+    let cx = signal_scope();
+    let id = create_rw_signal(cx, 1);
+    // every time the pending state changes
+    let pending = Rc::new(RefCell::new(Vec::new()));
+
+    let handle = mount_to(test_wrapper.clone(), {
+        let pending = Rc::clone(&pending);
+        move |cx| {
+            let value = create_resource(
+                cx,
+                move || id.get(),
+                |id| async move {
+                    sleep(20).await;
+                    id * 10
+                },
+            );
+            let set_pending = SignalSetter::map(cx, move |is_pending| {
+                let mut pending = pending.borrow_mut();
+                if pending.last() != Some(&is_pending) {
+                    pending.push(is_pending);
+                }
+            });
+
+            view! { cx,
+                <Transition
+                    fallback=move || view! { cx, <p id="fallback">"Loading..."</p> }
+                    set_pending
+                >
+                    <p id="value">"Value: " {move || value.read(cx)}</p>
+                </Transition>
+            }
+        }
+    });
+    let text = || test_wrapper.text_content().unwrap_or_default();
+
+    // the first load shows the fallback
+    sleep(0).await;
+    assert_eq!(text(), "Loading...");
+    sleep(40).await;
+    assert_eq!(text(), "Value: 10");
+    let shown = test_wrapper.query_selector("#value").unwrap().unwrap();
+    assert_eq!(*pending.borrow(), [false, true, false]);
+
+    // a refetch keeps the same nodes on screen until it resolves
+    id.set(2);
+    sleep(0).await;
+    assert_eq!(text(), "Value: 10");
+    assert!(test_wrapper.query_selector("#fallback").unwrap().is_none());
+    assert!(shown.is_connected());
+    assert_eq!(*pending.borrow(), [false, true, false, true]);
+
+    sleep(40).await;
+    assert_eq!(text(), "Value: 20");
+    assert_eq!(*pending.borrow(), [false, true, false, true, false]);
+
+    handle.unmount();
+}
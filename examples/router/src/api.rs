@@ -2,7 +2,7 @@ use futures::{
     channel::oneshot::{self, Canceled},
     Future,
 };
-use leptos::set_timeout;
+use leptos::{set_timeout, Scope};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
@@ -27,9 +27,9 @@ pub struct Contact {
     pub phone: String,
 }
 
-pub async fn get_contacts(_search: String) -> Vec<ContactSummary> {
+pub async fn get_contacts(cx: Scope, _search: String) -> Vec<ContactSummary> {
     // fake an API call with an artificial delay
-    _ = delay(Duration::from_millis(300)).await;
+    _ = delay(cx, Duration::from_millis(300)).await;
     vec![
         ContactSummary {
             id: 0,
@@ -49,9 +49,9 @@ pub async fn get_contacts(_search: String) -> Vec<ContactSummary> {
     ]
 }
 
-pub async fn get_contact(id: Option<usize>) -> Option<Contact> {
+pub async fn get_contact(cx: Scope, id: Option<usize>) -> Option<Contact> {
     // fake an API call with an artificial delay
-    _ = delay(Duration::from_millis(500)).await;
+    _ = delay(cx, Duration::from_millis(500)).await;
     match id {
         Some(0) => Some(Contact {
             id: 0,
@@ -93,13 +93,14 @@ pub async fn get_contact(id: Option<usize>) -> Option<Contact> {
     }
 }
 
-fn delay(duration: Duration) -> impl Future<Output = Result<(), Canceled>> {
+// resolves to `Err(Canceled)` if `cx` is disposed first
+fn delay(
+    cx: Scope,
+    duration: Duration,
+) -> impl Future<Output = Result<(), Canceled>> {
     let (tx, rx) = oneshot::channel();
-    set_timeout(
-        move || {
-            _ = tx.send(());
-        },
-        duration,
-    );
+    set_timeout(cx, duration, move || {
+        _ = tx.send(());
+    });
     rx
 }
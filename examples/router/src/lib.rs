@@ -72,7 +72,9 @@ pub fn ContactList(cx: Scope) -> impl IntoView {
 
     let location = use_location(cx);
     let contacts =
-        create_resource(cx, move || location.search.get(), get_contacts);
+        create_resource(cx, move || location.search.get(), move |search| {
+            get_contacts(cx, search)
+        });
     let contacts = move || {
         contacts.read(cx).map(|contacts| {
             // this data doesn't change frequently so we can use .map().collect() instead of a keyed <For/>
@@ -120,11 +122,9 @@ pub fn Contact(cx: Scope) -> impl IntoView {
     let contact = create_resource(
         cx,
         move || params().map(|params| params.id).ok(),
-        // any of the following would work (they're identical)
-        // move |id| async move { get_contact(id).await }
-        // move |id| get_contact(id),
-        // get_contact
-        get_contact,
+        // either of the following would work (they're identical)
+        // move |id| async move { get_contact(cx, id).await }
+        move |id| get_contact(cx, id),
     );
 
     let contact_display = move || match contact.read(cx) {
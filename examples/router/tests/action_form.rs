@@ -0,0 +1,231 @@
+mod common;
+
+use common::*;
+use leptos::{
+    server_fn::{
+        form, multipart, set_client_transport, ClientRequest, ClientResponse,
+        ClientTransport, Encoding,
+    },
+    *,
+};
+use leptos_router::*;
+use serde::Serialize;
+use std::{cell::RefCell, future::Future, pin::Pin, rc::Rc};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[server]
+pub async fn add_todo(
+    list: usize,
+    title: String,
+    tags: Vec<String>,
+    urgent: bool,
+) -> Result<usize, ServerFnError> {
+    todo!()
+}
+
+#[server(encoding = "Multipart")]
+pub async fn add_photos(
+    album: usize,
+    photos: Vec<UploadedFile>,
+) -> Result<usize, ServerFnError> {
+    todo!()
+}
+
+#[derive(Serialize)]
+struct List {
+    list: usize,
+}
+
+// the server, which takes 50ms to answer each call with `status` and `body`,
+// and every call it's sent, in order
+#[derive(Clone, Default)]
+struct MockServer {
+    status: u16,
+    body: Vec<u8>,
+    calls: Rc<RefCell<Vec<ClientRequest>>>,
+}
+
+impl ClientTransport for MockServer {
+    fn send(
+        &self,
+        request: ClientRequest,
+    ) -> Pin<Box<dyn Future<Output = Result<ClientResponse, ServerFnError>>>>
+    {
+        self.calls.borrow_mut().push(request);
+        let response = ClientResponse {
+            status: self.status,
+            status_text: String::new(),
+            body: self.body.clone(),
+        };
+        Box::pin(async move {
+            sleep(50).await;
+            Ok(response)
+        })
+    }
+}
+
+struct App {
+    form: web_sys::HtmlFormElement,
+    action: Action<AddTodo, Result<usize, ServerFnError>>,
+}
+
+fn mount(server: &MockServer) -> (App, MountHandle) {
+    set_client_transport(server.clone());
+
+    let test_wrapper = test_wrapper();
+
+    let action = Rc::new(RefCell::new(None));
+    let handle = mount_to(test_wrapper.clone(), {
+        let action = Rc::clone(&action);
+        move |cx| {
+            let add = create_server_action::<AddTodo>(cx);
+            *action.borrow_mut() = Some(add);
+            view! { cx,
+                <ActionForm action=add hidden=List { list: 3 }>
+                    <input name="title" value="Buy milk"/>
+                    <input name="tags[]" value="shopping"/>
+                    <input name="tags[]" value="today"/>
+                    <input type="checkbox" name="urgent" checked/>
+                </ActionForm>
+            }
+        }
+    });
+    let form = test_wrapper
+        .query_selector("form")
+        .unwrap()
+        .unwrap()
+        .unchecked_into();
+    let action = action.borrow().unwrap();
+    (App { form, action }, handle)
+}
+
+#[wasm_bindgen_test]
+async fn submitting_the_form_dispatches_the_action() {
+    let server = MockServer {
+        status: 200,
+        body: serde_json::to_vec(&1).unwrap(),
+        ..Default::default()
+    };
+    let (app, handle) = mount(&server);
+    let path = pathname();
+
+    app.form.request_submit().unwrap();
+    sleep(10).await;
+    assert_eq!(app.form.get_attribute("aria-busy").as_deref(), Some("true"));
+    assert!(app.action.pending().get());
+
+    sleep(100).await;
+    assert_eq!(app.form.get_attribute("aria-busy"), None);
+    assert_eq!(app.form.get_attribute("data-error"), None);
+    assert_eq!(app.action.value().get(), Some(Ok(1)));
+    // the page stays where it was
+    assert_eq!(pathname(), path);
+
+    let calls = server.calls.borrow();
+    assert_eq!(calls.len(), 1);
+    let AddTodo {
+        list,
+        title,
+        tags,
+        urgent,
+    } = form::from_bytes(&calls[0].body).unwrap();
+    assert_eq!(list, 3);
+    assert_eq!(title, "Buy milk");
+    assert_eq!(tags, ["shopping", "today"]);
+    assert!(urgent);
+
+    handle.unmount();
+}
+
+#[wasm_bindgen_test]
+async fn the_form_shows_the_error_of_a_failed_call() {
+    let error = ServerFnError::ServerError("the disk is full".into());
+    let server = MockServer {
+        status: 500,
+        body: serde_json::to_vec(&error).unwrap(),
+        ..Default::default()
+    };
+    let (app, handle) = mount(&server);
+
+    app.form.request_submit().unwrap();
+    sleep(100).await;
+    assert_eq!(app.action.value().get(), Some(Err(error.clone())));
+    assert_eq!(
+        app.form.get_attribute("data-error"),
+        Some(error.to_string())
+    );
+
+    handle.unmount();
+}
+
+#[wasm_bindgen_test]
+async fn the_files_picked_in_the_form_are_uploaded() {
+    let server = MockServer {
+        status: 200,
+        body: serde_json::to_vec(&1).unwrap(),
+        ..Default::default()
+    };
+    set_client_transport(server.clone());
+
+    let test_wrapper = test_wrapper();
+    let handle = mount_to(test_wrapper.clone(), |cx| {
+        let add = create_server_action::<AddPhotos>(cx);
+        view! { cx,
+            <ActionForm action=add>
+                <input name="album" value="3"/>
+                <input type="file" name="photos" multiple/>
+            </ActionForm>
+        }
+    });
+    let form: web_sys::HtmlFormElement = test_wrapper
+        .query_selector("form")
+        .unwrap()
+        .unwrap()
+        .unchecked_into();
+    assert_eq!(
+        form.get_attribute("enctype").as_deref(),
+        Some("multipart/form-data")
+    );
+
+    // as if the user picked a file
+    let input: web_sys::HtmlInputElement = test_wrapper
+        .query_selector("input[type=file]")
+        .unwrap()
+        .unwrap()
+        .unchecked_into();
+    let contents =
+        js_sys::Array::of1(&js_sys::Uint8Array::from(&[1, 2, 3][..]));
+    let options = web_sys::FilePropertyBag::new();
+    options.set_type("image/png");
+    let file = web_sys::File::new_with_u8_array_sequence_and_options(
+        &contents,
+        "beach.png",
+        &options,
+    )
+    .unwrap();
+    let picked = web_sys::DataTransfer::new().unwrap();
+    picked.items().add_with_file(&file).unwrap();
+    input.set_files(picked.files().as_ref());
+
+    form.request_submit().unwrap();
+    sleep(100).await;
+    let calls = server.calls.borrow();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].encoding, Encoding::Multipart);
+    let AddPhotos { album, photos } =
+        multipart::from_body(&calls[0].body).unwrap();
+    assert_eq!(album, 3);
+    assert_eq!(
+        photos,
+        [UploadedFile {
+            name: "beach.png".into(),
+            content_type: "image/png".into(),
+            bytes: vec![1, 2, 3],
+        }]
+    );
+
+    handle.unmount();
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use leptos_router::*;
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// mounts an app served under `/app` at `path`
+fn mount_at(
+    path: &str,
+) -> (web_sys::Element, Rc<RefCell<Vec<bool>>>, MountHandle) {
+    visit(path);
+
+    let test_wrapper = test_wrapper();
+
+    // whether the router handled each click; this listener runs after the
+    // router's, and keeps the browser from following the links it leaves
+    let handled = Rc::new(RefCell::new(Vec::new()));
+    let handle = mount_to(test_wrapper.clone(), {
+        let handled = Rc::clone(&handled);
+        move |cx| {
+            let view = view! { cx,
+                <Router base="/app">
+                    <nav>
+                        <A href="/" class="home">"Home"</A>
+                        <A href="/users/1" class="user">"User"</A>
+                        <a href="/application" class="other">"Another app"</a>
+                    </nav>
+                    <Routes>
+                        <Route path="" view=|cx| view! { cx, <p id="home">"Home"</p> }/>
+                        <Route path="users/:id" view=|cx| view! { cx, <p id="user">"User"</p> }/>
+                    </Routes>
+                </Router>
+            }
+            .into_view(cx);
+            window_event_listener(cx, ev::click, move |ev| {
+                handled.borrow_mut().push(ev.default_prevented());
+                ev.prevent_default();
+            });
+            view
+        }
+    });
+    (test_wrapper, handled, handle)
+}
+
+#[wasm_bindgen_test]
+async fn links_navigate_under_the_base() {
+    let (wrapper, handled, handle) = mount_at("/app");
+    assert!(wrapper.query_selector("#home").unwrap().is_some());
+
+    let user = link(&wrapper, ".user");
+    assert_eq!(user.get_attribute("href").as_deref(), Some("/app/users/1"));
+    user.click();
+    sleep(10).await;
+    assert_eq!(pathname(), "/app/users/1");
+    assert!(wrapper.query_selector("#user").unwrap().is_some());
+
+    // the bare base is the app's root
+    link(&wrapper, ".home").click();
+    sleep(10).await;
+    assert_eq!(pathname(), "/app");
+    assert!(wrapper.query_selector("#home").unwrap().is_some());
+
+    // a path that only starts with the same letters isn't under the base
+    link(&wrapper, ".other").click();
+    sleep(10).await;
+    assert_eq!(*handled.borrow(), [true, true, false]);
+    assert_eq!(pathname(), "/app");
+
+    handle.unmount();
+}
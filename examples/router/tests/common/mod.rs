@@ -0,0 +1,58 @@
+//! What the tests of the example share; each of them uses some of it.
+#![allow(dead_code)]
+
+use wasm_bindgen::{JsCast, JsValue};
+
+pub async fn sleep(ms: i32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        leptos::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+// an element of its own for each test to mount to
+pub fn test_wrapper() -> web_sys::Element {
+    let document = leptos::document();
+    let test_wrapper = document.create_element("section").unwrap();
+    document
+        .body()
+        .unwrap()
+        .append_child(&test_wrapper)
+        .unwrap();
+    test_wrapper
+}
+
+pub fn history() -> web_sys::History {
+    leptos::window().history().unwrap()
+}
+
+// goes to `url`, where the router mounted next starts from
+pub fn visit(url: &str) {
+    history()
+        .push_state_with_url(&JsValue::NULL, "", Some(url))
+        .unwrap();
+}
+
+pub fn pathname() -> String {
+    leptos::window().location().pathname().unwrap()
+}
+
+pub fn text(wrapper: &web_sys::Element, selector: &str) -> Option<String> {
+    wrapper
+        .query_selector(selector)
+        .unwrap()
+        .and_then(|el| el.text_content())
+}
+
+pub fn link(
+    wrapper: &web_sys::Element,
+    selector: &str,
+) -> web_sys::HtmlElement {
+    wrapper
+        .query_selector(selector)
+        .unwrap()
+        .unwrap_or_else(|| panic!("no link matching {selector}"))
+        .unchecked_into()
+}
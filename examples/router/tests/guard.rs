@@ -0,0 +1,76 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use leptos_router::*;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Clone, Copy)]
+struct LoggedIn(RwSignal<bool>);
+
+fn must_be_logged_in(cx: Scope, _params: &ParamsMap) -> GuardResult {
+    let LoggedIn(logged_in) = use_context(cx).unwrap();
+    if logged_in.get() {
+        GuardResult::Allow
+    } else {
+        GuardResult::Redirect("/login".into())
+    }
+}
+
+// mounts the app at `path`, with the signal its guard reads
+fn mount_at(path: &str) -> (web_sys::Element, RwSignal<bool>, MountHandle) {
+    visit(path);
+
+    let test_wrapper = test_wrapper();
+
+    let logged_in = std::rc::Rc::new(std::cell::Cell::new(None));
+    let handle = mount_to(test_wrapper.clone(), {
+        let logged_in = logged_in.clone();
+        move |cx| {
+            let signal = create_rw_signal(cx, true);
+            provide_context(cx, LoggedIn(signal));
+            logged_in.set(Some(signal));
+            view! { cx,
+                <Router>
+                    <Routes>
+                        <Route
+                            path="account"
+                            view=|cx| view! { cx, <p id="account">"Account"</p> }
+                            guard=Guard::new(must_be_logged_in)
+                        />
+                        <Route path="old" view=|cx| view! { cx, <Redirect path="/login"/> }/>
+                        <Route path="login" view=|cx| view! { cx, <p id="login">"Log in"</p> }/>
+                    </Routes>
+                </Router>
+            }
+        }
+    });
+    (test_wrapper, logged_in.get().unwrap(), handle)
+}
+
+#[wasm_bindgen_test]
+async fn redirect_navigates_on_the_client() {
+    let (wrapper, _, handle) = mount_at("/old");
+    sleep(10).await;
+    assert_eq!(pathname(), "/login");
+    assert!(wrapper.query_selector("#login").unwrap().is_some());
+
+    handle.unmount();
+}
+
+#[wasm_bindgen_test]
+async fn logging_out_leaves_the_guarded_page() {
+    let (wrapper, logged_in, handle) = mount_at("/account");
+    sleep(10).await;
+    assert!(wrapper.query_selector("#account").unwrap().is_some());
+
+    logged_in.set(false);
+    sleep(10).await;
+    assert_eq!(pathname(), "/login");
+    assert!(wrapper.query_selector("#account").unwrap().is_none());
+    assert!(wrapper.query_selector("#login").unwrap().is_some());
+
+    handle.unmount();
+}
@@ -0,0 +1,140 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use leptos_router::*;
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn browser_location() -> (String, String) {
+    let location = leptos::window().location();
+    (location.pathname().unwrap(), location.hash().unwrap())
+}
+
+#[derive(Clone)]
+struct App {
+    wrapper: web_sys::Element,
+    router: RouterContext,
+    location: Location,
+}
+
+// mounts the app in hash mode, at the page `url`
+fn mount_at(url: &str) -> (App, MountHandle) {
+    visit(url);
+
+    let test_wrapper = test_wrapper();
+
+    let app = Rc::new(RefCell::new(None));
+    let handle = mount_to(test_wrapper.clone(), {
+        let app = Rc::clone(&app);
+        let wrapper = test_wrapper.clone();
+        move |cx| {
+            view! { cx,
+                <Router mode=RouterMode::Hash>
+                    {
+                        *app.borrow_mut() = Some(App {
+                            wrapper,
+                            router: use_router(cx),
+                            location: use_location(cx),
+                        });
+                    }
+                    <nav>
+                        <A href="/users/2?tab=posts" class="user">"User 2"</A>
+                    </nav>
+                    <Routes>
+                        <Route path="users/:id" view=|cx| {
+                            let id = move || use_params_map(cx).with(|p| p.get("id").cloned());
+                            view! { cx, <p id="user">{id}</p> }
+                        }/>
+                        <Route path="" view=|cx| view! { cx, <p id="home">"Home"</p> }/>
+                    </Routes>
+                </Router>
+            }
+        }
+    });
+    let app = app.borrow().clone().unwrap();
+    (app, handle)
+}
+
+#[wasm_bindgen_test]
+async fn routes_are_read_from_the_hash() {
+    let (app, handle) = mount_at("/index.html?page=query#/users/1?tab=info");
+    sleep(10).await;
+
+    assert_eq!(app.location.pathname.get(), "/users/1");
+    // the query in the hash is the route's, not the page's
+    assert_eq!(app.location.search.get(), "?tab=info");
+    assert_eq!(
+        app.location.query.with(|q| q.get("tab").cloned()),
+        Some("info".to_string())
+    );
+    assert_eq!(app.location.query.with(|q| q.get("page").cloned()), None);
+    assert_eq!(text(&app.wrapper, "#user").as_deref(), Some("1"));
+
+    handle.unmount();
+}
+
+#[wasm_bindgen_test]
+async fn a_page_without_a_hash_is_the_root() {
+    let (app, handle) = mount_at("/index.html");
+    sleep(10).await;
+
+    assert_eq!(app.location.pathname.get(), "/");
+    assert!(text(&app.wrapper, "#home").is_some());
+
+    handle.unmount();
+}
+
+#[wasm_bindgen_test]
+async fn links_and_navigations_write_the_hash() {
+    let (app, handle) = mount_at("/index.html#/users/1");
+    let link: web_sys::HtmlElement = app
+        .wrapper
+        .query_selector(".user")
+        .unwrap()
+        .unwrap()
+        .unchecked_into();
+    assert_eq!(link.get_attribute("href").unwrap(), "#/users/2?tab=posts");
+
+    link.click();
+    sleep(10).await;
+    assert_eq!(
+        browser_location(),
+        ("/index.html".into(), "#/users/2?tab=posts".into())
+    );
+    assert_eq!(app.location.pathname.get(), "/users/2");
+    assert_eq!(text(&app.wrapper, "#user").as_deref(), Some("2"));
+
+    let navigate = use_navigate(app.router.base().cx());
+    navigate("/users/3", Default::default()).unwrap();
+    sleep(10).await;
+    assert_eq!(
+        browser_location(),
+        ("/index.html".into(), "#/users/3".into())
+    );
+    assert_eq!(text(&app.wrapper, "#user").as_deref(), Some("3"));
+
+    app.router.back();
+    sleep(50).await;
+    assert_eq!(app.location.pathname.get(), "/users/2");
+    assert_eq!(app.location.search.get(), "?tab=posts");
+
+    handle.unmount();
+}
+
+#[wasm_bindgen_test]
+async fn changing_the_hash_changes_the_route() {
+    let (app, handle) = mount_at("/index.html#/users/1");
+    sleep(10).await;
+
+    // as if the user had edited the URL
+    leptos::window().location().set_hash("/users/4").unwrap();
+    sleep(50).await;
+    assert_eq!(app.location.pathname.get(), "/users/4");
+    assert_eq!(text(&app.wrapper, "#user").as_deref(), Some("4"));
+
+    handle.unmount();
+}
@@ -0,0 +1,176 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use leptos_router::*;
+use serde_json::json;
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+thread_local! {
+    // every page that's created or disposed, in order
+    static LOG: RefCell<Vec<String>> = Default::default();
+}
+
+fn take_log() -> Vec<String> {
+    LOG.with(|log| log.take())
+}
+
+#[component]
+fn Page(cx: Scope) -> impl IntoView {
+    let id = use_params_map(cx)
+        .with_untracked(|p| p.get("id").cloned())
+        .unwrap_or_default();
+    LOG.with(|log| log.borrow_mut().push(format!("+page {id}")));
+    on_cleanup(cx, move || {
+        LOG.with(|log| log.borrow_mut().push(format!("-page {id}")))
+    });
+    view! { cx,
+        <div style="height: 5000px"></div>
+        <h2 id="section">"Section"</h2>
+        <div style="height: 5000px"></div>
+    }
+}
+
+type Navigate =
+    Rc<dyn Fn(&str, NavigateOptions) -> Result<(), NavigationError>>;
+
+#[derive(Clone)]
+struct App {
+    router: RouterContext,
+    location: Location,
+    navigate: Navigate,
+}
+
+// mounts the app at `path`, or where the browser already is
+fn mount(path: Option<&str>) -> (App, MountHandle) {
+    if let Some(path) = path {
+        visit(path);
+    }
+
+    let test_wrapper = test_wrapper();
+
+    let app = Rc::new(RefCell::new(None));
+    let handle = mount_to(test_wrapper, {
+        let app = Rc::clone(&app);
+        move |cx| {
+            view! { cx,
+                <Router>
+                    {
+                        *app.borrow_mut() = Some(App {
+                            router: use_router(cx),
+                            location: use_location(cx),
+                            navigate: Rc::new(use_navigate(cx)),
+                        });
+                    }
+                    <Routes>
+                        <Route path="pages/:id" view=|cx| view! { cx, <Page/> }/>
+                    </Routes>
+                </Router>
+            }
+        }
+    });
+    let app = app.borrow().clone().unwrap();
+    (app, handle)
+}
+
+fn state(location: &Location) -> Option<serde_json::Value> {
+    location
+        .state
+        .get()
+        .deserialize::<serde_json::Value>()
+        .and_then(Result::ok)
+}
+
+#[wasm_bindgen_test]
+async fn the_location_follows_back_and_forward() {
+    let (app, handle) = mount(Some("/pages/1"));
+    let App {
+        router,
+        location,
+        navigate,
+    } = app;
+
+    navigate(
+        "/pages/2?tab=info",
+        NavigateOptions {
+            state: State::from_serde(&json!({ "from": 1 })).unwrap(),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    navigate("/pages/3", Default::default()).unwrap();
+    sleep(10).await;
+    assert_eq!(location.pathname.get(), "/pages/3");
+    assert_eq!(state(&location), None);
+
+    // the browser fires `popstate` for the entry it goes back to
+    router.back();
+    sleep(50).await;
+    assert_eq!(location.pathname.get(), "/pages/2");
+    assert_eq!(location.search.get(), "?tab=info");
+    assert_eq!(state(&location), Some(json!({ "from": 1 })));
+
+    router.go(-1);
+    sleep(50).await;
+    assert_eq!(location.pathname.get(), "/pages/1");
+
+    router.go(2);
+    sleep(50).await;
+    assert_eq!(location.pathname.get(), "/pages/3");
+
+    router.back();
+    sleep(50).await;
+    router.forward();
+    sleep(50).await;
+    assert_eq!(location.pathname.get(), "/pages/3");
+
+    handle.unmount();
+}
+
+#[wasm_bindgen_test]
+async fn changing_only_the_hash_scrolls_without_recreating_the_page() {
+    let (app, handle) = mount(Some("/pages/1"));
+    sleep(10).await;
+    take_log();
+
+    (app.navigate)("/pages/1#section", Default::default()).unwrap();
+    sleep(10).await;
+    assert_eq!(app.location.hash.get(), "#section");
+    let section = leptos::document().get_element_by_id("section").unwrap();
+    assert!(section.get_bounding_client_rect().top().abs() < 1.0);
+
+    app.router.back();
+    sleep(50).await;
+    assert_eq!(app.location.hash.get(), "");
+    app.router.forward();
+    sleep(50).await;
+    assert_eq!(app.location.hash.get(), "#section");
+    assert!(take_log().is_empty());
+
+    handle.unmount();
+}
+
+#[wasm_bindgen_test]
+async fn state_survives_a_new_router() {
+    let (app, handle) = mount(Some("/pages/1"));
+    (app.navigate)(
+        "/pages/2",
+        NavigateOptions {
+            state: State::from_serde(&json!("kept")).unwrap(),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    sleep(10).await;
+    handle.unmount();
+
+    // like reloading the page, which keeps the entry's state
+    let (app, handle) = mount(None);
+    assert_eq!(app.location.pathname.get(), "/pages/2");
+    assert_eq!(state(&app.location), Some(json!("kept")));
+
+    handle.unmount();
+}
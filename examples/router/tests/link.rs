@@ -0,0 +1,158 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use leptos_router::*;
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn is_active(link: &web_sys::HtmlElement) -> bool {
+    let aria_current = link.get_attribute("aria-current");
+    let active = link.class_list().contains("active");
+    assert_eq!(aria_current.is_some(), active);
+    active
+}
+
+fn app(cx: Scope) -> impl IntoView {
+    view! { cx,
+        <Router>
+            <nav>
+                <A href="/users" class="nested">"Users"</A>
+                <A href="/users" class="exact" exact=true>"All users"</A>
+            </nav>
+            <Routes>
+                <Route path="users" view=|cx| view! { cx, <Outlet/> }>
+                    <Route path=":id" view=|cx| view! { cx,
+                        <A href="posts" class="posts">"Posts"</A>
+                        <Outlet/>
+                    }>
+                        <Route path="posts" view=|cx| view! { cx, <p id="posts">"Posts"</p> }/>
+                        <Route path="" view=|_| ()/>
+                    </Route>
+                    <Route path="" view=|cx| view! { cx, <p id="users">"All users"</p> }/>
+                </Route>
+            </Routes>
+        </Router>
+    }
+}
+
+// mounts the app at `path`, the location the router starts from
+fn mount_at(
+    path: &str,
+) -> (web_sys::Element, Rc<RefCell<Vec<bool>>>, MountHandle) {
+    visit(path);
+
+    let test_wrapper = test_wrapper();
+
+    // whether the router handled each click, i.e., prevented its default;
+    // this listener is added after the router's, so it runs after it, and
+    // keeps the browser from following the links the router leaves alone
+    let handled = Rc::new(RefCell::new(Vec::new()));
+    let handle = mount_to(test_wrapper.clone(), {
+        let handled = Rc::clone(&handled);
+        move |cx| {
+            let view = app(cx).into_view(cx);
+            window_event_listener(cx, ev::click, move |ev| {
+                handled.borrow_mut().push(ev.default_prevented());
+                ev.prevent_default();
+            });
+            view
+        }
+    });
+    (test_wrapper, handled, handle)
+}
+
+// a click that bubbles up to the router, which the test can set up further
+fn click(
+    link: &web_sys::HtmlElement,
+    init: impl FnOnce(&web_sys::MouseEventInit),
+) {
+    let dict = web_sys::MouseEventInit::new();
+    dict.set_bubbles(true);
+    dict.set_cancelable(true);
+    init(&dict);
+    let ev =
+        web_sys::MouseEvent::new_with_mouse_event_init_dict("click", &dict)
+            .unwrap();
+    link.dispatch_event(&ev).unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn links_resolve_relative_to_a_nested_route() {
+    let (wrapper, handled, handle) = mount_at("/users/1");
+
+    // `posts` is relative to `/users/:id`, not to the page's URL
+    let posts = link(&wrapper, ".posts");
+    assert_eq!(
+        posts.get_attribute("href").as_deref(),
+        Some("/users/1/posts")
+    );
+
+    posts.click();
+    sleep(10).await;
+    assert_eq!(*handled.borrow(), [true]);
+    assert_eq!(pathname(), "/users/1/posts");
+    assert!(wrapper.query_selector("#posts").unwrap().is_some());
+
+    handle.unmount();
+}
+
+#[wasm_bindgen_test]
+async fn modified_clicks_are_left_to_the_browser() {
+    let (wrapper, handled, handle) = mount_at("/users/1");
+    let posts = link(&wrapper, ".posts");
+
+    click(&posts, |init| init.set_ctrl_key(true));
+    click(&posts, |init| init.set_meta_key(true));
+    click(&posts, |init| init.set_shift_key(true));
+    // the middle button
+    click(&posts, |init| init.set_button(1));
+    posts.set_attribute("download", "").unwrap();
+    click(&posts, |_| ());
+    posts.remove_attribute("download").unwrap();
+    sleep(10).await;
+
+    assert_eq!(*handled.borrow(), [false; 5]);
+    assert_eq!(pathname(), "/users/1");
+    assert!(wrapper.query_selector("#posts").unwrap().is_none());
+
+    // an ordinary click is still handled by the router
+    click(&posts, |_| ());
+    sleep(10).await;
+    assert_eq!(handled.borrow().last(), Some(&true));
+    assert_eq!(pathname(), "/users/1/posts");
+
+    handle.unmount();
+}
+
+#[wasm_bindgen_test]
+async fn links_are_active_while_their_page_is_shown() {
+    let (wrapper, _, handle) = mount_at("/users/1");
+    let nested = link(&wrapper, ".nested");
+    let exact = link(&wrapper, ".exact");
+    let posts = link(&wrapper, ".posts");
+
+    // `/users/1` is nested in `/users`
+    assert!(is_active(&nested));
+    assert!(!is_active(&exact));
+    assert!(!is_active(&posts));
+
+    posts.click();
+    sleep(10).await;
+    assert!(is_active(&nested));
+    assert!(!is_active(&exact));
+    assert!(is_active(&link(&wrapper, ".posts")));
+
+    exact.click();
+    sleep(10).await;
+    assert_eq!(pathname(), "/users");
+    assert!(is_active(&nested));
+    assert!(is_active(&exact));
+    // the class the link was given is kept
+    assert!(exact.class_list().contains("exact"));
+    assert!(wrapper.query_selector("#users").unwrap().is_some());
+
+    handle.unmount();
+}
@@ -0,0 +1,40 @@
+use leptos_router::*;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// the same expectations as for the server, in the router's tests
+
+#[wasm_bindgen_test]
+fn optional_params_expand_as_on_the_server() {
+    assert_eq!(
+        expand_optionals("/shop/:category?/items/:item?"),
+        vec![
+            "/shop/items",
+            "/shop/:category/items",
+            "/shop/items/:item",
+            "/shop/:category/items/:item"
+        ]
+    );
+    assert_eq!(
+        expand_optionals("/files/:drive?/*path"),
+        vec!["/files/*path", "/files/:drive/*path"]
+    );
+}
+
+#[wasm_bindgen_test]
+fn urls_have_the_same_parts_as_on_the_server() {
+    let url = Url::try_from("/users/1?tab=posts&tab=likes#top").unwrap();
+    assert_eq!(url.pathname, "/users/1");
+    assert_eq!(url.search, "?tab=posts&tab=likes");
+    assert_eq!(url.hash, "#top");
+    assert_eq!(
+        url.search_params.get_all("tab").collect::<Vec<_>>(),
+        ["posts", "likes"]
+    );
+
+    let url = Url::try_from("http://leptos.dev/users").unwrap();
+    assert_eq!(url.pathname, "/users");
+    assert_eq!(url.search, "");
+    assert_eq!(url.hash, "");
+}
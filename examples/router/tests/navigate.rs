@@ -0,0 +1,132 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use leptos_router::*;
+use serde_json::json;
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+type Navigate =
+    Rc<dyn Fn(&str, NavigateOptions) -> Result<(), NavigationError>>;
+
+#[derive(Default, Clone)]
+struct Captured {
+    navigate: Rc<RefCell<Option<Navigate>>>,
+    location: Rc<RefCell<Option<Location>>>,
+}
+
+// mounts a router at `path`, and captures its `navigate` and location
+fn mount_at(path: &str) -> (Captured, MountHandle) {
+    visit(path);
+
+    let test_wrapper = test_wrapper();
+
+    let captured = Captured::default();
+    let handle = mount_to(test_wrapper, {
+        let captured = captured.clone();
+        move |cx| {
+            view! { cx,
+                <Router>
+                    <Routes>
+                        <Route path="users" view=move |cx| {
+                            *captured.navigate.borrow_mut() =
+                                Some(Rc::new(use_navigate(cx)));
+                            *captured.location.borrow_mut() =
+                                Some(use_location(cx));
+                            view! { cx, <Outlet/> }
+                        }>
+                            <Route path="*any" view=|_| ()/>
+                        </Route>
+                    </Routes>
+                </Router>
+            }
+        }
+    });
+    (captured, handle)
+}
+
+impl Captured {
+    fn navigate(&self, to: &str, options: NavigateOptions) {
+        let navigate = self.navigate.borrow().clone().unwrap();
+        navigate(to, options).unwrap();
+    }
+
+    fn location(&self) -> Location {
+        self.location.borrow().clone().unwrap()
+    }
+}
+
+#[wasm_bindgen_test]
+async fn state_is_kept_with_the_history_entry() {
+    let (captured, handle) = mount_at("/users");
+    let state = json!({ "saved": true, "id": 1 });
+
+    captured.navigate(
+        "1",
+        NavigateOptions {
+            state: State::from_serde(&state).unwrap(),
+            ..Default::default()
+        },
+    );
+    sleep(10).await;
+    let location = captured.location();
+    assert_eq!(location.pathname.get(), "/users/1");
+    assert_eq!(
+        location
+            .state
+            .get()
+            .deserialize::<serde_json::Value>()
+            .unwrap()
+            .ok(),
+        Some(state.clone())
+    );
+
+    // the state comes back with the entry
+    captured.navigate("2", Default::default());
+    sleep(10).await;
+    assert!(location
+        .state
+        .get()
+        .deserialize::<serde_json::Value>()
+        .is_none());
+    history().back().unwrap();
+    sleep(50).await;
+    assert_eq!(location.pathname.get(), "/users/1");
+    assert_eq!(
+        location
+            .state
+            .get()
+            .deserialize::<serde_json::Value>()
+            .unwrap()
+            .ok(),
+        Some(state)
+    );
+
+    handle.unmount();
+}
+
+#[wasm_bindgen_test]
+async fn replacing_does_not_add_an_entry() {
+    let (captured, handle) = mount_at("/users");
+    let length = history().length().unwrap();
+
+    captured.navigate("1", Default::default());
+    sleep(10).await;
+    assert_eq!(history().length().unwrap(), length + 1);
+
+    captured.navigate(
+        "/users/2",
+        NavigateOptions {
+            replace: true,
+            ..Default::default()
+        },
+    );
+    sleep(10).await;
+    assert_eq!(history().length().unwrap(), length + 1);
+    assert_eq!(captured.location().pathname.get(), "/users/2");
+
+    handle.unmount();
+}
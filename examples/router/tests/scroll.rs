@@ -0,0 +1,161 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use leptos_router::*;
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn scroll_y() -> f64 {
+    leptos::window().scroll_y().unwrap()
+}
+
+fn scroll_to(y: f64) {
+    leptos::window().scroll_to_with_x_and_y(0.0, y);
+}
+
+fn el(wrapper: &web_sys::Element, selector: &str) -> web_sys::HtmlElement {
+    wrapper
+        .query_selector(selector)
+        .unwrap()
+        .unwrap_or_else(|| panic!("nothing matching {selector}"))
+        .unchecked_into()
+}
+
+#[component]
+fn Long(cx: Scope) -> impl IntoView {
+    let sidebar = create_node_ref::<html::Nav>(cx);
+    use_scroll_restoration(cx, "sidebar", sidebar);
+    view! { cx,
+        <nav _ref=sidebar id="sidebar" style="height: 100px; overflow: auto">
+            <div style="height: 1000px"></div>
+        </nav>
+        <div id="long" style="height: 5000px"></div>
+    }
+}
+
+#[component]
+fn Slow(cx: Scope) -> impl IntoView {
+    let height = create_local_resource(
+        cx,
+        || (),
+        |_| async {
+            sleep(200).await;
+            5000
+        },
+    );
+    view! { cx,
+        <Suspense fallback=|| ()>
+            {move || height.read(cx).map(|height| view! { cx,
+                <div id="slow" style=format!("height: {height}px")></div>
+            })}
+        </Suspense>
+    }
+}
+
+type Navigate =
+    Rc<dyn Fn(&str, NavigateOptions) -> Result<(), NavigationError>>;
+
+// mounts the app at `path`, and captures its `navigate`
+fn mount_at(path: &str) -> (web_sys::Element, Navigate, MountHandle) {
+    visit(path);
+
+    let test_wrapper = test_wrapper();
+
+    let navigate = Rc::new(RefCell::new(None::<Navigate>));
+    let handle = mount_to(test_wrapper.clone(), {
+        let navigate = Rc::clone(&navigate);
+        move |cx| {
+            view! { cx,
+                <Router>
+                    {
+                        *navigate.borrow_mut() = Some(Rc::new(use_navigate(cx)));
+                    }
+                    <Routes>
+                        <Route path="long" view=|cx| view! { cx, <Long/> }/>
+                        <Route path="slow" view=|cx| view! { cx, <Slow/> }/>
+                        <Route path="other" view=|cx| view! { cx,
+                            <div id="other" style="height: 5000px"></div>
+                        }/>
+                    </Routes>
+                </Router>
+            }
+        }
+    });
+    let navigate = navigate.borrow().clone().unwrap();
+    (test_wrapper, navigate, handle)
+}
+
+#[wasm_bindgen_test]
+async fn going_back_restores_the_scroll_position() {
+    let (wrapper, navigate, handle) = mount_at("/long");
+    scroll_to(1234.0);
+    el(&wrapper, "#sidebar").set_scroll_top(300);
+
+    // a new page starts at the top
+    navigate("/other", Default::default()).unwrap();
+    sleep(10).await;
+    assert!(wrapper.query_selector("#other").unwrap().is_some());
+    assert_eq!(scroll_y(), 0.0);
+    scroll_to(200.0);
+
+    history().back().unwrap();
+    sleep(50).await;
+    assert!(wrapper.query_selector("#long").unwrap().is_some());
+    assert_eq!(scroll_y(), 1234.0);
+    // the sidebar is a new element, which is scrolled like the old one
+    assert_eq!(el(&wrapper, "#sidebar").scroll_top(), 300);
+
+    history().forward().unwrap();
+    sleep(50).await;
+    assert!(wrapper.query_selector("#other").unwrap().is_some());
+    assert_eq!(scroll_y(), 200.0);
+
+    handle.unmount();
+}
+
+#[wasm_bindgen_test]
+async fn navigating_without_scrolling_keeps_the_position() {
+    let (wrapper, navigate, handle) = mount_at("/long");
+    scroll_to(1234.0);
+
+    navigate(
+        "/other",
+        NavigateOptions {
+            scroll: false,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    sleep(10).await;
+    assert!(wrapper.query_selector("#other").unwrap().is_some());
+    assert_eq!(scroll_y(), 1234.0);
+
+    handle.unmount();
+}
+
+#[wasm_bindgen_test]
+async fn restoring_waits_for_suspended_content() {
+    let (wrapper, navigate, handle) = mount_at("/slow");
+    sleep(300).await;
+    scroll_to(2000.0);
+
+    navigate("/other", Default::default()).unwrap();
+    sleep(10).await;
+    assert_eq!(scroll_y(), 0.0);
+
+    // the page is too short until its resource has loaded again
+    history().back().unwrap();
+    sleep(50).await;
+    assert!(wrapper.query_selector("#slow").unwrap().is_none());
+    assert!(scroll_y() < 2000.0);
+
+    sleep(300).await;
+    assert!(wrapper.query_selector("#slow").unwrap().is_some());
+    assert_eq!(scroll_y(), 2000.0);
+
+    handle.unmount();
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use common::*;
+use leptos::*;
+use leptos_router::*;
+use std::{cell::RefCell, rc::Rc, time::Duration};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+thread_local! {
+    // every user page that's created, in order
+    static LOG: RefCell<Vec<String>> = Default::default();
+}
+
+fn take_log() -> Vec<String> {
+    LOG.with(|log| log.take())
+}
+
+// a loader that takes 200ms
+async fn load_user(_cx: Scope, params: ParamsMap) -> String {
+    let id = params.get("id").cloned().unwrap_or_default();
+    sleep(200).await;
+    format!("User #{id}")
+}
+
+#[component]
+fn User(cx: Scope) -> impl IntoView {
+    let id = use_params_map(cx)
+        .with_untracked(|p| p.get("id").cloned())
+        .unwrap_or_default();
+    LOG.with(|log| log.borrow_mut().push(format!("user {id}")));
+    let name = move || use_loader_data::<String>(cx);
+    view! { cx,
+        <Suspense fallback=move || view! { cx, <p id="loading">"Loading..."</p> }>
+            <h1 id="user">{name}</h1>
+        </Suspense>
+    }
+}
+
+#[derive(Clone)]
+struct App {
+    wrapper: web_sys::Element,
+    is_routing: Signal<bool>,
+    navigate: Rc<dyn Fn(&str, NavigateOptions) -> Result<(), NavigationError>>,
+}
+
+fn mount_at(path: &str, transition_timeout: Duration) -> (App, MountHandle) {
+    visit(path);
+
+    let test_wrapper = test_wrapper();
+
+    let app = Rc::new(RefCell::new(None));
+    let handle = mount_to(test_wrapper.clone(), {
+        let app = Rc::clone(&app);
+        let wrapper = test_wrapper.clone();
+        move |cx| {
+            view! { cx,
+                <Router transition_timeout>
+                    {
+                        *app.borrow_mut() = Some(App {
+                            wrapper,
+                            is_routing: use_is_routing(cx),
+                            navigate: Rc::new(use_navigate(cx)),
+                        });
+                    }
+                    <Routes>
+                        <Route
+                            path="users/:id"
+                            view=|cx| view! { cx, <User/> }
+                            loader=Loader::new(load_user)
+                        />
+                        <Route path="" view=|cx| view! { cx, <p id="home">"Home"</p> }/>
+                    </Routes>
+                </Router>
+            }
+        }
+    });
+    let app = app.borrow().clone().unwrap();
+    (app, handle)
+}
+
+#[wasm_bindgen_test]
+async fn the_old_page_stays_until_the_new_route_has_loaded() {
+    let (app, handle) = mount_at("/", Duration::from_secs(5));
+    sleep(10).await;
+    take_log();
+    assert!(!app.is_routing.get());
+
+    (app.navigate)("/users/1", Default::default()).unwrap();
+    sleep(50).await;
+    assert!(app.is_routing.get());
+    assert!(text(&app.wrapper, "#home").is_some());
+    assert!(text(&app.wrapper, "#loading").is_none());
+
+    sleep(300).await;
+    assert!(!app.is_routing.get());
+    assert!(text(&app.wrapper, "#home").is_none());
+    assert_eq!(text(&app.wrapper, "#user").as_deref(), Some("User #1"));
+    // the page was rendered, and swapped in, once
+    assert_eq!(take_log(), ["user 1"]);
+
+    handle.unmount();
+}
+
+#[wasm_bindgen_test]
+async fn the_new_route_is_shown_once_the_timeout_has_passed() {
+    let (app, handle) = mount_at("/", Duration::from_millis(50));
+    sleep(10).await;
+    take_log();
+
+    (app.navigate)("/users/2", Default::default()).unwrap();
+    sleep(100).await;
+    // it's shown while it's still loading, with its fallback
+    assert!(!app.is_routing.get());
+    assert!(text(&app.wrapper, "#home").is_none());
+    assert!(text(&app.wrapper, "#loading").is_some());
+
+    sleep(200).await;
+    assert_eq!(text(&app.wrapper, "#user").as_deref(), Some("User #2"));
+    assert_eq!(take_log(), ["user 2"]);
+
+    handle.unmount();
+}
@@ -25,7 +25,7 @@ use leptos_meta::*;
 use leptos_router::*;
 use parking_lot::RwLock;
 use regex::Regex;
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 /// This struct lets you define headers and override the status of the Response from an Element or a Server Function
 /// Typically contained inside of a ResponseOptions. Setting this is useful for cookies and custom responses.
@@ -92,6 +92,39 @@ impl ResponseOptions {
         let res_parts = &mut *writeable;
         res_parts.headers.append(key, value);
     }
+    /// Applies the status, headers, and redirect that were set on a
+    /// [ResponseMetadata] while rendering. Headers and statuses that aren't
+    /// valid are skipped.
+    pub fn apply_metadata(&self, collected: CollectedResponse) {
+        let mut writeable = self.0.write();
+        let res_parts = &mut *writeable;
+        if let Some(status) =
+            collected.status.and_then(|s| StatusCode::from_u16(s).ok())
+        {
+            res_parts.status = Some(status);
+        }
+        let mut seen = HashSet::new();
+        for (key, value) in collected.headers {
+            let (Ok(key), Ok(value)) = (
+                header::HeaderName::try_from(key),
+                header::HeaderValue::try_from(value),
+            ) else {
+                continue;
+            };
+            // this may be applied more than once for the same response
+            if seen.insert(key.clone()) {
+                res_parts.headers.insert(key, value);
+            } else {
+                res_parts.headers.append(key, value);
+            }
+        }
+        if let Some(path) = collected.redirect {
+            if let Ok(path) = header::HeaderValue::try_from(path) {
+                res_parts.status = Some(StatusCode::FOUND);
+                res_parts.headers.insert(header::LOCATION, path);
+            }
+        }
+    }
 }
 
 /// Provides an easy way to redirect the user from within a server function. Mimicing the Remix `redirect()`,
@@ -257,11 +290,16 @@ pub fn handle_server_fns_with_context(
                                 }
                             }
                         }
-                        Err(e) => HttpResponse::InternalServerError()
-                            .body(e.to_string()),
+                        // the client decodes the error that was returned
+                        Err(e) => HttpResponse::build(
+                            StatusCode::from_u16(e.status)
+                                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                        )
+                        .content_type(e.payload.content_type())
+                        .body(e.payload.into_bytes()),
                     }
                 } else {
-                    HttpResponse::BadRequest().body(format!(
+                    HttpResponse::NotFound().body(format!(
                         "Could not find a server function at the route {:?}. \
                          \n\nIt's likely that you need to call \
                          ServerFn::register() on the server function type, \
@@ -705,6 +743,7 @@ fn provide_contexts(
     provide_context(cx, RouterIntegrationContext::new(integration));
     provide_context(cx, MetaContext::new());
     provide_context(cx, res_options);
+    provide_context(cx, ResponseMetadata::default());
     provide_context(cx, req.clone());
     provide_server_redirect(cx, move |path| redirect(cx, path));
 }
@@ -761,8 +800,14 @@ async fn build_stream_response(
     scope: ScopeId,
 ) -> HttpResponse {
     let cx = leptos::Scope { runtime, id: scope };
-    let (head, tail) =
-        html_parts(options, use_context::<MetaContext>(cx).as_ref());
+    let (head, tail) = html_parts(
+        options,
+        use_context::<MetaContext>(cx).as_ref(),
+        cx.script_nonce().as_deref(),
+    );
+    if let Some(metadata) = use_context::<ResponseMetadata>(cx) {
+        res_options.apply_metadata(metadata.collect());
+    }
 
     let mut stream = Box::pin(
         futures::stream::once(async move { head.clone() })
@@ -816,8 +861,14 @@ async fn render_app_async_helper(
             additional_context,
         );
 
+    let metadata =
+        use_context::<ResponseMetadata>(leptos::Scope { runtime, id: scope });
+
     let html = build_async_response(stream, options, runtime, scope).await;
 
+    if let Some(metadata) = metadata {
+        res_options.apply_metadata(metadata.collect());
+    }
     let res_options = res_options.0.read();
 
     let (status, mut headers) =
@@ -21,7 +21,7 @@ use futures::{
     Future, SinkExt, Stream, StreamExt,
 };
 use http::{header, method::Method, uri::Uri, version::Version, Response};
-use hyper::body;
+use hyper::body::{self, HttpBody};
 use leptos::{
     leptos_server::{server_fn_by_path, Payload},
     ssr::*,
@@ -31,7 +31,7 @@ use leptos_integration_utils::{build_async_response, html_parts};
 use leptos_meta::{generate_head_metadata, MetaContext};
 use leptos_router::*;
 use parking_lot::RwLock;
-use std::{io, pin::Pin, sync::Arc};
+use std::{collections::HashSet, io, pin::Pin, sync::Arc};
 use tokio::task::{spawn_blocking, LocalSet};
 
 /// A struct to hold the parts of the incoming Request. Since `http::Request` isn't cloneable, we're forced
@@ -93,6 +93,38 @@ impl ResponseOptions {
         let res_parts = &mut *writeable;
         res_parts.headers.append(key, value);
     }
+    /// Applies the status, headers, and redirect that were set on a
+    /// [ResponseMetadata] while rendering. Headers and statuses that aren't
+    /// valid are skipped.
+    pub fn apply_metadata(&self, collected: CollectedResponse) {
+        let mut writeable = self.0.write();
+        let res_parts = &mut *writeable;
+        if let Some(status) =
+            collected.status.and_then(|s| StatusCode::from_u16(s).ok())
+        {
+            res_parts.status = Some(status);
+        }
+        let mut seen = HashSet::new();
+        for (key, value) in collected.headers {
+            let (Ok(key), Ok(value)) =
+                (HeaderName::try_from(key), HeaderValue::try_from(value))
+            else {
+                continue;
+            };
+            // this may be applied more than once for the same response
+            if seen.insert(key.clone()) {
+                res_parts.headers.insert(key, value);
+            } else {
+                res_parts.headers.append(key, value);
+            }
+        }
+        if let Some(path) = collected.redirect {
+            if let Ok(path) = HeaderValue::try_from(path) {
+                res_parts.status = Some(StatusCode::FOUND);
+                res_parts.headers.insert(header::LOCATION, path);
+            }
+        }
+    }
 }
 
 /// Provides an easy way to redirect the user from within a server function. Mimicing the Remix `redirect()`,
@@ -124,6 +156,38 @@ pub async fn generate_request_parts(req: Request<Body>) -> RequestParts {
     }
 }
 
+// Like `generate_request_parts`, for the call of a server function. A
+// `multipart/form-data` upload is only read up to its limit, so that the
+// function can reject a larger one without the whole of it being buffered.
+async fn generate_server_fn_request_parts(req: Request<Body>) -> RequestParts {
+    let (parts, mut body) = req.into_parts();
+    let multipart = parts
+        .headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("multipart/form-data"));
+    let body = if multipart {
+        let limit = leptos::server_fn::multipart::upload_limit();
+        let mut data = Vec::new();
+        while let Some(Ok(chunk)) = body.data().await {
+            data.extend_from_slice(&chunk);
+            if data.len() > limit {
+                break;
+            }
+        }
+        data.into()
+    } else {
+        body::to_bytes(body).await.unwrap_or_default()
+    };
+    RequestParts {
+        method: parts.method,
+        uri: parts.uri,
+        headers: parts.headers,
+        version: parts.version,
+        body,
+    }
+}
+
 /// An Axum handlers to listens for a request with Leptos server function arguments in the body,
 /// run the server function if found, and return the resulting [Response].
 ///
@@ -218,7 +282,8 @@ async fn handle_server_fns_inner(
 
                             additional_context(cx);
 
-                            let req_parts = generate_request_parts(req).await;
+                            let req_parts =
+                                generate_server_fn_request_parts(req).await;
                             // Add this so we can get details about the Request
                             provide_context(cx, req_parts.clone());
                             // Add this so that we can set headers and status of the response
@@ -308,13 +373,18 @@ async fn handle_server_fns_inner(
                                             .body(Full::from(data)),
                                     }
                                 }
+                                // the client decodes the error that was returned
                                 Err(e) => Response::builder()
-                                    .status(StatusCode::INTERNAL_SERVER_ERROR)
-                                    .body(Full::from(e.to_string())),
+                                    .status(e.status)
+                                    .header(
+                                        "Content-Type",
+                                        e.payload.content_type(),
+                                    )
+                                    .body(Full::from(e.payload.into_bytes())),
                             }
                         } else {
                             Response::builder()
-                                .status(StatusCode::BAD_REQUEST)
+                                .status(StatusCode::NOT_FOUND)
                                 .body(Full::from(format!(
                                     "Could not find a server function at the \
                                      route {fn_name}. \n\nIt's likely that \
@@ -623,8 +693,13 @@ async fn forward_stream(
     mut tx: Sender<String>,
 ) {
     let cx = Scope { runtime, id: scope };
-    let (head, tail) =
-        html_parts(options, use_context::<MetaContext>(cx).as_ref());
+    let (head, tail) = html_parts(
+        options,
+        use_context::<MetaContext>(cx).as_ref(),
+        cx.script_nonce().as_deref(),
+    );
+    let metadata = use_context::<ResponseMetadata>(cx).unwrap_or_default();
+    res_options2.apply_metadata(metadata.collect());
 
     _ = tx.send(head).await;
     let mut shell = Box::pin(bundle);
@@ -640,6 +715,8 @@ async fn forward_stream(
 
     let mut writable = res_options2.0.write();
     *writable = new_res_parts;
+    drop(writable);
+    res_options2.apply_metadata(metadata.collect());
 
     runtime.dispose();
 
@@ -763,6 +840,7 @@ fn provide_contexts(
     provide_context(cx, MetaContext::new());
     provide_context(cx, req_parts);
     provide_context(cx, default_res_options);
+    provide_context(cx, ResponseMetadata::default());
     provide_server_redirect(cx, move |path| redirect(cx, path));
 }
 
@@ -922,6 +1000,8 @@ where
                                             let cx = leptos::Scope { runtime, id: scope };
                                             let res_options =
                                                 use_context::<ResponseOptions>(cx).unwrap();
+                                            let metadata =
+                                                use_context::<ResponseMetadata>(cx).unwrap_or_default();
 
                                             let html = build_async_response(stream, &options, runtime, scope).await;
 
@@ -929,6 +1009,8 @@ where
 
                                             let mut writable = res_options2.0.write();
                                             *writable = new_res_parts;
+                                            drop(writable);
+                                            res_options2.apply_metadata(metadata.collect());
 
                                             _ = tx.send(html);
                                         })
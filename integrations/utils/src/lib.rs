@@ -1,11 +1,12 @@
 use futures::{Stream, StreamExt};
-use leptos::{use_context, RuntimeId, ScopeId};
+use leptos::{leptos_dom::ssr::escape_attr, use_context, RuntimeId, ScopeId};
 use leptos_config::LeptosOptions;
 use leptos_meta::MetaContext;
 
 pub fn html_parts(
     options: &LeptosOptions,
     meta: Option<&MetaContext>,
+    nonce: Option<&str>,
 ) -> (String, &'static str) {
     let pkg_path = &options.site_pkg_dir;
     let output_name = &options.output_name;
@@ -18,13 +19,17 @@ pub fn html_parts(
         wasm_output_name.push_str("_bg");
     }
 
+    let nonce = nonce
+        .map(|nonce| format!(r#" nonce="{}""#, escape_attr(&nonce)))
+        .unwrap_or_default();
+
     let site_ip = &options.site_addr.ip().to_string();
     let reload_port = options.reload_port;
 
     let leptos_autoreload = match std::env::var("LEPTOS_WATCH").is_ok() {
         true => format!(
             r#"
-                <script crossorigin="">(function () {{
+                <script crossorigin=""{nonce}>(function () {{
                     {}
                     var ws = new WebSocket('ws://{site_ip}:{reload_port}/live_reload');
                     ws.onmessage = (ev) => {{
@@ -64,7 +69,7 @@ pub fn html_parts(
                     <meta name="viewport" content="width=device-width, initial-scale=1"/>
                     <link rel="modulepreload" href="/{pkg_path}/{output_name}.js">
                     <link rel="preload" href="/{pkg_path}/{wasm_output_name}.wasm" as="fetch" type="application/wasm" crossorigin="">
-                    <script type="module">import init, {{ hydrate }} from '/{pkg_path}/{output_name}.js'; init('/{pkg_path}/{wasm_output_name}.wasm').then(hydrate);</script>
+                    <script type="module"{nonce}>import init, {{ hydrate }} from '/{pkg_path}/{output_name}.js'; init('/{pkg_path}/{wasm_output_name}.wasm').then(hydrate);</script>
                     {leptos_autoreload}
                     "#
     );
@@ -85,8 +90,11 @@ pub async fn build_async_response(
     }
 
     let cx = leptos::Scope { runtime, id: scope };
-    let (head, tail) =
-        html_parts(options, use_context::<MetaContext>(cx).as_ref());
+    let (head, tail) = html_parts(
+        options,
+        use_context::<MetaContext>(cx).as_ref(),
+        cx.script_nonce().as_deref(),
+    );
 
     // in async, we load the meta content *now*, after the suspenses have resolved
     let meta = use_context::<MetaContext>(cx);
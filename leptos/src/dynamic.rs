@@ -0,0 +1,89 @@
+use leptos::component;
+use leptos_dom::{IntoView, View};
+use leptos_reactive::{create_memo, signal_prelude::*, Scope};
+use std::{fmt, rc::Rc};
+
+/// A component stored as a value, so that it can be kept in a signal and
+/// rendered with [`Dynamic`].
+///
+/// Two `DynComponent`s are equal if they are clones of the same one.
+#[derive(Clone)]
+pub struct DynComponent(Rc<dyn Fn(Scope) -> View>);
+
+impl DynComponent {
+    /// Wraps a function that renders a view, usually a component.
+    pub fn new<F, IV>(component: F) -> Self
+    where
+        F: Fn(Scope) -> IV + 'static,
+        IV: IntoView,
+    {
+        Self(Rc::new(move |cx| component(cx).into_view(cx)))
+    }
+}
+
+impl PartialEq for DynComponent {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for DynComponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynComponent").finish_non_exhaustive()
+    }
+}
+
+impl IntoView for DynComponent {
+    fn into_view(self, cx: Scope) -> View {
+        // the component's own reads shouldn't make the parent swap it
+        cx.untrack(|| (self.0)(cx))
+    }
+}
+
+/// Renders whichever component `component` currently returns.
+///
+/// Each instance is created in its own child scope. When `component`
+/// changes, the previous instance's scope is disposed, running its cleanups,
+/// and its nodes are replaced by the new one's, however many root nodes
+/// either of them has. Returning the same [`DynComponent`] again keeps the
+/// current instance.
+///
+/// ```rust
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// #[component]
+/// fn Chart(cx: Scope) -> impl IntoView {
+///     view! { cx, <canvas/> }
+/// }
+///
+/// #[component]
+/// fn Table(cx: Scope) -> impl IntoView {
+///     view! { cx, <table/> }
+/// }
+///
+/// let chart = DynComponent::new(|cx| view! { cx, <Chart/> });
+/// let table = DynComponent::new(|cx| view! { cx, <Table/> });
+/// let (shown, set_shown) = create_signal(cx, chart);
+///
+/// view! { cx,
+///   <button on:click=move |_| set_shown.set(table.clone())>"Show table"</button>
+///   <Dynamic component=move || shown.get()/>
+/// }
+/// # ;
+/// # });
+/// ```
+#[component]
+pub fn Dynamic<C>(
+    /// The scope the component is running in
+    cx: Scope,
+    /// A closure that returns the component to render
+    component: C,
+) -> impl IntoView
+where
+    C: Fn() -> DynComponent + 'static,
+{
+    // setting the same component again doesn't recreate it
+    let component = create_memo(cx, move |_| component());
+
+    move || component.get()
+}
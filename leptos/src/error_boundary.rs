@@ -1,8 +1,11 @@
 use crate::Children;
-use leptos_dom::{Errors, IntoView};
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+use leptos_dom::IntoAttribute;
+use leptos_dom::{Errors, HydrationCtx, IntoView};
 use leptos_macro::{component, view};
 use leptos_reactive::{
-    create_rw_signal, provide_context, signal_prelude::*, RwSignal, Scope,
+    create_memo, create_rw_signal, provide_context, signal_prelude::*,
+    RwSignal, Scope,
 };
 
 /// When you render a `Result<_, _>` in your view, in the `Err` case it will
@@ -10,6 +13,15 @@ use leptos_reactive::{
 /// This component lets you define a fallback that should be rendered in that
 /// error case, allowing you to handle errors within a section of the interface.
 ///
+/// The children are shown again once every error has been removed, for
+/// example when a resource that failed is refetched successfully. If the
+/// fallback was shown on the server, the boundary starts out with the
+/// server's error messages while hydrating (see [ServerRenderedError]), so
+/// the fallback is not replaced by the children before they have reported
+/// errors of their own.
+///
+/// [ServerRenderedError]: crate::ServerRenderedError
+///
 /// ```
 /// # use leptos_reactive::*;
 /// # use leptos_macro::*;
@@ -40,23 +52,49 @@ where
     F: Fn(Scope, RwSignal<Errors>) -> IV + 'static,
     IV: IntoView,
 {
+    let id = HydrationCtx::peek().to_string();
     let errors: RwSignal<Errors> = create_rw_signal(cx, Errors::default());
 
+    #[cfg(all(
+        target_arch = "wasm32",
+        any(feature = "csr", feature = "hydrate")
+    ))]
+    if HydrationCtx::is_hydrating() {
+        let server_errors = leptos_dom::document()
+            .query_selector(&format!(
+                "leptos-error-boundary[data-error-boundary=\"{id}\"]"
+            ))
+            .ok()
+            .flatten()
+            .and_then(|el| el.get_attribute("data-errors"))
+            .and_then(|errors| Errors::from_serialized(&errors));
+        if let Some(server_errors) = server_errors {
+            errors.set_untracked(server_errors);
+        }
+    }
+
     provide_context(cx, errors);
 
     // Run children so that they render and execute resources
     let children = children(cx);
 
-    move || {
-        match errors.with(Errors::is_empty) {
-            true => children.clone().into_view(cx),
-            false => view! { cx,
-                <>
-                    {fallback(cx, errors)}
-                    <leptos-error-boundary style="display: none">{children.clone()}</leptos-error-boundary>
-                </>
-            }
-            .into_view(cx),
+    let has_errors =
+        create_memo(cx, move |_| errors.with(|errors| !errors.is_empty()));
+
+    move || match has_errors.get() {
+        false => children.clone().into_view(cx),
+        true => view! { cx,
+            <>
+                {fallback(cx, errors)}
+                <leptos-error-boundary
+                    style="display: none"
+                    data-error-boundary=id.clone()
+                    data-errors=errors.with_untracked(Errors::to_serialized)
+                >
+                    {children.clone()}
+                </leptos-error-boundary>
+            </>
         }
+        .into_view(cx),
     }
 }
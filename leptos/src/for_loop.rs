@@ -1,13 +1,21 @@
-use leptos_dom::IntoView;
+use leptos_dom::{Each, EachAnimation, IntoView};
 use leptos_macro::component;
-use leptos_reactive::Scope;
-use std::hash::Hash;
+use leptos_reactive::{
+    create_memo, create_rw_signal, on_cleanup, RwSignal, Scope, Signal,
+    SignalGet, SignalSet, SignalWith, SignalWithUntracked,
+};
+use std::{cell::RefCell, collections::HashMap, hash::Hash, rc::Rc};
 
 /// Iterates over children and displays them, keyed by the `key` function given.
 ///
 /// This is much more efficient than naively iterating over nodes with `.iter().map(|n| view! { cx,  ... })...`,
 /// as it avoids re-creating DOM nodes that are not being changed.
 ///
+/// Keyed lists are the right choice when items have an identity that
+/// survives reordering, inserting and removing, like a list of todos. For
+/// data whose items have no identity of their own, like log lines or the
+/// points of a chart, keying only adds overhead: use [`EachIndex`] instead.
+///
 /// ```
 /// # use leptos::*;
 ///
@@ -50,7 +58,137 @@ pub fn For<IF, I, T, EF, N, KF, K>(
     key: KF,
     /// The view that will be displayed for each item.
     view: EF,
+    /// Animates the rows as they're moved, added and removed in the browser.
+    #[prop(optional)]
+    animation: Option<EachAnimation>,
+) -> impl IntoView
+where
+    IF: Fn() -> I + 'static,
+    I: IntoIterator<Item = T>,
+    EF: Fn(Scope, T) -> N + 'static,
+    N: IntoView,
+    KF: Fn(&T) -> K + 'static,
+    K: Eq + Hash + 'static,
+    T: 'static,
+{
+    animated(leptos_dom::Each::new(each, key, view), animation).into_view(cx)
+}
+
+/// Like [`For`], but each row receives its item as a [`Signal`].
+///
+/// When `each` changes, an item whose key is already being displayed
+/// updates that row's signal instead of being dropped, so the row can be
+/// edited in place without re-creating its DOM nodes. Rows whose keys
+/// disappear are removed and their scopes disposed.
+///
+/// ```
+/// # use leptos::*;
+///
+/// #[derive(Clone, Debug, PartialEq, Eq)]
+/// struct Todo {
+///     id: usize,
+///     title: String,
+/// }
+///
+/// #[component]
+/// fn Todos(cx: Scope) -> impl IntoView {
+///     let (todos, _set_todos) = create_signal::<Vec<Todo>>(cx, vec![]);
+///
+///     view! {
+///       cx,
+///       <ul>
+///         <ForEach
+///           each=move || todos.get()
+///           key=|todo| todo.id
+///           // renaming a todo only updates its title
+///           children=move |cx, todo| {
+///             view! {
+///               cx,
+///               <li>{move || todo.with(|todo| todo.title.clone())}</li>
+///             }
+///           }
+///         />
+///       </ul>
+///     }
+/// }
+/// ```
+#[component(transparent)]
+pub fn ForEach<IF, I, T, CF, N, KF, K>(
+    cx: Scope,
+    /// Items over which the component should iterate.
+    each: IF,
+    /// A key function that will be applied to each item.
+    key: KF,
+    /// The view that will be displayed for each item.
+    children: CF,
+    /// Animates the rows as they're moved, added and removed in the browser.
+    #[prop(optional)]
+    animation: Option<EachAnimation>,
 ) -> impl IntoView
+where
+    IF: Fn() -> I + 'static,
+    I: IntoIterator<Item = T>,
+    CF: Fn(Scope, Signal<T>) -> N + 'static,
+    N: IntoView,
+    KF: Fn(&T) -> K + 'static,
+    K: Eq + Hash + Clone + 'static,
+    T: Clone + PartialEq + 'static,
+{
+    let key = Rc::new(key);
+    let rows = Rc::new(RefCell::new(HashMap::<K, RwSignal<T>>::new()));
+
+    let items = {
+        let key = Rc::clone(&key);
+        let rows = Rc::clone(&rows);
+        move || {
+            let items = each().into_iter().collect::<Vec<_>>();
+
+            let changed = {
+                let rows = rows.borrow();
+                items
+                    .iter()
+                    .filter_map(|item| {
+                        rows.get(&key(item))
+                            .filter(|row| {
+                                !row.with_untracked(|current| current == item)
+                            })
+                            .map(|row| (*row, item.clone()))
+                    })
+                    .collect::<Vec<_>>()
+            };
+            for (row, item) in changed {
+                row.set(item);
+            }
+
+            items
+        }
+    };
+
+    let row = {
+        let key = Rc::clone(&key);
+        move |cx, item: T| {
+            let item_key = key(&item);
+            let row = create_rw_signal(cx, item);
+            rows.borrow_mut().insert(item_key.clone(), row);
+            on_cleanup(cx, {
+                let rows = Rc::clone(&rows);
+                move || {
+                    rows.borrow_mut().remove(&item_key);
+                }
+            });
+
+            children(cx, row.into())
+        }
+    };
+
+    let each = leptos_dom::Each::new(items, move |item: &T| key(item), row);
+    animated(each, animation).into_view(cx)
+}
+
+fn animated<IF, I, T, EF, N, KF, K>(
+    each: Each<IF, I, T, EF, N, KF, K>,
+    animation: Option<EachAnimation>,
+) -> Each<IF, I, T, EF, N, KF, K>
 where
     IF: Fn() -> I + 'static,
     I: IntoIterator<Item = T>,
@@ -60,5 +198,74 @@ where
     K: Eq + Hash + 'static,
     T: 'static,
 {
-    leptos_dom::Each::new(each, key, view).into_view(cx)
+    match animation {
+        Some(animation) => each.animated(animation),
+        None => each,
+    }
+}
+
+/// Displays one row for each index of a list, where each row receives the
+/// item at its index as a [`Signal`].
+///
+/// Rows are only created or disposed when the length of the list changes:
+/// growing the list adds rows at the end, and shrinking it disposes the
+/// rows at the end. Changing an item only notifies the row at its index,
+/// and each row only clones its own item, never the whole list.
+///
+/// Because rows follow indices, not items, inserting at or removing from
+/// the front of the list updates every row after that point. If items have
+/// an identity of their own, use the keyed [`For`] or [`ForEach`] instead.
+///
+/// ```
+/// # use leptos::*;
+///
+/// #[component]
+/// fn Log(cx: Scope) -> impl IntoView {
+///     let (lines, set_lines) = create_signal(cx, Vec::<String>::new());
+///
+///     view! {
+///       cx,
+///       <button on:click=move |_| set_lines.update(|lines| lines.push("Clicked".into()))>
+///         "Log"
+///       </button>
+///       <ol>
+///         <EachIndex
+///           each=lines
+///           children=move |cx, line| {
+///             view! { cx, <li>{move || line.get()}</li> }
+///           }
+///         />
+///       </ol>
+///     }
+/// }
+/// ```
+#[component(transparent)]
+pub fn EachIndex<T, CF, N>(
+    cx: Scope,
+    /// The list over which the component should iterate.
+    #[prop(into)]
+    each: Signal<Vec<T>>,
+    /// The view that will be displayed for each index.
+    children: CF,
+) -> impl IntoView
+where
+    CF: Fn(Scope, Signal<T>) -> N + 'static,
+    N: IntoView,
+    T: Clone + PartialEq + 'static,
+{
+    let len = create_memo(cx, move |_| each.with(Vec::len));
+
+    let row = move |cx, idx: usize| {
+        let item = create_memo(cx, move |prev: Option<&T>| {
+            // a row can be notified that the list shrank before it is
+            // disposed, in which case it keeps its last item
+            each.with(|items| items.get(idx).cloned())
+                .or_else(|| prev.cloned())
+                .expect("a row to be created for an index in the list")
+        });
+
+        children(cx, item.into())
+    };
+
+    leptos_dom::Each::new(move || 0..len.get(), |idx| *idx, row).into_view(cx)
 }
@@ -0,0 +1,96 @@
+use crate::DynComponent;
+use leptos_dom::{IntoView, View};
+use leptos_reactive::{create_lazy_resource, Lazy, Scope};
+use std::{error::Error, fmt, future::Future, sync::Arc};
+
+/// Creates a component that's loaded by `loader` the first time it's
+/// rendered, e.g., from a chunk that's split out of the main wasm binary
+/// because it's rarely used.
+///
+/// Until it has loaded, the component suspends the nearest `<Suspense/>` or
+/// `<Transition/>`, which shows its fallback. The loader is only called
+/// once, and the component is rendered right away wherever it's used after
+/// that. If the loader fails, the error is reported to the nearest
+/// `<ErrorBoundary/>`.
+///
+/// The component isn't loaded on the server, which renders the fallback of
+/// the `<Suspense/>` it's in. The client loads and renders it once it has
+/// hydrated the rest of the page.
+///
+/// ```rust
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// #[component]
+/// fn Settings(cx: Scope) -> impl IntoView {
+///     view! { cx, <form>"..."</form> }
+/// }
+///
+/// let settings = lazy_component(|| async {
+///     // e.g., wait for the chunk with the component to be fetched
+///     Ok::<_, ServerFnError>(DynComponent::new(
+///         |cx| view! { cx, <Settings/> },
+///     ))
+/// });
+///
+/// # // like the resource examples, this can't load outside a browser
+/// # if false {
+/// view! { cx,
+///   <Suspense fallback=|| "Loading...">
+///     {settings.clone()}
+///   </Suspense>
+/// }
+/// # ;
+/// # }
+/// # });
+/// ```
+pub fn lazy_component<F, Fu, E>(loader: F) -> LazyComponent
+where
+    F: FnOnce() -> Fu + 'static,
+    Fu: Future<Output = Result<DynComponent, E>> + 'static,
+    E: Error + Send + Sync + 'static,
+{
+    LazyComponent(Lazy::new(|| async move {
+        loader().await.map_err(|error| LoadError(Arc::new(error)))
+    }))
+}
+
+/// A component that's loaded the first time it's rendered, created with
+/// [lazy_component]. Clones share the component, which is only loaded once.
+#[derive(Clone, Debug)]
+pub struct LazyComponent(Lazy<Result<DynComponent, LoadError>>);
+
+impl IntoView for LazyComponent {
+    fn into_view(self, cx: Scope) -> View {
+        // a resource would fetch the value again, if only from the finished
+        // future, and render the component a second time when it "loads"
+        if let Some(component) = self.0.get() {
+            return component.into_view(cx);
+        }
+
+        let component = create_lazy_resource(cx, &self.0);
+
+        (move || component.read(cx)).into_view(cx)
+    }
+}
+
+/// The error a [LazyComponent]'s loader failed with.
+#[derive(Clone)]
+pub struct LoadError(Arc<dyn Error + Send + Sync>);
+
+impl fmt::Debug for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.0)
+    }
+}
@@ -83,6 +83,9 @@
 //! # Feature Flags
 //! - `csr` (*Default*) Client-side rendering: Generate DOM nodes in the browser
 //! - `ssr` Server-side rendering: Generate an HTML string (typically on the server)
+//! - `ssr-send` Like `ssr`, but makes the streams of serialized resources `Send`, so they can be
+//!   polled with `tokio::spawn`. Only the streams are `Send`: fetchers are still run with
+//!   `spawn_local` on the thread that rendered the page, which has to keep running them.
 //! - `hydrate` Hydration: use this to add interactivity to an SSRed Leptos app
 //! - `stable` By default, Leptos requires `nightly` Rust, which is what allows the ergonomics
 //!   of calling signals as functions. If you need to use `stable`, you will need to call `.get()`
@@ -149,35 +152,51 @@ pub use leptos_config::{self, get_configuration, LeptosOptions};
 )))]
 /// Utilities for server-side rendering HTML.
 pub mod ssr {
-    pub use leptos_dom::{ssr::*, ssr_in_order::*};
+    pub use leptos_dom::{ssg::*, ssr::*, ssr_in_order::*};
 }
 pub use leptos_dom::{
-    self, create_node_ref, debug_warn, document, error, ev,
+    self, create_node_ref, debug_warn, define_custom_element,
+    define_custom_element_with, directives, document, error, ev,
     helpers::{
-        event_target, event_target_checked, event_target_value,
-        request_animation_frame, request_idle_callback, set_interval,
-        set_timeout, window_event_listener,
+        dispatch_custom_event, document_event_listener, event_target,
+        event_target_checked, event_target_value, request_animation_frame,
+        request_idle_callback, set_interval, set_timeout,
+        window_event_listener, AnimationFrameRequestHandle,
+        EventListenerHandle, IntervalHandle, TimeoutHandle,
     },
-    html, log, math, mount_to, mount_to_body, svg, warn, window, Attribute,
-    Class, Errors, Fragment, HtmlElement, IntoAttribute, IntoClass,
-    IntoProperty, IntoView, NodeRef, Property, View,
+    html, hydrate_at, hydrate_islands, hydrate_to, island,
+    island_with_strategy, log, math, mount_to, mount_to_body, on_mount,
+    on_unmount, svg, warn, window, Attribute, BindGroup, BindValue,
+    BoundingRect, Class, CustomElement, CustomElementOptions, EachAnimation,
+    ElementRef, Errors, Fragment, HtmlElement, HydrationStrategy,
+    IntoAttribute, IntoClass, IntoProperty, IntoStyle, IntoView, MountHandle,
+    NodeRef, Property, ServerRenderedError, Style, View,
 };
 pub use leptos_macro::*;
 pub use leptos_reactive::*;
 pub use leptos_server::{
     self, create_action, create_multi_action, create_server_action,
-    create_server_multi_action, Action, MultiAction, ServerFn, ServerFnError,
+    create_server_multi_action, Action, MultiAction, NoCustomError, ServerFn,
+    ServerFnCustomError, ServerFnError, UploadedFile,
 };
 pub use server_fn::{self, ServerFn as _};
 pub use typed_builder;
+mod dynamic;
+pub use dynamic::*;
 mod error_boundary;
 pub use error_boundary::*;
 mod for_loop;
+mod lazy;
+mod portal;
 mod show;
 pub use for_loop::*;
+pub use lazy::*;
+pub use portal::*;
 pub use show::*;
 mod suspense;
 pub use suspense::*;
+mod switch;
+pub use switch::*;
 mod transition;
 #[cfg(debug_assertions)]
 #[doc(hidden)]
@@ -0,0 +1,77 @@
+use crate::Children;
+use leptos::component;
+use leptos_dom::IntoView;
+use leptos_reactive::Scope;
+
+/// Renders its children into another element, `document.body` by default,
+/// instead of where the `<Portal/>` is. This lets modals, tooltips and toasts
+/// escape ancestors with `overflow: hidden` or their own stacking context.
+///
+/// The children are still part of the view they're written in: they run in
+/// a child scope of it, so they can use its contexts, and they're removed
+/// from the element they were rendered into when it is disposed. They're
+/// wrapped in a `<div>`, which is appended to that element.
+///
+/// Portals are only rendered in the browser. The server renders nothing
+/// where a portal is, and when the page is hydrated, its children are
+/// created from scratch instead of hydrated.
+///
+/// ```rust
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let (open, set_open) = create_signal(cx, false);
+///
+/// view! { cx,
+///   <div style="overflow: hidden">
+///     <button on:click=move |_| set_open.set(true)>"Open"</button>
+///     <Show when=move || open.get() fallback=|_| ()>
+///       <Portal>
+///         <dialog open>"Rendered at the end of the body"</dialog>
+///       </Portal>
+///     </Show>
+///   </div>
+/// }
+/// # ;
+/// # });
+/// ```
+#[component]
+pub fn Portal(
+    /// The scope the component is running in
+    cx: Scope,
+    /// The element the children are rendered into, `document.body` if it's
+    /// left out
+    #[prop(optional)]
+    mount: Option<web_sys::Element>,
+    /// What gets rendered into `mount`
+    children: Children,
+) -> impl IntoView {
+    #[cfg(all(
+        target_arch = "wasm32",
+        any(feature = "csr", feature = "hydrate")
+    ))]
+    {
+        use leptos_dom::{document, HydrationCtx, Mountable};
+
+        let mount = mount.unwrap_or_else(|| document().body().unwrap().into());
+
+        // the server didn't render the children, so they don't claim any
+        // nodes, and the keys of what comes after the portal stay the same
+        let view = HydrationCtx::with_key(HydrationCtx::peek(), || {
+            HydrationCtx::client_render(|| children(cx).into_view(cx))
+        });
+
+        let container = document().create_element("div").unwrap();
+        _ = container.append_child(&view.get_mountable_node());
+        _ = mount.append_child(&container);
+
+        leptos_reactive::on_cleanup(cx, move || container.remove());
+    }
+
+    #[cfg(not(all(
+        target_arch = "wasm32",
+        any(feature = "csr", feature = "hydrate")
+    )))]
+    {
+        _ = (cx, mount, children);
+    }
+}
@@ -1,6 +1,10 @@
 use leptos::component;
-use leptos_dom::{Fragment, IntoView};
-use leptos_reactive::{create_memo, signal_prelude::*, Scope};
+use leptos_dom::{Fragment, IntoView, View};
+use leptos_reactive::{create_memo, signal_prelude::*, Scope, ScopeDisposer};
+use std::{cell::RefCell, rc::Rc};
+
+// the view of a branch, and the disposer of the scope it was created in
+type Branch = (View, ScopeDisposer);
 
 /// A component that will show its children when the `when` condition is `true`,
 /// and show the fallback when it is `false`, without rerendering every time
@@ -11,6 +15,11 @@ use leptos_reactive::{create_memo, signal_prelude::*, Scope};
 /// words, if you want to show the children if `when` is true and noting otherwise—use
 /// `fallback=|_| ()` (i.e., a fallback function that returns the unit type `()`).
 ///
+/// Each branch is created in a child scope the first time it's shown, so the
+/// fallback is never created if `when` stays `true`. When the condition
+/// changes, the branch that's hidden is disposed, unless `keep_alive` is set,
+/// in which case it's kept out of the document and shown again as it was.
+///
 /// ```rust
 /// # use leptos_reactive::*;
 /// # use leptos_macro::*;
@@ -38,6 +47,10 @@ pub fn Show<F, W, IV>(
     when: W,
     /// A closure that returns what gets rendered if the when statement is false
     fallback: F,
+    /// Keeps the branch that was hidden, along with its nodes, so that it can
+    /// be shown again as it was instead of being created from scratch.
+    #[prop(optional)]
+    keep_alive: bool,
 ) -> impl IntoView
 where
     W: Fn() -> bool + 'static,
@@ -46,8 +59,49 @@ where
 {
     let memoized_when = create_memo(cx, move |_| when());
 
-    move || match memoized_when.get() {
-        true => children(cx).into_view(cx),
-        false => fallback(cx).into_view(cx),
+    // the fallback and the children, when they have been created, with the
+    // scopes they were created in
+    let branches: Rc<RefCell<[Option<Branch>; 2]>> = Default::default();
+
+    move || {
+        let shown = memoized_when.get();
+        let mut branches = branches.borrow_mut();
+
+        let hidden = &mut branches[usize::from(!shown)];
+        if !keep_alive {
+            if let Some((_, disposer)) = hidden.take() {
+                disposer.dispose();
+            }
+        }
+        // moves the hidden branch's nodes out of the document, so they're
+        // left alone when the branch that's shown is mounted in their place
+        #[cfg(all(
+            target_arch = "wasm32",
+            any(feature = "csr", feature = "hydrate")
+        ))]
+        if let Some((view, _)) = hidden {
+            _ = leptos_dom::Mountable::get_mountable_node(view);
+        }
+
+        let (view, _) = branches[usize::from(shown)].get_or_insert_with(|| {
+            cx.run_child_scope(|cx| {
+                cx.untrack(|| {
+                    let view = if shown {
+                        children(cx).into_view(cx)
+                    } else {
+                        fallback(cx).into_view(cx)
+                    };
+
+                    // a fragment can be moved out of the document and back
+                    if keep_alive {
+                        Fragment::from(view).into_view(cx)
+                    } else {
+                        view
+                    }
+                })
+            })
+        });
+
+        view.clone()
     }
 }
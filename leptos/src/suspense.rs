@@ -2,7 +2,7 @@ use cfg_if::cfg_if;
 use leptos_dom::{DynChild, Fragment, HydrationCtx, IntoView};
 use leptos_macro::component;
 use leptos_reactive::{provide_context, Scope, SuspenseContext};
-use std::rc::Rc;
+use std::{rc::Rc, time::Duration};
 
 /// If any [Resources](leptos_reactive::Resource) are read in the `children` of this
 /// component, it will show the `fallback` while they are loading. Once all are resolved,
@@ -57,6 +57,14 @@ pub fn Suspense<F, E>(
     fallback: F,
     /// Children will be displayed once all `async` [Resources](leptos_reactive::Resource) have resolved.
     children: Box<dyn Fn(Scope) -> Fragment>,
+    /// How long resources have to be loading before the fallback is shown in the
+    /// browser. If they resolve sooner, the fallback is never shown.
+    #[prop(optional)]
+    fallback_delay: Duration,
+    /// Once the fallback is shown in the browser, it's kept for at least this long,
+    /// even if the resources resolve sooner.
+    #[prop(optional)]
+    fallback_min_duration: Duration,
 ) -> impl IntoView
 where
     F: Fn() -> E + 'static,
@@ -69,25 +77,91 @@ where
 
     let orig_child = Rc::new(children);
 
-    let before_me = HydrationCtx::peek();
+    // the server renders the fallback right away, whatever the timing
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    let show_fallback = context.show_fallback(
+        cx,
+        leptos_reactive::FallbackTiming {
+            delay: fallback_delay,
+            min_duration: fallback_min_duration,
+        },
+    );
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    _ = (fallback_delay, fallback_min_duration);
+    // what was rendered last, and whether it was the fallback
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    let last_view = std::cell::RefCell::new(None::<(bool, leptos_dom::View)>);
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    let fallback = Rc::new(fallback);
+
+    // take the next offset among our siblings before nesting under it, so
+    // that sibling boundaries (e.g., in a loop) get distinct keys and each
+    // key is the boundary's path through the view
+    let before_me = HydrationCtx::id();
     let current_id = HydrationCtx::next_component();
 
+    // where the children's keys start, so that rendering them again once
+    // resources resolve gives them the keys the server did, no matter what
+    // has been rendered in the meantime
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    let children_key = std::cell::RefCell::new(None);
+
+    // if the server streams the children in after hydration has started, they
+    // are hydrated from that chunk once it lands and the resources resolve
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    let streamed = leptos_reactive::create_rw_signal(cx, false);
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    if cx.is_hydrating() {
+        use leptos_reactive::SignalSet;
+
+        HydrationCtx::on_streamed_chunk(&current_id.to_string(), move || {
+            streamed.set(true)
+        });
+    }
+
     let child = DynChild::new({
-        #[cfg(not(any(feature = "csr", feature = "hydrate")))]
         let current_id = current_id.clone();
         move || {
             cfg_if! {
                 if #[cfg(any(feature = "csr", feature = "hydrate"))] {
-                    if context.ready() {
-                        orig_child(cx).into_view(cx)
-                    } else {
-                        fallback().into_view(cx)
-                    }
+                    use leptos_reactive::SignalGet;
+
+                    let key = children_key
+                        .borrow_mut()
+                        .get_or_insert_with(HydrationCtx::peek)
+                        .clone();
+                    let none_pending = context.none_pending().get();
+                    let last = last_view.borrow_mut().take();
+                    let (is_fallback, view) = HydrationCtx::with_key(key, || {
+                        if show_fallback.get() {
+                            // a fallback that's held after the resources resolve
+                            // isn't created again
+                            match last {
+                                Some((true, view)) => (true, view),
+                                _ => (true, fallback().into_view(cx)),
+                            }
+                        } else if none_pending {
+                            streamed.get();
+                            let child = HydrationCtx::hydrate_streamed_chunk(
+                                &current_id.to_string(),
+                                || context.run_in_boundary(|| orig_child(cx).into_view(cx)),
+                            );
+                            context.children_rendered();
+                            (false, child)
+                        } else {
+                            // resources are loading, but the fallback is delayed,
+                            // so whatever was there stays
+                            last.unwrap_or_else(|| (false, ().into_view(cx)))
+                        }
+                    });
+                    *last_view.borrow_mut() = Some((is_fallback, view.clone()));
+                    view
                 } else {
                     use leptos_reactive::signal_prelude::*;
 
                     // run the child; we'll probably throw this away, but it will register resource reads
-                    let child = orig_child(cx).into_view(cx);
+                    let child = context
+                        .run_in_boundary(|| orig_child(cx).into_view(cx));
                     let after_original_child = HydrationCtx::id();
 
                     let initial = {
@@ -99,35 +173,58 @@ where
                         else {
                             let orig_child = Rc::clone(&orig_child);
 
+                            // local resources never load on the server, so if
+                            // they're all we're waiting for, the fragment resolves
+                            // right away with the fallback and the client loads them;
+                            // the same goes for resources still loading at the deadline
+                            let resolved_child = {
+                                let fallback = Rc::clone(&fallback);
+                                move |timed_out: bool| {
+                                    if timed_out || context.has_local_only() {
+                                        fallback().into_view(cx)
+                                    } else {
+                                        let orig_child = Rc::clone(&orig_child);
+                                        DynChild::new(move || orig_child(cx))
+                                            .into_view(cx)
+                                    }
+                                }
+                            };
+                            let resolved_child = Rc::new(resolved_child);
+
                             cx.register_suspense(
                                 context,
                                 &current_id.to_string(),
                                 // out-of-order streaming
                                 {
                                     let current_id = current_id.clone();
-                                    let orig_child = Rc::clone(&orig_child);
-                                    move || {
-                                        HydrationCtx::continue_from(current_id.clone());
-                                        DynChild::new(move || orig_child(cx))
-                                            .into_view(cx)
-                                            .render_to_string(cx)
-                                            .to_string()
+                                    let resolved_child = Rc::clone(&resolved_child);
+                                    move |timed_out| {
+                                        HydrationCtx::with_key(current_id.clone(), || {
+                                            resolved_child(timed_out)
+                                                .render_to_string(cx)
+                                                .to_string()
+                                        })
                                     }
                                 },
                                 // in-order streaming
                                 {
                                     let current_id = current_id.clone();
-                                    move || {
-                                        HydrationCtx::continue_from(current_id.clone());
-                                        DynChild::new(move || orig_child(cx))
-                                            .into_view(cx)
-                                            .into_stream_chunks(cx)
+                                    move |timed_out| {
+                                        HydrationCtx::with_key(current_id.clone(), || {
+                                            resolved_child(timed_out).into_stream_chunks(cx)
+                                        })
                                     }
                                 }
                             );
 
-                            // return the fallback for now, wrapped in fragment identifer
-                            fallback().into_view(cx)
+                            // in-order streaming waits for the fragment, so
+                            // there's no fallback to emit
+                            if cx.in_order_streaming() {
+                                ().into_view(cx)
+                            } else {
+                                // return the fallback for now, wrapped in fragment identifer
+                                fallback().into_view(cx)
+                            }
                         }
                     };
 
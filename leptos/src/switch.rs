@@ -0,0 +1,117 @@
+use crate::{Children, ChildrenFn};
+use leptos::component;
+use leptos_dom::{IntoView, Transparent, View};
+use leptos_reactive::{create_memo, signal_prelude::*, Scope, ScopeDisposer};
+use std::{cell::RefCell, rc::Rc};
+
+/// Shows the first of its [`Match`] children whose `when` condition is
+/// `true`, or the fallback if none of them are.
+///
+/// Only one branch is shown at a time, in its own child scope. The
+/// conditions are checked in order every time one of them changes, and the
+/// branch that's shown is only disposed and replaced when a different
+/// branch is selected.
+///
+/// ```rust
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let (items, set_items) = create_signal(cx, None::<Vec<String>>);
+///
+/// view! { cx,
+///   <Switch fallback=|cx| view! { cx, <p>"Loading..."</p> }>
+///     <Match when=move || items.with(|items| matches!(items, Some(items) if items.is_empty()))>
+///       <p>"Nothing here yet."</p>
+///     </Match>
+///     <Match when=move || items.with(Option::is_some)>
+///       <p>{move || items.with(|items| items.as_ref().map(Vec::len))} " items"</p>
+///     </Match>
+///   </Switch>
+/// }
+/// # ;
+/// # });
+/// ```
+#[component]
+pub fn Switch<F, IV>(
+    /// The scope the component is running in
+    cx: Scope,
+    /// The [`Match`] branches to choose from
+    children: Children,
+    /// What gets rendered if none of the branches match
+    fallback: F,
+) -> impl IntoView
+where
+    F: Fn(Scope) -> IV + 'static,
+    IV: IntoView,
+{
+    let branches = children(cx)
+        .as_children()
+        .iter()
+        .filter_map(|child| {
+            child
+                .as_transparent()
+                .and_then(|t| t.downcast_ref::<MatchBranch>())
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    // the conditions after the first one that's `true` don't affect which
+    // branch is selected, so they aren't checked or tracked
+    let selected = create_memo(cx, {
+        let branches = branches.clone();
+        move |_| branches.iter().position(|branch| (branch.when)())
+    });
+
+    let shown: Rc<RefCell<Option<ScopeDisposer>>> = Default::default();
+
+    move || {
+        let selected = selected.get();
+
+        if let Some(disposer) = shown.borrow_mut().take() {
+            disposer.dispose();
+        }
+
+        let (view, disposer) = cx.run_child_scope(|cx| {
+            cx.untrack(|| match selected {
+                Some(idx) => (branches[idx].children)(cx).into_view(cx),
+                None => fallback(cx).into_view(cx),
+            })
+        });
+        *shown.borrow_mut() = Some(disposer);
+
+        view
+    }
+}
+
+/// A branch of a [`Switch`], which is shown while `when` is `true`, unless
+/// an earlier branch matches.
+#[component(transparent)]
+pub fn Match<W>(
+    /// The scope the component is running in
+    cx: Scope,
+    /// A closure that returns whether this branch matches
+    when: W,
+    /// What gets rendered while this branch is selected
+    children: ChildrenFn,
+) -> impl IntoView
+where
+    W: Fn() -> bool + 'static,
+{
+    _ = cx;
+
+    MatchBranch {
+        when: Rc::new(when),
+        children: Rc::new(children),
+    }
+}
+
+#[derive(Clone)]
+struct MatchBranch {
+    when: Rc<dyn Fn() -> bool>,
+    children: Rc<ChildrenFn>,
+}
+
+impl IntoView for MatchBranch {
+    fn into_view(self, cx: Scope) -> View {
+        Transparent::new(self).into_view(cx)
+    }
+}
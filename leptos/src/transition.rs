@@ -1,15 +1,18 @@
 use leptos_dom::{Fragment, IntoView, View};
 use leptos_macro::component;
 use leptos_reactive::{use_context, Scope, SignalSetter, SuspenseContext};
-use std::{
-    cell::{Cell, RefCell},
-    rc::Rc,
-};
+use std::{cell::RefCell, rc::Rc, time::Duration};
 
 /// If any [Resource](leptos_reactive::Resource)s are read in the `children` of this
 /// component, it will show the `fallback` while they are loading. Once all are resolved,
 /// it will render the `children`. Unlike [`Suspense`](crate::Suspense), this will not fall
-/// back to the `fallback` state if there are further changes after the initial load.
+/// back to the `fallback` state if there are further changes after the initial load:
+/// while resources are refetched, the children that were shown last stay mounted, and
+/// they're only replaced once every resource the new render reads has resolved.
+///
+/// `set_pending` is set to `true` whenever resources are loading, on the first load
+/// as well as while refetching, so it can be used to show a spinner or dim the stale
+/// content.
 ///
 /// Note that the `children` will be rendered initially (in order to capture the fact that
 /// those resources are read under the suspense), so you cannot assume that resources have
@@ -72,83 +75,56 @@ pub fn Transition<F, E>(
     set_pending: Option<SignalSetter<bool>>,
     /// Will be displayed once all resources have resolved.
     children: Box<dyn Fn(Scope) -> Fragment>,
+    /// How long resources have to be loading before the fallback is shown in the
+    /// browser on the first load. See [`Suspense`](crate::Suspense).
+    #[prop(optional)]
+    fallback_delay: Duration,
+    /// Once the fallback is shown in the browser, it's kept for at least this long.
+    /// See [`Suspense`](crate::Suspense).
+    #[prop(optional)]
+    fallback_min_duration: Duration,
 ) -> impl IntoView
 where
     F: Fn() -> E + 'static,
     E: IntoView,
 {
+    // the children that were shown last, which stay on screen while the
+    // resources they read are refetched
     let prev_children = Rc::new(RefCell::new(None::<Vec<View>>));
 
-    let first_run = Rc::new(std::cell::Cell::new(true));
-    let child_runs = Cell::new(0);
-
     crate::Suspense(
         cx,
         crate::SuspenseProps::builder()
             .fallback({
-                let prev_child = Rc::clone(&prev_children);
-                let first_run = Rc::clone(&first_run);
+                let prev_children = Rc::clone(&prev_children);
                 move || {
                     let suspense_context = use_context::<SuspenseContext>(cx)
                         .expect("there to be a SuspenseContext");
 
-                    let is_first_run =
-                        is_first_run(&first_run, &suspense_context);
-                    first_run.set(is_first_run);
-
                     if let Some(set_pending) = &set_pending {
                         set_pending.set(true);
                     }
-                    if let Some(prev_children) = &*prev_child.borrow() {
-                        if is_first_run {
-                            fallback().into_view(cx)
-                        } else {
+                    match &*prev_children.borrow() {
+                        Some(prev_children)
+                            if suspense_context.has_loaded() =>
+                        {
                             prev_children.clone().into_view(cx)
                         }
-                    } else {
-                        fallback().into_view(cx)
+                        _ => fallback().into_view(cx),
                     }
                 }
             })
             .children(Box::new(move |cx| {
                 let frag = children(cx);
-
-                let suspense_context = use_context::<SuspenseContext>(cx)
-                    .expect("there to be a SuspenseContext");
-
-                if is_first_run(&first_run, &suspense_context) {
-                    let has_local_only = suspense_context.has_local_only();
-                    *prev_children.borrow_mut() = Some(frag.nodes.clone());
-                    if !has_local_only || child_runs.get() > 0 {
-                        first_run.set(false);
-                    }
-                }
-                child_runs.set(child_runs.get() + 1);
+                *prev_children.borrow_mut() = Some(frag.nodes.clone());
 
                 if let Some(set_pending) = &set_pending {
                     set_pending.set(false);
                 }
                 frag
             }))
+            .fallback_delay(fallback_delay)
+            .fallback_min_duration(fallback_min_duration)
             .build(),
     )
 }
-
-fn is_first_run(
-    first_run: &Rc<Cell<bool>>,
-    suspense_context: &SuspenseContext,
-) -> bool {
-    match (
-        first_run.get(),
-        cfg!(feature = "hydrate"),
-        suspense_context.has_local_only(),
-    ) {
-        (false, _, _) => false,
-        // is in hydrate mode, and has non-local resources (so, has streamed)
-        (_, false, false) => false,
-        // is in hydrate mode, but with only local resources (so, has not streamed)
-        (_, false, true) => true,
-        // either SSR or client mode: it's the first run
-        (_, true, _) => true,
-    }
-}
@@ -17,11 +17,11 @@ fn simple_ssr_test() {
         assert_eq!(
             rendered.into_view(cx).render_to_string(cx),
             "<!--leptos-view|leptos-tests-ssr.rs-8|open--><div \
-             id=\"_0-1\"><button id=\"_0-2\">-1</button><span \
-             id=\"_0-3\">Value: \
-             <!--hk=_0-4o|leptos-dyn-child-start-->0<!\
-             --hk=_0-4c|leptos-dyn-child-end-->!</span><button \
-             id=\"_0-5\">+1</button></div><!--leptos-view|leptos-tests-ssr.\
+             id=\"_1-1\"><button id=\"_1-2\">-1</button><span \
+             id=\"_1-3\">Value: \
+             <!--hk=_1-4o|leptos-dyn-child-start-->0<!\
+             --hk=_1-4c|leptos-dyn-child-end-->!</span><button \
+             id=\"_1-5\">+1</button></div><!--leptos-view|leptos-tests-ssr.\
              rs-8|close-->"
         );
     });
@@ -56,24 +56,24 @@ fn ssr_test_with_components() {
 
         assert_eq!(
             rendered.into_view(cx).render_to_string(cx),
-            "<!--leptos-view|leptos-tests-ssr.rs-49|open--><div id=\"_0-1\" \
-             class=\"counters\"><!--hk=_0-1-0o|leptos-counter-start--><!\
+            "<!--leptos-view|leptos-tests-ssr.rs-49|open--><div id=\"_1-1\" \
+             class=\"counters\"><!--hk=_1-1-0o|leptos-counter-start--><!\
              --leptos-view|leptos-tests-ssr.rs-38|open--><div \
-             id=\"_0-1-1\"><button id=\"_0-1-2\">-1</button><span \
-             id=\"_0-1-3\">Value: \
-             <!--hk=_0-1-4o|leptos-dyn-child-start-->1<!\
-             --hk=_0-1-4c|leptos-dyn-child-end-->!</span><button \
-             id=\"_0-1-5\">+1</button></div><!--leptos-view|leptos-tests-ssr.\
-             rs-38|close--><!--hk=_0-1-0c|leptos-counter-end--><!\
-             --hk=_0-1-5-0o|leptos-counter-start--><!\
+             id=\"_1-1-1\"><button id=\"_1-1-2\">-1</button><span \
+             id=\"_1-1-3\">Value: \
+             <!--hk=_1-1-4o|leptos-dyn-child-start-->1<!\
+             --hk=_1-1-4c|leptos-dyn-child-end-->!</span><button \
+             id=\"_1-1-5\">+1</button></div><!--leptos-view|leptos-tests-ssr.\
+             rs-38|close--><!--hk=_1-1-0c|leptos-counter-end--><!\
+             --hk=_1-1-5-0o|leptos-counter-start--><!\
              --leptos-view|leptos-tests-ssr.rs-38|open--><div \
-             id=\"_0-1-5-1\"><button id=\"_0-1-5-2\">-1</button><span \
-             id=\"_0-1-5-3\">Value: \
-             <!--hk=_0-1-5-4o|leptos-dyn-child-start-->2<!\
-             --hk=_0-1-5-4c|leptos-dyn-child-end-->!</span><button \
-             id=\"_0-1-5-5\">+1</button></div><!\
+             id=\"_1-1-5-1\"><button id=\"_1-1-5-2\">-1</button><span \
+             id=\"_1-1-5-3\">Value: \
+             <!--hk=_1-1-5-4o|leptos-dyn-child-start-->2<!\
+             --hk=_1-1-5-4c|leptos-dyn-child-end-->!</span><button \
+             id=\"_1-1-5-5\">+1</button></div><!\
              --leptos-view|leptos-tests-ssr.rs-38|close--><!\
-             --hk=_0-1-5-0c|leptos-counter-end--></div><!\
+             --hk=_1-1-5-0c|leptos-counter-end--></div><!\
              --leptos-view|leptos-tests-ssr.rs-49|close-->"
         );
     });
@@ -108,25 +108,25 @@ fn ssr_test_with_snake_case_components() {
 
         assert_eq!(
             rendered.into_view(cx).render_to_string(cx),
-            "<!--leptos-view|leptos-tests-ssr.rs-101|open--><div id=\"_0-1\" \
+            "<!--leptos-view|leptos-tests-ssr.rs-101|open--><div id=\"_1-1\" \
              class=\"counters\"><!\
-             --hk=_0-1-0o|leptos-snake-case-counter-start--><!\
+             --hk=_1-1-0o|leptos-snake-case-counter-start--><!\
              --leptos-view|leptos-tests-ssr.rs-90|open--><div \
-             id=\"_0-1-1\"><button id=\"_0-1-2\">-1</button><span \
-             id=\"_0-1-3\">Value: \
-             <!--hk=_0-1-4o|leptos-dyn-child-start-->1<!\
-             --hk=_0-1-4c|leptos-dyn-child-end-->!</span><button \
-             id=\"_0-1-5\">+1</button></div><!--leptos-view|leptos-tests-ssr.\
-             rs-90|close--><!--hk=_0-1-0c|leptos-snake-case-counter-end--><!\
-             --hk=_0-1-5-0o|leptos-snake-case-counter-start--><!\
+             id=\"_1-1-1\"><button id=\"_1-1-2\">-1</button><span \
+             id=\"_1-1-3\">Value: \
+             <!--hk=_1-1-4o|leptos-dyn-child-start-->1<!\
+             --hk=_1-1-4c|leptos-dyn-child-end-->!</span><button \
+             id=\"_1-1-5\">+1</button></div><!--leptos-view|leptos-tests-ssr.\
+             rs-90|close--><!--hk=_1-1-0c|leptos-snake-case-counter-end--><!\
+             --hk=_1-1-5-0o|leptos-snake-case-counter-start--><!\
              --leptos-view|leptos-tests-ssr.rs-90|open--><div \
-             id=\"_0-1-5-1\"><button id=\"_0-1-5-2\">-1</button><span \
-             id=\"_0-1-5-3\">Value: \
-             <!--hk=_0-1-5-4o|leptos-dyn-child-start-->2<!\
-             --hk=_0-1-5-4c|leptos-dyn-child-end-->!</span><button \
-             id=\"_0-1-5-5\">+1</button></div><!\
+             id=\"_1-1-5-1\"><button id=\"_1-1-5-2\">-1</button><span \
+             id=\"_1-1-5-3\">Value: \
+             <!--hk=_1-1-5-4o|leptos-dyn-child-start-->2<!\
+             --hk=_1-1-5-4c|leptos-dyn-child-end-->!</span><button \
+             id=\"_1-1-5-5\">+1</button></div><!\
              --leptos-view|leptos-tests-ssr.rs-90|close--><!\
-             --hk=_0-1-5-0c|leptos-snake-case-counter-end--></div><!\
+             --hk=_1-1-5-0c|leptos-snake-case-counter-end--></div><!\
              --leptos-view|leptos-tests-ssr.rs-101|close-->"
         );
     });
@@ -146,7 +146,7 @@ fn test_classes() {
 
         assert_eq!(
             rendered.into_view(cx).render_to_string(cx),
-            "<!--leptos-view|leptos-tests-ssr.rs-142|open--><div id=\"_0-1\" \
+            "<!--leptos-view|leptos-tests-ssr.rs-142|open--><div id=\"_1-1\" \
              class=\"my big  red \
              car\"></div><!--leptos-view|leptos-tests-ssr.rs-142|close-->"
         );
@@ -155,46 +155,1944 @@ fn test_classes() {
 
 #[cfg(not(any(feature = "csr", feature = "hydrate")))]
 #[test]
-fn ssr_with_styles() {
+fn ssr_static_class_attribute_keeps_class_toggles() {
     use leptos::*;
 
     _ = create_scope(create_runtime(), |cx| {
-        let (_, set_value) = create_signal(cx, 0);
-        let styles = "myclass";
-        let rendered = view! {
-            cx, class = styles,
+        let (active, _) = create_signal(cx, true);
+        let rendered = html::div(cx)
+            .class("active", move || active.get())
+            .class("hidden", false)
+            .attr("class", "btn")
+            .into_view(cx)
+            .render_to_string(cx);
+
+        assert_eq!(rendered.matches("class=").count(), 1, "{rendered}");
+        assert!(rendered.contains(r#"class="btn active""#), "{rendered}");
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_style_attribute_with_style_properties() {
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (angle, _) = create_signal(cx, 45);
+        let rendered = view! { cx,
+            <div
+                style:transform=move || format!("rotate({}deg)", angle.get())
+                style="color: red"
+                style=("--my-var", "4px")
+            />
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert_eq!(rendered.matches("style=").count(), 1, "{rendered}");
+        assert!(
+            rendered.contains(
+                r#"style="color: red; transform: rotate(45deg); --my-var: 4px;""#
+            ),
+            "{rendered}"
+        );
+
+        // the same, built without the macro
+        let rendered = html::div(cx)
+            .style("transform", move || format!("rotate({}deg)", angle.get()))
+            .attr("style", "color: red;")
+            .style("--my-var", "4px")
+            .into_view(cx)
+            .render_to_string(cx);
+
+        assert!(
+            rendered.contains(
+                r#"style="color: red; transform: rotate(45deg); --my-var: 4px;""#
+            ),
+            "{rendered}"
+        );
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_style_property_returning_none_is_removed() {
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (opacity, _) = create_signal(cx, None::<String>);
+        let rendered = view! { cx,
+            <div style:opacity=move || opacity.get() style:color="blue"/>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert!(rendered.contains(r#"style="color: blue;""#), "{rendered}");
+        assert!(!rendered.contains("opacity"), "{rendered}");
+
+        let rendered = view! { cx, <div style:opacity=move || opacity.get()/> }
+            .into_view(cx)
+            .render_to_string(cx);
+
+        assert!(!rendered.contains("style="), "{rendered}");
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_prop_value_renders_initial_attribute() {
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (name, _) = create_signal(cx, "Alice \"A\"".to_string());
+        let (done, _) = create_signal(cx, false);
+        let rendered = view! { cx,
             <div>
-                <button class="btn" on:click=move |_| set_value.update(|value| *value -= 1)>"-1"</button>
+                <input prop:value=move || name.get()/>
+                <input type="checkbox" prop:checked=move || !done.get()/>
+                <input type="checkbox" prop:checked=move || done.get()/>
+                <input value="Bob" prop:value=move || name.get()/>
             </div>
-        };
+        }
+        .into_view(cx)
+        .render_to_string(cx);
 
-        assert_eq!(
-            rendered.into_view(cx).render_to_string(cx),
-            "<!--leptos-view|leptos-tests-ssr.rs-164|open--><div id=\"_0-1\" \
-             class=\" myclass\"><button id=\"_0-2\" class=\"btn \
-             myclass\">-1</button></div><!--leptos-view|leptos-tests-ssr.\
-             rs-164|close-->"
+        assert!(
+            rendered.contains(r#"value="Alice &quot;A&quot;""#),
+            "{rendered}"
         );
+        assert_eq!(rendered.matches(r#"checked="""#).count(), 1, "{rendered}");
+        // the attribute sets the initial value, so it's kept as it is
+        assert!(rendered.contains(r#"value="Bob""#), "{rendered}");
+        assert_eq!(rendered.matches("value=").count(), 2, "{rendered}");
     });
 }
 
 #[cfg(not(any(feature = "csr", feature = "hydrate")))]
 #[test]
-fn ssr_option() {
+fn ssr_static_inner_html_is_not_escaped() {
     use leptos::*;
 
     _ = create_scope(create_runtime(), |cx| {
-        let (_, _) = create_signal(cx, 0);
-        let rendered = view! {
+        let (html, _) =
+            create_signal(cx, "<p>A &amp; <b>B</b></p>".to_string());
+        let rendered = view! { cx,
+            <div>
+                <article inner_html=move || html.get()/>
+                <p>"<b>"</p>
+            </div>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert!(
+            rendered.contains("><p>A &amp; <b>B</b></p></article>"),
+            "{rendered}"
+        );
+        // text is still escaped
+        assert!(rendered.contains("&lt;b&gt;</p>"), "{rendered}");
+
+        let rendered = html::section(cx)
+            .dangerously_set_inner_html(move || html.get())
+            .into_view(cx)
+            .render_to_string(cx);
+
+        assert!(
+            rendered.contains("><p>A &amp; <b>B</b></p></section>"),
+            "{rendered}"
+        );
+        assert!(!rendered.contains("inner_html"), "{rendered}");
+
+        let rendered = html::section(cx)
+            .dangerously_set_inner_html("")
+            .into_view(cx)
+            .render_to_string(cx);
+
+        assert!(rendered.contains("></section>"), "{rendered}");
+        assert!(!rendered.contains("inner_html"), "{rendered}");
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_svg_icon_with_foreign_object() {
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (fill, _) = create_signal(cx, "red");
+        let rendered = view! { cx,
+            <svg viewBox="0 0 10 10">
+                <title>"Icon"</title>
+                <path d="M0 0h10v10z" fill=move || fill.get()/>
+                <use xlink:href="#icon"/>
+                <foreignObject>
+                    <a href="/">"Home"</a>
+                </foreignObject>
+            </svg>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert!(rendered.contains(r#"fill="red""#), "{rendered}");
+        assert!(rendered.contains(r##"xlink:href="#icon""##), "{rendered}");
+        assert!(rendered.contains(">Icon</title>"), "{rendered}");
+        assert!(rendered.contains(r#"<a href="/""#), "{rendered}");
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_event_listener_options() {
+    use leptos::{ev::EventDescriptor, *};
+
+    _ = create_scope(create_runtime(), |cx| {
+        let rendered = view! { cx,
+            <div on:wheel:passive=|_| {} on:click:once:capture=|_| {}>
+                <button on:click:undelegated:once=|_| {}>"Once"</button>
+            </div>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert!(!rendered.contains("on:"), "{rendered}");
+        assert!(!rendered.contains("wheel"), "{rendered}");
+
+        let options = ev::capture(ev::once(ev::click)).options();
+        assert!(options.capture && options.once && !options.passive);
+        assert!(!ev::passive(ev::scroll).bubbles());
+        assert_eq!(ev::undelegated(ev::click).options(), Default::default());
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_typed_custom_event_listener() {
+    use leptos::{ev::EventDescriptor, *};
+
+    _ = create_scope(create_runtime(), |cx| {
+        let rendered = view! { cx,
+            <div on:item-selected={|ev: ev::CustomEvent<usize>| {
+                _ = ev.detail();
+            }}>
+                <span on:ping={|_: ev::CustomEvent<String>| {}}/>
+            </div>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert!(!rendered.contains("item-selected"), "{rendered}");
+
+        // a typed handler listens for the `CustomEvent` it's made from
+        let event = ev::Custom::<
+            <ev::CustomEvent<usize> as ev::CustomEventArg>::Raw,
+        >::new("item-selected");
+        assert_eq!(event.name(), "item-selected");
+        assert!(!event.bubbles());
+        assert!(event.delegated().bubbles());
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_global_event_listeners_do_nothing() {
+    use leptos::*;
+
+    let runtime = create_runtime();
+    let (_, _, disposer) = run_scope_undisposed(runtime, |cx| {
+        let handle = window_event_listener(cx, ev::keydown, |_| {
+            panic!("there are no window events on the server")
+        });
+        document_event_listener(cx, ev::passive(ev::scroll), |_| {});
+        handle.remove();
+    });
+    disposer.dispose();
+    runtime.dispose();
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_timers_do_nothing() {
+    use leptos::*;
+    use std::time::Duration;
+
+    let runtime = create_runtime();
+    let (_, _, disposer) = run_scope_undisposed(runtime, |cx| {
+        set_timeout(cx, Duration::ZERO, || {
+            panic!("there are no timers on the server")
+        });
+        let interval = set_interval(cx, Duration::from_millis(10), || {
+            panic!("there are no timers on the server")
+        });
+        interval.clear();
+        request_animation_frame(cx, || {
+            panic!("there are no animation frames on the server")
+        })
+        .clear();
+    });
+    disposer.dispose();
+    runtime.dispose();
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_boolean_attributes() {
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (busy, _) = create_signal(cx, false);
+        let rendered = view! { cx,
+            <form>
+                <button disabled=move || busy.get()>"Save"</button>
+                <button disabled="false">"Cancel"</button>
+                <input disabled=move || "false"/>
+                <details open=true>"More"</details>
+                <my-dialog open="false" attr:bool:modal="false"/>
+            </form>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert!(rendered.contains("<button id="), "{rendered}");
+        assert!(!rendered.contains("disabled"), "{rendered}");
+        assert!(rendered.contains("<details open "), "{rendered}");
+        assert!(rendered.contains(r#"open="false""#), "{rendered}");
+        assert!(!rendered.contains("modal"), "{rendered}");
+
+        let rendered = html::details(cx)
+            .attr("open", move || !busy.get())
+            .attr("hidden", "false")
+            .into_view(cx)
+            .render_to_string(cx);
+        assert!(rendered.starts_with("<details open "), "{rendered}");
+        assert!(!rendered.contains("hidden"), "{rendered}");
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_optional_attributes() {
+    use leptos::*;
+    use std::borrow::Cow;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (hint, set_hint) = create_signal(cx, None::<Cow<'static, str>>);
+        let (pressed, set_pressed) = create_signal(cx, None::<bool>);
+        let view = move || {
+            view! { cx,
+                <button
+                    title=move || hint.get()
+                    aria-pressed=move || pressed.get()
+                    disabled=move || pressed.get()
+                >
+                    "Bold"
+                </button>
+            }
+            .into_view(cx)
+            .render_to_string(cx)
+        };
+
+        let rendered = view();
+        assert!(rendered.starts_with("<button id="), "{rendered}");
+        assert!(!rendered.contains("title"), "{rendered}");
+        assert!(!rendered.contains("aria-pressed"), "{rendered}");
+        assert!(!rendered.contains("  "), "{rendered}");
+
+        set_hint.set(Some("Bold text".into()));
+        set_pressed.set(Some(false));
+        let rendered = view();
+        assert!(rendered.contains(r#" title="Bold text""#), "{rendered}");
+        assert!(rendered.contains(r#" aria-pressed="false""#), "{rendered}");
+        assert!(!rendered.contains("disabled"), "{rendered}");
+
+        set_pressed.set(Some(true));
+        let rendered = view();
+        assert!(rendered.contains(r#" aria-pressed="true""#), "{rendered}");
+        assert!(rendered.contains(r#" disabled="true""#), "{rendered}");
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_fragment_with_several_roots() {
+    use leptos::*;
+
+    #[component]
+    fn Term(cx: Scope, count: ReadSignal<i32>) -> impl IntoView {
+        view! { cx,
+            <dt>"Count"</dt>
+            <dd>{move || count.get()}</dd>
+            <dd>{move || count.get() * 2}</dd>
+        }
+    }
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (count, set_count) = create_signal(cx, 1);
+        let view = move || {
+            view! { cx,
+                <dl>
+                    <Term count/>
+                </dl>
+            }
+            .into_view(cx)
+            .render_to_string(cx)
+        };
+
+        let rendered = view();
+        assert!(rendered.starts_with("<dl id="), "{rendered}");
+        // the three roots are siblings inside the `<dl>`, between the
+        // component's markers
+        let dt = rendered.find("<dt").unwrap();
+        let dds = rendered.match_indices("<dd").collect::<Vec<_>>();
+        assert_eq!(dds.len(), 2, "{rendered}");
+        assert!(dt < dds[0].0, "{rendered}");
+        assert!(rendered.contains("leptos-term-end"), "{rendered}");
+        assert!(rendered.contains(">1<"), "{rendered}");
+        assert!(rendered.contains(">2<"), "{rendered}");
+
+        set_count.set(5);
+        let rendered = view();
+        assert!(rendered.contains(">5<"), "{rendered}");
+        assert!(rendered.contains(">10<"), "{rendered}");
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_lifecycle_callbacks_do_nothing() {
+    use leptos::*;
+
+    let runtime = create_runtime();
+    let (rendered, _, disposer) = run_scope_undisposed(runtime, |cx| {
+        let map_ref = create_node_ref::<html::Div>(cx);
+        on_mount(cx, map_ref, |_| panic!("nothing is mounted on the server"));
+        on_unmount(cx, map_ref, |_| {
+            panic!("nothing is unmounted on the server")
+        });
+
+        let el = html::span(cx);
+        on_mount(cx, el.clone(), |_| {
+            panic!("nothing is mounted on the server")
+        });
+        on_unmount(cx, el, |_| panic!("nothing is unmounted on the server"));
+
+        view! { cx, <div _ref=map_ref>"Map"</div> }
+            .into_view(cx)
+            .render_to_string(cx)
+            .into_owned()
+    });
+    assert!(rendered.contains(">Map</div>"), "{rendered}");
+    disposer.dispose();
+    runtime.dispose();
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_class_list_from_signal() {
+    use leptos::*;
+    use std::collections::HashSet;
+
+    fn class_names(rendered: &str) -> Vec<&str> {
+        let (_, class) = rendered.split_once(r#"class=""#).unwrap();
+        let (class, _) = class.split_once('"').unwrap();
+        class.split_ascii_whitespace().collect()
+    }
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (classes, set_classes) = create_signal(
             cx,
-            <option/>
+            vec!["card".to_string(), "selected".into(), "card".into()],
+        );
+        let view = move || {
+            view! { cx, <div class=move || classes.get() class:wide=true/> }
+                .into_view(cx)
+                .render_to_string(cx)
         };
 
-        assert_eq!(
-            rendered.into_view(cx).render_to_string(cx),
-            "<!--leptos-view|leptos-tests-ssr.rs-188|open--><option \
-             id=\"_0-1\"></option><!--leptos-view|leptos-tests-ssr.\
-             rs-188|close-->"
+        assert_eq!(class_names(&view()), ["card", "selected", "wide"]);
+
+        set_classes.set(vec!["card".into(), "two words".into()]);
+        assert_eq!(class_names(&view()), ["card", "wide"]);
+
+        let tags = HashSet::from(["b".to_string(), "a".into(), "c".into()]);
+        let rendered = html::div(cx)
+            .attr("class", tags)
+            .into_view(cx)
+            .render_to_string(cx);
+        assert!(rendered.contains(r#"class="a b c""#), "{rendered}");
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_directives_do_nothing() {
+    use leptos::{directives::click_outside, *};
+
+    fn autofocus(_cx: Scope, _el: web_sys::Element, _: ()) {
+        panic!("directives don't run on the server")
+    }
+
+    fn resize(_cx: Scope, _el: web_sys::Element, max_rows: usize) {
+        panic!("directives don't run on the server, even with {max_rows}")
+    }
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (_, set_open) = create_signal(cx, true);
+        let rendered = view! { cx,
+            <div use:click_outside=move |_: ev::MouseEvent| set_open.set(false)>
+                <textarea use:autofocus use:resize=4/>
+            </div>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert!(!rendered.contains("use:"), "{rendered}");
+        assert!(rendered.contains("<textarea id="), "{rendered}");
+
+        // the builder is used outside of the SSR fast path
+        _ = html::textarea(cx)
+            .directive(autofocus, ())
+            .directive(resize, 4);
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_event_modifiers() {
+    use leptos::{ev::EventDescriptor, *};
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (_, set_sent) = create_signal(cx, false);
+        let rendered = view! { cx,
+            <form on:submit:prevent=move |_| set_sent.set(true)>
+                <button on:click:prevent:stop=|_| {} on:keydown:stop:capture=|_| {}>
+                    "Send"
+                </button>
+            </form>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert!(!rendered.contains("on:"), "{rendered}");
+        assert!(!rendered.contains("prevent"), "{rendered}");
+        assert!(rendered.contains(">Send</button></form>"), "{rendered}");
+
+        let modifiers = ev::EventModifiers::PREVENT_DEFAULT
+            | ev::EventModifiers::STOP_PROPAGATION;
+        assert!(modifiers.contains(ev::EventModifiers::STOP_PROPAGATION));
+        assert!(!ev::EventModifiers::NONE.contains(modifiers));
+
+        // modifiers don't stop an event from being delegated
+        let submit =
+            ev::modified(ev::submit, ev::EventModifiers::PREVENT_DEFAULT);
+        assert!(submit.bubbles());
+        assert_eq!(submit.modifiers(), ev::EventModifiers::PREVENT_DEFAULT);
+        let click = ev::modified(ev::capture(ev::click), modifiers);
+        assert!(!click.bubbles());
+        assert_eq!(click.modifiers(), modifiers);
+
+        _ = html::form(cx).on(submit, |_: web_sys::SubmitEvent| {});
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_typed_event_arguments() {
+    use leptos::{ev::EventDescriptor, *};
+
+    // only compiles if `event` is handled with a `T`
+    fn handled_with<T, E: EventDescriptor<EventType = T>>(_event: E) {}
+
+    handled_with::<web_sys::MouseEvent, _>(ev::click);
+    handled_with::<web_sys::MouseEvent, _>(ev::dblclick);
+    handled_with::<web_sys::KeyboardEvent, _>(ev::keydown);
+    handled_with::<web_sys::KeyboardEvent, _>(ev::keyup);
+    handled_with::<web_sys::FocusEvent, _>(ev::focus);
+    handled_with::<web_sys::FocusEvent, _>(ev::blur);
+    handled_with::<web_sys::InputEvent, _>(ev::beforeinput);
+    handled_with::<web_sys::Event, _>(ev::input);
+    handled_with::<web_sys::Event, _>(ev::change);
+    handled_with::<web_sys::SubmitEvent, _>(ev::submit);
+    handled_with::<web_sys::PointerEvent, _>(ev::pointerdown);
+    handled_with::<web_sys::WheelEvent, _>(ev::wheel);
+    handled_with::<web_sys::DragEvent, _>(ev::drop);
+    handled_with::<web_sys::TouchEvent, _>(ev::touchstart);
+    handled_with::<web_sys::ClipboardEvent, _>(ev::paste);
+    handled_with::<web_sys::Event, _>(ev::Custom::new("my-event"));
+    // wrappers keep the type
+    handled_with::<web_sys::WheelEvent, _>(ev::passive(ev::wheel));
+    handled_with::<web_sys::MouseEvent, _>(ev::undelegated(ev::click));
+
+    _ = create_scope(create_runtime(), |cx| {
+        _ = view! { cx,
+            <form on:submit=|_ev: web_sys::SubmitEvent| {}>
+                <input
+                    on:input=|_ev: web_sys::Event| {}
+                    on:beforeinput=|_ev: web_sys::InputEvent| {}
+                    on:keydown=|_ev: web_sys::KeyboardEvent| {}
+                    on:focus=|_ev: web_sys::FocusEvent| {}
+                    on:paste=|_ev: web_sys::ClipboardEvent| {}
+                />
+                <button on:click=|_ev: web_sys::MouseEvent| {}>"Send"</button>
+                <div on:my-event=|_ev: web_sys::Event| {}/>
+            </form>
+        };
+
+        _ = window_event_listener(
+            cx,
+            ev::keyup,
+            |_ev: web_sys::KeyboardEvent| {},
+        );
+        _ = document_event_listener(
+            cx,
+            ev::pointermove,
+            |_ev: web_sys::PointerEvent| {},
+        );
+        _ = html::div(cx).on(ev::wheel, |_ev: web_sys::WheelEvent| {});
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_bindings() {
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let name = create_rw_signal(cx, "Alice \"A\"".to_string());
+        let age = create_rw_signal(cx, 30u8);
+        let subscribed = create_rw_signal(cx, true);
+        let plan = create_rw_signal(cx, "pro");
+        let toppings = create_rw_signal(cx, vec![2, 3]);
+
+        let rendered = view! { cx,
+            <form>
+                <input type="text" bind:value=name/>
+                <input type="number" bind:value=age/>
+                <input type="checkbox" bind:checked=subscribed/>
+                <input type="radio" name="plan" value="free" bind:group=(plan, "free")/>
+                <input type="radio" name="plan" value="pro" bind:group=(plan, "pro")/>
+                <input type="checkbox" bind:group=(toppings, 1)/>
+                <input type="checkbox" bind:group=(toppings, 2)/>
+                <input value="ignored" bind:value=name/>
+            </form>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        let inputs = rendered
+            .split("<input")
+            .skip(1)
+            .map(|input| &input[..input.find('>').unwrap()])
+            .collect::<Vec<_>>();
+        assert_eq!(inputs.len(), 8, "{rendered}");
+        assert!(
+            inputs[0].contains(r#" value="Alice &quot;A&quot;""#),
+            "{rendered}"
         );
+        assert!(inputs[1].contains(r#" value="30""#), "{rendered}");
+        assert!(inputs[2].contains(" checked"), "{rendered}");
+        assert!(!inputs[3].contains(" checked"), "{rendered}");
+        assert!(inputs[4].contains(" checked"), "{rendered}");
+        assert!(!inputs[5].contains(" checked"), "{rendered}");
+        assert!(inputs[6].contains(" checked"), "{rendered}");
+        assert!(inputs[7].contains(r#" value="ignored""#), "{rendered}");
+        assert!(!inputs[7].contains("Alice"), "{rendered}");
+        assert!(!rendered.contains("bind:"), "{rendered}");
+
+        // the builder is used outside of the SSR fast path
+        _ = html::input(cx)
+            .bind_value(name)
+            .bind_checked(subscribed)
+            .bind_group(toppings, 1);
+
+        // the group follows whichever inputs are checked
+        toppings.set_selected(&1, true);
+        toppings.set_selected(&3, false);
+        toppings.set_selected(&1, true);
+        assert_eq!(toppings.get(), vec![2, 1]);
+        plan.set_selected(&"free", false);
+        assert_eq!(plan.get(), "pro");
+        plan.set_selected(&"free", true);
+        assert_eq!(plan.get(), "free");
+
+        assert_eq!(u8::from_value(" 42 "), Some(42));
+        assert_eq!(u8::from_value("256"), None);
+        assert_eq!(f64::from_value("1."), Some(1.0));
+        assert_eq!(String::from_value(" a "), Some(" a ".to_string()));
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_for_each() {
+    use leptos::*;
+
+    #[derive(Clone, PartialEq)]
+    struct Todo {
+        id: usize,
+        title: &'static str,
+    }
+
+    _ = create_scope(create_runtime(), |cx| {
+        let todos = vec![
+            Todo {
+                id: 1,
+                title: "Write",
+            },
+            Todo {
+                id: 2,
+                title: "Test",
+            },
+        ];
+
+        let rendered = view! { cx,
+            <ul>
+                <ForEach
+                    each=move || todos.clone()
+                    key=|todo| todo.id
+                    children=|cx, todo| {
+                        view! { cx, <li>{move || todo.with(|todo| todo.title)}</li> }
+                    }
+                />
+            </ul>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert!(rendered.contains(">Write<"), "{rendered}");
+        assert!(rendered.contains(">Test<"), "{rendered}");
+        assert!(
+            rendered.find("Write").unwrap() < rendered.find("Test").unwrap(),
+            "{rendered}"
+        );
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_each_index() {
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let lines = create_rw_signal(cx, vec!["first", "second", "third"]);
+
+        let rendered = view! { cx,
+            <ol>
+                <EachIndex
+                    each=lines
+                    children=|cx, line| {
+                        view! { cx, <li>{move || line.get()}</li> }
+                    }
+                />
+            </ol>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+
+        assert_eq!(rendered.matches("<li").count(), 3, "{rendered}");
+        let first = rendered.find(">first<").unwrap();
+        let second = rendered.find(">second<").unwrap();
+        let third = rendered.find(">third<").unwrap();
+        assert!(first < second && second < third, "{rendered}");
     });
 }
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_show_only_creates_the_branch_shown() {
+    use leptos::*;
+    use std::{cell::Cell, rc::Rc};
+
+    fn render(keep_alive: bool, fallbacks: Rc<Cell<usize>>) -> String {
+        leptos_dom::HydrationCtx::reset_id();
+        run_scope(create_runtime(), move |cx| {
+            view! { cx,
+                <Show
+                    when=|| true
+                    fallback=move |cx| {
+                        fallbacks.set(fallbacks.get() + 1);
+                        view! { cx, <p>"Fallback"</p> }
+                    }
+                    keep_alive=keep_alive
+                >
+                    <p>"Shown"</p>
+                </Show>
+            }
+            .into_view(cx)
+            .render_to_string(cx)
+            .to_string()
+        })
+    }
+
+    for keep_alive in [false, true] {
+        let fallbacks = Rc::new(Cell::new(0));
+        let rendered = render(keep_alive, Rc::clone(&fallbacks));
+        assert!(rendered.contains(">Shown<"), "{rendered}");
+        assert!(!rendered.contains("Fallback"), "{rendered}");
+        assert_eq!(fallbacks.get(), 0);
+
+        // the client creates the same branch, with the same keys
+        assert_eq!(render(keep_alive, fallbacks), rendered);
+    }
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_switch_renders_the_first_match() {
+    use leptos::*;
+
+    fn render(state: i32) -> String {
+        leptos_dom::HydrationCtx::reset_id();
+        run_scope(create_runtime(), move |cx| {
+            view! { cx,
+                <Switch fallback=|cx| view! { cx, <p>"Fallback"</p> }>
+                    <Match when=move || state < 0>
+                        <p>"Negative"</p>
+                    </Match>
+                    <Match when=move || state == 0>
+                        <p>"Zero"</p>
+                    </Match>
+                    <Match when=move || state < 10>
+                        <p>"Small"</p>
+                    </Match>
+                </Switch>
+            }
+            .into_view(cx)
+            .render_to_string(cx)
+            .to_string()
+        })
+    }
+
+    for (state, expected) in [
+        (-1, "Negative"),
+        (0, "Zero"),
+        (5, "Small"),
+        (50, "Fallback"),
+    ] {
+        let rendered = render(state);
+        let shown = ["Negative", "Zero", "Small", "Fallback"]
+            .into_iter()
+            .filter(|branch| rendered.contains(&format!(">{branch}<")))
+            .collect::<Vec<_>>();
+        assert_eq!(shown, [expected], "{rendered}");
+        // the client selects the same branch, with the same keys
+        assert_eq!(render(state), rendered);
+    }
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_with_styles() {
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (_, set_value) = create_signal(cx, 0);
+        let styles = "myclass";
+        let rendered = view! {
+            cx, class = styles,
+            <div>
+                <button class="btn" on:click=move |_| set_value.update(|value| *value -= 1)>"-1"</button>
+            </div>
+        };
+
+        assert_eq!(
+            rendered.into_view(cx).render_to_string(cx),
+            "<!--leptos-view|leptos-tests-ssr.rs-164|open--><div id=\"_1-1\" \
+             class=\" myclass\"><button id=\"_1-2\" class=\"btn \
+             myclass\">-1</button></div><!--leptos-view|leptos-tests-ssr.\
+             rs-164|close-->"
+        );
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_option() {
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (_, _) = create_signal(cx, 0);
+        let rendered = view! {
+            cx,
+            <option/>
+        };
+
+        assert_eq!(
+            rendered.into_view(cx).render_to_string(cx),
+            "<!--leptos-view|leptos-tests-ssr.rs-188|open--><option \
+             id=\"_1-1\"></option><!--leptos-view|leptos-tests-ssr.\
+             rs-188|close-->"
+        );
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_suspense_with_only_local_resources_resolves_to_fallback() {
+    use futures::FutureExt;
+    use leptos::*;
+
+    _ = create_scope(create_runtime(), |cx| {
+        let local = create_local_resource(cx, || (), |_| async { 42 });
+        let rendered = view! {
+            cx,
+            <Suspense fallback=|| "Loading...">
+                {move || local.read(cx).map(|n| n.to_string())}
+            </Suspense>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+        assert!(rendered.contains("Loading..."));
+
+        let fragments = cx.pending_fragments();
+        assert_eq!(fragments.len(), 1);
+        let (_, (out_of_order, in_order)) =
+            fragments.into_iter().next().unwrap();
+        let html = out_of_order
+            .now_or_never()
+            .expect("fragment should resolve without waiting");
+        assert!(html.contains("Loading..."));
+        assert!(!html.contains("42"));
+        assert!(in_order.now_or_never().is_some());
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_suspense_lazy_component_is_left_to_the_client() {
+    use futures::FutureExt;
+    use leptos::*;
+    use std::{cell::Cell, rc::Rc};
+
+    _ = create_scope(create_runtime(), |cx| {
+        let loads = Rc::new(Cell::new(0));
+        let settings = lazy_component({
+            let loads = Rc::clone(&loads);
+            move || {
+                loads.set(loads.get() + 1);
+                async {
+                    Ok::<_, ServerFnError>(DynComponent::new(|cx| {
+                        view! { cx, <form>"Settings"</form> }
+                    }))
+                }
+            }
+        });
+        let rendered = view! {
+            cx,
+            <Suspense fallback=|| "Loading...">{settings.clone()}</Suspense>
+        }
+        .into_view(cx)
+        .render_to_string(cx);
+        assert!(rendered.contains("Loading..."), "{rendered}");
+
+        // the boundary doesn't wait for a component the server never loads
+        let (_, (out_of_order, _)) =
+            cx.pending_fragments().into_iter().next().unwrap();
+        let html = out_of_order
+            .now_or_never()
+            .expect("fragment should resolve without waiting");
+        assert!(html.contains("Loading..."), "{html}");
+        assert!(!html.contains("Settings"), "{html}");
+        assert_eq!(loads.get(), 0);
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_in_order_suspense_streams_fragments_in_document_order() {
+    use leptos::*;
+
+    let html = run_local(ssr::render_to_string_async(|cx| {
+        let first =
+            create_resource(cx, || (), |_| async { "first".to_string() });
+        let second =
+            create_resource(cx, || (), |_| async { "second".to_string() });
+        // the boundaries sit at the top level, because elements with
+        // only static content are rendered to a string up front
+        Fragment::new(vec![
+            view! { cx, <p>"before"</p> }.into_view(cx),
+            view! {
+                cx,
+                <Suspense fallback=|| "Loading...">
+                    {move || first.read(cx)}
+                </Suspense>
+            }
+            .into_view(cx),
+            view! { cx, <p>"between"</p> }.into_view(cx),
+            view! {
+                cx,
+                <Suspense fallback=|| "Loading...">
+                    {move || second.read(cx)}
+                </Suspense>
+            }
+            .into_view(cx),
+            view! { cx, <p>"after"</p> }.into_view(cx),
+        ])
+        .into_view(cx)
+    }));
+
+    assert!(!html.contains("Loading..."));
+    let positions =
+        ["before", "first", "between", "second", "after"].map(|text| {
+            html.find(text).unwrap_or_else(|| panic!("missing {text}"))
+        });
+    assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_resolved_nested_suspense_renders_without_fallbacks() {
+    use leptos::*;
+
+    #[component]
+    fn Inner(cx: Scope, outer: String) -> impl IntoView {
+        let inner = create_resource(
+            cx,
+            move || outer.clone(),
+            |outer| async move { format!("{outer} > inner") },
+        );
+        view! {
+            cx,
+            <Suspense fallback=|| "Loading inner...">
+                {move || inner.read(cx)}
+            </Suspense>
+        }
+    }
+
+    let html = run_local(ssr::render_to_string_resolved(|cx| {
+        let outer =
+            create_resource(cx, || (), |_| async { "outer".to_string() });
+        view! {
+            cx,
+            <Suspense fallback=|| "Loading outer...">
+                {move || {
+                    outer
+                        .read(cx)
+                        .map(|outer| view! { cx, <Inner outer/> })
+                }}
+            </Suspense>
+        }
+        .into_view(cx)
+    }));
+
+    assert!(html.contains("outer > inner"));
+    assert!(!html.contains("Loading"));
+    assert!(!html.contains("<script>"));
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_suspense_error_boundary_renders_failed_resources() {
+    use leptos::*;
+
+    let html = run_local(ssr::render_to_string_resolved(|cx| {
+        let count = create_resource(
+            cx,
+            || (),
+            |_| async {
+                Err::<i32, ServerFnError>(ServerFnError::ServerError(
+                    "no count".into(),
+                ))
+            },
+        );
+        view! {
+            cx,
+            <ErrorBoundary fallback=|cx, errors| view! { cx,
+                <p class="error">
+                    {errors.with(|errors| {
+                        errors.iter().map(|(_, e)| e.to_string()).collect::<String>()
+                    })}
+                </p>
+            }>
+                <Suspense fallback=|| "Loading...">
+                    <p>"Count: " {move || count.read(cx)}</p>
+                </Suspense>
+            </ErrorBoundary>
+        }
+        .into_view(cx)
+    }));
+
+    assert!(html.contains(r#"class="error">"#), "{html}");
+    assert!(html.contains("no count"), "{html}");
+    // the children are still rendered, but hidden, along with the errors
+    // for the client to start from
+    let hidden = &html[html.find("<leptos-error-boundary").unwrap()..];
+    assert!(
+        hidden[..hidden.find('>').unwrap()]
+            .contains(r#"style="display: none""#),
+        "{html}"
+    );
+    assert!(hidden.contains("Count: "), "{html}");
+    let serialized = hidden
+        .split(r#"data-errors=""#)
+        .nth(1)
+        .and_then(|rest| rest.split('"').next())
+        .expect("the serialized errors");
+    let serialized = serialized.replace("&quot;", "\"");
+    let errors = Errors::from_serialized(&serialized).unwrap();
+    let messages = errors
+        .iter()
+        .map(|(_, e)| e.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contains("no count"), "{messages:?}");
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_suspense_keys_in_loop_are_unique_and_match_between_passes() {
+    use leptos::*;
+    use std::collections::HashSet;
+
+    // renders the page from scratch, as the server and then the client would
+    fn render_pass() -> Vec<String> {
+        leptos_dom::HydrationCtx::reset_id();
+        run_local(async {
+            run_scope(create_runtime(), |cx| {
+                _ = view! {
+                    cx,
+                    <For
+                        each=|| vec![1, 2]
+                        key=|n| *n
+                        view=move |cx, n: i32| {
+                            let a = create_resource(cx, move || n, |n| async move { n });
+                            let b = create_resource(cx, move || n, |n| async move { -n });
+                            view! {
+                                cx,
+                                <Suspense fallback=|| "Loading...">
+                                    {move || a.read(cx)}
+                                </Suspense>
+                                <Suspense fallback=|| "Loading...">
+                                    {move || b.read(cx)}
+                                </Suspense>
+                            }
+                        }
+                    />
+                }
+                .into_view(cx)
+                .render_to_string(cx);
+                cx.pending_fragment_keys()
+            })
+        })
+    }
+
+    let server = render_pass();
+    let client = render_pass();
+    assert_eq!(server.len(), 4);
+    assert_eq!(server.iter().collect::<HashSet<_>>().len(), 4);
+    assert_eq!(server, client);
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+fn suspense_page(cx: leptos::Scope) -> leptos::View {
+    use leptos::*;
+
+    let first = create_resource(cx, || (), |_| async { "first".to_string() });
+    view! {
+        cx,
+        <Suspense fallback=|| "Loading...">
+            {move || first.read(cx)}
+        </Suspense>
+    }
+    .into_view(cx)
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_suspense_scripts_carry_nonce() {
+    use futures::StreamExt;
+    use leptos::*;
+
+    let with_nonce = |cx: Scope| cx.set_script_nonce("abc123");
+    let out_of_order = run_local(async move {
+        let (stream, runtime, _) =
+            ssr::render_to_stream_with_prefix_undisposed_with_context(
+                suspense_page,
+                |_| "".into(),
+                with_nonce,
+            );
+        let html = stream.collect::<String>().await;
+        runtime.dispose();
+        html
+    });
+    let in_order = run_local(async move {
+        let (stream, runtime, _) =
+            ssr::render_to_stream_in_order_with_prefix_undisposed_with_context(
+                suspense_page,
+                |_| "".into(),
+                with_nonce,
+            );
+        let html = stream.collect::<String>().await;
+        runtime.dispose();
+        html
+    });
+
+    for html in [out_of_order, in_order] {
+        assert!(html.contains("first"));
+        let scripts = html.matches("<script").count();
+        assert!(scripts > 0);
+        assert_eq!(html.matches(r#"<script nonce="abc123">"#).count(), scripts);
+    }
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_stream_prefix_records_events_to_replay() {
+    use futures::StreamExt;
+    use leptos::*;
+
+    fn render(replay: bool) -> String {
+        run_local(async move {
+            let (stream, runtime, _) =
+                ssr::render_to_stream_with_prefix_undisposed_with_context(
+                    suspense_page,
+                    |_| "<head></head>".into(),
+                    move |cx| {
+                        cx.set_script_nonce("abc123");
+                        if replay {
+                            cx.set_replayed_events(
+                                &ssr::DEFAULT_REPLAYED_EVENTS,
+                            );
+                        }
+                    },
+                );
+            let html = stream.collect::<String>().await;
+            runtime.dispose();
+            html
+        })
+    }
+
+    let html = render(true);
+    let script = html
+        .split(r#"<script nonce="abc123">"#)
+        .nth(1)
+        .and_then(|rest| rest.split("</script>").next())
+        .unwrap();
+    assert!(script.contains("__LEPTOS_CAPTURED_EVENTS"));
+    assert!(script.contains(r#"["click","input","submit"]"#));
+    // recorded before the shell, so nothing is missed
+    assert!(html.find("__LEPTOS_CAPTURED_EVENTS") < html.find("Loading..."));
+
+    assert!(!render(false).contains("__LEPTOS_CAPTURED_EVENTS"));
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_suspense_streamed_chunk_announces_its_key() {
+    use futures::StreamExt;
+    use leptos::*;
+
+    let html = run_local(async move {
+        let (stream, runtime, _) =
+            ssr::render_to_stream_with_prefix_undisposed_with_context(
+                suspense_page,
+                |_| "".into(),
+                |_| {},
+            );
+        let html = stream.collect::<String>().await;
+        runtime.dispose();
+        html
+    });
+
+    let id = html
+        .split("<!--suspense-open-")
+        .nth(1)
+        .and_then(|rest| rest.split("-->").next())
+        .unwrap();
+    let swap = html.split(&format!(r#"var id = "{id}";"#)).nth(1).unwrap();
+    let swap = swap.split("</script>").next().unwrap();
+    // the client registers the chunk once it's in the page
+    let inserted = swap.find("insertBefore").unwrap();
+    let announced = swap
+        .find(r#"new CustomEvent("leptos:streamed-chunk", { detail: id })"#)
+        .unwrap();
+    assert!(inserted < announced);
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_suspense_json_resources_round_trip() {
+    use futures::StreamExt;
+    use leptos::*;
+
+    let html = run_local(async {
+        let (stream, runtime, _) =
+            ssr::render_to_stream_in_order_with_prefix_undisposed_with_context(
+                suspense_page,
+                |_| "".into(),
+                |cx| cx.set_json_resources(true),
+            );
+        let html = stream.collect::<String>().await;
+        runtime.dispose();
+        html
+    });
+
+    // the only script is the resource, which isn't executable
+    assert_eq!(html.matches("<script").count(), 1);
+    let (_, block) = html
+        .split_once(r#"<script type="application/json" data-leptos-resource=""#)
+        .unwrap();
+    let (id, rest) = block.split_once(r#"">"#).unwrap();
+    let (content, _) = rest.split_once("</script>").unwrap();
+
+    let (_, data) =
+        resource_from_json_script(&id.replace("&quot;", "\""), content)
+            .unwrap();
+    assert!(data.ends_with(r#":"first""#));
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+fn run_local<F: std::future::Future>(fut: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap()
+        .block_on(tokio::task::LocalSet::new().run_until(fut))
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_nested_suspense_streams_inner_fragment_out_of_order() {
+    use futures::StreamExt;
+    use leptos::*;
+
+    #[component]
+    fn Inner(cx: Scope, outer: String) -> impl IntoView {
+        let inner = create_resource(
+            cx,
+            move || outer.clone(),
+            |outer| async move { format!("{outer} > inner") },
+        );
+        view! {
+            cx,
+            <Suspense fallback=|| "Loading inner...">
+                {move || inner.read(cx)}
+            </Suspense>
+        }
+    }
+
+    let html = run_local(async {
+        let (stream, runtime, _) = ssr::render_to_stream_with_prefix_undisposed(
+            |cx| {
+                let outer = create_resource(
+                    cx,
+                    || (),
+                    |_| async { "outer".to_string() },
+                );
+                view! {
+                    cx,
+                    <Suspense fallback=|| "Loading outer...">
+                        {move || {
+                            outer
+                                .read(cx)
+                                .map(|outer| view! { cx, <Inner outer/> })
+                        }}
+                    </Suspense>
+                }
+                .into_view(cx)
+            },
+            |_| "".into(),
+        );
+        let html = stream.collect::<String>().await;
+        runtime.dispose();
+        html
+    });
+
+    assert!(html.contains("outer > inner"));
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_response_metadata_is_collected_from_nested_components() {
+    use leptos::*;
+
+    #[component]
+    fn NotFound(cx: Scope) -> impl IntoView {
+        if let Some(response) = use_context::<ResponseMetadata>(cx) {
+            response.set_status(500);
+            // a later write wins
+            response.set_status(404);
+            response.insert_header("Cache-Control", "no-store");
+        }
+        view! { cx, <p>"Not found"</p> }
+    }
+
+    let metadata = ResponseMetadata::default();
+    let html = ssr::render_to_string({
+        let metadata = metadata.clone();
+        move |cx| {
+            provide_context(cx, metadata);
+            view! { cx, <main><NotFound/></main> }
+        }
+    });
+
+    assert!(html.contains("Not found"));
+    assert_eq!(
+        metadata.collect(),
+        CollectedResponse {
+            status: Some(404),
+            headers: vec![("Cache-Control".into(), "no-store".into())],
+            redirect: None,
+        }
+    );
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+fn static_page(cx: leptos::Scope) -> leptos::View {
+    use leptos::*;
+
+    let post =
+        create_resource(cx, || 1, |id| async move { format!("post {id}") });
+    view! {
+        cx,
+        <Suspense fallback=|| "Loading...">
+            {move || post.read(cx)}
+        </Suspense>
+    }
+    .into_view(cx)
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_page_renders_without_fallbacks() {
+    use leptos::*;
+
+    let html = run_local(ssr::render_to_static(static_page)).unwrap();
+
+    assert!(html.contains("post 1"));
+    assert!(!html.contains("Loading"));
+    assert!(!html.contains("<script>"));
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_page_fails_when_a_resource_never_resolves() {
+    use leptos::*;
+    use std::time::Duration;
+
+    let result = run_local(ssr::render_static_page(
+        |cx| {
+            let stuck = create_resource(
+                cx,
+                || (),
+                |_| futures::future::pending::<String>(),
+            );
+            view! {
+                cx,
+                <Suspense fallback=|| "Loading...">
+                    {move || stuck.read(cx)}
+                </Suspense>
+            }
+            .into_view(cx)
+        },
+        Duration::from_millis(10),
+    ));
+
+    assert!(
+        matches!(result, Err(ssr::SsgError::Timeout(ids)) if ids.len() == 1)
+    );
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_page_emits_resource_payloads_for_hydration() {
+    use leptos::*;
+    use std::time::Duration;
+
+    let page =
+        run_local(ssr::render_static_page(static_page, Duration::from_secs(1)))
+            .unwrap();
+
+    // the IDs match the ones a client creates in a new runtime
+    let client_ids = run_local(async {
+        run_scope(create_runtime(), |cx| {
+            _ = static_page(cx);
+            cx.all_resources()
+        })
+    });
+    let ids = page.resources.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+    assert_eq!(ids, client_ids);
+    let data = decode_resource_payload(&page.resources[0].1).unwrap();
+    assert!(data.ends_with(r#""post 1""#));
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_suspense_fragment_keys_do_not_depend_on_resolution_order() {
+    use leptos::*;
+    use leptos_dom::HydrationCtx;
+
+    // renders two sibling boundaries and resolves their fragments in the
+    // given order, returning each fragment's HTML by key
+    fn resolve_fragments(reverse: bool) -> Vec<(String, String)> {
+        HydrationCtx::reset_id();
+        run_local(async move {
+            let runtime = create_runtime();
+            let (cx, disposer) = raw_scope_and_disposer(runtime);
+            _ = view! {
+                cx,
+                <div>
+                    {(0..2)
+                        .map(|n| {
+                            let post = create_resource(cx, move || n, |n| async move {
+                                format!("post {n}")
+                            });
+                            view! {
+                                cx,
+                                <Suspense fallback=|| "Loading...">
+                                    <p>{move || post.read(cx)}</p>
+                                </Suspense>
+                            }
+                        })
+                        .collect::<Vec<_>>()}
+                </div>
+            }
+            .into_view(cx)
+            .render_to_string(cx);
+
+            let mut fragments =
+                cx.pending_fragments().into_iter().collect::<Vec<_>>();
+            assert_eq!(fragments.len(), 2);
+            if reverse {
+                fragments.reverse();
+            }
+
+            let mut html = Vec::new();
+            for (key, (out_of_order, _)) in fragments {
+                let before = HydrationCtx::peek();
+                html.push((key, out_of_order.await));
+                // resolving a fragment doesn't move the keys of what's next
+                assert_eq!(HydrationCtx::peek(), before);
+            }
+            html.sort();
+
+            disposer.dispose();
+            runtime.dispose();
+            html
+        })
+    }
+
+    let in_order = resolve_fragments(false);
+    assert!(in_order[0].1.contains("post 0"));
+    assert!(in_order[1].1.contains("post 1"));
+    assert_eq!(resolve_fragments(true), in_order);
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_suspense_nested_keys_are_restored_at_each_level() {
+    use leptos_dom::{HydrationCtx, HydrationKey};
+
+    HydrationCtx::reset_id();
+    let outer = HydrationCtx::peek();
+    let keys = HydrationCtx::with_key(HydrationKey::root("a"), || {
+        HydrationCtx::id();
+        let a = HydrationCtx::peek();
+        let inner = HydrationCtx::with_key(HydrationKey::root("b"), || {
+            HydrationCtx::id();
+            let b = HydrationCtx::peek();
+            let innermost =
+                HydrationCtx::with_key(HydrationKey::root("c"), || {
+                    HydrationCtx::id();
+                    HydrationCtx::peek().to_string()
+                });
+            assert_eq!(HydrationCtx::peek(), b);
+            (b.to_string(), innermost)
+        });
+        assert_eq!(HydrationCtx::peek(), a);
+        (a.to_string(), inner)
+    });
+    assert_eq!(HydrationCtx::peek(), outer);
+    assert_eq!(keys, ("a-1".into(), ("b-1".into(), "c-1".into())));
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_suspense_key_is_restored_when_rendering_panics() {
+    use leptos_dom::{HydrationCtx, HydrationKey};
+
+    HydrationCtx::reset_id();
+    HydrationCtx::id();
+    let before = HydrationCtx::peek();
+    let result = std::panic::catch_unwind(|| {
+        HydrationCtx::with_key(HydrationKey::root("a"), || {
+            HydrationCtx::id();
+            panic!("rendering failed");
+        })
+    });
+    assert!(result.is_err());
+    assert_eq!(HydrationCtx::peek(), before);
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_hydration_keys_are_compact_for_500_components() {
+    use leptos::*;
+    use leptos_dom::HydrationKey;
+
+    #[component]
+    fn Item(cx: Scope, n: usize) -> impl IntoView {
+        view! { cx, <li>{n}</li> }
+    }
+
+    let html = leptos::ssr::render_to_string(|cx| {
+        view! {
+            cx,
+            <ul>
+                {(0..500).map(|n| view! { cx, <Item n/> }).collect::<Vec<_>>()}
+            </ul>
+        }
+    });
+
+    // every key in the markup, and the same key in the previous format,
+    // which wrote out each offset of the path in decimal
+    let mut compact = 0;
+    let mut previous_format = 0;
+    for (prefix, marker) in [("id=\"_", false), ("hk=_", true)] {
+        for (start, _) in html.match_indices(prefix) {
+            let rest = &html[start + prefix.len()..];
+            let end = rest
+                .find(|c: char| {
+                    !(c.is_ascii_alphanumeric() || c == '-' || c == '_')
+                })
+                .unwrap();
+            let mut key = rest[..end].trim_end_matches('-');
+            // markers end in `o` or `c` in debug builds
+            if marker && cfg!(debug_assertions) {
+                key = &key[..key.len() - 1];
+            }
+            let id = HydrationKey::parse(key)
+                .unwrap_or_else(|| panic!("couldn't parse {key}"));
+            assert_eq!(id.to_string(), key);
+
+            let mut legacy = "0-".to_string();
+            for offset in id.path() {
+                legacy.push_str(&format!("{offset}-"));
+            }
+            legacy.push_str(&id.offset.to_string());
+
+            compact += key.len();
+            previous_format += legacy.len();
+        }
+    }
+
+    assert!(previous_format > 500_000, "{previous_format}");
+    assert!(
+        compact * 50 < previous_format,
+        "{compact} bytes of keys, compared to {previous_format} before"
+    );
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_island_inside_static_page() {
+    use futures::StreamExt;
+    use leptos::*;
+    use leptos_dom::HydrationCtx;
+
+    #[component]
+    fn Counter(cx: Scope) -> impl IntoView {
+        let initial = create_resource(cx, || (), |_| async { 5 });
+        let (count, set_count) = create_signal(cx, 0);
+        view! {
+            cx,
+            <button on:click=move |_| set_count.update(|n| *n += 1)>
+                {move || count.get() + initial.read(cx).unwrap_or_default()}
+            </button>
+        }
+    }
+
+    HydrationCtx::reset_id();
+    run_local(async {
+        let runtime = create_runtime();
+        let (cx, disposer) = raw_scope_and_disposer(runtime);
+
+        let title =
+            create_resource(cx, || (), |_| async { "Static".to_string() });
+        let html = view! {
+            cx,
+            <main>
+                <h1>{move || title.read(cx)}</h1>
+                <p>"Lots of static text."</p>
+                {island(cx, "counter", |cx| view! { cx, <Counter/> })}
+            </main>
+        }
+        .into_view(cx)
+        .render_to_string(cx)
+        .into_owned();
+
+        // the island is marked, with its own keys
+        let start = html.find("<leptos-island").unwrap();
+        let island_html = &html[start..html.find("</leptos-island>").unwrap()];
+        assert!(island_html.contains(r#"data-island="counter""#), "{html}");
+        let key = island_html
+            .split(r#"data-key=""#)
+            .nth(1)
+            .and_then(|rest| rest.split('"').next())
+            .unwrap();
+        assert!(key.starts_with("island_"), "{key}");
+        assert!(
+            island_html.contains(&format!(r#"<button id="_{key}-"#)),
+            "{island_html}"
+        );
+
+        // only the island's resource is sent to the client, and the island
+        // says which one it is
+        let pending = cx.pending_resources();
+        assert_eq!(pending.len(), 1);
+        assert!(island_html.contains(&format!(
+            "data-resources=\"{}\"",
+            serde_json::to_string(&pending)
+                .unwrap()
+                .replace('"', "&quot;")
+        )));
+        let resolved = cx.serialization_resolvers().collect::<Vec<_>>().await;
+        assert_eq!(
+            resolved.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            pending
+        );
+        assert!(!cx.in_island());
+        assert!(cx.has_islands());
+
+        disposer.dispose();
+        runtime.dispose();
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_island_marks_its_hydration_strategy() {
+    use leptos::*;
+
+    let html = leptos::ssr::render_to_string(|cx| {
+        view! {
+            cx,
+            <main>
+                {island(cx, "eager", |cx| view! { cx, <button>"Now"</button> })}
+                {island_with_strategy(cx, "lazy", HydrationStrategy::OnVisible, |cx| {
+                    view! { cx, <button>"Later"</button> }
+                })}
+            </main>
+        }
+    });
+    assert_eq!(html.matches("data-hydrate").count(), 1, "{html}");
+    assert!(html.contains(r#"data-hydrate="visible""#), "{html}");
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_hydration_keys_of_separate_apps_do_not_collide() {
+    use futures::StreamExt;
+    use leptos::*;
+    use std::collections::HashSet;
+
+    fn render(app_id: &'static str) -> String {
+        run_local(async move {
+            let (stream, runtime, _) =
+                ssr::render_to_stream_with_prefix_undisposed_with_context(
+                    suspense_page,
+                    |_| "".into(),
+                    move |cx| cx.set_app_id(app_id),
+                );
+            let html = stream.collect::<String>().await;
+            runtime.dispose();
+            html
+        })
+    }
+
+    // every `_key` in the ids, markers and fragment templates
+    fn keys(html: &str) -> HashSet<String> {
+        html.split(['"', ' ', '='])
+            .filter_map(|part| part.strip_prefix('_'))
+            .filter(|key| key.contains('-'))
+            .map(|key| key.trim_end_matches(['o', 'c', 'f']).to_string())
+            .collect()
+    }
+
+    let header = render("header");
+    let comments = render("comments");
+    let (header_keys, comments_keys) = (keys(&header), keys(&comments));
+    assert!(!header_keys.is_empty());
+    assert!(header_keys.iter().all(|key| key.starts_with("header-")));
+    assert!(comments_keys.iter().all(|key| key.starts_with("comments-")));
+    assert!(header_keys.is_disjoint(&comments_keys));
+    // the swap script of each app only finds its own fragment
+    assert!(header.contains("<!--suspense-open-header-"));
+    assert!(!header.contains("suspense-open-comments-"));
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_hydration_keys_tag_resources_created_before_any_key_is_used() {
+    use futures::StreamExt;
+    use leptos::*;
+
+    // the tag of the data serialized for a resource created in `f`, on a
+    // thread of its own
+    fn tag(f: fn(Scope)) -> String {
+        std::thread::spawn(move || {
+            run_local(async move {
+                let runtime = create_runtime();
+                let (cx, disposer) = raw_scope_and_disposer(runtime);
+                f(cx);
+                let (_, data) =
+                    cx.serialization_resolvers().next().await.unwrap();
+                disposer.dispose();
+                runtime.dispose();
+                data.split_once(':').unwrap().0.to_string()
+            })
+        })
+        .join()
+        .unwrap()
+    }
+
+    fn resource(cx: Scope) {
+        create_resource(cx, || (), |_| async { 0 });
+    }
+
+    // rendering anything makes resources tagged with the hydration key
+    _ = ssr::render_to_string(|cx| view! { cx, <p/> });
+
+    // a resource created before the thread's hydration key is used gets the
+    // same tag as one created after it's reset
+    let untouched = tag(resource);
+    let reset = tag(|cx| {
+        leptos_dom::HydrationCtx::reset_id();
+        resource(cx);
+    });
+    assert_eq!(untouched, reset);
+    // which differs from one created at another key
+    let other = tag(|cx| {
+        leptos_dom::HydrationCtx::id();
+        resource(cx);
+    });
+    assert_ne!(untouched, other);
+}
+
+#[test]
+fn ssr_static_dynamic_renders_the_current_component() {
+    use leptos::*;
+
+    let runtime = create_runtime();
+    let rendered = run_scope(runtime, |cx| {
+        let single = DynComponent::new(|cx| view! { cx, <p>"Single"</p> });
+        let pair = DynComponent::new(|cx| {
+            view! { cx, <dt>"Term"</dt><dd>"Definition"</dd> }
+        });
+        let (shown, set_shown) = create_signal(cx, single);
+        set_shown.set(pair);
+
+        view! { cx, <Dynamic component=move || shown.get()/> }
+            .into_view(cx)
+            .render_to_string(cx)
+            .to_string()
+    });
+
+    assert!(rendered.contains(">Term</dt>"), "{rendered}");
+    assert!(rendered.contains(">Definition</dd>"), "{rendered}");
+    assert!(!rendered.contains("Single"), "{rendered}");
+    // the instance's scope went away with the one it was rendered in
+    assert_eq!(runtime.stats().scopes, 0);
+    runtime.dispose();
+}
+
+#[test]
+fn ssr_static_portal_is_only_rendered_in_the_browser() {
+    use leptos::*;
+
+    let runtime = create_runtime();
+    let rendered = run_scope(runtime, |cx| {
+        view! { cx,
+            <main>
+                <p>"Before"</p>
+                <Portal><dialog>"Inside"</dialog></Portal>
+                <p>"After"</p>
+            </main>
+        }
+        .into_view(cx)
+        .render_to_string(cx)
+        .to_string()
+    });
+
+    assert!(!rendered.contains("Inside"), "{rendered}");
+    assert!(rendered.contains(">Before</p>"), "{rendered}");
+    assert!(rendered.contains(">After</p>"), "{rendered}");
+    runtime.dispose();
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_suspense_transition_renders_like_suspense() {
+    use leptos::*;
+
+    // the server only ever renders the first load, so a `<Transition/>`
+    // resolves to the same HTML as a `<Suspense/>`
+    fn render(transition: bool) -> String {
+        run_local(ssr::render_to_string_resolved(move |cx| {
+            let count = create_resource(cx, || (), |_| async { 42 });
+            let children = move |cx| {
+                view! { cx, <p>"Count: " {move || count.read(cx)}</p> }
+            };
+
+            if transition {
+                view! { cx,
+                    <Transition fallback=|| "Loading...">
+                        {children(cx)}
+                    </Transition>
+                }
+                .into_view(cx)
+            } else {
+                view! { cx,
+                    <Suspense fallback=|| "Loading...">
+                        {children(cx)}
+                    </Suspense>
+                }
+                .into_view(cx)
+            }
+        }))
+    }
+
+    let html = render(true);
+    assert!(html.contains("42"), "{html}");
+    assert!(!html.contains("Loading..."), "{html}");
+    assert_eq!(html, render(false));
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_suspense_ignores_fallback_timing() {
+    use leptos::*;
+    use std::time::Duration;
+
+    let render = |timed: bool| {
+        leptos_dom::HydrationCtx::reset_id();
+        run_scope(create_runtime(), move |cx| {
+            let count = create_resource(cx, || (), |_| async { 42 });
+            let children = move |cx| {
+                view! { cx, <p>"Count: " {move || count.read(cx)}</p> }
+            };
+            let view = if timed {
+                view! { cx,
+                    <Suspense
+                        fallback=|| "Loading..."
+                        fallback_delay=Duration::from_secs(1)
+                        fallback_min_duration=Duration::from_secs(1)
+                    >
+                        {children(cx)}
+                    </Suspense>
+                }
+                .into_view(cx)
+            } else {
+                view! { cx,
+                    <Suspense fallback=|| "Loading...">{children(cx)}</Suspense>
+                }
+                .into_view(cx)
+            };
+            view.render_to_string(cx).to_string()
+        })
+    };
+
+    // the fallback is sent right away, to be replaced when the data streams in
+    let html = run_local(async { render(true) });
+    assert!(html.contains("Loading..."), "{html}");
+    assert_eq!(html, run_local(async { render(false) }));
+}
@@ -88,10 +88,9 @@ fn Example(cx: Scope) -> impl IntoView {
     trace!("logging value of derived..., {}", derived.get());
   });
 
-  set_timeout(
-    move || set_value.update(|v| *v += 1),
-    std::time::Duration::from_millis(50),
-  );
+  set_timeout(cx, std::time::Duration::from_millis(50), move || {
+    set_value.update(|v| *v += 1)
+  });
 
   view! { cx,
     <h1>"Example"</h1>
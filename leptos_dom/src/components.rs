@@ -1,7 +1,9 @@
 mod dyn_child;
 mod each;
+mod each_animation;
 mod errors;
 mod fragment;
+mod island;
 mod unit;
 
 use crate::{
@@ -12,8 +14,10 @@ use crate::{
 use crate::{mount_child, prepare_to_move, MountKind, Mountable};
 pub use dyn_child::*;
 pub use each::*;
+pub use each_animation::*;
 pub use errors::*;
 pub use fragment::*;
+pub use island::*;
 use leptos_reactive::Scope;
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 use once_cell::unsync::OnceCell;
@@ -248,14 +252,30 @@ where
             children_fn,
         } = self;
 
-        let mut repr = ComponentRepr::new_with_id(name, id);
+        // if the server didn't render this component, render it from scratch,
+        // so that its children don't claim nodes from the rest of the page
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        let client_render = HydrationCtx::is_hydrating()
+            && !HydrationCtx::has_component(&id, &name);
+
+        let render = move || {
+            let mut repr = ComponentRepr::new_with_id(name, id);
 
-        // disposed automatically when the parent scope is disposed
-        let (child, _) = cx
-            .run_child_scope(|cx| cx.untrack(|| children_fn(cx).into_view(cx)));
+            // disposed automatically when the parent scope is disposed
+            let (child, _) = cx.run_child_scope(|cx| {
+                cx.untrack(|| children_fn(cx).into_view(cx))
+            });
 
-        repr.children.push(child);
+            repr.children.push(child);
+
+            repr.into_view(cx)
+        };
+
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        if client_render {
+            return HydrationCtx::client_render(render);
+        }
 
-        repr.into_view(cx)
+        render()
     }
 }
@@ -163,11 +163,20 @@ where
         // concrete inner function
         fn create_dyn_view(
             cx: Scope,
+            id: HydrationKey,
             component: DynChildRepr,
             child_fn: Box<dyn Fn() -> View>,
         ) -> DynChildRepr {
             #[cfg(all(target_arch = "wasm32", feature = "web"))]
             let closing = component.closing.node.clone();
+            #[cfg(all(
+                debug_assertions,
+                target_arch = "wasm32",
+                feature = "web"
+            ))]
+            let opening = component.opening.node.clone();
+            #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+            let _ = id;
 
             let child = component.child.clone();
 
@@ -188,9 +197,42 @@ where
                     #[cfg(debug_assertions)]
                     let _guard = span.enter();
 
-                    let (new_child, disposer) =
+                    let pending = HydrationCtx::pending_mismatches();
+                    let (mut new_child, mut disposer) =
                         cx.run_child_scope(|cx| child_fn().into_view(cx));
 
+                    // If hydrating the child found a mismatch, some of its
+                    // nodes were created instead of claimed, and are missing
+                    // from the page, so take out what the server rendered and
+                    // render it from scratch
+                    let recovered = prev_run.is_none()
+                        && HydrationCtx::is_hydrating()
+                        && HydrationCtx::pending_mismatches() > pending;
+                    if recovered {
+                        disposer.dispose();
+
+                        #[cfg(debug_assertions)]
+                        let first = opening.next_sibling();
+                        // without an opening marker, the first node is only
+                        // known if it was claimed
+                        #[cfg(not(debug_assertions))]
+                        let first =
+                            Some(new_child.get_opening_node()).filter(|node| {
+                                node.parent_node() == closing.parent_node()
+                            });
+                        HydrationCtx::recover_subtree(
+                            &id, first, &closing, pending,
+                        );
+
+                        (new_child, disposer) =
+                            HydrationCtx::client_render(|| {
+                                cx.run_child_scope(|cx| {
+                                    child_fn().into_view(cx)
+                                })
+                            });
+                        mount_child(MountKind::Before(&closing), &new_child);
+                    }
+
                     let mut child_borrow = child.borrow_mut();
 
                     // Is this at least the second time we are loading a child?
@@ -216,12 +258,23 @@ where
                         let ret = if let Some(prev_t) = prev_t {
                             // Here, our child is also a text node
                             if let Some(new_t) = new_child.get_text() {
-                                if !was_child_moved && child != new_child {
-                                    prev_t
-                                        .unchecked_ref::<web_sys::Text>()
-                                        .set_data(&new_t.content);
-
-                                    **child_borrow = Some(new_child);
+                                if !was_child_moved {
+                                    // keep the same node, and only write to
+                                    // it if the text changed
+                                    let changed =
+                                        child.get_text().map(|t| &t.content)
+                                            != Some(&new_t.content);
+                                    if changed {
+                                        prev_t
+                                            .unchecked_ref::<web_sys::Text>()
+                                            .set_data(&new_t.content);
+                                    }
+
+                                    **child_borrow =
+                                        Some(View::Text(crate::Text {
+                                            node: prev_t.clone(),
+                                            content: new_t.content.clone(),
+                                        }));
 
                                     (Some(prev_t), disposer)
                                 } else {
@@ -297,29 +350,56 @@ where
                     else {
                         // We need to remove the text created from SSR
                         if HydrationCtx::is_hydrating()
+                            && !recovered
                             && new_child.get_text().is_some()
                         {
-                            let t = closing
-                                .previous_sibling()
-                                .unwrap()
-                                .unchecked_into::<web_sys::Element>();
+                            // the browser doesn't create a node for empty
+                            // text, so this may be a marker instead
+                            let prev = closing.previous_sibling().unwrap();
+                            let server_text = (prev.node_type()
+                                == web_sys::Node::TEXT_NODE)
+                                .then_some(prev);
 
                             // See note on ssr.rs when matching on `DynChild`
                             // for more details on why we need to do this for
                             // release
                             if !cfg!(debug_assertions) {
-                                t.previous_sibling()
-                                    .unwrap()
-                                    .unchecked_into::<web_sys::Element>()
-                                    .remove();
+                                let marker = match &server_text {
+                                    Some(t) => t.previous_sibling(),
+                                    None => closing.previous_sibling(),
+                                };
+                                if let Some(marker) = marker.filter(|marker| {
+                                    marker.node_type()
+                                        == web_sys::Node::COMMENT_NODE
+                                }) {
+                                    marker
+                                        .unchecked_into::<web_sys::Element>()
+                                        .remove();
+                                }
                             }
 
-                            t.remove();
-
-                            mount_child(
-                                MountKind::Before(&closing),
-                                &new_child,
-                            );
+                            // claim the server's text node, so that later
+                            // updates write to the node that's on the page
+                            if let Some(t) = server_text {
+                                let content = new_child
+                                    .get_text()
+                                    .map(|t| t.content.clone())
+                                    .unwrap_or_default();
+                                if t.text_content().as_deref() != Some(&content)
+                                {
+                                    t.unchecked_ref::<web_sys::Text>()
+                                        .set_data(&content);
+                                }
+                                new_child = View::Text(crate::Text {
+                                    node: t,
+                                    content,
+                                });
+                            } else {
+                                mount_child(
+                                    MountKind::Before(&closing),
+                                    &new_child,
+                                );
+                            }
                         }
 
                         // If we are not hydrating, we simply mount the child
@@ -354,9 +434,10 @@ where
         // monomorphized outer function
         let Self { id, child_fn } = self;
 
-        let component = DynChildRepr::new_with_id(id);
+        let component = DynChildRepr::new_with_id(id.clone());
         let component = create_dyn_view(
             cx,
+            id,
             component,
             Box::new(move || child_fn().into_view(cx)),
         );
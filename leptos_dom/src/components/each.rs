@@ -1,8 +1,13 @@
-use crate::{hydration::HydrationCtx, Comment, CoreComponent, IntoView, View};
+use crate::{
+    hydration::HydrationCtx, Comment, CoreComponent, EachAnimation, IntoView,
+    View,
+};
 use cfg_if::cfg_if;
 cfg_if! {
   if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
-    use crate::{mount_child, prepare_to_move, MountKind, Mountable, RANGE};
+    use crate::{
+      mount_child, prepare_to_move, BoundingRect, MountKind, Mountable, RANGE,
+    };
     use once_cell::unsync::OnceCell;
     use leptos_reactive::create_effect;
     use rustc_hash::FxHasher;
@@ -299,6 +304,94 @@ impl EachItem {
             node.unchecked_into::<web_sys::Element>().remove();
         }
     }
+
+    /// The first element among the item's nodes, which is what gets
+    /// measured and animated.
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    fn element(&self) -> Option<web_sys::Element> {
+        if self.closing.is_none() {
+            return self.child.get_mountable_node().dyn_into().ok();
+        }
+
+        let end = self.get_closing_node();
+        let mut node = Some(self.get_opening_node());
+        while let Some(current) = node {
+            if current == end {
+                break;
+            }
+            if let Some(el) = current.dyn_ref::<web_sys::Element>() {
+                return Some(el.clone());
+            }
+            node = current.next_sibling();
+        }
+        None
+    }
+}
+
+/// The items that have been removed, but whose exit animations haven't
+/// finished yet.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+#[derive(Clone, Default)]
+struct Exits {
+    items: Rc<RefCell<Vec<(usize, EachItem)>>>,
+    next_id: Rc<std::cell::Cell<usize>>,
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+impl Exits {
+    /// Removes `item`, after its exit animation if there is one.
+    fn remove(&self, item: EachItem, animation: Option<&EachAnimation>) {
+        item.cx.dispose();
+
+        let on_exit =
+            animation.and_then(|animation| animation.on_exit.as_ref());
+        let done = on_exit
+            .zip(item.element())
+            .and_then(|(on_exit, el)| on_exit(&el));
+        let done = match done {
+            Some(done) => done,
+            None => {
+                item.prepare_for_move();
+                return;
+            }
+        };
+
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        self.items.borrow_mut().push((id, item));
+
+        let items = Rc::clone(&self.items);
+        wasm_bindgen_futures::spawn_local(async move {
+            _ = wasm_bindgen_futures::JsFuture::from(done).await;
+            // the list may have been disposed in the meantime, which removed
+            // the item already
+            let item = {
+                let mut items = items.borrow_mut();
+                items
+                    .iter()
+                    .position(|(item_id, _)| *item_id == id)
+                    .map(|idx| items.remove(idx).1)
+            };
+            if let Some(item) = item {
+                item.prepare_for_move();
+            }
+        });
+    }
+
+    /// Removes every item that's still exiting right away.
+    fn finish(&self) {
+        for (_, item) in self.items.take() {
+            item.prepare_for_move();
+        }
+    }
+}
+
+/// How the rows of an [`Each`] are animated, along with the ones that are
+/// still exiting.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+struct RowAnimations {
+    animation: Option<EachAnimation>,
+    exits: Exits,
 }
 
 /// A component for efficiently rendering an iterable.
@@ -315,6 +408,7 @@ where
     pub(crate) items_fn: IF,
     pub(crate) each_fn: EF,
     key_fn: KF,
+    animation: Option<EachAnimation>,
 }
 
 impl<IF, I, T, EF, N, KF, K> Each<IF, I, T, EF, N, KF, K>
@@ -333,8 +427,15 @@ where
             items_fn,
             each_fn,
             key_fn,
+            animation: None,
         }
     }
+
+    /// Animates the rows as they're moved, added and removed.
+    pub fn animated(mut self, animation: EachAnimation) -> Self {
+        self.animation = Some(animation);
+        self
+    }
 }
 
 impl<IF, I, T, EF, N, KF, K> IntoView for Each<IF, I, T, EF, N, KF, K>
@@ -356,10 +457,24 @@ where
             items_fn,
             each_fn,
             key_fn,
+            animation,
         } = self;
 
         #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
-        let _ = key_fn;
+        let _ = (key_fn, animation);
+
+        // rows that are still exiting when the list is disposed are removed
+        // along with it
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        let animations = RowAnimations {
+            animation,
+            exits: Exits::default(),
+        };
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        leptos_reactive::on_cleanup(cx, {
+            let exits = animations.exits.clone();
+            move || exits.finish()
+        });
 
         let component = EachRepr::default();
 
@@ -371,13 +486,8 @@ where
           if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
             create_effect(cx, move |prev_hash_run: Option<HashRun<FxIndexSet<K>>>| {
               let mut children_borrow = children.borrow_mut();
-
-              #[cfg(all(target_arch = "wasm32", feature = "web"))]
-              let opening = if let Some(Some(child)) = children_borrow.get(0) {
-                child.get_opening_node()
-              } else {
-                closing.clone()
-              };
+              // the rows rendered along with the list don't enter
+              let is_first_run = prev_hash_run.is_none();
 
                 let items_iter = items_fn().into_iter();
 
@@ -400,13 +510,12 @@ where
                     apply_cmds(
                         cx,
                         #[cfg(all(target_arch = "wasm32", feature = "web"))]
-                        &opening,
-                        #[cfg(all(target_arch = "wasm32", feature = "web"))]
                         &closing,
                         cmds,
                         &mut children_borrow,
                         items,
-                        &each_fn
+                        &each_fn,
+                        &animations,
                     );
                     return HashRun(hashed_items);
                 }
@@ -434,6 +543,15 @@ where
                 .before_with_node_1(&fragment)
                 .expect("before to not err");
 
+                #[cfg(all(target_arch = "wasm32", feature = "web"))]
+                if let Some(on_enter) = animations.animation.as_ref().and_then(|a| a.on_enter.as_ref()) {
+                  if !is_first_run {
+                    for el in children_borrow.iter().flatten().filter_map(EachItem::element) {
+                      on_enter(&el);
+                    }
+                  }
+                }
+
               HashRun(hashed_items)
             });
           } else {
@@ -477,72 +595,49 @@ fn diff<K: Eq + Hash>(from: &FxIndexSet<K>, to: &FxIndexSet<K>) -> Diff {
     }
 
     // Get removed items
-    let mut removed = from.difference(to);
-
-    let removed_cmds = removed
-        .clone()
+    let removed_cmds = from
+        .difference(to)
         .map(|k| from.get_full(k).unwrap().0)
         .map(|idx| DiffOpRemove { at: idx });
 
     // Get added items
-    let mut added = to.difference(from);
-
-    let added_cmds =
-        added
-            .clone()
-            .map(|k| to.get_full(k).unwrap().0)
-            .map(|idx| DiffOpAdd {
-                at: idx,
-                mode: Default::default(),
-            });
-
-    // Get moved items
-    let mut normalized_idx = 0;
-    let mut move_cmds = Vec::new();
-    let mut added_idx = added.next().map(|k| to.get_full(k).unwrap().0);
-    let mut removed_idx = removed.next().map(|k| from.get_full(k).unwrap().0);
-
-    for (idx, k) in to.iter().enumerate() {
-        if let Some(added_idx) = added_idx.as_mut().filter(|r_i| **r_i == idx) {
-            if let Some(next_added) =
-                added.next().map(|k| to.get_full(k).unwrap().0)
-            {
-                *added_idx = next_added;
-
-                normalized_idx = usize::wrapping_sub(normalized_idx, 1);
-            }
-        }
-
-        if let Some(removed_idx) =
-            removed_idx.as_mut().filter(|r_i| **r_i == idx)
-        {
-            normalized_idx = normalized_idx.wrapping_add(1);
-
-            if let Some(next_removed) =
-                removed.next().map(|k| from.get_full(k).unwrap().0)
-            {
-                *removed_idx = next_removed;
-            }
-        }
-
-        if let Some((from_idx, _)) = from.get_full(k) {
-            if from_idx != normalized_idx {
-                move_cmds.push(DiffOpMove {
-                    from: from_idx,
-                    to: idx,
-                    move_in_dom: true,
-                });
-            } else if from_idx != idx {
-                move_cmds.push(DiffOpMove {
-                    from: from_idx,
-                    to: idx,
-                    move_in_dom: false,
-                });
-            }
-        }
+    let added_cmds = to
+        .difference(from)
+        .map(|k| to.get_full(k).unwrap().0)
+        .map(|idx| DiffOpAdd {
+            at: idx,
+            mode: Default::default(),
+        });
 
-        normalized_idx = normalized_idx.wrapping_add(1);
-    }
+    // Get moved items. The longest run of kept items that are still in
+    // the same order stays where it is in the DOM, and only the others
+    // are moved around it.
+    let kept = to
+        .iter()
+        .enumerate()
+        .filter_map(|(to_idx, k)| {
+            from.get_index_of(k).map(|from_idx| (from_idx, to_idx))
+        })
+        .collect::<Vec<_>>();
+    let in_place = longest_increasing_subsequence(
+        &kept
+            .iter()
+            .map(|(from_idx, _)| *from_idx)
+            .collect::<Vec<_>>(),
+    );
+
+    let move_cmds = kept
+        .into_iter()
+        .zip(in_place)
+        .filter(|((from_idx, to_idx), in_place)| {
+            !in_place || from_idx != to_idx
+        })
+        .map(|((from, to), in_place)| DiffOpMove {
+            from,
+            to,
+            move_in_dom: !in_place,
+        })
+        .collect();
 
     let mut diffs = Diff {
         removed: removed_cmds.collect(),
@@ -556,6 +651,40 @@ fn diff<K: Eq + Hash>(from: &FxIndexSet<K>, to: &FxIndexSet<K>) -> Diff {
     diffs
 }
 
+/// Marks the values that make up a longest strictly increasing subsequence
+/// of `values`.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn longest_increasing_subsequence(values: &[usize]) -> Vec<bool> {
+    // `tails[len]` is the index of the smallest value that ends an
+    // increasing subsequence of length `len + 1`
+    let mut tails: Vec<usize> = Vec::with_capacity(values.len());
+    let mut prev = vec![usize::MAX; values.len()];
+
+    for (idx, value) in values.iter().enumerate() {
+        let len = tails.partition_point(|tail| values[*tail] < *value);
+
+        if len > 0 {
+            prev[idx] = tails[len - 1];
+        }
+
+        if len == tails.len() {
+            tails.push(idx);
+        } else {
+            tails[len] = idx;
+        }
+    }
+
+    let mut in_subsequence = vec![false; values.len()];
+    let mut idx = tails.last().copied().unwrap_or(usize::MAX);
+
+    while idx != usize::MAX {
+        in_subsequence[idx] = true;
+        idx = prev[idx];
+    }
+
+    in_subsequence
+}
+
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 fn apply_opts<K: Eq + Hash>(
     from: &FxIndexSet<K>,
@@ -637,18 +766,41 @@ impl Default for DiffOpAddMode {
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 fn apply_cmds<T, EF, N>(
     cx: Scope,
-    opening: &web_sys::Node,
     closing: &web_sys::Node,
     mut cmds: Diff,
     children: &mut Vec<Option<EachItem>>,
     mut items: Vec<Option<T>>,
     each_fn: &EF,
+    animations: &RowAnimations,
 ) where
     EF: Fn(Scope, T) -> N,
     N: IntoView,
 {
+    let RowAnimations { animation, exits } = animations;
+    let animation = animation.as_ref();
     let range = RANGE.with(|range| (*range).clone());
 
+    let opening = if let Some(Some(child)) = children.first() {
+        child.get_opening_node()
+    } else {
+        closing.clone()
+    };
+
+    // where each row is before anything is moved, so the ones that end up
+    // somewhere else can be animated from there
+    let animate = animation.and_then(|animation| animation.animate.as_ref());
+    let first_rects = animate.map(|_| {
+        children
+            .iter()
+            .map(|child| {
+                let el = child.as_ref()?.element()?;
+                let rect = BoundingRect::of(&el);
+                Some((el, rect))
+            })
+            .collect::<Vec<_>>()
+    });
+    let mut entered = Vec::new();
+
     // Resize children if needed
     if cmds.added.len().checked_sub(cmds.removed.len()).is_some() {
         let target_size = children.len()
@@ -668,9 +820,25 @@ fn apply_cmds<T, EF, N>(
     // 2. Removed
     // 3. Moved
     // 4. Add
+    if cmds.clear && animation.is_some_and(|a| a.on_exit.is_some()) {
+        // the rows may stay in the document for a while, so they have to
+        // be removed one by one
+        cmds.clear = false;
+        cmds.removed =
+            (0..children.len()).map(|at| DiffOpRemove { at }).collect();
+    }
+
     if cmds.clear {
         cmds.removed.clear();
 
+        // dispose of the items first, so that their cleanups run while
+        // they're still in the document
+        for item in children.iter_mut() {
+            if let Some(item) = item.take() {
+                item.cx.dispose();
+            }
+        }
+
         if opening.previous_sibling().is_none()
             && closing.next_sibling().is_none()
         {
@@ -681,22 +849,27 @@ fn apply_cmds<T, EF, N>(
             parent.set_text_content(Some(""));
 
             #[cfg(debug_assertions)]
-            parent.append_with_node_2(opening, closing).unwrap();
+            parent.append_with_node_2(&opening, closing).unwrap();
 
             #[cfg(not(debug_assertions))]
             parent.append_with_node_1(closing).unwrap();
         } else {
-            range.set_start_before(opening).unwrap();
+            range.set_start_before(&opening).unwrap();
             range.set_end_before(closing).unwrap();
 
             range.delete_contents().unwrap();
         }
     }
 
+    let mut removed =
+        vec![cmds.clear; first_rects.as_ref().map_or(0, Vec::len)];
     for DiffOpRemove { at } in cmds.removed {
         let item_to_remove = std::mem::take(&mut children[at]).unwrap();
 
-        item_to_remove.prepare_for_move();
+        if let Some(removed) = removed.get_mut(at) {
+            *removed = true;
+        }
+        exits.remove(item_to_remove, animation);
     }
 
     for DiffOpMove {
@@ -714,6 +887,16 @@ fn apply_cmds<T, EF, N>(
         items_to_move.push((move_in_dom, to, item));
     }
 
+    // Items that keep their place in the DOM go back in first, so that
+    // everything else can be mounted relative to them
+    let (in_place, items_to_move): (Vec<_>, Vec<_>) = items_to_move
+        .into_iter()
+        .partition(|(move_in_dom, _, _)| !move_in_dom);
+
+    for (_, to, each_item) in in_place {
+        children[to] = Some(each_item);
+    }
+
     for DiffOpAdd { at, mode } in cmds.added {
         let item = items[at].take().unwrap();
 
@@ -737,22 +920,41 @@ fn apply_cmds<T, EF, N>(
             DiffOpAddMode::_Prepend => todo!(),
         }
 
+        entered.push(at);
         children[at] = Some(each_item);
     }
 
-    for (move_in_dom, to, each_item) in items_to_move {
-        if move_in_dom {
-            let opening = children
-                .get_next_closest_mounted_sibling(to + 1, closing.to_owned());
+    for (_, to, each_item) in items_to_move {
+        let opening = children
+            .get_next_closest_mounted_sibling(to + 1, closing.to_owned());
 
-            mount_child(MountKind::Before(&opening), &each_item);
-        }
+        mount_child(MountKind::Before(&opening), &each_item);
 
         children[to] = Some(each_item);
     }
 
+    if let Some(on_enter) = animation.and_then(|a| a.on_enter.as_ref()) {
+        for at in entered {
+            if let Some(el) = children[at].as_ref().and_then(EachItem::element)
+            {
+                on_enter(&el);
+            }
+        }
+    }
+
     // Now, remove the holes that might have been left from removing
     // items
     #[allow(unstable_name_collisions)]
     children.drain_filter(|c| c.is_none());
+
+    if let (Some(animate), Some(first_rects)) = (animate, first_rects) {
+        for (rect, removed) in first_rects.into_iter().zip(removed) {
+            if let (Some((el, from)), false) = (rect, removed) {
+                let to = BoundingRect::of(&el);
+                if (from.left, from.top) != (to.left, to.top) {
+                    animate(&el, &from, &to);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,147 @@
+use std::{fmt, rc::Rc, time::Duration};
+
+/// Callbacks that let an [`Each`](crate::Each) animate its rows as they are
+/// moved, added and removed. They only run in the browser, and never for the
+/// rows that are rendered along with the list itself.
+///
+/// Rows that are moved can be animated with the
+/// [FLIP](https://aerotwist.com/blog/flip-your-animations/) technique: the
+/// position of each row is measured before the DOM is updated, and
+/// [`animate`](EachAnimation::animate) is called with the old and new
+/// positions of every row that ended up somewhere else.
+/// [`EachAnimation::flip`] does this with a CSS transition.
+#[derive(Clone, Default)]
+pub struct EachAnimation {
+    pub(crate) animate: Option<AnimateFn>,
+    pub(crate) on_enter: Option<OnEnterFn>,
+    pub(crate) on_exit: Option<OnExitFn>,
+}
+
+/// Animates a row from where it was to where it is now.
+type AnimateFn = Rc<dyn Fn(&web_sys::Element, &BoundingRect, &BoundingRect)>;
+
+/// Starts a row's enter animation.
+type OnEnterFn = Rc<dyn Fn(&web_sys::Element)>;
+
+/// Starts a row's exit animation, returning a promise that resolves once the
+/// row can be removed.
+type OnExitFn = Rc<dyn Fn(&web_sys::Element) -> Option<js_sys::Promise>>;
+
+impl fmt::Debug for EachAnimation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EachAnimation")
+            .field("animate", &self.animate.is_some())
+            .field("on_enter", &self.on_enter.is_some())
+            .field("on_exit", &self.on_exit.is_some())
+            .finish()
+    }
+}
+
+/// Where a row was on the page, relative to the viewport, as returned by
+/// [`getBoundingClientRect()`](https://developer.mozilla.org/en-US/docs/Web/API/Element/getBoundingClientRect).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoundingRect {
+    /// The distance from the left edge of the viewport.
+    pub left: f64,
+    /// The distance from the top of the viewport.
+    pub top: f64,
+    /// The width of the row.
+    pub width: f64,
+    /// The height of the row.
+    pub height: f64,
+}
+
+impl EachAnimation {
+    /// Animates the rows that were moved with a CSS transition of the given
+    /// duration, which slides each of them from where it was to where it is.
+    pub fn flip(duration: Duration) -> Self {
+        Self::default()
+            .animate(move |el, from, to| flip(el, from, to, duration))
+    }
+
+    /// Calls `animate` with the element of each row that was moved, where it
+    /// was before the DOM was updated, and where it is now.
+    pub fn animate(
+        mut self,
+        animate: impl Fn(&web_sys::Element, &BoundingRect, &BoundingRect) + 'static,
+    ) -> Self {
+        self.animate = Some(Rc::new(animate));
+        self
+    }
+
+    /// Calls `on_enter` with the element of each row that is added once it
+    /// has been inserted.
+    pub fn on_enter(
+        mut self,
+        on_enter: impl Fn(&web_sys::Element) + 'static,
+    ) -> Self {
+        self.on_enter = Some(Rc::new(on_enter));
+        self
+    }
+
+    /// Calls `on_exit` with the element of each row that is removed. The
+    /// row's scope is disposed right away, but if `on_exit` returns a
+    /// [`Promise`](js_sys::Promise), its nodes are only removed once the
+    /// promise settles, e.g., when
+    /// [`Animation.finished`](https://developer.mozilla.org/en-US/docs/Web/API/Animation/finished)
+    /// does. Disposing the list removes them right away.
+    pub fn on_exit(
+        mut self,
+        on_exit: impl Fn(&web_sys::Element) -> Option<js_sys::Promise> + 'static,
+    ) -> Self {
+        self.on_exit = Some(Rc::new(on_exit));
+        self
+    }
+}
+
+/// Slides `el` from `from` to `to` with a CSS transition of the given
+/// duration, by starting it off translated back to where it was. This is what
+/// [`EachAnimation::flip`] does for each row that was moved.
+pub fn flip(
+    el: &web_sys::Element,
+    from: &BoundingRect,
+    to: &BoundingRect,
+    duration: Duration,
+) {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        use wasm_bindgen::JsCast;
+
+        let el = match el.dyn_ref::<web_sys::HtmlElement>() {
+            Some(el) => el,
+            None => return,
+        };
+        let style = el.style();
+        let (dx, dy) = (from.left - to.left, from.top - to.top);
+
+        _ = style.set_property("transition", "none");
+        _ = style
+            .set_property("transform", &format!("translate({dx}px, {dy}px)"));
+        // reading the layout applies the transform before the transition is
+        // set, so the row starts from where it was
+        _ = el.offset_width();
+        _ = style.set_property(
+            "transition",
+            &format!("transform {}ms", duration.as_millis()),
+        );
+        _ = style.remove_property("transform");
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = (el, from, to, duration);
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+impl BoundingRect {
+    pub(crate) fn of(el: &web_sys::Element) -> Self {
+        let rect = el.get_bounding_client_rect();
+        Self {
+            left: rect.left(),
+            top: rect.top(),
+            width: rect.width(),
+            height: rect.height(),
+        }
+    }
+}
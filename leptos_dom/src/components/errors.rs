@@ -7,6 +7,24 @@ use std::{collections::HashMap, error::Error, sync::Arc};
 #[derive(Debug, Clone, Default)]
 pub struct Errors(HashMap<ErrorKey, Arc<dyn Error + Send + Sync>>);
 
+/// An error that was reported to an `<ErrorBoundary/>` while rendering on the
+/// server, of which only the message is sent to the client.
+///
+/// While hydrating, a boundary that showed its fallback on the server starts
+/// out with these in place of the original errors, so that it shows the
+/// fallback again rather than flashing its children. Each is replaced or
+/// removed when the part of the view that reported it renders on the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerRenderedError(pub String);
+
+impl std::fmt::Display for ServerRenderedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for ServerRenderedError {}
+
 /// A unique key for an error that occurs at a particular location in the user interface.
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub struct ErrorKey(String);
@@ -159,4 +177,36 @@ impl Errors {
     pub fn iter(&self) -> Iter<'_> {
         Iter(self.0.iter())
     }
+
+    #[doc(hidden)]
+    /// Serializes the key and message of each error, to be read back with
+    /// [`Errors::from_serialized`].
+    pub fn to_serialized(&self) -> String {
+        let errors = self
+            .0
+            .iter()
+            .map(|(key, error)| (&key.0, error.to_string()))
+            .collect::<Vec<_>>();
+        serde_json::to_string(&errors).unwrap_or_default()
+    }
+
+    #[doc(hidden)]
+    /// Reads errors serialized with [`Errors::to_serialized`], each as a
+    /// [`ServerRenderedError`].
+    pub fn from_serialized(serialized: &str) -> Option<Errors> {
+        let errors =
+            serde_json::from_str::<Vec<(String, String)>>(serialized).ok()?;
+        Some(Errors(
+            errors
+                .into_iter()
+                .map(|(key, message)| {
+                    (
+                        ErrorKey(key),
+                        Arc::new(ServerRenderedError(message))
+                            as Arc<dyn Error + Send + Sync>,
+                    )
+                })
+                .collect(),
+        ))
+    }
 }
@@ -20,6 +20,12 @@ where
 }
 
 /// Represents a group of [`views`](View).
+///
+/// This is what `view!` returns when it has several root nodes, so that a
+/// component can render siblings, like `<dt>` and `<dd>` pairs, without a
+/// wrapper element. In the DOM, the nodes are kept between comment markers,
+/// so the group is inserted, moved or removed as a unit, while each child
+/// still updates on its own.
 #[derive(Debug, Clone)]
 pub struct Fragment {
     id: HydrationKey,
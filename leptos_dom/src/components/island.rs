@@ -0,0 +1,306 @@
+use crate::{
+    html::{self, Custom},
+    hydration::{HydrationCtx, HydrationKey},
+    IntoView, View,
+};
+use leptos_reactive::Scope;
+
+/// The tag of the element an [island] is rendered in.
+pub const ISLAND_TAG: &str = "leptos-island";
+
+/// When [hydrate_islands] hydrates an [island].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HydrationStrategy {
+    /// Right away.
+    #[default]
+    Eager,
+    /// When the island scrolls into view.
+    OnVisible,
+    /// When the pointer moves over or presses the island, or something in
+    /// it gets focus. The event that triggered hydration goes on to reach
+    /// the island's own event listeners.
+    OnInteraction,
+    /// When the browser is idle.
+    OnIdle,
+}
+
+impl HydrationStrategy {
+    fn as_attribute(self) -> &'static str {
+        match self {
+            HydrationStrategy::Eager => "eager",
+            HydrationStrategy::OnVisible => "visible",
+            HydrationStrategy::OnInteraction => "interaction",
+            HydrationStrategy::OnIdle => "idle",
+        }
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    fn from_attribute(value: Option<&str>) -> Self {
+        match value {
+            Some("visible") => HydrationStrategy::OnVisible,
+            Some("interaction") => HydrationStrategy::OnInteraction,
+            Some("idle") => HydrationStrategy::OnIdle,
+            _ => HydrationStrategy::Eager,
+        }
+    }
+}
+
+/// Renders `f` as an island: an interactive part of an otherwise static page.
+/// A page made of islands is hydrated with [hydrate_islands] instead of
+/// [mount_to_body](crate::mount_to_body), which only hydrates the islands, so
+/// the code for the rest of the page never has to run in the browser.
+///
+/// The island is rendered in a `<leptos-island>` element, with its own
+/// [hydration keys](HydrationKey::root), and `name` tells [hydrate_islands]
+/// which view to hydrate it with. Only the [Resource](leptos_reactive::Resource)s
+/// created inside islands are sent to the client. See
+/// [Scope::with_island].
+pub fn island<F, N>(cx: Scope, name: &'static str, f: F) -> View
+where
+    F: FnOnce(Scope) -> N,
+    N: IntoView,
+{
+    island_with_strategy(cx, name, HydrationStrategy::Eager, f)
+}
+
+/// Renders `f` as an [island] that is hydrated according to `strategy`, e.g.,
+/// only once it scrolls into view.
+pub fn island_with_strategy<F, N>(
+    cx: Scope,
+    name: &'static str,
+    strategy: HydrationStrategy,
+    f: F,
+) -> View
+where
+    F: FnOnce(Scope) -> N,
+    N: IntoView,
+{
+    // keys are unique per island, even if the same one is used twice
+    let key = format!(
+        "island_{}",
+        HydrationCtx::id().to_string().replace('-', "_")
+    );
+    let (content, resources) =
+        HydrationCtx::with_key(HydrationKey::root(&key), || {
+            cx.with_island(|| f(cx).into_view(cx))
+        });
+
+    let mut el = html::custom(cx, Custom::new(ISLAND_TAG))
+        .attr("data-island", name)
+        .attr("data-key", key)
+        .attr(
+            "data-resources",
+            serde_json::to_string(&resources).unwrap_or_default(),
+        );
+    if strategy != HydrationStrategy::Eager {
+        el = el.attr("data-hydrate", strategy.as_attribute());
+    }
+    el.child(content).into_view(cx)
+}
+
+/// The islands found by [hydrate_islands] that are still waiting for their
+/// [HydrationStrategy] to trigger.
+pub struct PendingIslands {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    cancel: Vec<Box<dyn FnOnce()>>,
+}
+
+impl PendingIslands {
+    /// Stops waiting for the islands that haven't been hydrated yet, and
+    /// removes the observers and event listeners that were waiting for them.
+    /// Islands that were already hydrated are left alone.
+    pub fn dispose(self) {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        for cancel in self.cancel {
+            cancel();
+        }
+    }
+}
+
+impl std::fmt::Debug for PendingIslands {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PendingIslands").finish()
+    }
+}
+
+/// Hydrates each [island] on the page, and nothing else. `f` is called with
+/// the name of each island, in a new scope, and should return the same view
+/// the server rendered with that name, or `None` if there isn't one. Islands
+/// inside another island are hydrated along with it.
+///
+/// Each island is hydrated when its [HydrationStrategy] says so. Call
+/// [dispose](PendingIslands::dispose) on the result to stop waiting for the
+/// ones that haven't been.
+pub fn hydrate_islands<F>(f: F) -> PendingIslands
+where
+    F: Fn(Scope, &str) -> Option<View> + Clone + 'static,
+{
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        use std::{cell::RefCell, rc::Rc};
+        use wasm_bindgen::JsCast;
+
+        let found = crate::document().get_elements_by_tag_name(ISLAND_TAG);
+        let islands = (0..found.length())
+            .filter_map(|idx| found.item(idx))
+            .filter(|el| {
+                el.parent_element()
+                    .and_then(|parent| {
+                        parent.closest(ISLAND_TAG).ok().flatten()
+                    })
+                    .is_none()
+            })
+            .collect::<Vec<_>>();
+
+        let mut cancel = Vec::<Box<dyn FnOnce()>>::new();
+        for el in islands {
+            let name = el.get_attribute("data-island").unwrap_or_default();
+            let Some(key) = el.get_attribute("data-key") else {
+                crate::warn!(
+                    "[hydrate_islands] The island {name:?} has no key, so it \
+                     can't be hydrated."
+                );
+                continue;
+            };
+            let resources = el
+                .get_attribute("data-resources")
+                .and_then(|ids| serde_json::from_str(&ids).ok())
+                .unwrap_or_default();
+            let strategy = HydrationStrategy::from_attribute(
+                el.get_attribute("data-hydrate").as_deref(),
+            );
+
+            // taken by whichever comes first: the trigger, or `dispose`
+            let hydrate: Rc<OnceCallback> =
+                Rc::new(RefCell::new(Some(Box::new({
+                    let f = f.clone();
+                    let el = el.clone();
+                    move || {
+                        _ = crate::hydrate_to(
+                            el.unchecked_ref(),
+                            &key,
+                            move |cx| {
+                                cx.set_island_resources(resources);
+                                f(cx, &name).unwrap_or_else(|| {
+                                    crate::warn!(
+                                        "[hydrate_islands] There is no island \
+                                         named {name:?}, so it was left as it \
+                                         is."
+                                    );
+                                    View::default()
+                                })
+                            },
+                        )
+                    }
+                }))));
+            let trigger = {
+                let hydrate = Rc::clone(&hydrate);
+                move || run_once(&hydrate)
+            };
+
+            match strategy {
+                HydrationStrategy::Eager => trigger(),
+                HydrationStrategy::OnIdle => {
+                    crate::helpers::request_idle_callback(trigger);
+                    cancel.push(Box::new(move || {
+                        hydrate.borrow_mut().take();
+                    }));
+                }
+                HydrationStrategy::OnVisible => {
+                    let callback = wasm_bindgen::closure::Closure::<
+                        dyn Fn(js_sys::Array, web_sys::IntersectionObserver),
+                    >::new(
+                        move |entries: js_sys::Array,
+                              observer: web_sys::IntersectionObserver| {
+                            let visible = entries.iter().any(|entry| {
+                                entry
+                                    .unchecked_into::<web_sys::IntersectionObserverEntry>()
+                                    .is_intersecting()
+                            });
+                            if visible {
+                                observer.disconnect();
+                                trigger();
+                            }
+                        },
+                    )
+                    .into_js_value();
+                    match web_sys::IntersectionObserver::new(
+                        callback.unchecked_ref(),
+                    ) {
+                        Ok(observer) => {
+                            observer.observe(&el);
+                            cancel.push(Box::new(move || {
+                                observer.disconnect();
+                                hydrate.borrow_mut().take();
+                            }));
+                        }
+                        // without IntersectionObserver, there's no way to
+                        // know, so hydrate it now
+                        Err(_) => run_once(&hydrate),
+                    }
+                }
+                HydrationStrategy::OnInteraction => {
+                    const EVENTS: [&str; 3] =
+                        ["pointerover", "pointerdown", "focusin"];
+
+                    let stop: Rc<OnceCallback> = Default::default();
+                    // hydrating while the event is still being captured
+                    // means the listeners it creates receive it too
+                    let listener = wasm_bindgen::closure::Closure::<
+                        dyn Fn(web_sys::Event),
+                    >::new({
+                        let stop = Rc::clone(&stop);
+                        move |_| {
+                            run_once(&stop);
+                            trigger();
+                        }
+                    })
+                    .into_js_value()
+                    .unchecked_into::<js_sys::Function>();
+                    for event in EVENTS {
+                        _ = el.add_event_listener_with_callback_and_bool(
+                            event, &listener, true,
+                        );
+                    }
+                    *stop.borrow_mut() = Some(Box::new(move || {
+                        for event in EVENTS {
+                            _ = el
+                                .remove_event_listener_with_callback_and_bool(
+                                    event, &listener, true,
+                                );
+                        }
+                    }));
+                    cancel.push(Box::new(move || {
+                        run_once(&stop);
+                        hydrate.borrow_mut().take();
+                    }));
+                }
+            }
+        }
+
+        PendingIslands { cancel }
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = f;
+        crate::warn!(
+            "`hydrate_islands` should not be called outside the browser."
+        );
+        PendingIslands {}
+    }
+}
+
+// a closure that `run_once` takes out the first time it's called
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+type OnceCallback = std::cell::RefCell<Option<Box<dyn FnOnce()>>>;
+
+// takes the closure out before calling it, so it can't run twice and isn't
+// borrowed while it runs
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn run_once(f: &OnceCallback) {
+    let f = f.borrow_mut().take();
+    if let Some(f) = f {
+        f();
+    }
+}
@@ -0,0 +1,341 @@
+//! Registering a view as a [custom element](https://developer.mozilla.org/en-US/docs/Web/Web_Components/Using_custom_elements),
+//! so it can be used like any other HTML element, e.g., from a page that
+//! doesn't use Leptos at all.
+
+use crate::IntoView;
+use leptos_reactive::{ReadSignal, RwSignal, Scope};
+use std::{borrow::Cow, rc::Rc};
+
+/// The options for [define_custom_element_with].
+#[derive(Debug, Clone, Default)]
+pub struct CustomElementOptions {
+    /// The attributes that are reflected into signals, which can be read
+    /// with [CustomElement::attribute]. Changes to any other attribute are
+    /// ignored.
+    pub observed_attributes: Vec<&'static str>,
+    /// CSS added to the shadow root, each in its own `<style>` element. It
+    /// only applies inside the element, and styles from the page don't apply
+    /// inside it.
+    pub styles: Vec<Cow<'static, str>>,
+}
+
+/// An instance of an element registered with [define_custom_element], which
+/// is passed to its view.
+#[derive(Clone)]
+pub struct CustomElement {
+    cx: Scope,
+    host: web_sys::HtmlElement,
+    attributes: Rc<ObservedAttributes>,
+}
+
+/// The signal each observed attribute is reflected into, by name.
+type ObservedAttributes = Vec<(&'static str, RwSignal<Option<String>>)>;
+
+impl CustomElement {
+    /// The element itself, which the view is rendered in the shadow root of.
+    pub fn host(&self) -> &web_sys::HtmlElement {
+        &self.host
+    }
+
+    /// The value of the attribute `name`, or `None` if it isn't set. This
+    /// is updated when it changes, if `name` is one of the
+    /// [observed_attributes](CustomElementOptions::observed_attributes).
+    /// Otherwise, this is only its value right now.
+    pub fn attribute(&self, name: &str) -> ReadSignal<Option<String>> {
+        match self
+            .attributes
+            .iter()
+            .find(|(observed, _)| *observed == name)
+        {
+            Some((_, value)) => value.read_only(),
+            None => {
+                crate::debug_warn!(
+                    "[CustomElement::attribute] The attribute {name:?} isn't \
+                     observed, so changes to it will be ignored."
+                );
+                leptos_reactive::create_signal(
+                    self.cx,
+                    self.host.get_attribute(name),
+                )
+                .0
+            }
+        }
+    }
+
+    /// Dispatches a [CustomEvent](web_sys::CustomEvent) with the given
+    /// `detail` from the element. As with [dispatch_custom_event](crate::helpers::dispatch_custom_event),
+    /// it bubbles and is composed, so it reaches listeners outside the
+    /// shadow root, e.g., `el.addEventListener("change", ...)` on the page.
+    ///
+    /// Returns `false` if a listener called `prevent_default`.
+    pub fn emit<T: serde::Serialize>(
+        &self,
+        event_name: &str,
+        detail: &T,
+    ) -> bool {
+        crate::helpers::dispatch_custom_event(&self.host, event_name, detail)
+    }
+}
+
+impl std::fmt::Debug for CustomElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomElement")
+            .field("host", &self.host)
+            .finish()
+    }
+}
+
+/// Registers `f` as the custom element `name`, which has to contain a `-`.
+/// It doesn't observe any attributes; see [define_custom_element_with].
+///
+/// ```
+/// # use leptos::*;
+/// define_custom_element("hello-world", |cx, _el| {
+///     view! { cx, <p>"Hello, world!"</p> }
+/// });
+/// ```
+pub fn define_custom_element<F, N>(name: &'static str, f: F)
+where
+    F: Fn(Scope, CustomElement) -> N + 'static,
+    N: IntoView,
+{
+    define_custom_element_with(name, CustomElementOptions::default(), f)
+}
+
+/// Registers `f` as the custom element `name`, which has to contain a `-`.
+///
+/// Whenever an element with that name is added to the document, including
+/// ones that were already in the page, `f` is called in a new scope with the
+/// [CustomElement], and the view it returns is rendered in the element's open
+/// shadow root, after the [styles](CustomElementOptions::styles). When the
+/// element is removed, the scope is disposed and the shadow root is emptied.
+/// Moving the element renders it again.
+///
+/// Each custom element has its own
+/// [runtime](leptos_reactive::create_runtime), so it can't use signals or
+/// contexts from the rest of the page. Communicate with it through its
+/// attributes and [events](CustomElement::emit) instead.
+///
+/// This has to create the element's class with `new Function`, so it won't
+/// work with a Content Security Policy that doesn't allow `unsafe-eval`.
+/// Defining the same name twice only logs a warning. Does nothing on the
+/// server.
+///
+/// ```
+/// # use leptos::*;
+/// let options = CustomElementOptions {
+///     observed_attributes: vec!["count"],
+///     styles: vec!["p { color: red; }".into()],
+/// };
+/// define_custom_element_with("my-counter", options, |cx, el| {
+///     let count = el.attribute("count");
+///     let count = move || {
+///         count
+///             .get()
+///             .and_then(|count| count.parse::<i32>().ok())
+///             .unwrap_or(0)
+///     };
+///     view! { cx,
+///         <p>"Count: " {count}</p>
+///         <button on:click=move |_| { el.emit("increment", &count()); }>
+///             "+1"
+///         </button>
+///     }
+/// });
+/// ```
+pub fn define_custom_element_with<F, N>(
+    name: &'static str,
+    options: CustomElementOptions,
+    f: F,
+) where
+    F: Fn(Scope, CustomElement) -> N + 'static,
+    N: IntoView,
+{
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        use crate::Mountable;
+        use leptos_reactive::{
+            RenderMode, ScopeDisposer, SignalSet, SignalWithUntracked,
+        };
+        use std::cell::RefCell;
+        use wasm_bindgen::{prelude::Closure, JsCast, JsValue, UnwrapThrowExt};
+
+        struct Connected {
+            el: CustomElement,
+            disposer: ScopeDisposer,
+        }
+
+        let registry = crate::window().custom_elements();
+        if !registry.get(name).is_undefined() {
+            crate::warn!(
+                "[define_custom_element] The custom element {name:?} is \
+                 already defined."
+            );
+            return;
+        }
+
+        crate::HydrationCtx::tag_resources();
+        // never disposed, as a custom element can't be undefined
+        let (root, _disposer) = leptos_reactive::raw_scope_and_disposer(
+            leptos_reactive::create_runtime(),
+        );
+        std::mem::forget(_disposer);
+        root.set_render_mode(RenderMode::Csr);
+
+        let CustomElementOptions {
+            observed_attributes,
+            styles,
+        } = options;
+        let instances = Rc::new(RefCell::new(Vec::<Connected>::new()));
+
+        let connected = Closure::<dyn Fn(web_sys::HtmlElement)>::new({
+            let instances = Rc::clone(&instances);
+            let observed_attributes = observed_attributes.clone();
+            move |host: web_sys::HtmlElement| {
+                let shadow_root = match host.shadow_root() {
+                    Some(shadow_root) => shadow_root,
+                    None => {
+                        match host.attach_shadow(&web_sys::ShadowRootInit::new(
+                            web_sys::ShadowRootMode::Open,
+                        )) {
+                            Ok(shadow_root) => shadow_root,
+                            Err(e) => {
+                                crate::error!(
+                                    "[define_custom_element] Couldn't attach \
+                                     a shadow root to {name:?}: {e:?}"
+                                );
+                                return;
+                            }
+                        }
+                    }
+                };
+
+                let (el, disposer) = root.run_child_scope(|cx| {
+                    let attributes = observed_attributes
+                        .iter()
+                        .map(|attr| {
+                            let value = host.get_attribute(attr);
+                            (
+                                *attr,
+                                leptos_reactive::create_rw_signal(cx, value),
+                            )
+                        })
+                        .collect();
+                    let el = CustomElement {
+                        cx,
+                        host: host.clone(),
+                        attributes: Rc::new(attributes),
+                    };
+
+                    for css in &styles {
+                        let style = crate::document()
+                            .create_element("style")
+                            .unwrap_throw();
+                        style.set_text_content(Some(css));
+                        _ = shadow_root.append_child(&style);
+                    }
+                    let view = f(cx, el.clone()).into_view(cx);
+                    _ = shadow_root.append_child(&view.get_mountable_node());
+                    std::mem::forget(view);
+
+                    el
+                });
+                instances.borrow_mut().push(Connected { el, disposer });
+            }
+        })
+        .into_js_value();
+
+        let disconnected = Closure::<dyn Fn(web_sys::HtmlElement)>::new({
+            let instances = Rc::clone(&instances);
+            move |host: web_sys::HtmlElement| {
+                let connected = {
+                    let mut instances = instances.borrow_mut();
+                    instances
+                        .iter()
+                        .position(|connected| connected.el.host == host)
+                        .map(|idx| instances.remove(idx))
+                };
+                if let Some(connected) = connected {
+                    connected.disposer.dispose();
+                    if let Some(shadow_root) = host.shadow_root() {
+                        shadow_root.set_inner_html("");
+                    }
+                }
+            }
+        })
+        .into_js_value();
+
+        let attribute_changed = Closure::<
+            dyn Fn(web_sys::HtmlElement, String, Option<String>),
+        >::new(
+            move |host: web_sys::HtmlElement,
+                  attr: String,
+                  value: Option<String>| {
+                // clone it out, so the update can connect or disconnect
+                // elements
+                let signal = instances
+                    .borrow()
+                    .iter()
+                    .find(|connected| connected.el.host == host)
+                    .and_then(|connected| {
+                        connected
+                            .el
+                            .attributes
+                            .iter()
+                            .find(|(observed, _)| *observed == attr)
+                            .map(|(_, signal)| *signal)
+                    });
+                // before it's connected, the value is read when it is
+                if let Some(signal) = signal {
+                    if signal.with_untracked(|old| *old != value) {
+                        signal.set(value);
+                    }
+                }
+            },
+        )
+        .into_js_value();
+
+        let observed = observed_attributes
+            .iter()
+            .map(|attr| JsValue::from_str(attr))
+            .collect::<js_sys::Array>();
+        let class = js_sys::Function::new_with_args(
+            "observed, connected, disconnected, attributeChanged",
+            "return class extends HTMLElement {
+                static get observedAttributes() { return observed; }
+                connectedCallback() { connected(this); }
+                disconnectedCallback() { disconnected(this); }
+                attributeChangedCallback(name, _old, value) {
+                    attributeChanged(this, name, value);
+                }
+            };",
+        )
+        .apply(
+            &JsValue::NULL,
+            &js_sys::Array::of4(
+                &observed,
+                &connected,
+                &disconnected,
+                &attribute_changed,
+            ),
+        );
+        let result = class.and_then(|class| {
+            registry.define(name, class.unchecked_ref::<js_sys::Function>())
+        });
+        if let Err(e) = result {
+            crate::error!(
+                "[define_custom_element] Couldn't define {name:?}: {e:?}"
+            );
+        }
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = name;
+        _ = options;
+        _ = f;
+        crate::warn!(
+            "`define_custom_element` should not be called outside the browser."
+        );
+    }
+}
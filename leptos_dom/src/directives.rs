@@ -0,0 +1,59 @@
+//! Reusable behaviors that can be attached to an element with `use:` in the
+//! `view` macro, or with [HtmlElement::directive](crate::HtmlElement::directive).
+//!
+//! A directive is a function that takes the element's [Scope], the element
+//! itself, and a parameter, and is called once the element is mounted:
+//!
+//! ```
+//! # use leptos::*;
+//! fn autofocus(_cx: Scope, el: web_sys::Element, _: ()) {
+//!     use wasm_bindgen::JsCast;
+//!
+//!     _ = el.unchecked_into::<web_sys::HtmlElement>().focus();
+//! }
+//!
+//! # run_scope(create_runtime(), |cx| {
+//! # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+//! view! { cx, <input use:autofocus/> }
+//! # ;
+//! # }
+//! # });
+//! ```
+
+use crate::{ev, helpers::document_event_listener};
+use leptos_reactive::Scope;
+use wasm_bindgen::JsCast;
+
+/// Calls `on_outside` when there's a click anywhere outside the element, e.g.,
+/// to close a menu. The listener is removed when `cx` is disposed.
+///
+/// ```
+/// # use leptos::*;
+/// use leptos::directives::click_outside;
+///
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// let (open, set_open) = create_signal(cx, true);
+/// view! { cx,
+///     <menu use:click_outside=move |_: ev::MouseEvent| set_open.set(false)>
+///         <li>"Copy"</li>
+///     </menu>
+/// }
+/// # ;
+/// # }
+/// # });
+/// ```
+pub fn click_outside(
+    cx: Scope,
+    el: web_sys::Element,
+    mut on_outside: impl FnMut(ev::MouseEvent) + 'static,
+) {
+    document_event_listener(cx, ev::click, move |ev| {
+        let target = ev
+            .target()
+            .and_then(|target| target.dyn_into::<web_sys::Node>().ok());
+        if !el.contains(target.as_ref()) {
+            on_outside(ev);
+        }
+    });
+}
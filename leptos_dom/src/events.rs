@@ -27,23 +27,33 @@ pub fn add_event_helper<E: crate::ev::EventDescriptor + 'static>(
             target,
             event.event_delegation_key(),
             event_name,
+            event.modifiers(),
             event_handler,
         );
     } else {
-        add_event_listener_undelegated(target, &event_name, event_handler);
+        add_event_listener_undelegated(
+            target,
+            &event_name,
+            event.options(),
+            event.modifiers(),
+            event_handler,
+        );
     }
 }
 
 /// Adds an event listener to the target DOM element using implicit event delegation.
+/// Returns the handler, which [remove_event_listener] takes.
 #[doc(hidden)]
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 pub fn add_event_listener<E>(
     target: &web_sys::Element,
     key: Cow<'static, str>,
     event_name: Cow<'static, str>,
+    modifiers: typed::EventModifiers,
     #[cfg(debug_assertions)] mut cb: impl FnMut(E) + 'static,
     #[cfg(not(debug_assertions))] cb: impl FnMut(E) + 'static,
-) where
+) -> JsValue
+where
     E: FromWasmAbi + 'static,
 {
     cfg_if::cfg_if! {
@@ -57,19 +67,24 @@ pub fn add_event_listener<E>(
     }
 
     let cb = Closure::wrap(Box::new(cb) as Box<dyn FnMut(E)>).into_js_value();
+    let cb = with_modifiers(cb, modifiers);
     let key = intern(&key);
     _ = js_sys::Reflect::set(target, &JsValue::from_str(&key), &cb);
     add_delegated_event_listener(&key, event_name);
+    cb
 }
 
 #[doc(hidden)]
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 pub(crate) fn add_event_listener_undelegated<E>(
-    target: &web_sys::Element,
+    target: &web_sys::EventTarget,
     event_name: &str,
+    options: typed::EventOptions,
+    modifiers: typed::EventModifiers,
     #[cfg(debug_assertions)] mut cb: impl FnMut(E) + 'static,
     #[cfg(not(debug_assertions))] cb: impl FnMut(E) + 'static,
-) where
+) -> JsValue
+where
     E: FromWasmAbi + 'static,
 {
     cfg_if::cfg_if! {
@@ -84,7 +99,74 @@ pub(crate) fn add_event_listener_undelegated<E>(
 
     let event_name = intern(event_name);
     let cb = Closure::wrap(Box::new(cb) as Box<dyn FnMut(E)>).into_js_value();
-    _ = target.add_event_listener_with_callback(event_name, cb.unchecked_ref());
+    let cb = with_modifiers(cb, modifiers);
+    if options == typed::EventOptions::default() {
+        _ = target
+            .add_event_listener_with_callback(event_name, cb.unchecked_ref());
+    } else {
+        let js_options = web_sys::AddEventListenerOptions::new();
+        js_options.set_capture(options.capture);
+        js_options.set_passive(options.passive);
+        js_options.set_once(options.once);
+        _ = target
+            .add_event_listener_with_callback_and_add_event_listener_options(
+                event_name,
+                cb.unchecked_ref(),
+                &js_options,
+            );
+    }
+    cb
+}
+
+// calls the modifiers before the handler, in a listener of its own, so they
+// still apply if the handler panics
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn with_modifiers(cb: JsValue, modifiers: typed::EventModifiers) -> JsValue {
+    use typed::EventModifiers;
+
+    if modifiers.is_empty() {
+        return cb;
+    }
+    Closure::<dyn Fn(web_sys::Event)>::new(move |ev: web_sys::Event| {
+        if modifiers.contains(EventModifiers::PREVENT_DEFAULT) {
+            ev.prevent_default();
+        }
+        if modifiers.contains(EventModifiers::STOP_PROPAGATION) {
+            ev.stop_propagation();
+        }
+        _ = cb
+            .unchecked_ref::<js_sys::Function>()
+            .call1(&JsValue::UNDEFINED, &ev);
+    })
+    .into_js_value()
+}
+
+/// Removes a handler added with [add_event_listener], if `delegated`, or
+/// with [add_event_listener_undelegated], so that it can be freed. A
+/// delegated handler that has since been replaced is left alone. The
+/// browser only removes a listener added in the capture phase if `capture`
+/// matches.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+pub(crate) fn remove_event_listener(
+    target: &web_sys::Element,
+    key: &str,
+    event_name: &str,
+    delegated: bool,
+    capture: bool,
+    cb: &JsValue,
+) {
+    if delegated {
+        let key = JsValue::from_str(key);
+        if js_sys::Reflect::get(target, &key).as_ref() == Ok(cb) {
+            _ = js_sys::Reflect::delete_property(target, &key);
+        }
+    } else {
+        _ = target.remove_event_listener_with_callback_and_bool(
+            event_name,
+            cb.unchecked_ref(),
+            capture,
+        );
+    }
 }
 
 // cf eventHandler in ryansolid/dom-expressions
@@ -1,6 +1,7 @@
 //! Types for all DOM events.
 
-use std::{borrow::Cow, marker::PhantomData};
+use serde::de::DeserializeOwned;
+use std::{borrow::Cow, marker::PhantomData, ops::Deref};
 use wasm_bindgen::convert::FromWasmAbi;
 
 /// A trait for converting types into [web_sys events](web_sys).
@@ -22,6 +23,109 @@ pub trait EventDescriptor: Clone {
     fn bubbles(&self) -> bool {
         true
     }
+
+    /// The options the event listener is added with. Only listeners that
+    /// aren't delegated can have options, so an event with any of them set
+    /// shouldn't [bubble](EventDescriptor::bubbles).
+    fn options(&self) -> EventOptions {
+        EventOptions::default()
+    }
+
+    /// What's done to the event before the handler is called, whether or
+    /// not the listener is delegated.
+    fn modifiers(&self) -> EventModifiers {
+        EventModifiers::default()
+    }
+}
+
+/// The options of an event listener, which map to
+/// [`AddEventListenerOptions`](https://developer.mozilla.org/en-US/docs/Web/API/EventTarget/addEventListener#options).
+/// They're set by wrapping an event in [capture], [passive], or [once].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct EventOptions {
+    /// Runs the listener while the event is captured on its way down to the
+    /// target, before any listeners on the way back up.
+    pub capture: bool,
+    /// Promises that the listener won't call `prevent_default`, which lets
+    /// the browser scroll without waiting for it. `prevent_default` is
+    /// ignored.
+    pub passive: bool,
+    /// Removes the listener after it has run once.
+    pub once: bool,
+}
+
+/// Flags for what's done to an event before its handler is called, so that
+/// it happens even if the handler returns early or panics. They're set by
+/// wrapping an event in [modified], or with the `:prevent` and `:stop`
+/// suffixes in the `view` macro, e.g., `on:submit:prevent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct EventModifiers(u8);
+
+impl EventModifiers {
+    /// Nothing is done to the event.
+    pub const NONE: Self = Self(0);
+    /// Calls [`prevent_default`](web_sys::Event::prevent_default), e.g., so
+    /// submitting a form doesn't reload the page.
+    pub const PREVENT_DEFAULT: Self = Self(1);
+    /// Calls [`stop_propagation`](web_sys::Event::stop_propagation), so
+    /// listeners on the element's ancestors don't receive the event. This
+    /// applies to delegated listeners too.
+    pub const STOP_PROPAGATION: Self = Self(1 << 1);
+
+    /// Whether all the flags in `other` are set.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Whether no flags are set.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl std::ops::BitOr for EventModifiers {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for EventModifiers {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Sets [EventModifiers] on an event, e.g.,
+/// `modified(ev::submit, EventModifiers::PREVENT_DEFAULT)`. Unlike the
+/// [EventOptions], they don't stop the listener from being delegated.
+#[derive(Clone)]
+#[allow(non_camel_case_types)]
+pub struct modified<Ev: EventDescriptor>(pub Ev, pub EventModifiers);
+
+impl<Ev: EventDescriptor> EventDescriptor for modified<Ev> {
+    type EventType = Ev::EventType;
+
+    fn name(&self) -> Cow<'static, str> {
+        self.0.name()
+    }
+
+    fn event_delegation_key(&self) -> Cow<'static, str> {
+        self.0.event_delegation_key()
+    }
+
+    fn bubbles(&self) -> bool {
+        self.0.bubbles()
+    }
+
+    fn options(&self) -> EventOptions {
+        self.0.options()
+    }
+
+    fn modifiers(&self) -> EventModifiers {
+        self.0.modifiers() | self.1
+    }
 }
 
 /// Overrides the [`EventDescriptor::bubbles`] method to always return
@@ -44,11 +148,74 @@ impl<Ev: EventDescriptor> EventDescriptor for undelegated<Ev> {
     fn bubbles(&self) -> bool {
         false
     }
+
+    fn options(&self) -> EventOptions {
+        self.0.options()
+    }
+
+    fn modifiers(&self) -> EventModifiers {
+        self.0.modifiers()
+    }
+}
+
+macro_rules! event_option {
+    ($(#[$meta:meta])* $option:ident) => {
+        $(#[$meta])*
+        ///
+        /// The listener is added to the element itself, as if the event were
+        /// [undelegated].
+        #[derive(Clone)]
+        #[allow(non_camel_case_types)]
+        pub struct $option<Ev: EventDescriptor>(pub Ev);
+
+        impl<Ev: EventDescriptor> EventDescriptor for $option<Ev> {
+            type EventType = Ev::EventType;
+
+            fn name(&self) -> Cow<'static, str> {
+                self.0.name()
+            }
+
+            fn event_delegation_key(&self) -> Cow<'static, str> {
+                self.0.event_delegation_key()
+            }
+
+            fn bubbles(&self) -> bool {
+                false
+            }
+
+            fn options(&self) -> EventOptions {
+                EventOptions {
+                    $option: true,
+                    ..self.0.options()
+                }
+            }
+
+            fn modifiers(&self) -> EventModifiers {
+                self.0.modifiers()
+            }
+        }
+    };
+}
+
+event_option! {
+    /// Adds the listener in the capture phase. See [EventOptions::capture].
+    capture
+}
+event_option! {
+    /// Adds the listener as passive, e.g., for `scroll`, `wheel` and `touch`
+    /// listeners that don't need to prevent the default. See
+    /// [EventOptions::passive].
+    passive
+}
+event_option! {
+    /// Removes the listener after it has run once. See [EventOptions::once].
+    once
 }
 
 /// A custom event.
 pub struct Custom<E: FromWasmAbi = web_sys::Event> {
     name: Cow<'static, str>,
+    delegated: bool,
     _event_type: PhantomData<E>,
 }
 
@@ -56,6 +223,7 @@ impl<E: FromWasmAbi> Clone for Custom<E> {
     fn clone(&self) -> Self {
         Self {
             name: self.name.clone(),
+            delegated: self.delegated,
             _event_type: PhantomData,
         }
     }
@@ -73,7 +241,7 @@ impl<E: FromWasmAbi> EventDescriptor for Custom<E> {
     }
 
     fn bubbles(&self) -> bool {
-        false
+        self.delegated
     }
 }
 
@@ -81,12 +249,121 @@ impl<E: FromWasmAbi> Custom<E> {
     /// Creates a custom event type that can be used within
     /// [`HtmlElement::on`](crate::HtmlElement::on), for events
     /// which are not covered in the [`ev`](crate::ev) module.
+    ///
+    /// For a handler that takes the typed `detail` sent by
+    /// [dispatch_custom_event](crate::helpers::dispatch_custom_event), see
+    /// [CustomEvent].
     pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
         Self {
             name: name.into(),
+            delegated: false,
             _event_type: PhantomData,
         }
     }
+
+    /// Handles the event with the global, delegated listener, like the events
+    /// in the [`ev`](crate::ev) module that bubble, rather than a listener on
+    /// the element. Only use this for events that bubble, such as the ones
+    /// sent by [dispatch_custom_event](crate::helpers::dispatch_custom_event).
+    pub fn delegated(mut self) -> Self {
+        self.delegated = true;
+        self
+    }
+}
+
+/// A [`CustomEvent`](web_sys::CustomEvent) whose `detail` is a `T`, as sent by
+/// [dispatch_custom_event](crate::helpers::dispatch_custom_event).
+///
+/// A custom event in the [`view`](https://docs.rs/leptos_macro/latest/leptos_macro/macro.view.html)
+/// macro can take a handler for it directly. With [`HtmlElement::on`](crate::HtmlElement::on),
+/// wrap the handler in [custom_handler].
+///
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// view! { cx,
+///   // braces keep the `>` of the type from ending the tag
+///   <div on:item-selected={|ev: ev::CustomEvent<usize>| {
+///     log!("selected item {:?}", ev.detail());
+///   }}/>
+/// }
+/// # ;
+/// # }
+/// # });
+/// ```
+pub struct CustomEvent<T> {
+    event: web_sys::CustomEvent,
+    _detail: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> CustomEvent<T> {
+    /// Deserializes the `detail` of the event, or returns `None` if it isn't
+    /// a `T`.
+    pub fn detail(&self) -> Option<T> {
+        serde_wasm_bindgen::from_value(self.event.detail()).ok()
+    }
+}
+
+impl<T> Deref for CustomEvent<T> {
+    type Target = web_sys::CustomEvent;
+
+    fn deref(&self) -> &Self::Target {
+        &self.event
+    }
+}
+
+/// The argument of a handler for a [Custom] event: either an event the
+/// browser passes to the listener as it is, or a [CustomEvent] with a typed
+/// `detail`.
+pub trait CustomEventArg: Sized {
+    /// The event the listener receives.
+    type Raw: FromWasmAbi;
+
+    /// Converts the event the listener receives into the handler's argument.
+    fn from_raw(raw: Self::Raw) -> Self;
+}
+
+impl<E: FromWasmAbi> CustomEventArg for E {
+    type Raw = E;
+
+    fn from_raw(raw: Self::Raw) -> Self {
+        raw
+    }
+}
+
+impl<T> CustomEventArg for CustomEvent<T> {
+    type Raw = web_sys::CustomEvent;
+
+    fn from_raw(raw: Self::Raw) -> Self {
+        Self {
+            event: raw,
+            _detail: PhantomData,
+        }
+    }
+}
+
+/// Wraps the handler of a [Custom] event, so that it can take a [CustomEvent]
+/// with a typed `detail`.
+///
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// html::div(cx).on(
+///     ev::Custom::new("item-selected"),
+///     ev::custom_handler(|ev: ev::CustomEvent<usize>| {
+///         log!("selected item {:?}", ev.detail());
+///     }),
+/// )
+/// # ;
+/// # }
+/// # });
+/// ```
+pub fn custom_handler<A: CustomEventArg + 'static>(
+    mut handler: impl FnMut(A) + 'static,
+) -> impl FnMut(A::Raw) + 'static {
+    move |raw| handler(A::from_raw(raw))
 }
 
 macro_rules! generate_event_types {
@@ -314,9 +591,9 @@ generate_event_types! {
   // =========================================================
   // DocumentAndElementEventHandlersEventMap
   // =========================================================
-  copy: Event, // ClipboardEvent is unstable
-  cut: Event, // ClipboardEvent is unstable
-  paste: Event, // ClipboardEvent is unstable
+  copy: ClipboardEvent,
+  cut: ClipboardEvent,
+  paste: ClipboardEvent,
 
   // =========================================================
   // DocumentEventMap
@@ -332,10 +609,11 @@ generate_event_types! {
 
 // Export `web_sys` event types
 pub use web_sys::{
-    AnimationEvent, BeforeUnloadEvent, CompositionEvent, DeviceMotionEvent,
-    DeviceOrientationEvent, DragEvent, ErrorEvent, FocusEvent, GamepadEvent,
-    HashChangeEvent, InputEvent, KeyboardEvent, MouseEvent,
-    PageTransitionEvent, PointerEvent, PopStateEvent, ProgressEvent,
-    PromiseRejectionEvent, SecurityPolicyViolationEvent, StorageEvent,
-    SubmitEvent, TouchEvent, TransitionEvent, UiEvent, WheelEvent,
+    AnimationEvent, BeforeUnloadEvent, ClipboardEvent, CompositionEvent,
+    DeviceMotionEvent, DeviceOrientationEvent, DragEvent, ErrorEvent,
+    FocusEvent, GamepadEvent, HashChangeEvent, InputEvent, KeyboardEvent,
+    MouseEvent, PageTransitionEvent, PointerEvent, PopStateEvent,
+    ProgressEvent, PromiseRejectionEvent, SecurityPolicyViolationEvent,
+    StorageEvent, SubmitEvent, TouchEvent, TransitionEvent, UiEvent,
+    WheelEvent,
 };
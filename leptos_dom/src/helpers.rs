@@ -1,6 +1,7 @@
 //! A variety of DOM utility functions.
 
-use crate::{is_server, window};
+use crate::{ev::EventDescriptor, is_server, window};
+use leptos_reactive::Scope;
 use std::time::Duration;
 use wasm_bindgen::{prelude::Closure, JsCast, JsValue, UnwrapThrowExt};
 
@@ -80,20 +81,66 @@ pub fn event_target_checked(ev: &web_sys::Event) -> bool {
         .checked()
 }
 
-/// Runs the given function between the next repaint
+/// Handle that is generated by [request_animation_frame] and can be used to
+/// cancel the request.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AnimationFrameRequestHandle(i32);
+
+impl AnimationFrameRequestHandle {
+    /// Cancels the animation frame request to which this refers.
+    /// See [`cancelAnimationFrame()`](https://developer.mozilla.org/en-US/docs/Web/API/Window/cancelAnimationFrame)
+    pub fn clear(&self) {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            _ = window().cancel_animation_frame(self.0);
+        }
+    }
+}
+
+/// Runs the given function before the next repaint
 /// using [`Window.requestAnimationFrame`](https://developer.mozilla.org/en-US/docs/Web/API/window/requestAnimationFrame).
+/// The request is cancelled if `cx` is disposed first. Does nothing on the
+/// server.
 #[cfg_attr(debug_assertions, instrument(level = "trace", skip_all))]
-pub fn request_animation_frame(cb: impl FnOnce() + 'static) {
-    cfg_if::cfg_if! {
-      if #[cfg(debug_assertions)] {
-        let span = ::tracing::Span::current();
-        let cb = move || {
-          let _guard = span.enter();
-          cb();
-        };
-      }
+pub fn request_animation_frame(
+    cx: Scope,
+    cb: impl FnOnce() + 'static,
+) -> AnimationFrameRequestHandle {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        cfg_if::cfg_if! {
+          if #[cfg(debug_assertions)] {
+            let span = ::tracing::Span::current();
+            let cb = move || {
+              let _guard = span.enter();
+              cb();
+            };
+          }
+        }
+
+        let cb = Closure::once(cb);
+        let handle = window()
+            .request_animation_frame(cb.as_ref().unchecked_ref())
+            .map(AnimationFrameRequestHandle)
+            .unwrap_or_else(|e| {
+                crate::error!("[request_animation_frame] {e:?}");
+                AnimationFrameRequestHandle(0)
+            });
+        clear_on_cleanup(cx, cb, move || handle.clear());
+        handle
     }
 
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = cx;
+        _ = cb;
+        AnimationFrameRequestHandle(0)
+    }
+}
+
+// for internal callers that outlive any one scope
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+pub(crate) fn request_animation_frame_unscoped(cb: impl FnOnce() + 'static) {
     let cb = Closure::once_into_js(cb);
     _ = window().request_animation_frame(cb.as_ref().unchecked_ref());
 }
@@ -116,28 +163,68 @@ pub fn request_idle_callback(cb: impl Fn() + 'static) {
     _ = window().request_idle_callback(cb.as_ref().unchecked_ref());
 }
 
+/// Handle that is generated by [set_timeout] and can be used to clear the timeout.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TimeoutHandle(i32);
+
+impl TimeoutHandle {
+    /// Cancels the timeout to which this refers.
+    /// See [`clearTimeout()`](https://developer.mozilla.org/en-US/docs/Web/API/clearTimeout)
+    pub fn clear(&self) {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            window().clear_timeout_with_handle(self.0);
+        }
+    }
+}
+
 /// Executes the given function after the given duration of time has passed.
 /// [`setTimeout()`](https://developer.mozilla.org/en-US/docs/Web/API/setTimeout).
+/// The timeout is cleared if `cx` is disposed first. Does nothing on the
+/// server.
 #[cfg_attr(
   debug_assertions,
   instrument(level = "trace", skip_all, fields(duration = ?duration))
 )]
-pub fn set_timeout(cb: impl FnOnce() + 'static, duration: Duration) {
-    cfg_if::cfg_if! {
-      if #[cfg(debug_assertions)] {
-        let span = ::tracing::Span::current();
-        let cb = move || {
-          let _guard = span.enter();
-          cb();
-        };
-      }
+pub fn set_timeout(
+    cx: Scope,
+    duration: Duration,
+    cb: impl FnOnce() + 'static,
+) -> TimeoutHandle {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        cfg_if::cfg_if! {
+          if #[cfg(debug_assertions)] {
+            let span = ::tracing::Span::current();
+            let cb = move || {
+              let _guard = span.enter();
+              cb();
+            };
+          }
+        }
+
+        let cb = Closure::once(cb);
+        let handle = window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(
+                cb.as_ref().unchecked_ref(),
+                duration.as_millis().try_into().unwrap_throw(),
+            )
+            .map(TimeoutHandle)
+            .unwrap_or_else(|e| {
+                crate::error!("[set_timeout] {e:?}");
+                TimeoutHandle(0)
+            });
+        clear_on_cleanup(cx, cb, move || handle.clear());
+        handle
     }
 
-    let cb = Closure::once_into_js(Box::new(cb) as Box<dyn FnOnce()>);
-    _ = window().set_timeout_with_callback_and_timeout_and_arguments_0(
-        cb.as_ref().unchecked_ref(),
-        duration.as_millis().try_into().unwrap_throw(),
-    );
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = cx;
+        _ = duration;
+        _ = cb;
+        TimeoutHandle(0)
+    }
 }
 
 /// Handle that is generated by [set_interval] and can be used to clear the interval.
@@ -148,65 +235,224 @@ impl IntervalHandle {
     /// Cancels the repeating event to which this refers.
     /// See [`clearInterval()`](https://developer.mozilla.org/en-US/docs/Web/API/clearInterval)
     pub fn clear(&self) {
-        window().clear_interval_with_handle(self.0);
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            window().clear_interval_with_handle(self.0);
+        }
     }
 }
 
 /// Repeatedly calls the given function, with a delay of the given duration between calls.
 /// See [`setInterval()`](https://developer.mozilla.org/en-US/docs/Web/API/setInterval).
+/// The interval is cleared when `cx` is disposed. Does nothing on the server.
 #[cfg_attr(
   debug_assertions,
   instrument(level = "trace", skip_all, fields(duration = ?duration))
 )]
 pub fn set_interval(
-    cb: impl Fn() + 'static,
+    cx: Scope,
     duration: Duration,
-) -> Result<IntervalHandle, JsValue> {
-    cfg_if::cfg_if! {
-      if #[cfg(debug_assertions)] {
-        let span = ::tracing::Span::current();
-        let cb = move || {
-          let _guard = span.enter();
-          cb();
-        };
-      }
+    cb: impl Fn() + 'static,
+) -> IntervalHandle {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        cfg_if::cfg_if! {
+          if #[cfg(debug_assertions)] {
+            let span = ::tracing::Span::current();
+            let cb = move || {
+              let _guard = span.enter();
+              cb();
+            };
+          }
+        }
+
+        let cb = Closure::wrap(Box::new(cb) as Box<dyn Fn()>);
+        let handle = window()
+            .set_interval_with_callback_and_timeout_and_arguments_0(
+                cb.as_ref().unchecked_ref(),
+                duration.as_millis().try_into().unwrap_throw(),
+            )
+            .map(IntervalHandle)
+            .unwrap_or_else(|e| {
+                crate::error!("[set_interval] {e:?}");
+                IntervalHandle(0)
+            });
+        clear_on_cleanup(cx, cb, move || handle.clear());
+        handle
     }
 
-    let cb = Closure::wrap(Box::new(cb) as Box<dyn Fn()>).into_js_value();
-    let handle = window()
-        .set_interval_with_callback_and_timeout_and_arguments_0(
-            cb.as_ref().unchecked_ref(),
-            duration.as_millis().try_into().unwrap_throw(),
-        )?;
-    Ok(IntervalHandle(handle))
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = cx;
+        _ = duration;
+        _ = cb;
+        IntervalHandle(0)
+    }
+}
+
+// the browser may still call `cb` until it's cleared, so it's only dropped
+// afterward
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn clear_on_cleanup<T: ?Sized + 'static>(
+    cx: Scope,
+    cb: Closure<T>,
+    clear: impl FnOnce() + 'static,
+) {
+    leptos_reactive::on_cleanup(cx, move || {
+        clear();
+        drop(cb);
+    });
+}
+
+/// Handle that is generated by [window_event_listener] and
+/// [document_event_listener], and can be used to remove the listener before
+/// its scope is disposed.
+#[derive(Clone, Debug)]
+pub struct EventListenerHandle {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    target: web_sys::EventTarget,
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    event_name: std::borrow::Cow<'static, str>,
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    cb: JsValue,
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    capture: bool,
+}
+
+impl EventListenerHandle {
+    /// Removes the listener. This happens anyway when the scope it was added
+    /// in is disposed.
+    pub fn remove(&self) {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            _ = self.target.remove_event_listener_with_callback_and_bool(
+                &self.event_name,
+                self.cb.unchecked_ref(),
+                self.capture,
+            );
+        }
+    }
 }
 
-/// Adds an event listener to the `Window`.
+/// Adds an event listener to the `Window`, which is removed when `cx` is
+/// disposed. The event can be wrapped in [`ev::passive`](crate::ev::passive),
+/// [`ev::capture`](crate::ev::capture) or [`ev::once`](crate::ev::once) to set
+/// the listener's options. Does nothing on the server.
 #[cfg_attr(
   debug_assertions,
-  instrument(level = "trace", skip_all, fields(event_name = %event_name))
+  instrument(level = "trace", skip_all, fields(event_name = %event.name()))
 )]
-pub fn window_event_listener(
+pub fn window_event_listener<E: EventDescriptor + 'static>(
+    cx: Scope,
+    event: E,
+    cb: impl FnMut(E::EventType) + 'static,
+) -> EventListenerHandle {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    let target = window().unchecked_into();
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    let target = ();
+    scoped_event_listener(cx, target, event, cb)
+}
+
+/// Adds an event listener to the `Document`, which is removed when `cx` is
+/// disposed. See [window_event_listener].
+#[cfg_attr(
+  debug_assertions,
+  instrument(level = "trace", skip_all, fields(event_name = %event.name()))
+)]
+pub fn document_event_listener<E: EventDescriptor + 'static>(
+    cx: Scope,
+    event: E,
+    cb: impl FnMut(E::EventType) + 'static,
+) -> EventListenerHandle {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    let target = crate::document().unchecked_into();
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    let target = ();
+    scoped_event_listener(cx, target, event, cb)
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn scoped_event_listener<E: EventDescriptor + 'static>(
+    cx: Scope,
+    target: web_sys::EventTarget,
+    event: E,
+    cb: impl FnMut(E::EventType) + 'static,
+) -> EventListenerHandle {
+    let event_name = event.name();
+    let options = event.options();
+    let cb = crate::events::add_event_listener_undelegated(
+        &target,
+        &event_name,
+        options,
+        event.modifiers(),
+        cb,
+    );
+    let handle = EventListenerHandle {
+        target,
+        event_name,
+        cb,
+        capture: options.capture,
+    };
+    leptos_reactive::on_cleanup(cx, {
+        let handle = handle.clone();
+        move || handle.remove()
+    });
+    handle
+}
+
+#[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+fn scoped_event_listener<E: EventDescriptor + 'static>(
+    _cx: Scope,
+    _target: (),
+    _event: E,
+    _cb: impl FnMut(E::EventType) + 'static,
+) -> EventListenerHandle {
+    EventListenerHandle {}
+}
+
+/// Dispatches a custom event named `event_name` from `target`, with `detail`
+/// as its payload. The event bubbles, and is composed so that it crosses
+/// shadow DOM boundaries, so either a listener on an ancestor or a delegated
+/// one receives it. Listen for it with a [CustomEvent](crate::ev::CustomEvent)
+/// of the same type, e.g., `on:my-event=|ev: ev::CustomEvent<T>| ...`.
+///
+/// Returns `false` if a listener called `prevent_default`. Does nothing on
+/// the server.
+pub fn dispatch_custom_event<T: serde::Serialize>(
+    target: &web_sys::EventTarget,
     event_name: &str,
-    cb: impl Fn(web_sys::Event) + 'static,
-) {
-    cfg_if::cfg_if! {
-      if #[cfg(debug_assertions)] {
-        let span = ::tracing::Span::current();
-        let cb = move |e| {
-          let _guard = span.enter();
-          cb(e);
-        };
-      }
+    detail: &T,
+) -> bool {
+    if is_server() {
+        return true;
     }
 
-    if !is_server() {
-        let handler = Box::new(cb) as Box<dyn FnMut(web_sys::Event)>;
+    let detail = match serde_wasm_bindgen::to_value(detail) {
+        Ok(detail) => detail,
+        Err(e) => {
+            crate::error!("[dispatch_custom_event] {e}");
+            return true;
+        }
+    };
+    let init = web_sys::CustomEventInit::new();
+    init.set_bubbles(true);
+    init.set_cancelable(true);
+    init.set_composed(true);
+    init.set_detail(&detail);
+    web_sys::CustomEvent::new_with_event_init_dict(event_name, &init)
+        .and_then(|event| target.dispatch_event(&event))
+        .unwrap_throw()
+}
 
-        let cb = Closure::wrap(handler).into_js_value();
-        _ = window()
-            .add_event_listener_with_callback(event_name, cb.unchecked_ref());
-    }
+#[doc(hidden)]
+/// This exists only to enable type inference on directives when in SSR mode.
+pub fn ssr_directive<P: 'static>(
+    handler: impl FnOnce(Scope, web_sys::Element, P) + 'static,
+    param: P,
+) {
+    _ = handler;
+    _ = param;
 }
 
 #[doc(hidden)]
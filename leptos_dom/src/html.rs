@@ -41,7 +41,10 @@ cfg_if! {
 use crate::{
     ev::EventDescriptor,
     hydration::HydrationCtx,
-    macro_helpers::{IntoAttribute, IntoClass, IntoProperty},
+    macro_helpers::{
+        is_boolean_attribute, Attribute, IntoAttribute, IntoClass,
+        IntoProperty, IntoStyle,
+    },
     Element, Fragment, IntoView, NodeRef, Text, View,
 };
 use leptos_reactive::Scope;
@@ -170,46 +173,9 @@ impl Custom {
 
         #[cfg(all(target_arch = "wasm32", feature = "web"))]
         let element = if HydrationCtx::is_hydrating() {
-            if let Some(el) =
-                crate::document().get_element_by_id(&format!("_{id}"))
-            {
-                #[cfg(debug_assertions)]
-                assert_eq!(
-                    el.node_name().to_ascii_uppercase(),
-                    name.to_ascii_uppercase(),
-                    "SSR and CSR elements have the same `TopoId` but \
-                     different node kinds. This is either a discrepancy \
-                     between SSR and CSR rendering
-                    logic, which is considered a bug, or it can also be a \
-                     leptos hydration issue."
-                );
-
-                el.remove_attribute("id").unwrap();
-
-                el.unchecked_into()
-            } else if let Ok(Some(el)) =
-                crate::document().query_selector(&format!("[leptos-hk=_{id}]"))
-            {
-                #[cfg(debug_assertions)]
-                assert_eq!(
-                    el.node_name().to_ascii_uppercase(),
-                    name.to_ascii_uppercase(),
-                    "SSR and CSR elements have the same `TopoId` but \
-                     different node kinds. This is either a discrepancy \
-                     between SSR and CSR rendering
-                    logic, which is considered a bug, or it can also be a \
-                     leptos hydration issue."
-                );
-
-                el.remove_attribute("leptos-hk").unwrap();
-
-                el.unchecked_into()
-            } else {
-                crate::warn!(
-                    "element with id {id} not found, ignoring it for hydration"
-                );
-
-                crate::document().create_element(&name).unwrap()
+            match HydrationCtx::find_element(&id, &name) {
+                Some(el) => el,
+                None => crate::document().create_element(&name).unwrap(),
             }
         } else {
             crate::document().create_element(&name).unwrap()
@@ -435,7 +401,13 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
         Self: Clone,
     {
         #[cfg(all(target_arch = "wasm32", feature = "web"))]
-        node_ref.load(&self);
+        {
+            node_ref.load(&self);
+
+            // a reference that outlives the element shouldn't still point at it
+            let el = self.element.as_ref().clone();
+            leptos_reactive::on_cleanup(self.cx, move || node_ref.unload(&el));
+        }
 
         #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
         let _ = node_ref;
@@ -470,7 +442,7 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
                     let waker = Rc::new(RefCell::new(None::<Waker>));
                     let ready = Rc::new(OnceCell::new());
 
-                    crate::helpers::request_animation_frame({
+                    crate::helpers::request_animation_frame_unscoped({
                         let waker = waker.clone();
                         let ready = ready.clone();
 
@@ -509,6 +481,10 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
     }
 
     /// Adds an attribute to this element.
+    ///
+    /// The boolean attributes the HTML spec defines, like `disabled` or
+    /// `open`, are removed when their value is `false` or `"false"`, except
+    /// on custom elements. See [bool_attr](Self::bool_attr).
     #[track_caller]
     pub fn attr(
         self,
@@ -516,42 +492,65 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
         attr: impl IntoAttribute,
     ) -> Self {
         let name = name.into();
+        let attr = attr.into_attribute(self.cx);
+
+        // custom elements define their own attributes
+        if is_boolean_attribute(&name) && !self.element.name().contains('-') {
+            self.set_attribute(name, attr.into_boolean())
+        } else {
+            self.set_attribute(name, attr)
+        }
+    }
 
+    /// Adds an attribute that is removed when its value is `false` or
+    /// `"false"`, like a boolean attribute, e.g., for a custom element.
+    #[track_caller]
+    pub fn bool_attr(
+        self,
+        name: impl Into<Cow<'static, str>>,
+        attr: impl IntoAttribute,
+    ) -> Self {
+        let attr = attr.into_attribute(self.cx).into_boolean();
+        self.set_attribute(name.into(), attr)
+    }
+
+    #[track_caller]
+    fn set_attribute(self, name: Cow<'static, str>, attr: Attribute) -> Self {
         #[cfg(all(target_arch = "wasm32", feature = "web"))]
         {
-            attribute_helper(
-                self.element.as_ref(),
-                name,
-                attr.into_attribute(self.cx),
-            );
+            attribute_helper(self.element.as_ref(), name, attr);
             self
         }
 
         #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
         {
-            use crate::macro_helpers::Attribute;
-
             let mut this = self;
 
-            let mut attr = attr.into_attribute(this.cx);
+            let mut attr = attr;
             while let Attribute::Fn(_, f) = attr {
                 attr = f();
             }
-            match attr {
-                Attribute::String(value) => {
-                    this.attrs.push((name, value.into()));
-                }
-                Attribute::Bool(include) => {
-                    if include {
-                        this.attrs.push((name, "".into()));
+            let value = match attr {
+                Attribute::String(value) => Some(value),
+                Attribute::Bool(include) => include.then(String::new),
+                Attribute::Option(_, maybe) => maybe,
+                _ => unreachable!(),
+            };
+            if let Some(value) = value {
+                // classes added with `class()` and properties added with
+                // `style()` are kept, as in the browser
+                match this.attrs.iter_mut().find(|(prev, _)| {
+                    (name == "class" || name == "style") && *prev == name
+                }) {
+                    Some((_, prev)) if name == "class" => {
+                        *prev = format!("{value} {prev}").into()
                     }
-                }
-                Attribute::Option(_, maybe) => {
-                    if let Some(value) = maybe {
-                        this.attrs.push((name, value.into()));
+                    Some((_, prev)) => {
+                        let value = value.trim_end().trim_end_matches(';');
+                        *prev = format!("{value}; {prev}").into()
                     }
+                    None => this.attrs.push((name, value.into())),
                 }
-                _ => unreachable!(),
             }
 
             this
@@ -603,6 +602,78 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
         }
     }
 
+    /// Sets a single CSS property of an element, leaving the rest of its
+    /// `style` alone. A value of `None` removes the property.
+    #[track_caller]
+    pub fn style(
+        self,
+        name: impl Into<Cow<'static, str>>,
+        style: impl IntoStyle,
+    ) -> Self {
+        let name = name.into();
+
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            let el = self.element.as_ref();
+            let value = style.into_style(self.cx);
+            style_helper(el, name, value);
+
+            self
+        }
+
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            let mut this = self;
+
+            let style = style.into_style(this.cx);
+
+            if let Some(declaration) = style.as_value_string(&name) {
+                if let Some((_, ref mut value)) =
+                    this.attrs.iter_mut().find(|(name, _)| name == "style")
+                {
+                    let prev = value.trim_end().trim_end_matches(';');
+                    *value = if prev.is_empty() {
+                        declaration.into()
+                    } else {
+                        format!("{prev}; {declaration}").into()
+                    };
+                } else {
+                    this.attrs.push(("style".into(), declaration.into()));
+                }
+            }
+
+            this
+        }
+    }
+
+    /// Attaches a reusable behavior to this element, the way `use:` does in
+    /// the `view` macro. Once the element is mounted, `handler` is called with
+    /// its scope, the element and `param`, so the cleanups it registers run
+    /// when that scope is disposed. Several directives on one element run in
+    /// the order they were added. See [directives](crate::directives). Does
+    /// nothing on the server.
+    pub fn directive<P: 'static>(
+        self,
+        handler: impl FnOnce(Scope, web_sys::Element, P) + 'static,
+        param: P,
+    ) -> Self {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            let cx = self.cx;
+            crate::on_mount(cx, self.clone(), move |el| {
+                handler(cx, el.element.as_ref().clone().into(), param)
+            });
+        }
+
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            _ = handler;
+            _ = param;
+        }
+
+        self
+    }
+
     /// Adds a list of classes separated by ASCII whitespace to an element.
     #[track_caller]
     pub fn classes(self, classes: impl Into<Cow<'static, str>>) -> Self {
@@ -638,6 +709,167 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
         self
     }
 
+    /// Binds the element's `value` to `value`, in both directions: the
+    /// property is set when the signal changes, and the signal is set on each
+    /// `input` event, e.g., for an `<input>`, `<textarea>` or `<select>`.
+    ///
+    /// While the user is composing text with an IME, neither is updated
+    /// until they're done. What they've typed isn't replaced while the
+    /// element is focused and it already stands for the signal's value, e.g.,
+    /// `1.` for `1.0`; if it isn't a valid value, the signal is left as it
+    /// is, and the element is reset to it on `change`. See [BindValue].
+    pub fn bind_value<T: crate::BindValue>(
+        self,
+        value: leptos_reactive::RwSignal<T>,
+    ) -> Self {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            use leptos_reactive::{SignalSet, SignalWith, SignalWithUntracked};
+            use std::{cell::Cell, rc::Rc};
+
+            let el: web_sys::Element = self.element.as_ref().clone().into();
+            let current = {
+                let el = el.clone();
+                move || {
+                    js_sys::Reflect::get(&el, &"value".into())
+                        .ok()
+                        .and_then(|value| value.as_string())
+                        .unwrap_or_default()
+                }
+            };
+            let write = {
+                let el = el.clone();
+                move |value: &T| {
+                    property_expression(&el, "value", value.to_value().into())
+                }
+            };
+            let from_element = {
+                let current = current.clone();
+                move || {
+                    if let Some(new) = T::from_value(&current()) {
+                        if value.with_untracked(|old| *old != new) {
+                            value.set(new);
+                        }
+                    }
+                }
+            };
+            let composing = Rc::new(Cell::new(false));
+
+            leptos_reactive::create_render_effect(self.cx, {
+                let composing = Rc::clone(&composing);
+                let current = current.clone();
+                let write = write.clone();
+                move |_| {
+                    value.with(|value| {
+                        let focused =
+                            crate::document().active_element().as_ref()
+                                == Some(&el);
+                        let matches =
+                            T::from_value(&current()).as_ref() == Some(value);
+                        if !composing.get() && !(focused && matches) {
+                            write(value);
+                        }
+                    })
+                }
+            });
+
+            self.on(crate::ev::compositionstart, {
+                let composing = Rc::clone(&composing);
+                move |_| composing.set(true)
+            })
+            .on(crate::ev::compositionend, {
+                let composing = Rc::clone(&composing);
+                let from_element = from_element.clone();
+                move |_| {
+                    composing.set(false);
+                    from_element();
+                }
+            })
+            .on(crate::ev::input, move |ev| {
+                let is_composing = ev
+                    .dyn_ref::<web_sys::InputEvent>()
+                    .map(|ev| ev.is_composing())
+                    .unwrap_or(false);
+                if !composing.get() && !is_composing {
+                    from_element();
+                }
+            })
+            .on(crate::ev::change, move |_| {
+                if T::from_value(&current()).is_none() {
+                    value.with_untracked(|value| write(value));
+                }
+            })
+        }
+
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            _ = value;
+            self
+        }
+    }
+
+    /// Binds whether the element, e.g., a checkbox, is `checked` to
+    /// `checked`, in both directions: the property is set when the signal
+    /// changes, and the signal is set on each `change` event.
+    pub fn bind_checked(
+        self,
+        checked: leptos_reactive::RwSignal<bool>,
+    ) -> Self {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            use leptos_reactive::{SignalGet, SignalSet};
+
+            let el: web_sys::Element = self.element.as_ref().clone().into();
+            leptos_reactive::create_render_effect(self.cx, move |_| {
+                property_expression(&el, "checked", checked.get().into())
+            });
+            self.on(crate::ev::change, move |ev| {
+                checked.set(crate::helpers::event_target_checked(&ev))
+            })
+        }
+
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            _ = checked;
+            self
+        }
+    }
+
+    /// Binds a radio button or checkbox to a [group](crate::BindGroup), in
+    /// which it stands for `value`: it's checked while the group has
+    /// `value` selected, and checking or unchecking it updates the group.
+    pub fn bind_group<G, V>(self, group: G, value: V) -> Self
+    where
+        G: crate::BindGroup<V>,
+        V: 'static,
+    {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            use std::rc::Rc;
+
+            let el: web_sys::Element = self.element.as_ref().clone().into();
+            let value = Rc::new(value);
+            leptos_reactive::create_render_effect(self.cx, {
+                let value = Rc::clone(&value);
+                move |_| {
+                    let selected = group.is_selected(&value);
+                    property_expression(&el, "checked", selected.into())
+                }
+            });
+            self.on(crate::ev::change, move |ev| {
+                let checked = crate::helpers::event_target_checked(&ev);
+                group.set_selected(&value, checked)
+            })
+        }
+
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            _ = group;
+            _ = value;
+            self
+        }
+    }
+
     /// Adds an event listener to this element.
     #[track_caller]
     pub fn on<E: EventDescriptor + 'static>(
@@ -662,27 +894,58 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
             let event_name = event.name();
 
             let key = event.event_delegation_key();
+            let delegated = event.bubbles();
+            let options = event.options();
+            let modifiers = event.modifiers();
 
-            if event.bubbles() {
+            let cb = if delegated {
                 add_event_listener(
                     self.element.as_ref(),
-                    key,
-                    event_name,
+                    key.clone(),
+                    event_name.clone(),
+                    modifiers,
                     event_handler,
-                );
+                )
             } else {
                 add_event_listener_undelegated(
                     self.element.as_ref(),
                     &event_name,
+                    options,
+                    modifiers,
                     event_handler,
+                )
+            };
+
+            // the handler goes when the scope that added it does, so that
+            // its closure can be freed
+            let el = self.element.as_ref().clone();
+            leptos_reactive::on_cleanup(self.cx, move || {
+                remove_event_listener(
+                    &el,
+                    &key,
+                    &event_name,
+                    delegated,
+                    options.capture,
+                    &cb,
                 );
-            }
+            });
 
             self
         }
 
         #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
         {
+            // on a page of islands, only the islands are hydrated
+            #[cfg(debug_assertions)]
+            if self.cx.has_islands() && !self.cx.in_island() {
+                crate::warn!(
+                    "[HtmlElement::on] The {} listener added at {} is outside \
+                     any island, so it will never run in the browser.",
+                    event.name(),
+                    std::panic::Location::caller()
+                );
+            }
+
             _ = event;
             _ = event_handler;
 
@@ -702,6 +965,12 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
                 #[cfg(debug_assertions)]
                 warn_on_ambiguous_a(self.element.as_ref(), &child);
 
+                mount_child(MountKind::Append(self.element.as_ref()), &child);
+            } else if matches!(child, View::Element(_) | View::Component(_))
+                && !crate::Mountable::get_closing_node(&child).is_connected()
+            {
+                // the server didn't render this child, so it was created
+                // from scratch and needs to be added
                 mount_child(MountKind::Append(self.element.as_ref()), &child);
             }
 
@@ -730,7 +999,10 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
 
         #[cfg(all(target_arch = "wasm32", feature = "web"))]
         {
-            self.element.as_ref().set_inner_html(&html);
+            // the server rendered the same HTML, so it's left alone
+            if !HydrationCtx::is_hydrating() {
+                self.element.as_ref().set_inner_html(&html);
+            }
 
             self
         }
@@ -753,6 +1025,24 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
             this
         }
     }
+
+    /// Sets the inner HTML of this element, updating it whenever a (presumably
+    /// reactive) value changes. This is the same as the `inner_html` attribute
+    /// in the [`view`](https://docs.rs/leptos_macro/latest/leptos_macro/macro.view.html)
+    /// macro.
+    ///
+    /// The HTML is neither escaped nor sanitized, on the client or the
+    /// server. While hydrating, the HTML the server rendered is kept as it
+    /// is, and isn't walked for hydration keys, so this element shouldn't
+    /// have any other children.
+    ///
+    /// # Security
+    /// Only pass HTML you trust, or have sanitized yourself. Anything else
+    /// is a cross-site scripting (XSS) vulnerability.
+    #[track_caller]
+    pub fn dangerously_set_inner_html(self, html: impl IntoAttribute) -> Self {
+        self.attr("inner_html", html)
+    }
 }
 
 impl<El: ElementDescriptor> IntoView for HtmlElement<El> {
@@ -953,51 +1243,10 @@ fn create_leptos_element(
     id: crate::HydrationKey,
     clone_element: fn() -> web_sys::HtmlElement,
 ) -> web_sys::HtmlElement {
-    #[cfg(not(debug_assertions))]
-    {
-        _ = tag;
-    }
-
     if HydrationCtx::is_hydrating() {
-        if let Some(el) = crate::document().get_element_by_id(&format!("_{id}"))
-        {
-            #[cfg(debug_assertions)]
-            assert_eq!(
-                &el.node_name().to_ascii_uppercase(),
-                tag,
-                "SSR and CSR elements have the same `TopoId` but different \
-                 node kinds. This is either a discrepancy between SSR and CSR \
-                 rendering
-            logic, which is considered a bug, or it can also be a leptos \
-                 hydration issue."
-            );
-
-            el.remove_attribute("id").unwrap();
-
-            el.unchecked_into()
-        } else if let Ok(Some(el)) =
-            crate::document().query_selector(&format!("[leptos-hk=_{id}]"))
-        {
-            #[cfg(debug_assertions)]
-            assert_eq!(
-                el.node_name().to_ascii_uppercase(),
-                tag,
-                "SSR and CSR elements have the same `TopoId` but different \
-                 node kinds. This is either a discrepancy between SSR and CSR \
-                 rendering
-            logic, which is considered a bug, or it can also be a leptos \
-                 hydration issue."
-            );
-
-            el.remove_attribute("leptos-hk").unwrap();
-
-            el.unchecked_into()
-        } else {
-            crate::warn!(
-                "element with id {id} not found, ignoring it for hydration"
-            );
-
-            clone_element()
+        match HydrationCtx::find_element(&id, tag) {
+            Some(el) => el.unchecked_into(),
+            None => clone_element(),
         }
     } else {
         clone_element()
@@ -8,28 +8,24 @@ cfg_if! {
     use wasm_bindgen::JsCast;
 
     // We can tell if we start in hydration mode by checking to see if the
-    // id "_0-0-0" is present in the DOM. If it is, we know we are hydrating from
-    // the server, if not, we are starting off in CSR
+    // id "_1-0-0" is present in the DOM. If it is, we know we are hydrating from
+    // the server, if not, we are starting off in CSR. Only builds made to
+    // hydrate check, so that a client-rendered app never starts hydrating
+    // because the page happens to look server-rendered; `hydrate_to` starts
+    // hydrating explicitly, in any build.
     thread_local! {
       static HYDRATION_COMMENTS: LazyCell<HashMap<String, web_sys::Comment>> = LazyCell::new(|| {
-        let document = crate::document();
-        let body = document.body().unwrap();
-        let walker = document
-          .create_tree_walker_with_what_to_show(&body, 128)
-          .unwrap();
-        let mut map = HashMap::new();
-        while let Ok(Some(node)) = walker.next_node() {
-          if let Some(content) = node.text_content() {
-            if let Some(hk) = content.strip_prefix("hk=") {
-              if let Some(hk) = hk.split('|').next() {
-                map.insert(hk.into(), node.unchecked_into());
-              }
-            }
-          }
-        }
-        map
+        collect_markers(&crate::document().body().unwrap())
       });
 
+      // markers found in roots passed to `hydrate_to`, which may be in a
+      // shadow root the scan of the `<body>` doesn't reach
+      static ROOT_COMMENTS: RefCell<HashMap<String, web_sys::Comment>> = Default::default();
+
+      // the node `hydrate_to` is hydrating, if any, which elements are looked
+      // up in instead of the whole document
+      static ROOT: RefCell<Option<web_sys::Node>> = Default::default();
+
       #[cfg(debug_assertions)]
       pub(crate) static VIEW_MARKERS: LazyCell<HashMap<String, web_sys::Comment>> = LazyCell::new(|| {
         let document = crate::document();
@@ -48,28 +44,249 @@ cfg_if! {
         map
       });
 
+      // elements that couldn't be hydrated, reported when hydration ends
+      static MISMATCHES: std::cell::Cell<usize> = std::cell::Cell::new(0);
+
+      // mismatched elements no subtree has been rebuilt for yet
+      static PENDING_MISMATCHES: std::cell::Cell<usize> = std::cell::Cell::new(0);
+
+      // subtrees rebuilt on the client because of a mismatch
+      static RECOVERIES: std::cell::Cell<usize> = std::cell::Cell::new(0);
+
+      // server-rendered nodes no view claimed, counted when hydration ends
+      static UNCLAIMED: std::cell::Cell<usize> = std::cell::Cell::new(0);
+
+      // whether to take unclaimed nodes out of the DOM when hydration ends
+      static REMOVE_UNCLAIMED: std::cell::Cell<bool> = std::cell::Cell::new(false);
+
+      // the nodes that replaced markers claimed while hydrating, by key, so
+      // that a `<Suspense/>` chunk streamed in later, which had the same
+      // markers rendered again, can be given back the ones components hold
+      static CLAIMED: RefCell<HashMap<String, web_sys::Node>> = Default::default();
+
+      // the `<Suspense/>` chunks that have been registered, but not hydrated
+      static STREAMED: RefCell<std::collections::HashSet<String>> = Default::default();
+
+      // called when the `<Suspense/>` chunk with the given key is registered
+      #[allow(clippy::type_complexity)]
+      static CHUNK_WAITING: RefCell<HashMap<String, Vec<Box<dyn FnOnce()>>>> = Default::default();
+
+      // whether the swap script's event is being listened for
+      static CHUNK_LISTENER: std::cell::Cell<bool> = std::cell::Cell::new(false);
+
+      // set while rendering a subtree the server didn't render, which is
+      // created from scratch instead of hydrated
+      static CLIENT_RENDERING: std::cell::Cell<bool> = std::cell::Cell::new(false);
+
       static IS_HYDRATING: RefCell<LazyCell<bool>> = RefCell::new(LazyCell::new(|| {
-        #[cfg(debug_assertions)]
-        return crate::document().get_element_by_id("_0-0-0").is_some()
-          || crate::document().get_element_by_id("_0-0-0o").is_some()
-          || HYDRATION_COMMENTS.with(|comments| comments.get("_0-0-0o").is_some());
+        if leptos_reactive::RenderMode::default() != leptos_reactive::RenderMode::Hydrate {
+          return false;
+        }
 
-        #[cfg(not(debug_assertions))]
-        return crate::document().get_element_by_id("_0-0-0").is_some()
-          || HYDRATION_COMMENTS.with(|comments| comments.get("_0-0-0").is_some());
+        let rendered_with = |version: u8| {
+          let first = format!("_{version}-0-0");
+          let document = crate::document();
+
+          #[cfg(debug_assertions)]
+          return document.get_element_by_id(&first).is_some()
+            || document.get_element_by_id(&format!("{first}o")).is_some()
+            || HYDRATION_COMMENTS.with(|comments| comments.get(&format!("{first}o")).is_some());
+
+          #[cfg(not(debug_assertions))]
+          return document.get_element_by_id(&first).is_some()
+            || HYDRATION_COMMENTS.with(|comments| comments.get(&first).is_some());
+        };
+
+        let hydrating = rendered_with(HydrationKey::VERSION);
+        if !hydrating && rendered_with(0) {
+          crate::warn!(
+            "[HydrationCtx] The page was rendered by a server that formats \
+             hydration keys differently, so it will be rendered on the client \
+             instead of hydrated. The server and the client should use the \
+             same version of Leptos."
+          );
+        }
+        hydrating
       }));
     }
 
     pub(crate) fn get_marker(id: &str) -> Option<web_sys::Comment> {
-      HYDRATION_COMMENTS.with(|comments| comments.get(id).cloned())
+      ROOT_COMMENTS
+        .with(|comments| comments.borrow().get(id).cloned())
+        .or_else(|| HYDRATION_COMMENTS.with(|comments| comments.get(id).cloned()))
+    }
+
+    pub(crate) fn claim_marker(id: String, node: &web_sys::Node) {
+      CLAIMED.with(|claimed| claimed.borrow_mut().insert(id, node.clone()));
+    }
+
+    fn collect_markers(root: &web_sys::Node) -> HashMap<String, web_sys::Comment> {
+      let walker = crate::document()
+        .create_tree_walker_with_what_to_show(root, 128)
+        .unwrap();
+      let mut map = HashMap::new();
+      while let Ok(Some(node)) = walker.next_node() {
+        if let Some(hk) = marker_key(&node) {
+          map.insert(hk, node.unchecked_into());
+        }
+      }
+      map
+    }
+
+    // the key in a `<!--hk=...-->` marker
+    fn marker_key(node: &web_sys::Node) -> Option<String> {
+      let content = node.text_content()?;
+      let hk = content.strip_prefix("hk=")?;
+      hk.split('|').next().map(Into::into)
+    }
+
+    // the comments the server put around the `<Suspense/>` with the given key
+    fn suspense_boundary(id: &str) -> Option<(web_sys::Node, web_sys::Node)> {
+      let document = crate::document();
+      let body = document.body()?;
+      let walker = document
+        .create_tree_walker_with_what_to_show(&body, 128)
+        .ok()?;
+      let (open_text, close_text) = (format!("suspense-open-{id}"), format!("suspense-close-{id}"));
+      let (mut open, mut close) = (None, None);
+      while let Ok(Some(node)) = walker.next_node() {
+        match node.text_content() {
+          Some(text) if text == open_text => open = Some(node),
+          Some(text) if text == close_text => close = Some(node),
+          _ => {}
+        }
+      }
+      open.zip(close)
+    }
+
+    // the markers in either map that are still inside `root`, which means
+    // nothing claimed them, since claimed markers are replaced
+    #[cfg(debug_assertions)]
+    fn leftover_markers(root: &web_sys::Node) -> HashMap<String, web_sys::Comment> {
+      let mut leftover = HYDRATION_COMMENTS.with(|comments| (**comments).clone());
+      ROOT_COMMENTS.with(|comments| leftover.extend(comments.borrow().clone()));
+      leftover.retain(|_, marker| root.contains(Some(marker)));
+      leftover
+    }
+
+    // whether `id` looks like one given to an element by `HydrationCtx::id`,
+    // like `_0-0-3` or `_sidebar-1`
+    #[cfg(debug_assertions)]
+    fn is_hydration_id(id: &str) -> bool {
+      id.strip_prefix('_').is_some_and(|key| {
+        key.contains('-')
+          && key.ends_with(|c: char| c.is_ascii_digit())
+          && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+      })
+    }
+
+    // dispatches the events recorded by `event_replay_script` inside `root`
+    // again, now that hydration has added the listeners for them, and keeps
+    // the rest for roots that are hydrated later
+    fn replay_captured_events(root: &web_sys::Node) {
+      use wasm_bindgen::JsValue;
+
+      let window = crate::window();
+      let Ok(captured) = js_sys::Reflect::get(&window, &JsValue::from_str("__LEPTOS_CAPTURED_EVENTS")) else {
+        return;
+      };
+      let Some(captured) = captured.dyn_ref::<js_sys::Function>() else {
+        return;
+      };
+      let Ok(queue) = captured.call0(&JsValue::NULL) else {
+        return;
+      };
+      let queue = queue.unchecked_into::<js_sys::Array>();
+      let events = queue.splice(0, queue.length(), &JsValue::UNDEFINED);
+
+      for event in events.iter() {
+        let target = js_sys::Reflect::get(&event, &JsValue::from_str("target"))
+          .ok()
+          .and_then(|target| target.dyn_into::<web_sys::Node>().ok());
+        let kind = js_sys::Reflect::get(&event, &JsValue::from_str("type"))
+          .ok()
+          .and_then(|kind| kind.as_string());
+        let (Some(target), Some(kind)) = (target, kind) else {
+          continue;
+        };
+        if !root.contains(Some(&target)) {
+          queue.push(&event);
+          continue;
+        }
+
+        // a real click already toggled a checkbox or radio button, and a
+        // synthetic one would toggle it back
+        let toggles = target
+          .dyn_ref::<web_sys::HtmlInputElement>()
+          .is_some_and(|input| matches!(input.type_().as_str(), "checkbox" | "radio"));
+        let replayed = if kind == "click" && !toggles {
+          let init = web_sys::MouseEventInit::new();
+          init.set_bubbles(true);
+          init.set_cancelable(true);
+          web_sys::MouseEvent::new_with_mouse_event_init_dict(&kind, &init)
+            .map(|event| event.unchecked_into::<web_sys::Event>())
+        } else {
+          let init = web_sys::EventInit::new();
+          init.set_bubbles(true);
+          init.set_cancelable(true);
+          web_sys::Event::new_with_event_init_dict(&kind, &init)
+        };
+        let Ok(replayed) = replayed else {
+          continue;
+        };
+        _ = target.dispatch_event(&replayed);
+
+        // the recorded submit was prevented, so submit the form now if
+        // nothing handles it
+        if kind == "submit" && !replayed.default_prevented() {
+          if let Some(form) = target.dyn_ref::<web_sys::HtmlFormElement>() {
+            _ = form.submit();
+          }
+        }
+      }
+    }
+
+    // finds the first element matching `selector` within `root`
+    fn query_root(root: &web_sys::Node, selector: &str) -> Option<web_sys::Element> {
+      let found = if let Some(el) = root.dyn_ref::<web_sys::Element>() {
+        el.query_selector(selector)
+      } else if let Some(fragment) = root.dyn_ref::<web_sys::DocumentFragment>() {
+        fragment.query_selector(selector)
+      } else if let Some(document) = root.dyn_ref::<web_sys::Document>() {
+        document.query_selector(selector)
+      } else {
+        Ok(None)
+      };
+      found.ok().flatten()
     }
   }
 }
 
+// the tag names generated for multi-word SVG elements, like `FE_BLEND`, have
+// underscores where the DOM's are camel-cased
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn same_tag(node_name: &str, tag: &str) -> bool {
+    let letters = |name: &str| {
+        name.chars()
+            .filter(|c| *c != '_')
+            .map(|c| c.to_ascii_lowercase())
+            .collect::<String>()
+    };
+    letters(node_name) == letters(tag)
+}
+
 /// A stable identifier within the server-rendering or hydration process.
+///
+/// A key is the path of offsets from the root to the current component,
+/// followed by the offset within it. To keep the markup small, each offset is
+/// written in base 36, and a segment repeated `n` times in a row, like the `0`s
+/// left by a list of sibling components, is written once as `{offset}R{n}`.
+/// The first segment of the path is [VERSION](HydrationKey::VERSION), or the
+/// name given to [root](HydrationKey::root), so that HTML rendered with keys
+/// in another format is never hydrated with the wrong nodes.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct HydrationKey {
-    /// The key of the previous component.
+    /// The key of the previous component, already encoded.
     pub previous: String,
     /// The element offset within the current component.
     pub offset: usize,
@@ -77,14 +294,127 @@ pub struct HydrationKey {
 
 impl Display for HydrationKey {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}{}", self.previous, self.offset)
+        f.write_str(&self.previous)?;
+        f.write_str(&to_base36(self.offset))
+    }
+}
+
+fn to_base36(mut n: usize) -> String {
+    const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    let mut digits = Vec::new();
+    loop {
+        digits.push(DIGITS[n % 36]);
+        n /= 36;
+        if n == 0 {
+            break;
+        }
+    }
+    digits.reverse();
+    String::from_utf8(digits).unwrap()
+}
+
+fn from_base36(s: &str) -> Option<usize> {
+    // `from_str_radix` also accepts uppercase letters and a leading `+`
+    if s.is_empty()
+        || !s
+            .bytes()
+            .all(|b| b.is_ascii_digit() || b.is_ascii_lowercase())
+    {
+        return None;
+    }
+    usize::from_str_radix(s, 36).ok()
+}
+
+impl HydrationKey {
+    /// The format of the keys, which is the first segment of every key not
+    /// under a [root](HydrationKey::root). Keys without a version come from
+    /// the previous format, which always started with `0`.
+    pub const VERSION: u8 = 1;
+
+    /// Parses a key formatted with [Display], without the leading `_` of
+    /// element IDs.
+    pub fn parse(key: &str) -> Option<Self> {
+        let (previous, offset) = key.rsplit_once('-')?;
+        let previous = format!("{previous}-");
+        let offset = from_base36(offset)?;
+        let id = Self { previous, offset };
+        id.decode_path()?;
+        Some(id)
+    }
+
+    /// The offsets of the components this key is nested in, from the
+    /// outermost in, not counting the version or root name.
+    pub fn path(&self) -> Vec<usize> {
+        self.decode_path().unwrap_or_default()
+    }
+
+    fn decode_path(&self) -> Option<Vec<usize>> {
+        let mut segments = self.previous.strip_suffix('-')?.split('-');
+        segments.next()?;
+        let mut path = Vec::new();
+        for segment in segments {
+            match segment.split_once('R') {
+                Some((offset, count)) => {
+                    let offset = from_base36(offset)?;
+                    path.extend(std::iter::repeat_n(
+                        offset,
+                        from_base36(count)?,
+                    ));
+                }
+                None => path.push(from_base36(segment)?),
+            }
+        }
+        Some(path)
+    }
+
+    // adds the current offset to the end of the path, merging it into the
+    // last segment if that's a run of the same offset
+    fn push_offset(&mut self) {
+        let offset = to_base36(self.offset);
+        let path = self.previous.strip_suffix('-').unwrap_or(&self.previous);
+        // the first segment is the version or root name, so it's never merged
+        if let Some((start, last)) = path.rsplit_once('-') {
+            let (run, count) = last
+                .split_once('R')
+                .and_then(|(run, count)| Some((run, from_base36(count)?)))
+                .unwrap_or((last, 1));
+            if run == offset {
+                self.previous =
+                    format!("{start}-{run}R{}-", to_base36(count + 1));
+                return;
+            }
+        }
+        self.previous.push_str(&offset);
+        self.previous.push('-');
+    }
+
+    /// The first key of a separately hydrated root, so that its keys don't
+    /// collide with those of other roots on the same page. `name` should only
+    /// contain letters, digits and `_`.
+    ///
+    /// On the server, continue from this key before rendering the root's view:
+    /// ```
+    /// # use leptos::*;
+    /// # use leptos_dom::{HydrationCtx, HydrationKey};
+    /// let html = leptos::ssr::render_to_string(|cx| {
+    ///     HydrationCtx::continue_from(HydrationKey::root("sidebar"));
+    ///     view! { cx, <aside>"Sidebar"</aside> }
+    /// });
+    /// # assert!(html.contains("_sidebar-"), "{html}");
+    /// ```
+    /// and hydrate it in the browser with [hydrate_to](crate::hydrate_to).
+    pub fn root(name: &str) -> Self {
+        Self {
+            previous: format!("{name}-"),
+            offset: 0,
+        }
     }
 }
 
 impl Default for HydrationKey {
     fn default() -> Self {
         Self {
-            previous: "0-".to_string(),
+            previous: format!("{}-", Self::VERSION),
             offset: 0,
         }
     }
@@ -92,10 +422,23 @@ impl Default for HydrationKey {
 
 thread_local!(static ID: RefCell<HydrationKey> = Default::default());
 
+/// A server-rendered node nothing claimed: its key, its HTML, and a callback
+/// that removes it.
+#[cfg(all(debug_assertions, target_arch = "wasm32", feature = "web"))]
+type UnclaimedNode = (String, String, Box<dyn FnOnce()>);
+
 /// Control and utility methods for hydration.
 pub struct HydrationCtx;
 
 impl HydrationCtx {
+    /// Makes resources include the current key in the tag their serialized
+    /// data is sent with. Resources are created at the same key on the server
+    /// and the client, so this tells them apart if they're created in a
+    /// different order. Everything that runs a view calls this first.
+    pub(crate) fn tag_resources() {
+        leptos_reactive::tag_resources_with(|| Self::peek().to_string());
+    }
+
     /// Get the next `id` without incrementing it.
     pub fn peek() -> HydrationKey {
         ID.with(|id| id.borrow().clone())
@@ -114,9 +457,7 @@ impl HydrationCtx {
     pub fn next_component() -> HydrationKey {
         ID.with(|id| {
             let mut id = id.borrow_mut();
-            let offset = id.offset;
-            id.previous.push_str(&offset.to_string());
-            id.previous.push('-');
+            id.push_offset();
             id.offset = 0;
             id.clone()
         })
@@ -134,16 +475,534 @@ impl HydrationCtx {
         ID.with(|i| *i.borrow_mut() = id);
     }
 
+    /// Runs `f` with keys continuing from `id`, then goes back to the current
+    /// key. This way the keys of whatever `f` renders depend only on `id`, and
+    /// not on what was rendered before it, e.g., when `<Suspense/>` fragments
+    /// resolve in a different order than they were created.
+    ///
+    /// No borrow of the current key is held while `f` runs, so it can call
+    /// this again, and the key is still restored if `f` panics.
+    pub fn with_key<T>(id: HydrationKey, f: impl FnOnce() -> T) -> T {
+        // puts the previous key back when dropped, even while unwinding
+        struct Restore(Option<HydrationKey>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                if let Some(prev) = self.0.take() {
+                    HydrationCtx::continue_from(prev);
+                }
+            }
+        }
+
+        let _restore = Restore(Some(ID.with(|i| i.replace(id))));
+        f()
+    }
+
+    /// Starts hydrating the HTML inside `root`, which was rendered from
+    /// [HydrationKey::root] with the given `name`.
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    pub(crate) fn start_hydrating(root: &web_sys::Node, name: &str) {
+        Self::start_hydrating_from(root, HydrationKey::root(name));
+    }
+
+    /// Starts hydrating the HTML inside `root`, which was rendered with keys
+    /// starting from `id`.
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    pub(crate) fn start_hydrating_from(root: &web_sys::Node, id: HydrationKey) {
+        let markers = collect_markers(root);
+        ROOT_COMMENTS.with(|comments| comments.borrow_mut().extend(markers));
+        ROOT.with(|r| *r.borrow_mut() = Some(root.clone()));
+        IS_HYDRATING.with(|is_hydrating| {
+            *is_hydrating.borrow_mut() = LazyCell::new(|| true);
+        });
+        Self::continue_from(id);
+    }
+
     #[cfg(all(target_arch = "wasm32", feature = "web"))]
     pub(crate) fn stop_hydrating() {
+        let was_hydrating = Self::is_hydrating();
         IS_HYDRATING.with(|is_hydrating| {
             std::mem::take(&mut *is_hydrating.borrow_mut());
-        })
+        });
+        let root = ROOT.with(|root| root.borrow_mut().take());
+
+        if was_hydrating {
+            let root = root.unwrap_or_else(|| {
+                crate::document().body().unwrap().unchecked_into()
+            });
+            #[cfg(debug_assertions)]
+            Self::report_unclaimed(&root);
+
+            // once the view has been mounted
+            wasm_bindgen_futures::spawn_local(async move {
+                replay_captured_events(&root);
+            });
+        }
+
+        let mismatches = Self::mismatches();
+        if mismatches > 0 {
+            crate::warn!(
+                "[HydrationCtx] {mismatches} element(s) didn't match the \
+                 server-rendered HTML, so new ones were created instead. See \
+                 the warnings above for where."
+            );
+        }
     }
 
+    /// Registers the `<Suspense/>` chunk with the given key, which was streamed
+    /// in out of order after hydration started, so that the components inside
+    /// it are hydrated from the streamed HTML instead of being created again.
+    ///
+    /// The script that swaps each chunk in dispatches a `leptos:streamed-chunk`
+    /// event on the document, with the key as its `detail`, which calls this
+    /// once a `<Suspense/>` is [waiting](HydrationCtx::on_streamed_chunk) for
+    /// a chunk. An integration that inserts chunks some other way should do
+    /// the same, or call this. Chunks that arrive before hydration starts
+    /// don't need it.
+    pub fn register_streamed_chunk(id: &str) {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            let Some((open, close)) = suspense_boundary(id) else {
+                crate::warn!(
+                    "[HydrationCtx] The streamed chunk for <Suspense/> {id} \
+                     is not on the page, so it can't be hydrated."
+                );
+                return;
+            };
+
+            let mut markers = HashMap::new();
+            let mut next = open.next_sibling();
+            while let Some(node) = next.filter(|node| *node != close) {
+                if let Some(hk) = marker_key(&node) {
+                    markers.insert(hk, node.clone().unchecked_into());
+                }
+                markers.extend(collect_markers(&node));
+                next = node.next_sibling();
+            }
+
+            // components that were hydrated from the fallback's HTML keep
+            // the markers they claimed, which the swap took out of the page,
+            // so those go back where the chunk has the same ones
+            markers.retain(|hk, marker: &mut web_sys::Comment| {
+                let detached = CLAIMED.with(|claimed| {
+                    claimed
+                        .borrow()
+                        .get(hk)
+                        .filter(|claimed| !claimed.is_connected())
+                        .cloned()
+                });
+                match detached {
+                    Some(claimed) => {
+                        _ = marker.replace_with_with_node_1(&claimed);
+                        false
+                    }
+                    None => true,
+                }
+            });
+            ROOT_COMMENTS
+                .with(|comments| comments.borrow_mut().extend(markers));
+            STREAMED
+                .with(|streamed| streamed.borrow_mut().insert(id.to_string()));
+
+            let waiting = CHUNK_WAITING
+                .with(|waiting| waiting.borrow_mut().remove(id))
+                .unwrap_or_default();
+            for f in waiting {
+                f();
+            }
+        }
+
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        let _ = id;
+    }
+
+    /// Calls `f` once the `<Suspense/>` chunk with the given key is
+    /// [registered](HydrationCtx::register_streamed_chunk), and starts
+    /// listening for the swap script's event if nothing is yet. `f` is never
+    /// called outside the browser.
+    pub fn on_streamed_chunk(id: &str, f: impl FnOnce() + 'static) {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            if !CHUNK_LISTENER.with(|listening| listening.replace(true)) {
+                let listener = wasm_bindgen::closure::Closure::<
+                    dyn Fn(web_sys::CustomEvent),
+                >::new(
+                    |event: web_sys::CustomEvent| {
+                        if let Some(id) = event.detail().as_string() {
+                            Self::register_streamed_chunk(&id);
+                        }
+                    },
+                )
+                .into_js_value();
+                _ = crate::document().add_event_listener_with_callback(
+                    "leptos:streamed-chunk",
+                    listener.unchecked_ref(),
+                );
+            }
+            CHUNK_WAITING.with(|waiting| {
+                waiting
+                    .borrow_mut()
+                    .entry(id.to_string())
+                    .or_default()
+                    .push(Box::new(f));
+            });
+        }
+
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        let _ = (id, f);
+    }
+
+    /// Runs `f`, hydrating what it renders from the `<Suspense/>` chunk with
+    /// the given key if that was [registered](HydrationCtx::register_streamed_chunk)
+    /// and hasn't been hydrated yet. Otherwise, `f` just renders as usual.
+    pub fn hydrate_streamed_chunk<T>(id: &str, f: impl FnOnce() -> T) -> T {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            // hydration is over once `f` is done with the chunk, even if it
+            // panics
+            struct Restore;
+            impl Drop for Restore {
+                fn drop(&mut self) {
+                    IS_HYDRATING.with(|is_hydrating| {
+                        std::mem::take(&mut *is_hydrating.borrow_mut());
+                    });
+                }
+            }
+
+            let streamed =
+                STREAMED.with(|streamed| streamed.borrow_mut().remove(id));
+            if streamed && !Self::is_hydrating() {
+                IS_HYDRATING.with(|is_hydrating| {
+                    *is_hydrating.borrow_mut() = LazyCell::new(|| true);
+                });
+                let _restore = Restore;
+                return f();
+            }
+        }
+
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        let _ = id;
+
+        f()
+    }
+
+    /// How many elements couldn't be hydrated because the server-rendered
+    /// HTML didn't have a matching one. This is always `0` outside the browser.
+    pub fn mismatches() -> usize {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        return MISMATCHES.with(std::cell::Cell::get);
+
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        0
+    }
+
+    /// How many parts of the page were rendered on the client from scratch
+    /// while hydrating, because an element in them didn't match the
+    /// server-rendered HTML. This is always `0` outside the browser.
+    pub fn recoveries() -> usize {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        return RECOVERIES.with(std::cell::Cell::get);
+
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        0
+    }
+
+    /// How many mismatched elements haven't had their subtree
+    /// [recovered](HydrationCtx::recover_subtree) yet.
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    pub(crate) fn pending_mismatches() -> usize {
+        PENDING_MISMATCHES.with(std::cell::Cell::get)
+    }
+
+    /// Takes the server-rendered nodes from `first` up to, but not including,
+    /// `end` out of the page, so that the view hydrated from them, which
+    /// found a mismatch when there were `pending` [pending_mismatches](HydrationCtx::pending_mismatches),
+    /// can be rendered on the client again instead of being left with half
+    /// of its nodes claimed. The mismatches it found count as recovered, so
+    /// the views around it don't rebuild themselves too.
     #[cfg(all(target_arch = "wasm32", feature = "web"))]
-    pub(crate) fn is_hydrating() -> bool {
-        IS_HYDRATING.with(|is_hydrating| **is_hydrating.borrow())
+    pub(crate) fn recover_subtree(
+        id: &HydrationKey,
+        first: Option<web_sys::Node>,
+        end: &web_sys::Node,
+        pending: usize,
+    ) {
+        let mut removed = 0;
+        let mut next = first;
+        while let Some(node) = next.filter(|node| node != end) {
+            next = node.next_sibling();
+            // markers claimed in here can't be given back to anything later
+            CLAIMED.with(|claimed| {
+                claimed
+                    .borrow_mut()
+                    .retain(|_, claimed| !node.contains(Some(claimed)))
+            });
+            node.unchecked_ref::<web_sys::Element>().remove();
+            removed += 1;
+        }
+
+        PENDING_MISMATCHES.with(|n| n.set(pending));
+        RECOVERIES.with(|n| n.set(n.get() + 1));
+        crate::warn!(
+            "[HydrationCtx] The server-rendered HTML of the dynamic child at \
+             _{id} didn't match, so its {removed} node(s) were removed and it \
+             was rendered on the client instead."
+        );
+    }
+
+    /// How many server-rendered elements and components weren't claimed by
+    /// any view when hydration ended, e.g., because the client skipped a
+    /// component the server rendered. Nested ones are counted too. These are
+    /// only looked for in debug builds, so this is always `0` in release
+    /// builds and outside the browser.
+    pub fn unclaimed_count() -> usize {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        return UNCLAIMED.with(std::cell::Cell::get);
+
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        0
+    }
+
+    /// Sets whether server-rendered nodes that no view claimed are removed
+    /// from the page when hydration ends, so that they don't linger without
+    /// being updated. They're always left in place by default. Like
+    /// [unclaimed_count](HydrationCtx::unclaimed_count), this only has an
+    /// effect in debug builds.
+    pub fn remove_unclaimed(remove: bool) {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        REMOVE_UNCLAIMED.with(|r| r.set(remove));
+
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        let _ = remove;
+    }
+
+    // logs every node inside `root` that still has a hydration key, and
+    // removes it if asked to
+    #[cfg(all(debug_assertions, target_arch = "wasm32", feature = "web"))]
+    fn report_unclaimed(root: &web_sys::Node) {
+        use wasm_bindgen::JsValue;
+        use web_sys::console;
+
+        let remove = REMOVE_UNCLAIMED.with(std::cell::Cell::get);
+        let mut unclaimed: Vec<UnclaimedNode> = Vec::new();
+
+        let markers = leftover_markers(root);
+        for (key, marker) in &markers {
+            if let Some(base) = key.strip_suffix('o') {
+                let Some(end) = markers.get(&format!("{base}c")) else {
+                    continue;
+                };
+                let mut html = String::new();
+                let mut node = marker.next_sibling();
+                while let Some(n) =
+                    node.filter(|n| n != end.unchecked_ref::<web_sys::Node>())
+                {
+                    match n.dyn_ref::<web_sys::Element>() {
+                        Some(el) => html.push_str(&el.outer_html()),
+                        None => {
+                            html.push_str(&n.text_content().unwrap_or_default())
+                        }
+                    }
+                    node = n.next_sibling();
+                }
+                let range = web_sys::Range::new().unwrap();
+                range.set_start_before(marker).unwrap();
+                range.set_end_after(end).unwrap();
+                unclaimed.push((
+                    key.clone(),
+                    html,
+                    Box::new(move || range.delete_contents().unwrap()),
+                ));
+            }
+        }
+
+        let walker = crate::document()
+            .create_tree_walker_with_what_to_show(root, 1)
+            .unwrap();
+        while let Ok(Some(node)) = walker.next_node() {
+            let el = node.unchecked_into::<web_sys::Element>();
+            let key = el
+                .get_attribute("leptos-hk")
+                .or_else(|| Some(el.id()).filter(|id| is_hydration_id(id)));
+            if let Some(key) = key {
+                let html = el.outer_html();
+                unclaimed.push((key, html, Box::new(move || el.remove())));
+            }
+        }
+
+        UNCLAIMED.with(|n| n.set(unclaimed.len()));
+        if unclaimed.is_empty() {
+            return;
+        }
+
+        const SNIPPET_LEN: usize = 200;
+
+        console::group_collapsed_1(&JsValue::from_str(&format!(
+            "[HydrationCtx] {} server-rendered node(s) weren't claimed by any \
+             view{}",
+            unclaimed.len(),
+            if remove { ", and were removed" } else { "" }
+        )));
+        for (key, html, _) in &unclaimed {
+            let html = match html.char_indices().nth(SNIPPET_LEN) {
+                Some((end, _)) => format!("{}…", &html[..end]),
+                None => html.clone(),
+            };
+            console::warn_1(&JsValue::from_str(&format!("{key}: {html}")));
+        }
+        console::warn_1(&JsValue::from_str(
+            "The server rendered these, but the client didn't, e.g., because \
+             a `cfg` or the data it read differs between them.",
+        ));
+        console::group_end();
+
+        if remove {
+            for (_, _, remove) in unclaimed {
+                remove();
+            }
+        }
+    }
+
+    /// Finds the server-rendered element with the given key, and takes the key
+    /// off it. Returns `None` and reports a mismatch if there isn't one, or, in
+    /// debug builds, if it isn't a `tag` element.
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    pub(crate) fn find_element(
+        id: &HydrationKey,
+        tag: &str,
+    ) -> Option<web_sys::Element> {
+        let found = match ROOT.with(|root| root.borrow().clone()) {
+            Some(root) => query_root(&root, &format!("[id=\"_{id}\"]"))
+                .map(|el| (el, "id"))
+                .or_else(|| {
+                    query_root(&root, &format!("[leptos-hk=_{id}]"))
+                        .map(|el| (el, "leptos-hk"))
+                }),
+            None => {
+                let document = crate::document();
+                document
+                    .get_element_by_id(&format!("_{id}"))
+                    .map(|el| (el, "id"))
+                    .or_else(|| {
+                        document
+                            .query_selector(&format!("[leptos-hk=_{id}]"))
+                            .ok()
+                            .flatten()
+                            .map(|el| (el, "leptos-hk"))
+                    })
+            }
+        };
+
+        match found {
+            Some((el, attr))
+                if cfg!(not(debug_assertions))
+                    || same_tag(&el.node_name(), tag) =>
+            {
+                el.remove_attribute(attr).unwrap();
+                Some(el)
+            }
+            found => {
+                MISMATCHES.with(|n| n.set(n.get() + 1));
+                PENDING_MISMATCHES.with(|n| n.set(n.get() + 1));
+                Self::report_mismatch(id, tag, found.map(|(el, _)| el));
+                None
+            }
+        }
+    }
+
+    /// Whether the server rendered the component with the given key. If it
+    /// didn't, this reports a mismatch, and the component should be rendered
+    /// with [client_render](HydrationCtx::client_render).
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    pub(crate) fn has_component(id: &HydrationKey, name: &str) -> bool {
+        let found = get_marker(&Self::to_string(id, true)).is_some();
+        if !found {
+            MISMATCHES.with(|n| n.set(n.get() + 1));
+            crate::warn!(
+                "[HydrationCtx] The server didn't render <{name}/> at _{id}, \
+                 so it and everything inside it will be rendered on the \
+                 client instead."
+            );
+        }
+        found
+    }
+
+    /// Runs `f` as if the page weren't being hydrated, so that the subtree it
+    /// creates doesn't claim any server-rendered nodes. This keeps a part of
+    /// the page the server didn't render from taking nodes that belong to
+    /// the rest of it.
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    pub fn client_render<T>(f: impl FnOnce() -> T) -> T {
+        // like in `with_key`, restores the flag even if `f` panics
+        struct Restore(bool);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CLIENT_RENDERING.with(|c| c.set(self.0));
+            }
+        }
+
+        let _restore = Restore(CLIENT_RENDERING.with(|c| c.replace(true)));
+        f()
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    fn report_mismatch(
+        id: &HydrationKey,
+        tag: &str,
+        found: Option<web_sys::Element>,
+    ) {
+        cfg_if! {
+            if #[cfg(debug_assertions)] {
+                use wasm_bindgen::JsValue;
+                use web_sys::console;
+
+                const SNIPPET_LEN: usize = 200;
+
+                let found = match found {
+                    Some(el) => {
+                        let html = el.outer_html();
+                        match html.char_indices().nth(SNIPPET_LEN) {
+                            Some((end, _)) => format!("{}…", &html[..end]),
+                            None => html,
+                        }
+                    }
+                    None => "nothing".to_string(),
+                };
+                console::group_collapsed_1(&JsValue::from_str(&format!(
+                    "[HydrationCtx] Hydration mismatch at _{id}"
+                )));
+                console::warn_1(&JsValue::from_str(&format!(
+                    "expected: <{}>",
+                    tag.to_ascii_lowercase()
+                )));
+                console::warn_1(&JsValue::from_str(&format!("found: {found}")));
+                console::warn_1(&JsValue::from_str(
+                    "The server and the client rendered different views here, \
+                     e.g., because they read different data or a `cfg` \
+                     differs between them. A new element is created instead, \
+                     so this part of the page may be duplicated or lose its \
+                     event listeners.",
+                ));
+                console::group_end();
+            } else {
+                _ = (tag, found);
+                crate::warn!(
+                    "element with id {id} not found, ignoring it for hydration"
+                );
+            }
+        }
+    }
+
+    /// Whether server-rendered HTML is currently being hydrated. This is
+    /// always `false` outside the browser.
+    pub fn is_hydrating() -> bool {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        return IS_HYDRATING.with(|is_hydrating| **is_hydrating.borrow())
+            && !CLIENT_RENDERING.with(std::cell::Cell::get);
+
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        false
     }
 
     pub(crate) fn to_string(id: &HydrationKey, closing: bool) -> String {
@@ -10,20 +10,25 @@
 pub extern crate tracing;
 
 mod components;
+mod custom_element;
+pub mod directives;
 mod events;
 pub mod helpers;
 pub mod html;
 mod hydration;
+mod lifecycle;
 mod logging;
 mod macro_helpers;
 pub mod math;
 mod node_ref;
+pub mod ssg;
 pub mod ssr;
 pub mod ssr_in_order;
 pub mod svg;
 mod transparent;
 use cfg_if::cfg_if;
 pub use components::*;
+pub use custom_element::*;
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 pub use events::add_event_helper;
 pub use events::typed as ev;
@@ -33,6 +38,7 @@ pub use html::HtmlElement;
 use html::{AnyElement, ElementDescriptor};
 pub use hydration::{HydrationCtx, HydrationKey};
 use leptos_reactive::Scope;
+pub use lifecycle::*;
 pub use logging::*;
 pub use macro_helpers::*;
 pub use node_ref::*;
@@ -331,6 +337,7 @@ impl Comment {
                     marker.before_with_node_1(&node).unwrap();
 
                     marker.remove();
+                    hydration::claim_marker(id, &node);
                 } else {
                     crate::warn!(
                         "component with id {id} not found, ignoring it for \
@@ -605,11 +612,13 @@ impl View {
             match &self {
               Self::Element(el) => {
                 if event.bubbles() {
-                  add_event_listener(&el.element, event.event_delegation_key(), event.name(), event_handler);
+                  add_event_listener(&el.element, event.event_delegation_key(), event.name(), event.modifiers(), event_handler);
                 } else {
                   add_event_listener_undelegated(
                     &el.element,
                     &event.name(),
+                    event.options(),
+                    event.modifiers(),
                     event_handler,
                   );
                 }
@@ -727,7 +736,7 @@ where
 {
     cfg_if! {
       if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
-        mount_to(crate::document().body().expect("body element to exist"), f)
+        _ = mount_to(crate::document().body().expect("body element to exist").into(), f);
       } else {
         _ = f;
         crate::warn!("`mount_to_body` should not be called outside the browser.");
@@ -735,36 +744,226 @@ where
     }
 }
 
-/// Runs the provided closure and mounts the result to the provided element.
-pub fn mount_to<F, N>(parent: web_sys::HtmlElement, f: F)
+/// Runs the provided closure and mounts the result to the provided element,
+/// after anything already in it. Call [unmount](MountHandle::unmount) on the
+/// result to remove it again, e.g., when embedding it in a page that isn't
+/// rendered with Leptos. Mounting to an element that something is still
+/// mounted to unmounts that first.
+pub fn mount_to<F, N>(parent: web_sys::Element, f: F) -> MountHandle
 where
     F: FnOnce(Scope) -> N + 'static,
     N: IntoView,
 {
     cfg_if! {
       if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
-        let disposer = leptos_reactive::create_scope(
-          leptos_reactive::create_runtime(),
-          move |cx| {
-            let node = f(cx).into_view(cx);
-
-            HydrationCtx::stop_hydrating();
-
-            parent.append_child(&node.get_mountable_node()).unwrap();
-
-            std::mem::forget(node);
-          },
-        );
-
-        std::mem::forget(disposer);
+        let render_mode = if HydrationCtx::is_hydrating() {
+          leptos_reactive::RenderMode::Hydrate
+        } else {
+          leptos_reactive::RenderMode::Csr
+        };
+        MountHandle::mount(parent.into(), render_mode, f)
       } else {
         _ = parent;
         _ = f;
         crate::warn!("`mount_to` should not be called outside the browser.");
+        MountHandle {}
       }
     }
 }
 
+/// Hydrates the server-rendered HTML inside `parent`, which should be what the
+/// server rendered for the same view, e.g., with
+/// [render_to_string](crate::ssr::render_to_string). Unlike
+/// [mount_to_body], which hydrates the whole `<body>` once, this can hydrate
+/// part of a page, and be [unmounted](MountHandle::unmount), which removes
+/// everything inside `parent`. See [hydrate_to] for a page with several
+/// roots.
+pub fn hydrate_at<F, N>(parent: web_sys::Element, f: F) -> MountHandle
+where
+    F: FnOnce(Scope) -> N + 'static,
+    N: IntoView,
+{
+    cfg_if! {
+      if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
+        HydrationCtx::start_hydrating_from(&parent, HydrationKey::default());
+        MountHandle::mount(parent.into(), leptos_reactive::RenderMode::Hydrate, f)
+      } else {
+        _ = parent;
+        _ = f;
+        crate::warn!("`hydrate_at` should not be called outside the browser.");
+        MountHandle {}
+      }
+    }
+}
+
+/// Hydrates the server-rendered HTML inside `root`, which can be an element,
+/// a shadow root, or a document fragment. Unlike [mount_to], which hydrates the
+/// whole `<body>`, this lets a page have several independent roots, each
+/// rendered on the server from [HydrationKey::root] with the same `name`, or
+/// streamed with the same [app ID](Scope::set_app_id). As with [hydrate_at],
+/// unmounting it removes everything inside `root`.
+pub fn hydrate_to<F, N>(root: &web_sys::Node, name: &str, f: F) -> MountHandle
+where
+    F: FnOnce(Scope) -> N + 'static,
+    N: IntoView,
+{
+    cfg_if! {
+      if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
+        HydrationCtx::start_hydrating(root, name);
+        MountHandle::mount(root.clone(), leptos_reactive::RenderMode::Hydrate, f)
+      } else {
+        _ = root;
+        _ = name;
+        _ = f;
+        crate::warn!("`hydrate_to` should not be called outside the browser.");
+        MountHandle {}
+      }
+    }
+}
+
+/// A view mounted with [mount_to], [hydrate_at] or [hydrate_to]. Dropping this
+/// leaves it mounted.
+#[derive(Clone)]
+pub struct MountHandle {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    mounted: Rc<RefCell<Option<Mounted>>>,
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+struct Mounted {
+    parent: web_sys::Node,
+    // what was in `parent` before it was mounted; everything after it is the
+    // view's, including what the view adds once it's mounted, and is removed
+    // when it's unmounted
+    before: Option<web_sys::Node>,
+    disposer: leptos_reactive::ScopeDisposer,
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+thread_local! {
+    static MOUNTED: RefCell<Vec<MountHandle>> = Default::default();
+}
+
+impl MountHandle {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    fn mount<F, N>(
+        parent: web_sys::Node,
+        render_mode: leptos_reactive::RenderMode,
+        f: F,
+    ) -> Self
+    where
+        F: FnOnce(Scope) -> N + 'static,
+        N: IntoView,
+    {
+        let previous = MOUNTED.with(|mounted| {
+            mounted
+                .borrow()
+                .iter()
+                .filter(|handle| {
+                    handle.mounted.borrow().as_ref().map(|m| &m.parent)
+                        == Some(&parent)
+                })
+                .cloned()
+                .collect::<Vec<_>>()
+        });
+        for handle in previous {
+            handle.unmount();
+        }
+
+        let hydrating = render_mode == leptos_reactive::RenderMode::Hydrate;
+        let last_child = parent.last_child();
+        HydrationCtx::tag_resources();
+        let disposer =
+            leptos_reactive::create_scope(leptos_reactive::create_runtime(), {
+                let parent = parent.clone();
+                move |cx| {
+                    cx.set_render_mode(render_mode);
+
+                    let node = f(cx).into_view(cx);
+
+                    HydrationCtx::stop_hydrating();
+                    // anything rendered from now on is created in the browser
+                    cx.end_hydration();
+
+                    parent.append_child(&node.get_mountable_node()).unwrap();
+
+                    std::mem::forget(node);
+                }
+            });
+
+        // a hydrated view is everything in `parent`, but a mounted one is
+        // only what was added after what was already there
+        let before = if hydrating { None } else { last_child };
+
+        let handle = MountHandle {
+            mounted: Rc::new(RefCell::new(Some(Mounted {
+                parent,
+                before,
+                disposer,
+            }))),
+        };
+        MOUNTED.with(|mounted| mounted.borrow_mut().push(handle.clone()));
+        handle
+    }
+
+    /// Removes the view: its scope is disposed, which runs its cleanups and
+    /// removes its effects, timers and event listeners, and then the nodes it
+    /// rendered are removed. The element can then be mounted to again. Does
+    /// nothing if it has already been unmounted.
+    pub fn unmount(&self) {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            let Some(mounted) = self.mounted.borrow_mut().take() else {
+                return;
+            };
+            MOUNTED.with(|handles| {
+                handles.borrow_mut().retain(|handle| {
+                    !Rc::ptr_eq(&handle.mounted, &self.mounted)
+                })
+            });
+
+            mounted.disposer.dispose();
+            let Mounted { parent, before, .. } = mounted;
+            let first = match before {
+                Some(before)
+                    if before.parent_node().as_ref() == Some(&parent) =>
+                {
+                    before.next_sibling()
+                }
+                // what was there has been removed since, so there's nothing
+                // left to tell the view apart from
+                Some(_) => None,
+                None => parent.first_child(),
+            };
+            let nodes =
+                std::iter::successors(first, |node| node.next_sibling())
+                    .collect::<Vec<_>>();
+            for node in nodes {
+                _ = parent.remove_child(&node);
+            }
+        }
+    }
+
+    /// Whether the view is still mounted.
+    pub fn is_mounted(&self) -> bool {
+        cfg_if! {
+          if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
+            self.mounted.borrow().is_some()
+          } else {
+            false
+          }
+        }
+    }
+}
+
+impl fmt::Debug for MountHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MountHandle")
+            .field("is_mounted", &self.is_mounted())
+            .finish()
+    }
+}
+
 thread_local! {
     pub(crate) static WINDOW: web_sys::Window = web_sys::window().unwrap_throw();
 
@@ -0,0 +1,121 @@
+//! Callbacks for when an element is added to or removed from the document,
+//! e.g., to set up a third-party widget on it.
+
+use crate::{html::ElementDescriptor, HtmlElement, NodeRef};
+use leptos_reactive::Scope;
+
+/// Refers to an element that [on_mount] and [on_unmount] can wait for:
+/// either an [HtmlElement], or a [NodeRef] that will be loaded with one.
+pub trait ElementRef<T: ElementDescriptor + Clone + 'static> {
+    /// Calls `f` with the element, once there is one.
+    fn with_element(self, cx: Scope, f: impl FnOnce(HtmlElement<T>) + 'static);
+}
+
+impl<T: ElementDescriptor + Clone + 'static> ElementRef<T> for HtmlElement<T> {
+    fn with_element(
+        self,
+        _cx: Scope,
+        f: impl FnOnce(HtmlElement<T>) + 'static,
+    ) {
+        f(self)
+    }
+}
+
+impl<T: ElementDescriptor + Clone + 'static> ElementRef<T> for NodeRef<T> {
+    fn with_element(self, cx: Scope, f: impl FnOnce(HtmlElement<T>) + 'static) {
+        self.on_load(cx, f)
+    }
+}
+
+/// Runs `f` once the element is connected to the document, i.e., after the
+/// view it's in has been inserted, or after it has been claimed while
+/// hydrating. This happens at most once, even if it is moved later, and not
+/// at all if `cx` is disposed first. Does nothing on the server.
+pub fn on_mount<T, E>(
+    cx: Scope,
+    el: E,
+    f: impl FnOnce(HtmlElement<T>) + 'static,
+) where
+    T: ElementDescriptor + Clone + 'static,
+    E: ElementRef<T>,
+{
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        use std::{cell::Cell, rc::Rc};
+
+        let disposed = Rc::new(Cell::new(false));
+        leptos_reactive::on_cleanup(cx, {
+            let disposed = Rc::clone(&disposed);
+            move || disposed.set(true)
+        });
+
+        el.with_element(cx, move |el| {
+            let node: web_sys::Node = el.element.as_ref().clone().into();
+            // wait until the view the element was created in has been
+            // inserted, which happens right after it's built
+            leptos_reactive::queue_microtask(move || {
+                when_connected(cx, node, disposed, Box::new(move || f(el)))
+            });
+        });
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = cx;
+        _ = el;
+        _ = f;
+    }
+}
+
+// checks again before each repaint, as the element may be inserted later,
+// e.g., by a `<Suspense/>`
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn when_connected(
+    cx: Scope,
+    node: web_sys::Node,
+    disposed: std::rc::Rc<std::cell::Cell<bool>>,
+    f: Box<dyn FnOnce()>,
+) {
+    if disposed.get() {
+        return;
+    }
+    if node.is_connected() {
+        f();
+    } else {
+        crate::helpers::request_animation_frame(cx, move || {
+            when_connected(cx, node, disposed, f)
+        });
+    }
+}
+
+/// Runs `f` when `cx` is disposed, while the element is still in the
+/// document, e.g., to tear down a widget that [on_mount] set up. As with
+/// [on_cleanup](leptos_reactive::on_cleanup), the callbacks registered in
+/// child scopes run first. `f` isn't called if the element was never loaded
+/// or connected. Does nothing on the server.
+pub fn on_unmount<T, E>(
+    cx: Scope,
+    el: E,
+    f: impl FnOnce(HtmlElement<T>) + 'static,
+) where
+    T: ElementDescriptor + Clone + 'static,
+    E: ElementRef<T>,
+{
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        el.with_element(cx, move |el| {
+            leptos_reactive::on_cleanup(cx, move || {
+                if el.element.as_ref().is_connected() {
+                    f(el);
+                }
+            });
+        });
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        _ = cx;
+        _ = el;
+        _ = f;
+    }
+}
@@ -0,0 +1,112 @@
+use leptos_reactive::{RwSignal, SignalSet, SignalUpdate, SignalWith};
+
+/// A value that an input can be bound to with
+/// [`bind_value`](crate::HtmlElement::bind_value), or `bind:value` in the
+/// `view` macro.
+///
+/// This is implemented for [String] and the number types. Numbers are parsed
+/// from what the user types, and the signal is left as it is while that
+/// isn't a valid number.
+pub trait BindValue: Clone + PartialEq + 'static {
+    /// Converts the value to the input's `value`.
+    fn to_value(&self) -> String;
+
+    /// Parses the input's `value`, or returns `None` if it isn't valid.
+    fn from_value(value: &str) -> Option<Self>;
+}
+
+impl BindValue for String {
+    fn to_value(&self) -> String {
+        self.clone()
+    }
+
+    fn from_value(value: &str) -> Option<Self> {
+        Some(value.to_string())
+    }
+}
+
+macro_rules! bind_number {
+    ($($ty:ty),*) => {
+        $(
+            impl BindValue for $ty {
+                fn to_value(&self) -> String {
+                    self.to_string()
+                }
+
+                fn from_value(value: &str) -> Option<Self> {
+                    value.trim().parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+bind_number!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+);
+
+/// A signal that a set of radio buttons or checkboxes can be bound to with
+/// [`bind_group`](crate::HtmlElement::bind_group), or `bind:group` in the
+/// `view` macro, where each input stands for one `V`.
+///
+/// A `RwSignal<V>` is a radio group, which holds the selected value. A
+/// `RwSignal<Vec<V>>` is a checkbox group, which holds every checked value,
+/// in the order they were checked.
+pub trait BindGroup<V>: Copy + 'static {
+    /// Whether the input for `value` is checked. This is tracked.
+    fn is_selected(&self, value: &V) -> bool;
+
+    /// Updates the group when the input for `value` is checked or
+    /// unchecked.
+    fn set_selected(&self, value: &V, selected: bool);
+}
+
+impl<V: Clone + PartialEq + 'static> BindGroup<V> for RwSignal<V> {
+    fn is_selected(&self, value: &V) -> bool {
+        self.with(|selected| selected == value)
+    }
+
+    fn set_selected(&self, value: &V, selected: bool) {
+        // a radio button is only ever unchecked by checking another one
+        if selected {
+            self.set(value.clone());
+        }
+    }
+}
+
+impl<V: Clone + PartialEq + 'static> BindGroup<V> for RwSignal<Vec<V>> {
+    fn is_selected(&self, value: &V) -> bool {
+        self.with(|selected| selected.contains(value))
+    }
+
+    fn set_selected(&self, value: &V, selected: bool) {
+        self.update(|values| {
+            let idx = values.iter().position(|v| v == value);
+            match (selected, idx) {
+                (true, None) => values.push(value.clone()),
+                (false, Some(idx)) => {
+                    values.remove(idx);
+                }
+                _ => {}
+            }
+        });
+    }
+}
+
+#[doc(hidden)]
+/// The `value` that `bind:value` renders on the server.
+pub fn ssr_bind_value<T: BindValue>(value: RwSignal<T>) -> String {
+    value.with(T::to_value)
+}
+
+#[doc(hidden)]
+/// Whether `bind:checked` renders `checked` on the server.
+pub fn ssr_bind_checked(checked: RwSignal<bool>) -> bool {
+    checked.with(|checked| *checked)
+}
+
+#[doc(hidden)]
+/// Whether `bind:group` renders `checked` on the server.
+pub fn ssr_bind_group<G: BindGroup<V>, V>(group: G, value: V) -> bool {
+    group.is_selected(&value)
+}
@@ -1,5 +1,9 @@
 use leptos_reactive::Scope;
-use std::rc::Rc;
+use std::{
+    borrow::Cow,
+    collections::{BTreeSet, HashSet},
+    rc::Rc,
+};
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 use wasm_bindgen::UnwrapThrowExt;
 
@@ -21,6 +25,24 @@ pub enum Attribute {
 }
 
 impl Attribute {
+    /// Treats the attribute as a boolean one, like `disabled`, which is
+    /// removed if its value is `false` or `"false"`, and otherwise kept,
+    /// because the browser only checks whether a boolean attribute is there.
+    pub fn into_boolean(self) -> Attribute {
+        match self {
+            Attribute::String(value) if value == "false" => {
+                Attribute::Bool(false)
+            }
+            Attribute::Option(_, Some(value)) if value == "false" => {
+                Attribute::Bool(false)
+            }
+            Attribute::Fn(cx, f) => {
+                Attribute::Fn(cx, Rc::new(move || f().into_boolean()))
+            }
+            value => value,
+        }
+    }
+
     /// Converts the attribute to its HTML value at that moment, including the attribute name,
     /// so it can be rendered on the server.
     pub fn as_value_string(&self, attr_name: &'static str) -> String {
@@ -167,6 +189,17 @@ impl IntoAttribute for Option<String> {
     impl_into_attr_boxed! {}
 }
 
+/// `Some(true)` and `Some(false)` set the attribute to `"true"` or `"false"`,
+/// as for `aria-pressed`, and `None` removes it. A boolean attribute like
+/// `disabled` is removed by `"false"` too.
+impl IntoAttribute for Option<bool> {
+    fn into_attribute(self, cx: Scope) -> Attribute {
+        Attribute::Option(cx, self.map(|value| value.to_string()))
+    }
+
+    impl_into_attr_boxed! {}
+}
+
 impl<T, U> IntoAttribute for T
 where
     T: Fn() -> U + 'static,
@@ -235,6 +268,8 @@ macro_rules! attr_type {
 
 attr_type!(&String);
 attr_type!(&str);
+attr_type!(Cow<'static, str>);
+attr_type!(Rc<str>);
 attr_type!(usize);
 attr_type!(u8);
 attr_type!(u16);
@@ -251,8 +286,53 @@ attr_type!(f32);
 attr_type!(f64);
 attr_type!(char);
 
-#[cfg(all(target_arch = "wasm32", feature = "web"))]
-use std::borrow::Cow;
+// joins the tokens of a space-separated list, like `class`, leaving out
+// duplicates and tokens that contain whitespace
+fn token_list<'a>(tokens: impl IntoIterator<Item = &'a str>) -> String {
+    let mut list = Vec::new();
+    for token in tokens {
+        if token.contains(char::is_whitespace) {
+            crate::debug_warn!(
+                "[IntoAttribute] {token:?} contains whitespace, so it can't \
+                 be one of the tokens in a list, and was left out."
+            );
+        } else if !token.is_empty() && !list.contains(&token) {
+            list.push(token);
+        }
+    }
+    list.join(" ")
+}
+
+// a list of tokens, like the classes in `class`, is joined with spaces
+macro_rules! attr_list_type {
+    ($attr_type:ty) => {
+        impl IntoAttribute for $attr_type {
+            fn into_attribute(self, _: Scope) -> Attribute {
+                Attribute::String(token_list(self.iter().map(|t| t.as_ref())))
+            }
+
+            impl_into_attr_boxed! {}
+        }
+    };
+}
+
+attr_list_type!(Vec<String>);
+attr_list_type!(Vec<&'static str>);
+attr_list_type!(Vec<Cow<'static, str>>);
+attr_list_type!(BTreeSet<String>);
+attr_list_type!(BTreeSet<&'static str>);
+
+// sorted, so that the server renders the same HTML every time
+impl IntoAttribute for HashSet<String> {
+    fn into_attribute(self, _: Scope) -> Attribute {
+        let mut tokens = self.iter().map(String::as_str).collect::<Vec<_>>();
+        tokens.sort_unstable();
+        Attribute::String(token_list(tokens))
+    }
+
+    impl_into_attr_boxed! {}
+}
+
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 #[doc(hidden)]
 pub fn attribute_helper(
@@ -260,22 +340,131 @@ pub fn attribute_helper(
     name: Cow<'static, str>,
     value: Attribute,
 ) {
+    use crate::HydrationCtx;
     use leptos_reactive::create_render_effect;
+
+    // `class` is updated through `classList`, so that the classes toggled
+    // with `class:`, or added by other scripts, are left alone, and `style`
+    // is updated one property at a time for the same reason
+    let is_class = name == "class";
+    let is_style = name == "style";
     match value {
         Attribute::Fn(cx, f) => {
             let el = el.clone();
+            let is_inner_html = name == "inner_html";
             create_render_effect(cx, move |old| {
                 let new = f();
+                // the server rendered the same HTML, so hydrating leaves
+                // it alone rather than replacing it
+                if old.is_none()
+                    && is_inner_html
+                    && HydrationCtx::is_hydrating()
+                {
+                    return new;
+                }
                 if old.as_ref() != Some(&new) {
-                    attribute_expression(&el, &name, new.clone(), true);
+                    if is_class {
+                        class_attribute_expression(&el, old.as_ref(), &new);
+                    } else if is_style {
+                        style_attribute_expression(&el, old.as_ref(), &new);
+                    } else {
+                        attribute_expression(&el, &name, new.clone(), true);
+                    }
                 }
                 new
             });
         }
+        _ if is_class || is_style => {
+            if !HydrationCtx::is_hydrating() {
+                if is_class {
+                    class_attribute_expression(el, None, &value);
+                } else {
+                    style_attribute_expression(el, None, &value);
+                }
+            }
+        }
         _ => attribute_expression(el, &name, value, false),
     };
 }
 
+// removes the classes that were in `old` but aren't in `new`, and adds the
+// ones in `new` that weren't in `old`
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn class_attribute_expression(
+    el: &web_sys::Element,
+    old: Option<&Attribute>,
+    new: &Attribute,
+) {
+    fn class_names(value: &Attribute) -> Vec<&str> {
+        match value {
+            Attribute::String(value) | Attribute::Option(_, Some(value)) => {
+                value.split_ascii_whitespace().collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    let class_list = el.class_list();
+    let (old, new) =
+        (old.map(class_names).unwrap_or_default(), class_names(new));
+    for class_name in old.iter().filter(|name| !new.contains(name)) {
+        _ = class_list.remove_1(wasm_bindgen::intern(class_name));
+    }
+    for class_name in new.iter().filter(|name| !old.contains(name)) {
+        if let Err(e) = class_list.add_1(wasm_bindgen::intern(class_name)) {
+            crate::error!("[HtmlElement::attr()] {e:?}");
+        }
+    }
+}
+
+// removes the properties that were declared in `old` but aren't in `new`,
+// and sets the ones in `new`
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn style_attribute_expression(
+    el: &web_sys::Element,
+    old: Option<&Attribute>,
+    new: &Attribute,
+) {
+    use wasm_bindgen::JsCast;
+
+    fn declarations(value: &Attribute) -> Vec<(&str, &str)> {
+        match value {
+            Attribute::String(value) | Attribute::Option(_, Some(value)) => {
+                value
+                    .split(';')
+                    .filter_map(|declaration| declaration.split_once(':'))
+                    .map(|(name, value)| (name.trim(), value.trim()))
+                    .filter(|(name, _)| !name.is_empty())
+                    .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    let style = el.unchecked_ref::<web_sys::HtmlElement>().style();
+    let (old, new) =
+        (old.map(declarations).unwrap_or_default(), declarations(new));
+    for (name, _) in old
+        .iter()
+        .filter(|(name, _)| !new.iter().any(|(new, _)| new == name))
+    {
+        _ = style.remove_property(wasm_bindgen::intern(name));
+    }
+    for (name, value) in &new {
+        let (value, priority) = match value.strip_suffix("!important") {
+            Some(value) => (value.trim_end(), "important"),
+            None => (*value, ""),
+        };
+        if let Err(e) = style.set_property_with_priority(
+            wasm_bindgen::intern(name),
+            value,
+            priority,
+        ) {
+            crate::error!("[HtmlElement::attr()] {e:?}");
+        }
+    }
+}
+
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 pub(crate) fn attribute_expression(
     el: &web_sys::Element,
@@ -293,7 +482,7 @@ pub(crate) fn attribute_expression(
                     el.set_inner_html(value);
                 } else {
                     let attr_name = wasm_bindgen::intern(attr_name);
-                    el.set_attribute(attr_name, value).unwrap_throw();
+                    set_attribute(el, attr_name, value);
                 }
             }
             Attribute::Option(_, value) => {
@@ -304,21 +493,94 @@ pub(crate) fn attribute_expression(
                     match value {
                         Some(value) => {
                             let value = wasm_bindgen::intern(&value);
-                            el.set_attribute(attr_name, value).unwrap_throw();
+                            set_attribute(el, attr_name, value);
                         }
-                        None => el.remove_attribute(attr_name).unwrap_throw(),
+                        None => remove_attribute(el, attr_name),
                     }
                 }
             }
             Attribute::Bool(value) => {
                 let attr_name = wasm_bindgen::intern(attr_name);
                 if value {
-                    el.set_attribute(attr_name, attr_name).unwrap_throw();
+                    set_attribute(el, attr_name, "");
                 } else {
-                    el.remove_attribute(attr_name).unwrap_throw();
+                    remove_attribute(el, attr_name);
                 }
             }
             _ => panic!("Remove nested Fn in Attribute"),
         }
     }
 }
+
+/// Whether the HTML spec defines this as a boolean attribute, which is on
+/// whenever it's present, even as `disabled="false"`.
+pub(crate) fn is_boolean_attribute(attr_name: &str) -> bool {
+    matches!(
+        attr_name,
+        "allowfullscreen"
+            | "async"
+            | "autofocus"
+            | "autoplay"
+            | "checked"
+            | "controls"
+            | "default"
+            | "defer"
+            | "disabled"
+            | "formnovalidate"
+            | "hidden"
+            | "inert"
+            | "ismap"
+            | "itemscope"
+            | "loop"
+            | "multiple"
+            | "muted"
+            | "nomodule"
+            | "novalidate"
+            | "open"
+            | "playsinline"
+            | "readonly"
+            | "required"
+            | "reversed"
+            | "selected"
+    )
+}
+
+// the few attributes that have to be set in a namespace, e.g., `xlink:href`
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn attribute_namespace(attr_name: &str) -> Option<&'static str> {
+    if attr_name == "xmlns" || attr_name.starts_with("xmlns:") {
+        Some("http://www.w3.org/2000/xmlns/")
+    } else if attr_name.starts_with("xlink:") {
+        Some("http://www.w3.org/1999/xlink")
+    } else if attr_name.starts_with("xml:") {
+        Some("http://www.w3.org/XML/1998/namespace")
+    } else {
+        None
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn set_attribute(el: &web_sys::Element, attr_name: &str, value: &str) {
+    match attribute_namespace(attr_name) {
+        Some(ns) => el
+            .set_attribute_ns(Some(wasm_bindgen::intern(ns)), attr_name, value)
+            .unwrap_throw(),
+        None => el.set_attribute(attr_name, value).unwrap_throw(),
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn remove_attribute(el: &web_sys::Element, attr_name: &str) {
+    match attribute_namespace(attr_name) {
+        Some(ns) => {
+            // namespaced attributes are removed by their local name
+            let local_name = attr_name
+                .split_once(':')
+                .map(|(_, local_name)| local_name)
+                .unwrap_or(attr_name);
+            el.remove_attribute_ns(Some(wasm_bindgen::intern(ns)), local_name)
+                .unwrap_throw()
+        }
+        None => el.remove_attribute(attr_name).unwrap_throw(),
+    }
+}
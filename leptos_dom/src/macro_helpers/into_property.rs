@@ -116,6 +116,11 @@ pub(crate) fn property_expression(
     prop_name: &str,
     value: JsValue,
 ) {
-    js_sys::Reflect::set(el, &JsValue::from_str(prop_name), &value)
-        .unwrap_throw();
+    let prop_name = JsValue::from_str(prop_name);
+    // writing the value an input already has would move the user's cursor
+    // to the end, e.g., when `prop:value` follows what they've typed
+    let current = js_sys::Reflect::get(el, &prop_name).unwrap_throw();
+    if !js_sys::Object::is(&current, &value) {
+        js_sys::Reflect::set(el, &prop_name, &value).unwrap_throw();
+    }
 }
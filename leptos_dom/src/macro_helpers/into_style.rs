@@ -0,0 +1,163 @@
+use leptos_reactive::Scope;
+use std::borrow::Cow;
+
+/// Represents the different possible values a single CSS property on an element
+/// could have, allowing you to do fine-grained updates to single properties
+/// in [`HTMLElement.style`](https://developer.mozilla.org/en-US/docs/Web/API/HTMLElement/style).
+///
+/// This mostly exists for the [`view`](https://docs.rs/leptos_macro/latest/leptos_macro/macro.view.html)
+/// macro’s use. You usually won't need to interact with it directly, but it can be useful for defining
+/// permissive APIs for certain components.
+pub enum Style {
+    /// The value of the property, or `None` to remove it.
+    Value(Option<Cow<'static, str>>),
+    /// A (presumably reactive) function, which will be run inside an effect to update the property.
+    Fn(Scope, Box<dyn Fn() -> Option<Cow<'static, str>>>),
+}
+
+/// Converts some type into a [Style].
+pub trait IntoStyle {
+    /// Converts the object into a [Style].
+    fn into_style(self, cx: Scope) -> Style;
+}
+
+impl IntoStyle for &'static str {
+    fn into_style(self, _cx: Scope) -> Style {
+        Style::Value(Some(self.into()))
+    }
+}
+
+impl IntoStyle for String {
+    fn into_style(self, _cx: Scope) -> Style {
+        Style::Value(Some(self.into()))
+    }
+}
+
+impl IntoStyle for Option<&'static str> {
+    fn into_style(self, _cx: Scope) -> Style {
+        Style::Value(self.map(Into::into))
+    }
+}
+
+impl IntoStyle for Option<String> {
+    fn into_style(self, _cx: Scope) -> Style {
+        Style::Value(self.map(Into::into))
+    }
+}
+
+impl<T, S> IntoStyle for T
+where
+    T: Fn() -> S + 'static,
+    S: IntoStyle,
+{
+    fn into_style(self, cx: Scope) -> Style {
+        let modified_fn = Box::new(move || match self().into_style(cx) {
+            Style::Value(value) => value,
+            Style::Fn(_, f) => f(),
+        });
+        Style::Fn(cx, modified_fn)
+    }
+}
+
+impl<T: IntoStyle> IntoStyle for (Scope, T) {
+    fn into_style(self, _: Scope) -> Style {
+        self.1.into_style(self.0)
+    }
+}
+
+impl Style {
+    /// Converts the property to its CSS declaration at that moment, e.g.,
+    /// `color: red;`, so it can be rendered on the server. Returns `None`
+    /// if the property isn't set.
+    pub fn as_value_string(&self, style_name: &str) -> Option<String> {
+        let value = match self {
+            Style::Value(value) => value.clone(),
+            Style::Fn(_, f) => f(),
+        };
+        value.map(|value| format!("{style_name}: {value};"))
+    }
+}
+
+/// Renders the `style` attribute of an element that has `style:` bindings, on
+/// the server: the value of its full `style` attribute, if any, followed by
+/// each of the properties that is set.
+#[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+#[doc(hidden)]
+pub fn ssr_style_attribute(
+    base: Option<String>,
+    properties: Vec<(&'static str, Style)>,
+) -> String {
+    let mut style = base.unwrap_or_default();
+    for (name, value) in properties {
+        if let Some(declaration) = value.as_value_string(name) {
+            if !style.is_empty() && !style.trim_end().ends_with(';') {
+                style.push(';');
+            }
+            if !style.is_empty() {
+                style.push(' ');
+            }
+            style.push_str(&declaration);
+        }
+    }
+    if style.is_empty() {
+        String::new()
+    } else {
+        format!(" style=\"{}\"", crate::ssr::escape_attr(&style))
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+#[doc(hidden)]
+pub fn style_helper(
+    el: &web_sys::Element,
+    name: Cow<'static, str>,
+    value: Style,
+) {
+    use leptos_reactive::create_render_effect;
+    use wasm_bindgen::JsCast;
+
+    let style_list = el.unchecked_ref::<web_sys::HtmlElement>().style();
+    match value {
+        Style::Fn(cx, f) => {
+            create_render_effect(cx, move |old| {
+                let new = f();
+                if old.as_ref() != Some(&new)
+                    && (old.is_some() || new.is_some())
+                {
+                    style_expression(&style_list, &name, new.as_deref(), true)
+                }
+                new
+            });
+        }
+        Style::Value(value) => {
+            style_expression(&style_list, &name, value.as_deref(), false)
+        }
+    };
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+pub(crate) fn style_expression(
+    style_list: &web_sys::CssStyleDeclaration,
+    style_name: &str,
+    value: Option<&str>,
+    force: bool,
+) {
+    use crate::HydrationCtx;
+
+    if force || !HydrationCtx::is_hydrating() {
+        let style_name = wasm_bindgen::intern(style_name);
+
+        match value {
+            Some(value) => {
+                if let Err(e) = style_list.set_property(style_name, value) {
+                    crate::error!("[HtmlElement::style()] {e:?}");
+                }
+            }
+            None => {
+                if let Err(e) = style_list.remove_property(style_name) {
+                    crate::error!("[HtmlElement::style()] {e:?}");
+                }
+            }
+        }
+    }
+}
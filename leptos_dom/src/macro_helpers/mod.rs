@@ -1,6 +1,10 @@
+mod bind;
 mod into_attribute;
 mod into_class;
 mod into_property;
+mod into_style;
+pub use bind::*;
 pub use into_attribute::*;
 pub use into_class::*;
 pub use into_property::*;
+pub use into_style::*;
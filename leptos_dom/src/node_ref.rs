@@ -118,6 +118,22 @@ impl<T: ElementDescriptor + 'static> NodeRef<T> {
         });
     }
 
+    /// Empties the reference if it still holds this element, once the scope
+    /// the element was created in has been disposed.
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    pub(crate) fn unload(&self, node: &web_sys::HtmlElement) {
+        // the reference may have been disposed along with that scope, and
+        // effects that use it only rerun if it's actually emptied
+        let holds_node = self.0.try_with_untracked(|current| {
+            current
+                .as_ref()
+                .is_some_and(|current| current.element.as_ref() == node)
+        });
+        if holds_node == Some(true) {
+            self.0.set(None);
+        }
+    }
+
     /// Runs the provided closure when the `NodeRef` has been connected
     /// with it's [`HtmlElement`].
     pub fn on_load<F>(self, cx: Scope, f: F)
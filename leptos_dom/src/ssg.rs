@@ -0,0 +1,194 @@
+#![cfg(not(all(target_arch = "wasm32", feature = "web")))]
+
+//! Static site generation: rendering a view once, at build time, to HTML that
+//! doesn't depend on anything still loading.
+
+use crate::{ssr_in_order::MAX_RESOLVE_PASSES, HydrationCtx, View};
+use futures::StreamExt;
+use leptos_reactive::{
+    create_runtime, raw_scope_and_disposer, RenderMode, ResourceId, RuntimeId,
+    Scope, ScopeDisposer,
+};
+use std::{
+    collections::HashMap,
+    fmt,
+    panic::{catch_unwind, AssertUnwindSafe},
+    time::Duration,
+};
+
+/// How long [render_to_static] waits for each [Resource](leptos_reactive::Resource).
+pub const STATIC_RESOURCE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A page rendered by [render_static_page].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaticPage {
+    /// The fully-resolved HTML of the view.
+    pub html: String,
+    /// The ID and serialized value of every [Resource](leptos_reactive::Resource)
+    /// the view read, in the format sent by
+    /// [serialization_resolvers](Scope::serialization_resolvers). To hydrate
+    /// the page, write these out with [json_resource_script](crate::ssr::json_resource_script)
+    /// or hand them to [Scope::inject_serialized_resources] on the client.
+    pub resources: Vec<(ResourceId, String)>,
+}
+
+/// Describes why a view couldn't be rendered to a static page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SsgError {
+    /// These resources hadn't resolved when the timeout ran out.
+    Timeout(Vec<ResourceId>),
+    /// Every render pass created resources the previous one had not loaded.
+    TooManyPasses(usize),
+    /// These local resources only load in the browser, so whatever they
+    /// render can't be included in the page.
+    LocalResources(Vec<ResourceId>),
+    /// Rendering panicked, e.g., because it called a browser API.
+    Panicked(String),
+    /// Rendering the same view with the same resources gave different HTML,
+    /// e.g., because it depends on the time or a random number.
+    Nondeterministic,
+}
+
+impl fmt::Display for SsgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SsgError::Timeout(ids) => {
+                write!(f, "{} resource(s) did not resolve in time", ids.len())
+            }
+            SsgError::TooManyPasses(passes) => write!(
+                f,
+                "resources were still loading after {passes} render passes"
+            ),
+            SsgError::LocalResources(ids) => write!(
+                f,
+                "{} local resource(s) can only load in the browser",
+                ids.len()
+            ),
+            SsgError::Panicked(message) => {
+                write!(f, "rendering panicked: {message}")
+            }
+            SsgError::Nondeterministic => write!(
+                f,
+                "rendering the view twice with the same data gave different \
+                 HTML"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SsgError {}
+
+/// Renders a view to static HTML, waiting up to [STATIC_RESOURCE_TIMEOUT] for each
+/// of its [Resource](leptos_reactive::Resource)s. Unlike
+/// [render_to_string_resolved](crate::ssr_in_order::render_to_string_resolved),
+/// this returns an error rather than falling back to partial output.
+/// See [render_static_page].
+pub async fn render_to_static(
+    view: impl Fn(Scope) -> View + 'static,
+) -> Result<String, SsgError> {
+    render_static_page(view, STATIC_RESOURCE_TIMEOUT)
+        .await
+        .map(|page| page.html)
+}
+
+/// Renders a view to a [StaticPage], for generating a site ahead of time.
+///
+/// Like [render_to_string_resolved](crate::ssr_in_order::render_to_string_resolved),
+/// the view is rendered until every [Resource](leptos_reactive::Resource) starts out
+/// resolved. Each pass uses a new runtime, so the resource IDs match the ones a
+/// client creates when it hydrates. The last pass is then repeated to check
+/// that the output is the same. This fails with an [SsgError] if
+/// - a resource takes longer than `timeout` to resolve
+/// - resources are still loading after a few passes
+/// - the view creates local resources, which never load on the server
+/// - rendering panics, e.g., because it called a browser API
+/// - the check pass renders different HTML
+pub async fn render_static_page(
+    view: impl Fn(Scope) -> View + 'static,
+    timeout: Duration,
+) -> Result<StaticPage, SsgError> {
+    let mut resolved = HashMap::new();
+    for _ in 1..=MAX_RESOLVE_PASSES {
+        let pass = StaticPass::render(&view, timeout, resolved)?;
+        if pass.cx.all_resources_resolved() {
+            let mut resources =
+                pass.cx.serialization_resolvers().collect::<Vec<_>>().await;
+            resources.sort_by_key(|(id, _)| *id);
+            let html = pass.html.clone();
+
+            let check = StaticPass::render(
+                &view,
+                timeout,
+                pass.cx.resolved_resources(),
+            )?;
+            if check.html != html {
+                return Err(SsgError::Nondeterministic);
+            }
+            return Ok(StaticPage { html, resources });
+        }
+
+        let timed_out = pass.cx.resolve_all_resources().await;
+        if !timed_out.is_empty() {
+            return Err(SsgError::Timeout(timed_out));
+        }
+        resolved = pass.cx.resolved_resources();
+    }
+    Err(SsgError::TooManyPasses(MAX_RESOLVE_PASSES))
+}
+
+/// A view rendered in its own runtime, which is disposed when this is dropped.
+struct StaticPass {
+    cx: Scope,
+    runtime: RuntimeId,
+    disposer: Option<ScopeDisposer>,
+    html: String,
+}
+
+impl StaticPass {
+    fn render(
+        view: &impl Fn(Scope) -> View,
+        timeout: Duration,
+        resolved: HashMap<String, String>,
+    ) -> Result<Self, SsgError> {
+        HydrationCtx::tag_resources();
+        let runtime = create_runtime();
+        let (cx, disposer) = raw_scope_and_disposer(runtime);
+        let mut pass = StaticPass {
+            cx,
+            runtime,
+            disposer: Some(disposer),
+            html: String::new(),
+        };
+        cx.set_render_mode(RenderMode::Ssr);
+        cx.set_suspense_timeout(timeout);
+        cx.set_resolved_resources(resolved);
+
+        HydrationCtx::reset_id();
+        pass.html = catch_unwind(AssertUnwindSafe(|| {
+            view(cx).render_to_string(cx).into_owned()
+        }))
+        .map_err(|payload| {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            SsgError::Panicked(message)
+        })?;
+
+        let local = cx.local_resources();
+        if !local.is_empty() {
+            return Err(SsgError::LocalResources(local));
+        }
+        Ok(pass)
+    }
+}
+
+impl Drop for StaticPass {
+    fn drop(&mut self) {
+        if let Some(disposer) = self.disposer.take() {
+            disposer.dispose();
+        }
+        self.runtime.dispose();
+    }
+}
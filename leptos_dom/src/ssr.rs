@@ -4,12 +4,10 @@
 
 use crate::{CoreComponent, HydrationCtx, IntoView, View};
 use cfg_if::cfg_if;
-use futures::{stream::FuturesUnordered, Future, Stream, StreamExt};
+use futures::{Stream, StreamExt};
 use itertools::Itertools;
 use leptos_reactive::*;
-use std::{borrow::Cow, pin::Pin};
-
-type PinnedFuture<T> = Pin<Box<dyn Future<Output = T>>>;
+use std::borrow::Cow;
 
 /// Renders the given function to a static HTML string.
 ///
@@ -30,8 +28,10 @@ where
 {
     let runtime = leptos_reactive::create_runtime();
     HydrationCtx::reset_id();
+    HydrationCtx::tag_resources();
 
     let html = leptos_reactive::run_scope(runtime, |cx| {
+        cx.set_render_mode(RenderMode::Ssr);
         f(cx).into_view(cx).render_to_string(cx)
     });
 
@@ -125,49 +125,60 @@ pub fn render_to_stream_with_prefix_undisposed_with_context(
     additional_context: impl FnOnce(Scope) + 'static,
 ) -> (impl Stream<Item = String>, RuntimeId, ScopeId) {
     HydrationCtx::reset_id();
+    HydrationCtx::tag_resources();
 
     // create the runtime
     let runtime = create_runtime();
 
     let (
-        (shell, prefix, pending_resources, pending_fragments, serializers),
+        (shell, prefix, pending_resources, fragments, serializers),
         scope,
         disposer,
     ) = run_scope_undisposed(runtime, {
         move |cx| {
+            cx.set_render_mode(RenderMode::Ssr);
             // Add additional context items
             additional_context(cx);
+            start_app_keys(cx);
             // the actual app body/template code
             // this does NOT contain any of the data being loaded asynchronously in resources
             let shell = view(cx).render_to_string(cx);
 
             let resources = cx.pending_resources();
             let pending_resources = serde_json::to_string(&resources).unwrap();
-            let prefix = prefix(cx);
+            let prefix = format!(
+                "{}{}",
+                prefix(cx),
+                event_replay_script(
+                    &cx.replayed_events(),
+                    cx.script_nonce().as_deref()
+                )
+            );
 
             (
                 shell,
                 prefix,
                 pending_resources,
-                cx.pending_fragments(),
-                cx.serialization_resolvers(),
+                cx.fragments_stream(),
+                (
+                    cx.serialization_resolvers(),
+                    cx.script_nonce(),
+                    cx.json_resources(),
+                ),
             )
         }
     });
 
-    let fragments = FuturesUnordered::new();
-    for (fragment_id, (fut, _)) in pending_fragments {
-        fragments.push(async move { (fragment_id, fut.await) })
-    }
-
     // resources and fragments
     // stream HTML for each <Suspense/> as it resolves
     // TODO can remove id_before_suspense entirely now
-    let fragments = fragments.map(|(fragment_id, html)| {
+    let (serializers, nonce, json) = serializers;
+    let script = open_script_tag(nonce.as_deref());
+    let fragments = fragments.map(move |(fragment_id, html)| {
       format!(
         r#"
                 <template id="{fragment_id}f">{html}</template>
-                <script>
+                {script}
                     var id = "{fragment_id}";
                     var open;
                     var close;
@@ -185,20 +196,26 @@ pub fn render_to_stream_with_prefix_undisposed_with_context(
                     range.deleteContents();
                     var tpl = document.getElementById("{fragment_id}f");
                     close.parentNode.insertBefore(tpl.content.cloneNode(true), close);
+                    document.dispatchEvent(new CustomEvent("leptos:streamed-chunk", {{ detail: id }}));
                 </script>
                 "#
       )
     });
     // stream data for each Resource as it resolves
-    let resources = render_serializers(serializers);
+    let resources = render_serializers(serializers, nonce.clone(), json);
 
     // HTML for the view function and script to store resources
     let stream = futures::stream::once(async move {
+        // JSON resources are read when hydration starts, and don't need these
+        if json {
+            return format!("{prefix}{shell}");
+        }
+        let script = open_script_tag(nonce.as_deref());
         format!(
             r#"
                 {prefix}
                 {shell}
-                <script>
+                {script}
                     __LEPTOS_PENDING_RESOURCES = {pending_resources};
                     __LEPTOS_RESOLVED_RESOURCES = new Map();
                     __LEPTOS_RESOURCE_RESOLVERS = new Map();
@@ -392,11 +409,11 @@ impl View {
                         .into_iter()
                         .filter_map(
                             |(name, value)| -> Option<Cow<'static, str>> {
-                                if value.is_empty() {
-                                    Some(format!(" {name}").into())
-                                } else if name == "inner_html" {
+                                if name == "inner_html" {
                                     inner_html = Some(value);
                                     None
+                                } else if value.is_empty() {
+                                    Some(format!(" {name}").into())
                                 } else {
                                     Some(
                                         format!(
@@ -480,22 +497,87 @@ pub(crate) fn to_kebab_case(name: &str) -> String {
 }
 
 pub(crate) fn render_serializers(
-    serializers: FuturesUnordered<PinnedFuture<(ResourceId, String)>>,
+    serializers: impl Stream<Item = (ResourceId, String)>,
+    nonce: Option<String>,
+    json: bool,
 ) -> impl Stream<Item = String> {
-    serializers.map(|(id, json)| {
+    serializers.map(move |(id, data)| {
+        if json {
+            return json_resource_script(&id, &data);
+        }
         let id = serde_json::to_string(&id).unwrap();
+        let data = serde_json::to_string(&data).unwrap();
+        let data = escape_script_payload(&data);
+        let script = open_script_tag(nonce.as_deref());
         format!(
-            r#"<script>
+            r#"{script}
                   if(__LEPTOS_RESOURCE_RESOLVERS.get({id})) {{
-                      __LEPTOS_RESOURCE_RESOLVERS.get({id})({json:?})
+                      __LEPTOS_RESOURCE_RESOLVERS.get({id})({data})
                   }} else {{
-                      __LEPTOS_RESOLVED_RESOURCES.set({id}, {json:?});
+                      __LEPTOS_RESOLVED_RESOURCES.set({id}, {data});
                   }}
               </script>"#,
         )
     })
 }
 
+/// Events a user is likely to have meant when they happen before the page has
+/// hydrated, unlike, e.g., `scroll` or `mousemove`. See
+/// [Scope::set_replayed_events].
+pub const DEFAULT_REPLAYED_EVENTS: [&str; 3] = ["click", "input", "submit"];
+
+/// Renders an inline script that records the given events, in the order they
+/// happen, until hydration dispatches them again. Forms aren't submitted in
+/// the meantime: if nothing prevents the replayed `submit`, the form is
+/// submitted then. Returns an empty string if there are no events. See
+/// [Scope::set_replayed_events].
+pub fn event_replay_script(events: &[String], nonce: Option<&str>) -> String {
+    if events.is_empty() {
+        return String::new();
+    }
+    let events = serde_json::to_string(events).unwrap();
+    let events = escape_script_payload(&events);
+    let script = open_script_tag(nonce);
+    format!(
+        "{script}(function(){{var q=[],t={events};function \
+         r(e){{q.push({{target:e.target,type:e.type}});if(e.type==\"submit\"\
+         )e.preventDefault()}}t.forEach(function(n){{document.\
+         addEventListener(n,r,true)}});__LEPTOS_CAPTURED_EVENTS=function(){{t.\
+         forEach(function(n){{document.removeEventListener(n,r,true)}});\
+         return q}}}})()</script>"
+    )
+}
+
+/// Continues from the root of the [app ID](Scope::set_app_id), if one was
+/// set, so that all of the app's hydration keys and fragment IDs start with it.
+pub(crate) fn start_app_keys(cx: Scope) {
+    if let Some(app_id) = cx.app_id() {
+        HydrationCtx::continue_from(crate::HydrationKey::root(&app_id));
+    }
+}
+
+/// Opens an inline `<script>` tag, adding the given Content-Security-Policy
+/// nonce, if any. See [Scope::set_script_nonce].
+pub fn open_script_tag(nonce: Option<&str>) -> String {
+    match nonce {
+        Some(nonce) => format!(r#"<script nonce="{}">"#, escape_attr(&nonce)),
+        None => "<script>".to_string(),
+    }
+}
+
+/// Renders a serialized resource into a `<script type="application/json">`
+/// block, which the client reads with [resource_from_json_script] when it
+/// starts hydrating. See [Scope::set_json_resources].
+pub fn json_resource_script(id: &ResourceId, data: &str) -> String {
+    let id = serde_json::to_string(id).unwrap();
+    let data = serde_json::to_string(data).unwrap();
+    format!(
+        r#"<script type="application/json" data-leptos-resource="{}">{}</script>"#,
+        escape_attr(&id),
+        escape_script_payload(&data)
+    )
+}
+
 #[doc(hidden)]
 pub fn escape_attr<T>(value: &T) -> Cow<'_, str>
 where
@@ -2,14 +2,20 @@
 
 //! Server-side HTML rendering utilities for in-order streaming and async rendering.
 
-use crate::{ssr::render_serializers, CoreComponent, HydrationCtx, View};
+use crate::{
+    ssr::{
+        event_replay_script, open_script_tag, render_serializers,
+        start_app_keys,
+    },
+    CoreComponent, HydrationCtx, View,
+};
 use async_recursion::async_recursion;
 use cfg_if::cfg_if;
-use futures::{channel::mpsc::Sender, Stream, StreamExt};
+use futures::{channel::mpsc::Sender, SinkExt, Stream, StreamExt};
 use itertools::Itertools;
 use leptos_reactive::{
-    create_runtime, run_scope_undisposed, suspense::StreamChunk, RuntimeId,
-    Scope, ScopeId,
+    create_runtime, raw_scope_and_disposer, run_scope_undisposed,
+    suspense::StreamChunk, RenderMode, RuntimeId, Scope, ScopeId,
 };
 use std::borrow::Cow;
 
@@ -26,6 +32,55 @@ pub async fn render_to_string_async(
     buf
 }
 
+/// The most times [render_to_string_resolved] will render a view.
+pub(crate) const MAX_RESOLVE_PASSES: usize = 4;
+
+/// Renders a view to a single complete HTML document, with no `<Suspense/>`
+/// fallbacks and no scripts to swap in resolved fragments or resources. This
+/// is useful for crawlers and HTTP caches.
+///
+/// The view is rendered, the [Resource](leptos_reactive::Resource)s it created
+/// are waited on with [Scope::resolve_all_resources], and then it's rendered
+/// again with all of them resolved. If a pass creates resources the previous
+/// one did not, this repeats, up to a few times.
+pub async fn render_to_string_resolved(
+    view: impl Fn(Scope) -> View + 'static,
+) -> String {
+    HydrationCtx::tag_resources();
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    cx.set_render_mode(RenderMode::Ssr);
+
+    let mut html = String::new();
+    for pass in 1..=MAX_RESOLVE_PASSES {
+        HydrationCtx::reset_id();
+        let (rendered, pass_disposer) =
+            cx.run_child_scope(|cx| view(cx).render_to_string(cx).into_owned());
+        html = rendered;
+
+        if cx.all_resources_resolved() {
+            pass_disposer.dispose();
+            break;
+        } else if pass == MAX_RESOLVE_PASSES {
+            crate::debug_warn!(
+                "[render_to_string_resolved] Resources were still loading \
+                 after {MAX_RESOLVE_PASSES} render passes, so the HTML may \
+                 include <Suspense/> fallbacks."
+            );
+            pass_disposer.dispose();
+            break;
+        }
+
+        // keep this pass's resources alive until their values are saved
+        cx.resolve_all_resources().await;
+        pass_disposer.dispose();
+    }
+
+    disposer.dispose();
+    runtime.dispose();
+    html
+}
+
 /// Renders an in-order HTML stream, pausing at `<Suspense/>` components. The stream contains,
 /// in order:
 /// 1. HTML from the `view` in order, pausing to wait for each `<Suspense/>`
@@ -54,8 +109,11 @@ pub fn render_to_stream_in_order_with_prefix(
             prefix,
             |_| {},
         );
-    runtime.dispose();
-    stream
+    // the runtime has to outlive the stream, which waits on its resources
+    stream.chain(futures::stream::once(async move {
+        runtime.dispose();
+        Default::default()
+    }))
 }
 
 /// Renders an in-order HTML stream, pausing at `<Suspense/>` components. The stream contains,
@@ -72,37 +130,61 @@ pub fn render_to_stream_in_order_with_prefix_undisposed_with_context(
     additional_context: impl FnOnce(Scope) + 'static,
 ) -> (impl Stream<Item = String>, RuntimeId, ScopeId) {
     HydrationCtx::reset_id();
+    HydrationCtx::tag_resources();
 
     // create the runtime
     let runtime = create_runtime();
 
-    let ((chunks, prefix, pending_resources, serializers), scope_id, disposer) =
-        run_scope_undisposed(runtime, |cx| {
-            // add additional context
-            additional_context(cx);
+    let (
+        (chunks, prefix, pending_resources, serializers, nonce, json),
+        scope_id,
+        disposer,
+    ) = run_scope_undisposed(runtime, |cx| {
+        cx.set_render_mode(RenderMode::Ssr);
+        // <Suspense/> waits for its fragment instead of showing a fallback
+        cx.set_in_order_streaming(true);
+
+        // add additional context
+        additional_context(cx);
+        start_app_keys(cx);
 
-            // render view and return chunks
-            let view = view(cx);
+        // render view and return chunks
+        let view = view(cx);
 
-            let prefix = prefix(cx);
-            (
-                view.into_stream_chunks(cx),
-                prefix,
-                serde_json::to_string(&cx.pending_resources()).unwrap(),
-                cx.serialization_resolvers(),
+        let prefix: Cow<'static, str> = format!(
+            "{}{}",
+            prefix(cx),
+            event_replay_script(
+                &cx.replayed_events(),
+                cx.script_nonce().as_deref()
             )
-        });
+        )
+        .into();
+        (
+            view.into_stream_chunks(cx),
+            prefix,
+            serde_json::to_string(&cx.pending_resources()).unwrap(),
+            cx.serialization_resolvers(),
+            cx.script_nonce(),
+            cx.json_resources(),
+        )
+    });
 
     let (tx, rx) = futures::channel::mpsc::channel(1);
     leptos_reactive::spawn_local(async move {
         handle_chunks(tx, chunks).await;
     });
 
+    let script = open_script_tag(nonce.as_deref());
     let stream = futures::stream::once(async move {
+        // JSON resources are read when hydration starts, and don't need these
+        if json {
+            return prefix.into_owned();
+        }
         format!(
             r#"
         {prefix}
-        <script>
+        {script}
             __LEPTOS_PENDING_RESOURCES = {pending_resources};
             __LEPTOS_RESOLVED_RESOURCES = new Map();
             __LEPTOS_RESOURCE_RESOLVERS = new Map();
@@ -111,7 +193,7 @@ pub fn render_to_stream_in_order_with_prefix_undisposed_with_context(
         )
     })
     .chain(rx)
-    .chain(render_serializers(serializers))
+    .chain(render_serializers(serializers, nonce, json))
     // dispose of the scope
     .chain(futures::stream::once(async move {
         disposer.dispose();
@@ -129,7 +211,8 @@ async fn handle_chunks(mut tx: Sender<String>, chunks: Vec<StreamChunk>) {
             StreamChunk::Sync(sync) => buffer.push_str(&sync),
             StreamChunk::Async(suspended) => {
                 // add static HTML before the Suspense and stream it down
-                _ = tx.try_send(std::mem::take(&mut buffer));
+                // waiting for room in the channel, so no chunk is dropped
+                _ = tx.send(std::mem::take(&mut buffer)).await;
 
                 // send the inner stream
                 let suspended = suspended.await;
@@ -138,7 +221,7 @@ async fn handle_chunks(mut tx: Sender<String>, chunks: Vec<StreamChunk>) {
         }
     }
     // send final sync chunk
-    _ = tx.try_send(std::mem::take(&mut buffer));
+    _ = tx.send(std::mem::take(&mut buffer)).await;
 }
 
 impl View {
@@ -157,7 +240,7 @@ impl View {
         match self {
             View::Suspense(id, _) => {
                 let id = id.to_string();
-                if let Some((_, fragment)) = cx.take_pending_fragment(&id) {
+                if let Some(fragment) = cx.take_fragment(&id) {
                     chunks.push(StreamChunk::Async(fragment));
                 }
             }
@@ -198,11 +281,11 @@ impl View {
                         .into_iter()
                         .filter_map(
                             |(name, value)| -> Option<Cow<'static, str>> {
-                                if value.is_empty() {
-                                    Some(format!(" {name}").into())
-                                } else if name == "inner_html" {
+                                if name == "inner_html" {
                                     inner_html = Some(value);
                                     None
+                                } else if value.is_empty() {
+                                    Some(format!(" {name}").into())
                                 } else {
                                     Some(
                                         format!(
@@ -57,51 +57,17 @@ macro_rules! generate_svg_tags {
 
             #[cfg(all(target_arch = "wasm32", feature = "web"))]
             let element = if HydrationCtx::is_hydrating() {
-              if let Some(el) = crate::document().get_element_by_id(
-                &format!("_{id}")
+              match HydrationCtx::find_element(
+                &id,
+                stringify!([<$tag:upper $(_ $second:upper $(_ $third:upper)?)?>]),
               ) {
-                #[cfg(debug_assertions)]
-                assert_eq!(
-                  el.node_name().to_ascii_uppercase(),
-                  stringify!([<$tag:upper $(_ $second:upper $(_ $third:upper)?)?>]),
-                  "SSR and CSR elements have the same `TopoId` \
-                    but different node kinds. This is either a \
-                    discrepancy between SSR and CSR rendering
-                    logic, which is considered a bug, or it \
-                    can also be a leptos hydration issue."
-                );
-
-                el.remove_attribute("id").unwrap();
-
-                el.unchecked_into()
-              } else if let Ok(Some(el)) = crate::document().query_selector(
-                &format!("[leptos-hk=_{id}]")
-              ) {
-                #[cfg(debug_assertions)]
-                assert_eq!(
-                  el.node_name().to_ascii_uppercase(),
-                  stringify!([<$tag:upper $(_ $second:upper $(_ $third:upper)?)?>]),
-                  "SSR and CSR elements have the same `TopoId` \
-                    but different node kinds. This is either a \
-                    discrepancy between SSR and CSR rendering
-                    logic, which is considered a bug, or it \
-                    can also be a leptos hydration issue."
-                );
-
-                el.remove_attribute("leptos-hk").unwrap();
-
-                el.unchecked_into()
-              } else {
-                crate::warn!(
-                  "element with id {id} not found, ignoring it for hydration"
-                );
-
-                [<$tag:upper $(_ $second:upper $(_ $third:upper)?)?>]
+                Some(el) => el.unchecked_into(),
+                None => [<$tag:upper $(_ $second:upper $(_ $third:upper)?)?>]
                   .with(|el|
                     el.clone_node()
                       .unwrap()
                       .unchecked_into()
-                  )
+                  ),
               }
             } else {
               [<$tag:upper $(_ $second:upper $(_ $third:upper)?)?>]
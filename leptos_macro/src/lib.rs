@@ -91,7 +91,12 @@ mod template;
 ///    of text nodes, a `bool` for `class:` attributes, etc.)*
 ///
 ///    Attributes can take a wide variety of primitive types that can be converted to strings. They can also
-///    take an `Option`, in which case `Some` sets the attribute and `None` removes the attribute.
+///    take an `Option`, in which case `Some` sets the attribute and `None` removes the attribute, so
+///    `title=move || hint.get()` leaves out the `title` while there's no hint. An `Option<bool>` sets
+///    `"true"` or `"false"`, as for `aria-pressed`. A `bool` adds the attribute if `true` and removes it if `false`. Boolean attributes like
+///    `disabled`, `checked`, `hidden` or `open` are also removed when they're set to `"false"`, since
+///    the browser only checks whether they're there. Use `attr:bool:` to treat an attribute of a
+///    custom element the same way, e.g., `<my-dialog attr:bool:open=is_open/>`.
 ///
 /// ```rust
 /// # use leptos::*;
@@ -114,7 +119,13 @@ mod template;
 /// ```
 ///
 /// 5. Event handlers can be added with `on:` attributes. In most cases, the events are given the correct type
-///    based on the event name.
+///    based on the event name, e.g., `on:click` takes a `MouseEvent` and `on:keydown` a `KeyboardEvent`, as listed
+///    in [`leptos::ev`](https://docs.rs/leptos/latest/leptos/ev/index.html). `on:input`, which isn't always an
+///    `InputEvent`, and custom events take a plain `Event`. Events that bubble are delegated to a single listener on the `window`. The
+///    `:capture`, `:passive` and `:once` suffixes, e.g., `on:wheel:passive`, set those listener options, and
+///    bypass delegation, since they only apply to a listener on the element itself. The `:prevent` and `:stop`
+///    suffixes call `prevent_default` and `stop_propagation` before the handler runs, and can be combined, e.g.,
+///    `on:submit:prevent` or `on:click:prevent:stop`. They work with delegated listeners too.
 /// ```rust
 /// # use leptos::*;
 /// # run_scope(create_runtime(), |cx| {
@@ -128,13 +139,24 @@ mod template;
 ///   </button>
 /// }
 /// # ;
+/// view! {
+///   cx,
+///   // the page isn't reloaded, even if the handler panics
+///   <form on:submit:prevent=|_| log::debug!("submitted")>
+///     <button type="submit">"Send"</button>
+///   </form>
+/// }
+/// # ;
 /// # }
 /// # });
 /// ```
 ///
 /// 6. DOM properties can be set with `prop:` attributes, which take any primitive type or `JsValue` (or a signal
 ///    that returns a primitive or JsValue). They can also take an `Option`, in which case `Some` sets the property
-///    and `None` deletes the property.
+///    and `None` deletes the property. A property is only written when it's different from the value the element
+///    already has, so a `prop:value` that follows what the user types doesn't move their cursor. On the server,
+///    where there is no live value, `prop:value`, `prop:checked` and `prop:selected` are rendered as the
+///    attribute that sets the initial value, unless the element has that attribute too.
 /// ```rust
 /// # use leptos::*;
 /// # run_scope(create_runtime(), |cx| {
@@ -156,6 +178,33 @@ mod template;
 /// # });
 /// ```
 ///
+///    `bind:value`, `bind:checked` and `bind:group` do both at once, for an `RwSignal`: the property follows the
+///    signal, and the signal follows the input. `bind:value` takes a `String` or a number, which is only updated
+///    while what's typed parses. `bind:group=(group, value)` binds a radio button to an `RwSignal<V>` of the selected
+///    value, or a checkbox to an `RwSignal<Vec<V>>` of the checked ones. On the server, they render the attribute
+///    for the initial value.
+/// ```rust
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// let name = create_rw_signal(cx, "Alice".to_string());
+/// let age = create_rw_signal(cx, 30u8);
+/// let subscribed = create_rw_signal(cx, true);
+/// let plan = create_rw_signal(cx, "free");
+///
+/// view! {
+///   cx,
+///   <input type="text" bind:value=name/>
+///   <input type="number" bind:value=age/>
+///   <input type="checkbox" bind:checked=subscribed/>
+///   <input type="radio" name="plan" bind:group=(plan, "free")/>
+///   <input type="radio" name="plan" bind:group=(plan, "pro")/>
+/// }
+/// # ;
+/// # }
+/// # });
+/// ```
+///
 /// 7. Classes can be toggled with `class:` attributes, which take a `bool` (or a signal that returns a `bool`).
 /// ```rust
 /// # use leptos::*;
@@ -200,6 +249,20 @@ mod template;
 /// # });
 /// ```
 ///
+/// When the set of classes itself changes, `class` can take a signal that returns a `Vec`,
+/// `HashSet` or `BTreeSet` of class names. When it changes, only the classes that were added or
+/// removed are updated, and any others are left alone.
+/// ```rust
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// let (classes, set_classes) = create_signal(cx, vec!["card", "selected"]);
+/// view! { cx, <div class=move || classes.get()>"Card"</div> }
+/// # ;
+/// # }
+/// # });
+/// ```
+///
 /// 8. You can use the `node_ref` or `_ref` attribute to store a reference to its DOM element in a
 ///    [NodeRef](leptos_dom::NodeRef) to use later.
 /// ```rust
@@ -235,7 +298,8 @@ mod template;
 ///
 /// 10. You can set any HTML element’s `innerHTML` with the `inner_html` attribute on an
 ///     element. Be careful: this HTML will not be escaped, so you should ensure that it
-///     only contains trusted input.
+///     only contains trusted input. It can also take a signal, to replace the HTML whenever it
+///     changes. When hydrating, the HTML the server rendered is left as it is.
 /// ```rust
 /// # use leptos::*;
 /// # run_scope(create_runtime(), |cx| {
@@ -249,6 +313,28 @@ mod template;
 /// # });
 /// ```
 ///
+/// 11. You can attach a reusable behavior to an element with a `use:` directive, which names a
+///     function that takes the element’s `Scope`, the `web_sys::Element` and a parameter. It’s
+///     called once the element is mounted, and any cleanups it registers run when the scope is
+///     disposed. Several directives on one element run in order. See
+///     [directives](leptos_dom::directives).
+/// ```rust
+/// # use leptos::*;
+/// use leptos::directives::click_outside;
+///
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// let (open, set_open) = create_signal(cx, true);
+/// view! { cx,
+///   <menu use:click_outside=move |_: ev::MouseEvent| set_open.set(false)>
+///     <li>"Copy"</li>
+///   </menu>
+/// }
+/// # ;
+/// # }
+/// # });
+/// ```
+///
 /// Here’s a simple example that shows off several of these features, put together
 /// ```rust
 /// # use leptos::*;
@@ -424,10 +510,9 @@ pub fn template(tokens: TokenStream) -> TokenStream {
 ///     // create the signals (reactive values) that will update the UI
 ///     let (age, set_age) = create_signal(cx, age);
 ///     // increase `age` by 1 every second
-///     set_interval(
-///         move || set_age.update(|age| *age += 1),
-///         Duration::from_secs(1),
-///     );
+///     set_interval(cx, Duration::from_secs(1), move || {
+///         set_age.update(|age| *age += 1)
+///     });
 ///
 ///     // return the user interface, which will be automatically updated
 ///     // when signal values change
@@ -646,15 +731,28 @@ pub fn component(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 /// If you call a server function from the client (i.e., when the `csr` or `hydrate` features
 /// are enabled), it will instead make a network request to the server.
 ///
-/// You can specify one, two, or three arguments to the server function:
-/// 1. **Required**: A type name that will be used to identify and register the server function
-///   (e.g., `MyServerFn`).
+/// You can specify up to three arguments to the server function:
+/// 1. *Optional*: A type name that will be used to identify and register the server function
+///    (e.g., `MyServerFn`). Defaults to the function's name in `PascalCase`, e.g., `ReadPosts`
+///    for `read_posts`.
 /// 2. *Optional*: A URL prefix at which the function will be mounted when it’s registered
-///   (e.g., `"/api"`). Defaults to `"/"`.
-/// 3. *Optional*: either `"Cbor"` (specifying that it should use the binary `cbor` format for
-///   serialization) or `"Url"` (specifying that it should be use a URL-encoded form-data string).
-///   Defaults to `"Url"`. If you want to use this server function to power a `<form>` that will
-///   work without WebAssembly, the encoding must be `"Url"`.
+///    (e.g., `"/api"`). Defaults to `"/"`.
+/// 3. *Optional*: the encoding of the arguments, one of `"Cbor"` (specifying that it should use
+///    the binary `cbor` format for serialization), `"Json"`, or `"Url"` (specifying that it should
+///    use a URL-encoded form-data string). Defaults to `"Url"`. The server also accepts
+///    URL-encoded arguments for functions with another encoding, so any of them can power a `<form>`
+///    that will work without WebAssembly, as long as its arguments can be URL-encoded.
+///
+/// The prefix and the encoding can also be given by name, along with the encoding of the results,
+/// either `"Json"` or `"Cbor"`, which defaults to `"Cbor"` for functions whose arguments are
+/// `"Cbor"`, and to `"Json"` for the rest:
+///
+/// ```ignore
+/// #[server(prefix = "/api", encoding = "Url", output = "Cbor")]
+/// pub async fn upload(name: String) -> Result<Vec<u8>, ServerFnError> {
+///   todo!()
+/// }
+/// ```
 ///
 /// The server function itself can take any number of arguments, each of which should be serializable
 /// and deserializable with `serde`. Optionally, its first argument can be a Leptos [Scope](leptos_reactive::Scope),
@@ -674,20 +772,23 @@ pub fn component(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 ///
 /// Note the following:
 /// - You must **register** the server function by calling `T::register()` somewhere in your main function.
+/// - On the client, the call is sent with `fetch`, unless another transport has been set with
+///   `server_fn::set_client_transport`.
 /// - **Server functions must be `async`.** Even if the work being done inside the function body
 ///   can run synchronously on the server, from the client’s perspective it involves an asynchronous
 ///   function call.
 /// - **Server functions must return `Result<T, ServerFnError>`.** Even if the work being done
 ///   inside the function body can’t fail, the processes of serialization/deserialization and the
-///   network call are fallible.
+///   network call are fallible. They can also return errors of their own, as
+///   `Result<T, ServerFnError<MyAppError>>`, where `MyAppError` implements `ServerFnCustomError`.
 /// - **Return types must be [Serializable](leptos_reactive::Serializable).**
 ///   This should be fairly obvious: we have to serialize arguments to send them to the server, and we
 ///   need to deserialize the result to return it to the client.
 /// - **Arguments must be implement [`Serialize`](https://docs.rs/serde/latest/serde/trait.Serialize.html)
 ///   and [`DeserializeOwned`](https://docs.rs/serde/latest/serde/de/trait.DeserializeOwned.html).**
 ///   They are serialized as an `application/x-www-form-urlencoded`
-///   form data using [`serde_urlencoded`](https://docs.rs/serde_urlencoded/latest/serde_urlencoded/) or as `application/cbor`
-///   using [`cbor`](https://docs.rs/cbor/latest/cbor/).
+///   form data, as [`server_fn::form`](https://docs.rs/server_fn/latest/server_fn/form/index.html) describes, as `application/json`,
+///   or as `application/cbor` using [`cbor`](https://docs.rs/cbor/latest/cbor/).
 /// - **The [Scope](leptos_reactive::Scope) comes from the server.** Optionally, the first argument of a server function
 ///   can be a Leptos [Scope](leptos_reactive::Scope). This scope can be used to inject dependencies like the HTTP request
 ///   or response or other server-only dependencies, but it does *not* have access to reactive state that exists in the client.
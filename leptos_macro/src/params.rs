@@ -4,10 +4,12 @@ use syn::spanned::Spanned;
 pub fn impl_params(ast: &syn::DeriveInput) -> proc_macro::TokenStream {
     let name = &ast.ident;
 
-    let fields = if let syn::Data::Struct(syn::DataStruct {
-        fields: syn::Fields::Named(ref fields),
-        ..
-    }) = ast.data
+    let (fields, query_fields): (Vec<_>, Vec<_>) = if let syn::Data::Struct(
+        syn::DataStruct {
+            fields: syn::Fields::Named(ref fields),
+            ..
+        },
+    ) = ast.data
     {
         fields
             .named
@@ -18,22 +20,33 @@ pub fn impl_params(ast: &syn::DeriveInput) -> proc_macro::TokenStream {
 				let ty = &field.ty;
 				let span = field.span();
 
-				quote_spanned! {
-					span => #ident: <#ty>::into_param(map.get(#field_name_string).map(|n| n.as_str()), #field_name_string)?
-				}
+				(
+					quote_spanned! {
+						span => #ident: <#ty as ::leptos_router::IntoParam>::into_param(map.get(#field_name_string).map(|n| n.as_str()), #field_name_string)?
+					},
+					quote_spanned! {
+						span => #ident: <#ty as ::leptos_router::IntoParam>::from_values(&query.get_all(#field_name_string).map(|n| n.as_str()).collect::<::std::vec::Vec<_>>(), #field_name_string)?
+					},
+				)
 			})
-            .collect()
+            .unzip()
     } else {
-        vec![]
+        (vec![], vec![])
     };
 
     let gen = quote! {
-        impl Params for #name {
+        impl ::leptos_router::Params for #name {
             fn from_map(map: &::leptos_router::ParamsMap) -> Result<Self, ::leptos_router::ParamsError> {
                 Ok(Self {
                     #(#fields,)*
                 })
             }
+
+            fn from_query(query: &::leptos_router::QueryMap) -> Result<Self, ::leptos_router::ParamsError> {
+                Ok(Self {
+                    #(#query_fields,)*
+                })
+            }
         }
     };
     gen.into()
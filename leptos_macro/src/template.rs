@@ -45,12 +45,33 @@ fn root_element_to_tokens(
             true,
         );
 
+        // an SVG or MathML root is parsed inside an `<svg>` or `<math>`
+        // wrapper, so that it's created in the right namespace
+        let root_tag = node.name.to_string();
+        let wrapper = if root_tag == "svg" || root_tag == "math" {
+            None
+        } else if crate::view::is_svg_element(&root_tag) {
+            Some("svg")
+        } else if crate::view::is_math_ml_element(&root_tag) {
+            Some("math")
+        } else {
+            None
+        };
+        let (template, unwrap) = match wrapper {
+            Some(wrapper) => (
+                format!("<{wrapper}>{template}</{wrapper}>"),
+                quote! { .first_child().unwrap() },
+            ),
+            None => (template, quote! {}),
+        };
+
         // create the root element from which navigations and expressions will begin
         let generate_root = quote! {
             let root = #template_uid.with(|tpl| tpl.content().clone_node_with_deep(true))
                 .unwrap()
                 .first_child()
-                .unwrap();
+                .unwrap()
+                #unwrap;
         };
 
         let span = node.name.span();
@@ -139,7 +160,14 @@ fn element_to_tokens(
 
     // attributes
     for attr in attributes(node) {
-        attr_to_tokens(cx, attr, &this_el_ident, template, expressions);
+        attr_to_tokens(
+            cx,
+            &name_str,
+            attr,
+            &this_el_ident,
+            template,
+            expressions,
+        );
     }
 
     // navigation for this el
@@ -261,6 +289,7 @@ fn next_sibling_node(
 
 fn attr_to_tokens(
     cx: &Ident,
+    tag_name: &str,
     node: &NodeAttribute,
     el_id: &Ident,
     template: &mut String,
@@ -268,7 +297,15 @@ fn attr_to_tokens(
 ) {
     let name = node.key.to_string();
     let name = name.strip_prefix('_').unwrap_or(&name);
-    let name = name.strip_prefix("attr:").unwrap_or(name);
+    let (name, is_boolean) = match name.strip_prefix("attr:bool:") {
+        Some(name) => (name, true),
+        None => {
+            let name = name.strip_prefix("attr:").unwrap_or(name);
+            let is_boolean = crate::view::is_boolean_attribute(name)
+                && !tag_name.contains('-');
+            (name, is_boolean)
+        }
+    };
 
     let value = match &node.value {
         Some(expr) => match expr.as_ref() {
@@ -289,6 +326,10 @@ fn attr_to_tokens(
     // refs
     if name == "ref" {
         abort!(span, "node_ref not yet supported in template! macro")
+    } else if name.starts_with("use:") {
+        abort!(span, "directives are not yet supported in template! macro")
+    } else if name.starts_with("bind:") {
+        abort!(span, "bindings are not yet supported in template! macro")
     }
     // Event Handlers
     else if name.starts_with("on:") {
@@ -322,6 +363,10 @@ fn attr_to_tokens(
                 template.push_str(name);
             }
 
+            AttributeValue::Static(value) if is_boolean && value == "false" => {
+                // a boolean attribute set to "false" is left out
+            }
+
             // Static attributes (i.e., just a literal given as value, not an expression)
             // are just set in the template — again, nothing programmatic
             AttributeValue::Static(value) => {
@@ -331,6 +376,11 @@ fn attr_to_tokens(
                 template.push_str(&value);
                 template.push('"');
             }
+            AttributeValue::Dynamic(value) if is_boolean => {
+                expressions.push(quote_spanned! {
+                    span => leptos::leptos_dom::attribute_helper(#el_id.unchecked_ref(), #name.into(), {#value}.into_attribute(#cx).into_boolean())
+                });
+            }
             AttributeValue::Dynamic(value) => {
                 // For client-side rendering, dynamic attributes don't need to be rendered in the template
                 // They'll immediately be set synchronously before the cloned template is mounted
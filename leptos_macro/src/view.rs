@@ -352,13 +352,16 @@ fn element_to_tokens_ssr(
 
         for attr in &node.attributes {
             if let Node::Attribute(attr) = attr {
-                inner_html = attribute_to_tokens_ssr(
+                if let Some(value) = attribute_to_tokens_ssr(
                     cx,
+                    &tag_name,
                     attr,
                     template,
                     holes,
                     exprs_for_compiler,
-                );
+                ) {
+                    inner_html = Some(value);
+                }
             }
         }
 
@@ -383,6 +386,8 @@ fn element_to_tokens_ssr(
         holes.push(hydration_id);
 
         set_class_attribute_ssr(cx, node, template, holes, global_class);
+        set_style_attribute_ssr(cx, node, template, holes);
+        set_prop_attributes_ssr(cx, node, template, holes);
 
         if is_self_closing(node) {
             template.push_str("/>");
@@ -449,6 +454,7 @@ fn element_to_tokens_ssr(
 // returns `inner_html`
 fn attribute_to_tokens_ssr<'a>(
     cx: &Ident,
+    tag_name: &str,
     node: &'a NodeAttribute,
     template: &mut String,
     holes: &mut Vec<TokenStream>,
@@ -462,36 +468,74 @@ fn attribute_to_tokens_ssr<'a>(
         exprs_for_compiler.push(quote! {
             leptos::leptos_dom::helpers::ssr_event_listener(#event_type, #handler);
         })
+    } else if name.strip_prefix("use:").is_some() {
+        let (handler, param) = directive_from_attribute_node(node);
+        exprs_for_compiler.push(quote! {
+            leptos::leptos_dom::helpers::ssr_directive(#handler, #param);
+        })
     } else if name.strip_prefix("prop:").is_some()
+        || name.strip_prefix("bind:").is_some()
         || name.strip_prefix("class:").is_some()
+        || name.strip_prefix("style:").is_some()
     {
         // ignore props for SSR
-        // ignore classes: we'll handle these separately
+        // ignore classes and styles: we'll handle these separately
     } else if name == "inner_html" {
         return node.value.as_ref();
     } else {
-        let name = name.replacen("attr:", "", 1);
-
-        if name != "class" {
-            template.push(' ');
+        let (name, is_boolean) = match name.strip_prefix("attr:bool:") {
+            Some(name) => (name.to_string(), true),
+            None => {
+                let name = name.replacen("attr:", "", 1);
+                let is_boolean =
+                    is_boolean_attribute(&name) && !is_custom_element(tag_name);
+                (name, is_boolean)
+            }
+        };
 
+        if name != "class" && name != "style" {
             if let Some(value) = node.value.as_ref() {
                 if let Some(value) = value_to_string(value) {
-                    template.push_str(&name);
-                    template.push_str("=\"");
-                    template.push_str(&value);
-                    template.push('"');
+                    if is_boolean {
+                        if value != "false" {
+                            template.push(' ');
+                            template.push_str(&name);
+                        }
+                    } else {
+                        template.push(' ');
+                        template.push_str(&name);
+                        template.push_str("=\"");
+                        template.push_str(&value);
+                        template.push('"');
+                    }
+                } else if is_boolean {
+                    // each hole starts with its own space, so that it's
+                    // left out along with the attribute
+                    template.push_str("{}");
+                    let value = value.as_ref();
+                    holes.push(quote! {
+                        &{#value}.into_attribute(#cx)
+                            .into_boolean()
+                            .as_nameless_value_string()
+                            .map(|a| if a.is_empty() {
+                                format!(" {}", #name)
+                            } else {
+                                format!(" {}=\"{}\"", #name, leptos::leptos_dom::ssr::escape_attr(&a))
+                            })
+                            .unwrap_or_default(),
+                    })
                 } else {
                     template.push_str("{}");
                     let value = value.as_ref();
                     holes.push(quote! {
                         &{#value}.into_attribute(#cx)
                             .as_nameless_value_string()
-                            .map(|a| format!("{}=\"{}\"", #name, leptos::leptos_dom::ssr::escape_attr(&a)))
+                            .map(|a| format!(" {}=\"{}\"", #name, leptos::leptos_dom::ssr::escape_attr(&a)))
                             .unwrap_or_default(),
                     })
                 }
             } else {
+                template.push(' ');
                 template.push_str(&name);
             }
         }
@@ -631,6 +675,219 @@ fn set_class_attribute_ssr(
     }
 }
 
+// properties that have an attribute for their initial value
+const PROPS_WITH_INITIAL_ATTRIBUTE: [&str; 3] =
+    ["value", "checked", "selected"];
+
+// There's no live value on the server, so `prop:value`, `prop:checked` and
+// `prop:selected` are rendered as the attribute that sets the initial value,
+// unless the element already has that attribute.
+fn set_prop_attributes_ssr(
+    cx: &Ident,
+    node: &NodeElement,
+    template: &mut String,
+    holes: &mut Vec<TokenStream>,
+) {
+    let attrs = node
+        .attributes
+        .iter()
+        .filter_map(|a| match a {
+            Node::Attribute(attr) => Some(attr),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    for attr in &attrs {
+        let name = attr.key.to_string();
+        let Some(name) = name.strip_prefix("prop:") else {
+            continue;
+        };
+        if !PROPS_WITH_INITIAL_ATTRIBUTE.contains(&name)
+            || attrs.iter().any(|a| {
+                let key = a.key.to_string();
+                key == name || key.strip_prefix("attr:") == Some(name)
+            })
+        {
+            continue;
+        }
+        let value = attribute_value(attr);
+        template.push_str(" {}");
+        holes.push(quote! {
+            &{#value}.into_attribute(#cx)
+                .as_nameless_value_string()
+                .map(|a| format!("{}=\"{}\"", #name, leptos::leptos_dom::ssr::escape_attr(&a)))
+                .unwrap_or_default(),
+        });
+    }
+
+    // `bind:` sets the same properties, from the signal's current value
+    for attr in &attrs {
+        let key = attr.key.to_string();
+        let Some(binding) = key.strip_prefix("bind:") else {
+            continue;
+        };
+        let prop = if binding == "value" {
+            "value"
+        } else {
+            "checked"
+        };
+        if attrs.iter().any(|a| {
+            let key = a.key.to_string();
+            key == prop
+                || key.strip_prefix("attr:") == Some(prop)
+                || key.strip_prefix("prop:") == Some(prop)
+        }) {
+            continue;
+        }
+        template.push_str("{}");
+        holes.push(match binding {
+            "value" => {
+                let value = attribute_value(attr);
+                quote! {
+                    &format!(
+                        " value=\"{}\"",
+                        leptos::leptos_dom::ssr::escape_attr(
+                            &leptos::leptos_dom::ssr_bind_value(#value)
+                        )
+                    ),
+                }
+            }
+            "checked" => {
+                let value = attribute_value(attr);
+                quote! {
+                    if leptos::leptos_dom::ssr_bind_checked(#value) {
+                        " checked"
+                    } else {
+                        ""
+                    },
+                }
+            }
+            _ => {
+                let (group, value) = bind_group_from_attribute_node(attr);
+                quote! {
+                    if leptos::leptos_dom::ssr_bind_group(#group, #value) {
+                        " checked"
+                    } else {
+                        ""
+                    },
+                }
+            }
+        });
+    }
+}
+
+// `bind:group=(group, value)`
+fn bind_group_from_attribute_node(attr: &NodeAttribute) -> (&Expr, &Expr) {
+    match attribute_value(attr) {
+        Expr::Tuple(tuple) if tuple.elems.len() == 2 => {
+            (&tuple.elems[0], &tuple.elems[1])
+        }
+        value => abort!(
+            value.span(),
+            "`bind:group` takes the group and the value of this input, e.g., \
+             `bind:group=(selected, \"a\")`"
+        ),
+    }
+}
+
+fn set_style_attribute_ssr(
+    cx: &Ident,
+    node: &NodeElement,
+    template: &mut String,
+    holes: &mut Vec<TokenStream>,
+) {
+    let static_style_attr = node
+        .attributes
+        .iter()
+        .filter_map(|a| match a {
+            Node::Attribute(attr) if attr.key.to_string() == "style" => {
+                attr.value.as_ref().and_then(value_to_string)
+            }
+            _ => None,
+        })
+        .next();
+
+    let dyn_style_attr = node
+        .attributes
+        .iter()
+        .filter_map(|a| {
+            if let Node::Attribute(a) = a {
+                if a.key.to_string() == "style" {
+                    if a.value.as_ref().and_then(value_to_string).is_some()
+                        || fancy_style_name(&a.key.to_string(), cx, a).is_some()
+                    {
+                        None
+                    } else {
+                        a.value.as_ref()
+                    }
+                } else {
+                    None
+                }
+            } else {
+                None
+            }
+        })
+        .next();
+
+    let style_attrs = node
+        .attributes
+        .iter()
+        .filter_map(|node| {
+            if let Node::Attribute(node) = node {
+                let name = node.key.to_string();
+                if name == "style" {
+                    return fancy_style_name(&name, cx, node)
+                        .map(|(_, name, value)| (name, value));
+                }
+                name.strip_prefix("style:")
+                    .map(|name| (name.to_string(), attribute_value(node)))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if style_attrs.is_empty() {
+        // a `style` attribute on its own is rendered as it is
+        if let Some(value) = static_style_attr {
+            template.push_str(" style=\"");
+            template.push_str(&value);
+            template.push('"');
+        } else if let Some(value) = dyn_style_attr {
+            template.push_str(" {}");
+            let value = value.as_ref();
+            holes.push(quote! {
+                &{#value}.into_attribute(#cx)
+                    .as_nameless_value_string()
+                    .map(|a| format!("style=\"{}\"", leptos::leptos_dom::ssr::escape_attr(&a)))
+                    .unwrap_or_default(),
+            });
+        }
+    } else {
+        template.push_str("{}");
+        let base = if let Some(value) = static_style_attr {
+            quote! { Some(#value.to_string()) }
+        } else if let Some(value) = dyn_style_attr {
+            let value = value.as_ref();
+            quote! {
+                {#value}.into_attribute(#cx).as_nameless_value_string()
+            }
+        } else {
+            quote! { None }
+        };
+        let properties = style_attrs.iter().map(|(name, value)| {
+            quote! {
+                (#name, leptos::IntoStyle::into_style((#cx, #value), #cx))
+            }
+        });
+        holes.push(quote! {
+            leptos::leptos_dom::ssr_style_attribute(
+                #base,
+                vec![#(#properties),*],
+            ),
+        });
+    }
+}
+
 fn fragment_to_tokens(
     cx: &Ident,
     _span: Span,
@@ -745,6 +1002,10 @@ fn element_to_tokens(
             parent_type = TagType::Html;
             quote! { leptos::leptos_dom::html::#name(#cx) }
         };
+        // the children of a `<foreignObject>` are HTML again
+        if tag == "foreignObject" {
+            parent_type = TagType::Html;
+        }
         let attrs = node.attributes.iter().filter_map(|node| {
             if let Node::Attribute(node) = node {
                 Some(attribute_to_tokens(cx, node))
@@ -823,7 +1084,7 @@ fn attribute_to_tokens(cx: &Ident, node: &NodeAttribute) -> TokenStream {
         }
     } else if let Some(name) = name.strip_prefix("on:") {
         let handler = attribute_value(node);
-        let (name, is_force_undelegated) = parse_event(name);
+        let (name, modifiers) = parse_event(name);
 
         let event_type = TYPED_EVENTS
             .iter()
@@ -837,7 +1098,7 @@ fn attribute_to_tokens(cx: &Ident, node: &NodeAttribute) -> TokenStream {
         };
 
         let event_type = if is_custom {
-            quote! { leptos::ev::Custom::new(#name) }
+            quote! { Custom::new(#name) }
         } else {
             event_type
         };
@@ -853,13 +1114,9 @@ fn attribute_to_tokens(cx: &Ident, node: &NodeAttribute) -> TokenStream {
             _ => unreachable!(),
         };
         let undelegated_ident = match &node.key {
-            NodeName::Punctuated(parts) => parts.last().and_then(|last| {
-                if last == "undelegated" {
-                    Some(last)
-                } else {
-                    None
-                }
-            }),
+            NodeName::Punctuated(parts) => {
+                parts.iter().skip(2).find(|part| *part == "undelegated")
+            }
             _ => unreachable!(),
         };
         let on = match &node.key {
@@ -883,7 +1140,7 @@ fn attribute_to_tokens(cx: &Ident, node: &NodeAttribute) -> TokenStream {
             event_type
         };
 
-        let event_type = if is_force_undelegated {
+        let event_type = if modifiers.undelegated {
             let undelegated = if let Some(undelegated) = undelegated_ident {
                 let span = undelegated.span();
                 quote_spanned! {
@@ -896,10 +1153,63 @@ fn attribute_to_tokens(cx: &Ident, node: &NodeAttribute) -> TokenStream {
         } else {
             quote! { ::leptos::ev::#event_type }
         };
+        let event_type = modifiers.wrap(event_type);
+        let handler = if is_custom {
+            quote! {
+                ::leptos::leptos_dom::ev::custom_handler(
+                    #[allow(unused_braces)] #handler
+                )
+            }
+        } else {
+            quote! { #handler }
+        };
 
         quote! {
             #on(#event_type, #handler)
         }
+    } else if name.strip_prefix("use:").is_some() {
+        let (handler, param) = directive_from_attribute_node(node);
+        let directive = match &node.key {
+            NodeName::Punctuated(parts) => &parts[0],
+            _ => unreachable!(),
+        };
+        let directive = {
+            let span = directive.span();
+            quote_spanned! {
+                span => .directive
+            }
+        };
+        quote! {
+            #directive(#handler, #param)
+        }
+    } else if let Some(binding) = name.strip_prefix("bind:") {
+        let span = match &node.key {
+            NodeName::Punctuated(parts) => parts[0].span(),
+            _ => unreachable!(),
+        };
+        match binding {
+            "value" => {
+                let value = attribute_value(node);
+                let bind = quote_spanned! { span => .bind_value };
+                quote! { #bind(#value) }
+            }
+            "checked" => {
+                let value = attribute_value(node);
+                let bind = quote_spanned! { span => .bind_checked };
+                quote! { #bind(#value) }
+            }
+            "group" => {
+                let (group, value) = bind_group_from_attribute_node(node);
+                let bind = quote_spanned! { span => .bind_group };
+                quote! { #bind(#group, #value) }
+            }
+            _ => abort!(
+                node.key.span(),
+                "unknown binding `bind:{}`: expected `bind:value`, \
+                 `bind:checked` or `bind:group`",
+                binding
+            ),
+        }
     } else if let Some(name) = name.strip_prefix("prop:") {
         let value = attribute_value(node);
         let prop = match &node.key {
@@ -930,6 +1240,43 @@ fn attribute_to_tokens(cx: &Ident, node: &NodeAttribute) -> TokenStream {
         quote! {
             #class(#name, (#cx, #[allow(unused_braces)] #value))
         }
+    } else if let Some(name) = name.strip_prefix("style:") {
+        let value = attribute_value(node);
+        let style = match &node.key {
+            NodeName::Punctuated(parts) => &parts[0],
+            _ => unreachable!(),
+        };
+        let style = {
+            let span = style.span();
+            quote_spanned! {
+                span => .style
+            }
+        };
+        quote! {
+            #style(#name, (#cx, #[allow(unused_braces)] #value))
+        }
+    } else if let Some(name) = name.strip_prefix("attr:bool:") {
+        let value = match node.value.as_ref() {
+            Some(value) => {
+                let value = value.as_ref();
+
+                quote! { #value }
+            }
+            None => quote_spanned! { span => true },
+        };
+        let bool_attr = match &node.key {
+            NodeName::Punctuated(parts) => &parts[0],
+            _ => unreachable!(),
+        };
+        let bool_attr = {
+            let span = bool_attr.span();
+            quote_spanned! {
+                span => .bool_attr
+            }
+        };
+        quote! {
+            #bool_attr(#name, (#cx, #[allow(unused_braces)] #value))
+        }
     } else {
         let name = name.replacen("attr:", "", 1);
 
@@ -937,6 +1284,10 @@ fn attribute_to_tokens(cx: &Ident, node: &NodeAttribute) -> TokenStream {
             return fancy;
         }
 
+        if let Some((fancy, _, _)) = fancy_style_name(&name, cx, node) {
+            return fancy;
+        }
+
         // all other attributes
         let value = match node.value.as_ref() {
             Some(value) => {
@@ -1076,7 +1427,7 @@ pub(crate) fn component_to_tokens(
 pub(crate) fn event_from_attribute_node(
     attr: &NodeAttribute,
     force_undelegated: bool,
-) -> (TokenStream, &Expr) {
+) -> (TokenStream, TokenStream) {
     let event_name = attr
         .key
         .to_string()
@@ -1087,7 +1438,7 @@ pub(crate) fn event_from_attribute_node(
     let handler = attribute_value(attr);
 
     #[allow(unused_variables)]
-    let (name, name_undelegated) = parse_event(&event_name);
+    let (name, modifiers) = parse_event(&event_name);
 
     let event_type = TYPED_EVENTS
         .iter()
@@ -1098,13 +1449,28 @@ pub(crate) fn event_from_attribute_node(
     let Ok(event_type) = event_type.parse::<TokenStream>() else {
         abort!(attr.key, "couldn't parse event name");
     };
+    let is_custom = event_type.to_string() == "Custom";
+    let event_type = if is_custom {
+        quote! { Custom::new(#name) }
+    } else {
+        event_type
+    };
 
-    let event_type = if force_undelegated || name_undelegated {
+    let event_type = if force_undelegated || modifiers.undelegated {
         quote! { ::leptos::leptos_dom::ev::undelegated(::leptos::leptos_dom::ev::#event_type) }
     } else {
         quote! { ::leptos::leptos_dom::ev::#event_type }
     };
-    (event_type, handler)
+    let handler = if is_custom {
+        quote! {
+            ::leptos::leptos_dom::ev::custom_handler(
+                #[allow(unused_braces)] #handler
+            )
+        }
+    } else {
+        quote! { #handler }
+    };
+    (modifiers.wrap(event_type), handler)
 }
 
 fn ident_from_tag_name(tag_name: &NodeName) -> Ident {
@@ -1145,10 +1511,68 @@ fn expr_to_ident(expr: &syn::Expr) -> Option<&ExprPath> {
     }
 }
 
+// `use:name=param` calls the function `name` with `param`, or with `()` if
+// there isn't one
+fn directive_from_attribute_node(
+    node: &NodeAttribute,
+) -> (TokenStream, TokenStream) {
+    let key = node.key.to_string();
+    let name = key.strip_prefix("use:").unwrap_or(&key);
+    let Ok(handler) = syn::parse_str::<Ident>(name) else {
+        abort!(
+            node.key,
+            "a directive should be the name of a function, like \
+             `use:click_outside`"
+        );
+    };
+    let handler = quote_spanned! { node.key.span() => #handler };
+    let param = match node.value.as_ref() {
+        Some(value) => {
+            let value = value.as_ref();
+            quote! { #[allow(unused_braces)] #value }
+        }
+        None => quote! { () },
+    };
+    (handler, param)
+}
+
 fn is_custom_element(tag: &str) -> bool {
     tag.contains('-')
 }
 
+// the boolean attributes in the HTML spec, which are on whenever they're
+// present, even as `disabled="false"`
+pub(crate) fn is_boolean_attribute(name: &str) -> bool {
+    matches!(
+        name,
+        "allowfullscreen"
+            | "async"
+            | "autofocus"
+            | "autoplay"
+            | "checked"
+            | "controls"
+            | "default"
+            | "defer"
+            | "disabled"
+            | "formnovalidate"
+            | "hidden"
+            | "inert"
+            | "ismap"
+            | "itemscope"
+            | "loop"
+            | "multiple"
+            | "muted"
+            | "nomodule"
+            | "novalidate"
+            | "open"
+            | "playsinline"
+            | "readonly"
+            | "required"
+            | "reversed"
+            | "selected"
+    )
+}
+
 fn is_self_closing(node: &NodeElement) -> bool {
     // self-closing tags
     // https://developer.mozilla.org/en-US/docs/Glossary/Empty_element
@@ -1187,7 +1611,7 @@ fn camel_case_tag_name(tag_name: &str) -> String {
         + underscore
 }
 
-fn is_svg_element(tag: &str) -> bool {
+pub(crate) fn is_svg_element(tag: &str) -> bool {
     matches!(
         tag,
         "animate"
@@ -1256,7 +1680,7 @@ fn is_svg_element(tag: &str) -> bool {
     )
 }
 
-fn is_math_ml_element(tag: &str) -> bool {
+pub(crate) fn is_math_ml_element(tag: &str) -> bool {
     matches!(
         tag,
         "math"
@@ -1294,17 +1718,78 @@ fn is_math_ml_element(tag: &str) -> bool {
 }
 
 fn is_ambiguous_element(tag: &str) -> bool {
-    tag == "a" || tag == "script"
+    matches!(tag, "a" | "script" | "style" | "title")
 }
 
-fn parse_event(event_name: &str) -> (&str, bool) {
-    if let Some(event_name) = event_name.strip_suffix(":undelegated") {
-        (event_name, true)
-    } else {
-        (event_name, false)
+// the `:undelegated`, `:capture`, `:passive`, `:once`, `:prevent` and `:stop`
+// suffixes of an event, in any order
+#[derive(Default)]
+struct EventModifiers {
+    undelegated: bool,
+    capture: bool,
+    passive: bool,
+    once: bool,
+    prevent: bool,
+    stop: bool,
+}
+
+impl EventModifiers {
+    // wraps the event in the `ev` types that set its listener options
+    fn wrap(&self, mut event_type: TokenStream) -> TokenStream {
+        for (set, wrapper) in [
+            (self.once, quote! { once }),
+            (self.passive, quote! { passive }),
+            (self.capture, quote! { capture }),
+        ] {
+            if set {
+                event_type =
+                    quote! { ::leptos::leptos_dom::ev::#wrapper(#event_type) };
+            }
+        }
+
+        // these are done by the handler, so the listener can stay delegated
+        let flags = [
+            (self.prevent, quote! { PREVENT_DEFAULT }),
+            (self.stop, quote! { STOP_PROPAGATION }),
+        ]
+        .into_iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, flag)| quote! { ::leptos::leptos_dom::ev::EventModifiers::#flag })
+        .collect::<Vec<_>>();
+        if !flags.is_empty() {
+            event_type = quote! {
+                ::leptos::leptos_dom::ev::modified(#event_type, #(#flags)|*)
+            };
+        }
+        event_type
     }
 }
 
+fn parse_event(mut event_name: &str) -> (&str, EventModifiers) {
+    let mut modifiers = EventModifiers::default();
+    loop {
+        let (rest, modifier) =
+            if let Some(rest) = event_name.strip_suffix(":undelegated") {
+                (rest, &mut modifiers.undelegated)
+            } else if let Some(rest) = event_name.strip_suffix(":capture") {
+                (rest, &mut modifiers.capture)
+            } else if let Some(rest) = event_name.strip_suffix(":passive") {
+                (rest, &mut modifiers.passive)
+            } else if let Some(rest) = event_name.strip_suffix(":once") {
+                (rest, &mut modifiers.once)
+            } else if let Some(rest) = event_name.strip_suffix(":prevent") {
+                (rest, &mut modifiers.prevent)
+            } else if let Some(rest) = event_name.strip_suffix(":stop") {
+                (rest, &mut modifiers.stop)
+            } else {
+                break;
+            };
+        *modifier = true;
+        event_name = rest;
+    }
+    (event_name, modifiers)
+}
+
 fn fancy_class_name<'a>(
     name: &str,
     cx: &Ident,
@@ -1353,3 +1838,52 @@ fn fancy_class_name<'a>(
     }
     None
 }
+
+fn fancy_style_name<'a>(
+    name: &str,
+    cx: &Ident,
+    node: &'a NodeAttribute,
+) -> Option<(TokenStream, String, &'a Expr)> {
+    // special case for properties that can't be written as `style:name`:
+    // e.g., custom properties `style=("--my-var", value)`
+    if name == "style" {
+        if let Some(expr) = node.value.as_ref() {
+            if let syn::Expr::Tuple(tuple) = expr.as_ref() {
+                if tuple.elems.len() == 2 {
+                    let span = node.key.span();
+                    let style = quote_spanned! {
+                        span => .style
+                    };
+                    let style_name = &tuple.elems[0];
+                    let style_name = if let Expr::Lit(ExprLit {
+                        lit: Lit::Str(s),
+                        ..
+                    }) = style_name
+                    {
+                        s.value()
+                    } else {
+                        proc_macro_error::emit_error!(
+                            style_name.span(),
+                            "style name must be a string literal"
+                        );
+                        Default::default()
+                    };
+                    let value = &tuple.elems[1];
+                    return Some((
+                        quote! {
+                            #style(#style_name, (#cx, #value))
+                        },
+                        style_name,
+                        value,
+                    ));
+                } else {
+                    proc_macro_error::emit_error!(
+                        tuple.span(),
+                        "style tuples must have two elements."
+                    )
+                }
+            }
+        }
+    }
+    None
+}
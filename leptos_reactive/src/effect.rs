@@ -4,7 +4,6 @@ use crate::{
     runtime::{with_runtime, RuntimeId},
     Runtime, Scope, ScopeProperty,
 };
-use cfg_if::cfg_if;
 use std::{cell::RefCell, fmt::Debug};
 
 /// Effects run a certain chunk of code whenever the signals they depend on change.
@@ -22,7 +21,8 @@ use std::{cell::RefCell, fmt::Debug};
 ///
 /// By default, effects **do not run on the server**. This means you can call browser-specific
 /// APIs within the effect function without causing issues. If you need an effect to run on
-/// the server, use [create_isomorphic_effect].
+/// the server, use [create_isomorphic_effect]. Whether it's on the server is decided by the
+/// runtime's [RenderMode](crate::RenderMode), not only by the crate features.
 /// ```
 /// # use leptos_reactive::*;
 /// # use log::*;
@@ -66,16 +66,12 @@ pub fn create_effect<T>(cx: Scope, f: impl Fn(Option<T>) -> T + 'static)
 where
     T: 'static,
 {
-    cfg_if! {
-        if #[cfg(not(feature = "ssr"))] {
-            let e = cx.runtime.create_effect(f);
-            cx.with_scope_property(|prop| prop.push(ScopeProperty::Effect(e)))
-        } else {
-            // clear warnings
-            _ = cx;
-            _ = f;
-        }
+    // effects don't run while rendering on the server
+    if cx.is_server() {
+        return;
     }
+    let e = cx.runtime.create_effect(f);
+    cx.with_scope_property(|prop| prop.push(ScopeProperty::Effect(e)))
 }
 
 /// Creates an effect; unlike effects created by [create_effect], isomorphic effects will run on
@@ -183,8 +179,8 @@ where
             )
         )
     )]
-    fn run(&self, id: EffectId, runtime: RuntimeId) {
-        _ = with_runtime(runtime, |runtime| {
+    fn run(&self, id: EffectId, runtime_id: RuntimeId) {
+        _ = with_runtime(runtime_id, |runtime| {
             // clear previous dependencies
             id.cleanup(runtime);
 
@@ -194,7 +190,10 @@ where
 
             // run the effect
             let value = self.value.take();
-            let new_value = (self.f)(value);
+            let new_value =
+                crate::runtime::with_active_runtime(runtime_id, || {
+                    (self.f)(value)
+                });
             *self.value.borrow_mut() = Some(new_value);
 
             // restore the previous observer
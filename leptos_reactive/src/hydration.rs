@@ -1,7 +1,11 @@
 #![forbid(unsafe_code)]
 use crate::{runtime::PinnedFuture, suspense::StreamChunk, ResourceId};
 use cfg_if::cfg_if;
-use std::collections::{HashMap, HashSet};
+use indexmap::{IndexMap, IndexSet};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    rc::Rc,
+};
 
 pub struct SharedContext {
     pub events: Vec<()>,
@@ -13,8 +17,45 @@ pub struct SharedContext {
     //    Future of <Suspense/> HTML when resolved (out-of-order)
     //    Future of additional stream chunks when resolved (in-order)
     // )`
-    pub pending_fragments:
-        HashMap<String, (PinnedFuture<String>, PinnedFuture<Vec<StreamChunk>>)>,
+    // kept in the order the fragments were registered, i.e., document order
+    pub pending_fragments: IndexMap<
+        String,
+        (PinnedFuture<String>, PinnedFuture<Vec<StreamChunk>>),
+    >,
+    // whether the page is being streamed in order, in which case `<Suspense/>`
+    // waits for its fragment instead of emitting its fallback
+    pub in_order: bool,
+    // the key each resource was loaded with on the server, i.e., where it
+    // was created and the value of its source
+    pub(crate) resource_keys: HashMap<ResourceId, String>,
+    // serialized values found by `Scope::resolve_all_resources`, by key
+    pub(crate) resolved_by_key: HashMap<String, String>,
+    // resources loaded since the last `Scope::resolve_all_resources` that
+    // didn't have a value from it
+    pub(crate) unresolved_loads: usize,
+    // creates the future after which pending fragments and resources are
+    // given up on, if any
+    pub(crate) suspense_deadline: Option<Rc<dyn Fn() -> PinnedFuture<()>>>,
+    // the nonce to put on inline scripts, for a Content-Security-Policy
+    pub(crate) script_nonce: Option<String>,
+    // whether resources are sent as `application/json` script blocks
+    pub(crate) json_resources: bool,
+    // the events recorded before hydration and dispatched again after it
+    pub(crate) replayed_events: Vec<String>,
+    // the name the app's hydration keys start from, to keep them from
+    // colliding with another app's on the same page
+    pub(crate) app_id: Option<String>,
+    // whether the page has islands, in which case only the resources created
+    // inside them are sent to the client
+    pub(crate) islands: bool,
+    // how many `Scope::with_island` calls are running
+    pub(crate) island_depth: usize,
+    // the resources created inside an island, in the order they were created
+    pub(crate) island_resources: IndexSet<ResourceId>,
+    // while hydrating islands, the server's IDs for the next resources to be
+    // created, which the client numbers differently since it doesn't create
+    // the rest of the page's
+    pub(crate) island_resource_ids: Option<VecDeque<ResourceId>>,
 }
 
 impl std::fmt::Debug for SharedContext {
@@ -53,14 +94,45 @@ impl Default for SharedContext {
                 )
                 .unwrap_or(wasm_bindgen::JsValue::NULL);
 
-                let resolved_resources =
+                let mut resolved_resources: HashMap<ResourceId, String> =
                     serde_wasm_bindgen::from_value(resolved_resources).unwrap_or_default();
 
+                // resources sent as JSON instead of in scripts
+                if let Ok(scripts) = web_sys::window()
+                    .unwrap()
+                    .document()
+                    .unwrap()
+                    .query_selector_all("script[type='application/json'][data-leptos-resource]")
+                {
+                    for idx in 0..scripts.length() {
+                        let Some(script) = scripts.item(idx) else { continue };
+                        let script = wasm_bindgen::JsCast::unchecked_into::<web_sys::Element>(script);
+                        let id = script.get_attribute("data-leptos-resource").unwrap_or_default();
+                        let content = script.text_content().unwrap_or_default();
+                        if let Some((id, data)) = crate::resource_from_json_script(&id, &content) {
+                            resolved_resources.insert(id, data);
+                        }
+                    }
+                }
+
                 Self {
                     events: Default::default(),
                     pending_resources,
                     resolved_resources,
                     pending_fragments: Default::default(),
+                    in_order: false,
+                    resource_keys: Default::default(),
+                    resolved_by_key: Default::default(),
+                    unresolved_loads: 0,
+                    suspense_deadline: None,
+                    script_nonce: None,
+                    json_resources: false,
+                    replayed_events: Vec::new(),
+                    app_id: None,
+                    islands: false,
+                    island_depth: 0,
+                    island_resources: Default::default(),
+                    island_resource_ids: None,
                 }
             } else {
                 Self {
@@ -68,6 +140,19 @@ impl Default for SharedContext {
                     pending_resources: Default::default(),
                     resolved_resources: Default::default(),
                     pending_fragments: Default::default(),
+                    in_order: false,
+                    resource_keys: Default::default(),
+                    resolved_by_key: Default::default(),
+                    unresolved_loads: 0,
+                    suspense_deadline: None,
+                    script_nonce: None,
+                    json_resources: false,
+                    replayed_events: Vec::new(),
+                    app_id: None,
+                    islands: false,
+                    island_depth: 0,
+                    island_resources: Default::default(),
+                    island_resource_ids: None,
                 }
             }
         }
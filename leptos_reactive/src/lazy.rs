@@ -0,0 +1,114 @@
+#![forbid(unsafe_code)]
+use crate::{create_local_resource_with_initial_value, Resource, Scope};
+use futures::future::{FutureExt, Shared};
+use std::{cell::RefCell, fmt, future::Future, pin::Pin, rc::Rc};
+
+/// A value that arrives later: it's loaded by a [Future] the first time it's
+/// needed, and then shared by every clone of the `Lazy`, e.g., a component
+/// that's split into a separately loaded chunk.
+///
+/// The loader is called at most once, however many times the value is
+/// [loaded](Lazy::load) or [read through a resource](create_lazy_resource),
+/// and whether or not the first load has finished.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// let settings = Lazy::new(|| async {
+///     // e.g., fetching a module that's rarely used
+///     "settings"
+/// });
+/// let shared = settings.clone();
+/// assert!(shared.get().is_none());
+///
+/// assert_eq!(futures::executor::block_on(settings.load()), "settings");
+/// // the clone sees the value without loading it again
+/// assert_eq!(shared.get(), Some("settings"));
+/// ```
+pub struct Lazy<T>(Rc<LazyState<T>>);
+
+type LazyFuture<T> = Shared<Pin<Box<dyn Future<Output = T>>>>;
+
+type LazyLoader<T> = Box<dyn FnOnce() -> LazyFuture<T>>;
+
+struct LazyState<T> {
+    loader: RefCell<Option<LazyLoader<T>>>,
+    loading: RefCell<Option<LazyFuture<T>>>,
+}
+
+impl<T> Lazy<T>
+where
+    T: Clone + 'static,
+{
+    /// Creates a value that's loaded by the future `loader` returns, once
+    /// it's first needed.
+    pub fn new<Fu>(loader: impl FnOnce() -> Fu + 'static) -> Self
+    where
+        Fu: Future<Output = T> + 'static,
+    {
+        let loader = move || {
+            (Box::pin(loader()) as Pin<Box<dyn Future<Output = T>>>).shared()
+        };
+        Self(Rc::new(LazyState {
+            loader: RefCell::new(Some(Box::new(loader))),
+            loading: Default::default(),
+        }))
+    }
+
+    /// Starts loading the value, unless that has already happened, and
+    /// returns a future that resolves to it.
+    pub fn load(&self) -> impl Future<Output = T> {
+        if let Some(loader) = self.0.loader.borrow_mut().take() {
+            *self.0.loading.borrow_mut() = Some(loader());
+        }
+        self.0
+            .loading
+            .borrow()
+            .clone()
+            .expect("a Lazy is loading once its loader has been taken")
+    }
+
+    /// The value, if it has finished loading.
+    pub fn get(&self) -> Option<T> {
+        self.0
+            .loading
+            .borrow()
+            .as_ref()
+            .and_then(|loading| loading.peek().cloned())
+    }
+}
+
+impl<T> Clone for Lazy<T> {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<T> fmt::Debug for Lazy<T>
+where
+    T: Clone + fmt::Debug + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Lazy").field(&self.get()).finish()
+    }
+}
+
+/// Creates a local [Resource] that resolves to the value of a [Lazy], so
+/// that reading it inside a `<Suspense/>` suspends it until the value has
+/// loaded. It starts out resolved if the value already has.
+///
+/// Like other local resources, it isn't loaded while rendering on the
+/// server, where a `<Suspense/>` that reads it renders its fallback.
+#[track_caller]
+pub fn create_lazy_resource<T>(cx: Scope, lazy: &Lazy<T>) -> Resource<(), T>
+where
+    T: Clone + 'static,
+{
+    let initial_value = lazy.get();
+    let lazy = lazy.clone();
+    create_local_resource_with_initial_value(
+        cx,
+        || (),
+        move |_| lazy.load(),
+        initial_value,
+    )
+}
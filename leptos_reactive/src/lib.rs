@@ -74,8 +74,10 @@ mod signal;
 mod context;
 mod effect;
 mod hydration;
+mod lazy;
 mod memo;
 mod resource;
+mod response;
 mod runtime;
 mod scope;
 mod selector;
@@ -90,10 +92,17 @@ pub mod suspense;
 
 pub use context::*;
 pub use effect::*;
+pub use lazy::*;
 pub use memo::*;
 pub use resource::*;
+pub use response::*;
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+pub use runtime::assert_runtime_isolated;
 use runtime::*;
-pub use runtime::{create_runtime, RuntimeId};
+pub use runtime::{
+    create_runtime, HydrationPhase, PinnedResolverFuture, RenderMode,
+    RuntimeId, RuntimeStats,
+};
 pub use scope::*;
 pub use selector::*;
 pub use serialization::*;
@@ -104,7 +113,7 @@ pub use slice::*;
 pub use spawn::*;
 pub use spawn_microtask::*;
 pub use stored_value::*;
-pub use suspense::SuspenseContext;
+pub use suspense::{FallbackTiming, SuspenseContext};
 
 mod macros {
     macro_rules! debug_warn {
@@ -1,6 +1,6 @@
 #![forbid(unsafe_code)]
 use crate::{
-    create_effect, on_cleanup, ReadSignal, Scope, SignalGet,
+    create_effect, on_cleanup, ReadSignal, Scope, ScopeProperty, SignalGet,
     SignalGetUntracked, SignalStream, SignalWith, SignalWithUntracked,
 };
 use std::fmt::Debug;
@@ -75,7 +75,14 @@ pub fn create_memo<T>(
 where
     T: PartialEq + 'static,
 {
-    cx.runtime.create_memo(f)
+    let (memo, effect) = cx.runtime.create_memo(f);
+    // the memo's value and the effect that computes it are disposed along
+    // with the scope
+    cx.with_scope_property(|prop| {
+        prop.push(ScopeProperty::Signal(memo.0.id));
+        prop.push(ScopeProperty::Effect(effect));
+    });
+    memo
 }
 
 /// An efficient derived reactive value based on other reactive values.
@@ -355,7 +362,6 @@ impl<T> Memo<T>
 where
     T: 'static,
 {
-    #[cfg(feature = "hydrate")]
     pub(crate) fn subscribe(&self) {
         self.0.subscribe()
     }
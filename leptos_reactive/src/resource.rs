@@ -1,22 +1,32 @@
 #![forbid(unsafe_code)]
 use crate::{
     create_effect, create_isomorphic_effect, create_memo, create_signal,
-    queue_microtask,
-    runtime::{with_runtime, RuntimeId},
-    serialization::Serializable,
-    spawn::spawn_local,
-    use_context, Memo, ReadSignal, Scope, ScopeProperty, SignalUpdate,
-    SignalWith, SuspenseContext, WriteSignal,
+    on_cleanup, queue_microtask,
+    runtime::{with_runtime, PinnedFuture, PinnedResolverFuture, RuntimeId},
+    serialization::{
+        decode_resource_payload, encode_resource_payload, Serializable,
+    },
+    spawn::{sleep, spawn_local},
+    use_context, Memo, ReadSignal, Scope, ScopeProperty, SignalGet,
+    SignalGetUntracked, SignalUpdate, SignalWith, SignalWithUntracked,
+    SuspenseContext, WriteSignal,
+};
+use futures::{
+    channel::mpsc::Sender,
+    future::{AbortHandle, FutureExt, Shared},
+    stream::{Abortable, Stream, StreamExt},
 };
 use std::{
-    any::Any,
+    any::{Any, TypeId},
     cell::{Cell, RefCell},
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     fmt::Debug,
     future::Future,
+    hash::Hash,
     marker::PhantomData,
     pin::Pin,
     rc::Rc,
+    time::Duration,
 };
 
 /// Creates [Resource](crate::Resource), which is a signal that reflects the
@@ -62,6 +72,43 @@ use std::{
 /// # }
 /// # }).dispose();
 /// ```
+///
+/// ## Multiple sources
+/// To fetch based on several signals, return them from the `source` as a tuple
+/// (or any other type that is `Clone + PartialEq`). The `source` is memoized,
+/// so the `fetcher` only runs again when the tuple it returns is different
+/// from the previous one, even if the `source` itself reruns because one of
+/// the signals it reads has changed.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// async fn fetch_posts(
+///     user_id: u32,
+///     page: usize,
+///     search: bool,
+/// ) -> Vec<String> {
+///     // pretend we're fetching posts
+///     vec![]
+/// }
+///
+/// let (user_id, set_user_id) = create_signal(cx, 1);
+/// let (page, set_page) = create_signal(cx, 0);
+/// let (query, set_query) = create_signal(cx, "a".to_string());
+///
+/// # if false {
+/// let posts = create_resource(
+///     cx,
+///     move || (user_id.get(), page.get(), !query.with(String::is_empty)),
+///     |(user_id, page, search)| fetch_posts(user_id, page, search),
+/// );
+///
+/// // changes `query`, but not whether we're searching, so doesn't refetch
+/// set_query.set("ab".to_string());
+/// # }
+/// # }).dispose();
+/// ```
+#[track_caller]
 pub fn create_resource<S, T, Fu>(
     cx: Scope,
     source: impl Fn() -> S + 'static,
@@ -105,6 +152,29 @@ pub fn create_resource_with_initial_value<S, T, Fu>(
     fetcher: impl Fn(S) -> Fu + 'static,
     initial_value: Option<T>,
 ) -> Resource<S, T>
+where
+    S: PartialEq + Debug + Clone + 'static,
+    T: Serializable + 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    serializable_resource(
+        cx,
+        source,
+        fetcher,
+        initial_value,
+        None,
+        std::panic::Location::caller(),
+    )
+}
+
+fn serializable_resource<S, T, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+    initial_value: Option<T>,
+    on_loaded: Option<OnLoaded<T>>,
+    defined_at: &'static std::panic::Location<'static>,
+) -> Resource<S, T>
 where
     S: PartialEq + Debug + Clone + 'static,
     T: Serializable + 'static,
@@ -114,6 +184,7 @@ where
     let (value, set_value) = create_signal(cx, initial_value);
 
     let (loading, set_loading) = create_signal(cx, false);
+    let (server_error, set_server_error) = create_signal(cx, None);
 
     let fetcher = Rc::new(move |s| {
         Box::pin(fetcher(s)) as Pin<Box<dyn Future<Output = T>>>
@@ -125,24 +196,33 @@ where
         set_value,
         loading,
         set_loading,
+        server_error,
+        set_server_error,
         source,
         fetcher,
         resolved: Rc::new(Cell::new(resolved)),
         scheduled: Rc::new(Cell::new(false)),
+        canceled: Default::default(),
+        in_flight: Default::default(),
         suspense_contexts: Default::default(),
+        serializers: Default::default(),
         serializable: true,
+        tag: resource_tag(defined_at),
+        on_loaded,
     });
 
     let id = with_runtime(cx.runtime, |runtime| {
         let r = Rc::clone(&r) as Rc<dyn SerializableResource>;
-        runtime.create_serializable_resource(r)
+        let id = runtime.create_serializable_resource(r);
+        runtime.resource_signals.borrow_mut().insert(value.id, id);
+        id
     })
     .expect("tried to create a Resource in a Runtime that has been disposed.");
 
     create_isomorphic_effect(cx, {
         let r = Rc::clone(&r);
         move |_| {
-            load_resource(cx, id, r.clone());
+            load_resource(cx, id, r.clone(), defined_at);
         }
     });
 
@@ -154,7 +234,7 @@ where
         source_ty: PhantomData,
         out_ty: PhantomData,
         #[cfg(debug_assertions)]
-        defined_at: std::panic::Location::caller(),
+        defined_at,
     }
 }
 
@@ -229,6 +309,29 @@ pub fn create_local_resource_with_initial_value<S, T, Fu>(
     fetcher: impl Fn(S) -> Fu + 'static,
     initial_value: Option<T>,
 ) -> Resource<S, T>
+where
+    S: PartialEq + Debug + Clone + 'static,
+    T: 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    local_resource(
+        cx,
+        source,
+        fetcher,
+        initial_value,
+        None,
+        std::panic::Location::caller(),
+    )
+}
+
+fn local_resource<S, T, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+    initial_value: Option<T>,
+    on_loaded: Option<OnLoaded<T>>,
+    defined_at: &'static std::panic::Location<'static>,
+) -> Resource<S, T>
 where
     S: PartialEq + Debug + Clone + 'static,
     T: 'static,
@@ -238,6 +341,7 @@ where
     let (value, set_value) = create_signal(cx, initial_value);
 
     let (loading, set_loading) = create_signal(cx, false);
+    let (server_error, set_server_error) = create_signal(cx, None);
 
     let fetcher = Rc::new(move |s| {
         Box::pin(fetcher(s)) as Pin<Box<dyn Future<Output = T>>>
@@ -249,17 +353,26 @@ where
         set_value,
         loading,
         set_loading,
+        server_error,
+        set_server_error,
         source,
         fetcher,
         resolved: Rc::new(Cell::new(resolved)),
         scheduled: Rc::new(Cell::new(false)),
+        canceled: Default::default(),
+        in_flight: Default::default(),
         suspense_contexts: Default::default(),
+        serializers: Default::default(),
         serializable: false,
+        tag: resource_tag(defined_at),
+        on_loaded,
     });
 
     let id = with_runtime(cx.runtime, |runtime| {
         let r = Rc::clone(&r) as Rc<dyn UnserializableResource>;
-        runtime.create_unserializable_resource(r)
+        let id = runtime.create_unserializable_resource(r);
+        runtime.resource_signals.borrow_mut().insert(value.id, id);
+        id
     })
     .expect("tried to create a Resource in a runtime that has been disposed.");
 
@@ -278,47 +391,893 @@ where
         source_ty: PhantomData,
         out_ty: PhantomData,
         #[cfg(debug_assertions)]
-        defined_at: std::panic::Location::caller(),
+        defined_at,
     }
 }
 
-#[cfg(not(feature = "hydrate"))]
-fn load_resource<S, T>(_cx: Scope, _id: ResourceId, r: Rc<ResourceState<S, T>>)
+/// A [ResourceOptions] callback that's given the `Ok` or `Err` value of a
+/// fetch.
+type ResultCallback<T> = Rc<dyn Fn(&T)>;
+
+/// Options that control how a [Resource](crate::Resource) created with
+/// [create_resource_with_options()] or [create_local_resource_with_options()]
+/// fetches its data, where `V` and `E` are the `Ok` and `Err` types of the
+/// `fetcher`’s [Result].
+pub struct ResourceOptions<V, E> {
+    /// How many times a failed fetch is attempted again before its error is
+    /// stored in the resource.
+    pub retries: u32,
+    /// How long to wait before each retry, given the number of the retry
+    /// (starting from `0`).
+    pub backoff: Rc<dyn Fn(u32) -> Duration>,
+    /// Whether failed fetches are retried while server-side rendering.
+    /// Defaults to `false`, so the server fails fast and sends the error.
+    pub retry_on_server: bool,
+    /// If set, the resource is refetched on this interval for as long as its
+    /// [Scope] is alive. A tick is skipped if a fetch is already in flight.
+    /// Polling only happens in the browser, never during server rendering.
+    pub refetch_interval: Option<Duration>,
+    /// Whether to keep polling while the page is hidden, according to
+    /// `document.visibilityState`. Defaults to `false`.
+    pub refetch_when_hidden: bool,
+    /// Called, untracked, once each fetch resolves to an `Ok`.
+    pub on_resolved: Option<ResultCallback<V>>,
+    /// Called, untracked, once each fetch resolves to an `Err` (after any
+    /// retries).
+    pub on_error: Option<ResultCallback<E>>,
+    /// Whether `on_resolved` and `on_error` are called while server-side
+    /// rendering. Defaults to `false`.
+    pub callbacks_on_server: bool,
+}
+
+impl<V, E> Default for ResourceOptions<V, E> {
+    /// Retries three times, waiting 100ms, 200ms, and 400ms.
+    fn default() -> Self {
+        Self {
+            retries: 3,
+            backoff: Rc::new(|attempt| {
+                Duration::from_millis(100 * 2u64.saturating_pow(attempt))
+            }),
+            retry_on_server: false,
+            refetch_interval: None,
+            refetch_when_hidden: false,
+            on_resolved: None,
+            on_error: None,
+            callbacks_on_server: false,
+        }
+    }
+}
+
+impl<V, E> Clone for ResourceOptions<V, E> {
+    fn clone(&self) -> Self {
+        Self {
+            retries: self.retries,
+            backoff: Rc::clone(&self.backoff),
+            retry_on_server: self.retry_on_server,
+            refetch_interval: self.refetch_interval,
+            refetch_when_hidden: self.refetch_when_hidden,
+            on_resolved: self.on_resolved.clone(),
+            on_error: self.on_error.clone(),
+            callbacks_on_server: self.callbacks_on_server,
+        }
+    }
+}
+
+impl<V, E> Debug for ResourceOptions<V, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResourceOptions")
+            .field("retries", &self.retries)
+            .field("retry_on_server", &self.retry_on_server)
+            .field("refetch_interval", &self.refetch_interval)
+            .field("refetch_when_hidden", &self.refetch_when_hidden)
+            .field("on_resolved", &self.on_resolved.is_some())
+            .field("on_error", &self.on_error.is_some())
+            .field("callbacks_on_server", &self.callbacks_on_server)
+            .finish()
+    }
+}
+
+impl<V, E> ResourceOptions<V, E> {
+    fn retries(&self, cx: Scope) -> u32 {
+        if !cx.is_server() || self.retry_on_server {
+            self.retries
+        } else {
+            0
+        }
+    }
+
+    fn run_callbacks(&self, cx: Scope, res: &Result<V, E>) {
+        if cx.is_server() && !self.callbacks_on_server {
+            return;
+        }
+        match res {
+            Ok(value) => {
+                if let Some(on_resolved) = &self.on_resolved {
+                    on_resolved(value);
+                }
+            }
+            Err(error) => {
+                if let Some(on_error) = &self.on_error {
+                    on_error(error);
+                }
+            }
+        }
+    }
+}
+
+/// Creates a [Resource](crate::Resource) whose `fetcher` returns a [Result],
+/// and which retries the fetch according to the given [ResourceOptions] if it
+/// returns an `Err`.
+///
+/// The resource stays loading (and any `<Suspense/>` that reads it keeps
+/// waiting) while it retries; only the final attempt’s result is stored.
+/// Retrying stops when the `source` changes or the resource’s [Scope] is
+/// disposed.
+///
+/// If [ResourceOptions::refetch_interval] is set, the resource is also
+/// refetched on that interval while its [Scope] is alive. The
+/// [ResourceOptions::on_resolved] and [ResourceOptions::on_error] callbacks
+/// are called when a fetch completes, once its result is stored in the
+/// resource, but not for fetches that were superseded by a newer one.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{rc::Rc, time::Duration};
+/// # create_scope(create_runtime(), |cx| {
+/// async fn fetch_user(id: u32) -> Result<String, String> {
+///     // pretend this sometimes fails
+///     Ok(format!("user {id}"))
+/// }
+///
+/// # if false {
+/// let (id, set_id) = create_signal(cx, 1);
+/// let user = create_resource_with_options(
+///     cx,
+///     id,
+///     fetch_user,
+///     ResourceOptions {
+///         retries: 5,
+///         backoff: Rc::new(|_| Duration::from_secs(1)),
+///         ..Default::default()
+///     },
+/// );
+/// # }
+/// # }).dispose();
+/// ```
+#[track_caller]
+pub fn create_resource_with_options<S, V, E, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+    options: ResourceOptions<V, E>,
+) -> Resource<S, Result<V, E>>
+where
+    S: PartialEq + Debug + Clone + 'static,
+    Result<V, E>: Serializable,
+    V: 'static,
+    E: 'static,
+    Fu: Future<Output = Result<V, E>> + 'static,
+{
+    let refetch_interval = refetch_interval(cx, &options);
+    let (fetcher, on_loaded) = fetcher_with_options(cx, fetcher, options);
+    let resource = serializable_resource(
+        cx,
+        source,
+        fetcher,
+        None,
+        Some(on_loaded),
+        std::panic::Location::caller(),
+    );
+    refetch_interval(resource);
+    resource
+}
+
+/// Creates a _local_ [Resource](crate::Resource) whose `fetcher` returns a
+/// [Result], and which retries the fetch according to the given
+/// [ResourceOptions] if it returns an `Err`.
+///
+/// Unlike [create_resource_with_options()], this [Future] will always run
+/// on the local system and therefore its output type does not need to be
+/// [Serializable].
+#[track_caller]
+pub fn create_local_resource_with_options<S, V, E, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+    options: ResourceOptions<V, E>,
+) -> Resource<S, Result<V, E>>
+where
+    S: PartialEq + Debug + Clone + 'static,
+    V: 'static,
+    E: 'static,
+    Fu: Future<Output = Result<V, E>> + 'static,
+{
+    let refetch_interval = refetch_interval(cx, &options);
+    let (fetcher, on_loaded) = fetcher_with_options(cx, fetcher, options);
+    let resource = local_resource(
+        cx,
+        source,
+        fetcher,
+        None,
+        Some(on_loaded),
+        std::panic::Location::caller(),
+    );
+    refetch_interval(resource);
+    resource
+}
+
+/// Returns a function that starts polling the resource, if the options ask
+/// for it and we're running in the browser.
+fn refetch_interval<S, T, V, E>(
+    cx: Scope,
+    options: &ResourceOptions<V, E>,
+) -> impl FnOnce(Resource<S, T>)
+where
+    S: Clone + 'static,
+    T: 'static,
+{
+    let interval = options.refetch_interval;
+    let when_hidden = options.refetch_when_hidden;
+    move |resource| {
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                // a binary built for both sides doesn't poll on the server
+                if let Some(interval) = interval.filter(|_| !cx.is_server()) {
+                    let (disposed, on_disposed) =
+                        futures::channel::oneshot::channel::<()>();
+                    // dropping the sender also wakes the receiver
+                    on_cleanup(cx, move || drop(disposed));
+                    let loading = resource.loading();
+                    spawn_local(poll_resource(
+                        interval,
+                        sleep,
+                        move || resource.refetch(),
+                        move || loading.get_untracked(),
+                        move || !when_hidden && document_hidden(),
+                        async move {
+                            _ = on_disposed.await;
+                        },
+                    ));
+                }
+            } else {
+                _ = (cx, resource, interval, when_hidden);
+            }
+        }
+    }
+}
+
+/// Calls `refetch` every `interval`, unless a fetch is already in flight or
+/// polling is paused, until `cancelled` resolves. A pending sleep is dropped
+/// as soon as that happens.
+#[cfg(any(feature = "csr", feature = "hydrate", test))]
+async fn poll_resource<Fu>(
+    interval: Duration,
+    sleep: impl Fn(Duration) -> Fu,
+    refetch: impl Fn(),
+    loading: impl Fn() -> bool,
+    paused: impl Fn() -> bool,
+    cancelled: impl Future<Output = ()>,
+) where
+    Fu: Future<Output = ()>,
+{
+    use futures::future::{select, Either};
+
+    futures::pin_mut!(cancelled);
+    loop {
+        let tick = sleep(interval);
+        futures::pin_mut!(tick);
+        if let Either::Right(_) = select(tick, cancelled.as_mut()).await {
+            break;
+        }
+        if !loading() && !paused() {
+            refetch();
+        }
+    }
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+fn document_hidden() -> bool {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .map(|document| {
+            document.visibility_state() == web_sys::VisibilityState::Hidden
+        })
+        .unwrap_or(false)
+}
+
+/// Wraps a fetcher so that each `Future` it creates retries failed attempts,
+/// until it is superseded by a newer `Future` or the scope is disposed. Also
+/// returns the function that runs the callbacks once the result of a `Future`
+/// that wasn't superseded has been stored in the resource.
+fn fetcher_with_options<S, V, E, Fu>(
+    cx: Scope,
+    fetcher: impl Fn(S) -> Fu + 'static,
+    options: ResourceOptions<V, E>,
+) -> (RetryingFetcher<S, V, E>, OnLoaded<Result<V, E>>)
+where
+    S: Clone + 'static,
+    V: 'static,
+    E: 'static,
+    Fu: Future<Output = Result<V, E>> + 'static,
+{
+    let fetcher = Rc::new(fetcher);
+    let generation = Rc::new(Cell::new(0_usize));
+    on_cleanup(cx, {
+        let generation = Rc::clone(&generation);
+        move || generation.set(generation.get().wrapping_add(1))
+    });
+
+    // whether the result that's being stored is the latest one
+    let latest = Rc::new(Cell::new(false));
+    let on_loaded = Rc::new({
+        let latest = Rc::clone(&latest);
+        let options = options.clone();
+        move |res: &Result<V, E>| {
+            if latest.take() {
+                cx.untrack(|| options.run_callbacks(cx, res));
+            }
+        }
+    });
+
+    let fetcher = move |source: S| {
+        let fetcher = Rc::clone(&fetcher);
+        let generation = Rc::clone(&generation);
+        let latest = Rc::clone(&latest);
+        let options = options.clone();
+        generation.set(generation.get().wrapping_add(1));
+        let current = generation.get();
+        // the first attempt starts right away, like any other fetch
+        let mut fut = fetcher(source.clone());
+
+        Box::pin(async move {
+            let mut attempt = 0;
+            let res = loop {
+                let res = fut.await;
+                if res.is_ok()
+                    || attempt >= options.retries(cx)
+                    || generation.get() != current
+                {
+                    break res;
+                }
+                sleep((options.backoff)(attempt)).await;
+                // the source changed or the resource was disposed meanwhile
+                if generation.get() != current {
+                    break res;
+                }
+                attempt += 1;
+                fut = fetcher(source.clone());
+            };
+            // superseded fetches don't run the callbacks
+            latest.set(generation.get() == current);
+            res
+        }) as PinnedFuture<Result<V, E>>
+    };
+    (Box::new(fetcher), on_loaded)
+}
+
+/// A fetcher returned by [fetcher_with_options].
+type RetryingFetcher<S, V, E> = Box<dyn Fn(S) -> PinnedFuture<Result<V, E>>>;
+
+/// Starts loading the data for a [Resource](crate::Resource) created with
+/// [create_cached_resource()], before that resource exists.
+///
+/// The `fetcher` is called with the `key` right away, but nothing reads the
+/// result: this doesn’t subscribe to anything or register with any
+/// `<Suspense/>`. The next cached resource whose `source` returns the same
+/// `key`, and that’s created with the same `fetcher` (the same function, or a
+/// clone of the same closure), takes the prefetched data and uses it instead
+/// of calling its `fetcher`. If the key is already being prefetched with that
+/// `fetcher`, this does nothing. Prefetched data that no resource took is
+/// dropped when `cx` is disposed.
+///
+/// This is useful to start loading the data for a route, e.g., when hovering a
+/// link to it.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// async fn fetch_user(id: u32) -> String {
+///     // pretend we're fetching a user
+///     format!("user {id}")
+/// }
+///
+/// # if false {
+/// // e.g., on hovering a link to /users/1
+/// prefetch_resource(cx, 1, fetch_user);
+///
+/// // later, after navigating to /users/1
+/// let user = create_cached_resource(cx, || 1, fetch_user);
+/// # }
+/// # }).dispose();
+/// ```
+pub fn prefetch_resource<S, T, F, Fu>(cx: Scope, key: S, fetcher: F)
+where
+    S: Hash + Eq + Clone + 'static,
+    T: Clone + 'static,
+    F: FnOnce(S) -> Fu + 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    let fut = with_prefetched::<F, S, T, _>(cx, |prefetched| {
+        if prefetched.contains_key(&key) {
+            return None;
+        }
+        let fut = (Box::pin(fetcher(key.clone()))
+            as Pin<Box<dyn Future<Output = T>>>)
+            .shared();
+        prefetched.insert(key.clone(), fut.clone());
+        Some(fut)
+    })
+    .flatten();
+
+    if let Some(fut) = fut {
+        // drops the data with `cx`, unless a resource took it or it was
+        // prefetched again meanwhile
+        let weak = fut.downgrade();
+        on_cleanup(cx, move || {
+            with_prefetched::<F, S, T, _>(cx, |prefetched| {
+                let current = prefetched.get(&key);
+                let prefetched_here = weak
+                    .and_then(|weak| weak.upgrade())
+                    .zip(current)
+                    .is_some_and(|(fut, current)| fut.ptr_eq(current));
+                if prefetched_here {
+                    prefetched.remove(&key);
+                }
+            });
+        });
+
+        // drive the future, so it's (hopefully) done by the time it's needed
+        spawn_local(fut.map(drop));
+    }
+}
+
+/// Creates a [Resource](crate::Resource) that uses data loaded by
+/// [prefetch_resource()] for the same `source`, rather than calling its
+/// `fetcher`, if there is any.
+///
+/// If the prefetched data has already loaded, the resource starts out resolved
+/// with it. Otherwise, it is pending until the prefetch finishes. Each
+/// prefetched value is only used once: later fetches (e.g., after the `source`
+/// changes or a [Resource::refetch]) call the `fetcher` as usual.
+#[track_caller]
+pub fn create_cached_resource<S, T, F, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: F,
+) -> Resource<S, T>
+where
+    S: PartialEq + Hash + Eq + Debug + Clone + 'static,
+    T: Serializable + Clone + 'static,
+    F: Fn(S) -> Fu + 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    let key = cx.untrack(&source);
+    let initial_value = with_prefetched::<F, S, T, _>(cx, |prefetched| {
+        prefetched.get(&key).and_then(|fut| fut.peek().cloned())
+    })
+    .flatten();
+
+    create_resource_with_initial_value(
+        cx,
+        source,
+        move |source| {
+            let prefetched = with_prefetched::<F, S, T, _>(cx, |prefetched| {
+                prefetched.remove(&source)
+            })
+            .flatten();
+            match prefetched {
+                Some(fut) => Box::pin(fut) as Pin<Box<dyn Future<Output = T>>>,
+                None => Box::pin(fetcher(source)),
+            }
+        },
+        initial_value,
+    )
+}
+
+type PrefetchedResources<S, T> =
+    HashMap<S, Shared<Pin<Box<dyn Future<Output = T>>>>>;
+
+// the data prefetched with a `fetcher` of type `F`, which tells apart
+// fetchers with the same key and output
+fn with_prefetched<F, S, T, U>(
+    cx: Scope,
+    f: impl FnOnce(&mut PrefetchedResources<S, T>) -> U,
+) -> Option<U>
+where
+    F: 'static,
+    S: Hash + Eq + 'static,
+    T: 'static,
+{
+    with_runtime(cx.runtime, |runtime| {
+        let mut prefetched = runtime.prefetched_resources.borrow_mut();
+        let prefetched = prefetched
+            .entry(TypeId::of::<F>())
+            .or_insert_with(|| Box::<PrefetchedResources<S, T>>::default())
+            .downcast_mut::<PrefetchedResources<S, T>>()
+            .expect("prefetched resources stored with the wrong type");
+        f(prefetched)
+    })
+    .ok()
+}
+
+/// Creates a [Resource](crate::Resource) that is driven by a [Stream] of values,
+/// rather than by a single [Future].
+///
+/// Takes a `fetcher` function that generates a [Stream] when called and a
+/// `source` signal that provides the argument for the `fetcher`. Each item
+/// yielded by the stream replaces the current value of the resource with
+/// `Some(item)`. The first item resolves the resource (so any `<Suspense/>` reading it can show its
+/// children), and later items are ordinary updates. Whenever the value of the
+/// `source` changes, the previous stream is dropped and a new one is created.
+/// The stream is also dropped when the resource’s [Scope] is disposed.
+///
+/// When server-side rendering, only the first item is awaited and serialized
+/// to the client; the stream is then dropped. Once the client has hydrated the
+/// server’s value, it opens its own stream and continues from there.
+///
+/// If the stream ends without yielding any items, the resource resolves to
+/// `None`.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// # // like the other resource examples, this can't run outside a browser
+/// # // or a Tokio runtime
+/// # if false {
+/// // a stream of values, e.g., from server-sent events
+/// fn ticks(start: u32) -> impl futures::Stream<Item = u32> {
+///     futures::stream::iter(start..start + 3)
+/// }
+///
+/// let (start, set_start) = create_signal(cx, 0);
+/// let ticker = create_stream_resource(cx, start, ticks);
+/// # }
+/// # }).dispose();
+/// ```
+#[track_caller]
+pub fn create_stream_resource<S, T, St>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> St + 'static,
+) -> Resource<S, Option<T>>
 where
     S: PartialEq + Debug + Clone + 'static,
     T: 'static,
+    Option<T>: Serializable,
+    St: Stream<Item = T> + 'static,
+{
+    let fetcher = Rc::new(move |source| fetcher(source).map(Some));
+    let state = Rc::new(StreamState::<Option<T>>::default());
+
+    let resource = create_resource(cx, source, {
+        let fetcher = Rc::clone(&fetcher);
+        let state = Rc::clone(&state);
+        move |source| {
+            state.started.set(true);
+            let mut stream = Box::pin(state.start(fetcher(source)));
+            let state = Rc::clone(&state);
+            async move {
+                match stream.next().await {
+                    Some(first) => {
+                        state.drive(cx, stream);
+                        first
+                    }
+                    // the stream was empty, or has been cancelled, in which
+                    // case this fetch has been superseded and isn't stored
+                    None => None,
+                }
+            }
+        }
+    });
+
+    let (set_value, loading, source) = with_runtime(cx.runtime, |runtime| {
+        runtime.resource(resource.id, |r: &ResourceState<S, Option<T>>| {
+            (r.set_value, r.loading, r.source)
+        })
+    })
+    .expect("tried to create a Resource in a Runtime that has been disposed.");
+    state.set_value.set(Some(set_value));
+
+    // if the value came from the server, the stream hasn't been opened on
+    // this side yet, so pick it back up once that value has arrived
+    create_effect(cx, {
+        let state = Rc::clone(&state);
+        move |_| {
+            if !state.started.get() && !loading.get() {
+                state.started.set(true);
+                let stream = state.start(fetcher(source.get_untracked()));
+                state.drive(cx, Box::pin(stream));
+            }
+        }
+    });
+
+    on_cleanup(cx, move || state.cancel());
+
+    resource
+}
+
+/// Tracks the stream that is currently driving a stream resource.
+struct StreamState<T>
+where
+    T: 'static,
+{
+    set_value: Cell<Option<WriteSignal<Option<T>>>>,
+    abort_handle: RefCell<Option<AbortHandle>>,
+    started: Cell<bool>,
+}
+
+impl<T> Default for StreamState<T> {
+    fn default() -> Self {
+        Self {
+            set_value: Cell::new(None),
+            abort_handle: Default::default(),
+            started: Cell::new(false),
+        }
+    }
+}
+
+impl<T> StreamState<T> {
+    /// Makes the given stream the current one, cancelling the previous stream.
+    fn start<St>(&self, stream: St) -> Abortable<St>
+    where
+        St: Stream<Item = T>,
+    {
+        let (stream, abort_handle) = futures::stream::abortable(stream);
+        if let Some(prev) = self.abort_handle.replace(Some(abort_handle)) {
+            prev.abort();
+        }
+        stream
+    }
+
+    /// Cancels the current stream, which drops it.
+    fn cancel(&self) {
+        if let Some(abort_handle) = self.abort_handle.take() {
+            abort_handle.abort();
+        }
+    }
+
+    /// Applies any further items from the stream to the resource's value.
+    fn drive<St>(&self, cx: Scope, mut stream: Pin<Box<St>>)
+    where
+        St: Stream<Item = T> + ?Sized + 'static,
+    {
+        // only the first item is sent from the server
+        if cx.is_server() {
+            return;
+        }
+        let set_value = self.set_value.get();
+        spawn_local(async move {
+            while let Some(value) = stream.next().await {
+                if let Some(set_value) = set_value {
+                    set_value.update(|n| *n = Some(value));
+                }
+            }
+        })
+    }
+}
+
+/// Identifies where a resource was created, so that serialized data can't be
+/// deserialized into a different resource if resources were created in a
+/// different order on the server and the client. This is the hydration key
+/// at that point, if something [tags resources](tag_resources_with) with it,
+/// and the line and column of the call, which are the same in the server's
+/// and the browser's build.
+fn resource_tag(defined_at: &'static std::panic::Location<'static>) -> u64 {
+    let key = RESOURCE_KEY.get().map(|key| key()).unwrap_or_default();
+    let position =
+        format!("{key}@{}:{}", defined_at.line(), defined_at.column());
+    // FNV-1a, which gives the same result on the server and in the browser
+    position.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Sent in place of a resource's serialized data when the server gave up
+/// waiting for it, so that the client loads it instead. It has no
+/// [resource_tag], so it can't be mistaken for data.
+pub(crate) const UNRESOLVED_RESOURCE: &str = "unresolved";
+
+/// Prefixes serialized resource data with the [resource_tag] of the resource.
+fn tag_serialized(tag: u64, data: String) -> String {
+    format!("{tag:016x}:{data}")
+}
+
+/// Strips the [resource_tag] from serialized resource data, returning `None`
+/// if it was serialized for a resource created somewhere else.
+fn untag_serialized(tag: u64, data: &str) -> Option<&str> {
+    let (found, data) = data.split_once(':')?;
+    (u64::from_str_radix(found, 16).ok() == Some(tag)).then_some(data)
+}
+
+/// Deserializes the payload the server sent for a resource. Returns `None` if
+/// it was serialized for a resource created somewhere else, and an error
+/// message if the server failed to load or serialize it, or it can't be
+/// deserialized.
+fn deserialize_payload<T: Serializable>(
+    tag: u64,
+    payload: &str,
+) -> Option<Result<T, String>> {
+    match decode_resource_payload(payload) {
+        Ok(data) => untag_serialized(tag, data)
+            .map(|data| T::de(data).map_err(|e| e.to_string())),
+        Err(message) => Some(Err(message.to_string())),
+    }
+}
+
+#[cfg(feature = "hydrate")]
+fn warn_position_mismatch(id: ResourceId) {
+    crate::console_warn(&format!(
+        "[Resource] The data sent from the server for {id:?} was serialized \
+         for a resource created somewhere else, so it will be loaded on the \
+         client instead. This usually means that resources were created in a \
+         different order on the server and the client, e.g., because one is \
+         created conditionally."
+    ));
+}
+
+#[cfg(not(feature = "hydrate"))]
+fn load_resource<S, T>(
+    cx: Scope,
+    id: ResourceId,
+    r: Rc<ResourceState<S, T>>,
+    defined_at: &'static std::panic::Location<'static>,
+) where
+    S: PartialEq + Debug + Clone + 'static,
+    T: Serializable + 'static,
 {
     SUPPRESS_RESOURCE_LOAD.with(|s| {
         if !s.get() {
+            if load_injected(cx, id, &r) {
+                return;
+            }
+            #[cfg(not(feature = "csr"))]
+            if load_from_previous_pass(cx, id, &r, defined_at) {
+                return;
+            }
+            #[cfg(feature = "csr")]
+            let _ = defined_at;
+
             r.load(false)
         }
     });
 }
 
-#[cfg(feature = "hydrate")]
-fn load_resource<S, T>(cx: Scope, id: ResourceId, r: Rc<ResourceState<S, T>>)
+/// Sets the resource to the value given to
+/// [Scope::inject_serialized_resource] before it was created, if any, and
+/// returns whether it did.
+#[cfg(not(feature = "hydrate"))]
+fn load_injected<S, T>(
+    cx: Scope,
+    id: ResourceId,
+    r: &Rc<ResourceState<S, T>>,
+) -> bool
 where
     S: PartialEq + Debug + Clone + 'static,
     T: Serializable + 'static,
+{
+    let data = with_runtime(cx.runtime, |runtime| {
+        runtime
+            .shared_context
+            .borrow_mut()
+            .resolved_resources
+            .remove(&id)
+    })
+    .ok()
+    .flatten();
+
+    match data {
+        Some(data) if r.inject(id, &data) => {
+            // for reactivity
+            r.source.subscribe();
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Sets the resource to the value it resolved to in an earlier render pass,
+/// if [Scope::resolve_all_resources] found one, and returns whether it did.
+///
+/// Resources are matched by where they were created and the value of their
+/// source, because new resources may be created in between them in later
+/// passes.
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+fn load_from_previous_pass<S, T>(
+    cx: Scope,
+    id: ResourceId,
+    r: &Rc<ResourceState<S, T>>,
+    defined_at: &'static std::panic::Location<'static>,
+) -> bool
+where
+    S: PartialEq + Debug + Clone + 'static,
+    T: Serializable + 'static,
+{
+    let key = format!("{defined_at}|{:?}", r.source.get_untracked());
+    let value = with_runtime(cx.runtime, |runtime| {
+        let mut context = runtime.shared_context.borrow_mut();
+        let value = context
+            .resolved_by_key
+            .get(&key)
+            .and_then(|data| deserialize_payload::<T>(r.tag, data))
+            .and_then(Result::ok);
+        context.resource_keys.insert(id, key);
+        if value.is_none() {
+            context.unresolved_loads += 1;
+        }
+        value
+    })
+    .ok()
+    .flatten();
+
+    match value {
+        Some(value) => {
+            r.set_resolved(value);
+
+            // for reactivity
+            r.source.subscribe();
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(feature = "hydrate")]
+fn load_resource<S, T>(
+    cx: Scope,
+    id: ResourceId,
+    r: Rc<ResourceState<S, T>>,
+    _defined_at: &'static std::panic::Location<'static>,
+) where
+    S: PartialEq + Debug + Clone + 'static,
+    T: Serializable + 'static,
 {
     use wasm_bindgen::{JsCast, UnwrapThrowExt};
 
     _ = with_runtime(cx.runtime, |runtime| {
         let mut context = runtime.shared_context.borrow_mut();
+        // while hydrating islands, the server's ID for this resource is the
+        // next one it handed over, if any
+        let id = match &mut context.island_resource_ids {
+            Some(ids) => match ids.pop_front() {
+                Some(id) => id,
+                None => {
+                    drop(context);
+                    r.load(false);
+                    return;
+                }
+            },
+            None => id,
+        };
         if let Some(data) = context.resolved_resources.remove(&id) {
             // The server already sent us the serialized resource value, so
             // deserialize & set it now
             context.pending_resources.remove(&id); // no longer pending
-            r.resolved.set(true);
-
-            let res = T::de(&data)
-                .expect_throw("could not deserialize Resource JSON");
-
-            r.set_value.update(|n| *n = Some(res));
-            r.set_loading.update(|n| *n = false);
 
-            // for reactivity
-            r.source.subscribe();
+            if data == UNRESOLVED_RESOURCE {
+                // the server gave up waiting for it
+                drop(context);
+                r.load(false);
+            } else if let Some(res) = deserialize_payload::<T>(r.tag, &data) {
+                match res {
+                    Ok(res) => {
+                        r.resolved.set(true);
+                        r.set_value.update(|n| *n = Some(res));
+                        r.set_loading.update(|n| *n = false);
+
+                        // for reactivity
+                        r.source.subscribe();
+                    }
+                    Err(message) => {
+                        drop(context);
+                        r.load_after_server_error(id, message);
+                    }
+                }
+            } else {
+                // the data was meant for a different resource
+                warn_position_mismatch(id);
+                drop(context);
+                r.load(false);
+            }
         } else if context.pending_resources.remove(&id) {
             // We're still waiting for the resource, add a "resolver" closure so
             // that it will be set as soon as the server sends the serialized
@@ -326,15 +1285,29 @@ where
             r.set_loading.update(|n| *n = true);
 
             let resolve = {
-                let resolved = r.resolved.clone();
-                let set_value = r.set_value;
-                let set_loading = r.set_loading;
+                let r = Rc::clone(&r);
                 move |res: String| {
-                    let res = T::de(&res)
-                        .expect_throw("could not deserialize Resource JSON");
-                    resolved.set(true);
-                    set_value.update(|n| *n = Some(res));
-                    set_loading.update(|n| *n = false);
+                    if res == UNRESOLVED_RESOURCE {
+                        // the server gave up waiting for it
+                        r.load(false);
+                        return;
+                    }
+                    let res = match deserialize_payload::<T>(r.tag, &res) {
+                        Some(Ok(res)) => res,
+                        Some(Err(message)) => {
+                            r.load_after_server_error(id, message);
+                            return;
+                        }
+                        None => {
+                            // the data was meant for a different resource
+                            warn_position_mismatch(id);
+                            r.load(false);
+                            return;
+                        }
+                    };
+                    r.resolved.set(true);
+                    r.set_value.update(|n| *n = Some(res));
+                    r.set_loading.update(|n| *n = false);
                 }
             };
             let resolve = wasm_bindgen::closure::Closure::wrap(
@@ -370,6 +1343,14 @@ where
     S: Clone + 'static,
     T: 'static,
 {
+    /// The runtime this resource was created in. In debug builds on the server,
+    /// this panics if a scope or effect from another runtime is running.
+    fn runtime(&self) -> RuntimeId {
+        #[cfg(debug_assertions)]
+        crate::runtime::check_runtime(self.runtime, self.defined_at);
+        self.runtime
+    }
+
     /// Clones and returns the current value of the resource ([Option::None] if the
     /// resource is still pending). Also subscribes the running effect to this
     /// resource.
@@ -380,7 +1361,7 @@ where
     where
         T: Clone,
     {
-        with_runtime(self.runtime, |runtime| {
+        with_runtime(self.runtime(), |runtime| {
             runtime.resource(self.id, |resource: &ResourceState<S, T>| {
                 resource.read(cx)
             })
@@ -397,7 +1378,7 @@ where
     /// If you want to get the value by cloning it, you can use
     /// [Resource::read].
     pub fn with<U>(&self, cx: Scope, f: impl FnOnce(&T) -> U) -> Option<U> {
-        with_runtime(self.runtime, |runtime| {
+        with_runtime(self.runtime(), |runtime| {
             runtime.resource(self.id, |resource: &ResourceState<S, T>| {
                 resource.with(cx, f)
             })
@@ -408,7 +1389,7 @@ where
 
     /// Returns a signal that indicates whether the resource is currently loading.
     pub fn loading(&self) -> ReadSignal<bool> {
-        with_runtime(self.runtime, |runtime| {
+        with_runtime(self.runtime(), |runtime| {
             runtime.resource(self.id, |resource: &ResourceState<S, T>| {
                 resource.loading
             })
@@ -419,26 +1400,55 @@ where
         )
     }
 
+    /// The error the server sent instead of this resource's value, if loading
+    /// or serializing it failed there. The resource is then loaded on the
+    /// client instead.
+    pub fn server_error(&self) -> ReadSignal<Option<String>> {
+        with_runtime(self.runtime(), |runtime| {
+            runtime.resource(self.id, |resource: &ResourceState<S, T>| {
+                resource.server_error
+            })
+        })
+        .expect(
+            "tried to call Resource::server_error() in a runtime that has \
+             already been disposed.",
+        )
+    }
+
     /// Re-runs the async function with the current source data.
     pub fn refetch(&self) {
-        _ = with_runtime(self.runtime, |runtime| {
+        _ = with_runtime(self.runtime(), |runtime| {
             runtime.resource(self.id, |resource: &ResourceState<S, T>| {
                 resource.refetch()
             })
         });
     }
 
+    /// Updates the value of the resource in place, as if it had loaded it,
+    /// e.g., to show the result of a change before the resource is
+    /// refetched. The value is [Option::None] while the resource is
+    /// pending.
+    pub fn update(&self, f: impl FnOnce(&mut Option<T>)) {
+        _ = with_runtime(self.runtime(), |runtime| {
+            runtime.resource(self.id, |resource: &ResourceState<S, T>| {
+                resource.set_value.update(f)
+            })
+        });
+    }
+
     /// Returns a [std::future::Future] that will resolve when the resource has loaded,
     /// yield its [ResourceId] and a JSON string.
+    ///
+    /// Resolves to `None` if the resource is disposed before it loads.
     #[cfg(any(feature = "ssr", doc))]
     pub async fn to_serialization_resolver(
         &self,
         cx: Scope,
-    ) -> (ResourceId, String)
+    ) -> Option<(ResourceId, String)>
     where
         T: Serializable,
     {
-        with_runtime(self.runtime, |runtime| {
+        with_runtime(self.runtime(), |runtime| {
             runtime.resource(self.id, |resource: &ResourceState<S, T>| {
                 resource.to_serialization_resolver(cx, self.id)
             })
@@ -548,15 +1558,36 @@ where
     set_value: WriteSignal<Option<T>>,
     pub loading: ReadSignal<bool>,
     set_loading: WriteSignal<bool>,
+    server_error: ReadSignal<Option<String>>,
+    set_server_error: WriteSignal<Option<String>>,
     source: Memo<S>,
     #[allow(clippy::type_complexity)]
     fetcher: Rc<dyn Fn(S) -> Pin<Box<dyn Future<Output = T>>>>,
     resolved: Rc<Cell<bool>>,
     scheduled: Rc<Cell<bool>>,
+    /// Bumped to cancel the fetches that are in flight, whose results are
+    /// then ignored.
+    canceled: Rc<Cell<usize>>,
+    /// The number of fetches that are in flight.
+    in_flight: Rc<Cell<usize>>,
     suspense_contexts: Rc<RefCell<HashSet<SuspenseContext>>>,
     serializable: bool,
+    /// The [resource_tag] its serialized data is tagged with.
+    tag: u64,
+    /// Senders for any serialization resolvers that are waiting for a value.
+    /// The resolvers only hold weak references, so these are dropped (and the
+    /// resolvers skip this resource) when the resource is disposed.
+    serializers: RefCell<Vec<SerializerSender>>,
+    /// Called with the result of each fetch once it's stored.
+    on_loaded: Option<OnLoaded<T>>,
 }
 
+/// Called with the value a fetch stored in a resource.
+type OnLoaded<T> = Rc<dyn Fn(&T)>;
+
+/// Sends a resource's serialized data to a serialization resolver.
+type SerializerSender = Rc<RefCell<Sender<(ResourceId, String)>>>;
+
 impl<S, T> ResourceState<S, T>
 where
     S: Clone + 'static,
@@ -614,6 +1645,22 @@ where
         self.load(true);
     }
 
+    /// Registers a `<Suspense/>` boundary that reads this resource indirectly,
+    /// i.e., without providing a [Scope] that has access to the context.
+    fn track_suspense(&self, context: SuspenseContext) {
+        let has_value = self
+            .value
+            .try_with_untracked(|n| n.is_some())
+            .unwrap_or(false);
+        if let Ok(ref mut contexts) = self.suspense_contexts.try_borrow_mut() {
+            // same as in `with()`: if this is the first time we've seen this
+            // context and we're still loading, it hasn't been counted yet
+            if contexts.insert(context) && !has_value {
+                context.increment(self.serializable);
+            }
+        }
+    }
+
     fn load(&self, refetching: bool) {
         // doesn't refetch if already refetching
         if refetching && self.scheduled.get() {
@@ -645,13 +1692,25 @@ where
 
             // run the Future
             let serializable = self.serializable;
+            self.in_flight.set(self.in_flight.get() + 1);
             spawn_local({
                 let resolved = self.resolved.clone();
                 let set_value = self.set_value;
                 let set_loading = self.set_loading;
+                let canceled = Rc::clone(&self.canceled);
+                let generation = canceled.get();
+                let in_flight = Rc::clone(&self.in_flight);
+                let value = self.value;
+                let on_loaded = self.on_loaded.clone();
                 async move {
                     let res = fut.await;
 
+                    // the value was set some other way in the meantime
+                    if canceled.get() != generation {
+                        return;
+                    }
+                    in_flight.set(in_flight.get().saturating_sub(1));
+
                     resolved.set(true);
 
                     set_value.update(|n| *n = Some(res));
@@ -660,61 +1719,120 @@ where
                     for suspense_context in suspense_contexts.borrow().iter() {
                         suspense_context.decrement(serializable);
                     }
+
+                    if let Some(on_loaded) = on_loaded {
+                        value.with_untracked(|value| {
+                            if let Some(value) = value {
+                                on_loaded(value);
+                            }
+                        });
+                    }
                 }
             })
         });
     }
 
+    /// Sets the resource to the given value, canceling any fetches that are
+    /// still in flight.
+    fn set_resolved(&self, value: T) {
+        self.canceled.set(self.canceled.get() + 1);
+        for _ in 0..self.in_flight.replace(0) {
+            for suspense_context in self.suspense_contexts.borrow().iter() {
+                suspense_context.decrement(self.serializable);
+            }
+        }
+
+        self.resolved.set(true);
+        self.set_value.update(|n| *n = Some(value));
+        self.set_loading.update(|n| *n = false);
+    }
+
+    /// Records the error the server sent instead of a value, and loads the
+    /// resource locally instead.
+    fn load_after_server_error(&self, id: ResourceId, message: String) {
+        crate::console_warn(&format!(
+            "[Resource] The server failed to load {id:?}, so it will be \
+             loaded on the client instead: {message}"
+        ));
+        self.set_server_error.update(|error| *error = Some(message));
+        self.load(false);
+    }
+
+    /// Returns a future that resolves to the serialized value of the resource
+    /// once it has loaded, or to `None` if it is disposed before then.
     pub fn resource_to_serialization_resolver(
         &self,
         cx: Scope,
         id: ResourceId,
-    ) -> std::pin::Pin<Box<dyn futures::Future<Output = (ResourceId, String)>>>
+    ) -> PinnedResolverFuture
     where
         T: Serializable,
     {
-        use futures::StreamExt;
-
         let (tx, mut rx) = futures::channel::mpsc::channel(1);
-        let value = self.value;
+        let tx = Rc::new(RefCell::new(tx));
+        let weak_tx = Rc::downgrade(&tx);
+        self.serializers.borrow_mut().push(tx);
+
+        let (value, tag) = (self.value, self.tag);
         create_isomorphic_effect(cx, move |_| {
-            value.with({
-                let mut tx = tx.clone();
+            // the resource has been disposed
+            let Some(tx) = weak_tx.upgrade() else {
+                return;
+            };
+            _ = value.try_with({
+                let mut tx = tx.borrow().clone();
                 move |value| {
                     if let Some(value) = value.as_ref() {
-                        tx.try_send((
-                            id,
-                            value.ser().expect("could not serialize Resource"),
-                        ))
-                        .expect(
+                        let data =
+                            value.ser().map(|data| tag_serialized(tag, data));
+                        let payload = match &data {
+                            Ok(data) => encode_resource_payload(Ok(data)),
+                            Err(e) => {
+                                encode_resource_payload(Err(&e.to_string()))
+                            }
+                        };
+                        tx.try_send((id, payload)).expect(
                             "failed while trying to write to Resource \
                              serializer",
                         );
                     }
                 }
-            })
+            });
         });
-        Box::pin(async move {
-            rx.next()
-                .await
-                .expect("failed while trying to resolve Resource serializer")
-        })
+        Box::pin(async move { rx.next().await })
     }
 }
 
+#[derive(Clone)]
 pub(crate) enum AnyResource {
     Unserializable(Rc<dyn UnserializableResource>),
     Serializable(Rc<dyn SerializableResource>),
 }
 
+impl AnyResource {
+    pub(crate) fn track_suspense(&self, context: SuspenseContext) {
+        match self {
+            AnyResource::Unserializable(res) => res.track_suspense(context),
+            AnyResource::Serializable(res) => res.track_suspense(context),
+        }
+    }
+}
+
 pub(crate) trait SerializableResource {
     fn as_any(&self) -> &dyn Any;
 
+    fn track_suspense(&self, context: SuspenseContext);
+
     fn to_serialization_resolver(
         &self,
         cx: Scope,
         id: ResourceId,
-    ) -> Pin<Box<dyn Future<Output = (ResourceId, String)>>>;
+    ) -> PinnedResolverFuture;
+
+    /// Sets the resource from its serialized value, or loads it again if the
+    /// server sent an error instead, returning `false` if it was serialized
+    /// for a resource created somewhere else.
+    fn inject(&self, id: ResourceId, data: &str) -> bool;
 }
 
 impl<S, T> SerializableResource for ResourceState<S, T>
@@ -726,11 +1844,29 @@ where
         self
     }
 
+    fn track_suspense(&self, context: SuspenseContext) {
+        ResourceState::track_suspense(self, context)
+    }
+
+    fn inject(&self, id: ResourceId, data: &str) -> bool {
+        match deserialize_payload::<T>(self.tag, data) {
+            Some(Ok(value)) => {
+                self.set_resolved(value);
+                true
+            }
+            Some(Err(message)) => {
+                self.load_after_server_error(id, message);
+                true
+            }
+            None => false,
+        }
+    }
+
     fn to_serialization_resolver(
         &self,
         cx: Scope,
         id: ResourceId,
-    ) -> Pin<Box<dyn Future<Output = (ResourceId, String)>>> {
+    ) -> PinnedResolverFuture {
         let fut = self.resource_to_serialization_resolver(cx, id);
         Box::pin(fut)
     }
@@ -738,19 +1874,173 @@ where
 
 pub(crate) trait UnserializableResource {
     fn as_any(&self) -> &dyn Any;
+
+    fn track_suspense(&self, context: SuspenseContext);
 }
 
-impl<S, T> UnserializableResource for ResourceState<S, T> {
+impl<S, T> UnserializableResource for ResourceState<S, T>
+where
+    S: Clone,
+{
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn track_suspense(&self, context: SuspenseContext) {
+        ResourceState::track_suspense(self, context)
+    }
 }
 
 thread_local! {
     static SUPPRESS_RESOURCE_LOAD: Cell<bool> = Cell::new(false);
 }
 
+// shared by every thread, so that a resource is tagged the same way wherever
+// it's created, even on a thread that hasn't rendered anything yet
+static RESOURCE_KEY: std::sync::OnceLock<fn() -> String> =
+    std::sync::OnceLock::new();
+
 #[doc(hidden)]
 pub fn suppress_resource_load(suppress: bool) {
     SUPPRESS_RESOURCE_LOAD.with(|w| w.set(suppress));
 }
+
+/// Makes resources include the key returned by `key` in the tag their
+/// serialized data is sent with, so that data is only bound to a resource
+/// created in the same place on the client. The renderer calls this with the
+/// current hydration key, which is the same on the server and the client,
+/// before it runs a view. Only the first `key` is used.
+#[doc(hidden)]
+pub fn tag_resources_with(key: fn() -> String) {
+    _ = RESOURCE_KEY.set(key);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{poll_resource, tag_serialized, untag_serialized};
+    use futures::{channel::oneshot, executor::LocalPool, task::LocalSpawnExt};
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+        time::Duration,
+    };
+
+    /// A timer whose sleeps only finish when the test advances it.
+    #[derive(Clone, Default)]
+    struct MockTimer(Rc<RefCell<Vec<oneshot::Sender<()>>>>);
+
+    impl MockTimer {
+        fn sleep(&self, _: Duration) -> impl std::future::Future<Output = ()> {
+            let (tx, rx) = oneshot::channel();
+            self.0.borrow_mut().push(tx);
+            async move {
+                _ = rx.await;
+            }
+        }
+
+        fn advance(&self, pool: &mut LocalPool) {
+            for tx in self.0.borrow_mut().drain(..) {
+                _ = tx.send(());
+            }
+            pool.run_until_stalled();
+        }
+    }
+
+    #[test]
+    fn polls_on_each_tick_unless_loading_or_cancelled() {
+        let mut pool = LocalPool::new();
+        let timer = MockTimer::default();
+        let fetches = Rc::new(Cell::new(0));
+        let loading = Rc::new(Cell::new(false));
+        let (cancel, cancelled) = oneshot::channel::<()>();
+
+        pool.spawner()
+            .spawn_local(poll_resource(
+                Duration::from_secs(30),
+                {
+                    let timer = timer.clone();
+                    move |duration| timer.sleep(duration)
+                },
+                {
+                    let fetches = Rc::clone(&fetches);
+                    move || fetches.set(fetches.get() + 1)
+                },
+                {
+                    let loading = Rc::clone(&loading);
+                    move || loading.get()
+                },
+                || false,
+                async move {
+                    _ = cancelled.await;
+                },
+            ))
+            .unwrap();
+        pool.run_until_stalled();
+        assert_eq!(fetches.get(), 0);
+
+        timer.advance(&mut pool);
+        timer.advance(&mut pool);
+        assert_eq!(fetches.get(), 2);
+
+        // skips ticks while a fetch is in flight
+        loading.set(true);
+        timer.advance(&mut pool);
+        assert_eq!(fetches.get(), 2);
+        loading.set(false);
+        timer.advance(&mut pool);
+        assert_eq!(fetches.get(), 3);
+
+        // stops once the scope is disposed, without waiting for the sleep
+        _ = cancel.send(());
+        pool.run_until_stalled();
+        assert!(timer.0.borrow().iter().all(|tx| tx.is_canceled()));
+        timer.advance(&mut pool);
+        assert_eq!(fetches.get(), 3);
+        assert!(timer.0.borrow().is_empty());
+    }
+
+    #[test]
+    fn pauses_polling_while_hidden() {
+        let mut pool = LocalPool::new();
+        let timer = MockTimer::default();
+        let fetches = Rc::new(Cell::new(0));
+        let hidden = Rc::new(Cell::new(true));
+
+        pool.spawner()
+            .spawn_local(poll_resource(
+                Duration::from_secs(30),
+                {
+                    let timer = timer.clone();
+                    move |duration| timer.sleep(duration)
+                },
+                {
+                    let fetches = Rc::clone(&fetches);
+                    move || fetches.set(fetches.get() + 1)
+                },
+                || false,
+                {
+                    let hidden = Rc::clone(&hidden);
+                    move || hidden.get()
+                },
+                futures::future::pending(),
+            ))
+            .unwrap();
+        pool.run_until_stalled();
+
+        timer.advance(&mut pool);
+        assert_eq!(fetches.get(), 0);
+        hidden.set(false);
+        timer.advance(&mut pool);
+        assert_eq!(fetches.get(), 1);
+    }
+
+    #[test]
+    fn serialized_data_only_binds_to_the_same_resource() {
+        // e.g., a resource created conditionally on the server, so its data
+        // reaches the next one on the client
+        let data = tag_serialized(1, "1".to_string());
+        assert_eq!(untag_serialized(1, &data), Some("1"));
+        assert_eq!(untag_serialized(2, &data), None);
+        assert_eq!(untag_serialized(1, "1"), None);
+    }
+}
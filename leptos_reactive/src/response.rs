@@ -0,0 +1,88 @@
+#![forbid(unsafe_code)]
+use crate::macros::debug_warn;
+use std::{cell::RefCell, rc::Rc};
+
+/// Lets components deep in the tree set the status, headers, or redirect of
+/// the HTTP response while it's being rendered on the server, e.g., to return
+/// a 404 from a "not found" page.
+///
+/// Server integrations provide this as context in the root scope, and apply
+/// what was collected once the synchronous render is done, and again after
+/// resources have resolved. Components find it with
+/// [use_context](crate::use_context), which returns `None` in the browser.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # run_scope(create_runtime(), |cx| {
+/// # provide_context(cx, ResponseMetadata::default());
+/// if let Some(response) = use_context::<ResponseMetadata>(cx) {
+///     response.set_status(404);
+/// }
+/// # assert_eq!(use_context::<ResponseMetadata>(cx).unwrap().collect().status, Some(404));
+/// # });
+/// ```
+///
+/// If several components set different statuses or redirects, the last one
+/// wins, and a warning is logged in debug mode.
+#[derive(Debug, Clone, Default)]
+pub struct ResponseMetadata(Rc<RefCell<CollectedResponse>>);
+
+/// The response details collected by a [ResponseMetadata].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CollectedResponse {
+    /// The HTTP status code.
+    pub status: Option<u16>,
+    /// Headers, in the order they were set. A name may appear more than once
+    /// if it was appended.
+    pub headers: Vec<(String, String)>,
+    /// The path or URL to redirect to.
+    pub redirect: Option<String>,
+}
+
+impl ResponseMetadata {
+    /// Sets the status code of the response.
+    pub fn set_status(&self, status: u16) {
+        let mut response = self.0.borrow_mut();
+        if let Some(prev) = response.status.filter(|prev| *prev != status) {
+            debug_warn!(
+                "[ResponseMetadata] The response status was set to {prev}, \
+                 and is now being replaced with {status}."
+            );
+        }
+        response.status = Some(status);
+    }
+
+    /// Sets a header, replacing any others with the same name.
+    pub fn insert_header(&self, name: &str, value: &str) {
+        let mut response = self.0.borrow_mut();
+        response
+            .headers
+            .retain(|(prev, _)| !prev.eq_ignore_ascii_case(name));
+        response.headers.push((name.to_string(), value.to_string()));
+    }
+
+    /// Adds a header, keeping any others with the same name.
+    pub fn append_header(&self, name: &str, value: &str) {
+        self.0
+            .borrow_mut()
+            .headers
+            .push((name.to_string(), value.to_string()));
+    }
+
+    /// Redirects to the given path or URL instead of sending the page.
+    pub fn redirect(&self, path: &str) {
+        let mut response = self.0.borrow_mut();
+        if let Some(prev) = response.redirect.as_ref().filter(|p| *p != path) {
+            debug_warn!(
+                "[ResponseMetadata] The response was redirected to {prev}, \
+                 and is now being redirected to {path} instead."
+            );
+        }
+        response.redirect = Some(path.to_string());
+    }
+
+    /// Returns everything that has been set so far.
+    pub fn collect(&self) -> CollectedResponse {
+        self.0.borrow().clone()
+    }
+}
@@ -1,12 +1,13 @@
 #![forbid(unsafe_code)]
 use crate::{
-    hydration::SharedContext, AnyEffect, AnyResource, Effect, EffectId, Memo,
-    ReadSignal, ResourceId, ResourceState, RwSignal, Scope, ScopeDisposer,
-    ScopeId, ScopeProperty, SerializableResource, SignalId, SignalUpdate,
+    hydration::SharedContext, resource::UNRESOLVED_RESOURCE, AnyEffect,
+    AnyResource, Effect, EffectId, Memo, ReadSignal, ResourceId, ResourceState,
+    RwSignal, Scope, ScopeDisposer, ScopeId, ScopeProperty,
+    SerializableResource, SignalId, SignalUpdate, SuspenseContext,
     UnserializableResource, WriteSignal,
 };
 use cfg_if::cfg_if;
-use futures::stream::FuturesUnordered;
+use futures::{future::Either, stream::FuturesUnordered};
 use slotmap::{SecondaryMap, SlotMap, SparseSecondaryMap};
 use std::{
     any::{Any, TypeId},
@@ -21,6 +22,29 @@ use std::{
 
 pub(crate) type PinnedFuture<T> = Pin<Box<dyn Future<Output = T>>>;
 
+// the data loaded by `prefetch_resource`, by the type of its fetcher
+pub(crate) type PrefetchedResources = HashMap<TypeId, Box<dyn Any>>;
+
+cfg_if! {
+    if #[cfg(feature = "ssr-send")] {
+        /// A future that resolves to the serialized value of a resource. With the
+        /// `ssr-send` feature this is `Send`, so the resolvers can be polled from
+        /// another thread than the one rendering the page. Only the resolvers
+        /// are `Send`: the fetchers still run on the rendering thread, so it has
+        /// to keep running its local tasks until they all resolve.
+        pub type PinnedResolverFuture =
+            Pin<Box<dyn Future<Output = Option<(ResourceId, String)>> + Send>>;
+    } else {
+        /// A future that resolves to the serialized value of a resource. With the
+        /// `ssr-send` feature this is `Send`, so the resolvers can be polled from
+        /// another thread than the one rendering the page. Only the resolvers
+        /// are `Send`: the fetchers still run on the rendering thread, so it has
+        /// to keep running its local tasks until they all resolve.
+        pub type PinnedResolverFuture =
+            Pin<Box<dyn Future<Output = Option<(ResourceId, String)>>>>;
+    }
+}
+
 cfg_if! {
     if #[cfg(any(feature = "csr", feature = "hydrate"))] {
         thread_local! {
@@ -33,6 +57,68 @@ cfg_if! {
     }
 }
 
+cfg_if! {
+    if #[cfg(all(debug_assertions, not(any(feature = "csr", feature = "hydrate"))))] {
+        thread_local! {
+            // the runtimes whose scopes and effects are running, innermost last
+            static ACTIVE_RUNTIMES: RefCell<Vec<RuntimeId>> = Default::default();
+        }
+
+        struct ActiveRuntime;
+
+        impl Drop for ActiveRuntime {
+            fn drop(&mut self) {
+                ACTIVE_RUNTIMES.with(|active| active.borrow_mut().pop());
+            }
+        }
+
+        /// Marks `runtime` as the one running while `f` runs, so that handles
+        /// from other runtimes can be caught by [check_runtime].
+        pub(crate) fn with_active_runtime<T>(
+            runtime: RuntimeId,
+            f: impl FnOnce() -> T,
+        ) -> T {
+            ACTIVE_RUNTIMES.with(|active| active.borrow_mut().push(runtime));
+            let _active = ActiveRuntime;
+            f()
+        }
+
+        /// Panics if a handle created in `runtime` is used while a scope or
+        /// effect from another runtime is running. This usually means the
+        /// handle was stored in a `static` or `thread_local`, and one request
+        /// is reading another's data.
+        pub(crate) fn check_runtime(
+            runtime: RuntimeId,
+            defined_at: &'static std::panic::Location<'static>,
+        ) {
+            let active = ACTIVE_RUNTIMES.with(|active| active.borrow().last().copied());
+            if let Some(active) = active.filter(|active| *active != runtime) {
+                panic!(
+                    "[leptos] A handle created at {defined_at} in {runtime:?} \
+                     was used while {active:?} was running. Each request is \
+                     rendered in its own runtime, so signals and resources \
+                     can't be shared between them, e.g., by storing them in a \
+                     `static` or `thread_local`."
+                );
+            }
+        }
+    } else {
+        pub(crate) fn with_active_runtime<T>(
+            _runtime: RuntimeId,
+            f: impl FnOnce() -> T,
+        ) -> T {
+            f()
+        }
+
+        #[cfg(debug_assertions)]
+        pub(crate) fn check_runtime(
+            _runtime: RuntimeId,
+            _defined_at: &'static std::panic::Location<'static>,
+        ) {
+        }
+    }
+}
+
 /// Get the selected runtime from the thread-local set of runtimes. On the server,
 /// this will return the correct runtime. In the browser, there should only be one runtime.
 pub(crate) fn with_runtime<T>(
@@ -69,6 +155,73 @@ pub fn create_runtime() -> RuntimeId {
     }
 }
 
+/// Runs `f` in two new runtimes, the second while the first is still alive,
+/// and panics if they return different values or the second run added
+/// signals to the first runtime. This is meant for tests that check that a
+/// view doesn't share state between requests rendered at the same time.
+///
+/// In debug builds, reading or writing a signal or resource from the first
+/// runtime while the second is running also panics, naming where it was
+/// created.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// assert_runtime_isolated(|cx| {
+///     let (count, set_count) = create_signal(cx, 0);
+///     set_count.update(|n| *n += 1);
+///     count.get()
+/// });
+/// ```
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[track_caller]
+pub fn assert_runtime_isolated<T>(f: impl Fn(Scope) -> T)
+where
+    T: PartialEq + Debug,
+{
+    let signal_count = |runtime| {
+        with_runtime(runtime, |runtime: &Runtime| {
+            runtime.signals.borrow().len()
+        })
+        .unwrap_or_default()
+    };
+
+    let first = create_runtime();
+    let (first_value, _, first_disposer) = first.run_scope_undisposed(&f, None);
+    let first_signals = signal_count(first);
+
+    let second = create_runtime();
+    let second_value = second.run_scope(&f, None);
+    let first_signals_after = signal_count(first);
+    second.dispose();
+
+    first_disposer.dispose();
+    first.dispose();
+
+    assert_eq!(
+        first_value, second_value,
+        "rendering in a new runtime gave a different result"
+    );
+    assert_eq!(
+        first_signals, first_signals_after,
+        "rendering in a new runtime created signals in an earlier one"
+    );
+}
+
+/// How many scopes, signals, effects and resources are alive in a runtime,
+/// as returned by [RuntimeId::stats].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeStats {
+    /// The number of [Scope]s that haven't been disposed.
+    pub scopes: usize,
+    /// The number of signals, including the ones that back memos and
+    /// resources.
+    pub signals: usize,
+    /// The number of effects, including the ones that compute memos.
+    pub effects: usize,
+    /// The number of [Resource](crate::Resource)s.
+    pub resources: usize,
+}
+
 slotmap::new_key_type! {
     /// Unique ID assigned to a [Runtime](crate::Runtime).
     pub struct RuntimeId;
@@ -85,6 +238,19 @@ impl RuntimeId {
         }
     }
 
+    /// Counts what's currently alive in the runtime. This is useful for
+    /// checking that scopes and everything they own are disposed when
+    /// they should be, for example in tests.
+    pub fn stats(self) -> RuntimeStats {
+        with_runtime(self, |runtime| RuntimeStats {
+            scopes: runtime.scopes.borrow().len(),
+            signals: runtime.signals.borrow().len(),
+            effects: runtime.effects.borrow().len(),
+            resources: runtime.resources.borrow().len(),
+        })
+        .unwrap_or_default()
+    }
+
     pub(crate) fn raw_scope_and_disposer(self) -> (Scope, ScopeDisposer) {
         with_runtime(self, |runtime| {
             let id = { runtime.scopes.borrow_mut().insert(Default::default()) };
@@ -109,7 +275,7 @@ impl RuntimeId {
                 runtime.scope_parents.borrow_mut().insert(id, parent.id);
             }
             let scope = Scope { runtime: self, id };
-            let val = f(scope);
+            let val = with_active_runtime(self, || f(scope));
             let disposer = ScopeDisposer(Box::new(move || scope.dispose()));
             (val, id, disposer)
         })
@@ -272,7 +438,7 @@ impl RuntimeId {
     pub(crate) fn create_memo<T>(
         self,
         f: impl Fn(Option<&T>) -> T + 'static,
-    ) -> Memo<T>
+    ) -> (Memo<T>, EffectId)
     where
         T: PartialEq + Any + 'static,
     {
@@ -281,7 +447,7 @@ impl RuntimeId {
 
         let (read, write) = self.create_signal(None);
 
-        self.create_effect(move |_| {
+        let effect = self.create_effect(move |_| {
             let (new, changed) = read.with_no_subscription(|p| {
                 let new = f(p.as_ref());
                 let changed = Some(&new) != p.as_ref();
@@ -293,10 +459,19 @@ impl RuntimeId {
             }
         });
 
-        Memo(
-            read,
-            #[cfg(debug_assertions)]
-            defined_at,
+        // remember which effect computes this memo, so that reads of the memo
+        // can be traced back to the resources it depends on
+        _ = with_runtime(self, |runtime| {
+            runtime.memo_effects.borrow_mut().insert(read.id, effect);
+        });
+
+        (
+            Memo(
+                read,
+                #[cfg(debug_assertions)]
+                defined_at,
+            ),
+            effect,
         )
     }
 }
@@ -321,6 +496,51 @@ pub(crate) struct Runtime {
     pub effect_sources:
         RefCell<SecondaryMap<EffectId, RefCell<HashSet<SignalId>>>>,
     pub resources: RefCell<SlotMap<ResourceId, AnyResource>>,
+    pub resource_signals: RefCell<SecondaryMap<SignalId, ResourceId>>,
+    pub memo_effects: RefCell<SecondaryMap<SignalId, EffectId>>,
+    pub suspense_stack: RefCell<Vec<SuspenseContext>>,
+    pub prefetched_resources: RefCell<PrefetchedResources>,
+    pub render_mode: Cell<RenderMode>,
+    pub hydrated: Cell<bool>,
+}
+
+/// How the views in a runtime are being rendered. See
+/// [Scope::render_mode](crate::Scope::render_mode).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenderMode {
+    /// Rendering to HTML on the server. Effects don't run.
+    Ssr,
+    /// Hydrating HTML that was rendered on the server.
+    Hydrate,
+    /// Rendering in the browser.
+    Csr,
+}
+
+impl Default for RenderMode {
+    /// The mode implied by the crate features, until an entry point sets it.
+    fn default() -> Self {
+        if cfg!(feature = "hydrate") {
+            RenderMode::Hydrate
+        } else if cfg!(feature = "ssr") {
+            RenderMode::Ssr
+        } else {
+            RenderMode::Csr
+        }
+    }
+}
+
+/// Where a runtime is in hydrating server-rendered HTML. See
+/// [Scope::hydration_phase](crate::Scope::hydration_phase).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HydrationPhase {
+    /// Nothing is being hydrated, e.g., on the server or in a client-rendered
+    /// app.
+    NotHydrating,
+    /// The views are being created from server-rendered HTML.
+    Hydrating,
+    /// The views were hydrated, and anything rendered from now on is created in
+    /// the browser.
+    Done,
 }
 
 impl Debug for Runtime {
@@ -357,9 +577,22 @@ impl Runtime {
         &self,
         state: Rc<dyn SerializableResource>,
     ) -> ResourceId {
-        self.resources
+        let id = self
+            .resources
             .borrow_mut()
-            .insert(AnyResource::Serializable(state))
+            .insert(AnyResource::Serializable(state));
+        let mut context = self.shared_context.borrow_mut();
+        if context.island_depth > 0 {
+            context.island_resources.insert(id);
+        }
+        id
+    }
+
+    // whether a resource should be sent to the client, which on a page with
+    // islands is only the case for those created inside one
+    fn is_sent(&self, id: ResourceId) -> bool {
+        let context = self.shared_context.borrow();
+        !context.islands || context.island_resources.contains(&id)
     }
 
     pub(crate) fn resource<S, T, U>(
@@ -410,7 +643,9 @@ impl Runtime {
             .borrow()
             .iter()
             .filter_map(|(resource_id, res)| {
-                if matches!(res, AnyResource::Serializable(_)) {
+                if matches!(res, AnyResource::Serializable(_))
+                    && self.is_sent(resource_id)
+                {
                     Some(resource_id)
                 } else {
                     None
@@ -419,20 +654,145 @@ impl Runtime {
             .collect()
     }
 
+    /// Returns IDs for all local [resources](crate::Resource) found on any
+    /// scope, which only load in the browser.
+    pub(crate) fn local_resources(&self) -> Vec<ResourceId> {
+        self.resources
+            .borrow()
+            .iter()
+            .filter_map(|(resource_id, res)| {
+                if matches!(res, AnyResource::Unserializable(_)) {
+                    Some(resource_id)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Removes an effect, so that it never runs again.
+    pub(crate) fn dispose_effect(&self, id: EffectId) {
+        self.effects.borrow_mut().remove(id);
+        self.effect_sources.borrow_mut().remove(id);
+    }
+
+    /// Removes a signal, and forgets which resource or memo it holds the value
+    /// of, if any, so that suspense reads aren't traced through it anymore.
+    pub(crate) fn dispose_signal(&self, id: SignalId) {
+        self.signals.borrow_mut().remove(id);
+        self.resource_signals.borrow_mut().remove(id);
+        self.memo_effects.borrow_mut().remove(id);
+        let subs = self.signal_subscribers.borrow_mut().remove(id);
+
+        // each of the subs needs to remove the signal from its dependencies
+        // so that it doesn't try to read the (now disposed) signal
+        if let Some(subs) = subs {
+            let source_map = self.effect_sources.borrow();
+            for effect in subs.borrow().iter() {
+                if let Some(effect_sources) = source_map.get(*effect) {
+                    effect_sources.borrow_mut().remove(&id);
+                }
+            }
+        }
+    }
+
+    /// Called whenever a signal is read while a `<Suspense/>` boundary is
+    /// rendering. Follows the signal back through any memos it is derived
+    /// from, and registers every resource it depends on with the innermost
+    /// boundary. This means that a resource read inside a memo created
+    /// outside the boundary is still tracked by it.
+    pub(crate) fn track_suspense_read(&self, signal: SignalId) {
+        let context = match self.suspense_stack.borrow().last() {
+            Some(context) => *context,
+            None => return,
+        };
+
+        let mut resources = Vec::new();
+        let mut visited = HashSet::new();
+        let mut to_visit = vec![signal];
+        while let Some(signal) = to_visit.pop() {
+            if !visited.insert(signal) {
+                continue;
+            }
+            if let Some(resource) = self.resource_signals.borrow().get(signal) {
+                resources.push(*resource);
+            } else if let Some(effect) = self.memo_effects.borrow().get(signal)
+            {
+                if let Some(sources) = self.effect_sources.borrow().get(*effect)
+                {
+                    to_visit.extend(sources.borrow().iter().copied());
+                }
+            }
+        }
+
+        // registering may notify the suspense context synchronously, so
+        // don't hold onto the resource map while doing it
+        let resources = {
+            let all_resources = self.resources.borrow();
+            resources
+                .into_iter()
+                .filter_map(|id| all_resources.get(id).cloned())
+                .collect::<Vec<_>>()
+        };
+        for resource in resources {
+            resource.track_suspense(context);
+        }
+    }
+
     pub(crate) fn serialization_resolvers(
         &self,
         cx: Scope,
-    ) -> FuturesUnordered<PinnedFuture<(ResourceId, String)>> {
+    ) -> FuturesUnordered<PinnedResolverFuture> {
+        let deadline = self.shared_context.borrow().suspense_deadline.clone();
         let f = FuturesUnordered::new();
         for (id, resource) in self.resources.borrow().iter() {
+            if !self.is_sent(id) {
+                continue;
+            }
             if let AnyResource::Serializable(resource) = resource {
-                f.push(resource.to_serialization_resolver(cx, id));
+                let resolver = resource.to_serialization_resolver(cx, id);
+                f.push(match &deadline {
+                    // past the deadline, tell the client to load it instead
+                    Some(deadline) => {
+                        let deadline = send_deadline(deadline());
+                        Box::pin(async move {
+                            match futures::future::select(resolver, deadline)
+                                .await
+                            {
+                                Either::Left((resolved, _)) => resolved,
+                                Either::Right(_) => {
+                                    Some((id, UNRESOLVED_RESOURCE.to_string()))
+                                }
+                            }
+                        })
+                    }
+                    None => resolver,
+                });
             }
         }
         f
     }
 }
 
+// the deadline is usually a timer on the runtime's own thread, so with
+// `ssr-send` it's awaited there and only its completion is sent to the resolver
+#[cfg(feature = "ssr-send")]
+fn send_deadline(
+    deadline: PinnedFuture<()>,
+) -> futures::channel::oneshot::Receiver<()> {
+    let (tx, rx) = futures::channel::oneshot::channel();
+    crate::spawn_local(async move {
+        deadline.await;
+        _ = tx.send(());
+    });
+    rx
+}
+
+#[cfg(not(feature = "ssr-send"))]
+fn send_deadline(deadline: PinnedFuture<()>) -> PinnedFuture<()> {
+    deadline
+}
+
 impl PartialEq for Runtime {
     fn eq(&self, other: &Self) -> bool {
         std::ptr::eq(self, other)
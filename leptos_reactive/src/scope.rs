@@ -1,12 +1,17 @@
 #![forbid(unsafe_code)]
 use crate::{
     console_warn,
-    runtime::{with_runtime, RuntimeId},
-    suspense::StreamChunk,
-    EffectId, PinnedFuture, ResourceId, SignalId, SuspenseContext,
+    resource::UNRESOLVED_RESOURCE,
+    runtime::{with_runtime, HydrationPhase, RenderMode, RuntimeId},
+    suspense::{PinnedFragmentFuture, StreamChunk},
+    AnyResource, EffectId, PinnedFuture, ResourceId, SignalId, SuspenseContext,
 };
-use futures::stream::FuturesUnordered;
-use std::{collections::HashMap, fmt};
+use futures::{
+    stream::{FuturesUnordered, Stream, StreamExt},
+    Future,
+};
+use indexmap::IndexMap;
+use std::{cell::Cell, collections::HashMap, fmt, rc::Rc};
 
 #[doc(hidden)]
 #[must_use = "Scope will leak memory if the disposer function is never called"]
@@ -209,10 +214,9 @@ impl Scope {
                     .dispose();
                 }
             }
-            // run cleanups
-            if let Some(cleanups) =
-                runtime.scope_cleanups.borrow_mut().remove(self.id)
-            {
+            // run cleanups, which can dispose other scopes
+            let cleanups = runtime.scope_cleanups.borrow_mut().remove(self.id);
+            if let Some(cleanups) = cleanups {
                 for cleanup in cleanups {
                     cleanup();
                 }
@@ -227,33 +231,18 @@ impl Scope {
                 for property in owned {
                     match property {
                         ScopeProperty::Signal(id) => {
-                            // remove the signal
-                            runtime.signals.borrow_mut().remove(id);
-                            let subs = runtime
-                                .signal_subscribers
-                                .borrow_mut()
-                                .remove(id);
-
-                            // each of the subs needs to remove the signal from its dependencies
-                            // so that it doesn't try to read the (now disposed) signal
-                            if let Some(subs) = subs {
-                                let source_map =
-                                    runtime.effect_sources.borrow();
-                                for effect in subs.borrow().iter() {
-                                    if let Some(effect_sources) =
-                                        source_map.get(*effect)
-                                    {
-                                        effect_sources.borrow_mut().remove(&id);
-                                    }
-                                }
-                            }
+                            runtime.dispose_signal(id);
                         }
                         ScopeProperty::Effect(id) => {
-                            runtime.effects.borrow_mut().remove(id);
-                            runtime.effect_sources.borrow_mut().remove(id);
+                            runtime.dispose_effect(id);
                         }
                         ScopeProperty::Resource(id) => {
                             runtime.resources.borrow_mut().remove(id);
+                            runtime
+                                .shared_context
+                                .borrow_mut()
+                                .resource_keys
+                                .remove(&id);
                         }
                     }
                 }
@@ -353,68 +342,372 @@ impl Scope {
             .unwrap_or_default()
     }
 
-    /// Returns IDs for all [Resource](crate::Resource)s found on any scope.
+    /// Returns IDs for all local [Resource](crate::Resource)s found on any
+    /// scope. These only load in the browser, so they never resolve on the server.
+    pub fn local_resources(&self) -> Vec<ResourceId> {
+        with_runtime(self.runtime, |runtime| runtime.local_resources())
+            .unwrap_or_default()
+    }
+
+    /// Returns a stream of the IDs and serialized values of all
+    /// [Resource](crate::Resource)s found on any scope, in the order they
+    /// resolve. Resources that are disposed before they resolve are skipped.
     pub fn serialization_resolvers(
         &self,
-    ) -> FuturesUnordered<PinnedFuture<(ResourceId, String)>> {
+    ) -> impl Stream<Item = (ResourceId, String)> + Unpin {
         with_runtime(self.runtime, |runtime| {
             runtime.serialization_resolvers(*self)
         })
         .unwrap_or_default()
+        .filter_map(futures::future::ready)
+    }
+
+    /// Waits until every [Resource](crate::Resource) in the runtime has
+    /// resolved, including those created by `<Suspense/>` fragments as they
+    /// resolve, and keeps their values so that resources created again in a
+    /// later render pass start out resolved.
+    ///
+    /// This allows a fully-resolved page, with no fallbacks and no resolver
+    /// scripts, to be rendered in two passes within the same scope:
+    /// 1. render the view once, which starts loading its resources
+    /// 2. `.await` this
+    /// 3. render the view again, so that every `resource.read(cx)` returns `Some`
+    ///
+    /// If the second pass creates resources the first did not, e.g., because
+    /// they depend on data that was still loading, repeat until
+    /// [all_resources_resolved](Scope::all_resources_resolved) returns `true`.
+    ///
+    /// This takes all the [pending_fragments](Scope::pending_fragments), and
+    /// returns the IDs of any resources that were given up on because the
+    /// [suspense timeout](Scope::set_suspense_timeout) ran out first.
+    pub fn resolve_all_resources(
+        &self,
+    ) -> impl Future<Output = Vec<ResourceId>> {
+        let cx = *self;
+        async move {
+            let mut resolved = HashMap::new();
+            let mut timed_out = Vec::new();
+            loop {
+                let mut resolvers = cx.serialization_resolvers();
+                while let Some((id, data)) = resolvers.next().await {
+                    if data == UNRESOLVED_RESOURCE {
+                        // resources are waited on again after each fragment
+                        if !timed_out.contains(&id) {
+                            timed_out.push(id);
+                        }
+                    } else {
+                        resolved.insert(id, data);
+                    }
+                }
+
+                // resolving a fragment renders its children, which may
+                // create more resources and nested fragments
+                let mut fragments = cx
+                    .pending_fragments()
+                    .into_values()
+                    .map(|(_, in_order)| in_order)
+                    .collect::<Vec<_>>();
+                if fragments.is_empty() {
+                    break;
+                }
+                while let Some(fragment) = fragments.pop() {
+                    for chunk in fragment.await {
+                        if let StreamChunk::Async(fragment) = chunk {
+                            fragments.push(fragment);
+                        }
+                    }
+                }
+            }
+
+            _ = with_runtime(cx.runtime, |runtime| {
+                let mut context = runtime.shared_context.borrow_mut();
+                for (id, data) in resolved {
+                    if let Some(key) = context.resource_keys.get(&id).cloned() {
+                        context.resolved_by_key.insert(key, data);
+                    }
+                }
+                context.unresolved_loads = 0;
+            });
+            timed_out
+        }
+    }
+
+    /// Whether every [Resource](crate::Resource) loaded since the last call to
+    /// [resolve_all_resources](Scope::resolve_all_resources) started out with
+    /// the value it resolved to.
+    pub fn all_resources_resolved(&self) -> bool {
+        with_runtime(self.runtime, |runtime| {
+            runtime.shared_context.borrow().unresolved_loads == 0
+        })
+        .unwrap_or(true)
+    }
+
+    /// Returns the serialized values saved by
+    /// [resolve_all_resources](Scope::resolve_all_resources), keyed by where
+    /// each [Resource](crate::Resource) was created and the value of its source.
+    ///
+    /// Passing these to [set_resolved_resources](Scope::set_resolved_resources)
+    /// in a new runtime lets it render with the same resources already resolved.
+    pub fn resolved_resources(&self) -> HashMap<String, String> {
+        with_runtime(self.runtime, |runtime| {
+            runtime.shared_context.borrow().resolved_by_key.clone()
+        })
+        .unwrap_or_default()
+    }
+
+    /// Adds values returned by [resolved_resources](Scope::resolved_resources),
+    /// so that matching [Resource](crate::Resource)s created in this runtime
+    /// start out resolved.
+    pub fn set_resolved_resources(&self, resolved: HashMap<String, String>) {
+        _ = with_runtime(self.runtime, |runtime| {
+            runtime
+                .shared_context
+                .borrow_mut()
+                .resolved_by_key
+                .extend(resolved);
+        });
+    }
+
+    /// Hands the serialized value of a [Resource](crate::Resource), as sent by
+    /// the server's [serialization_resolvers](Scope::serialization_resolvers),
+    /// to this runtime. This is useful when the data is delivered some other way
+    /// than the default inline scripts.
+    ///
+    /// If the resource hasn't been created yet, it will start out with this value
+    /// instead of fetching. If it has, any fetch that is still in flight is
+    /// canceled and the resource is set to this value.
+    pub fn inject_serialized_resource(&self, id: ResourceId, data: String) {
+        _ = with_runtime(self.runtime, |runtime| {
+            let resource = runtime.resources.borrow().get(id).cloned();
+            match resource {
+                Some(AnyResource::Serializable(resource)) => {
+                    if !resource.inject(id, &data) {
+                        console_warn(&format!(
+                            "[Scope::inject_serialized_resource] The data for \
+                             {id:?} was serialized for a resource created \
+                             somewhere else, so it was ignored."
+                        ));
+                    }
+                }
+                Some(AnyResource::Unserializable(_)) => {}
+                None => {
+                    runtime
+                        .shared_context
+                        .borrow_mut()
+                        .resolved_resources
+                        .insert(id, data);
+                }
+            }
+        });
+    }
+
+    /// Hands the serialized values of several resources to this runtime. See
+    /// [inject_serialized_resource](Scope::inject_serialized_resource).
+    pub fn inject_serialized_resources(
+        &self,
+        resources: impl IntoIterator<Item = (ResourceId, String)>,
+    ) {
+        for (id, data) in resources {
+            self.inject_serialized_resource(id, data);
+        }
     }
 
     /// Registers the given [SuspenseContext](crate::SuspenseContext) with the current scope,
     /// calling the `resolver` when its resources are all resolved.
+    ///
+    /// If a deadline was set with [set_suspense_timeout](Scope::set_suspense_timeout)
+    /// and runs out first, the `resolver` is called with `true`, and should render
+    /// the fallback instead.
     pub fn register_suspense(
         &self,
         context: SuspenseContext,
         key: &str,
-        out_of_order_resolver: impl FnOnce() -> String + 'static,
-        in_order_resolver: impl FnOnce() -> Vec<StreamChunk> + 'static,
+        out_of_order_resolver: impl FnOnce(bool) -> String + 'static,
+        in_order_resolver: impl FnOnce(bool) -> Vec<StreamChunk> + 'static,
     ) {
-        use crate::create_isomorphic_effect;
-        use futures::StreamExt;
-
+        let runtime_id = self.runtime;
         _ = with_runtime(self.runtime, |runtime| {
             let mut shared_context = runtime.shared_context.borrow_mut();
-            let (tx1, mut rx1) = futures::channel::mpsc::unbounded();
-            let (tx2, mut rx2) = futures::channel::mpsc::unbounded();
-
-            create_isomorphic_effect(*self, move |_| {
-                let pending = context
-                    .pending_serializable_resources
-                    .read_only()
-                    .try_with(|n| *n)
-                    .unwrap_or(0);
-                if pending == 0 {
-                    _ = tx1.unbounded_send(());
-                    _ = tx2.unbounded_send(());
+            let (tx1, rx1) = futures::channel::mpsc::unbounded();
+            let (tx2, rx2) = futures::channel::mpsc::unbounded();
+
+            let pending = context.pending_serializable_resources.read_only();
+            // a disposed context has nothing left to wait for
+            let ready = move || pending.try_with(|n| *n == 0).unwrap_or(true);
+
+            // notifies the fragments each time the count reaches zero; they
+            // check it again when they're polled, because other resources
+            // may have started loading in the meantime. Once both of them
+            // are done waiting, it's disposed the next time it runs.
+            let effect = Rc::new(Cell::new(None));
+            let id = runtime_id.create_effect({
+                let effect = Rc::clone(&effect);
+                move |_| {
+                    if tx1.is_closed() && tx2.is_closed() {
+                        if let Some(id) = effect.get() {
+                            _ = with_runtime(runtime_id, |runtime| {
+                                runtime.dispose_effect(id)
+                            });
+                        }
+                        return;
+                    }
+                    if ready() {
+                        if !tx1.is_closed() {
+                            _ = tx1.unbounded_send(());
+                        }
+                        if !tx2.is_closed() {
+                            _ = tx2.unbounded_send(());
+                        }
+                    }
                 }
             });
+            effect.set(Some(id));
+            self.with_scope_property(|prop| {
+                prop.push(ScopeProperty::Effect(id))
+            });
+
+            let deadline =
+                || shared_context.suspense_deadline.as_ref().map(|f| f());
+            let out_of_order = wait_for_resources(ready, rx1, deadline());
+            let in_order = wait_for_resources(ready, rx2, deadline());
 
             shared_context.pending_fragments.insert(
                 key.to_string(),
                 (
                     Box::pin(async move {
-                        rx1.next().await;
-                        out_of_order_resolver()
-                    }),
-                    Box::pin(async move {
-                        rx2.next().await;
-                        in_order_resolver()
+                        out_of_order_resolver(out_of_order.await)
                     }),
+                    Box::pin(async move { in_order_resolver(in_order.await) }),
                 ),
             );
         })
     }
 
-    /// The set of all HTML fragments currently pending.
+    /// Sets the nonce to put on the inline `<script>` tags used for streaming
+    /// and hydration, so that they're allowed by a Content-Security-Policy that
+    /// requires it. This is usually set per request, e.g., from a request extension.
+    pub fn set_script_nonce(&self, nonce: &str) {
+        _ = with_runtime(self.runtime, |runtime| {
+            runtime.shared_context.borrow_mut().script_nonce =
+                Some(nonce.to_string());
+        });
+    }
+
+    /// The nonce set with [set_script_nonce](Scope::set_script_nonce), if any.
+    pub fn script_nonce(&self) -> Option<String> {
+        with_runtime(self.runtime, |runtime| {
+            runtime.shared_context.borrow().script_nonce.clone()
+        })
+        .ok()
+        .flatten()
+    }
+
+    /// Sets whether serialized [Resource](crate::Resource)s are sent in
+    /// `<script type="application/json">` blocks, which don't run any code,
+    /// rather than in inline scripts.
+    ///
+    /// The client only reads these when it starts hydrating, so this only works
+    /// when the whole response is sent first, e.g., when rendering asynchronously.
+    pub fn set_json_resources(&self, json: bool) {
+        _ = with_runtime(self.runtime, |runtime| {
+            runtime.shared_context.borrow_mut().json_resources = json;
+        });
+    }
+
+    /// Whether serialized resources are sent as JSON. See
+    /// [set_json_resources](Scope::set_json_resources).
+    pub fn json_resources(&self) -> bool {
+        with_runtime(self.runtime, |runtime| {
+            runtime.shared_context.borrow().json_resources
+        })
+        .unwrap_or(false)
+    }
+
+    /// Sets the events, e.g., `["click", "input", "submit"]`, that are recorded
+    /// while the page loads and dispatched again once hydration has added the
+    /// event listeners for them, so that clicking a server-rendered button
+    /// before the app has loaded isn't ignored. An inline script is sent before
+    /// the page to do this. No events are recorded by default.
+    ///
+    /// Events that happen constantly, like `scroll` or `mousemove`, shouldn't
+    /// be replayed.
+    pub fn set_replayed_events(&self, events: &[&str]) {
+        _ = with_runtime(self.runtime, |runtime| {
+            runtime.shared_context.borrow_mut().replayed_events =
+                events.iter().map(|event| event.to_string()).collect();
+        });
+    }
+
+    /// The events set with [set_replayed_events](Scope::set_replayed_events).
+    pub fn replayed_events(&self) -> Vec<String> {
+        with_runtime(self.runtime, |runtime| {
+            runtime.shared_context.borrow().replayed_events.clone()
+        })
+        .unwrap_or_default()
+    }
+
+    /// Sets the name this app's hydration keys, and so its `<Suspense/>`
+    /// fragment IDs, start from, for a page that has several apps rendered
+    /// separately on it, e.g., a header and a comments widget. The streaming
+    /// renderers use it when it's set in their `additional_context`, and the
+    /// browser should hydrate the app with `hydrate_to` and the same name, so
+    /// that it only claims its own nodes. `id` should only contain letters,
+    /// digits and `_`.
+    ///
+    /// Without one, the keys start from the same place as always, which is
+    /// fine for a page with a single app.
+    pub fn set_app_id(&self, id: &str) {
+        if !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            crate::macros::debug_warn!(
+                "[Scope::set_app_id] The app ID {id:?} should only contain \
+                 letters, digits and `_`, or its hydration keys may be \
+                 mistaken for another app's."
+            );
+        }
+        _ = with_runtime(self.runtime, |runtime| {
+            runtime.shared_context.borrow_mut().app_id = Some(id.to_string());
+        });
+    }
+
+    /// The ID set with [set_app_id](Scope::set_app_id).
+    pub fn app_id(&self) -> Option<String> {
+        with_runtime(self.runtime, |runtime| {
+            runtime.shared_context.borrow().app_id.clone()
+        })
+        .ok()
+        .flatten()
+    }
+
+    /// Sets how long `<Suspense/>` fragments and serialized
+    /// [Resource](crate::Resource)s wait on the server before giving up. After
+    /// that, fragments render their fallback, and the client loads the resources
+    /// itself once it has hydrated.
+    pub fn set_suspense_timeout(&self, timeout: std::time::Duration) {
+        self.set_suspense_deadline(move || crate::spawn::sleep(timeout));
+    }
+
+    /// Like [set_suspense_timeout](Scope::set_suspense_timeout), but with a custom
+    /// timer: `deadline` is called once for each fragment and resource, and
+    /// returns a `Future` that completes when it should give up.
+    pub fn set_suspense_deadline<Fu>(&self, deadline: impl Fn() -> Fu + 'static)
+    where
+        Fu: Future<Output = ()> + 'static,
+    {
+        _ = with_runtime(self.runtime, |runtime| {
+            runtime.shared_context.borrow_mut().suspense_deadline =
+                Some(Rc::new(move || Box::pin(deadline()) as PinnedFuture<()>));
+        });
+    }
+
+    /// The set of all HTML fragments currently pending, in the order they were
+    /// registered (i.e., document order).
     ///
     /// The keys are hydration IDs. Valeus are tuples of two pinned
     /// `Future`s that return content for out-of-order and in-order streaming, respectively.
+    /// Out-of-order integrations may still resolve them in any order.
     pub fn pending_fragments(
         &self,
-    ) -> HashMap<String, (PinnedFuture<String>, PinnedFuture<Vec<StreamChunk>>)>
+    ) -> IndexMap<String, (PinnedFuture<String>, PinnedFuture<Vec<StreamChunk>>)>
     {
         with_runtime(self.runtime, |runtime| {
             let mut shared_context = runtime.shared_context.borrow_mut();
@@ -423,6 +716,37 @@ impl Scope {
         .unwrap_or_default()
     }
 
+    /// Returns a stream of the keys and out-of-order HTML of all pending
+    /// fragments, in the order they resolve.
+    ///
+    /// Resolving a fragment renders its children, which may register nested
+    /// `<Suspense/>` fragments; these are picked up as well, and the stream
+    /// ends once no fragments remain. The fragments already registered are
+    /// taken when this is called.
+    pub fn fragments_stream(
+        &self,
+    ) -> impl Stream<Item = (String, String)> + Unpin {
+        let cx = *self;
+        let take_pending = move || {
+            cx.pending_fragments()
+                .into_iter()
+                .map(|(key, (fragment, _))| {
+                    Box::pin(async move { (key, fragment.await) })
+                        as PinnedFuture<(String, String)>
+                })
+        };
+        let fragments = take_pending().collect::<FuturesUnordered<_>>();
+
+        Box::pin(futures::stream::unfold(
+            fragments,
+            move |mut fragments| async move {
+                fragments.extend(take_pending());
+                let next = fragments.next().await?;
+                Some((next, fragments))
+            },
+        ))
+    }
+
     /// Takes the pending HTML for a single `<Suspense/>` node.
     ///
     /// Returns a tuple of two pinned `Future`s that return content for out-of-order
@@ -433,11 +757,183 @@ impl Scope {
     ) -> Option<(PinnedFuture<String>, PinnedFuture<Vec<StreamChunk>>)> {
         with_runtime(self.runtime, |runtime| {
             let mut shared_context = runtime.shared_context.borrow_mut();
-            shared_context.pending_fragments.remove(id)
+            shared_context.pending_fragments.shift_remove(id)
         })
         .ok()
         .flatten()
     }
+
+    /// Takes the in-order stream chunks for a single `<Suspense/>` node, so the
+    /// renderer can wait for them at the point it reaches the boundary.
+    pub fn take_fragment(&self, key: &str) -> Option<PinnedFragmentFuture> {
+        self.take_pending_fragment(key)
+            .map(|(_, in_order)| in_order)
+    }
+
+    /// Sets whether the page is being streamed in order. While this is `true`,
+    /// `<Suspense/>` does not emit its fallback, because the renderer waits for
+    /// each fragment before continuing.
+    pub fn set_in_order_streaming(&self, in_order: bool) {
+        _ = with_runtime(self.runtime, |runtime| {
+            runtime.shared_context.borrow_mut().in_order = in_order;
+        });
+    }
+
+    /// Whether the page is being streamed in order. See
+    /// [set_in_order_streaming](Scope::set_in_order_streaming).
+    pub fn in_order_streaming(&self) -> bool {
+        with_runtime(self.runtime, |runtime| {
+            runtime.shared_context.borrow().in_order
+        })
+        .unwrap_or(false)
+    }
+
+    /// How the views in this runtime are being rendered.
+    ///
+    /// This defaults to the mode implied by the crate features, and is set by
+    /// the rendering and mounting functions, so unlike `cfg!(feature = "ssr")`
+    /// it is correct even when one binary is built with several features.
+    pub fn render_mode(&self) -> RenderMode {
+        with_runtime(self.runtime, |runtime| runtime.render_mode.get())
+            .unwrap_or_default()
+    }
+
+    /// Sets how the views in this runtime are being rendered. This is usually
+    /// done by the rendering and mounting functions.
+    pub fn set_render_mode(&self, mode: RenderMode) {
+        _ = with_runtime(self.runtime, |runtime| {
+            runtime.render_mode.set(mode);
+        });
+    }
+
+    /// Whether this runtime is rendering HTML on the server.
+    pub fn is_server(&self) -> bool {
+        self.render_mode() == RenderMode::Ssr
+    }
+
+    /// Whether this runtime is hydrating HTML that was rendered on the server.
+    /// Once hydration is done, the mode becomes [RenderMode::Csr].
+    pub fn is_hydrating(&self) -> bool {
+        self.render_mode() == RenderMode::Hydrate
+    }
+
+    /// Where this runtime is in hydrating HTML that was rendered on the
+    /// server, e.g., to skip an animation while the page is being hydrated
+    /// but run it when the same view is rendered later.
+    ///
+    /// This is available with any crate features, and is
+    /// [NotHydrating](HydrationPhase::NotHydrating) unless the runtime is
+    /// hydrating or has [finished](Scope::end_hydration).
+    pub fn hydration_phase(&self) -> HydrationPhase {
+        with_runtime(self.runtime, |runtime| {
+            match (runtime.render_mode.get(), runtime.hydrated.get()) {
+                (RenderMode::Hydrate, _) => HydrationPhase::Hydrating,
+                (RenderMode::Csr, true) => HydrationPhase::Done,
+                _ => HydrationPhase::NotHydrating,
+            }
+        })
+        .unwrap_or(HydrationPhase::NotHydrating)
+    }
+
+    /// Marks the end of the initial render in the browser, after which views
+    /// are created in the browser, as with [RenderMode::Csr]. If the runtime
+    /// was hydrating, its [hydration_phase](Scope::hydration_phase) becomes
+    /// [Done](HydrationPhase::Done). This is usually done by the mounting
+    /// functions.
+    pub fn end_hydration(&self) {
+        _ = with_runtime(self.runtime, |runtime| {
+            if runtime.render_mode.get() == RenderMode::Hydrate {
+                runtime.hydrated.set(true);
+            }
+            runtime.render_mode.set(RenderMode::Csr);
+        });
+    }
+
+    /// Runs `f` as an island: a part of a mostly static page that is hydrated
+    /// on its own. Returns what `f` returns, and the IDs of the serializable
+    /// [Resource](crate::Resource)s created while it ran, in the order they
+    /// were created.
+    ///
+    /// Once a runtime has islands, only the resources created inside them are
+    /// sent to the client by [serialization_resolvers](Scope::serialization_resolvers)
+    /// and [pending_resources](Scope::pending_resources), since nothing else
+    /// is hydrated. The client gets the returned IDs to hydrate them with
+    /// [set_island_resources](Scope::set_island_resources).
+    pub fn with_island<T>(
+        &self,
+        f: impl FnOnce() -> T,
+    ) -> (T, Vec<ResourceId>) {
+        let before = with_runtime(self.runtime, |runtime| {
+            let mut context = runtime.shared_context.borrow_mut();
+            context.islands = true;
+            context.island_depth += 1;
+            context.island_resources.len()
+        })
+        .unwrap_or_default();
+
+        let value = f();
+
+        let created = with_runtime(self.runtime, |runtime| {
+            let mut context = runtime.shared_context.borrow_mut();
+            context.island_depth -= 1;
+            context
+                .island_resources
+                .iter()
+                .skip(before)
+                .copied()
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+        (value, created)
+    }
+
+    /// Whether [with_island](Scope::with_island) is running.
+    pub fn in_island(&self) -> bool {
+        with_runtime(self.runtime, |runtime| {
+            runtime.shared_context.borrow().island_depth > 0
+        })
+        .unwrap_or(false)
+    }
+
+    /// Whether this runtime renders or hydrates islands, i.e., if
+    /// [with_island](Scope::with_island) or
+    /// [set_island_resources](Scope::set_island_resources) has been called.
+    pub fn has_islands(&self) -> bool {
+        with_runtime(self.runtime, |runtime| {
+            let context = runtime.shared_context.borrow();
+            context.islands || context.island_resource_ids.is_some()
+        })
+        .unwrap_or(false)
+    }
+
+    /// While hydrating an island, hands the [Resource](crate::Resource) IDs
+    /// returned by [with_island](Scope::with_island) on the server to this
+    /// runtime. The resources created next take the values the server sent
+    /// for these IDs, in order, and any created after them load on the
+    /// client, since the client doesn't create the rest of the page's
+    /// resources and so numbers them differently.
+    pub fn set_island_resources(&self, ids: Vec<ResourceId>) {
+        _ = with_runtime(self.runtime, |runtime| {
+            runtime.shared_context.borrow_mut().island_resource_ids =
+                Some(ids.into());
+        });
+    }
+
+    /// The hydration IDs of all HTML fragments currently pending, in the order
+    /// they were registered. Unlike [Scope::pending_fragments], this leaves the
+    /// fragments in place.
+    pub fn pending_fragment_keys(&self) -> Vec<String> {
+        with_runtime(self.runtime, |runtime| {
+            runtime
+                .shared_context
+                .borrow()
+                .pending_fragments
+                .keys()
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default()
+    }
 }
 
 impl fmt::Debug for ScopeDisposer {
@@ -445,3 +941,30 @@ impl fmt::Debug for ScopeDisposer {
         f.debug_tuple("ScopeDisposer").finish()
     }
 }
+
+/// Waits until `ready` returns `true`, checking again each time `notified`
+/// receives a message, or until the `deadline` completes. Returns whether it
+/// gave up.
+async fn wait_for_resources(
+    ready: impl Fn() -> bool,
+    mut notified: futures::channel::mpsc::UnboundedReceiver<()>,
+    deadline: Option<PinnedFuture<()>>,
+) -> bool {
+    let resolved = Box::pin(async move {
+        while !ready() {
+            if notified.next().await.is_none() {
+                break;
+            }
+        }
+    });
+    match deadline {
+        Some(deadline) => matches!(
+            futures::future::select(resolved, deadline).await,
+            futures::future::Either::Right(_)
+        ),
+        None => {
+            resolved.await;
+            false
+        }
+    }
+}
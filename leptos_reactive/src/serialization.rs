@@ -1,6 +1,7 @@
 #![forbid(unsafe_code)]
+use crate::ResourceId;
 use cfg_if::cfg_if;
-use std::rc::Rc;
+use std::{borrow::Cow, rc::Rc};
 use thiserror::Error;
 
 /// Describes errors that can occur while serializing and deserializing data,
@@ -37,6 +38,67 @@ where
     fn de(bytes: &str) -> Result<Self, SerializationError>;
 }
 
+/// Escapes a JSON value or JavaScript string literal so that it can be inlined
+/// into a `<script>` tag, e.g., to send serialized [Resource](crate::Resource)
+/// data from the server.
+///
+/// Data containing `</script>` or `<!--` would otherwise end the script or
+/// change how the rest of it is parsed, which breaks the page and, if the data
+/// includes user-generated content, allows script injection. `<` and the
+/// U+2028 and U+2029 line separators are replaced with their `\u` escapes,
+/// which mean the same thing inside a string, so nothing needs to be undone
+/// when the script runs.
+pub fn escape_script_payload(payload: &str) -> Cow<'_, str> {
+    if !payload.contains(['<', '\u{2028}', '\u{2029}']) {
+        return Cow::Borrowed(payload);
+    }
+    let mut escaped = String::with_capacity(payload.len() + 16);
+    for c in payload.chars() {
+        match c {
+            '<' => escaped.push_str("\\u003c"),
+            '\u{2028}' => escaped.push_str("\\u2028"),
+            '\u{2029}' => escaped.push_str("\\u2029"),
+            c => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+const RESOURCE_OK: char = '+';
+const RESOURCE_ERR: char = '!';
+
+/// Wraps the serialized value of a [Resource](crate::Resource), or the error
+/// that loading or serializing it failed with on the server, so that the
+/// client can tell the two apart.
+pub fn encode_resource_payload(result: Result<&str, &str>) -> String {
+    match result {
+        Ok(data) => format!("{RESOURCE_OK}{data}"),
+        Err(message) => format!("{RESOURCE_ERR}{message}"),
+    }
+}
+
+/// Unwraps a payload created by [encode_resource_payload]. Payloads without
+/// the envelope, as sent by earlier versions, are treated as `Ok`.
+pub fn decode_resource_payload(payload: &str) -> Result<&str, &str> {
+    match payload.strip_prefix(RESOURCE_ERR) {
+        Some(message) => Err(message),
+        None => Ok(payload.strip_prefix(RESOURCE_OK).unwrap_or(payload)),
+    }
+}
+
+/// Reads the ID and serialized data of a [Resource](crate::Resource) sent in a
+/// `<script type="application/json">` block, from its `data-leptos-resource`
+/// attribute and text content. Unlike the default inline scripts, these don't
+/// run any code, so they're allowed by a strict Content-Security-Policy.
+pub fn resource_from_json_script(
+    id: &str,
+    content: &str,
+) -> Option<(ResourceId, String)> {
+    let id = serde_json::from_str(id).ok()?;
+    let data = serde_json::from_str(content).ok()?;
+    Some((id, data))
+}
+
 cfg_if! {
     if #[cfg(feature = "rkyv")] {
         use rkyv::{Archive, Deserialize, Serialize, ser::serializers::AllocSerializer, de::deserializers::SharedDeserializeMap, validation::validators::DefaultValidator};
@@ -490,7 +490,7 @@ impl<T: Clone> SignalGetUntracked<T> for ReadSignal<T> {
         )
     )]
     fn get_untracked(&self) -> T {
-        match with_runtime(self.runtime, |runtime| {
+        match with_runtime(self.runtime(), |runtime| {
             self.id.try_with_no_subscription(runtime, T::clone)
         })
         .expect("runtime to be alive")
@@ -517,7 +517,7 @@ impl<T: Clone> SignalGetUntracked<T> for ReadSignal<T> {
         )
     )]
     fn try_get_untracked(&self) -> Option<T> {
-        with_runtime(self.runtime, |runtime| {
+        with_runtime(self.runtime(), |runtime| {
             self.id.try_with_no_subscription(runtime, Clone::clone).ok()
         })
         .ok()
@@ -557,11 +557,13 @@ impl<T> SignalWithUntracked<T> for ReadSignal<T> {
         )
     )]
     fn try_with_untracked<O>(&self, f: impl FnOnce(&T) -> O) -> Option<O> {
-        with_runtime(self.runtime, |runtime| self.id.try_with(runtime, f))
-            .ok()
-            .transpose()
-            .ok()
-            .flatten()
+        with_runtime(self.runtime(), |runtime| {
+            self.id.try_with_no_subscription(runtime, f)
+        })
+        .ok()
+        .transpose()
+        .ok()
+        .flatten()
     }
 }
 
@@ -598,8 +600,10 @@ impl<T> SignalWith<T> for ReadSignal<T> {
         )
     )]
     fn with<O>(&self, f: impl FnOnce(&T) -> O) -> O {
-        match with_runtime(self.runtime, |runtime| self.id.try_with(runtime, f))
-            .expect("runtime to be alive ")
+        match with_runtime(self.runtime(), |runtime| {
+            self.id.try_with(runtime, f)
+        })
+        .expect("runtime to be alive ")
         {
             Ok(o) => o,
             Err(_) => panic_getting_dead_signal(
@@ -623,9 +627,11 @@ impl<T> SignalWith<T> for ReadSignal<T> {
         )
     )]
     fn try_with<O>(&self, f: impl FnOnce(&T) -> O) -> Option<O> {
-        with_runtime(self.runtime, |runtime| self.id.try_with(runtime, f).ok())
-            .ok()
-            .flatten()
+        with_runtime(self.runtime(), |runtime| {
+            self.id.try_with(runtime, f).ok()
+        })
+        .ok()
+        .flatten()
     }
 }
 
@@ -657,7 +663,7 @@ impl<T: Clone> SignalGet<T> for ReadSignal<T> {
         )
     )]
     fn get(&self) -> T {
-        match with_runtime(self.runtime, |runtime| {
+        match with_runtime(self.runtime(), |runtime| {
             self.id.try_with(runtime, T::clone)
         })
         .expect("runtime to be alive")
@@ -723,13 +729,20 @@ impl<T> ReadSignal<T>
 where
     T: 'static,
 {
+    /// The runtime this signal was created in. In debug builds on the server,
+    /// this panics if a scope or effect from another runtime is running.
+    fn runtime(&self) -> RuntimeId {
+        #[cfg(debug_assertions)]
+        crate::runtime::check_runtime(self.runtime, self.defined_at);
+        self.runtime
+    }
+
     pub(crate) fn with_no_subscription<U>(&self, f: impl FnOnce(&T) -> U) -> U {
-        self.id.with_no_subscription(self.runtime, f)
+        self.id.with_no_subscription(self.runtime(), f)
     }
 
-    #[cfg(feature = "hydrate")]
     pub(crate) fn subscribe(&self) {
-        _ = with_runtime(self.runtime, |runtime| self.id.subscribe(runtime))
+        _ = with_runtime(self.runtime(), |runtime| self.id.subscribe(runtime))
     }
 
     /// Applies the function to the current Signal, if it exists, and subscribes
@@ -738,8 +751,9 @@ where
         &self,
         f: impl FnOnce(&T) -> U,
     ) -> Result<U, SignalError> {
-        match with_runtime(self.runtime, |runtime| self.id.try_with(runtime, f))
-        {
+        match with_runtime(self.runtime(), |runtime| {
+            self.id.try_with(runtime, f)
+        }) {
             Ok(Ok(v)) => Ok(v),
             Ok(Err(e)) => Err(e),
             Err(_) => Err(SignalError::RuntimeDisposed),
@@ -837,7 +851,7 @@ where
     )]
     fn set_untracked(&self, new_value: T) {
         self.id
-            .update_with_no_effect(self.runtime, |v| *v = new_value);
+            .update_with_no_effect(self.runtime(), |v| *v = new_value);
     }
 
     #[cfg_attr(
@@ -857,7 +871,7 @@ where
         let mut new_value = Some(new_value);
 
         self.id
-            .update(self.runtime, |t| *t = new_value.take().unwrap());
+            .update(self.runtime(), |t| *t = new_value.take().unwrap());
 
         new_value
     }
@@ -878,7 +892,7 @@ impl<T> SignalUpdateUntracked<T> for WriteSignal<T> {
         )
     )]
     fn update_untracked(&self, f: impl FnOnce(&mut T)) {
-        self.id.update_with_no_effect(self.runtime, f);
+        self.id.update_with_no_effect(self.runtime(), f);
     }
 
     #[cfg_attr(
@@ -898,14 +912,14 @@ impl<T> SignalUpdateUntracked<T> for WriteSignal<T> {
         &self,
         f: impl FnOnce(&mut T) -> U,
     ) -> Option<U> {
-        self.id.update_with_no_effect(self.runtime, f)
+        self.id.update_with_no_effect(self.runtime(), f)
     }
 
     fn try_update_untracked<O>(
         &self,
         f: impl FnOnce(&mut T) -> O,
     ) -> Option<O> {
-        self.id.update_with_no_effect(self.runtime, f)
+        self.id.update_with_no_effect(self.runtime(), f)
     }
 }
 
@@ -940,7 +954,7 @@ impl<T> SignalUpdate<T> for WriteSignal<T> {
         )
     )]
     fn update(&self, f: impl FnOnce(&mut T)) {
-        if self.id.update(self.runtime, f).is_none() {
+        if self.id.update(self.runtime(), f).is_none() {
             warn_updating_dead_signal(
                 #[cfg(debug_assertions)]
                 self.defined_at,
@@ -962,7 +976,7 @@ impl<T> SignalUpdate<T> for WriteSignal<T> {
         )
     )]
     fn try_update<O>(&self, f: impl FnOnce(&mut T) -> O) -> Option<O> {
-        self.id.update(self.runtime, f)
+        self.id.update(self.runtime(), f)
     }
 }
 
@@ -998,7 +1012,7 @@ impl<T> SignalSet<T> for WriteSignal<T> {
         )
     )]
     fn set(&self, new_value: T) {
-        self.id.update(self.runtime, |n| *n = new_value);
+        self.id.update(self.runtime(), |n| *n = new_value);
     }
 
     #[cfg_attr(
@@ -1018,7 +1032,7 @@ impl<T> SignalSet<T> for WriteSignal<T> {
         let mut new_value = Some(new_value);
 
         self.id
-            .update(self.runtime, |t| *t = new_value.take().unwrap());
+            .update(self.runtime(), |t| *t = new_value.take().unwrap());
 
         new_value
     }
@@ -1038,6 +1052,16 @@ impl<T> Clone for WriteSignal<T> {
 
 impl<T> Copy for WriteSignal<T> {}
 
+impl<T> WriteSignal<T> {
+    /// The runtime this signal was created in. In debug builds on the server,
+    /// this panics if a scope or effect from another runtime is running.
+    fn runtime(&self) -> RuntimeId {
+        #[cfg(debug_assertions)]
+        crate::runtime::check_runtime(self.runtime, self.defined_at);
+        self.runtime
+    }
+}
+
 /// Creates a reactive signal with the getter and setter unified in one value.
 /// You may prefer this style, or it may be easier to pass around in a context
 /// or as a function argument.
@@ -1069,6 +1093,7 @@ impl<T> Copy for WriteSignal<T> {}
         )
     )
 )]
+#[track_caller]
 pub fn create_rw_signal<T>(cx: Scope, value: T) -> RwSignal<T> {
     let s = cx.runtime.create_rw_signal(value);
     cx.with_scope_property(|prop| prop.push(ScopeProperty::Signal(s.id)));
@@ -1159,7 +1184,7 @@ impl<T: Clone> SignalGetUntracked<T> for RwSignal<T> {
         )
     )]
     fn get_untracked(&self) -> T {
-        self.id.with_no_subscription(self.runtime, Clone::clone)
+        self.id.with_no_subscription(self.runtime(), Clone::clone)
     }
 
     #[cfg_attr(
@@ -1176,7 +1201,7 @@ impl<T: Clone> SignalGetUntracked<T> for RwSignal<T> {
         )
     )]
     fn try_get_untracked(&self) -> Option<T> {
-        match with_runtime(self.runtime, |runtime| {
+        match with_runtime(self.runtime(), |runtime| {
             self.id.try_with_no_subscription(runtime, Clone::clone)
         })
         .expect("runtime to be alive")
@@ -1205,7 +1230,7 @@ impl<T> SignalWithUntracked<T> for RwSignal<T> {
         )
     )]
     fn with_untracked<O>(&self, f: impl FnOnce(&T) -> O) -> O {
-        self.id.with_no_subscription(self.runtime, f)
+        self.id.with_no_subscription(self.runtime(), f)
     }
 
     #[cfg_attr(
@@ -1222,11 +1247,13 @@ impl<T> SignalWithUntracked<T> for RwSignal<T> {
         )
     )]
     fn try_with_untracked<O>(&self, f: impl FnOnce(&T) -> O) -> Option<O> {
-        with_runtime(self.runtime, |runtime| self.id.try_with(runtime, f))
-            .ok()
-            .transpose()
-            .ok()
-            .flatten()
+        with_runtime(self.runtime(), |runtime| {
+            self.id.try_with_no_subscription(runtime, f)
+        })
+        .ok()
+        .transpose()
+        .ok()
+        .flatten()
     }
 }
 
@@ -1246,7 +1273,7 @@ impl<T> SignalSetUntracked<T> for RwSignal<T> {
     )]
     fn set_untracked(&self, new_value: T) {
         self.id
-            .update_with_no_effect(self.runtime, |v| *v = new_value);
+            .update_with_no_effect(self.runtime(), |v| *v = new_value);
     }
 
     #[cfg_attr(
@@ -1266,7 +1293,7 @@ impl<T> SignalSetUntracked<T> for RwSignal<T> {
         let mut new_value = Some(new_value);
 
         self.id
-            .update(self.runtime, |t| *t = new_value.take().unwrap());
+            .update(self.runtime(), |t| *t = new_value.take().unwrap());
 
         new_value
     }
@@ -1287,7 +1314,7 @@ impl<T> SignalUpdateUntracked<T> for RwSignal<T> {
     )
     )]
     fn update_untracked(&self, f: impl FnOnce(&mut T)) {
-        self.id.update_with_no_effect(self.runtime, f);
+        self.id.update_with_no_effect(self.runtime(), f);
     }
 
     #[cfg_attr(
@@ -1307,7 +1334,7 @@ impl<T> SignalUpdateUntracked<T> for RwSignal<T> {
         &self,
         f: impl FnOnce(&mut T) -> U,
     ) -> Option<U> {
-        self.id.update_with_no_effect(self.runtime, f)
+        self.id.update_with_no_effect(self.runtime(), f)
     }
 
     #[cfg_attr(
@@ -1327,7 +1354,7 @@ impl<T> SignalUpdateUntracked<T> for RwSignal<T> {
         &self,
         f: impl FnOnce(&mut T) -> O,
     ) -> Option<O> {
-        self.id.update_with_no_effect(self.runtime, f)
+        self.id.update_with_no_effect(self.runtime(), f)
     }
 }
 
@@ -1365,8 +1392,10 @@ impl<T> SignalWith<T> for RwSignal<T> {
         )
     )]
     fn with<O>(&self, f: impl FnOnce(&T) -> O) -> O {
-        match with_runtime(self.runtime, |runtime| self.id.try_with(runtime, f))
-            .expect("runtime to be alive")
+        match with_runtime(self.runtime(), |runtime| {
+            self.id.try_with(runtime, f)
+        })
+        .expect("runtime to be alive")
         {
             Ok(o) => o,
             Err(_) => panic_getting_dead_signal(
@@ -1390,9 +1419,11 @@ impl<T> SignalWith<T> for RwSignal<T> {
         )
     )]
     fn try_with<O>(&self, f: impl FnOnce(&T) -> O) -> Option<O> {
-        with_runtime(self.runtime, |runtime| self.id.try_with(runtime, f).ok())
-            .ok()
-            .flatten()
+        with_runtime(self.runtime(), |runtime| {
+            self.id.try_with(runtime, f).ok()
+        })
+        .ok()
+        .flatten()
     }
 }
 
@@ -1428,7 +1459,7 @@ impl<T: Clone> SignalGet<T> for RwSignal<T> {
     where
         T: Clone,
     {
-        match with_runtime(self.runtime, |runtime| {
+        match with_runtime(self.runtime(), |runtime| {
             self.id.try_with(runtime, T::clone)
         })
         .expect("runtime to be alive")
@@ -1455,7 +1486,7 @@ impl<T: Clone> SignalGet<T> for RwSignal<T> {
         )
     )]
     fn try_get(&self) -> Option<T> {
-        with_runtime(self.runtime, |runtime| {
+        with_runtime(self.runtime(), |runtime| {
             self.id.try_with(runtime, Clone::clone).ok()
         })
         .ok()
@@ -1499,7 +1530,7 @@ impl<T> SignalUpdate<T> for RwSignal<T> {
         )
     )]
     fn update(&self, f: impl FnOnce(&mut T)) {
-        if self.id.update(self.runtime, f).is_none() {
+        if self.id.update(self.runtime(), f).is_none() {
             warn_updating_dead_signal(
                 #[cfg(debug_assertions)]
                 self.defined_at,
@@ -1521,7 +1552,7 @@ impl<T> SignalUpdate<T> for RwSignal<T> {
         )
     )]
     fn try_update<O>(&self, f: impl FnOnce(&mut T) -> O) -> Option<O> {
-        self.id.update(self.runtime, f)
+        self.id.update(self.runtime(), f)
     }
 }
 
@@ -1552,7 +1583,7 @@ impl<T> SignalSet<T> for RwSignal<T> {
         )
     )]
     fn set(&self, value: T) {
-        self.id.update(self.runtime, |n| *n = value);
+        self.id.update(self.runtime(), |n| *n = value);
     }
 
     #[cfg_attr(
@@ -1572,7 +1603,7 @@ impl<T> SignalSet<T> for RwSignal<T> {
         let mut new_value = Some(new_value);
 
         self.id
-            .update(self.runtime, |t| *t = new_value.take().unwrap());
+            .update(self.runtime(), |t| *t = new_value.take().unwrap());
 
         new_value
     }
@@ -1597,6 +1628,14 @@ impl<T: Clone> SignalStream<T> for RwSignal<T> {
 }
 
 impl<T> RwSignal<T> {
+    /// The runtime this signal was created in. In debug builds on the server,
+    /// this panics if a scope or effect from another runtime is running.
+    fn runtime(&self) -> RuntimeId {
+        #[cfg(debug_assertions)]
+        crate::runtime::check_runtime(self.runtime, self.defined_at);
+        self.runtime
+    }
+
     /// Returns a read-only handle to the signal.
     ///
     /// Useful if you're trying to give read access to another component but ensure that it can't write
@@ -1755,6 +1794,9 @@ impl SignalId {
                 sources.borrow_mut().insert(*self);
             }
         }
+
+        // let the nearest <Suspense/> know about any resources behind this read
+        runtime.track_suspense_read(*self);
     }
 
     pub(crate) fn try_with_no_subscription<T, U>(
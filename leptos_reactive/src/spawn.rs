@@ -1,6 +1,6 @@
 #![forbid(unsafe_code)]
 use cfg_if::cfg_if;
-use std::future::Future;
+use std::{future::Future, time::Duration};
 
 /// Spawns and runs a thread-local [std::future::Future] in a platform-independent way.
 ///
@@ -9,8 +9,11 @@ pub fn spawn_local<F>(fut: F)
 where
     F: Future<Output = ()> + 'static,
 {
+    // `csr` or `hydrate` can be enabled outside the browser, e.g., by
+    // `leptos`'s default features when a workspace's tests are built along
+    // with `ssr`, where `wasm_bindgen_futures` can't run anything
     cfg_if! {
-        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+        if #[cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))] {
             wasm_bindgen_futures::spawn_local(fut)
         }
         else if #[cfg(any(test, doctest))] {
@@ -22,3 +25,34 @@ where
         }
     }
 }
+
+/// Waits for the given [Duration] in a platform-independent way.
+pub(crate) async fn sleep(duration: Duration) {
+    cfg_if! {
+        if #[cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))] {
+            use wasm_bindgen::UnwrapThrowExt;
+
+            let ms = duration.as_millis().try_into().unwrap_or(i32::MAX);
+            let promise = js_sys::Promise::new(&mut |resolve, _| {
+                web_sys::window()
+                    .unwrap_throw()
+                    .set_timeout_with_callback_and_timeout_and_arguments_0(
+                        &resolve, ms,
+                    )
+                    .unwrap_throw();
+            });
+            _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+        } else if #[cfg(feature = "ssr")] {
+            tokio::time::sleep(duration).await;
+        } else {
+            // there's no runtime to provide a timer, so this waits on a thread
+            // of its own rather than blocking the one the future runs on
+            let (tx, rx) = futures::channel::oneshot::channel();
+            std::thread::spawn(move || {
+                std::thread::sleep(duration);
+                _ = tx.send(());
+            });
+            _ = rx.await;
+        }
+    }
+}
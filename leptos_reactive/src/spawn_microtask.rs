@@ -6,14 +6,13 @@
 use cfg_if::cfg_if;
 
 cfg_if! {
-    if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+    if #[cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))] {
         /// Exposes the [queueMicrotask](https://developer.mozilla.org/en-US/docs/Web/API/queueMicrotask) method
         /// in the browser, and simply runs the given function when on the server.
         pub fn queue_microtask(task: impl FnOnce() + 'static) {
             microtask(wasm_bindgen::closure::Closure::once_into_js(task));
         }
 
-        #[cfg(any(feature = "csr", feature = "hydrate"))]
         #[wasm_bindgen::prelude::wasm_bindgen(
             inline_js = "export function microtask(f) { queueMicrotask(f); }"
         )]
@@ -23,7 +22,7 @@ cfg_if! {
     } else {
         /// Exposes the [queueMicrotask](https://developer.mozilla.org/en-US/docs/Web/API/queueMicrotask) method
         /// in the browser, and simply runs the given function when on the server.
-        #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+        // outside the browser, there's no JavaScript to queue it with
         pub fn queue_microtask(task: impl FnOnce()) {
             task();
         }
@@ -365,6 +365,7 @@ impl<T> StoredValue<T> {
 /// let callback_b = move || data.with(|data| data.value == "b");
 /// # }).dispose();
 /// ```
+#[track_caller]
 pub fn store_value<T>(cx: Scope, value: T) -> StoredValue<T>
 where
     T: 'static,
@@ -2,11 +2,14 @@
 
 #![forbid(unsafe_code)]
 use crate::{
-    create_rw_signal, create_signal, queue_microtask, store_value, ReadSignal,
-    RwSignal, Scope, SignalUpdate, StoredValue, WriteSignal,
+    create_isomorphic_effect, create_memo, create_rw_signal, create_signal,
+    macros::debug_warn, on_cleanup, queue_microtask, runtime::with_runtime,
+    spawn::sleep, spawn_local, store_value, use_context, Memo, ReadSignal,
+    RuntimeId, RwSignal, Scope, Signal, SignalGet, SignalGetUntracked,
+    SignalSet, SignalUpdate, SignalWith, StoredValue, WriteSignal,
 };
 use futures::Future;
-use std::{borrow::Cow, pin::Pin};
+use std::{borrow::Cow, cell::Cell, pin::Pin, rc::Rc, time::Duration};
 
 /// Tracks [Resource](crate::Resource)s that are read under a suspense context,
 /// i.e., within a [`Suspense`](https://docs.rs/leptos_core/latest/leptos_core/fn.Suspense.html) component.
@@ -17,6 +20,9 @@ pub struct SuspenseContext {
     set_pending_resources: WriteSignal<usize>,
     pub(crate) pending_serializable_resources: RwSignal<usize>,
     pub(crate) has_local_only: StoredValue<bool>,
+    has_loaded: StoredValue<bool>,
+    none_pending: Memo<bool>,
+    waits_for_nested: bool,
 }
 
 impl SuspenseContext {
@@ -24,6 +30,25 @@ impl SuspenseContext {
     pub fn has_local_only(&self) -> bool {
         self.has_local_only.get_value()
     }
+
+    /// Whether the children of this boundary have been shown with all of their
+    /// resources resolved. Until they have, any pending resources are the first
+    /// load; after that, they're being refetched.
+    pub fn has_loaded(&self) -> bool {
+        self.has_loaded.get_value()
+    }
+
+    /// Called by the boundary whenever it has rendered its children. Once the
+    /// resources they read have been counted, the context is marked as
+    /// [loaded](SuspenseContext::has_loaded) if none of them are pending.
+    pub fn children_rendered(&self) {
+        let context = *self;
+        queue_microtask(move || {
+            if context.ready() {
+                context.has_loaded.set_value(true);
+            }
+        });
+    }
 }
 
 impl std::hash::Hash for SuspenseContext {
@@ -42,16 +67,78 @@ impl Eq for SuspenseContext {}
 
 impl SuspenseContext {
     /// Creates an empty suspense context.
+    ///
+    /// If it's created under a context made with
+    /// [new_transition](SuspenseContext::new_transition), that one is pending
+    /// for as long as this one is.
     pub fn new(cx: Scope) -> Self {
+        Self::create(cx, false)
+    }
+
+    /// Creates an empty suspense context that also waits for the boundaries
+    /// nested in it: while any [SuspenseContext] created under it has pending
+    /// resources, it counts as one more pending resource of this one. This is
+    /// how the router waits for everything a route loads before showing it.
+    /// A transition created under another is one of its nested boundaries.
+    pub fn new_transition(cx: Scope) -> Self {
+        Self::create(cx, true)
+    }
+
+    // makes the transition this context is created under, if any, pending
+    // for as long as this context is
+    fn wait_in_transition(self, cx: Scope) {
+        let transition = use_context::<SuspenseContext>(cx)
+            .filter(|parent| parent.waits_for_nested);
+        if let Some(transition) = transition {
+            // whether this boundary is counted as one of its pending resources
+            let counted = Rc::new(Cell::new(false));
+            create_isomorphic_effect(cx, {
+                let counted = Rc::clone(&counted);
+                move |_| {
+                    let pending = !self.none_pending.get();
+                    if pending != counted.replace(pending) {
+                        transition.count_nested(pending);
+                    }
+                }
+            });
+            on_cleanup(cx, move || {
+                if counted.get() {
+                    transition.count_nested(false);
+                }
+            });
+        }
+    }
+
+    // counts a nested boundary right away, rather than in a microtask like
+    // resources, so that it's counted as soon as that boundary's resources are
+    fn count_nested(&self, pending: bool) {
+        _ = self.set_pending_resources.try_update(|n| {
+            if pending {
+                *n += 1;
+            } else if *n > 0 {
+                *n -= 1;
+            }
+        });
+    }
+
+    fn create(cx: Scope, waits_for_nested: bool) -> Self {
         let (pending_resources, set_pending_resources) = create_signal(cx, 0);
         let pending_serializable_resources = create_rw_signal(cx, 0);
         let has_local_only = store_value(cx, true);
-        Self {
+        let has_loaded = store_value(cx, false);
+        let none_pending =
+            create_memo(cx, move |_| pending_resources.with(|n| *n == 0));
+        let context = Self {
             pending_resources,
             set_pending_resources,
             pending_serializable_resources,
             has_local_only,
-        }
+            has_loaded,
+            none_pending,
+            waits_for_nested,
+        };
+        context.wait_in_transition(cx);
+        context
     }
 
     /// Notifies the suspense context that a new resource is now pending.
@@ -76,6 +163,12 @@ impl SuspenseContext {
             setter.update(|n| {
                 if *n > 0 {
                     *n -= 1
+                } else {
+                    debug_warn!(
+                        "[SuspenseContext] A resource resolved more times \
+                         than it started loading; this usually means it \
+                         was decremented twice."
+                    );
                 }
             });
             if serializable {
@@ -88,12 +181,151 @@ impl SuspenseContext {
         });
     }
 
+    /// A reactive signal that is `true` whenever none of the resources read
+    /// under this context are loading. Unlike reading
+    /// [pending_resources](SuspenseContext::pending_resources), it only
+    /// notifies its subscribers when this changes, which makes it suitable for
+    /// switching between the fallback and the children.
+    ///
+    /// This works the same way whether or not the page was rendered on the server.
+    pub fn none_pending(&self) -> Signal<bool> {
+        self.none_pending.into()
+    }
+
     /// Tests whether all of the pending resources have resolved.
     pub fn ready(&self) -> bool {
         self.pending_resources
             .try_with(|n| *n == 0)
             .unwrap_or(false)
     }
+
+    /// Runs the given function with this context marked as the innermost
+    /// `<Suspense/>` boundary currently rendering.
+    ///
+    /// Any resource read within `f` is registered with this context, even if
+    /// it is read indirectly, e.g., through a [Memo](crate::Memo) that was
+    /// created outside the boundary and so can't find it through its [Scope].
+    pub fn run_in_boundary<T>(&self, f: impl FnOnce() -> T) -> T {
+        // pops the boundary even if `f` panics, so that nothing read after it
+        // is registered with this one
+        struct Restore(RuntimeId);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                _ = with_runtime(self.0, |runtime| {
+                    runtime.suspense_stack.borrow_mut().pop()
+                });
+            }
+        }
+
+        let runtime = self.pending_resources.runtime;
+        _ = with_runtime(runtime, |runtime| {
+            runtime.suspense_stack.borrow_mut().push(*self)
+        });
+        let _restore = Restore(runtime);
+        f()
+    }
+}
+
+/// When a boundary starts and stops showing its fallback, relative to its
+/// resources starting and finishing loading. See
+/// [show_fallback](SuspenseContext::show_fallback).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FallbackTiming {
+    /// How long resources have to be pending before the fallback is shown.
+    /// If they all resolve sooner, it never is.
+    pub delay: Duration,
+    /// Once the fallback is shown, it's kept for at least this long, even if
+    /// the resources resolve sooner.
+    pub min_duration: Duration,
+}
+
+impl SuspenseContext {
+    /// A signal that is `true` while the boundary should show its fallback.
+    ///
+    /// With the default [FallbackTiming], this is just the opposite of
+    /// [none_pending](SuspenseContext::none_pending). Otherwise, the fallback
+    /// is only shown once resources have been pending for `timing.delay`, and
+    /// then kept until they have all resolved and `timing.min_duration` has
+    /// passed. The timers belong to `cx`, so disposing it cancels them.
+    pub fn show_fallback(
+        &self,
+        cx: Scope,
+        timing: FallbackTiming,
+    ) -> Signal<bool> {
+        let none_pending = self.none_pending();
+        if timing == FallbackTiming::default() {
+            return Signal::derive(cx, move || !none_pending.get());
+        }
+
+        let shown = create_rw_signal(cx, false);
+        // whether the fallback has been shown for less than `min_duration`
+        let held = create_rw_signal(cx, false);
+        // the timer that is currently running, if any; starting another one
+        // or disposing the scope cancels it
+        let timer = Rc::new(Cell::new(0_usize));
+        let delaying = Rc::new(Cell::new(false));
+
+        let start_timer = {
+            let timer = Rc::clone(&timer);
+            move |duration: Duration, f: Box<dyn FnOnce()>| {
+                let id = timer.get() + 1;
+                timer.set(id);
+                if duration.is_zero() {
+                    f();
+                } else {
+                    let timer = Rc::clone(&timer);
+                    spawn_local(async move {
+                        sleep(duration).await;
+                        if timer.get() == id {
+                            f();
+                        }
+                    });
+                }
+            }
+        };
+        on_cleanup(cx, {
+            let timer = Rc::clone(&timer);
+            move || timer.set(timer.get() + 1)
+        });
+
+        create_isomorphic_effect(cx, move |_| {
+            let pending = !none_pending.get();
+            let is_held = held.get();
+
+            match (shown.get_untracked(), pending) {
+                // resources started loading: wait, unless we already are
+                (false, true) => {
+                    if !delaying.replace(true) {
+                        let delaying = Rc::clone(&delaying);
+                        let start_hold = start_timer.clone();
+                        start_timer(
+                            timing.delay,
+                            Box::new(move || {
+                                delaying.set(false);
+                                shown.set(true);
+                                held.set(true);
+                                start_hold(
+                                    timing.min_duration,
+                                    Box::new(move || held.set(false)),
+                                );
+                            }),
+                        );
+                    }
+                }
+                // they all resolved before the delay ran out
+                (false, false) => {
+                    if delaying.replace(false) {
+                        timer.set(timer.get() + 1);
+                    }
+                }
+                (true, false) if !is_held => shown.set(false),
+                _ => {}
+            }
+        });
+
+        shown.into()
+    }
 }
 
 /// Represents a chunk in a stream of HTML.
@@ -101,9 +333,13 @@ pub enum StreamChunk {
     /// A chunk of synchronous HTML.
     Sync(Cow<'static, str>),
     /// A future that resolves to be a list of additional chunks.
-    Async(Pin<Box<dyn Future<Output = Vec<StreamChunk>>>>),
+    Async(PinnedFragmentFuture),
 }
 
+/// A pinned `Future` that resolves to the stream chunks of a `<Suspense/>`
+/// fragment, for in-order streaming.
+pub type PinnedFragmentFuture = Pin<Box<dyn Future<Output = Vec<StreamChunk>>>>;
+
 impl std::fmt::Debug for StreamChunk {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
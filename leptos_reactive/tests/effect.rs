@@ -91,3 +91,95 @@ fn untrack_mutes_effect() {
     })
     .dispose()
 }
+
+#[test]
+fn effects_follow_runtime_render_mode() {
+    use leptos_reactive::{
+        create_effect, create_runtime, create_scope, create_signal, RenderMode,
+        SignalGet, SignalSet,
+    };
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let runs = Rc::new(Cell::new(0));
+        let (a, set_a) = create_signal(cx, 0);
+
+        cx.set_render_mode(RenderMode::Ssr);
+        assert!(cx.is_server());
+        create_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                a.get();
+                runs.set(runs.get() + 1);
+            }
+        });
+        set_a.set(1);
+        assert_eq!(runs.get(), 0);
+
+        cx.set_render_mode(RenderMode::Csr);
+        assert!(!cx.is_server() && !cx.is_hydrating());
+        create_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                a.get();
+                runs.set(runs.get() + 1);
+            }
+        });
+        assert_eq!(runs.get(), 1);
+        set_a.set(2);
+        assert_eq!(runs.get(), 2);
+    })
+    .dispose()
+}
+
+#[test]
+fn hydration_phase_follows_runtime_render_mode() {
+    use leptos_reactive::{
+        create_runtime, create_scope, HydrationPhase, RenderMode,
+    };
+
+    create_scope(create_runtime(), |cx| {
+        // a client-rendered app never hydrates
+        cx.set_render_mode(RenderMode::Csr);
+        cx.end_hydration();
+        assert_eq!(cx.hydration_phase(), HydrationPhase::NotHydrating);
+
+        cx.set_render_mode(RenderMode::Ssr);
+        assert_eq!(cx.hydration_phase(), HydrationPhase::NotHydrating);
+
+        cx.set_render_mode(RenderMode::Hydrate);
+        assert!(cx.is_hydrating());
+        assert_eq!(cx.hydration_phase(), HydrationPhase::Hydrating);
+
+        cx.end_hydration();
+        assert!(!cx.is_hydrating());
+        assert_eq!(cx.render_mode(), RenderMode::Csr);
+        assert_eq!(cx.hydration_phase(), HydrationPhase::Done);
+    })
+    .dispose();
+}
+
+#[test]
+fn runtimes_start_in_the_render_mode_of_the_build() {
+    use leptos_reactive::{
+        create_runtime, create_scope, HydrationPhase, RenderMode,
+    };
+
+    create_scope(create_runtime(), |cx| {
+        let expected = if cfg!(feature = "hydrate") {
+            RenderMode::Hydrate
+        } else if cfg!(feature = "ssr") {
+            RenderMode::Ssr
+        } else {
+            RenderMode::Csr
+        };
+        assert_eq!(RenderMode::default(), expected);
+        assert_eq!(cx.render_mode(), expected);
+        assert_eq!(cx.is_hydrating(), cfg!(feature = "hydrate"));
+        assert_eq!(
+            cx.hydration_phase() == HydrationPhase::Hydrating,
+            cfg!(feature = "hydrate")
+        );
+    })
+    .dispose();
+}
@@ -0,0 +1,987 @@
+#[cfg(all(
+    feature = "ssr",
+    not(any(feature = "csr", feature = "hydrate"))
+))]
+use leptos_reactive::{
+    create_cached_resource, create_resource, create_resource_with_options,
+    create_runtime, create_signal, create_stream_resource, prefetch_resource,
+    provide_context, raw_scope_and_disposer, Resource, ResourceOptions, Scope,
+    SignalGet, SignalSet, SignalWith, SuspenseContext,
+};
+
+// yields enough times for any resource futures woken by a send to run
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+async fn tick() {
+    for _ in 0..10 {
+        tokio::task::yield_now().await;
+    }
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+fn run_local(fut: impl std::future::Future<Output = ()>) {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap();
+    tokio::task::LocalSet::new().block_on(&rt, fut);
+}
+
+// a runtime whose clock only moves when the test advances it
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+fn run_paused(fut: impl std::future::Future<Output = ()>) {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .start_paused(true)
+        .build()
+        .unwrap();
+    tokio::task::LocalSet::new().block_on(&rt, fut);
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+async fn advance(ms: u64) {
+    // lets timers that were just started register before the clock moves
+    tick().await;
+    tokio::time::advance(std::time::Duration::from_millis(ms)).await;
+    tick().await;
+}
+
+// creates a resource in the same place for the server and the client, the
+// way an app does, so that the client accepts the server's data for it
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+fn shared_resource<Fu>(
+    cx: Scope,
+    fetcher: impl Fn(()) -> Fu + 'static,
+) -> Resource<(), usize>
+where
+    Fu: std::future::Future<Output = usize> + 'static,
+{
+    create_resource(cx, || (), fetcher)
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn stream_resource_serializes_first_item_on_server() {
+    use futures::{channel::mpsc, StreamExt};
+    use std::{cell::RefCell, rc::Rc};
+
+    run_local(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+
+        let (tx, rx) = mpsc::unbounded::<usize>();
+        let rx = Rc::new(RefCell::new(Some(rx)));
+        let resource = create_stream_resource(
+            cx,
+            || (),
+            move |_| rx.borrow_mut().take().unwrap(),
+        );
+        assert_eq!(resource.read(cx), None);
+
+        tx.unbounded_send(1).unwrap();
+        let (_, serialized) =
+            cx.serialization_resolvers().next().await.unwrap();
+        assert!(serialized.ends_with(":1"));
+        assert_eq!(resource.read(cx), Some(Some(1)));
+
+        // the rest of the stream is dropped once the first item is sent
+        tick().await;
+        assert!(tx.is_closed());
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn stream_resource_applies_each_item_in_the_browser() {
+    use futures::channel::mpsc;
+    use leptos_reactive::RenderMode;
+    use std::{cell::RefCell, rc::Rc};
+
+    run_local(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+        cx.set_render_mode(RenderMode::Csr);
+
+        let (tx, rx) = mpsc::unbounded::<usize>();
+        let rx = Rc::new(RefCell::new(Some(rx)));
+        let resource = create_stream_resource(
+            cx,
+            || (),
+            move |_| rx.borrow_mut().take().unwrap(),
+        );
+        tick().await;
+        assert!(resource.loading().get());
+
+        tx.unbounded_send(1).unwrap();
+        tick().await;
+        assert_eq!(resource.read(cx), Some(Some(1)));
+        assert!(!resource.loading().get());
+
+        // later items are driven after the first one resolved the resource
+        tx.unbounded_send(2).unwrap();
+        tick().await;
+        assert_eq!(resource.read(cx), Some(Some(2)));
+        tx.unbounded_send(3).unwrap();
+        tick().await;
+        assert_eq!(resource.read(cx), Some(Some(3)));
+
+        disposer.dispose();
+        tick().await;
+        assert!(tx.is_closed());
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn stream_resource_resolves_to_none_if_the_stream_is_empty() {
+    use leptos_reactive::RenderMode;
+
+    run_local(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+        cx.set_render_mode(RenderMode::Csr);
+
+        let resource = create_stream_resource(
+            cx,
+            || (),
+            |_| futures::stream::empty::<usize>(),
+        );
+        tick().await;
+        assert_eq!(resource.read(cx), Some(None));
+        assert!(!resource.loading().get());
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn stream_resource_drops_stream_on_dispose() {
+    use futures::channel::mpsc;
+    use std::{cell::RefCell, rc::Rc};
+
+    run_local(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+
+        let (tx, rx) = mpsc::unbounded::<usize>();
+        let rx = Rc::new(RefCell::new(Some(rx)));
+        let resource = create_stream_resource(
+            cx,
+            || (),
+            move |_| rx.borrow_mut().take().unwrap(),
+        );
+        assert_eq!(resource.read(cx), None);
+        tick().await;
+        assert!(!tx.is_closed());
+
+        disposer.dispose();
+        tick().await;
+        assert!(tx.is_closed());
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn resource_retries_failed_fetches() {
+    use std::{cell::Cell, rc::Rc, time::Duration};
+
+    run_paused(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+
+        let calls = Rc::new(Cell::new(0));
+        let resource = create_resource_with_options(
+            cx,
+            || (),
+            {
+                let calls = Rc::clone(&calls);
+                move |_| {
+                    calls.set(calls.get() + 1);
+                    let n = calls.get();
+                    async move {
+                        if n < 3 {
+                            Err(format!("attempt {n} failed"))
+                        } else {
+                            Ok(n)
+                        }
+                    }
+                }
+            },
+            ResourceOptions {
+                backoff: Rc::new(|_| Duration::from_millis(1)),
+                retry_on_server: true,
+                ..Default::default()
+            },
+        );
+
+        let (context, _) = cx.run_child_scope(|cx| {
+            let context = SuspenseContext::new(cx);
+            provide_context(cx, context);
+            assert_eq!(resource.read(cx), None);
+            context
+        });
+        assert_eq!(context.pending_resources.get(), 1);
+
+        // still pending while it waits to retry
+        tick().await;
+        assert_eq!(calls.get(), 1);
+        assert!(resource.loading().get());
+        assert_eq!(context.pending_resources.get(), 1);
+        assert_eq!(resource.read(cx), None);
+
+        advance(1).await;
+        assert_eq!(calls.get(), 2);
+        assert!(resource.loading().get());
+
+        advance(1).await;
+        assert_eq!(calls.get(), 3);
+        assert_eq!(resource.read(cx), Some(Ok(3)));
+        assert!(!resource.loading().get());
+        assert_eq!(context.pending_resources.get(), 0);
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn resource_stops_retrying_when_source_changes() {
+    use std::{cell::RefCell, rc::Rc, time::Duration};
+
+    run_paused(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let (source, set_source) = create_signal(cx, 0);
+        let resource = create_resource_with_options(
+            cx,
+            move || source.get(),
+            {
+                let calls = Rc::clone(&calls);
+                move |source| {
+                    calls.borrow_mut().push(source);
+                    async move { Err::<(), _>(source) }
+                }
+            },
+            ResourceOptions {
+                retries: 10,
+                backoff: Rc::new(|_| Duration::from_millis(5)),
+                retry_on_server: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(resource.read(cx), None);
+        tick().await;
+        set_source.set(1);
+
+        for _ in 0..20 {
+            advance(5).await;
+        }
+        let calls = calls.borrow();
+        assert_eq!(calls.iter().filter(|n| **n == 0).count(), 1);
+        assert_eq!(calls.iter().filter(|n| **n == 1).count(), 11);
+        assert_eq!(resource.read(cx), Some(Err(1)));
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn resource_does_not_retry_on_server_by_default() {
+    use std::{cell::Cell, rc::Rc};
+
+    run_local(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+
+        let calls = Rc::new(Cell::new(0));
+        let resource = create_resource_with_options(
+            cx,
+            || (),
+            {
+                let calls = Rc::clone(&calls);
+                move |_| {
+                    calls.set(calls.get() + 1);
+                    async { Err::<(), _>(()) }
+                }
+            },
+            ResourceOptions::default(),
+        );
+        assert_eq!(resource.read(cx), None);
+
+        tick().await;
+        assert_eq!(calls.get(), 1);
+        assert_eq!(resource.read(cx), Some(Err(())));
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn resource_retries_when_the_runtime_renders_in_the_browser() {
+    use leptos_reactive::RenderMode;
+    use std::{cell::Cell, rc::Rc, time::Duration};
+
+    run_paused(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+        // e.g., in a binary that's built with both `ssr` and `hydrate`
+        cx.set_render_mode(RenderMode::Csr);
+
+        let calls = Rc::new(Cell::new(0));
+        let resource = create_resource_with_options(
+            cx,
+            || (),
+            {
+                let calls = Rc::clone(&calls);
+                move |_| {
+                    calls.set(calls.get() + 1);
+                    async { Err::<(), _>(()) }
+                }
+            },
+            ResourceOptions {
+                retries: 1,
+                backoff: Rc::new(|_| Duration::from_millis(1)),
+                ..Default::default()
+            },
+        );
+        assert_eq!(resource.read(cx), None);
+
+        advance(1).await;
+        assert_eq!(calls.get(), 2);
+        assert_eq!(resource.read(cx), Some(Err(())));
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn resource_with_tuple_source_skips_equal_sources() {
+    use std::{cell::RefCell, rc::Rc};
+
+    run_local(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+
+        let (user_id, set_user_id) = create_signal(cx, 1);
+        let (page, set_page) = create_signal(cx, 0);
+        let (query, set_query) = create_signal(cx, String::new());
+
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let resource = create_resource(
+            cx,
+            move || (user_id.get(), page.get(), !query.with(String::is_empty)),
+            {
+                let calls = Rc::clone(&calls);
+                move |source: (u32, usize, bool)| {
+                    calls.borrow_mut().push(source);
+                    async move { source.1 }
+                }
+            },
+        );
+        assert_eq!(resource.read(cx), None);
+        tick().await;
+        assert_eq!(*calls.borrow(), [(1, 0, false)]);
+
+        // reruns the source, but it returns the same tuple
+        set_query.set("a".to_string());
+        tick().await;
+        assert_eq!(calls.borrow().len(), 2);
+        set_query.set("ab".to_string());
+        set_user_id.set(1);
+        tick().await;
+        assert_eq!(*calls.borrow(), [(1, 0, false), (1, 0, true)]);
+
+        set_page.set(1);
+        tick().await;
+        assert_eq!(
+            *calls.borrow(),
+            [(1, 0, false), (1, 0, true), (1, 1, true)]
+        );
+        assert_eq!(resource.read(cx), Some(1));
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn resource_callbacks_run_once_per_completed_fetch() {
+    use futures::channel::oneshot;
+    use std::{
+        cell::{Cell, RefCell},
+        collections::VecDeque,
+        rc::Rc,
+    };
+
+    run_local(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+
+        let (source, set_source) = create_signal(cx, 0);
+        let senders = Rc::new(RefCell::new(VecDeque::new()));
+        let resolved = Rc::new(Cell::new(0));
+        let errors = Rc::new(Cell::new(0));
+        let resource = create_resource_with_options(
+            cx,
+            move || source.get(),
+            {
+                let senders = Rc::clone(&senders);
+                move |_| {
+                    let (tx, rx) = oneshot::channel::<Result<usize, ()>>();
+                    senders.borrow_mut().push_back(tx);
+                    async move { rx.await.unwrap_or(Err(())) }
+                }
+            },
+            ResourceOptions {
+                retries: 0,
+                on_resolved: Some(Rc::new({
+                    let resolved = Rc::clone(&resolved);
+                    move |_: &usize| resolved.set(resolved.get() + 1)
+                })),
+                on_error: Some(Rc::new({
+                    let errors = Rc::clone(&errors);
+                    move |_: &()| errors.set(errors.get() + 1)
+                })),
+                callbacks_on_server: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(resource.read(cx), None);
+
+        // initial load
+        senders
+            .borrow_mut()
+            .pop_front()
+            .unwrap()
+            .send(Ok(1))
+            .unwrap();
+        tick().await;
+        assert_eq!((resolved.get(), errors.get()), (1, 0));
+
+        // refetch
+        resource.refetch();
+        senders
+            .borrow_mut()
+            .pop_front()
+            .unwrap()
+            .send(Err(()))
+            .unwrap();
+        tick().await;
+        assert_eq!((resolved.get(), errors.get()), (1, 1));
+
+        // the first fetch is superseded before it completes
+        set_source.set(1);
+        set_source.set(2);
+        let superseded = senders.borrow_mut().pop_front().unwrap();
+        let current = senders.borrow_mut().pop_front().unwrap();
+        superseded.send(Ok(1)).unwrap();
+        tick().await;
+        assert_eq!((resolved.get(), errors.get()), (1, 1));
+        current.send(Ok(2)).unwrap();
+        tick().await;
+        assert_eq!((resolved.get(), errors.get()), (2, 1));
+        assert_eq!(resource.read(cx), Some(Ok(2)));
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn resource_callbacks_run_once_the_result_is_stored() {
+    use leptos_reactive::SignalGetUntracked;
+    use std::{cell::RefCell, rc::Rc};
+
+    run_local(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+
+        type Handle = Rc<RefCell<Option<Resource<(), Result<usize, ()>>>>>;
+        let handle: Handle = Default::default();
+        // what the resource looked like when the callback ran
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let resource = create_resource_with_options(
+            cx,
+            || (),
+            |_| async { Ok(1) },
+            ResourceOptions {
+                on_resolved: Some(Rc::new({
+                    let handle = Rc::clone(&handle);
+                    let seen = Rc::clone(&seen);
+                    move |_: &usize| {
+                        let resource = handle.borrow().unwrap();
+                        seen.borrow_mut().push((
+                            resource.with(cx, Clone::clone),
+                            resource.loading().get_untracked(),
+                        ));
+                    }
+                })),
+                callbacks_on_server: true,
+                ..Default::default()
+            },
+        );
+        *handle.borrow_mut() = Some(resource);
+
+        tick().await;
+        assert_eq!(*seen.borrow(), [(Some(Ok(1)), false)]);
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn cached_resource_uses_prefetched_data() {
+    use std::{cell::Cell, rc::Rc};
+
+    run_local(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+
+        let calls = Rc::new(Cell::new(0));
+        let fetcher = {
+            let calls = Rc::clone(&calls);
+            move |id: u32| {
+                calls.set(calls.get() + 1);
+                async move { id * 10 }
+            }
+        };
+
+        prefetch_resource(cx, 1, fetcher.clone());
+        prefetch_resource(cx, 1, fetcher.clone());
+        tick().await;
+        assert_eq!(calls.get(), 1);
+
+        let resource = create_cached_resource(cx, || 1, fetcher.clone());
+        assert_eq!(resource.read(cx), Some(10));
+        tick().await;
+        assert_eq!(calls.get(), 1);
+
+        // the prefetched value is only used once
+        resource.refetch();
+        tick().await;
+        assert_eq!(calls.get(), 2);
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn cached_resource_waits_for_pending_prefetch() {
+    use futures::channel::oneshot;
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    run_local(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+
+        let (tx, rx) = oneshot::channel::<u32>();
+        let rx = Rc::new(RefCell::new(Some(rx)));
+        let calls = Rc::new(Cell::new(0));
+        let fetcher = {
+            let calls = Rc::clone(&calls);
+            move |_| {
+                calls.set(calls.get() + 1);
+                let rx = rx.borrow_mut().take();
+                async move {
+                    match rx {
+                        Some(rx) => rx.await.unwrap(),
+                        None => 0_u32,
+                    }
+                }
+            }
+        };
+        prefetch_resource(cx, (), fetcher.clone());
+        assert_eq!(calls.get(), 1);
+
+        let resource = create_cached_resource(cx, || (), fetcher);
+        let (context, _) = cx.run_child_scope(|cx| {
+            let context = SuspenseContext::new(cx);
+            provide_context(cx, context);
+            assert_eq!(resource.read(cx), None);
+            context
+        });
+        assert_eq!(context.pending_resources.get(), 1);
+
+        tx.send(42).unwrap();
+        tick().await;
+        assert_eq!(resource.read(cx), Some(42));
+        assert_eq!(context.pending_resources.get(), 0);
+        assert_eq!(calls.get(), 1);
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn cached_resource_only_uses_data_prefetched_with_its_fetcher() {
+    run_local(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+
+        async fn user(id: u32) -> String {
+            format!("user {id}")
+        }
+        async fn posts(id: u32) -> String {
+            format!("posts by {id}")
+        }
+
+        prefetch_resource(cx, 1, user);
+        tick().await;
+        let posts = create_cached_resource(cx, || 1, posts);
+        let user = create_cached_resource(cx, || 1, user);
+        tick().await;
+        assert_eq!(posts.read(cx).as_deref(), Some("posts by 1"));
+        assert_eq!(user.read(cx).as_deref(), Some("user 1"));
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn prefetched_data_is_dropped_with_the_scope_that_prefetched_it() {
+    use std::{cell::Cell, rc::Rc};
+
+    run_local(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+
+        let calls = Rc::new(Cell::new(0));
+        let fetcher = {
+            let calls = Rc::clone(&calls);
+            move |id: u32| {
+                calls.set(calls.get() + 1);
+                async move { id * 10 }
+            }
+        };
+
+        let (_, hovered) = cx.run_child_scope({
+            let fetcher = fetcher.clone();
+            move |cx| prefetch_resource(cx, 1, fetcher)
+        });
+        tick().await;
+        assert_eq!(calls.get(), 1);
+        hovered.dispose();
+
+        let resource = create_cached_resource(cx, || 1, fetcher);
+        tick().await;
+        assert_eq!(resource.read(cx), Some(10));
+        assert_eq!(calls.get(), 2);
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn resource_value_is_dropped_with_its_scope() {
+    use serde::{Deserialize, Serialize};
+    use std::cell::Cell;
+
+    thread_local! {
+        static DROPPED: Cell<usize> = const { Cell::new(0) };
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Payload(Vec<u8>);
+
+    impl Drop for Payload {
+        fn drop(&mut self) {
+            DROPPED.with(|dropped| dropped.set(dropped.get() + 1));
+        }
+    }
+
+    run_local(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+
+        let (_, child) = cx.run_child_scope(|cx| {
+            create_resource(cx, || (), |_| async { Payload(vec![0; 1024]) });
+        });
+        tick().await;
+        assert_eq!(DROPPED.with(Cell::get), 0);
+
+        // never polled
+        let resolvers = cx.serialization_resolvers();
+        child.dispose();
+        assert_eq!(DROPPED.with(Cell::get), 1);
+
+        drop(resolvers);
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn serialization_resolvers_skip_disposed_resources() {
+    use futures::{channel::oneshot, StreamExt};
+    use std::{cell::RefCell, rc::Rc};
+
+    run_local(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+
+        let (tx, rx) = oneshot::channel::<usize>();
+        let rx = Rc::new(RefCell::new(Some(rx)));
+        let (_, child) = cx.run_child_scope(|cx| {
+            create_resource(cx, || (), |_| async { 0_usize });
+        });
+        create_resource(
+            cx,
+            || (),
+            move |_| {
+                let rx = rx.borrow_mut().take().unwrap();
+                async { rx.await.unwrap() }
+            },
+        );
+
+        let mut resolvers = cx.serialization_resolvers();
+        child.dispose();
+        tx.send(1).unwrap();
+
+        let (_, serialized) = resolvers.next().await.unwrap();
+        assert!(serialized.ends_with(":1"));
+        // instead of waiting forever for the disposed resource
+        assert!(resolvers.next().await.is_none());
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn injected_resource_data_is_used_instead_of_fetching() {
+    use futures::StreamExt;
+    use std::{cell::Cell, rc::Rc};
+
+    run_local(async {
+        let (server, server_disposer) =
+            raw_scope_and_disposer(create_runtime());
+        shared_resource(server, |_| async { 42 });
+        let (id, data) = server.serialization_resolvers().next().await.unwrap();
+        server_disposer.dispose();
+
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+        cx.inject_serialized_resource(id, data);
+
+        let fetches = Rc::new(Cell::new(0));
+        let resource = shared_resource(cx, {
+            let fetches = Rc::clone(&fetches);
+            move |_| {
+                fetches.set(fetches.get() + 1);
+                async { 0 }
+            }
+        });
+        tick().await;
+
+        assert_eq!(resource.read(cx), Some(42));
+        assert_eq!(fetches.get(), 0);
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn injected_resource_data_wins_over_in_flight_fetch() {
+    use futures::{channel::oneshot, StreamExt};
+    use std::{cell::RefCell, rc::Rc};
+
+    run_local(async {
+        let (server, server_disposer) =
+            raw_scope_and_disposer(create_runtime());
+        shared_resource(server, |_| async { 42 });
+        let (id, data) = server.serialization_resolvers().next().await.unwrap();
+        server_disposer.dispose();
+
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+        let (tx, rx) = oneshot::channel::<usize>();
+        let rx = Rc::new(RefCell::new(Some(rx)));
+        let resource = shared_resource(cx, move |_| {
+            let rx = rx.borrow_mut().take().unwrap();
+            async { rx.await.unwrap() }
+        });
+        assert!(resource.loading().get());
+
+        cx.inject_serialized_resource(id, data);
+        assert_eq!(resource.read(cx), Some(42));
+        assert!(!resource.loading().get());
+
+        tx.send(7).unwrap();
+        tick().await;
+        assert_eq!(resource.read(cx), Some(42));
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn data_for_a_conditional_resource_is_not_bound_to_the_next_one() {
+    use futures::StreamExt;
+
+    // `user` is only created for a logged-in user, so if the server and the
+    // client disagree on that, `posts` has a different ID on each
+    fn resources(
+        cx: Scope,
+        logged_in: bool,
+        posts: &'static str,
+    ) -> Resource<(), String> {
+        if logged_in {
+            create_resource(cx, || (), |_| async { "user".to_string() });
+        }
+        create_resource(cx, || (), move |_| async move { posts.to_string() })
+    }
+
+    run_local(async {
+        let (server, server_disposer) =
+            raw_scope_and_disposer(create_runtime());
+        resources(server, true, "posts");
+        let resolved =
+            server.serialization_resolvers().collect::<Vec<_>>().await;
+        server_disposer.dispose();
+        assert_eq!(resolved.len(), 2);
+
+        // the data sent for `user` isn't taken for `posts`, which is loaded
+        // on the client instead
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+        for (id, data) in resolved.clone() {
+            cx.inject_serialized_resource(id, data);
+        }
+        let posts = resources(cx, false, "posts from the client");
+        tick().await;
+        assert_eq!(posts.read(cx).as_deref(), Some("posts from the client"));
+        disposer.dispose();
+
+        // while resources created in the same places get their own data
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+        for (id, data) in resolved {
+            cx.inject_serialized_resource(id, data);
+        }
+        let posts = resources(cx, true, "posts from the client");
+        tick().await;
+        assert_eq!(posts.read(cx).as_deref(), Some("posts"));
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(
+    feature = "ssr-send",
+    not(any(feature = "csr", feature = "hydrate"))
+))]
+#[test]
+fn serialization_resolvers_can_be_driven_by_tokio_spawn() {
+    use futures::StreamExt;
+    use std::time::Duration;
+
+    run_local(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+        cx.set_suspense_timeout(Duration::from_secs(60));
+
+        create_resource(cx, || (), |_| async { 1_usize });
+        create_resource(cx, || (), |_| async { 2_usize });
+
+        let resolvers = cx.serialization_resolvers();
+        let resolved =
+            tokio::spawn(async move { resolvers.collect::<Vec<_>>().await })
+                .await
+                .unwrap();
+        assert_eq!(resolved.len(), 2);
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn resource_that_fails_to_serialize_is_loaded_again_on_client() {
+    use futures::StreamExt;
+    use leptos_reactive::decode_resource_payload;
+    use std::{cell::Cell, collections::HashMap, rc::Rc};
+
+    run_local(async {
+        // JSON objects can't have tuples as keys
+        let (server, server_disposer) =
+            raw_scope_and_disposer(create_runtime());
+        create_resource(
+            server,
+            || (),
+            |_| async { HashMap::from([((1_u8, 2_u8), 3_u8)]) },
+        );
+        let (id, data) = server.serialization_resolvers().next().await.unwrap();
+        server_disposer.dispose();
+        assert!(decode_resource_payload(&data).is_err());
+
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+        cx.inject_serialized_resource(id, data);
+
+        let fetches = Rc::new(Cell::new(0));
+        let resource = create_resource(cx, || (), {
+            let fetches = Rc::clone(&fetches);
+            move |_| {
+                fetches.set(fetches.get() + 1);
+                async { HashMap::<(u8, u8), u8>::new() }
+            }
+        });
+        tick().await;
+
+        assert!(resource.server_error().get().is_some());
+        assert_eq!(fetches.get(), 1);
+        assert_eq!(resource.read(cx), Some(HashMap::new()));
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn lazy_resources_share_one_load_and_suspend_until_it_finishes() {
+    use futures::channel::oneshot;
+    use leptos_reactive::{create_lazy_resource, Lazy, RenderMode};
+    use std::{cell::Cell, rc::Rc};
+
+    run_local(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+        // local resources are only loaded outside the server
+        cx.set_render_mode(RenderMode::Csr);
+
+        let loads = Rc::new(Cell::new(0));
+        let (tx, rx) = oneshot::channel::<&'static str>();
+        let lazy = Lazy::new({
+            let loads = Rc::clone(&loads);
+            move || {
+                loads.set(loads.get() + 1);
+                async move { rx.await.unwrap_or_default() }
+            }
+        });
+        assert_eq!(loads.get(), 0);
+
+        let (context, (first, second)) = cx
+            .run_child_scope(|cx| {
+                let context = SuspenseContext::new(cx);
+                provide_context(cx, context);
+                let first = create_lazy_resource(cx, &lazy);
+                let second = create_lazy_resource(cx, &lazy);
+                (context, (first.read(cx), second.read(cx)))
+            })
+            .0;
+        assert_eq!((first, second), (None, None));
+        assert_eq!(context.pending_resources.get(), 2);
+        assert_eq!(loads.get(), 1);
+
+        tx.send("loaded").unwrap();
+        tick().await;
+        assert_eq!(context.pending_resources.get(), 0);
+        assert_eq!(lazy.get(), Some("loaded"));
+
+        // once it has loaded, a new resource doesn't suspend at all
+        let (context, value) = cx
+            .run_child_scope(|cx| {
+                let context = SuspenseContext::new(cx);
+                provide_context(cx, context);
+                (context, create_lazy_resource(cx, &lazy).read(cx))
+            })
+            .0;
+        assert_eq!(value, Some("loaded"));
+        assert_eq!(context.pending_resources.get(), 0);
+        assert_eq!(loads.get(), 1);
+
+        disposer.dispose();
+    });
+}
@@ -0,0 +1,76 @@
+use leptos_reactive::{
+    create_isomorphic_effect, create_memo, create_runtime, create_rw_signal,
+    create_scope, on_cleanup, RuntimeStats, SignalGet,
+};
+
+#[test]
+fn disposing_a_child_scope_frees_what_it_owns() {
+    use std::{cell::RefCell, rc::Rc};
+
+    let runtime = create_runtime();
+    create_scope(runtime, move |cx| {
+        let cleanups = Rc::new(RefCell::new(Vec::new()));
+        let count = create_rw_signal(cx, 0);
+        let before = runtime.stats();
+
+        let (_, disposer) = cx.run_child_scope(|cx| {
+            let doubled = create_memo(cx, move |_| count.get() * 2);
+            create_isomorphic_effect(cx, move |_| _ = doubled.get());
+            on_cleanup(cx, {
+                let cleanups = Rc::clone(&cleanups);
+                move || cleanups.borrow_mut().push("outer")
+            });
+            cx.run_child_scope(|cx| {
+                create_rw_signal(cx, "nested");
+                on_cleanup(cx, {
+                    let cleanups = Rc::clone(&cleanups);
+                    move || cleanups.borrow_mut().push("inner")
+                });
+            });
+        });
+        assert_eq!(
+            runtime.stats(),
+            RuntimeStats {
+                scopes: before.scopes + 2,
+                // the memo's value, and the nested signal
+                signals: before.signals + 2,
+                // the memo's computation, and the effect
+                effects: before.effects + 2,
+                resources: before.resources,
+            }
+        );
+
+        disposer.dispose();
+        assert_eq!(runtime.stats(), before);
+        // children are disposed before their parents
+        assert_eq!(*cleanups.borrow(), ["inner", "outer"]);
+    })
+    .dispose();
+
+    assert_eq!(runtime.stats(), RuntimeStats::default());
+    runtime.dispose();
+}
+
+#[test]
+fn a_cleanup_can_dispose_another_scope() {
+    use std::{cell::Cell, rc::Rc};
+
+    let runtime = create_runtime();
+    create_scope(runtime, move |cx| {
+        let disposed = Rc::new(Cell::new(false));
+        let (_, other) = cx.run_child_scope({
+            let disposed = Rc::clone(&disposed);
+            move |cx| on_cleanup(cx, move || disposed.set(true))
+        });
+        let (_, owner) = cx.run_child_scope(move |cx| {
+            // e.g., a route whose scope goes away with the view showing it
+            on_cleanup(cx, move || other.dispose())
+        });
+
+        owner.dispose();
+        assert!(disposed.get());
+        assert_eq!(runtime.stats().scopes, 1);
+    })
+    .dispose();
+    runtime.dispose();
+}
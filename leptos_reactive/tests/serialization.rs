@@ -0,0 +1,45 @@
+use leptos_reactive::{
+    decode_resource_payload, encode_resource_payload, escape_script_payload,
+};
+
+#[test]
+fn escaped_script_payloads_round_trip() {
+    let payloads = [
+        "</script><script>alert(1)</script>",
+        "<!-- <script>",
+        "line\u{2028}separator\u{2029}paragraph",
+        "</SCRIPT >",
+    ];
+    for payload in payloads {
+        let json = serde_json::to_string(payload).unwrap();
+        let escaped = escape_script_payload(&json);
+        assert!(!escaped.contains('<'));
+        assert!(!escaped.contains(['\u{2028}', '\u{2029}']));
+        assert_eq!(serde_json::from_str::<String>(&escaped).unwrap(), payload);
+    }
+}
+
+#[test]
+fn safe_script_payloads_are_not_copied() {
+    let json = serde_json::to_string("plain > text & more").unwrap();
+    assert!(matches!(
+        escape_script_payload(&json),
+        std::borrow::Cow::Borrowed(_)
+    ));
+}
+
+#[test]
+fn resource_payloads_round_trip() {
+    for data in ["", "0000000000000001:42", "!+", "{\"Err\":\"oops\"}"] {
+        let ok = encode_resource_payload(Ok(data));
+        assert_eq!(decode_resource_payload(&ok), Ok(data));
+        let err = encode_resource_payload(Err(data));
+        assert_eq!(decode_resource_payload(&err), Err(data));
+    }
+}
+
+#[test]
+fn resource_payloads_without_envelope_are_ok() {
+    let legacy = "00000000000000ff:\"data\"";
+    assert_eq!(decode_resource_payload(legacy), Ok(legacy));
+}
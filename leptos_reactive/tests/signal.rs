@@ -28,3 +28,121 @@ fn derived_signals() {
     })
     .dispose()
 }
+
+#[cfg(all(debug_assertions, not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn signal_from_another_runtime_panics_while_its_scope_runs() {
+    use leptos_reactive::{
+        create_runtime, create_scope, create_signal, ReadSignal, SignalGet,
+    };
+    use std::{cell::Cell, panic};
+
+    thread_local! {
+        static LEAKED: Cell<Option<ReadSignal<i32>>> =
+            const { Cell::new(None) };
+    }
+
+    let first = create_runtime();
+    let disposer = create_scope(first, |cx| {
+        let (count, _) = create_signal(cx, 1);
+        LEAKED.with(|leaked| leaked.set(Some(count)));
+    });
+
+    let second = create_runtime();
+    let result = panic::catch_unwind(|| {
+        create_scope(second, |_| {
+            LEAKED.with(|leaked| leaked.get()).unwrap().get();
+        })
+        .dispose();
+    });
+    let message = result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("tests/signal.rs"));
+    assert!(message.contains(&format!("{first:?}")));
+    assert!(message.contains(&format!("{second:?}")));
+
+    // outside of any scope, there's nothing to compare it with
+    assert_eq!(LEAKED.with(|leaked| leaked.get()).unwrap().get(), 1);
+
+    disposer.dispose();
+    second.dispose();
+    first.dispose();
+}
+
+#[cfg(all(debug_assertions, not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn stored_value_from_another_runtime_panics_while_its_scope_runs() {
+    use leptos_reactive::{
+        create_runtime, create_scope, store_value, StoredValue,
+    };
+    use std::{cell::Cell, panic};
+
+    thread_local! {
+        static LEAKED: Cell<Option<StoredValue<i32>>> =
+            const { Cell::new(None) };
+    }
+
+    let first = create_runtime();
+    let disposer = create_scope(first, |cx| {
+        let value = store_value(cx, 1);
+        LEAKED.with(|leaked| leaked.set(Some(value)));
+    });
+
+    let second = create_runtime();
+    let result = panic::catch_unwind(|| {
+        create_scope(second, |_| {
+            LEAKED.with(|leaked| leaked.get()).unwrap().get_value();
+        })
+        .dispose();
+    });
+    // points at the `store_value` call, not at its implementation
+    let message = result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("tests/signal.rs"));
+
+    assert_eq!(LEAKED.with(|leaked| leaked.get()).unwrap().get_value(), 1);
+
+    disposer.dispose();
+    second.dispose();
+    first.dispose();
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn assert_runtime_isolated_accepts_independent_renders() {
+    use leptos_reactive::{
+        assert_runtime_isolated, create_memo, create_signal, SignalGet,
+        SignalUpdate,
+    };
+
+    assert_runtime_isolated(|cx| {
+        let (count, set_count) = create_signal(cx, 1);
+        let double = create_memo(cx, move |_| count.get() * 2);
+        set_count.update(|n| *n += 1);
+        double.get()
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+#[should_panic]
+fn assert_runtime_isolated_catches_state_shared_between_runtimes() {
+    use leptos_reactive::{
+        assert_runtime_isolated, create_rw_signal, RwSignal, SignalGet,
+        SignalUpdate,
+    };
+    use std::cell::Cell;
+
+    thread_local! {
+        static SHARED: Cell<Option<RwSignal<i32>>> =
+            const { Cell::new(None) };
+    }
+
+    assert_runtime_isolated(|cx| {
+        let count = SHARED.with(|shared| {
+            let count = shared.get().unwrap_or_else(|| create_rw_signal(cx, 0));
+            shared.set(Some(count));
+            count
+        });
+        count.update(|n| *n += 1);
+        count.get()
+    });
+}
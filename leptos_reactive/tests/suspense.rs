@@ -0,0 +1,565 @@
+#[cfg(all(
+    feature = "ssr",
+    not(any(feature = "csr", feature = "hydrate"))
+))]
+use leptos_reactive::{
+    create_memo, create_resource, create_runtime, provide_context,
+    raw_scope_and_disposer, RenderMode, SignalGet, SuspenseContext,
+};
+
+// yields enough times for any resource futures woken by a send to run
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+async fn tick() {
+    for _ in 0..10 {
+        tokio::task::yield_now().await;
+    }
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+fn run_local(fut: impl std::future::Future<Output = ()>) {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    tokio::task::LocalSet::new().block_on(&rt, fut);
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn resource_read_through_memo_registers_with_suspense() {
+    read_through_memo_registers_with_suspense(RenderMode::Ssr);
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn resource_read_through_memo_registers_with_suspense_in_the_browser() {
+    read_through_memo_registers_with_suspense(RenderMode::Hydrate);
+    read_through_memo_registers_with_suspense(RenderMode::Csr);
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+fn read_through_memo_registers_with_suspense(mode: RenderMode) {
+    use futures::channel::oneshot;
+    use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+    run_local(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+        cx.set_render_mode(mode);
+
+        let senders = Rc::new(RefCell::new(VecDeque::new()));
+        let resource = create_resource(cx, || (), {
+            let senders = Rc::clone(&senders);
+            move |_| {
+                let (tx, rx) = oneshot::channel::<usize>();
+                senders.borrow_mut().push_back(tx);
+                async move { rx.await.unwrap_or_default() }
+            }
+        });
+
+        // the memo is created outside the boundary, so the read inside it
+        // can't find the SuspenseContext through its Scope
+        let summary =
+            create_memo(cx, move |_| resource.read(cx).map(|n| n * 2));
+
+        let (context, _) = cx.run_child_scope(|cx| {
+            let context = SuspenseContext::new(cx);
+            provide_context(cx, context);
+            context.run_in_boundary(|| summary.get());
+            context
+        });
+        assert_eq!(context.pending_resources.get(), 1);
+
+        senders.borrow_mut().pop_front().unwrap().send(21).unwrap();
+        tick().await;
+        assert_eq!(summary.get(), Some(42));
+        assert_eq!(context.pending_resources.get(), 0);
+
+        // the memo recomputes after refetching, and the boundary is still
+        // notified without having to read it again
+        resource.refetch();
+        assert_eq!(context.pending_resources.get(), 1);
+
+        senders.borrow_mut().pop_front().unwrap().send(1).unwrap();
+        tick().await;
+        assert_eq!(summary.get(), Some(2));
+        assert_eq!(context.pending_resources.get(), 0);
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn a_boundary_that_panicked_does_not_track_later_reads() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    run_local(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+
+        let context = SuspenseContext::new(cx);
+        let panicked = catch_unwind(AssertUnwindSafe(|| {
+            context.run_in_boundary(|| panic!("rendering failed"))
+        }));
+        assert!(panicked.is_err());
+
+        // read outside of any boundary, so nothing is waiting for it
+        let never =
+            create_resource(cx, || (), |_| futures::future::pending::<i32>());
+        never.read(cx);
+        assert_eq!(context.pending_resources.get(), 0);
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn suspense_fragment_resolves_only_once_all_resources_are_done() {
+    use futures::FutureExt;
+
+    let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+
+    let context = SuspenseContext::new(cx);
+    context.increment(true);
+    cx.register_suspense(
+        context,
+        "0-0",
+        |_| "out-of-order".to_string(),
+        |_| Vec::new(),
+    );
+    let (mut out_of_order, mut in_order) =
+        cx.take_pending_fragment("0-0").unwrap();
+    assert!((&mut out_of_order).now_or_never().is_none());
+
+    // one resource resolves, then another starts before the fragment is
+    // polled again
+    context.decrement(true);
+    context.increment(true);
+    assert!((&mut out_of_order).now_or_never().is_none());
+    assert!((&mut in_order).now_or_never().is_none());
+
+    context.decrement(true);
+    assert_eq!(out_of_order.now_or_never().as_deref(), Some("out-of-order"));
+    assert!(in_order.now_or_never().is_some());
+
+    disposer.dispose();
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn suspense_stops_waking_its_fragments_once_they_resolve() {
+    use futures::FutureExt;
+
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+
+    let context = SuspenseContext::new(cx);
+    context.increment(true);
+    let effects = runtime.stats().effects;
+    cx.register_suspense(context, "0-0", |_| String::new(), |_| Vec::new());
+    assert_eq!(runtime.stats().effects, effects + 1);
+    let (out_of_order, in_order) = cx.take_pending_fragment("0-0").unwrap();
+
+    context.decrement(true);
+    assert!(out_of_order.now_or_never().is_some());
+    assert!(in_order.now_or_never().is_some());
+
+    // the effect that wakes the fragments runs once more, and is disposed,
+    // since nothing is waiting anymore
+    context.increment(true);
+    assert_eq!(runtime.stats().effects, effects);
+    context.decrement(true);
+    context.increment(true);
+    assert_eq!(runtime.stats().effects, effects);
+
+    disposer.dispose();
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn pending_fragments_keep_registration_order() {
+    let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+
+    let keys = ["0-3", "0-1", "0-2"];
+    for key in keys {
+        let context = SuspenseContext::new(cx);
+        context.increment(true);
+        cx.register_suspense(context, key, |_| String::new(), |_| Vec::new());
+    }
+
+    // inspecting the keys doesn't take the fragments
+    assert_eq!(cx.pending_fragment_keys(), keys);
+    assert_eq!(cx.pending_fragment_keys(), keys);
+
+    let drained = cx.pending_fragments().into_keys().collect::<Vec<_>>();
+    assert_eq!(drained, keys);
+    assert!(cx.pending_fragment_keys().is_empty());
+
+    disposer.dispose();
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn suspense_fragment_resolves_with_fallback_after_deadline() {
+    use futures::{channel::oneshot, FutureExt, StreamExt};
+
+    run_local(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+
+        // a mock timer, which runs out when `times_up` is sent
+        let (times_up, deadline) = oneshot::channel::<()>();
+        let deadline = deadline.shared();
+        cx.set_suspense_deadline(move || deadline.clone().map(|_| ()));
+
+        let context = SuspenseContext::new(cx);
+        provide_context(cx, context);
+        let never =
+            create_resource(cx, || (), |_| futures::future::pending::<i32>());
+        context.run_in_boundary(|| never.read(cx));
+        cx.register_suspense(
+            context,
+            "0-0",
+            |timed_out| {
+                if timed_out { "fallback" } else { "children" }.to_string()
+            },
+            |_| Vec::new(),
+        );
+        let (mut out_of_order, _) = cx.take_pending_fragment("0-0").unwrap();
+        let mut resolvers = cx.serialization_resolvers();
+
+        tick().await;
+        assert!((&mut out_of_order).now_or_never().is_none());
+
+        _ = times_up.send(());
+        assert_eq!(out_of_order.await, "fallback");
+        // the client is told to load the resource itself
+        let (_, data) = resolvers.next().await.unwrap();
+        assert_eq!(data, "unresolved");
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(not(any(feature = "ssr", feature = "csr", feature = "hydrate")))]
+#[test]
+fn suspense_context_tracks_pending_resources_without_ssr() {
+    use leptos_reactive::{
+        create_runtime, raw_scope_and_disposer, SignalGet, SuspenseContext,
+    };
+
+    let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+    let context = SuspenseContext::new(cx);
+    let none_pending = context.none_pending();
+    assert!(none_pending.get());
+
+    context.increment(false);
+    context.increment(true);
+    assert_eq!(context.pending_resources.get(), 2);
+    assert!(!none_pending.get());
+
+    context.decrement(true);
+    assert!(!none_pending.get());
+    context.decrement(false);
+    assert!(none_pending.get());
+
+    // resolving more often than loading saturates instead of wrapping
+    context.decrement(false);
+    assert_eq!(context.pending_resources.get(), 0);
+    assert!(none_pending.get());
+
+    disposer.dispose();
+}
+
+#[cfg(not(any(feature = "ssr", feature = "csr", feature = "hydrate")))]
+#[test]
+fn none_pending_only_notifies_when_it_changes() {
+    use leptos_reactive::{
+        create_effect, create_runtime, raw_scope_and_disposer, SignalGet,
+        SuspenseContext,
+    };
+    use std::{cell::Cell, rc::Rc};
+
+    let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+    let context = SuspenseContext::new(cx);
+    let runs = Rc::new(Cell::new(0));
+    create_effect(cx, {
+        let runs = Rc::clone(&runs);
+        move |_| {
+            context.none_pending().get();
+            runs.set(runs.get() + 1);
+        }
+    });
+    assert_eq!(runs.get(), 1);
+
+    context.increment(false);
+    context.increment(false);
+    context.decrement(false);
+    assert_eq!(runs.get(), 2);
+    context.decrement(false);
+    assert_eq!(runs.get(), 3);
+
+    disposer.dispose();
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn fragments_stream_yields_fragments_registered_while_resolving() {
+    use futures::StreamExt;
+
+    run_local(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+
+        let outer = SuspenseContext::new(cx);
+        let inner = SuspenseContext::new(cx);
+        inner.increment(true);
+        cx.register_suspense(
+            outer,
+            "0-1",
+            move |_| {
+                // rendering the outer fragment's children registers another
+                cx.register_suspense(
+                    inner,
+                    "0-1-1",
+                    |_| "inner".to_string(),
+                    |_| Vec::new(),
+                );
+                "outer".to_string()
+            },
+            |_| Vec::new(),
+        );
+
+        let mut fragments = cx.fragments_stream();
+        assert!(cx.pending_fragment_keys().is_empty());
+        assert_eq!(
+            fragments.next().await,
+            Some(("0-1".to_string(), "outer".to_string()))
+        );
+
+        inner.decrement(true);
+        assert_eq!(
+            fragments.next().await,
+            Some(("0-1-1".to_string(), "inner".to_string()))
+        );
+        assert_eq!(fragments.next().await, None);
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn suspense_context_distinguishes_first_load_from_refetch() {
+    use leptos_reactive::{
+        create_runtime, raw_scope_and_disposer, SuspenseContext,
+    };
+
+    let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+    let context = SuspenseContext::new(cx);
+
+    // the children are rendered while their resources are still loading
+    context.increment(true);
+    context.children_rendered();
+    assert!(!context.has_loaded());
+
+    // they're rendered again once the resources resolve
+    context.decrement(true);
+    context.children_rendered();
+    assert!(context.has_loaded());
+
+    // anything that's pending from now on is being refetched
+    context.increment(true);
+    assert!(context.has_loaded());
+    context.children_rendered();
+    assert!(context.has_loaded());
+
+    disposer.dispose();
+}
+
+// a runtime whose clock only moves when the test advances it
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+fn run_paused(fut: impl std::future::Future<Output = ()>) {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .start_paused(true)
+        .build()
+        .unwrap();
+    tokio::task::LocalSet::new().block_on(&rt, fut);
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+async fn advance(ms: u64) {
+    // lets timers that were just started register before the clock moves
+    tick().await;
+    tokio::time::advance(std::time::Duration::from_millis(ms)).await;
+    tick().await;
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+fn timing(delay: u64, min_duration: u64) -> leptos_reactive::FallbackTiming {
+    use std::time::Duration;
+
+    leptos_reactive::FallbackTiming {
+        delay: Duration::from_millis(delay),
+        min_duration: Duration::from_millis(min_duration),
+    }
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn fallback_is_not_shown_if_resources_resolve_within_the_delay() {
+    run_paused(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+        let context = SuspenseContext::new(cx);
+        let show_fallback = context.show_fallback(cx, timing(100, 300));
+
+        context.increment(true);
+        advance(99).await;
+        assert!(!show_fallback.get());
+        context.decrement(true);
+        assert!(!show_fallback.get());
+
+        // the delay running out afterward doesn't show it either
+        advance(500).await;
+        assert!(!show_fallback.get());
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn fallback_is_held_for_the_minimum_duration() {
+    run_paused(async {
+        let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+        let context = SuspenseContext::new(cx);
+        let show_fallback = context.show_fallback(cx, timing(100, 300));
+
+        context.increment(true);
+        advance(90).await;
+        assert!(!show_fallback.get());
+        advance(20).await;
+        assert!(show_fallback.get());
+
+        // resolving soon after it's shown still keeps it until 300ms later
+        advance(40).await;
+        context.decrement(true);
+        assert!(show_fallback.get());
+        advance(240).await;
+        assert!(show_fallback.get());
+        advance(20).await;
+        assert!(!show_fallback.get());
+
+        // resolving after the minimum hides it right away
+        context.increment(true);
+        advance(110).await;
+        assert!(show_fallback.get());
+        advance(1000).await;
+        assert!(show_fallback.get());
+        context.decrement(true);
+        assert!(!show_fallback.get());
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn default_fallback_timing_follows_pending_resources() {
+    let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+    let context = SuspenseContext::new(cx);
+    let show_fallback =
+        context.show_fallback(cx, leptos_reactive::FallbackTiming::default());
+
+    assert!(!show_fallback.get());
+    context.increment(true);
+    assert!(show_fallback.get());
+    context.decrement(true);
+    assert!(!show_fallback.get());
+
+    disposer.dispose();
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn disposing_the_scope_cancels_fallback_timers() {
+    run_paused(async {
+        let runtime = create_runtime();
+        let (cx, disposer) = raw_scope_and_disposer(runtime);
+        let context = SuspenseContext::new(cx);
+        let before = runtime.stats();
+
+        let (show_fallback, timers) = cx
+            .run_child_scope(|cx| context.show_fallback(cx, timing(100, 300)));
+        context.increment(true);
+        advance(50).await;
+        assert!(!show_fallback.get());
+
+        timers.dispose();
+        assert_eq!(runtime.stats(), before);
+        // the delay runs out after the signals it would have set are gone
+        advance(500).await;
+        assert_eq!(runtime.stats(), before);
+
+        disposer.dispose();
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn transitions_wait_for_the_boundaries_nested_in_them() {
+    use leptos_reactive::{
+        create_runtime, provide_context, raw_scope_and_disposer, SignalGet,
+        SuspenseContext,
+    };
+
+    let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+    let transition = SuspenseContext::new_transition(cx);
+    provide_context(cx, transition);
+
+    let (nested, nested_disposer) = cx.run_child_scope(SuspenseContext::new);
+    let (other, _) = cx.run_child_scope(SuspenseContext::new);
+    assert!(transition.ready());
+
+    // each pending boundary counts once, however many resources it has
+    nested.increment(true);
+    nested.increment(true);
+    other.increment(false);
+    assert_eq!(transition.pending_resources.get(), 2);
+
+    nested.decrement(true);
+    assert_eq!(transition.pending_resources.get(), 2);
+    nested.decrement(true);
+    assert_eq!(transition.pending_resources.get(), 1);
+
+    // a boundary that goes away while pending isn't waited for anymore
+    other.increment(false);
+    let (outer, outer_disposer) = cx.run_child_scope(|cx| {
+        let outer = SuspenseContext::new(cx);
+        provide_context(cx, outer);
+        // only the transition's own children are counted
+        SuspenseContext::new(cx).increment(true);
+        outer
+    });
+    assert!(outer.ready());
+    assert_eq!(transition.pending_resources.get(), 1);
+    nested.increment(true);
+    assert_eq!(transition.pending_resources.get(), 2);
+    nested_disposer.dispose();
+    outer_disposer.dispose();
+    assert_eq!(transition.pending_resources.get(), 1);
+
+    // a transition nested in another is one of its boundaries, too
+    let (inner, _) = cx.run_child_scope(SuspenseContext::new_transition);
+    inner.increment(true);
+    assert_eq!(transition.pending_resources.get(), 2);
+
+    // an ordinary context doesn't wait for the ones nested in it
+    let (plain, _) = cx.run_child_scope(|cx| {
+        let plain = SuspenseContext::new(cx);
+        provide_context(cx, plain);
+        SuspenseContext::new(cx).increment(true);
+        plain
+    });
+    assert!(plain.ready());
+
+    disposer.dispose();
+}
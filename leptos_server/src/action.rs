@@ -1,9 +1,15 @@
 use crate::{ServerFn, ServerFnError};
 use leptos_reactive::{
     create_rw_signal, signal_prelude::*, spawn_local, store_value, ReadSignal,
-    RwSignal, Scope, StoredValue,
+    Resource, RwSignal, Scope, StoredValue,
+};
+use server_fn::{with_upload_progress, UploadProgress};
+use std::{
+    cell::{Cell, RefCell},
+    future::Future,
+    pin::Pin,
+    rc::Rc,
 };
-use std::{future::Future, pin::Pin, rc::Rc};
 
 /// An action synchronizes an imperative `async` call to the synchronous reactive system.
 ///
@@ -89,6 +95,13 @@ where
     O: 'static,
 {
     /// Calls the `async` function with a reference to the input type as its argument.
+    ///
+    /// If the action is already pending, the new call replaces the previous one:
+    /// the earlier call's result is ignored when it resolves, and only the latest
+    /// one updates [Action::value] and [Action::version].
+    ///
+    /// Actions never run during server-side rendering, so this does nothing on
+    /// the server.
     pub fn dispatch(&self, input: I) {
         self.0.with_value(|a| a.dispatch(input))
     }
@@ -138,6 +151,174 @@ where
     pub fn value(&self) -> RwSignal<Option<O>> {
         self.0.with_value(|a| a.value)
     }
+
+    /// How much of the arguments of the latest call have been sent to the
+    /// server, from `0.0` to `1.0`, e.g., to show the progress of uploading
+    /// an [UploadedFile](server_fn::multipart::UploadedFile).
+    ///
+    /// It's updated as the server functions that the call makes report their
+    /// [UploadProgress], which they do in the browser, and is `1.0` once the
+    /// call has settled.
+    pub fn progress(&self) -> ReadSignal<f64> {
+        self.0.with_value(|a| a.progress.read_only())
+    }
+
+    /// Calls `f` with the result each time a call settles, i.e., once the
+    /// `async` function has resolved, just before its result is stored in
+    /// [Action::value].
+    ///
+    /// A call that's replaced by a newer one doesn't settle.
+    pub fn on_settled(self, f: impl Fn(&O) + 'static) -> Self {
+        let f = Rc::new(f);
+        self.on_dispatch(move |_| {
+            let f = Rc::clone(&f);
+            Box::new(move |value| f(value))
+        })
+    }
+
+    /// Refetches the resource each time a call settles, whether it
+    /// succeeded or not, e.g., to load the data that a server function
+    /// changed.
+    ///
+    /// ```rust
+    /// # use leptos::*;
+    /// # run_scope(create_runtime(), |cx| {
+    /// #[server(AddTodo)]
+    /// async fn add_todo(title: String) -> Result<(), ServerFnError> {
+    ///     todo!()
+    /// }
+    ///
+    /// let todos = create_resource(cx, || (), |_| async { Vec::<String>::new() });
+    /// let add_todo = create_server_action::<AddTodo>(cx).invalidates(todos);
+    /// # });
+    /// ```
+    pub fn invalidates<S, T>(self, resource: Resource<S, T>) -> Self
+    where
+        S: Clone + 'static,
+        T: 'static,
+    {
+        self.on_settled(move |_| resource.refetch())
+    }
+
+    // runs `hook` as each call is dispatched, and what it returns once the
+    // call settles
+    fn on_dispatch(
+        self,
+        hook: impl Fn(&I) -> Box<dyn FnOnce(&O)> + 'static,
+    ) -> Self {
+        self.0.update_value(|state| state.hooks.push(Rc::new(hook)));
+        self
+    }
+}
+
+impl<I, T, E> Action<I, Result<T, E>>
+where
+    I: 'static,
+    T: 'static,
+    E: 'static,
+{
+    /// The error of the most recent call, if it failed, or [Option::None]
+    /// if it succeeded or hasn't resolved yet.
+    pub fn error(&self) -> Signal<Option<E>>
+    where
+        E: Clone,
+    {
+        let value = self.value();
+        Signal::derive(self.0.with_value(|a| a.cx), move || {
+            value.with(|value| value.as_ref()?.as_ref().err().cloned())
+        })
+    }
+
+    /// Optimistically updates `target` as each call is dispatched, with a
+    /// prediction of what the call will change. If the call fails, `target`
+    /// is rolled back to what it was before, and if it succeeds, the
+    /// prediction stays until `target` is updated otherwise, e.g., by
+    /// refetching it with [Action::invalidates]. If something else has
+    /// updated `target` while the call was pending, that update is kept
+    /// instead of rolling it back.
+    ///
+    /// ```rust
+    /// # use leptos::*;
+    /// # run_scope(create_runtime(), |cx| {
+    /// #[server(AddTodo)]
+    /// async fn add_todo(title: String) -> Result<(), ServerFnError> {
+    ///     todo!()
+    /// }
+    ///
+    /// let todos = create_resource(cx, || (), |_| async { Vec::<String>::new() });
+    /// let add_todo = create_server_action::<AddTodo>(cx)
+    ///     .optimistic(
+    ///         todos,
+    ///         |input: &AddTodo, todos: &mut Option<Vec<String>>| {
+    ///             if let Some(todos) = todos {
+    ///                 todos.push(input.title.clone());
+    ///             }
+    ///         },
+    ///     )
+    ///     .invalidates(todos);
+    /// # });
+    /// ```
+    pub fn optimistic<V>(
+        self,
+        target: impl OptimisticTarget<V>,
+        predict: impl Fn(&I, &mut V) + 'static,
+    ) -> Self
+    where
+        V: Clone + PartialEq + 'static,
+    {
+        // what `target` was before the calls that haven't settled yet, of
+        // which only the most recent one will settle, and what it was after
+        // their predictions
+        let predicted = Rc::new(RefCell::new(None::<(V, V)>));
+        self.on_dispatch(move |input| {
+            target.update_value(|value| {
+                let mut predicted = predicted.borrow_mut();
+                let before = match predicted.take() {
+                    Some((before, after)) if after == *value => before,
+                    // updated since, so there's nothing to roll back to
+                    _ => value.clone(),
+                };
+                predict(input, value);
+                *predicted = Some((before, value.clone()));
+            });
+            let predicted = Rc::clone(&predicted);
+            Box::new(move |result| {
+                if let (Err(_), Some((before, after))) =
+                    (result, predicted.take())
+                {
+                    target.update_value(|value| {
+                        if *value == after {
+                            *value = before;
+                        }
+                    });
+                }
+            })
+        })
+    }
+}
+
+/// A value that an [Action] can update optimistically, with
+/// [Action::optimistic]: an [RwSignal], or the value of a [Resource], which
+/// is [Option::None] while it's pending.
+pub trait OptimisticTarget<T>: Copy + 'static {
+    /// Updates the value in place.
+    fn update_value(&self, f: impl FnOnce(&mut T));
+}
+
+impl<T> OptimisticTarget<T> for RwSignal<T> {
+    fn update_value(&self, f: impl FnOnce(&mut T)) {
+        SignalUpdate::update(self, f)
+    }
+}
+
+impl<S, T> OptimisticTarget<Option<T>> for Resource<S, T>
+where
+    S: Clone + 'static,
+    T: 'static,
+{
+    fn update_value(&self, f: impl FnOnce(&mut Option<T>)) {
+        self.update(f)
+    }
 }
 
 impl<I, O> Clone for Action<I, O>
@@ -170,11 +351,22 @@ where
     /// The most recent return value of the `async` function.
     pub value: RwSignal<Option<O>>,
     pending: RwSignal<bool>,
+    progress: RwSignal<f64>,
+    /// How many times the action has been dispatched, so that a call that's
+    /// been replaced by a newer one can be ignored when it resolves.
+    dispatched: Rc<Cell<usize>>,
     url: Option<String>,
+    cx: Scope,
     #[allow(clippy::complexity)]
     action_fn: Rc<dyn Fn(&I) -> Pin<Box<dyn Future<Output = O>>>>,
+    /// Run as each call is dispatched, returning what to run once it settles.
+    hooks: Vec<DispatchHook<I, O>>,
 }
 
+/// Run as a call to an [Action] is dispatched, returning what to run once
+/// the call settles.
+type DispatchHook<I, O> = Rc<dyn Fn(&I) -> Box<dyn FnOnce(&O)>>;
+
 impl<I, O> ActionState<I, O>
 where
     I: 'static,
@@ -182,17 +374,43 @@ where
 {
     /// Calls the `async` function with a reference to the input type as its argument.
     pub fn dispatch(&self, input: I) {
-        let fut = (self.action_fn)(&input);
+        // actions are only dispatched in the browser
+        if self.cx.is_server() {
+            return;
+        }
+
+        let progress = self.progress;
+        progress.set(0.0);
+        let fut = with_upload_progress(
+            UploadProgress::new(move |sent| _ = progress.try_set(sent)),
+            (self.action_fn)(&input),
+        );
+        let settled = self
+            .hooks
+            .iter()
+            .map(|hook| hook(&input))
+            .collect::<Vec<_>>();
         self.input.set(Some(input));
         let input = self.input;
         let version = self.version;
         let pending = self.pending;
         let value = self.value;
+        let dispatched = Rc::clone(&self.dispatched);
+        dispatched.set(dispatched.get() + 1);
+        let this_dispatch = dispatched.get();
         pending.set(true);
         spawn_local(async move {
             let new_value = fut.await;
+            // a newer call has replaced this one
+            if dispatched.get() != this_dispatch {
+                return;
+            }
+            for settled in settled {
+                settled(&new_value);
+            }
             value.set(Some(new_value));
             input.set(None);
+            progress.set(1.0);
             pending.set(false);
             version.update(|n| *n += 1);
         })
@@ -284,6 +502,7 @@ where
     let input = create_rw_signal(cx, None);
     let value = create_rw_signal(cx, None);
     let pending = create_rw_signal(cx, false);
+    let progress = create_rw_signal(cx, 0.0);
     let action_fn = Rc::new(move |input: &I| {
         let fut = action_fn(input);
         Box::pin(async move { fut.await }) as Pin<Box<dyn Future<Output = O>>>
@@ -294,10 +513,14 @@ where
         ActionState {
             version,
             url: None,
+            cx,
             input,
             value,
             pending,
+            progress,
+            dispatched: Default::default(),
             action_fn,
+            hooks: Vec::new(),
         },
     ))
 }
@@ -318,7 +541,7 @@ where
 /// ```
 pub fn create_server_action<S>(
     cx: Scope,
-) -> Action<S, Result<S::Output, ServerFnError>>
+) -> Action<S, Result<S::Output, ServerFnError<S::Error>>>
 where
     S: Clone + ServerFn,
 {
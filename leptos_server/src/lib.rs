@@ -67,19 +67,23 @@
 //!   function call.
 //! - **Server functions must return `Result<T, ServerFnError>`.** Even if the work being done
 //!   inside the function body can’t fail, the processes of serialization/deserialization and the
-//!   network call are fallible.
+//!   network call are fallible. They can also return errors of their own, as
+//!   `Result<T, ServerFnError<MyAppError>>`, where `MyAppError` implements [ServerFnCustomError].
 //! - **Return types must be [Serializable](leptos_reactive::Serializable).**
 //!   This should be fairly obvious: we have to serialize arguments to send them to the server, and we
 //!   need to deserialize the result to return it to the client.
 //! - **Arguments must be implement [serde::Serialize].** They are serialized as an `application/x-www-form-urlencoded`
-//!   form data using [`serde_urlencoded`](https://docs.rs/serde_urlencoded/latest/serde_urlencoded/) or as `application/cbor`
+//!   form data, as [`server_fn::form`](https://docs.rs/server_fn/latest/server_fn/form/index.html) describes, or as `application/cbor`
 //!   using [`cbor`](https://docs.rs/cbor/latest/cbor/).
 //! - **The [Scope](leptos_reactive::Scope) comes from the server.** Optionally, the first argument of a server function
 //!   can be a Leptos [Scope](leptos_reactive::Scope). This scope can be used to inject dependencies like the HTTP request
 //!   or response or other server-only dependencies, but it does *not* have access to reactive state that exists in the client.
 
 use leptos_reactive::*;
-pub use server_fn::{Encoding, Payload, ServerFnError};
+pub use server_fn::{
+    multipart::UploadedFile, Encoding, ErrorPayload, NoCustomError, Payload,
+    ServerFnCustomError, ServerFnError,
+};
 
 mod action;
 mod multi_action;
@@ -199,12 +203,14 @@ pub enum ServerRegistrationFnError {
 ///                 }
 ///             }
 ///             Err(e) => {
-///                 eprintln!("server function error: {e:#?}");
-///                 HttpResponse::InternalServerError().body(e.to_string())
+///                 eprintln!("server function error: {e}");
+///                 HttpResponse::build(StatusCode::from_u16(e.status).unwrap())
+///                     .content_type(e.payload.content_type())
+///                     .body(e.payload.into_bytes())
 ///             }
 ///         }
 ///     } else {
-///         HttpResponse::BadRequest().body(format!("Could not find a server function at that route."))
+///         HttpResponse::NotFound().body(format!("Could not find a server function at that route."))
 ///     }
 /// }
 /// ```
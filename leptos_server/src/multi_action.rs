@@ -94,6 +94,12 @@ where
     O: 'static,
 {
     /// Calls the `async` function with a reference to the input type as its argument.
+    ///
+    /// Each call creates a new [Submission], which stays in [MultiAction::submissions]
+    /// after it resolves, until it is removed with [MultiAction::clear_completed].
+    ///
+    /// Actions never run during server-side rendering, so this does nothing on
+    /// the server.
     pub fn dispatch(&self, input: I) {
         self.0.with_value(|a| a.dispatch(input))
     }
@@ -103,6 +109,11 @@ where
         self.0.with_value(|a| a.submissions())
     }
 
+    /// Removes every submission that is no longer pending from [MultiAction::submissions].
+    pub fn clear_completed(&self) {
+        self.0.with_value(|a| a.clear_completed())
+    }
+
     /// The URL associated with the action (typically as part of a server function.)
     /// This enables integration with the `MultiActionForm` component in `leptos_router`.
     pub fn url(&self) -> Option<String> {
@@ -196,6 +207,13 @@ where
 {
     /// Calls the `async` function with a reference to the input type as its argument.
     pub fn dispatch(&self, input: I) {
+        // actions are only dispatched in the browser
+        if self.cx.is_server() {
+            return;
+        }
+
+        // the submission's signals belong to the action's scope, not to
+        // whichever scope happened to dispatch it, so they outlive the latter
         let cx = self.cx;
         let fut = (self.action_fn)(&input);
 
@@ -222,7 +240,9 @@ where
             }
             input.set(None);
             pending.set(false);
-            version.update(|n| *n += 1);
+            if !canceled {
+                version.update(|n| *n += 1);
+            }
         })
     }
 
@@ -230,6 +250,12 @@ where
     pub fn submissions(&self) -> ReadSignal<Vec<Submission<I, O>>> {
         self.submissions.read_only()
     }
+
+    /// Removes every submission that is no longer pending.
+    pub fn clear_completed(&self) {
+        self.submissions
+            .update(|subs| subs.retain(|sub| sub.pending.get_untracked()));
+    }
 }
 
 /// Creates an [MultiAction] to synchronize an imperative `async` call to the synchronous reactive system.
@@ -328,7 +354,7 @@ where
 /// ```
 pub fn create_server_multi_action<S>(
     cx: Scope,
-) -> MultiAction<S, Result<S::Output, ServerFnError>>
+) -> MultiAction<S, Result<S::Output, ServerFnError<S::Error>>>
 where
     S: Clone + ServerFn,
 {
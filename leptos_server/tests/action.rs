@@ -0,0 +1,339 @@
+#[cfg(not(any(feature = "csr", feature = "hydrate", feature = "ssr")))]
+#[test]
+fn action_signals_follow_each_dispatch() {
+    use leptos_reactive::{
+        create_runtime, create_scope, SignalGetUntracked, SignalSet,
+    };
+    use leptos_server::create_action;
+    use std::{cell::RefCell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        // what the action's signals looked like while each call was running
+        let in_flight = Rc::new(RefCell::new(Vec::new()));
+        let action = create_action(cx, {
+            let in_flight = Rc::clone(&in_flight);
+            move |n: &usize| {
+                let n = *n;
+                let in_flight = Rc::clone(&in_flight);
+                async move {
+                    in_flight.borrow_mut().push(n);
+                    n * 2
+                }
+            }
+        });
+        let (pending, value, input, version) = (
+            action.pending(),
+            action.value(),
+            action.input(),
+            action.version(),
+        );
+        assert!(!pending.get_untracked());
+        assert_eq!(value.get_untracked(), None);
+        assert_eq!(input.get_untracked(), None);
+        assert_eq!(version.get_untracked(), 0);
+
+        action.dispatch(1);
+        assert!(!pending.get_untracked());
+        assert_eq!(value.get_untracked(), Some(2));
+        assert_eq!(input.get_untracked(), None);
+        assert_eq!(version.get_untracked(), 1);
+
+        action.dispatch(2);
+        assert_eq!(*in_flight.borrow(), [1, 2]);
+        assert_eq!(value.get_untracked(), Some(4));
+        assert_eq!(version.get_untracked(), 2);
+
+        // the version keeps counting resolved calls
+        version.set(10);
+        action.dispatch(3);
+        assert_eq!(version.get_untracked(), 11);
+    })
+    .dispose();
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn action_does_not_run_on_server() {
+    use leptos_reactive::{create_runtime, create_scope, SignalGetUntracked};
+    use leptos_server::create_action;
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let calls = Rc::new(Cell::new(0));
+        let action = create_action(cx, {
+            let calls = Rc::clone(&calls);
+            move |_: &()| {
+                calls.set(calls.get() + 1);
+                async {}
+            }
+        });
+
+        action.dispatch(());
+        assert_eq!(calls.get(), 0);
+        assert!(!action.pending().get_untracked());
+        assert_eq!(action.input().get_untracked(), None);
+        assert_eq!(action.version().get_untracked(), 0);
+    })
+    .dispose();
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn action_runs_when_the_runtime_renders_in_the_browser() {
+    use leptos_reactive::{
+        create_runtime, create_scope, RenderMode, SignalGetUntracked,
+    };
+    use leptos_server::create_action;
+    use std::{cell::Cell, rc::Rc};
+
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    tokio::task::LocalSet::new().block_on(&rt, async {
+        create_scope(create_runtime(), |cx| {
+            // e.g., in a binary that's built with both `ssr` and `hydrate`
+            cx.set_render_mode(RenderMode::Csr);
+            let calls = Rc::new(Cell::new(0));
+            let action = create_action(cx, {
+                let calls = Rc::clone(&calls);
+                move |_: &()| {
+                    calls.set(calls.get() + 1);
+                    async {}
+                }
+            });
+
+            action.dispatch(());
+            assert_eq!(calls.get(), 1);
+            assert!(action.pending().get_untracked());
+        })
+        .dispose();
+    });
+}
+
+// server actions only run in the browser, so these run them in a runtime
+// that renders in the browser, to call the functions directly
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+mod server_actions {
+    use leptos::*;
+    use std::{cell::RefCell, rc::Rc};
+
+    type Handler = Box<dyn Fn(String) -> Result<usize, ServerFnError>>;
+
+    thread_local! {
+        // the todos on the server
+        static TODOS: RefCell<Vec<String>> = Default::default();
+        // how the server answers each call
+        static SERVER: RefCell<Option<Handler>> = Default::default();
+    }
+
+    #[server]
+    pub async fn add_todo(title: String) -> Result<usize, ServerFnError> {
+        SERVER.with(|server| (server.borrow().as_ref().unwrap())(title))
+    }
+
+    fn answer_with(
+        handler: impl Fn(String) -> Result<usize, ServerFnError> + 'static,
+    ) {
+        SERVER.with(|server| *server.borrow_mut() = Some(Box::new(handler)));
+    }
+
+    // runs `f` in a runtime rendering in the browser, on a local executor
+    // that `tick` lets the action run on
+    fn run_in_browser<Fut>(f: impl FnOnce(Scope) -> Fut)
+    where
+        Fut: std::future::Future<Output = ()>,
+    {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        tokio::task::LocalSet::new().block_on(&rt, async {
+            let runtime = create_runtime();
+            let (cx, disposer) = raw_scope_and_disposer(runtime);
+            cx.set_render_mode(RenderMode::Csr);
+            f(cx).await;
+            disposer.dispose();
+            runtime.dispose();
+        });
+    }
+
+    async fn tick() {
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    fn todos(cx: Scope) -> Resource<(), Vec<String>> {
+        create_resource(
+            cx,
+            || (),
+            |_| async { TODOS.with(|todos| todos.borrow().clone()) },
+        )
+    }
+
+    fn add_to(todos: &mut Option<Vec<String>>, title: &str) {
+        if let Some(todos) = todos {
+            todos.push(format!("{title} (saving)"));
+        }
+    }
+
+    #[test]
+    fn settled_calls_invalidate_resources() {
+        TODOS.with(|todos| *todos.borrow_mut() = vec!["Nap".into()]);
+        answer_with(|title| {
+            TODOS.with(|todos| {
+                let mut todos = todos.borrow_mut();
+                todos.push(title);
+                Ok(todos.len())
+            })
+        });
+        run_in_browser(|cx| async move {
+            let todos = todos(cx);
+            let settled = Rc::new(RefCell::new(Vec::new()));
+            let add = create_server_action::<AddTodo>(cx)
+                .invalidates(todos)
+                .on_settled({
+                    let settled = Rc::clone(&settled);
+                    move |result| settled.borrow_mut().push(result.clone())
+                });
+            tick().await;
+            assert_eq!(todos.read(cx), Some(vec!["Nap".to_string()]));
+
+            add.dispatch(AddTodo {
+                title: "Buy milk".into(),
+            });
+            tick().await;
+            assert_eq!(
+                todos.read(cx),
+                Some(vec!["Nap".to_string(), "Buy milk".to_string()])
+            );
+            assert_eq!(add.value().get(), Some(Ok(2)));
+            assert_eq!(add.error().get(), None);
+            assert!(!add.pending().get());
+            assert_eq!(add.version().get(), 1);
+            assert_eq!(*settled.borrow(), [Ok(2)]);
+        });
+    }
+
+    #[test]
+    fn optimistic_updates_stay_until_the_resource_is_refetched() {
+        TODOS.with(|todos| *todos.borrow_mut() = vec!["Nap".into()]);
+        run_in_browser(|cx| async move {
+            let todos = todos(cx);
+            tick().await;
+            // what the page shows while the server handles the call
+            let seen = Rc::new(RefCell::new(Vec::new()));
+            answer_with({
+                let seen = Rc::clone(&seen);
+                move |title| {
+                    seen.borrow_mut().push(todos.read(cx));
+                    TODOS.with(|todos| todos.borrow_mut().push(title));
+                    Ok(2)
+                }
+            });
+            let add = create_server_action::<AddTodo>(cx)
+                .optimistic(todos, |input: &AddTodo, todos| {
+                    add_to(todos, &input.title)
+                })
+                .invalidates(todos);
+
+            add.dispatch(AddTodo {
+                title: "Buy milk".into(),
+            });
+            tick().await;
+            assert_eq!(
+                *seen.borrow(),
+                [Some(vec![
+                    "Nap".to_string(),
+                    "Buy milk (saving)".to_string()
+                ])]
+            );
+            assert_eq!(
+                todos.read(cx),
+                Some(vec!["Nap".to_string(), "Buy milk".to_string()])
+            );
+
+            // without refetching, the prediction stays
+            let count = create_rw_signal(cx, 0);
+            let add = create_server_action::<AddTodo>(cx)
+                .optimistic(count, |_, count| *count += 1);
+            add.dispatch(AddTodo {
+                title: "Buy eggs".into(),
+            });
+            tick().await;
+            assert_eq!(count.get(), 1);
+        });
+    }
+
+    #[test]
+    fn optimistic_updates_are_rolled_back_if_the_call_fails() {
+        TODOS.with(|todos| *todos.borrow_mut() = vec!["Nap".into()]);
+        run_in_browser(|cx| async move {
+            let todos = todos(cx);
+            tick().await;
+            let seen = Rc::new(RefCell::new(Vec::new()));
+            answer_with({
+                let seen = Rc::clone(&seen);
+                move |_| {
+                    seen.borrow_mut().push(todos.read(cx));
+                    Err(ServerFnError::ServerError("the disk is full".into()))
+                }
+            });
+            let count = create_rw_signal(cx, 0);
+            let add = create_server_action::<AddTodo>(cx)
+                .optimistic(todos, |input: &AddTodo, todos| {
+                    add_to(todos, &input.title)
+                })
+                .optimistic(count, |_, count| *count += 1);
+
+            add.dispatch(AddTodo {
+                title: "Buy milk".into(),
+            });
+            tick().await;
+            assert_eq!(
+                *seen.borrow(),
+                [Some(vec![
+                    "Nap".to_string(),
+                    "Buy milk (saving)".to_string()
+                ])]
+            );
+            assert_eq!(todos.read(cx), Some(vec!["Nap".to_string()]));
+            assert_eq!(count.get(), 0);
+            assert_eq!(
+                add.error().get(),
+                Some(ServerFnError::ServerError("the disk is full".into()))
+            );
+            assert_eq!(add.version().get(), 1);
+        });
+    }
+
+    #[test]
+    fn updates_made_while_the_call_is_pending_are_not_rolled_back() {
+        run_in_browser(|cx| async move {
+            let count = create_rw_signal(cx, 0);
+            answer_with(move |_| {
+                // e.g., another tab's change arriving meanwhile
+                count.set(10);
+                Err(ServerFnError::ServerError("the disk is full".into()))
+            });
+            let add = create_server_action::<AddTodo>(cx)
+                .optimistic(count, |_, count| *count += 1);
+
+            add.dispatch(AddTodo {
+                title: "Buy milk".into(),
+            });
+            tick().await;
+            assert_eq!(count.get(), 10);
+            assert!(add.error().get().is_some());
+
+            // once nothing else changes it, a failed call is rolled back
+            answer_with(|_| {
+                Err(ServerFnError::ServerError("the disk is full".into()))
+            });
+            add.dispatch(AddTodo {
+                title: "Buy eggs".into(),
+            });
+            tick().await;
+            assert_eq!(count.get(), 10);
+        });
+    }
+}
@@ -0,0 +1,132 @@
+use leptos::server_fn::form;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt::Debug};
+
+// encodes `value` as a form body, and reads it back
+fn round_trip<T>(value: &T) -> (String, T)
+where
+    T: Serialize + DeserializeOwned,
+{
+    let body = form::to_string(value).unwrap();
+    let decoded = form::from_bytes(body.as_bytes())
+        .unwrap_or_else(|e| panic!("couldn't decode {body:?}: {e}"));
+    (body, decoded)
+}
+
+fn assert_round_trips<T>(value: T)
+where
+    T: Debug + PartialEq + Serialize + DeserializeOwned,
+{
+    let (body, decoded) = round_trip(&value);
+    assert_eq!(decoded, value, "through {body:?}");
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Nested {
+    vv: Vec<Vec<i32>>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Scores {
+    scores: Vec<Option<i32>>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Maybe {
+    tags: Option<Vec<String>>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Keyed {
+    k: BTreeMap<String, i32>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Step {
+    title: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Steps {
+    steps: Vec<Step>,
+}
+
+#[test]
+fn nested_sequences_keep_their_items_together() {
+    let (body, decoded) = round_trip(&Nested {
+        vv: vec![vec![1, 2], vec![3]],
+    });
+    assert_eq!(body, "vv%5B0%5D=1&vv%5B0%5D=2&vv%5B1%5D=3");
+    assert_eq!(decoded.vv, [vec![1, 2], vec![3]]);
+
+    assert_round_trips(Nested {
+        vv: vec![vec![1], vec![2]],
+    });
+    assert_round_trips(Nested {
+        vv: vec![vec![1, 2]],
+    });
+    assert_round_trips(Nested { vv: vec![] });
+}
+
+#[test]
+fn none_items_keep_their_place() {
+    let (body, decoded) = round_trip(&Scores {
+        scores: vec![Some(1), None, Some(3)],
+    });
+    assert_eq!(body, "scores=1&scores=&scores=3");
+    assert_eq!(decoded.scores, [Some(1), None, Some(3)]);
+
+    assert_round_trips(Scores {
+        scores: vec![None, None],
+    });
+}
+
+#[test]
+fn single_field_items_are_numbered_with_the_others() {
+    assert_round_trips(Steps {
+        steps: vec![
+            Step {
+                title: "Lie down".into(),
+            },
+            Step {
+                title: "Sleep".into(),
+            },
+        ],
+    });
+    let (body, _) = round_trip(&Steps {
+        steps: vec![Step {
+            title: "Sleep".into(),
+        }],
+    });
+    assert_eq!(body, "steps%5B0%5D%5Btitle%5D=Sleep");
+}
+
+#[test]
+fn values_that_would_be_read_back_differently_are_errors() {
+    // `Some(vec![])` would be read back as `None`
+    assert!(form::to_string(&Maybe {
+        tags: Some(Vec::new())
+    })
+    .is_err());
+    assert_round_trips(Maybe { tags: None });
+    assert_round_trips(Maybe {
+        tags: Some(vec!["home".into()]),
+    });
+
+    // an empty item would be dropped
+    assert!(form::to_string(&Nested {
+        vv: vec![vec![], vec![3]],
+    })
+    .is_err());
+
+    // and brackets in a key would be read as other fields
+    for key in ["k[1]", "a]", ""] {
+        let keyed = Keyed {
+            k: BTreeMap::from([(key.to_string(), 1)]),
+        };
+        assert!(form::to_string(&keyed).is_err(), "{key:?}");
+    }
+    assert_round_trips(Keyed {
+        k: BTreeMap::from([("a b".to_string(), 1), ("c".to_string(), 2)]),
+    });
+}
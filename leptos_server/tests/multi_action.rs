@@ -0,0 +1,106 @@
+#[cfg(not(any(feature = "csr", feature = "hydrate", feature = "ssr")))]
+#[test]
+fn multi_action_keeps_submissions_until_cleared() {
+    use leptos_reactive::{create_runtime, create_scope, SignalGetUntracked};
+    use leptos_server::create_multi_action;
+
+    create_scope(create_runtime(), |cx| {
+        let add_todo = create_multi_action(cx, |task: &String| {
+            let len = task.len();
+            async move { len }
+        });
+        let submissions = add_todo.submissions();
+
+        // the submission's signals outlive the scope that dispatched it
+        cx.run_child_scope(|cx| {
+            _ = cx;
+            add_todo.dispatch("Buy milk".to_string());
+        })
+        .1
+        .dispose();
+        add_todo.dispatch("Profit!!!".to_string());
+
+        let subs = submissions.get_untracked();
+        assert_eq!(subs.len(), 2);
+        assert_eq!(subs[0].value.get_untracked(), Some(8));
+        assert_eq!(subs[1].value.get_untracked(), Some(9));
+        assert!(subs.iter().all(|sub| !sub.pending().get_untracked()));
+        assert!(subs.iter().all(|sub| sub.input.get_untracked().is_none()));
+        assert_eq!(add_todo.version().get_untracked(), 2);
+
+        add_todo.clear_completed();
+        assert!(submissions.get_untracked().is_empty());
+    })
+    .dispose();
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate", feature = "ssr")))]
+#[test]
+fn canceled_submission_does_not_resolve() {
+    use leptos_reactive::{create_runtime, create_scope, SignalGetUntracked};
+    use leptos_server::{create_multi_action, MultiAction};
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let action = Rc::new(Cell::new(None::<MultiAction<(), ()>>));
+        let add_todo = create_multi_action(cx, {
+            let action = Rc::clone(&action);
+            move |_: &()| {
+                let action = Rc::clone(&action);
+                async move {
+                    // cancel the submission while it's still in flight
+                    let action = action.get().unwrap();
+                    let subs = action.submissions().get_untracked();
+                    subs.last().unwrap().cancel();
+                }
+            }
+        });
+        action.set(Some(add_todo));
+
+        add_todo.dispatch(());
+        let subs = add_todo.submissions().get_untracked();
+        assert_eq!(subs.len(), 1);
+        assert!(subs[0].canceled.get_untracked());
+        assert_eq!(subs[0].value.get_untracked(), None);
+        assert!(!subs[0].pending().get_untracked());
+        assert_eq!(add_todo.version().get_untracked(), 0);
+    })
+    .dispose();
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn multi_action_does_not_run_on_server() {
+    use leptos_reactive::{create_runtime, create_scope, SignalGetUntracked};
+    use leptos_server::create_multi_action;
+
+    create_scope(create_runtime(), |cx| {
+        let add_todo = create_multi_action(cx, |_: &()| async {});
+        add_todo.dispatch(());
+        assert!(add_todo.submissions().get_untracked().is_empty());
+    })
+    .dispose();
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn multi_action_runs_when_the_runtime_renders_in_the_browser() {
+    use leptos_reactive::{
+        create_runtime, create_scope, RenderMode, SignalGetUntracked,
+    };
+    use leptos_server::create_multi_action;
+
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    tokio::task::LocalSet::new().block_on(&rt, async {
+        create_scope(create_runtime(), |cx| {
+            // e.g., in a binary that's built with both `ssr` and `hydrate`
+            cx.set_render_mode(RenderMode::Csr);
+            let add_todo = create_multi_action(cx, |_: &()| async {});
+            add_todo.dispatch(());
+            assert_eq!(add_todo.submissions().get_untracked().len(), 1);
+        })
+        .dispose();
+    });
+}
@@ -0,0 +1,1043 @@
+use leptos::{
+    server_fn::{ClientRequest, ClientTransport, Encoding},
+    UploadedFile, *,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, Wake},
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Todo {
+    title: String,
+    author: String,
+}
+
+// what a server integration would provide from the request
+#[cfg(feature = "ssr")]
+#[derive(Clone)]
+struct RequestUser(String);
+
+#[server]
+pub async fn add_todo(cx: Scope, title: String) -> Result<Todo, ServerFnError> {
+    let RequestUser(author) = use_context(cx).ok_or_else(|| {
+        ServerFnError::ServerError("no user in the request".into())
+    })?;
+    Ok(Todo { title, author })
+}
+
+#[server(CountWords, "/api", "Cbor")]
+pub async fn count_words(text: String) -> Result<usize, ServerFnError> {
+    Ok(text.split_whitespace().count())
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attachment {
+    name: String,
+    bytes: Vec<u8>,
+    caption: Option<String>,
+}
+
+#[server(encoding = "Json")]
+pub async fn echo_json(
+    attachment: Attachment,
+) -> Result<Attachment, ServerFnError> {
+    Ok(attachment)
+}
+
+#[server(prefix = "/api", encoding = "Cbor")]
+pub async fn echo_cbor(
+    attachment: Attachment,
+) -> Result<Attachment, ServerFnError> {
+    Ok(attachment)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, thiserror::Error)]
+pub enum TodoError {
+    #[error("there's already a todo called {0:?}")]
+    Duplicate(String),
+    #[error("titles can be at most {max} characters long")]
+    TooLong { max: usize },
+}
+
+impl ServerFnCustomError for TodoError {
+    fn status(&self) -> u16 {
+        match self {
+            TodoError::Duplicate(_) => 409,
+            TodoError::TooLong { .. } => 422,
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn check_title(title: &str) -> Result<(), TodoError> {
+    if title == "Buy milk" {
+        Err(TodoError::Duplicate(title.into()))
+    } else if title.len() > 20 {
+        Err(TodoError::TooLong { max: 20 })
+    } else {
+        Ok(())
+    }
+}
+
+#[server]
+pub async fn rename_todo(
+    title: String,
+) -> Result<String, ServerFnError<TodoError>> {
+    check_title(&title)?;
+    Ok(title)
+}
+
+#[server(encoding = "Cbor")]
+pub async fn rename_todo_cbor(
+    title: String,
+) -> Result<String, ServerFnError<TodoError>> {
+    check_title(&title)?;
+    Ok(title)
+}
+
+// a form's fields, with a binary result
+#[server(output = "Cbor")]
+pub async fn attach(
+    name: String,
+    caption: Option<String>,
+) -> Result<Attachment, ServerFnError> {
+    Ok(Attachment {
+        bytes: name.bytes().rev().collect(),
+        name,
+        caption,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Step {
+    title: String,
+    minutes: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Plan {
+    title: String,
+    steps: Vec<Step>,
+    tags: Vec<String>,
+    urgent: bool,
+}
+
+// a form's fields, with nested and repeated ones
+#[server]
+pub async fn plan_day(
+    title: String,
+    steps: Vec<Step>,
+    tags: Vec<String>,
+    urgent: bool,
+) -> Result<Plan, ServerFnError> {
+    Ok(Plan {
+        title,
+        steps,
+        tags,
+        urgent,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Album {
+    title: String,
+    photos: Vec<UploadedFile>,
+    cover: Option<UploadedFile>,
+}
+
+// a form's files, along with its other fields
+#[server(encoding = "Multipart")]
+pub async fn upload(
+    title: String,
+    photos: Vec<UploadedFile>,
+    cover: Option<UploadedFile>,
+) -> Result<Album, ServerFnError> {
+    Ok(Album {
+        title,
+        photos,
+        cover,
+    })
+}
+
+fn photos() -> Vec<UploadedFile> {
+    vec![
+        UploadedFile {
+            name: "plage à Nice.png".into(),
+            content_type: "image/png".into(),
+            bytes: vec![137, 80, 78, 71, 13, 10, 26, 10, 0, 255],
+        },
+        UploadedFile {
+            name: "notes \"draft\".txt".into(),
+            content_type: "text/plain".into(),
+            bytes: b"--the\r\n\r\nend".to_vec(),
+        },
+    ]
+}
+
+// the server functions here don't wait on anything
+fn block_on<T>(fut: impl Future<Output = T>) -> T {
+    struct Noop;
+
+    impl Wake for Noop {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    let waker = Arc::new(Noop).into();
+    let mut cx = Context::from_waker(&waker);
+    let mut fut = Box::pin(fut);
+    loop {
+        if let Poll::Ready(value) = fut.as_mut().poll(&mut cx) {
+            return value;
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+mod server {
+    use super::*;
+    use leptos::server_fn::{
+        call_server_fn_with, ClientResponse, ErrorPayload, Payload,
+    };
+
+    // routes the client's calls straight into the registry, with the scope
+    // of the request they're handled in
+    struct Registry(Scope);
+
+    impl ClientTransport for Registry {
+        fn send(
+            &self,
+            request: ClientRequest,
+        ) -> Pin<Box<dyn Future<Output = Result<ClientResponse, ServerFnError>>>>
+        {
+            let cx = self.0;
+            let ClientRequest { url, body, .. } = request;
+            // a server would mount the functions under their prefix
+            let path = url.trim_start_matches("/api").trim_start_matches('/');
+            let server_fn = leptos_server::server_fn_by_path(path);
+            Box::pin(async move {
+                let response =
+                    |status, status_text: &str, body| ClientResponse {
+                        status,
+                        status_text: status_text.into(),
+                        body,
+                    };
+                let Some(server_fn) = server_fn else {
+                    return Ok(response(
+                        404,
+                        "Not Found",
+                        b"Not Found".to_vec(),
+                    ));
+                };
+                Ok(match server_fn(cx, &body).await {
+                    Ok(Payload::Url(json) | Payload::Json(json)) => {
+                        response(200, "OK", json.into_bytes())
+                    }
+                    Ok(Payload::Binary(bytes)) => response(200, "OK", bytes),
+                    Err(e) => response(
+                        e.status,
+                        "Something Went Wrong",
+                        e.payload.into_bytes(),
+                    ),
+                })
+            })
+        }
+    }
+
+    // calls the function as its client stub would, but through the registry
+    fn call<F: leptos::ServerFn>(
+        cx: Scope,
+        args: F,
+    ) -> Result<F::Output, ServerFnError<F::Error>>
+    where
+        F::Output: serde::de::DeserializeOwned,
+    {
+        let url = format!("{}/{}", F::prefix(), F::url());
+        block_on(call_server_fn_with(
+            &Registry(cx),
+            &url,
+            args,
+            F::encoding(),
+        ))
+    }
+
+    fn register() {
+        // every test registers them, but only once
+        _ = AddTodo::register();
+        _ = CountWords::register();
+        _ = EchoJson::register();
+        _ = EchoCbor::register();
+        _ = Attach::register();
+        _ = RenameTodo::register();
+        _ = RenameTodoCbor::register();
+        _ = PlanDay::register();
+        _ = Upload::register();
+    }
+
+    // calls the function at `url` with an encoded body, as a server would
+    fn dispatch(url: &str, body: &[u8]) -> Result<Payload, ErrorPayload> {
+        let runtime = create_runtime();
+        let server_fn = leptos_server::server_fn_by_path(url).unwrap();
+        let body = body.to_vec();
+        let payload =
+            run_scope(runtime, move |cx| block_on(server_fn(cx, &body)));
+        runtime.dispose();
+        payload
+    }
+
+    fn attachments() -> [Attachment; 2] {
+        [
+            Attachment {
+                name: "photo.png".into(),
+                bytes: vec![0, 1, 127, 128, 255],
+                caption: Some("At the beach".into()),
+            },
+            Attachment {
+                name: "empty".into(),
+                bytes: Vec::new(),
+                caption: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn each_encoding_round_trips_bytes_and_options() {
+        register();
+        let runtime = create_runtime();
+        run_scope(runtime, |cx| {
+            for attachment in attachments() {
+                let echoed = call(
+                    cx,
+                    EchoJson {
+                        attachment: attachment.clone(),
+                    },
+                );
+                assert_eq!(echoed.unwrap(), attachment);
+
+                let echoed = call(
+                    cx,
+                    EchoCbor {
+                        attachment: attachment.clone(),
+                    },
+                );
+                assert_eq!(echoed.unwrap(), attachment);
+
+                // the bytes can't be URL-encoded, but they can be returned
+                let attached = call(
+                    cx,
+                    Attach {
+                        name: attachment.name.clone(),
+                        caption: attachment.caption.clone(),
+                    },
+                );
+                let attached = attached.unwrap();
+                assert_eq!(attached.caption, attachment.caption);
+                assert_eq!(
+                    attached.bytes,
+                    attachment.name.bytes().rev().collect::<Vec<_>>()
+                );
+            }
+        });
+        runtime.dispose();
+    }
+
+    #[test]
+    fn the_results_are_encoded_independently_of_the_arguments() {
+        register();
+        assert_eq!(EchoJson::encoding(), Encoding::Json);
+        assert_eq!(EchoJson::output_encoding(), Encoding::Json);
+        assert_eq!(EchoCbor::encoding(), Encoding::Cbor);
+        assert_eq!(EchoCbor::output_encoding(), Encoding::Cbor);
+        assert_eq!(AddTodo::encoding(), Encoding::Url);
+        assert_eq!(AddTodo::output_encoding(), Encoding::Json);
+        assert_eq!(Attach::encoding(), Encoding::Url);
+        assert_eq!(Attach::output_encoding(), Encoding::Cbor);
+
+        let payload = dispatch(Attach::url(), b"name=a.txt");
+        let Ok(Payload::Binary(bytes)) = payload else {
+            panic!("{payload:?}");
+        };
+        let attached: Attachment =
+            ciborium::de::from_reader(bytes.as_slice()).unwrap();
+        assert_eq!(attached.caption, None);
+
+        let payload = dispatch(
+            EchoJson::url(),
+            br#"{"attachment":{"name":"a","bytes":[1],"caption":null}}"#,
+        );
+        let Ok(Payload::Json(json)) = payload else {
+            panic!("{payload:?}");
+        };
+        assert_eq!(json, r#"{"name":"a","bytes":[1],"caption":null}"#);
+    }
+
+    #[test]
+    fn forms_can_call_functions_with_any_encoding() {
+        register();
+        // a <form> posts its fields URL-encoded, whatever the encoding is
+        let payload = dispatch(CountWords::url(), b"text=one+two");
+        let Ok(Payload::Binary(bytes)) = payload else {
+            panic!("{payload:?}");
+        };
+        let count: usize = ciborium::de::from_reader(bytes.as_slice()).unwrap();
+        assert_eq!(count, 2);
+
+        // but a body that's neither is still an error
+        let payload = dispatch(EchoJson::url(), b"{not json");
+        let Err(ErrorPayload {
+            status: 400,
+            payload: Payload::Json(json),
+            ..
+        }) = payload
+        else {
+            panic!("{payload:?}");
+        };
+        let error: ServerFnError = serde_json::from_str(&json).unwrap();
+        assert!(matches!(error, ServerFnError::Args(_)), "{error:?}");
+    }
+
+    #[test]
+    fn forms_can_post_nested_and_repeated_fields() {
+        register();
+        let plan = |body: &[u8]| {
+            let payload = dispatch(PlanDay::url(), body);
+            let Ok(Payload::Json(json)) = payload else {
+                panic!("{payload:?}");
+            };
+            serde_json::from_str::<Plan>(&json).unwrap()
+        };
+        let step = |title: &str, minutes| Step {
+            title: title.into(),
+            minutes,
+        };
+
+        let posted = plan(
+            b"title=Nap&tags[]=home&tags[]=sofa&steps[1][title]=Sleep\
+              &steps[0][title]=Lie+down&steps[0][minutes]=5\
+              &steps[1][minutes]=&urgent=on",
+        );
+        assert_eq!(
+            posted,
+            Plan {
+                title: "Nap".into(),
+                steps: vec![step("Lie down", Some(5)), step("Sleep", None)],
+                tags: vec!["home".into(), "sofa".into()],
+                urgent: true,
+            }
+        );
+        // a field can be repeated without brackets too
+        assert_eq!(plan(b"title=Nap&tags=home&tags=sofa").tags, posted.tags);
+
+        // the fields that are left out are empty, as unchecked boxes are
+        let posted = plan(b"title=Nap");
+        assert_eq!(posted.steps, []);
+        assert!(posted.tags.is_empty());
+        assert!(!posted.urgent);
+
+        // and the client sends them the same way
+        let runtime = create_runtime();
+        run_scope(runtime, move |cx| {
+            let plan = Plan {
+                title: "Nap".into(),
+                steps: vec![step("Lie down", Some(5)), step("Sleep", None)],
+                tags: vec!["home".into()],
+                urgent: false,
+            };
+            let called = call(
+                cx,
+                PlanDay {
+                    title: plan.title.clone(),
+                    steps: plan.steps.clone(),
+                    tags: plan.tags.clone(),
+                    urgent: plan.urgent,
+                },
+            );
+            assert_eq!(called.unwrap(), plan);
+        });
+        runtime.dispose();
+    }
+
+    #[test]
+    fn files_are_uploaded_along_with_the_other_fields() {
+        register();
+        let runtime = create_runtime();
+        run_scope(runtime, |cx| {
+            let album = call(
+                cx,
+                Upload {
+                    title: "Holidays".into(),
+                    photos: photos(),
+                    cover: None,
+                },
+            );
+            assert_eq!(
+                album.unwrap(),
+                Album {
+                    title: "Holidays".into(),
+                    photos: photos(),
+                    cover: None,
+                }
+            );
+        });
+        runtime.dispose();
+    }
+
+    #[test]
+    fn forms_can_post_files_as_browsers_do() {
+        register();
+        // a <form enctype="multipart/form-data"> with an empty file input
+        let body = [
+            "------WebKitFormBoundaryx7Fz",
+            r#"Content-Disposition: form-data; name="title""#,
+            "",
+            "Holidays",
+            "------WebKitFormBoundaryx7Fz",
+            r#"Content-Disposition: form-data; name="photos"; filename="plage à Nice.png""#,
+            "Content-Type: image/png",
+            "",
+            "PNG",
+            "------WebKitFormBoundaryx7Fz",
+            r#"Content-Disposition: form-data; name="cover"; filename="""#,
+            "Content-Type: application/octet-stream",
+            "",
+            "",
+            "------WebKitFormBoundaryx7Fz--",
+            "",
+        ]
+        .join("\r\n");
+        let payload = dispatch(Upload::url(), body.as_bytes());
+        let Ok(Payload::Json(json)) = payload else {
+            panic!("{payload:?}");
+        };
+        let album: Album = serde_json::from_str(&json).unwrap();
+        assert_eq!(album.title, "Holidays");
+        assert_eq!(
+            album.photos,
+            [UploadedFile {
+                name: "plage à Nice.png".into(),
+                content_type: "image/png".into(),
+                bytes: b"PNG".to_vec(),
+            }]
+        );
+        assert_eq!(album.cover, None);
+
+        // or without any files, URL-encoded
+        let payload = dispatch(Upload::url(), b"title=Holidays");
+        let Ok(Payload::Json(json)) = payload else {
+            panic!("{payload:?}");
+        };
+        let album: Album = serde_json::from_str(&json).unwrap();
+        assert_eq!(album.photos, []);
+    }
+
+    #[test]
+    fn typed_errors_round_trip_through_each_encoding() {
+        register();
+        let runtime = create_runtime();
+        run_scope(runtime, |cx| {
+            let duplicate =
+                ServerFnError::Custom(TodoError::Duplicate("Buy milk".into()));
+            let too_long =
+                ServerFnError::Custom(TodoError::TooLong { max: 20 });
+
+            let renamed = call(
+                cx,
+                RenameTodo {
+                    title: "Buy milk".into(),
+                },
+            );
+            assert_eq!(renamed, Err(duplicate.clone()));
+            let renamed = call(
+                cx,
+                RenameTodo {
+                    title: "Buy milk, eggs and bread".into(),
+                },
+            );
+            assert_eq!(renamed, Err(too_long.clone()));
+
+            let renamed = call(
+                cx,
+                RenameTodoCbor {
+                    title: "Buy milk".into(),
+                },
+            );
+            assert_eq!(renamed, Err(duplicate));
+            let renamed = call(
+                cx,
+                RenameTodoCbor {
+                    title: "Buy milk, eggs and bread".into(),
+                },
+            );
+            assert_eq!(renamed, Err(too_long));
+
+            let renamed = call(
+                cx,
+                RenameTodoCbor {
+                    title: "Buy eggs".into(),
+                },
+            );
+            assert_eq!(renamed, Ok("Buy eggs".to_string()));
+        });
+        runtime.dispose();
+    }
+
+    #[test]
+    fn typed_errors_are_sent_with_their_own_status() {
+        register();
+        let payload = dispatch(RenameTodo::url(), b"title=Buy+milk");
+        let Err(error) = payload else {
+            panic!("{payload:?}");
+        };
+        assert_eq!(error.status, 409);
+        assert_eq!(error.payload.content_type(), "application/json");
+        assert_eq!(
+            error.message,
+            r#"there's already a todo called "Buy milk""#
+        );
+
+        let payload = dispatch(
+            RenameTodoCbor::url(),
+            b"title=Buy+milk%2C+eggs+and+bread",
+        );
+        let Err(error) = payload else {
+            panic!("{payload:?}");
+        };
+        assert_eq!(error.status, 422);
+        assert_eq!(error.payload.content_type(), "application/cbor");
+
+        // and the rest with theirs
+        let payload = dispatch(AddTodo::url(), b"title=Nap");
+        assert!(matches!(payload, Err(ErrorPayload { status: 500, .. })));
+        let payload = dispatch(RenameTodo::url(), b"");
+        assert!(matches!(payload, Err(ErrorPayload { status: 400, .. })));
+    }
+
+    #[test]
+    fn each_error_crosses_the_transport() {
+        register();
+        let runtime = create_runtime();
+        run_scope(runtime, |cx| {
+            // an error of the function's
+            let todo = call(
+                cx,
+                AddTodo {
+                    title: "Nap".into(),
+                },
+            );
+            assert_eq!(
+                todo,
+                Err(ServerFnError::ServerError(
+                    "no user in the request".into()
+                ))
+            );
+
+            // arguments the function can't decode
+            let todo = block_on(call_server_fn_with::<Todo, _, _>(
+                &Registry(cx),
+                &format!("/{}", AddTodo::url()),
+                EchoJson {
+                    attachment: attachments()[1].clone(),
+                },
+                Encoding::Json,
+            ));
+            assert!(matches!(todo, Err(ServerFnError::Args(_))), "{todo:?}");
+
+            // a function that doesn't exist
+            let todo = block_on(call_server_fn_with::<Todo, _, _>(
+                &Registry(cx),
+                "/nothing",
+                AddTodo {
+                    title: "Nap".into(),
+                },
+                Encoding::Url,
+            ));
+            assert_eq!(
+                todo,
+                Err(ServerFnError::NotRegistered("/nothing".into()))
+            );
+        });
+        runtime.dispose();
+    }
+
+    #[test]
+    fn calls_go_through_the_registry_and_back() {
+        register();
+        let runtime = create_runtime();
+        run_scope(runtime, |cx| {
+            provide_context(cx, RequestUser("Greg".into()));
+            let todo = call(
+                cx,
+                AddTodo {
+                    title: "Buy milk & eggs".into(),
+                },
+            );
+            assert_eq!(
+                todo.unwrap(),
+                Todo {
+                    title: "Buy milk & eggs".into(),
+                    author: "Greg".into(),
+                }
+            );
+
+            let count = call(
+                cx,
+                CountWords {
+                    text: "one two  three".into(),
+                },
+            );
+            assert_eq!(count.unwrap(), 3);
+        });
+        runtime.dispose();
+    }
+
+    #[test]
+    fn server_fns_read_the_context_of_the_request() {
+        register();
+        let runtime = create_runtime();
+        run_scope(runtime, |cx| {
+            // without the context, the function fails on the server
+            let todo = call(
+                cx,
+                AddTodo {
+                    title: "Nap".into(),
+                },
+            );
+            assert!(
+                matches!(todo, Err(ServerFnError::ServerError(_))),
+                "{todo:?}"
+            );
+        });
+        runtime.dispose();
+    }
+
+    #[test]
+    fn server_fns_are_registered_at_stable_urls() {
+        register();
+        // named after the function, unless they're given another name
+        assert!(AddTodo::url().starts_with("add_todo"));
+        assert!(CountWords::url().starts_with("count_words"));
+        assert_eq!(AddTodo::url(), AddTodo::url());
+        assert_ne!(AddTodo::url(), CountWords::url());
+        assert_eq!(CountWords::prefix(), "/api");
+        assert!(leptos_server::server_fns_by_path().contains(&AddTodo::url()));
+        assert!(leptos_server::server_fn_by_path(CountWords::url()).is_some());
+        assert!(leptos_server::server_fn_by_path("nothing").is_none());
+    }
+}
+
+#[cfg(not(feature = "ssr"))]
+mod client {
+    use super::*;
+    use leptos::server_fn::{set_client_transport, ClientResponse};
+    use std::{cell::RefCell, rc::Rc};
+
+    // what the client sent, and what the server answers with
+    #[derive(Clone, Default)]
+    struct Recorder {
+        sent: Rc<RefCell<Vec<ClientRequest>>>,
+        response: Rc<RefCell<Option<ClientResponse>>>,
+    }
+
+    impl ClientTransport for Recorder {
+        fn send(
+            &self,
+            request: ClientRequest,
+        ) -> Pin<Box<dyn Future<Output = Result<ClientResponse, ServerFnError>>>>
+        {
+            self.sent.borrow_mut().push(request);
+            let response = self.response.borrow_mut().take();
+            Box::pin(async move {
+                response.ok_or_else(|| ServerFnError::Request("offline".into()))
+            })
+        }
+    }
+
+    fn answer(recorder: &Recorder, status: u16, body: Vec<u8>) {
+        *recorder.response.borrow_mut() = Some(ClientResponse {
+            status,
+            status_text: if status == 200 {
+                "OK"
+            } else {
+                "Internal Server Error"
+            }
+            .into(),
+            body,
+        });
+    }
+
+    #[test]
+    fn the_client_stub_posts_its_arguments_and_reads_the_result() {
+        let recorder = Recorder::default();
+        set_client_transport(recorder.clone());
+        let runtime = create_runtime();
+        run_scope(runtime, move |cx| {
+            answer(
+                &recorder,
+                200,
+                br#"{"title":"Buy milk","author":"Greg"}"#.to_vec(),
+            );
+            let todo = block_on(add_todo(cx, "Buy milk".into()));
+            assert_eq!(
+                todo.unwrap(),
+                Todo {
+                    title: "Buy milk".into(),
+                    author: "Greg".into(),
+                }
+            );
+            let request = recorder.sent.borrow_mut().remove(0);
+            assert_eq!(request.url, format!("/{}", AddTodo::url()));
+            assert_eq!(request.encoding, Encoding::Url);
+            assert_eq!(request.accept, Encoding::Json);
+            assert_eq!(request.body, b"title=Buy+milk");
+
+            let mut three = Vec::new();
+            ciborium::ser::into_writer(&3usize, &mut three).unwrap();
+            answer(&recorder, 200, three);
+            let count = block_on(count_words("a b c".into()));
+            assert_eq!(count.unwrap(), 3);
+            let request = recorder.sent.borrow_mut().remove(0);
+            assert_eq!(request.url, format!("/api/{}", CountWords::url()));
+            assert_eq!(request.encoding.content_type(), "application/cbor");
+            assert_eq!(request.accept, Encoding::Cbor);
+        });
+        runtime.dispose();
+    }
+
+    #[test]
+    fn the_client_stub_encodes_as_the_function_declares() {
+        let recorder = Recorder::default();
+        set_client_transport(recorder.clone());
+        let attachment = Attachment {
+            name: "photo.png".into(),
+            bytes: vec![0, 255],
+            caption: None,
+        };
+
+        answer(&recorder, 200, serde_json::to_vec(&attachment).unwrap());
+        let echoed = block_on(echo_json(attachment.clone()));
+        assert_eq!(echoed.unwrap(), attachment);
+        let request = recorder.sent.borrow_mut().remove(0);
+        assert_eq!(request.encoding.content_type(), "application/json");
+        assert_eq!(request.accept, Encoding::Json);
+        let sent: EchoJson = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(sent.attachment, attachment);
+
+        let mut encoded = Vec::new();
+        ciborium::ser::into_writer(&attachment, &mut encoded).unwrap();
+        answer(&recorder, 200, encoded);
+        let attached =
+            block_on(attach("photo.png".into(), Some("At the beach".into())));
+        assert_eq!(attached.unwrap(), attachment);
+        let request = recorder.sent.borrow_mut().remove(0);
+        assert_eq!(request.encoding, Encoding::Url);
+        assert_eq!(request.accept, Encoding::Cbor);
+        assert_eq!(request.body, b"name=photo.png&caption=At+the+beach");
+    }
+
+    #[test]
+    fn errors_from_the_server_and_the_transport_are_returned() {
+        let recorder = Recorder::default();
+        set_client_transport(recorder.clone());
+        let runtime = create_runtime();
+        run_scope(runtime, move |cx| {
+            // what the server sends for the errors that it returns
+            let error = ServerFnError::<NoCustomError>::ServerError(
+                "no user in the request".into(),
+            );
+            answer(&recorder, 500, serde_json::to_vec(&error).unwrap());
+            let todo = block_on(add_todo(cx, "Nap".into()));
+            assert_eq!(todo, Err(error));
+
+            // and for anything else that fails along the way
+            answer(&recorder, 502, b"Bad Gateway".to_vec());
+            let todo = block_on(add_todo(cx, "Nap".into()));
+            assert_eq!(
+                todo,
+                Err(ServerFnError::ServerError("Internal Server Error".into()))
+            );
+            answer(&recorder, 404, b"Not Found".to_vec());
+            let todo = block_on(add_todo(cx, "Nap".into()));
+            assert_eq!(
+                todo,
+                Err(ServerFnError::NotRegistered(format!(
+                    "/{}",
+                    AddTodo::url()
+                )))
+            );
+
+            answer(&recorder, 200, b"not json".to_vec());
+            let todo = block_on(add_todo(cx, "Nap".into()));
+            assert!(
+                matches!(todo, Err(ServerFnError::Deserialization(_))),
+                "{todo:?}"
+            );
+
+            // no response at all
+            let todo = block_on(add_todo(cx, "Nap".into()));
+            assert!(matches!(todo, Err(ServerFnError::Request(_))), "{todo:?}");
+        });
+        runtime.dispose();
+    }
+
+    #[test]
+    fn the_client_stub_returns_typed_errors() {
+        let recorder = Recorder::default();
+        set_client_transport(recorder.clone());
+        let duplicate =
+            ServerFnError::Custom(TodoError::Duplicate("Buy milk".into()));
+
+        answer(&recorder, 409, serde_json::to_vec(&duplicate).unwrap());
+        let renamed = block_on(rename_todo("Buy milk".into()));
+        assert_eq!(renamed, Err(duplicate.clone()));
+
+        let mut encoded = Vec::new();
+        ciborium::ser::into_writer(&duplicate, &mut encoded).unwrap();
+        answer(&recorder, 409, encoded);
+        let renamed = block_on(rename_todo_cbor("Buy milk".into()));
+        assert_eq!(renamed, Err(duplicate));
+    }
+
+    // sends the body in two halves, as a browser reports its progress
+    struct Uploader {
+        sent: Rc<RefCell<Vec<ClientRequest>>>,
+        progress: ReadSignal<f64>,
+        seen: Rc<RefCell<Vec<f64>>>,
+    }
+
+    impl ClientTransport for Uploader {
+        fn send(
+            &self,
+            request: ClientRequest,
+        ) -> Pin<Box<dyn Future<Output = Result<ClientResponse, ServerFnError>>>>
+        {
+            let progress = request.progress.clone().expect("no progress");
+            for sent in [0.5, 1.0] {
+                progress.report(sent);
+                self.seen.borrow_mut().push(self.progress.get_untracked());
+            }
+            let Upload { title, photos, .. } =
+                leptos::server_fn::multipart::from_body(&request.body).unwrap();
+            let album = Album {
+                title,
+                photos,
+                cover: None,
+            };
+            self.sent.borrow_mut().push(request);
+            let response = ClientResponse {
+                status: 200,
+                status_text: "OK".into(),
+                body: serde_json::to_vec(&album).unwrap(),
+            };
+            Box::pin(async move { Ok(response) })
+        }
+    }
+
+    #[test]
+    fn actions_report_the_progress_of_their_uploads() {
+        let runtime = create_runtime();
+        run_scope(runtime, move |cx| {
+            let upload = create_server_action::<Upload>(cx);
+            let sent = Rc::new(RefCell::new(Vec::new()));
+            let seen = Rc::new(RefCell::new(Vec::new()));
+            set_client_transport(Uploader {
+                sent: Rc::clone(&sent),
+                progress: upload.progress(),
+                seen: Rc::clone(&seen),
+            });
+            assert_eq!(upload.progress().get(), 0.0);
+
+            upload.dispatch(Upload {
+                title: "Holidays".into(),
+                photos: photos(),
+                cover: None,
+            });
+            assert_eq!(*seen.borrow(), [0.5, 1.0]);
+            assert_eq!(upload.progress().get(), 1.0);
+            assert_eq!(
+                upload.value().get(),
+                Some(Ok(Album {
+                    title: "Holidays".into(),
+                    photos: photos(),
+                    cover: None,
+                }))
+            );
+
+            let request = sent.borrow_mut().remove(0);
+            assert_eq!(request.encoding, Encoding::Multipart);
+            assert_eq!(request.accept, Encoding::Json);
+            assert!(
+                request
+                    .content_type
+                    .starts_with("multipart/form-data; boundary="),
+                "{}",
+                request.content_type
+            );
+            let boundary = request.content_type.split("boundary=").nth(1);
+            assert!(request.body.starts_with(
+                format!("--{}\r\n", boundary.unwrap()).as_bytes()
+            ));
+            let body = String::from_utf8_lossy(&request.body);
+            assert!(
+                body.contains(r#"name="photos"; filename="plage à Nice.png""#),
+                "{body}"
+            );
+            assert!(
+                body.contains("filename=\"notes %22draft%22.txt\""),
+                "{body}"
+            );
+        });
+        runtime.dispose();
+    }
+
+    #[test]
+    fn a_failed_action_renders_its_error_in_an_error_boundary() {
+        let recorder = Recorder::default();
+        set_client_transport(recorder.clone());
+        let runtime = create_runtime();
+        run_scope(runtime, move |cx| {
+            // what an <ErrorBoundary/> provides
+            let errors = create_rw_signal(cx, Errors::default());
+            provide_context(cx, errors);
+
+            let rename = create_server_action::<RenameTodo>(cx);
+
+            answer(
+                &recorder,
+                409,
+                serde_json::to_vec(&ServerFnError::Custom(
+                    TodoError::Duplicate("Buy milk".into()),
+                ))
+                .unwrap(),
+            );
+            rename.dispatch(RenameTodo {
+                title: "Buy milk".into(),
+            });
+            // as the action's value is rendered, after each dispatch
+            _ = rename.value().get().into_view(cx);
+            errors.with(|errors| {
+                let (_, error) = errors.iter().next().expect("no errors");
+                assert_eq!(
+                    error.to_string(),
+                    r#"there's already a todo called "Buy milk""#
+                );
+                assert_eq!(
+                    error.downcast_ref::<ServerFnError<TodoError>>(),
+                    Some(&ServerFnError::Custom(TodoError::Duplicate(
+                        "Buy milk".into()
+                    )))
+                );
+            });
+
+            answer(&recorder, 200, br#""Buy eggs""#.to_vec());
+            rename.dispatch(RenameTodo {
+                title: "Buy eggs".into(),
+            });
+            _ = rename.value().get().into_view(cx);
+            assert!(errors.with(Errors::is_empty));
+        });
+        runtime.dispose();
+    }
+}
@@ -0,0 +1,65 @@
+// the upload limit is shared by the whole process, so the test that changes
+// it has a binary of its own
+#![cfg(feature = "ssr")]
+
+use leptos::{
+    server_fn::{multipart, ErrorPayload, Payload},
+    UploadedFile, *,
+};
+
+#[server(encoding = "Multipart")]
+pub async fn upload(photos: Vec<UploadedFile>) -> Result<usize, ServerFnError> {
+    Ok(photos.len())
+}
+
+// calls the function with an encoded body, as a server would
+fn dispatch(body: &[u8]) -> Result<Payload, ErrorPayload> {
+    let runtime = create_runtime();
+    let server_fn = leptos_server::server_fn_by_path(Upload::url()).unwrap();
+    let body = body.to_vec();
+    let payload = run_scope(runtime, move |cx| {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(server_fn(cx, &body))
+    });
+    runtime.dispose();
+    payload
+}
+
+fn body(bytes: usize) -> Vec<u8> {
+    let (_, body) = multipart::to_body(&Upload {
+        photos: vec![UploadedFile {
+            name: "large.bin".into(),
+            content_type: String::new(),
+            bytes: vec![0; bytes],
+        }],
+    })
+    .unwrap();
+    body
+}
+
+#[test]
+fn uploads_larger_than_the_limit_are_rejected() {
+    Upload::register().unwrap();
+    multipart::set_upload_limit(1000);
+
+    let payload = dispatch(&body(100));
+    assert!(matches!(payload, Ok(Payload::Json(_))), "{payload:?}");
+
+    let payload = dispatch(&body(2000));
+    let Err(ErrorPayload {
+        status: 400,
+        payload: Payload::Json(json),
+        ..
+    }) = payload
+    else {
+        panic!("{payload:?}");
+    };
+    let error: ServerFnError = serde_json::from_str(&json).unwrap();
+    assert!(matches!(error, ServerFnError::Args(_)), "{error:?}");
+
+    // what an integration reads of a larger upload is rejected as well
+    let payload = dispatch(&body(2000)[..1001]);
+    assert!(matches!(payload, Err(ErrorPayload { status: 400, .. })));
+}
@@ -1,8 +1,7 @@
 use crate::{use_navigate, use_resolved_path, ToHref, Url};
-use leptos::*;
+use leptos::{server_fn::Encoding, *};
 use std::{error::Error, rc::Rc};
 use wasm_bindgen::{JsCast, UnwrapThrowExt};
-use wasm_bindgen_futures::JsFuture;
 use web_sys::RequestRedirect;
 
 type OnFormData = Rc<dyn Fn(&web_sys::FormData)>;
@@ -118,14 +117,17 @@ where
                                 let resp_url = &resp.url();
                                 match Url::try_from(resp_url.as_str()) {
                                     Ok(url) => {
-                                        request_animation_frame(move || {
-                                            if let Err(e) = navigate(
-                                                &url.pathname,
-                                                Default::default(),
-                                            ) {
-                                                warn!("{}", e);
-                                            }
-                                        });
+                                        request_animation_frame(
+                                            cx,
+                                            move || {
+                                                if let Err(e) = navigate(
+                                                    &url.pathname,
+                                                    Default::default(),
+                                                ) {
+                                                    warn!("{}", e);
+                                                }
+                                            },
+                                        );
                                     }
                                     Err(e) => warn!("{}", e),
                                 }
@@ -179,13 +181,31 @@ where
 /// Automatically turns a server [Action](leptos_server::Action) into an HTML
 /// [`form`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/form)
 /// progressively enhanced to use client-side routing.
+///
+/// Until the app is hydrated, or without JavaScript, the form is posted to the
+/// server function's URL, and the server redirects back to the page. Once it's
+/// hydrated, submitting the form reads its fields into the function's
+/// arguments, as [server_fn::form] describes, and dispatches the action
+/// instead, without navigating.
+///
+/// A form for a server function with [Multipart](Encoding::Multipart)
+/// arguments, e.g., [UploadedFile]s, is posted as `multipart/form-data`, and
+/// once it's hydrated, the files that are picked in it are read before the
+/// action is dispatched.
+///
+/// The `<form>` is `aria-busy` while the action is pending, and has a
+/// `data-error` attribute with the error if its latest call failed.
 #[component]
 pub fn ActionForm<I, O>(
     cx: Scope,
     /// The action from which to build the form. This should include a URL, which can be generated
     /// by default using [create_server_action](leptos_server::create_server_action) or added
     /// manually using [leptos_server::Action::using_server_fn].
-    action: Action<I, Result<O, ServerFnError>>,
+    action: Action<I, Result<O, ServerFnError<I::Error>>>,
+    /// Arguments that are sent with the form, but aren't entered in it, as
+    /// hidden fields. Takes any struct or map that can be serialized.
+    #[prop(optional, into)]
+    hidden: Option<HiddenFields>,
     /// Sets the `class` attribute on the underlying `<form>` tag, making it easier to style.
     #[prop(optional, into)]
     class: Option<AttributeValue>,
@@ -205,64 +225,128 @@ where
         );
         String::new()
     };
-    let version = action.version();
+    let pending = action.pending();
     let value = action.value();
-    let input = action.input();
 
-    let on_form_data = Rc::new(move |form_data: &web_sys::FormData| {
-        let data = I::from_form_data(form_data);
-        match data {
-            Ok(data) => {
-                input.set(Some(data));
-                action.set_pending(true);
-            }
-            Err(e) => error!("{e}"),
+    let on_submit = move |ev: web_sys::SubmitEvent| {
+        if ev.default_prevented() {
+            return;
         }
-    });
 
-    let on_response = Rc::new(move |resp: &web_sys::Response| {
-        let resp = resp.clone().expect("couldn't get Response");
-        spawn_local(async move {
-            let body = JsFuture::from(
-                resp.text().expect("couldn't get .text() from Response"),
-            )
-            .await;
-            match body {
-                Ok(json) => {
-                    match O::de(
-                        &json
-                            .as_string()
-                            .expect("couldn't get String from JsString"),
-                    ) {
-                        Ok(res) => {
-                            value.try_set(Some(Ok(res)));
-                        }
-                        Err(e) => {
-                            value.try_set(Some(Err(
-                                ServerFnError::Deserialization(e.to_string()),
-                            )));
-                        }
+        // if the form doesn't match the arguments, it's posted to the server
+        // as it is, which responds with the error
+        if I::encoding() == Encoding::Multipart {
+            ev.prevent_default();
+            let form = event_target::<web_sys::HtmlFormElement>(&ev);
+            spawn_local(async move {
+                match read_multipart(&form).await {
+                    Err(e) => {
+                        error!("{e}");
+                        _ = form.submit();
                     }
+                    Ok(input) => action.dispatch(input),
                 }
-                Err(e) => error!("{e:?}"),
-            };
-            input.try_set(None);
-            action.set_pending(false);
-        });
-    });
+            });
+            return;
+        }
+        match I::from_event(&ev) {
+            Err(e) => error!("{e}"),
+            Ok(input) => {
+                ev.prevent_default();
+                action.dispatch(input);
+            }
+        }
+    };
+
+    let hidden = hidden
+        .unwrap_or_default()
+        .0
+        .into_iter()
+        .map(|(name, value)| {
+            view! { cx, <input type="hidden" name=name value=value/> }
+        })
+        .collect::<Vec<_>>();
     let class = class.map(|bx| bx.into_attribute_boxed(cx));
-    Form(
-        cx,
-        FormProps::builder()
-            .action(action_url)
-            .version(version)
-            .on_form_data(on_form_data)
-            .on_response(on_response)
-            .method("post")
-            .class(class)
-            .children(children)
-            .build(),
-    )
+    view! { cx,
+        <form
+            method="post"
+            action=action_url
+            enctype=form_enctype(I::encoding())
+            class=class
+            aria-busy=move || pending.get().then_some("true")
+            data-error=move || {
+                value.with(|value| match value {
+                    Some(Err(e)) => Some(e.to_string()),
+                    _ => None,
+                })
+            }
+            on:submit=on_submit
+        >
+            {hidden}
+            {children(cx)}
+        </form>
+    }
+}
+
+// whatever a server function's encoding is, a form can only be posted
+// URL-encoded, which each of them accepts, or with its files as multipart
+fn form_enctype(encoding: Encoding) -> &'static str {
+    match encoding {
+        Encoding::Multipart => encoding.content_type(),
+        _ => "application/x-www-form-urlencoded",
+    }
+}
+
+// reads the fields of a form, along with the contents of its files, which
+// can only be read asynchronously
+async fn read_multipart<I: serde::de::DeserializeOwned>(
+    form: &web_sys::HtmlFormElement,
+) -> Result<I, server_fn::form::Error> {
+    use server_fn::multipart::{self, Field};
+    use wasm_bindgen_futures::JsFuture;
+
+    let form_data = web_sys::FormData::new_with_form(form).unwrap_throw();
+    let mut fields = Vec::new();
+    for entry in js_sys::try_iter(&form_data).unwrap_throw().unwrap_throw() {
+        let entry: js_sys::Array = entry.unwrap_throw().unchecked_into();
+        let name = entry.get(0).as_string().unwrap_or_default();
+        let value = entry.get(1);
+        let field = match value.dyn_into::<web_sys::File>() {
+            Ok(file) => {
+                let bytes =
+                    JsFuture::from(file.array_buffer()).await.map_err(|e| {
+                        <server_fn::form::Error as serde::de::Error>::custom(
+                            format!("couldn't read {:?}: {e:?}", file.name()),
+                        )
+                    })?;
+                Field::File(UploadedFile {
+                    name: file.name(),
+                    content_type: file.type_(),
+                    bytes: js_sys::Uint8Array::new(&bytes).to_vec(),
+                })
+            }
+            Err(value) => Field::Text(value.as_string().unwrap_or_default()),
+        };
+        fields.push((name, field));
+    }
+    multipart::from_fields(fields)
+}
+
+/// The fields that an [ActionForm] sends with the ones that are entered in it,
+/// encoded from a struct or map as [server_fn::form] describes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HiddenFields(pub Vec<(String, String)>);
+
+impl<T: serde::Serialize> From<T> for HiddenFields {
+    fn from(value: T) -> Self {
+        match server_fn::form::to_fields(&value) {
+            Ok(fields) => Self(fields),
+            Err(e) => {
+                error!("couldn't encode the hidden fields of a form: {e}");
+                Self::default()
+            }
+        }
+    }
 }
 
 /// Automatically turns a server [MultiAction](leptos_server::MultiAction) into an HTML
@@ -274,7 +358,7 @@ pub fn MultiActionForm<I, O>(
     /// The action from which to build the form. This should include a URL, which can be generated
     /// by default using [create_server_action](leptos_server::create_server_action) or added
     /// manually using [leptos_server::Action::using_server_fn].
-    action: MultiAction<I, Result<O, ServerFnError>>,
+    action: MultiAction<I, Result<O, ServerFnError<I::Error>>>,
     /// Sets the `class` attribute on the underlying `<form>` tag, making it easier to style.
     #[prop(optional, into)]
     class: Option<AttributeValue>,
@@ -429,8 +513,8 @@ fn extract_form_attributes(
     }
 }
 
-/// Tries to deserialize a type from form data. This can be used for client-side
-/// validation during form submission.
+/// Tries to deserialize a type from form data, as [server_fn::form] describes.
+/// This can be used for client-side validation during form submission.
 pub trait FromFormData
 where
     Self: Sized + serde::de::DeserializeOwned,
@@ -467,6 +551,6 @@ where
             web_sys::UrlSearchParams::new_with_str_sequence_sequence(form_data)
                 .unwrap_throw();
         let data = data.to_string().as_string().unwrap_or_default();
-        serde_urlencoded::from_str::<Self>(&data)
+        server_fn::form::from_bytes(data.as_bytes())
     }
 }
@@ -1,5 +1,6 @@
-use crate::{use_location, use_resolved_path, State};
+use crate::{use_location, use_resolved_path, use_router, RouterMode, State};
 use leptos::{leptos_dom::IntoView, *};
+use std::borrow::Cow;
 
 /// Describes a value that is either a static or a reactive URL, i.e.,
 /// a [String], a [&str], or a reactive `Fn() -> String`.
@@ -39,9 +40,13 @@ where
 /// 1) Correctly resolves relative nested routes. Relative routing with ordinary `<a>` tags can be tricky.
 ///    For example, if you have a route like `/post/:id`, `<A href="1">` will generate the correct relative
 ///    route, but `<a href="1">` likely will not (depending on where it appears in your view.)
-/// 2) Sets the `aria-current` attribute if this link is the active link (i.e., it’s a link to the page you’re on).
-///    This is helpful for accessibility and for styling. For example, maybe you want to set the link a
-///    different color if it’s a link to the page you’re currently on.
+/// 2) Sets the `aria-current` attribute and an `active` class if this link is the active link (i.e., it’s
+///    a link to the page you’re on). This is helpful for accessibility and for styling. For example, maybe
+///    you want to set the link a different color if it’s a link to the page you’re currently on.
+///
+/// Clicks on the link are handled by the [Router](crate::Router), which navigates without reloading the
+/// page, unless a modifier key or a button other than the main one is pressed, or the link has a
+/// `target`, a `download` attribute, `rel="external"`, or leads to another origin.
 #[component]
 pub fn A<H>(
     cx: Scope,
@@ -49,7 +54,8 @@ pub fn A<H>(
     /// to the current route.
     href: H,
     /// If `true`, the link is marked active when the location matches exactly;
-    /// if false, link is marked active if the current route starts with it.
+    /// if false, link is marked active if the current route is it or is nested in it,
+    /// e.g., `/users` is active at `/users/1` but not at `/users2`.
     #[prop(optional)]
     exact: bool,
     /// An object of any type that will be pushed to router state
@@ -62,6 +68,9 @@ pub fn A<H>(
     /// Sets the `class` attribute on the underlying `<a>` tag, making it easier to style.
     #[prop(optional, into)]
     class: Option<AttributeValue>,
+    /// The class that's added to the `<a>` tag while the link is active, `active` if it's left out.
+    #[prop(optional, into)]
+    active_class: Option<Cow<'static, str>>,
     /// The nodes or elements to be shown inside the link.
     children: Children,
 ) -> impl IntoView
@@ -75,6 +84,7 @@ where
         state: Option<State>,
         replace: bool,
         class: Option<AttributeValue>,
+        active_class: Cow<'static, str>,
         children: Children,
     ) -> HtmlElement<leptos::html::A> {
         #[cfg(not(any(feature = "hydrate", feature = "csr")))]
@@ -94,17 +104,48 @@ where
                     .unwrap_or_default()
                     .to_lowercase();
                 let loc = location.pathname.get().to_lowercase();
+                let (path, loc) = (trim_slash(&path), trim_slash(&loc));
                 if exact {
                     loc == path
                 } else {
-                    loc.starts_with(&path)
+                    // only whole segments, so `/users` isn't active at `/users2`
+                    loc.strip_prefix(path).is_some_and(|rest| {
+                        rest.is_empty() || rest.starts_with('/') || path == "/"
+                    })
                 }
             }
         });
 
+        // the active class is added to the one that was passed in, if any
+        let class = class.map(|class| class.into_attribute_boxed(cx));
+        let class = move || {
+            let class =
+                class.as_ref().and_then(Attribute::as_nameless_value_string);
+            let active = is_active.get().then(|| active_class.to_string());
+            match (class, active) {
+                (Some(class), Some(active)) => {
+                    Some(format!("{class} {active}"))
+                }
+                (class, active) => class.or(active),
+            }
+        };
+
+        // in hash mode, the paths of the app are in the hash of this page
+        let mode = use_router(cx).mode();
+        let href = move || {
+            href.get()
+                .map(|href| match mode {
+                    RouterMode::Hash if href.starts_with('/') => {
+                        format!("#{href}")
+                    }
+                    _ => href,
+                })
+                .unwrap_or_default()
+        };
+
         view! { cx,
             <a
-                href=move || href.get().unwrap_or_default()
+                href=href
                 prop:state={state.map(|s| s.to_js_value())}
                 prop:replace={replace}
                 aria-current=move || if is_active.get() { Some("page") } else { None }
@@ -116,5 +157,23 @@ where
     }
 
     let href = use_resolved_path(cx, move || href.to_href()());
-    inner(cx, href, exact, state, replace, class, children)
+    let active_class = active_class.unwrap_or(Cow::Borrowed("active"));
+    inner(
+        cx,
+        href,
+        exact,
+        state,
+        replace,
+        class,
+        active_class,
+        children,
+    )
+}
+
+// `/users/` and `/users` are the same page
+fn trim_slash(path: &str) -> &str {
+    match path.trim_end_matches('/') {
+        "" => "/",
+        path => path,
+    }
 }
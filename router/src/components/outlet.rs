@@ -1,4 +1,4 @@
-use crate::use_route;
+use crate::{components::router::PendingView, use_route, use_router};
 use leptos::{leptos_dom::HydrationCtx, *};
 use std::{cell::Cell, rc::Rc};
 
@@ -8,31 +8,61 @@ use std::{cell::Cell, rc::Rc};
 pub fn Outlet(cx: Scope) -> impl IntoView {
     let id = HydrationCtx::id();
     let route = use_route(cx);
-    let is_showing = Rc::new(Cell::new(None::<(usize, Scope)>));
+    let router = use_router(cx);
+    // the scope of the child route that's shown, and of its view
+    let is_showing = Rc::new(Cell::new(None::<(Scope, Scope)>));
+    // the child route's view while it waits to be shown
+    let pending = PendingView::default();
     let (outlet, set_outlet) = create_signal(cx, None::<View>);
-    create_isomorphic_effect(cx, move |_| {
+    create_isomorphic_effect(cx, move |prev: Option<()>| {
         match (route.child(cx), &is_showing.get()) {
             (None, prev) => {
+                pending.cancel();
                 if let Some(prev_scope) = prev.map(|(_, scope)| scope) {
                     prev_scope.dispose();
                 }
+                is_showing.set(None);
                 set_outlet.set(None);
             }
             (Some(child), Some((is_showing_val, _)))
-                if child.id() == *is_showing_val =>
+                if child.cx() == *is_showing_val =>
             {
                 // do nothing: we don't need to rerender the component, because it's the same
+                // but one that was navigated to in the meantime isn't shown
+                pending.cancel();
             }
-            (Some(child), prev) => {
-                if let Some(prev_scope) = prev.map(|(_, scope)| scope) {
-                    prev_scope.dispose();
-                }
-                _ = cx.child_scope(|child_cx| {
-                    provide_context(child_cx, child.clone());
-                    set_outlet
-                        .set(Some(child.outlet(child_cx).into_view(child_cx)));
-                    is_showing.set(Some((child.id(), child_cx)));
-                });
+            (Some(child), _) if pending.is_for(Some(child.cx())) => {
+                // it's already on its way
+            }
+            (Some(child), _) => {
+                let is_showing = Rc::clone(&is_showing);
+                // the first child is shown right away; the ones that are
+                // navigated to wait until they've loaded
+                router.show_route(
+                    cx,
+                    &pending,
+                    Some(child.cx()),
+                    prev.is_some(),
+                    {
+                        let child = child.clone();
+                        move |child_cx| {
+                            provide_context(child_cx, child.clone());
+                            // the route's own scope goes away with its view
+                            on_cleanup(child_cx, {
+                                let child = child.clone();
+                                move || child.dispose()
+                            });
+                            child.outlet(child_cx).into_view(child_cx)
+                        }
+                    },
+                    move |child_cx, view| {
+                        if let Some((_, prev_scope)) = is_showing.get() {
+                            prev_scope.dispose();
+                        }
+                        set_outlet.set(Some(view));
+                        is_showing.set(Some((child.cx(), child_cx)));
+                    },
+                );
             }
         }
     });
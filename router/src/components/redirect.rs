@@ -1,6 +1,7 @@
 use crate::{use_navigate, use_resolved_path, NavigateOptions};
 use leptos::{
-    component, provide_context, signal_prelude::*, use_context, IntoView, Scope,
+    component, create_effect, provide_context, signal_prelude::*, IntoView,
+    Scope,
 };
 use std::rc::Rc;
 
@@ -30,14 +31,15 @@ where
     let path = use_resolved_path(cx, move || path.to_string());
     let path = path.get().unwrap_or_else(|| "/".to_string());
 
-    // redirect on the server
-    if let Some(redirect_fn) = use_context::<ServerRedirectFunction>(cx) {
-        (redirect_fn.f)(&path);
-    }
-
-    // redirect on the client
+    // redirects on the server while rendering, and navigates on the client
+    // once the view that contains it has been created
     let navigate = use_navigate(cx);
-    navigate(&path, options.unwrap_or_default())
+    let options = options.unwrap_or_default();
+    if cx.is_server() {
+        _ = navigate(&path, options);
+    } else {
+        create_effect(cx, move |_| _ = navigate(&path, options.clone()));
+    }
 }
 
 /// Wrapping type for a function provided as context to allow for
@@ -45,7 +47,7 @@ where
 /// and [Redirect].
 #[derive(Clone)]
 pub struct ServerRedirectFunction {
-    f: Rc<dyn Fn(&str)>,
+    pub(crate) f: Rc<dyn Fn(&str)>,
 }
 
 impl std::fmt::Debug for ServerRedirectFunction {
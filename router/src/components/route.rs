@@ -4,7 +4,9 @@ use crate::{
 };
 use leptos::{leptos_dom::Transparent, *};
 use std::{
+    any::Any,
     cell::{Cell, RefCell},
+    future::Future,
     rc::Rc,
 };
 
@@ -19,7 +21,12 @@ pub fn Route<E, F, P>(
     cx: Scope,
     /// The path fragment that this route should match. This can be static (`users`),
     /// include a parameter (`:id`) or an optional parameter (`:id?`), or match a
-    /// wildcard (`user/*any`).
+    /// wildcard (`user/*any`), which catches the rest of the path.
+    ///
+    /// When several routes match a path, they're compared segment by segment:
+    /// a static segment wins over a parameter, a parameter over an optional
+    /// one, and any of those over a wildcard. Routes that are just as specific
+    /// are matched in the order they're defined in.
     path: P,
     /// The view that should be shown when this route is matched. This can be any function
     /// that takes a [Scope] and returns a type that implements [IntoView] (like `|cx| view! { cx, <p>"Show this"</p> })`
@@ -28,6 +35,14 @@ pub fn Route<E, F, P>(
     /// The mode that this route prefers during server-side rendering. Defaults to out-of-order streaming.
     #[prop(optional)]
     ssr: SsrMode,
+    /// Loads the data for this route while it's matched, which its views can
+    /// read with [use_loader_data](crate::use_loader_data).
+    #[prop(optional)]
+    loader: Option<Loader>,
+    /// Decides whether the route is shown when it's matched, before its
+    /// scope is created.
+    #[prop(optional)]
+    guard: Option<Guard>,
     /// `children` may be empty or include nested routes.
     #[prop(optional)]
     children: Option<Children>,
@@ -43,6 +58,8 @@ where
         path: String,
         view: Rc<dyn Fn(Scope) -> View>,
         ssr_mode: SsrMode,
+        loader: Option<Loader>,
+        guard: Option<Guard>,
     ) -> RouteDefinition {
         let children = children
             .map(|children| {
@@ -59,18 +76,15 @@ where
             })
             .unwrap_or_default();
 
-        let id = ROUTE_ID.with(|id| {
-            let next = id.get() + 1;
-            id.set(next);
-            next
-        });
-
         RouteDefinition {
-            id,
+            id: next_route_id(),
             path,
             children,
             view,
             ssr_mode,
+            loader,
+            guard,
+            not_found: false,
         }
     }
 
@@ -80,9 +94,236 @@ where
         path.to_string(),
         Rc::new(move |cx| view(cx).into_view(cx)),
         ssr,
+        loader,
+        guard,
     )
 }
 
+/// The page that's shown, in the [Outlet](crate::Outlet) of the route it's
+/// nested in, when none of the routes next to it match the rest of the path.
+///
+/// Unlike the [Router](crate::Router)'s `fallback`, which replaces the whole
+/// page, this keeps the routes it's nested in, like the app's shell and
+/// navigation, around it. When several of them could be shown, the one
+/// nested the deepest is. On the server, it sets the response's status to
+/// `404` with [ResponseMetadata].
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// # if false {
+/// # run_scope(create_runtime(), |cx| {
+/// view! { cx,
+///     <Router>
+///         <Routes>
+///             <Route path="" view=|cx| view! { cx, <nav>"Menu"</nav><Outlet/> }>
+///                 <Route path="users/:id" view=|_| "User"/>
+///                 <Route path="" view=|_| "Home"/>
+///                 <NotFound view=|_| "This page doesn't exist."/>
+///             </Route>
+///         </Routes>
+///     </Router>
+/// }
+/// # });
+/// # }
+/// ```
+#[component(transparent)]
+pub fn NotFound<E, F>(
+    cx: Scope,
+    /// The view that should be shown when nothing else matches.
+    view: F,
+    /// The mode that this route prefers during server-side rendering. Defaults to out-of-order streaming.
+    #[prop(optional)]
+    ssr: SsrMode,
+) -> impl IntoView
+where
+    E: IntoView,
+    F: Fn(Scope) -> E + 'static,
+{
+    _ = cx;
+    RouteDefinition {
+        id: next_route_id(),
+        path: "*any".into(),
+        children: Vec::new(),
+        view: Rc::new(move |cx| view(cx).into_view(cx)),
+        ssr_mode: ssr,
+        loader: None,
+        guard: None,
+        not_found: true,
+    }
+}
+
+fn next_route_id() -> usize {
+    ROUTE_ID.with(|id| {
+        let next = id.get() + 1;
+        id.set(next);
+        next
+    })
+}
+
+/// Loads the data for a [Route](crate::Route) from its params.
+///
+/// When the route is matched, the loader becomes a [Resource] in the route's
+/// scope, keyed on the route's params, so it starts loading as soon as the
+/// user navigates to the route, and doesn't run at all for routes that
+/// aren't matched. Like any other resource, it's loaded on the server while
+/// rendering and serialized to the client, which hydrates it without
+/// loading it again.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Serialize, Deserialize, Clone)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// async fn load_user(_cx: Scope, params: ParamsMap) -> User {
+///     // e.g., fetch the user with this id
+///     let id = params.get("id").cloned().unwrap_or_default();
+///     User {
+///         name: format!("User #{id}"),
+///     }
+/// }
+///
+/// #[component]
+/// fn User(cx: Scope) -> impl IntoView {
+///     view! { cx,
+///         <Suspense fallback=|| "Loading...">
+///             {move || use_loader_data::<User>(cx).map(|user| user.name)}
+///         </Suspense>
+///     }
+/// }
+///
+/// # if false {
+/// # run_scope(create_runtime(), |cx| {
+/// view! { cx,
+///     <Router>
+///         <Routes>
+///             <Route
+///                 path="users/:id"
+///                 view=|cx| view! { cx, <User/> }
+///                 loader=Loader::new(load_user)
+///             />
+///         </Routes>
+///     </Router>
+/// }
+/// # });
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Loader {
+    load: Rc<dyn Fn(Scope, Memo<ParamsMap>) -> Rc<dyn Any>>,
+}
+
+impl Loader {
+    /// Creates a loader from an `async` function of the route's scope and
+    /// params.
+    pub fn new<F, Fu, T>(f: F) -> Self
+    where
+        F: Fn(Scope, ParamsMap) -> Fu + 'static,
+        Fu: Future<Output = T> + 'static,
+        T: Serializable + 'static,
+    {
+        let f = Rc::new(f);
+        Self {
+            load: Rc::new(move |cx, params| {
+                let f = Rc::clone(&f);
+                let resource = create_resource(
+                    cx,
+                    move || params.get(),
+                    move |params| f(cx, params),
+                );
+                Rc::new(resource)
+            }),
+        }
+    }
+}
+
+impl std::fmt::Debug for Loader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Loader").finish_non_exhaustive()
+    }
+}
+
+/// What a route's [Guard] decides about showing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuardResult {
+    /// Shows the route.
+    Allow,
+    /// Redirects to another path instead of showing the route, while the
+    /// routes that were shown before stay until then. A relative path is
+    /// resolved relative to the route.
+    Redirect(String),
+    /// Shows neither the route nor the routes nested in it, as if it hadn't
+    /// been matched.
+    Block,
+}
+
+/// Decides whether a [Route](crate::Route) is shown, from its params, when
+/// it's matched.
+///
+/// The guard runs before the route's scope is created, so a guarded route's
+/// view and [Loader] only run once it's allowed. It's tracked like a memo: if
+/// it reads a signal, it runs again whenever that changes, e.g., to redirect
+/// the user away from a page once they've logged out.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// #[derive(Clone, Copy)]
+/// struct LoggedIn(ReadSignal<bool>);
+///
+/// fn must_be_logged_in(cx: Scope, _params: &ParamsMap) -> GuardResult {
+///     let LoggedIn(logged_in) = use_context(cx).unwrap();
+///     if logged_in.get() {
+///         GuardResult::Allow
+///     } else {
+///         GuardResult::Redirect("/login".into())
+///     }
+/// }
+///
+/// # if false {
+/// # run_scope(create_runtime(), |cx| {
+/// let (logged_in, _) = create_signal(cx, false);
+/// provide_context(cx, LoggedIn(logged_in));
+/// view! { cx,
+///     <Router>
+///         <Routes>
+///             <Route
+///                 path="account"
+///                 view=|_| "Account"
+///                 guard=Guard::new(must_be_logged_in)
+///             />
+///             <Route path="login" view=|_| "Log in"/>
+///         </Routes>
+///     </Router>
+/// }
+/// # });
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Guard {
+    pub(crate) check: GuardFn,
+}
+
+type GuardFn = Rc<dyn Fn(Scope, &ParamsMap) -> GuardResult>;
+
+impl Guard {
+    /// Creates a guard from a function of the `<Routes/>`' scope and the
+    /// route's params.
+    pub fn new(f: impl Fn(Scope, &ParamsMap) -> GuardResult + 'static) -> Self {
+        Self { check: Rc::new(f) }
+    }
+}
+
+impl std::fmt::Debug for Guard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Guard").finish_non_exhaustive()
+    }
+}
+
 impl IntoView for RouteDefinition {
     fn into_view(self, cx: Scope) -> View {
         Transparent::new(self).into_view(cx)
@@ -105,25 +346,45 @@ impl RouteContext {
         let base = router.base();
         let base = base.path();
         let RouteMatch { path_match, route } = matcher()?;
-        let PathMatch { path, .. } = path_match;
+        let PathMatch { path, params } = path_match;
         let RouteDefinition {
-            view: element, id, ..
+            view: element,
+            loader,
+            ..
         } = route.key;
-        let params = create_memo(cx, move |_| {
-            matcher()
-                .map(|matched| matched.path_match.params)
-                .unwrap_or_default()
+        let original_path = route.original_path.to_string();
+        // each matched route has its own scope, which is disposed along with
+        // the view that shows it
+        let ((cx, params, data), disposer) = cx.run_child_scope(|cx| {
+            let path = path.clone();
+            let original_path = original_path.clone();
+            let params = create_memo(cx, move |prev: Option<&ParamsMap>| {
+                match matcher() {
+                    // until it's disposed, a route that's being replaced
+                    // keeps its own params, rather than the new route's
+                    Some(matched)
+                        if matched.path_match.path == path
+                            && matched.route.original_path == original_path =>
+                    {
+                        matched.path_match.params
+                    }
+                    _ => prev.cloned().unwrap_or_else(|| params.clone()),
+                }
+            });
+            let data = loader.map(|loader| (loader.load)(cx, params));
+            (cx, params, data)
         });
 
         Some(Self {
             inner: Rc::new(RouteContextInner {
                 cx,
-                id,
+                disposer: RefCell::new(Some(disposer)),
                 base_path: base,
                 child: Box::new(child),
                 path: RefCell::new(path),
-                original_path: route.original_path.to_string(),
+                original_path,
                 params,
+                data,
                 outlet: Box::new(move |cx| Some(element(cx))),
             }),
         })
@@ -134,8 +395,11 @@ impl RouteContext {
         self.inner.cx
     }
 
-    pub(crate) fn id(&self) -> usize {
-        self.inner.id
+    /// Disposes the route's scope, once the view that showed it is gone.
+    pub(crate) fn dispose(&self) {
+        if let Some(disposer) = self.inner.disposer.borrow_mut().take() {
+            disposer.dispose();
+        }
     }
 
     /// Returns the URL path of the current route,
@@ -147,10 +411,6 @@ impl RouteContext {
         self.inner.path.borrow().to_string()
     }
 
-    pub(crate) fn set_path(&mut self, path: String) {
-        *self.inner.path.borrow_mut() = path;
-    }
-
     /// Returns the original URL path of the current route,
     /// with the param name rather than the matched parameter itself.
     ///
@@ -165,6 +425,16 @@ impl RouteContext {
         self.inner.params
     }
 
+    /// The [Resource] that the route's [Loader] loads its data into, if it
+    /// has a loader whose data is a `T`.
+    pub fn loader_data<T: 'static>(&self) -> Option<Resource<ParamsMap, T>> {
+        self.inner
+            .data
+            .as_ref()
+            .and_then(|data| data.downcast_ref::<Resource<ParamsMap, T>>())
+            .copied()
+    }
+
     pub(crate) fn base(
         cx: Scope,
         path: &str,
@@ -173,12 +443,13 @@ impl RouteContext {
         Self {
             inner: Rc::new(RouteContextInner {
                 cx,
-                id: 0,
+                disposer: Default::default(),
                 base_path: path.to_string(),
                 child: Box::new(|_| None),
                 path: RefCell::new(path.to_string()),
                 original_path: path.to_string(),
                 params: create_memo(cx, |_| ParamsMap::new()),
+                data: None,
                 outlet: Box::new(move |cx| {
                     fallback.as_ref().map(move |f| f(cx))
                 }),
@@ -205,12 +476,13 @@ impl RouteContext {
 
 pub(crate) struct RouteContextInner {
     cx: Scope,
+    disposer: RefCell<Option<ScopeDisposer>>,
     base_path: String,
-    pub(crate) id: usize,
     pub(crate) child: Box<dyn Fn(Scope) -> Option<RouteContext>>,
     pub(crate) path: RefCell<String>,
     pub(crate) original_path: String,
     pub(crate) params: Memo<ParamsMap>,
+    pub(crate) data: Option<Rc<dyn Any>>,
     pub(crate) outlet: Box<dyn Fn(Scope) -> Option<View>>,
 }
 
@@ -8,7 +8,11 @@ use cfg_if::cfg_if;
 use leptos::*;
 #[cfg(feature = "transition")]
 use leptos_reactive::use_transition;
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::Duration,
+};
 use thiserror::Error;
 #[cfg(not(feature = "ssr"))]
 use wasm_bindgen::JsCast;
@@ -18,24 +22,63 @@ use wasm_bindgen::JsCast;
 #[component]
 pub fn Router(
     cx: Scope,
-    /// The base URL for the router. Defaults to "".
+    /// The path the app is served under, like `/app`, which is left out
+    /// when matching routes, and added to the absolute paths the app links
+    /// and navigates to. Defaults to the `LEPTOS_ROUTER_BASE` environment
+    /// variable at compile time, if it's set, or to "".
     #[prop(optional)]
     base: Option<&'static str>,
+    /// Where in the URL the router keeps the current route. Defaults to
+    /// [RouterMode::BrowserHistory].
+    #[prop(optional)]
+    mode: RouterMode,
     /// A fallback that should be shown if no route is matched.
     #[prop(optional)]
     fallback: Option<fn(Scope) -> View>,
+    /// How long a navigation keeps showing the page it's leaving while the
+    /// route it's going to is loading, i.e., while the resources read in it
+    /// or in the `<Suspense/>` boundaries at its top are pending, before
+    /// showing it anyway. [Duration::ZERO] shows new routes right away.
+    /// Defaults to 5 seconds.
+    #[prop(optional)]
+    transition_timeout: Option<Duration>,
     /// The `<Router/>` should usually wrap your whole page. It can contain
     /// any elements, and should include a [Routes](crate::Routes) component somewhere
     /// to define and display [Route](crate::Route)s.
     children: Children,
 ) -> impl IntoView {
     // create a new RouterContext and provide it to every component beneath the router
-    let router = RouterContext::new(cx, base, fallback);
+    let router = RouterContext::new(
+        cx,
+        base,
+        mode,
+        fallback,
+        transition_timeout.unwrap_or(Duration::from_secs(5)),
+    );
     provide_context(cx, router);
 
     children(cx)
 }
 
+/// Where in the URL the [Router] keeps the current route.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RouterMode {
+    /// The route is the URL's path and query, like `/users/1?tab=info`,
+    /// changed through the [`History API`](https://developer.mozilla.org/en-US/docs/Web/API/History).
+    /// The server has to serve the app at every path it routes to.
+    #[default]
+    BrowserHistory,
+    /// The route is kept in the URL's hash, like `/#/users/1?tab=info`,
+    /// so that the server only ever sees the path the app is served from.
+    /// This is useful for static hosts, which can't serve the same page at
+    /// every path. [A](crate::A) links to the hash, and the query in it is
+    /// read like a query in the path would be.
+    ///
+    /// The server never sees the hash, so this is meant for apps that are
+    /// rendered in the browser.
+    Hash,
+}
+
 /// Context type that contains information about the current router state.
 #[derive(Debug, Clone)]
 pub struct RouterContext {
@@ -45,11 +88,15 @@ pub(crate) struct RouterContextInner {
     pub location: Location,
     pub base: RouteContext,
     pub possible_routes: RefCell<Option<Vec<Branch>>>,
-    #[allow(unused)] // used in CSR/hydrate
-    base_path: String,
+    pub(crate) base_path: String,
+    pub(crate) mode: RouterMode,
+    transition_timeout: Duration,
+    // the number of routes that are waiting to be shown
+    pending_routes: RwSignal<usize>,
+    is_routing: Memo<bool>,
     history: Box<dyn History>,
     cx: Scope,
-    reference: ReadSignal<String>,
+    pub(crate) reference: ReadSignal<String>,
     set_reference: WriteSignal<String>,
     referrers: Rc<RefCell<Vec<LocationChange>>>,
     state: ReadSignal<State>,
@@ -61,6 +108,9 @@ impl std::fmt::Debug for RouterContextInner {
         f.debug_struct("RouterContextInner")
             .field("location", &self.location)
             .field("base", &self.base)
+            .field("mode", &self.mode)
+            .field("transition_timeout", &self.transition_timeout)
+            .field("pending_routes", &self.pending_routes)
             .field("cx", &self.cx)
             .field("reference", &self.reference)
             .field("set_reference", &self.set_reference)
@@ -75,12 +125,17 @@ impl RouterContext {
     pub(crate) fn new(
         cx: Scope,
         base: Option<&'static str>,
+        mode: RouterMode,
         fallback: Option<fn(Scope) -> View>,
+        transition_timeout: Duration,
     ) -> Self {
         cfg_if! {
             if #[cfg(any(feature = "csr", feature = "hydrate"))] {
                 let history = use_context::<RouterIntegrationContext>(cx)
-                    .unwrap_or_else(|| RouterIntegrationContext(Rc::new(crate::BrowserIntegration {})));
+                    .unwrap_or_else(|| match mode {
+                        RouterMode::BrowserHistory => RouterIntegrationContext(Rc::new(crate::BrowserIntegration {})),
+                        RouterMode::Hash => RouterIntegrationContext(Rc::new(crate::HashIntegration {})),
+                    });
             } else {
                 let history = use_context::<RouterIntegrationContext>(cx).unwrap_or_else(|| {
                     let msg = "No router integration found.\n\nIf you are using this in the browser, \
@@ -100,7 +155,9 @@ impl RouterContext {
         let source = history.location(cx);
 
         // if initial route is empty, redirect to base path, if it exists
-        let base = base.unwrap_or_default();
+        let base = base
+            .or(option_env!("LEPTOS_ROUTER_BASE"))
+            .unwrap_or_default();
         let base_path = resolve_path("", base, None);
 
         if let Some(base_path) = &base_path {
@@ -150,8 +207,15 @@ impl RouterContext {
             });
         });
 
+        let pending_routes = create_rw_signal(cx, 0);
+        let is_routing = create_memo(cx, move |_| pending_routes.get() > 0);
+
         let inner = Rc::new(RouterContextInner {
             base_path: base_path.into_owned(),
+            mode,
+            transition_timeout,
+            pending_routes,
+            is_routing,
             location,
             base,
             history: Box::new(history),
@@ -166,11 +230,10 @@ impl RouterContext {
 
         // handle all click events on anchor tags
         #[cfg(not(feature = "ssr"))]
-        leptos::window_event_listener("click", {
+        leptos::window_event_listener(cx, leptos::ev::click, {
             let inner = Rc::clone(&inner);
-            move |ev| inner.clone().handle_anchor_click(ev)
+            move |ev| inner.clone().handle_anchor_click(ev.into())
         });
-        // TODO on_cleanup remove event listener
 
         Self { inner }
     }
@@ -180,6 +243,37 @@ impl RouterContext {
         self.inner.location.pathname
     }
 
+    /// Where in the URL the router keeps the current route.
+    pub fn mode(&self) -> RouterMode {
+        self.inner.mode
+    }
+
+    /// Whether the router is waiting for the route it's navigating to to
+    /// load, while it keeps showing the page it's leaving. This can be used
+    /// to show a progress bar. See [use_is_routing](crate::use_is_routing).
+    pub fn is_routing(&self) -> Signal<bool> {
+        self.inner.is_routing.into()
+    }
+
+    /// Moves `delta` entries through the history stack, e.g., `go(-1)` to
+    /// go back one page. The [Location](crate::Location) is updated once the
+    /// [History](crate::History) has moved.
+    pub fn go(&self, delta: i32) {
+        self.inner.history.go(delta)
+    }
+
+    /// Goes back one entry in the history stack, like the browser's back
+    /// button.
+    pub fn back(&self) {
+        self.go(-1)
+    }
+
+    /// Goes forward one entry in the history stack, like the browser's
+    /// forward button.
+    pub fn forward(&self) {
+        self.go(1)
+    }
+
     /// The [RouteContext] of the base route.
     pub fn base(&self) -> RouteContext {
         self.inner.base.clone()
@@ -195,6 +289,142 @@ impl RouterContext {
     }
 }
 
+// The view of a route that's waiting to be shown, by the scope of the route
+// it shows, which is `None` for the fallback, and the scope of the view.
+#[derive(Clone, Default)]
+pub(crate) struct PendingView(Rc<Cell<Option<PendingScopes>>>);
+
+type PendingScopes = (Option<Scope>, Scope);
+
+impl PendingView {
+    // whether the view of `route` is the one waiting
+    pub(crate) fn is_for(&self, route: Option<Scope>) -> bool {
+        matches!(self.0.get(), Some((pending, _)) if pending == route)
+    }
+
+    // stops waiting for the view in the scope `cx`, if it's the one waiting
+    fn take_for(&self, cx: Scope) -> bool {
+        match self.0.take() {
+            Some((_, pending)) if pending == cx => true,
+            other => {
+                self.0.set(other);
+                false
+            }
+        }
+    }
+
+    // drops the view that's waiting, if there is one
+    pub(crate) fn cancel(&self) {
+        if let Some((_, cx)) = self.0.take() {
+            cx.dispose();
+        }
+    }
+}
+
+impl RouterContext {
+    // Renders the view of `route` in a child scope of `cx`, and calls `show`
+    // with the view and its scope, once it's ready to replace what was shown
+    // there before. When `wait` is true, in the browser, that's once the
+    // resources read in the view, and the `<Suspense/>` boundaries at its
+    // top, have resolved, or the transition timeout has passed; until then,
+    // the old view stays, and the new one is `pending`, which a newer one
+    // replaces.
+    pub(crate) fn show_route(
+        &self,
+        cx: Scope,
+        pending: &PendingView,
+        route: Option<Scope>,
+        wait: bool,
+        render: impl FnOnce(Scope) -> View,
+        show: impl FnOnce(Scope, View) + 'static,
+    ) {
+        // what the view reads doesn't concern whoever decided to show it
+        cx.untrack(|| {
+            self.show_route_untracked(cx, pending, route, wait, render, show)
+        })
+    }
+
+    fn show_route_untracked(
+        &self,
+        cx: Scope,
+        pending: &PendingView,
+        route: Option<Scope>,
+        wait: bool,
+        render: impl FnOnce(Scope) -> View,
+        show: impl FnOnce(Scope, View) + 'static,
+    ) {
+        pending.cancel();
+
+        let timeout = self.inner.transition_timeout;
+        if !wait || cx.is_server() || timeout.is_zero() {
+            let ((view, cx), _) = cx.run_child_scope(|cx| (render(cx), cx));
+            show(cx, view);
+            return;
+        }
+
+        let pending_routes = self.inner.pending_routes;
+        let pending = pending.clone();
+        _ = cx.child_scope(move |cx| {
+            let context = SuspenseContext::new_transition(cx);
+            provide_context(cx, context);
+            let view = context.run_in_boundary(|| render(cx));
+
+            // the view is shown exactly once, unless a newer one replaces it
+            let show = Rc::new(RefCell::new(Some(move || show(cx, view))));
+            let waiting = Rc::new(Cell::new(false));
+            let swap = {
+                let show = Rc::clone(&show);
+                let waiting = Rc::clone(&waiting);
+                let pending = pending.clone();
+                move || {
+                    // one that's been cancelled isn't waiting anymore, even
+                    // while the boundaries in it are being disposed
+                    if pending.take_for(cx) {
+                        waiting.set(false);
+                        pending_routes.update(|n| *n -= 1);
+                        if let Some(show) = show.take() {
+                            show();
+                        }
+                    }
+                }
+            };
+            on_cleanup(cx, {
+                let show = Rc::clone(&show);
+                let waiting = Rc::clone(&waiting);
+                move || {
+                    show.take();
+                    if waiting.replace(false) {
+                        _ = pending_routes.try_update(|n| *n -= 1);
+                    }
+                }
+            });
+
+            // once the resources the view read have been counted
+            queue_microtask(move || {
+                if context.ready() {
+                    if let Some(show) = show.take() {
+                        show();
+                    }
+                    return;
+                }
+                pending.0.set(Some((route, cx)));
+                waiting.set(true);
+                pending_routes.update(|n| *n += 1);
+                let none_pending = context.none_pending();
+                create_effect(cx, {
+                    let swap = swap.clone();
+                    move |_| {
+                        if none_pending.get() {
+                            swap();
+                        }
+                    }
+                });
+                set_timeout(cx, timeout, swap);
+            });
+        });
+    }
+}
+
 impl RouterContextInner {
     pub(crate) fn navigate_from_route(
         self: Rc<Self>,
@@ -229,6 +459,12 @@ impl RouterContextInner {
                                 state: options.state.clone(),
                             });
                         } else {
+                            // before the page changes; a redirect on the
+                            // way is part of the same navigation
+                            #[cfg(not(feature = "ssr"))]
+                            if self.referrers.borrow().is_empty() {
+                                crate::history::scroll::save();
+                            }
                             {
                                 self.referrers.borrow_mut().push(
                                     LocationChange {
@@ -336,22 +572,43 @@ impl RouterContextInner {
             }
 
             let url = Url::try_from(href.as_str()).unwrap();
-            let path_name = unescape(&url.pathname);
+            let here = leptos_dom::helpers::location();
 
             // let browser handle this event if it leaves our domain
-            // or our base path
-            if url.origin
-                != leptos_dom::helpers::location().origin().unwrap_or_default()
-                || (!self.base_path.is_empty()
-                    && !path_name.is_empty()
-                    && !path_name
-                        .to_lowercase()
-                        .starts_with(&self.base_path.to_lowercase()))
+            if url.origin != here.origin().unwrap_or_default() {
+                return;
+            }
+
+            let to = match self.mode {
+                RouterMode::BrowserHistory => {
+                    unescape(&url.pathname)
+                        + &unescape(&url.search)
+                        + &unescape(&url.hash)
+                }
+                // the route is the hash of a link to this page; any other
+                // link, or a hash that isn't a path, is the browser's
+                RouterMode::Hash => match url.hash.strip_prefix('#') {
+                    Some(route)
+                        if route.starts_with('/')
+                            && url.pathname
+                                == here.pathname().unwrap_or_default()
+                            && url.search
+                                == here.search().unwrap_or_default() =>
+                    {
+                        unescape(route)
+                    }
+                    _ => return,
+                },
+            };
+
+            // or if it leaves our base path
+            let path_name = to.split(['?', '#']).next().unwrap_or_default();
+            if !path_name.is_empty()
+                && !is_under_base(path_name, &self.base_path)
             {
                 return;
             }
 
-            let to = path_name + &unescape(&url.search) + &unescape(&url.hash);
             let state =
                 leptos_dom::helpers::get_property(a.unchecked_ref(), "state")
                     .ok()
@@ -385,6 +642,18 @@ impl RouterContextInner {
     }
 }
 
+// whether the path is the base path, or one of the paths under it, ignoring
+// case like the browser does for paths
+#[cfg(not(feature = "ssr"))]
+fn is_under_base(path: &str, base: &str) -> bool {
+    let base = base.trim_end_matches('/').to_lowercase();
+    let path = path.to_lowercase();
+    match path.strip_prefix(&base) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/'),
+        None => false,
+    }
+}
+
 /// An error that occurs during navigation.
 #[derive(Debug, Error)]
 pub enum NavigationError {
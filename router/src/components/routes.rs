@@ -1,9 +1,10 @@
 use crate::{
+    components::router::PendingView,
     matching::{
-        expand_optionals, get_route_matches, join_paths, Branch, Matcher,
-        RouteDefinition, RouteMatch,
+        expand_optionals, get_route_matches, join_paths, resolve_path, Branch,
+        Matcher, RouteDefinition, RouteMatch,
     },
-    RouteContext, RouterContext,
+    use_navigate, GuardResult, NavigateOptions, RouteContext, RouterContext,
 };
 use leptos::{leptos_dom::HydrationCtx, *};
 use std::{
@@ -39,34 +40,63 @@ pub fn Routes(
         .cloned()
         .collect::<Vec<_>>();
 
-    create_branches(
-        &children,
-        &base.unwrap_or_default(),
-        &mut Vec::new(),
-        &mut branches,
-    );
+    // the routes are matched under the router's base path, too
+    let base = join_paths(&base_route.path(), &base.unwrap_or_default());
+    create_branches(&children, &base, &mut Vec::new(), &mut branches);
 
     #[cfg(feature = "ssr")]
     if let Some(context) = use_context::<crate::PossibleBranchContext>(cx) {
         *context.0.borrow_mut() = branches.clone();
+        *context.1.borrow_mut() = children.clone();
     }
 
-    // whenever path changes, update matches
-    let matches = create_memo(cx, {
+    // whenever path changes, update matches, stopping at the first route
+    // whose guard doesn't allow it; guards are tracked along with the path
+    let guarded = create_memo(cx, {
         let router = router.clone();
-        move |_| get_route_matches(branches.clone(), router.pathname().get())
+        move |prev: Option<&(Vec<RouteMatch>, Option<String>)>| {
+            let matches =
+                get_route_matches(branches.clone(), router.pathname().get());
+            guard_matches(cx, &router, matches, prev.map(|prev| &prev.0))
+        }
+    });
+    let matches = create_memo(cx, move |_| guarded.with(|g| g.0.clone()));
+
+    // on the server, a path that only a <NotFound/> route, or no route at
+    // all, matches is a 404
+    if let Some(response) = use_context::<ResponseMetadata>(cx) {
+        let not_found = matches.with_untracked(|matches| {
+            matches.is_empty()
+                || matches.iter().any(|matched| matched.route.key.not_found)
+        });
+        if not_found {
+            response.set_status(404);
+        }
+    }
+
+    // redirects on the server, and navigates on the client, as <Redirect/>
+    let redirect = create_memo(cx, move |_| guarded.with(|g| g.1.clone()));
+    let navigate = use_navigate(cx);
+    create_isomorphic_effect(cx, move |_| {
+        if let Some(to) = redirect.get() {
+            _ = navigate(
+                &to,
+                NavigateOptions {
+                    resolve: false,
+                    replace: true,
+                    ..Default::default()
+                },
+            );
+        }
     });
 
     // iterate over the new matches, reusing old routes when they are the same
     // and replacing them with new routes when they differ
     let next: Rc<RefCell<Vec<RouteContext>>> = Default::default();
 
-    let root_equal = Rc::new(Cell::new(true));
-
     let route_states: Memo<RouterState> = create_memo(cx, {
-        let root_equal = Rc::clone(&root_equal);
+        let router = router.clone();
         move |prev: Option<&RouterState>| {
-            root_equal.set(true);
             next.borrow_mut().clear();
 
             let next_matches = matches.get();
@@ -78,21 +108,26 @@ pub fn Routes(
                 .map(|prev_matches| next_matches.len() == prev_matches.len())
                 .unwrap_or(false);
 
+            // once a segment has changed, the ones nested in it are created
+            // again as well, because their scopes are children of its scope
+            let mut changed = false;
+
             for i in 0..next_matches.len() {
                 let next = next.clone();
                 let prev_match = prev_matches.and_then(|p| p.get(i));
                 let next_match = next_matches.get(i).unwrap();
 
                 match (prev_routes, prev_match) {
+                    // a segment is kept if it matches the same route with
+                    // the same params, i.e., the same part of the path
                     (Some(prev), Some(prev_match))
-                        if next_match.route.key == prev_match.route.key
-                            && next_match.route.id == prev_match.route.id =>
+                        if !changed
+                            && next_match.route.key == prev_match.route.key
+                            && next_match.route.id == prev_match.route.id
+                            && next_match.path_match.path
+                                == prev_match.path_match.path =>
                     {
-                        let mut prev_one = { prev.borrow()[i].clone() };
-                        if next_match.path_match.path != prev_one.path() {
-                            prev_one
-                                .set_path(next_match.path_match.path.clone());
-                        }
+                        let prev_one = { prev.borrow()[i].clone() };
                         if i >= next.borrow().len() {
                             next.borrow_mut().push(prev_one);
                         } else {
@@ -101,19 +136,20 @@ pub fn Routes(
                     }
                     _ => {
                         equal = false;
-                        if i == 0 {
-                            root_equal.set(false);
-                        }
+                        changed = true;
 
                         let next = next.clone();
                         let router = Rc::clone(&router.inner);
 
                         let next = next.clone();
+                        let this = Rc::new(Cell::new(None::<Scope>));
                         let next_ctx = RouteContext::new(
                             cx,
                             &RouterContext { inner: router },
                             {
                                 let next = next.clone();
+                                let this = Rc::clone(&this);
+                                let last_child = RefCell::new(None);
                                 move |cx| {
                                     if let Some(route_states) =
                                         use_context::<Memo<RouterState>>(cx)
@@ -121,7 +157,24 @@ pub fn Routes(
                                         route_states.with(|route_states| {
                                             let routes =
                                                 route_states.routes.borrow();
-                                            routes.get(i + 1).cloned()
+                                            // a route that's been replaced
+                                            // keeps the child it had, so
+                                            // that its outlet doesn't render
+                                            // the new one, and its page stays
+                                            // whole until the new one is shown
+                                            let current = routes
+                                                .get(i)
+                                                .map(RouteContext::cx)
+                                                == this.get();
+                                            if current {
+                                                let child =
+                                                    routes.get(i + 1).cloned();
+                                                *last_child.borrow_mut() =
+                                                    child.clone();
+                                                child
+                                            } else {
+                                                last_child.borrow().clone()
+                                            }
                                         })
                                     } else {
                                         next.borrow().get(i + 1).cloned()
@@ -132,6 +185,7 @@ pub fn Routes(
                         );
 
                         if let Some(next_ctx) = next_ctx {
+                            this.set(Some(next_ctx.cx()));
                             if next.borrow().len() > i + 1 {
                                 next.borrow_mut()[i] = next_ctx;
                             } else {
@@ -168,44 +222,103 @@ pub fn Routes(
         }
     });
 
-    // show the root route
+    // show the root route, or the fallback if no route matches
     let id = HydrationCtx::id();
-    let root_cx = RefCell::new(None);
-    let root = create_memo(cx, move |prev| {
-        provide_context(cx, route_states);
+    let root_cx = Rc::new(Cell::new(None::<Scope>));
+    // the root route that's shown and the one that's waiting to be, by their
+    // scopes, with `None` for the fallback
+    let shown = Rc::new(Cell::new(None::<Option<Scope>>));
+    let pending = PendingView::default();
+    let (root, set_root) = create_signal(cx, None::<View>);
+    provide_context(cx, route_states);
+    create_isomorphic_effect(cx, move |prev: Option<()>| {
         route_states.with(|state| {
-            if state.routes.borrow().is_empty() {
-                Some(base_route.outlet(cx).into_view(cx))
-            } else {
-                let root = state.routes.borrow();
-                let root = root.get(0);
-                if let Some(route) = root {
-                    provide_context(cx, route.clone());
-                }
+            let root = state.routes.borrow();
+            let root = root.get(0);
+            if let Some(route) = root {
+                provide_context(cx, route.clone());
+            }
 
-                if prev.is_none() || !root_equal.get() {
-                    let (root_view, _) = cx.run_child_scope(|cx| {
-                        let prev_cx = std::mem::replace(
-                            &mut *root_cx.borrow_mut(),
-                            Some(cx),
-                        );
-                        if let Some(prev_cx) = prev_cx {
-                            prev_cx.dispose();
-                        }
-                        root.as_ref()
-                            .map(|route| route.outlet(cx).into_view(cx))
-                    });
-                    root_view
-                } else {
-                    prev.cloned().unwrap()
-                }
+            let target = root.map(RouteContext::cx);
+            if shown.get() == Some(target) {
+                // going back to what's shown drops a route that's waiting
+                pending.cancel();
+                return;
+            }
+            if pending.is_for(target) {
+                return;
             }
+
+            let root = root.cloned();
+            let base_route = base_route.clone();
+            let root_cx = Rc::clone(&root_cx);
+            let shown = Rc::clone(&shown);
+            // the first route is shown right away; the ones that are
+            // navigated to wait until they've loaded
+            router.show_route(
+                cx,
+                &pending,
+                target,
+                prev.is_some(),
+                move |cx| match root {
+                    Some(route) => {
+                        on_cleanup(cx, {
+                            let route = route.clone();
+                            move || route.dispose()
+                        });
+                        route.outlet(cx).into_view(cx)
+                    }
+                    None => base_route.outlet(cx).into_view(cx),
+                },
+                move |cx, view| {
+                    if let Some(prev_cx) = root_cx.replace(Some(cx)) {
+                        prev_cx.dispose();
+                    }
+                    shown.set(Some(target));
+                    set_root.set(Some(view));
+                },
+            );
         })
     });
 
     leptos::leptos_dom::DynChild::new_with_id(id, move || root.get())
 }
 
+// the matched routes that their guards allow, and where to redirect to if
+// one of them redirects; until the redirect has happened, the routes that
+// were shown before stay, rather than showing the fallback in between
+fn guard_matches(
+    cx: Scope,
+    router: &RouterContext,
+    mut matches: Vec<RouteMatch>,
+    prev: Option<&Vec<RouteMatch>>,
+) -> (Vec<RouteMatch>, Option<String>) {
+    for (i, matched) in matches.iter().enumerate() {
+        let Some(guard) = &matched.route.key.guard else {
+            continue;
+        };
+        match (guard.check)(cx, &matched.path_match.params) {
+            GuardResult::Allow => {}
+            GuardResult::Block => {
+                matches.truncate(i);
+                return (matches, None);
+            }
+            GuardResult::Redirect(to) => {
+                let base = router.base().path();
+                let to =
+                    resolve_path(&base, &to, Some(&matched.path_match.path))
+                        .map(String::from);
+                match prev {
+                    Some(prev) => matches = prev.clone(),
+                    None => matches.truncate(i),
+                }
+                return (matches, to);
+            }
+        }
+    }
+    (matches, None)
+}
+
 #[derive(Clone, Debug, PartialEq)]
 struct RouterState {
     matches: Vec<RouteMatch>,
@@ -222,23 +335,70 @@ pub struct RouteData {
     pub matcher: Matcher,
 }
 
+// How specific a segment of a route's pattern is, from the most specific to
+// the least, with `End` after the last segment of a pattern that doesn't end
+// in a catch-all, which is matched by an empty rest of the path, too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Segment {
+    CatchAll,
+    End,
+    Optional,
+    Param,
+    Static,
+}
+
 impl RouteData {
-    fn score(&self) -> i32 {
-        let (pattern, splat) = match self.pattern.split_once("/*") {
-            Some((p, s)) => (p, Some(s)),
-            None => (self.pattern.as_str(), None),
-        };
-        let segments = pattern
+    // the segments of this route's own part of the pattern, and how many of
+    // its optional params it leaves out
+    fn segments(&self) -> (Vec<Segment>, usize) {
+        let is_optional =
+            |name: &str| self.key.path.contains(&format!(":{name}?"));
+        let segments = self
+            .original_path
             .split('/')
             .filter(|n| !n.is_empty())
+            .map(|segment| {
+                if segment.starts_with('*') {
+                    Segment::CatchAll
+                } else if let Some(name) = segment.strip_prefix(':') {
+                    if is_optional(name) {
+                        Segment::Optional
+                    } else {
+                        Segment::Param
+                    }
+                } else {
+                    Segment::Static
+                }
+            })
             .collect::<Vec<_>>();
-        #[allow(clippy::bool_to_int_with_if)] // on the splat.is_none()
-        segments.iter().fold(
-            (segments.len() as i32) - if splat.is_none() { 0 } else { 1 },
-            |score, segment| {
-                score + if segment.starts_with(':') { 2 } else { 3 }
-            },
-        )
+        let optionals = self.key.path.matches('?').count();
+        let used = segments
+            .iter()
+            .filter(|segment| **segment == Segment::Optional)
+            .count();
+        (segments, optionals.saturating_sub(used))
+    }
+}
+
+impl Branch {
+    // Branches are compared segment by segment, from the start of the path:
+    // a static segment wins over a param, a param over an optional param,
+    // and those over a catch-all. When they're the same, the one that leaves
+    // out fewer optional params wins, then the one that isn't a
+    // `<NotFound/>` route, and then the one that was defined first.
+    fn rank(&self) -> impl Ord {
+        let mut segments = Vec::new();
+        let mut left_out = 0;
+        for route in &self.routes {
+            let (route_segments, route_left_out) = route.segments();
+            segments.extend(route_segments);
+            left_out += route_left_out;
+        }
+        if segments.last() != Some(&Segment::CatchAll) {
+            segments.push(Segment::End);
+        }
+        let not_found = self.routes.iter().any(|route| route.key.not_found);
+        (segments, Reverse(left_out), !not_found)
     }
 }
 
@@ -254,8 +414,7 @@ fn create_branches(
             stack.push(route.clone());
 
             if def.children.is_empty() {
-                let branch = create_branch(stack, branches.len());
-                branches.push(branch);
+                branches.push(create_branch(stack));
             } else {
                 create_branches(&def.children, &route.pattern, stack, branches);
             }
@@ -265,14 +424,20 @@ fn create_branches(
     }
 
     if stack.is_empty() {
-        branches.sort_by_key(|branch| Reverse(branch.score));
+        // the sort is stable, so equal branches keep the order they're
+        // defined in
+        branches.sort_by_cached_key(|branch| Reverse(branch.rank()));
+        let len = branches.len();
+        for (index, branch) in branches.iter_mut().enumerate() {
+            branch.score = (len - index) as i32;
+        }
     }
 }
 
-pub(crate) fn create_branch(routes: &[RouteData], index: usize) -> Branch {
+pub(crate) fn create_branch(routes: &[RouteData]) -> Branch {
     Branch {
         routes: routes.to_vec(),
-        score: routes.last().unwrap().score() * 10000 - (index as i32),
+        score: 0,
     }
 }
 
@@ -1,10 +1,16 @@
-use crate::{Branch, RouterIntegrationContext, ServerIntegration, SsrMode};
+use crate::{
+    Branch, RouteDefinition, RouterIntegrationContext, ServerIntegration,
+    SsrMode,
+};
 use leptos::*;
 use std::{cell::RefCell, rc::Rc};
 
 /// Context to contain all possible routes.
 #[derive(Clone, Default, Debug)]
-pub struct PossibleBranchContext(pub(crate) Rc<RefCell<Vec<Branch>>>);
+pub struct PossibleBranchContext(
+    pub(crate) Rc<RefCell<Vec<Branch>>>,
+    pub(crate) Rc<RefCell<Vec<RouteDefinition>>>,
+);
 
 /// Generates a list of all routes this application could possibly serve. This returns the raw routes in the leptos_router
 /// format. Odds are you want `generate_route_list()` from either the actix, axum, or viz integrations if you want
@@ -12,6 +18,47 @@ pub struct PossibleBranchContext(pub(crate) Rc<RefCell<Vec<Branch>>>);
 pub fn generate_route_list_inner<IV>(
     app_fn: impl FnOnce(Scope) -> IV + 'static,
 ) -> Vec<(String, SsrMode)>
+where
+    IV: IntoView + 'static,
+{
+    let context = possible_branches(app_fn);
+    let branches = context.0.borrow();
+    branches
+        .iter()
+        .flat_map(|branch| {
+            let mode = branch
+                .routes
+                .iter()
+                .map(|route| route.key.ssr_mode)
+                .max()
+                .unwrap_or_default();
+            let pattern =
+                branch.routes.last().map(|route| route.pattern.clone());
+            pattern.map(|pattern| (pattern, mode))
+        })
+        .collect()
+}
+
+/// Collects the tree of routes defined in the application's `<Routes/>`, with
+/// their paths, the [SsrMode] each of them prefers and their loaders. This can
+/// be used at server startup, e.g., to register the routes with the server's
+/// own router.
+///
+/// The routes are collected by rendering the application once, without
+/// loading any resources.
+pub fn collect_routes<IV>(
+    app_fn: impl FnOnce(Scope) -> IV + 'static,
+) -> Vec<RouteDefinition>
+where
+    IV: IntoView + 'static,
+{
+    let context = possible_branches(app_fn);
+    context.1.take()
+}
+
+fn possible_branches<IV>(
+    app_fn: impl FnOnce(Scope) -> IV + 'static,
+) -> PossibleBranchContext
 where
     IV: IntoView + 'static,
 {
@@ -22,27 +69,13 @@ where
         };
 
         provide_context(cx, RouterIntegrationContext::new(integration));
-        let branches = PossibleBranchContext::default();
-        provide_context(cx, branches.clone());
+        let context = PossibleBranchContext::default();
+        provide_context(cx, context.clone());
 
         leptos::suppress_resource_load(true);
         _ = app_fn(cx).into_view(cx);
         leptos::suppress_resource_load(false);
 
-        let branches = branches.0.borrow();
-        branches
-            .iter()
-            .flat_map(|branch| {
-                let mode = branch
-                    .routes
-                    .iter()
-                    .map(|route| route.key.ssr_mode)
-                    .max()
-                    .unwrap_or_default();
-                let pattern =
-                    branch.routes.last().map(|route| route.pattern.clone());
-                pattern.map(|pattern| (pattern, mode))
-            })
-            .collect()
+        context
     })
 }
@@ -1,5 +1,4 @@
-use super::params::ParamsMap;
-use crate::{State, Url};
+use crate::{QueryMap, State, Url};
 use leptos::*;
 
 /// Creates a reactive location from the given path and state.
@@ -43,7 +42,7 @@ pub struct Location {
     /// The raw query string.
     pub search: Memo<String>,
     /// The query string parsed into its key-value pairs.
-    pub query: Memo<ParamsMap>,
+    pub query: Memo<QueryMap>,
     /// The hash fragment.
     pub hash: Memo<String>,
     /// The [`state`](https://developer.mozilla.org/en-US/docs/Web/API/History/state) at the top of the history stack.
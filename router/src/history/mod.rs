@@ -4,12 +4,15 @@ use wasm_bindgen::UnwrapThrowExt;
 
 mod location;
 mod params;
+mod query;
+pub(crate) mod scroll;
 mod state;
 mod url;
 
 pub use self::url::*;
 pub use location::*;
 pub use params::*;
+pub use query::*;
 pub use state::*;
 
 impl std::fmt::Debug for RouterIntegrationContext {
@@ -27,6 +30,14 @@ pub trait History {
 
     /// Called to navigate to a new location.
     fn navigate(&self, loc: &LocationChange);
+
+    /// Moves `delta` entries through the history stack, back if it's
+    /// negative and forward if it's positive, like the browser's buttons do.
+    /// The new location is then reported through [History::location]. By
+    /// default, there's no stack to move through, e.g., on the server.
+    fn go(&self, delta: i32) {
+        _ = delta;
+    }
 }
 
 /// The default integration when you are running in the browser, which uses
@@ -43,35 +54,102 @@ impl BrowserIntegration {
                 + &loc.hash().unwrap_or_default(),
             replace: true,
             scroll: true,
-            state: State(None), // TODO
+            state: State(
+                leptos_dom::window()
+                    .history()
+                    .and_then(|history| history.state())
+                    .ok()
+                    .map(scroll::user_state)
+                    .filter(|state| !state.is_undefined() && !state.is_null()),
+            ),
         }
     }
 }
 
 impl History for BrowserIntegration {
     fn location(&self, cx: Scope) -> ReadSignal<LocationChange> {
-        use crate::{NavigateOptions, RouterContext};
-
+        scroll::start();
         let (location, set_location) = create_signal(cx, Self::current());
 
-        leptos::window_event_listener("popstate", move |_| {
-            let router = use_context::<RouterContext>(cx);
-            if let Some(router) = router {
-                let change = Self::current();
-                if let Err(e) = router.inner.navigate_from_route(
-                    &change.value,
-                    &NavigateOptions {
-                        resolve: false,
-                        replace: change.replace,
-                        scroll: change.scroll,
-                        state: change.state,
-                    },
-                ) {
-                    log::error!("{e:#?}");
-                }
-                set_location.set(Self::current());
+        leptos::window_event_listener(cx, ev::popstate, move |_| {
+            popped(cx, Self::current(), set_location, || {
+                scroll_to_hash_or_top(&location_hash(), true)
+            })
+        });
+
+        location
+    }
+
+    fn navigate(&self, loc: &LocationChange) {
+        push_or_replace(loc, &loc.value);
+
+        // going back or forward restores the entry's scroll positions instead
+        if !scroll::is_restoring() {
+            scroll_to_hash_or_top(&location_hash(), loc.scroll);
+        }
+    }
+
+    // the browser then fires `popstate` for the entry it moved to
+    fn go(&self, delta: i32) {
+        go(delta)
+    }
+}
+
+/// An integration for the browser that keeps the route in the URL's hash,
+/// like `/#/users/1`, which the [Router](crate::Router) uses in
+/// [RouterMode::Hash](crate::RouterMode::Hash).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HashIntegration {}
+
+impl HashIntegration {
+    fn current() -> LocationChange {
+        let route = Self::route();
+        LocationChange {
+            value: if route.starts_with('/') {
+                route
             } else {
-                log::warn!("RouterContext not found");
+                format!("/{route}")
+            },
+            ..BrowserIntegration::current()
+        }
+    }
+
+    // the path, query and hash of the route, without the `#` before them
+    fn route() -> String {
+        let hash = location_hash();
+        hash.strip_prefix('#').unwrap_or(&hash).to_string()
+    }
+
+    // the hash in the route, like the `#section` of `/#/users/1#section`
+    fn route_hash() -> String {
+        let route = Self::route();
+        route
+            .find('#')
+            .map(|i| route[i..].to_string())
+            .unwrap_or_default()
+    }
+}
+
+impl History for HashIntegration {
+    fn location(&self, cx: Scope) -> ReadSignal<LocationChange> {
+        scroll::start();
+        let (location, set_location) = create_signal(cx, Self::current());
+
+        let on_change = move || {
+            popped(cx, Self::current(), set_location, || {
+                scroll_to_hash_or_top(&Self::route_hash(), true)
+            })
+        };
+        leptos::window_event_listener(cx, ev::popstate, move |_| on_change());
+        // changing the hash fires `popstate` first, so this only handles
+        // the changes the router doesn't have yet
+        leptos::window_event_listener(cx, ev::hashchange, move |_| {
+            let router = use_context::<crate::RouterContext>(cx);
+            let known = router.is_some_and(|router| {
+                router.inner.reference.get_untracked() == Self::current().value
+            });
+            if !known {
+                on_change();
             }
         });
 
@@ -79,40 +157,104 @@ impl History for BrowserIntegration {
     }
 
     fn navigate(&self, loc: &LocationChange) {
-        let history = leptos_dom::window().history().unwrap_throw();
-
-        if loc.replace {
-            history
-                .replace_state_with_url(
-                    &loc.state.to_js_value(),
-                    "",
-                    Some(&loc.value),
-                )
-                .unwrap_throw();
-        } else {
-            history
-                .push_state_with_url(
-                    &loc.state.to_js_value(),
-                    "",
-                    Some(&loc.value),
-                )
-                .unwrap_throw();
+        push_or_replace(loc, &format!("#{}", loc.value));
+
+        if !scroll::is_restoring() {
+            scroll_to_hash_or_top(&Self::route_hash(), loc.scroll);
         }
-        // scroll to el
-        if let Ok(hash) = leptos_dom::helpers::location().hash() {
-            if !hash.is_empty() {
-                let hash = js_sys::decode_uri(&hash[1..])
-                    .ok()
-                    .and_then(|decoded| decoded.as_string())
-                    .unwrap_or(hash);
-                let el = leptos_dom::document().get_element_by_id(&hash);
-                if let Some(el) = el {
-                    el.scroll_into_view()
-                } else if loc.scroll {
-                    leptos_dom::window().scroll_to_with_x_and_y(0.0, 0.0);
-                }
+    }
+
+    fn go(&self, delta: i32) {
+        go(delta)
+    }
+}
+
+fn location_hash() -> String {
+    leptos_dom::helpers::location().hash().unwrap_or_default()
+}
+
+// tells the router about the entry the browser went back or forward to,
+// `change`, and then scrolls it back to where it was
+fn popped(
+    cx: Scope,
+    change: LocationChange,
+    set_location: WriteSignal<LocationChange>,
+    scroll_to_hash: impl FnOnce() + 'static,
+) {
+    use crate::{NavigateOptions, RouterContext};
+
+    let router = use_context::<RouterContext>(cx);
+    if let Some(router) = router {
+        let navigated = router.inner.navigate_from_route(
+            &change.value,
+            &NavigateOptions {
+                resolve: false,
+                replace: change.replace,
+                scroll: change.scroll,
+                state: change.state.clone(),
+            },
+        );
+        // once the router has saved the positions of the entry the
+        // user is leaving, and before it renders the one they're
+        // going to, which is then scrolled back to where it was
+        scroll::popped();
+        queue_microtask(move || {
+            if !scroll::restore() {
+                scroll_to_hash();
             }
+        });
+        if let Err(e) = navigated {
+            log::error!("{e:#?}");
         }
+        set_location.set(change);
+    } else {
+        log::warn!("RouterContext not found");
+    }
+}
+
+// adds an entry for `loc` at `url` to the browser's history, or replaces the
+// current one
+fn push_or_replace(loc: &LocationChange, url: &str) {
+    let history = leptos_dom::window().history().unwrap_throw();
+
+    if loc.replace {
+        let state =
+            scroll::entry_state(&scroll::current(), &loc.state.to_js_value());
+        history
+            .replace_state_with_url(&state, "", Some(url))
+            .unwrap_throw();
+    } else {
+        let state =
+            scroll::entry_state(&scroll::push(), &loc.state.to_js_value());
+        history
+            .push_state_with_url(&state, "", Some(url))
+            .unwrap_throw();
+    }
+}
+
+fn go(delta: i32) {
+    if let Ok(history) = leptos_dom::window().history() {
+        _ = history.go_with_delta(delta);
+    }
+}
+
+// scrolls to the element `hash` points to, if there is one, or else to the
+// top of the page if `scroll` is true
+fn scroll_to_hash_or_top(hash: &str, scroll: bool) {
+    let el =
+        hash.strip_prefix('#')
+            .filter(|id| !id.is_empty())
+            .and_then(|id| {
+                let id = js_sys::decode_uri(id)
+                    .ok()
+                    .and_then(|decoded| decoded.as_string())
+                    .unwrap_or_else(|| id.to_string());
+                leptos_dom::document().get_element_by_id(&id)
+            });
+    if let Some(el) = el {
+        el.scroll_into_view()
+    } else if scroll {
+        leptos_dom::window().scroll_to_with_x_and_y(0.0, 0.0);
     }
 }
 
@@ -148,11 +290,17 @@ impl History for RouterIntegrationContext {
     fn navigate(&self, loc: &LocationChange) {
         self.0.navigate(loc)
     }
+
+    fn go(&self, delta: i32) {
+        self.0.go(delta)
+    }
 }
 
 /// A generic router integration for the server side. All its need is the current path.
 #[derive(Clone, Debug)]
 pub struct ServerIntegration {
+    /// The URL of the request, with its query string, which the router
+    /// matches and reads from in place of `window.location`.
     pub path: String,
 }
 
@@ -1,5 +1,6 @@
+use crate::QueryMap;
 use linear_map::LinearMap;
-use std::{rc::Rc, str::FromStr};
+use std::{str::FromStr, sync::Arc};
 use thiserror::Error;
 
 /// A key-value map of the current named route params and their values.
@@ -92,6 +93,14 @@ where
 {
     /// Attempts to deserialize the map into the given type.
     fn from_map(map: &ParamsMap) -> Result<Self, ParamsError>;
+
+    /// Attempts to deserialize a query string, in which a key can have
+    /// several values, into the given type. Unless this is overridden, as
+    /// the derive macro does for `Vec` fields, only the first value of each
+    /// key is used.
+    fn from_query(query: &QueryMap) -> Result<Self, ParamsError> {
+        Self::from_map(&query.into())
+    }
 }
 
 impl Params for () {
@@ -100,40 +109,86 @@ impl Params for () {
     }
 }
 
+/// Parses a single field of a [Params] struct from the value of the param
+/// with the same name, if there is one.
+///
+/// An `Option<T>` is `None` when the param is missing, a `Vec<T>` is empty,
+/// and any other type is a [ParamsError::MissingParam]. With the `stable` feature, only `Option`s
+/// and the standard library's types that implement [FromStr] (numbers,
+/// `bool`, `char` and `String`) can be parsed without an implementation of
+/// this trait; otherwise, every type that implements [FromStr] can be.
 pub trait IntoParam
 where
     Self: Sized,
 {
+    /// Parses the value of the param called `name`.
     fn into_param(value: Option<&str>, name: &str)
         -> Result<Self, ParamsError>;
+
+    /// Parses every value of the param called `name`, in a query string
+    /// that can repeat it. Only a `Vec` uses more than the first value.
+    fn from_values(values: &[&str], name: &str) -> Result<Self, ParamsError> {
+        Self::into_param(values.first().copied(), name)
+    }
 }
 
 impl<T> IntoParam for Option<T>
 where
     T: FromStr,
-    <T as FromStr>::Err: std::error::Error + 'static,
+    <T as FromStr>::Err: std::error::Error + Send + Sync + 'static,
 {
     fn into_param(
         value: Option<&str>,
         _name: &str,
+    ) -> Result<Self, ParamsError> {
+        value
+            .map(|value| {
+                T::from_str(value).map_err(|e| ParamsError::Params(Arc::new(e)))
+            })
+            .transpose()
+    }
+}
+
+/// A `Vec` is empty when the param is missing, and in a query string, it has
+/// each value of a key that's repeated, as in `?tag=a&tag=b`.
+impl<T> IntoParam for Vec<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: std::error::Error + Send + Sync + 'static,
+{
+    fn into_param(
+        value: Option<&str>,
+        name: &str,
     ) -> Result<Self, ParamsError> {
         match value {
-            None => Ok(None),
-            Some(value) => match T::from_str(value) {
-                Ok(value) => Ok(Some(value)),
-                Err(e) => {
-                    eprintln!("{e}");
-                    Err(ParamsError::Params(Rc::new(e)))
-                }
-            },
+            Some(value) => Self::from_values(&[value], name),
+            None => Ok(Vec::new()),
         }
     }
+
+    fn from_values(values: &[&str], name: &str) -> Result<Self, ParamsError> {
+        values
+            .iter()
+            .map(|value| parse_param(Some(value), name))
+            .collect()
+    }
+}
+
+fn parse_param<T>(value: Option<&str>, name: &str) -> Result<T, ParamsError>
+where
+    T: FromStr,
+    <T as FromStr>::Err: std::error::Error + Send + Sync + 'static,
+{
+    let value =
+        value.ok_or_else(|| ParamsError::MissingParam(name.to_string()))?;
+    T::from_str(value).map_err(|e| ParamsError::Params(Arc::new(e)))
 }
 
 cfg_if::cfg_if! {
     if #[cfg(not(feature = "stable"))] {
         auto trait NotOption {}
         impl<T> !NotOption for Option<T> {}
+        impl<T, A: std::alloc::Allocator> !NotOption for Vec<T, A> {}
 
         impl<T> IntoParam for T
         where
@@ -141,10 +196,29 @@ cfg_if::cfg_if! {
             <T as FromStr>::Err: std::error::Error + Send + Sync + 'static,
         {
             fn into_param(value: Option<&str>, name: &str) -> Result<Self, ParamsError> {
-                let value = value.ok_or_else(|| ParamsError::MissingParam(name.to_string()))?;
-                Self::from_str(value).map_err(|e| ParamsError::Params(Rc::new(e)))
+                parse_param(value, name)
             }
         }
+    } else {
+        // without auto traits, a blanket impl would overlap with the one
+        // for `Option<T>`
+        macro_rules! impl_into_param {
+            ($($ty:ty),*) => {
+                $(
+                    impl IntoParam for $ty {
+                        fn into_param(value: Option<&str>, name: &str) -> Result<Self, ParamsError> {
+                            parse_param(value, name)
+                        }
+                    }
+                )*
+            };
+        }
+
+        impl_into_param!(
+            String, bool, char, f32, f64,
+            i8, i16, i32, i64, i128, isize,
+            u8, u16, u32, u64, u128, usize
+        );
     }
 }
 
@@ -155,8 +229,8 @@ pub enum ParamsError {
     #[error("could not find parameter {0}")]
     MissingParam(String),
     /// Something went wrong while deserializing a field.
-    #[error("failed to deserialize parameters")]
-    Params(Rc<dyn std::error::Error>),
+    #[error("failed to deserialize parameters: {0}")]
+    Params(Arc<dyn std::error::Error + Send + Sync>),
 }
 
 impl PartialEq for ParamsError {
@@ -0,0 +1,116 @@
+use crate::ParamsMap;
+
+/// The key-value pairs of a URL's query string, in order. Unlike a
+/// [ParamsMap], a key can have several values, as in `?tag=a&tag=b`.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct QueryMap(Vec<(String, String)>);
+
+impl QueryMap {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Gets the first value of a key.
+    pub fn get(&self, key: &str) -> Option<&String> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Gets every value of a key, in order.
+    pub fn get_all<'a>(
+        &'a self,
+        key: &'a str,
+    ) -> impl Iterator<Item = &'a String> + 'a {
+        self.0.iter().filter(move |(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Sets the value of a key, replacing all of its values, and returns the
+    /// first of them. A new key goes at the end of the query string, and an
+    /// existing one keeps its place.
+    pub fn insert(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Option<String> {
+        let key = key.into();
+        let mut value = Some(value.into());
+        let mut prev = None;
+        self.0.retain_mut(|(k, v)| {
+            if *k != key {
+                true
+            } else if let Some(value) = value.take() {
+                prev = Some(std::mem::replace(v, value));
+                true
+            } else {
+                false
+            }
+        });
+        if let Some(value) = value {
+            self.0.push((key, value));
+        }
+        prev
+    }
+
+    /// Adds another value for a key, after the ones it already has.
+    pub fn append(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.0.push((key.into(), value.into()));
+    }
+
+    /// Removes every value of a key, and returns the first of them.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        let prev = self.get(key).cloned();
+        self.0.retain(|(k, _)| k != key);
+        prev
+    }
+
+    /// Iterates over the key-value pairs, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.0.iter().map(|(k, v)| (k, v))
+    }
+
+    /// Whether there are no keys.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Converts the map to a query string, starting with `?` unless the
+    /// map is empty.
+    pub fn to_query_string(&self) -> String {
+        use crate::history::url::escape;
+        let mut buf = String::new();
+        for (k, v) in &self.0 {
+            buf.push(if buf.is_empty() { '?' } else { '&' });
+            buf.push_str(&escape(k));
+            buf.push('=');
+            buf.push_str(&escape(v));
+        }
+        buf
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for QueryMap
+where
+    K: Into<String>,
+    V: Into<String>,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+}
+
+impl From<&QueryMap> for ParamsMap {
+    /// Keeps the first value of each key.
+    fn from(query: &QueryMap) -> Self {
+        let mut map = ParamsMap::new();
+        for (k, v) in &query.0 {
+            if map.get(k).is_none() {
+                map.insert(k.clone(), v.clone());
+            }
+        }
+        map
+    }
+}
@@ -0,0 +1,253 @@
+//! Scroll restoration for the entries of the browser's history stack.
+//!
+//! Each entry that the [BrowserIntegration](crate::BrowserIntegration)
+//! pushes has a key, kept alongside its [State](crate::State) in
+//! `history.state`. Before navigating away from an entry, the scroll
+//! positions of the window and of the elements registered with
+//! [use_scroll_restoration](crate::use_scroll_restoration) are saved under
+//! its key, and they're restored when the user goes back or forward to it.
+
+// the router only navigates, and saves positions, in the browser
+#![cfg_attr(feature = "ssr", allow(dead_code))]
+
+use leptos::leptos_dom;
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use wasm_bindgen::{JsCast, JsValue};
+
+// the properties of the object the router keeps in `history.state`
+const KEY: &str = "_leptos_key";
+const STATE: &str = "_leptos_state";
+
+// how long to keep trying to restore a position, while the page isn't tall
+// enough yet, e.g., because `<Suspense/>` is still showing its fallback
+const RESTORE_TIMEOUT_MS: f64 = 5_000.0;
+
+#[derive(Debug, Clone, Default)]
+struct Position {
+    window: (f64, f64),
+    elements: HashMap<String, (i32, i32)>,
+}
+
+type Element = Rc<dyn Fn() -> Option<web_sys::Element>>;
+
+#[derive(Default)]
+struct Positions {
+    // the key of the entry that's being shown
+    current: Option<String>,
+    saved: HashMap<String, Position>,
+    elements: HashMap<String, (usize, Element)>,
+    next_element_id: usize,
+    // the entry the user just went back or forward to
+    restoring: Option<String>,
+}
+
+thread_local! {
+    static POSITIONS: RefCell<Positions> = Default::default();
+}
+
+fn new_key() -> String {
+    format!("{:x}", js_sys::Math::random().to_bits())
+}
+
+/// Wraps the state of a new entry with the key of its scroll positions.
+pub(crate) fn entry_state(key: &str, state: &JsValue) -> JsValue {
+    let entry = js_sys::Object::new();
+    _ = js_sys::Reflect::set(&entry, &KEY.into(), &key.into());
+    _ = js_sys::Reflect::set(&entry, &STATE.into(), state);
+    entry.into()
+}
+
+fn entry_key(entry: &JsValue) -> Option<String> {
+    if !entry.is_object() {
+        return None;
+    }
+    js_sys::Reflect::get(entry, &KEY.into())
+        .ok()
+        .and_then(|key| key.as_string())
+}
+
+/// The state the app pushed with an entry, without the router's key.
+pub(crate) fn user_state(entry: JsValue) -> JsValue {
+    if entry_key(&entry).is_some() {
+        js_sys::Reflect::get(&entry, &STATE.into())
+            .unwrap_or(JsValue::UNDEFINED)
+    } else {
+        entry
+    }
+}
+
+// the key of the entry that's being shown, which is given one if it was
+// pushed by something other than the router
+fn current_entry_key() -> Option<String> {
+    let history = leptos_dom::window().history().ok()?;
+    let entry = history.state().unwrap_or(JsValue::UNDEFINED);
+    entry_key(&entry).or_else(|| {
+        let key = new_key();
+        history.replace_state(&entry_state(&key, &entry), "").ok()?;
+        Some(key)
+    })
+}
+
+/// Takes over scroll restoration from the browser, which would otherwise
+/// restore the window's position before the entry's content is rendered.
+pub(crate) fn start() {
+    if let Ok(history) = leptos_dom::window().history() {
+        _ = history.set_scroll_restoration(web_sys::ScrollRestoration::Manual);
+    }
+    let key = current_entry_key();
+    POSITIONS.with(|p| p.borrow_mut().current = key);
+}
+
+/// Saves the scroll positions of the entry that's being shown.
+pub(crate) fn save() {
+    let window = leptos_dom::window();
+    let position = Position {
+        window: (
+            window.scroll_x().unwrap_or_default(),
+            window.scroll_y().unwrap_or_default(),
+        ),
+        elements: POSITIONS.with(|p| {
+            p.borrow()
+                .elements
+                .iter()
+                .filter_map(|(key, (_, el))| {
+                    let el = el()?;
+                    Some((key.clone(), (el.scroll_left(), el.scroll_top())))
+                })
+                .collect()
+        }),
+    };
+    POSITIONS.with(|p| {
+        let mut p = p.borrow_mut();
+        if let Some(key) = p.current.clone() {
+            p.saved.insert(key, position);
+        }
+    });
+}
+
+/// The key for an entry that's about to be pushed, which is then shown.
+pub(crate) fn push() -> String {
+    let key = new_key();
+    POSITIONS.with(|p| p.borrow_mut().current = Some(key.clone()));
+    key
+}
+
+/// The key of the entry that's being shown, for replacing it.
+pub(crate) fn current() -> String {
+    POSITIONS.with(|p| {
+        let mut p = p.borrow_mut();
+        p.current.get_or_insert_with(new_key).clone()
+    })
+}
+
+/// Called when the user has gone back or forward to another entry, whose
+/// positions are restored once the router has rendered it.
+pub(crate) fn popped() {
+    let key = current_entry_key();
+    POSITIONS.with(|p| {
+        let mut p = p.borrow_mut();
+        p.current = key.clone();
+        p.restoring = key;
+    });
+}
+
+/// Whether the entry being navigated to is one the user went back or
+/// forward to, whose positions will be restored, rather than a new one.
+pub(crate) fn is_restoring() -> bool {
+    POSITIONS.with(|p| p.borrow().restoring.is_some())
+}
+
+/// Restores the positions of the entry the user went back or forward to,
+/// if they were saved. Returns `false` if there weren't any, in which case
+/// the entry is scrolled like a new one.
+pub(crate) fn restore() -> bool {
+    let position = POSITIONS.with(|p| {
+        let mut p = p.borrow_mut();
+        let key = p.restoring.take()?;
+        p.saved.get(&key).cloned().map(|position| (key, position))
+    });
+    let Some((key, position)) = position else {
+        return false;
+    };
+    let started = js_sys::Date::now();
+    restore_until_done(key, position, started, None);
+    true
+}
+
+// scrolls to the saved positions, and tries again before the next repaint
+// while they can't be reached yet, until the user navigates or scrolls
+// somewhere else
+fn restore_until_done(
+    key: String,
+    position: Position,
+    started: f64,
+    last: Option<(f64, f64)>,
+) {
+    let window = leptos_dom::window();
+    let scrolled = (
+        window.scroll_x().unwrap_or_default(),
+        window.scroll_y().unwrap_or_default(),
+    );
+    let still_shown =
+        POSITIONS.with(|p| p.borrow().current.as_deref() == Some(&key));
+    if !still_shown
+        || last.is_some_and(|last| last != scrolled)
+        || js_sys::Date::now() - started > RESTORE_TIMEOUT_MS
+    {
+        return;
+    }
+
+    let (x, y) = position.window;
+    window.scroll_to_with_x_and_y(x, y);
+    let mut done = window.scroll_y().unwrap_or_default() == y
+        && window.scroll_x().unwrap_or_default() == x;
+    for (el_key, (left, top)) in &position.elements {
+        let el = POSITIONS.with(|p| {
+            p.borrow().elements.get(el_key).map(|(_, el)| Rc::clone(el))
+        });
+        match el.and_then(|el| el()) {
+            Some(el) => {
+                el.set_scroll_left(*left);
+                el.set_scroll_top(*top);
+                done &= el.scroll_left() == *left && el.scroll_top() == *top;
+            }
+            None => done = false,
+        }
+    }
+
+    if !done {
+        let last = Some((
+            window.scroll_x().unwrap_or_default(),
+            window.scroll_y().unwrap_or_default(),
+        ));
+        let next = wasm_bindgen::closure::Closure::once_into_js(move || {
+            restore_until_done(key, position, started, last)
+        });
+        _ = window.request_animation_frame(next.unchecked_ref());
+    }
+}
+
+/// Registers an element whose scroll position is saved and restored, and
+/// returns the ID to unregister it with.
+pub(crate) fn register(
+    key: String,
+    el: impl Fn() -> Option<web_sys::Element> + 'static,
+) -> usize {
+    POSITIONS.with(|p| {
+        let mut p = p.borrow_mut();
+        p.next_element_id += 1;
+        let id = p.next_element_id;
+        p.elements.insert(key, (id, Rc::new(el)));
+        id
+    })
+}
+
+/// Unregisters an element, unless another one was registered with its key.
+pub(crate) fn unregister(key: &str, id: usize) {
+    POSITIONS.with(|p| {
+        let mut p = p.borrow_mut();
+        if p.elements.get(key).map(|(el_id, _)| *el_id) == Some(id) {
+            p.elements.remove(key);
+        }
+    });
+}
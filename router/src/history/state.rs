@@ -1,15 +1,65 @@
+use serde::{de::DeserializeOwned, Serialize};
 use wasm_bindgen::JsValue;
 
+/// The [`state`](https://developer.mozilla.org/en-US/docs/Web/API/History/state)
+/// of an entry in the history stack, which is pushed along with its URL, and
+/// can be read from [Location::state](crate::Location::state).
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct State(pub Option<JsValue>);
 
 impl State {
+    /// The state as a JavaScript value, `undefined` if there isn't any.
     pub fn to_js_value(&self) -> JsValue {
         match &self.0 {
             Some(v) => v.clone(),
             None => JsValue::UNDEFINED,
         }
     }
+
+    /// Creates state from any value that can be serialized, e.g., a
+    /// `serde_json::Value`. The browser keeps a copy of it with the history
+    /// entry, so it's still there after going back to the entry or reloading
+    /// the page. There's no history outside the browser, where the state is
+    /// always empty.
+    pub fn from_serde<T>(value: &T) -> Result<Self, serde_json::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let json = serde_json::to_string(value)?;
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            Ok(State(js_sys::JSON::parse(&json).ok()))
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            _ = json;
+            Ok(State(None))
+        }
+    }
+
+    /// Deserializes state that was created with [State::from_serde], or
+    /// returns `None` if there isn't any.
+    pub fn deserialize<T>(&self) -> Option<Result<T, serde_json::Error>>
+    where
+        T: DeserializeOwned,
+    {
+        #[cfg(target_arch = "wasm32")]
+        {
+            let value = self
+                .0
+                .as_ref()
+                .filter(|value| !value.is_undefined() && !value.is_null())?;
+            let json = js_sys::JSON::stringify(value).ok()?;
+            Some(serde_json::from_str(&String::from(json)))
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            None
+        }
+    }
 }
 
 impl<T> From<T> for State
@@ -1,4 +1,4 @@
-use crate::ParamsMap;
+use crate::QueryMap;
 #[cfg(not(feature = "ssr"))]
 use js_sys::{try_iter, Array, JsString};
 #[cfg(not(feature = "ssr"))]
@@ -11,7 +11,7 @@ pub struct Url {
     pub origin: String,
     pub pathname: String,
     pub search: String,
-    pub search_params: ParamsMap,
+    pub search_params: QueryMap,
     pub hash: String,
 }
 
@@ -28,7 +28,7 @@ pub fn escape(s: &str) -> String {
 
 #[cfg(not(feature = "ssr"))]
 pub fn escape(s: &str) -> String {
-    js_sys::encode_uri(s).as_string().unwrap()
+    js_sys::encode_uri_component(s).as_string().unwrap()
 }
 
 #[cfg(not(feature = "ssr"))]
@@ -43,34 +43,30 @@ impl TryFrom<&str> for Url {
             origin: url.origin(),
             pathname: url.pathname(),
             search: url.search(),
-            search_params: ParamsMap(
-                try_iter(&url.search_params())
-                    .map_js_error()?
-                    .ok_or(
-                        "Failed to use URLSearchParams as an iterator"
-                            .to_string(),
-                    )?
-                    .map(|value| {
-                        let array: Array =
-                            value.map_js_error()?.dyn_into().map_js_error()?;
-                        Ok((
+            search_params: try_iter(&url.search_params())
+                .map_js_error()?
+                .ok_or(
+                    "Failed to use URLSearchParams as an iterator".to_string(),
+                )?
+                .map(|value| {
+                    let array: Array =
+                        value.map_js_error()?.dyn_into().map_js_error()?;
+                    Ok((
+                        String::from(
                             array
                                 .get(0)
                                 .dyn_into::<JsString>()
-                                .map_js_error()?
-                                .into(),
+                                .map_js_error()?,
+                        ),
+                        String::from(
                             array
                                 .get(1)
                                 .dyn_into::<JsString>()
-                                .map_js_error()?
-                                .into(),
-                        ))
-                    })
-                    .collect::<Result<
-                        linear_map::LinearMap<String, String>,
-                        Self::Error,
-                    >>()?,
-            ),
+                                .map_js_error()?,
+                        ),
+                    ))
+                })
+                .collect::<Result<QueryMap, Self::Error>>()?,
             hash: url.hash(),
         })
     }
@@ -81,17 +77,25 @@ impl TryFrom<&str> for Url {
     type Error = String;
 
     fn try_from(url: &str) -> Result<Self, Self::Error> {
-        let url = url::Url::parse(url).map_err(|e| e.to_string())?;
+        // like the browser, resolve paths the router navigates to against
+        // a placeholder origin
+        let fake_host = url::Url::parse("http://leptos").unwrap();
+        let url = fake_host.join(url).map_err(|e| e.to_string())?;
+        // `search` and `hash` keep their `?` and `#`, as in the browser
         Ok(Self {
             origin: url.origin().unicode_serialization(),
             pathname: url.path().to_string(),
-            search: url.query().unwrap_or_default().to_string(),
-            search_params: ParamsMap(
-                url.query_pairs()
-                    .map(|(key, value)| (key.to_string(), value.to_string()))
-                    .collect::<linear_map::LinearMap<String, String>>(),
-            ),
-            hash: Default::default(),
+            search: url
+                .query()
+                .filter(|query| !query.is_empty())
+                .map(|query| format!("?{query}"))
+                .unwrap_or_default(),
+            search_params: url.query_pairs().collect(),
+            hash: url
+                .fragment()
+                .filter(|hash| !hash.is_empty())
+                .map(|hash| format!("#{hash}"))
+                .unwrap_or_default(),
         })
     }
 }
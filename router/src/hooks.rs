@@ -1,8 +1,12 @@
 use crate::{
-    Location, NavigateOptions, NavigationError, Params, ParamsError, ParamsMap,
-    RouteContext, RouterContext,
+    IntoParam, Location, NavigateOptions, NavigationError, Params, ParamsError,
+    ParamsMap, QueryMap, RouteContext, RouterContext, ServerRedirectFunction,
+};
+use cfg_if::cfg_if;
+use leptos::{
+    create_memo, html::ElementDescriptor, signal_prelude::*, use_context, Memo,
+    NodeRef, Scope,
 };
-use leptos::{create_memo, signal_prelude::*, use_context, Memo, Scope};
 use std::rc::Rc;
 
 /// Returns the current [RouterContext], containing information about the router's state.
@@ -17,6 +21,25 @@ pub fn use_router(cx: Scope) -> RouterContext {
     }
 }
 
+/// Returns whether the router is waiting for the route it's navigating to to
+/// load, while it keeps showing the page it's leaving. This can be used to
+/// show a progress bar.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// #[component]
+/// fn ProgressBar(cx: Scope) -> impl IntoView {
+///     let is_routing = use_is_routing(cx);
+///     view! { cx,
+///         <div class="progress" class:hidden=move || !is_routing.get()></div>
+///     }
+/// }
+/// ```
+pub fn use_is_routing(cx: Scope) -> Signal<bool> {
+    use_router(cx).is_routing()
+}
+
 /// Returns the current [RouteContext], containing information about the matched route.
 pub fn use_route(cx: Scope) -> RouteContext {
     use_context::<RouteContext>(cx).unwrap_or_else(|| use_router(cx).base())
@@ -34,6 +57,26 @@ pub fn use_params_map(cx: Scope) -> Memo<ParamsMap> {
 }
 
 /// Returns the current route params, parsed into the given type, or an error.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// // in a route like "/users/:id/:tab?"
+/// #[derive(Params, Clone, PartialEq, Debug)]
+/// struct UserParams {
+///     id: usize,
+///     tab: Option<String>,
+/// }
+///
+/// #[component]
+/// fn User(cx: Scope) -> impl IntoView {
+///     let params = use_params::<UserParams>(cx);
+///     move || match params.get() {
+///         Ok(UserParams { id, tab }) => format!("User #{id}, {tab:?}"),
+///         Err(e) => e.to_string(),
+///     }
+/// }
+/// ```
 pub fn use_params<T: Params>(cx: Scope) -> Memo<Result<T, ParamsError>>
 where
     T: PartialEq + std::fmt::Debug,
@@ -42,45 +85,278 @@ where
     create_memo(cx, move |_| route.params().with(T::from_map))
 }
 
+/// Returns the route param called `name`, parsed into the given type, or an
+/// error. A param that's missing is `None` if `T` is an [Option], and a
+/// [ParamsError::MissingParam] otherwise.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// #[component]
+/// fn User(cx: Scope) -> impl IntoView {
+///     let id = use_param::<usize>(cx, "id");
+///     move || match id.get() {
+///         Ok(id) => format!("User #{id}"),
+///         Err(e) => e.to_string(),
+///     }
+/// }
+/// ```
+pub fn use_param<T>(
+    cx: Scope,
+    name: impl Into<String>,
+) -> Memo<Result<T, ParamsError>>
+where
+    T: IntoParam + PartialEq + std::fmt::Debug,
+{
+    let route = use_route(cx);
+    let name = name.into();
+    create_memo(cx, move |_| {
+        route
+            .params()
+            .with(|p| T::into_param(p.get(&name).map(String::as_str), &name))
+    })
+}
+
+/// Reads the data loaded by the current route's [Loader](crate::Loader),
+/// which is `None` while it's loading. Like reading any other resource, this
+/// suspends the nearest `<Suspense/>`, and is reactive when it's called in
+/// a reactive context, like a closure in the view.
+///
+/// # Panics
+/// Panics if the current route doesn't have a loader whose data is a `T`.
+pub fn use_loader_data<T: Clone + 'static>(cx: Scope) -> Option<T> {
+    let route = use_route(cx);
+    let Some(data) = route.loader_data::<T>() else {
+        panic!(
+            "the route {:?} doesn't have a loader whose data is a {}",
+            route.original_path(),
+            std::any::type_name::<T>()
+        );
+    };
+    data.read(cx)
+}
+
 /// Returns a raw key-value map of the URL search query.
-pub fn use_query_map(cx: Scope) -> Memo<ParamsMap> {
+pub fn use_query_map(cx: Scope) -> Memo<QueryMap> {
     use_router(cx).inner.location.query
 }
 
 /// Returns the current URL search query, parsed into the given type, or an error.
+/// A `Vec` field collects every value of a key that's repeated.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// // e.g., "?page=2&tag=rust&tag=web"
+/// #[derive(Params, Clone, PartialEq, Debug)]
+/// struct Filters {
+///     page: Option<usize>,
+///     tag: Vec<String>,
+/// }
+///
+/// #[component]
+/// fn Search(cx: Scope) -> impl IntoView {
+///     let filters = use_query::<Filters>(cx);
+///     let page = move || {
+///         filters.with(|f| f.as_ref().ok().and_then(|f| f.page).unwrap_or(1))
+///     };
+///     view! { cx, <p>"Page " {page}</p> }
+/// }
+/// ```
 pub fn use_query<T: Params>(cx: Scope) -> Memo<Result<T, ParamsError>>
 where
     T: PartialEq + std::fmt::Debug,
 {
     let router = use_router(cx);
     create_memo(cx, move |_| {
-        router.inner.location.query.with(|m| T::from_map(m))
+        router.inner.location.query.with(|m| T::from_query(m))
     })
 }
 
-/// Resolves the given path relative to the current route.
+/// Updates the URL search query with `f`, keeping its other keys, the path
+/// and the hash. This is an ordinary navigation within the app, that adds an
+/// entry to the history stack without scrolling to the top of the page, so
+/// only the routes and memos that depend on the query are updated.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// #[component]
+/// fn NextPage(cx: Scope) -> impl IntoView {
+///     let query = use_query_map(cx);
+///     let next = move |_| {
+///         let page = query.with(|q| {
+///             q.get("page").and_then(|p| p.parse().ok()).unwrap_or(1)
+///         });
+///         _ = update_query(cx, |q| {
+///             q.insert("page", (page + 1).to_string());
+///         });
+///     };
+///     view! { cx, <button on:click=next>"Next"</button> }
+/// }
+/// ```
+pub fn update_query(
+    cx: Scope,
+    f: impl FnOnce(&mut QueryMap),
+) -> Result<(), NavigationError> {
+    let state = use_location(cx).state.get_untracked();
+    update_query_with_options(
+        cx,
+        NavigateOptions {
+            scroll: false,
+            state,
+            ..Default::default()
+        },
+        f,
+    )
+}
+
+/// Updates the URL search query with `f`, like [update_query], but with the
+/// given options, e.g., to replace the current entry in the history stack
+/// instead of adding one. The path is never resolved relative to the route.
+pub fn update_query_with_options(
+    cx: Scope,
+    options: NavigateOptions,
+    f: impl FnOnce(&mut QueryMap),
+) -> Result<(), NavigationError> {
+    let router = use_router(cx);
+    let Location {
+        pathname,
+        query,
+        hash,
+        ..
+    } = &router.inner.location;
+    let mut query = query.get_untracked();
+    f(&mut query);
+    let to = pathname.get_untracked()
+        + &query.to_query_string()
+        + &hash.get_untracked();
+    Rc::clone(&router.inner).navigate_from_route(
+        &to,
+        &NavigateOptions {
+            resolve: false,
+            ..options
+        },
+    )
+}
+
+/// Resolves the given path relative to the current route. An absolute path
+/// is resolved against the [Router](crate::Router)'s `base`, if it has one,
+/// and a full URL, like `https://leptos.dev`, is left as it is.
 pub fn use_resolved_path(
     cx: Scope,
     path: impl Fn() -> String + 'static,
 ) -> Memo<Option<String>> {
     let route = use_route(cx);
+    let has_base = use_router(cx).inner.base_path != "/";
 
     create_memo(cx, move |_| {
         let path = path();
-        if path.starts_with('/') {
+        if path.starts_with('/') && !has_base {
             Some(path)
         } else {
-            route.resolve_path(&path).map(String::from)
+            route.resolve_path(&path).or(Some(path))
         }
     })
 }
 
 /// Returns a function that can be used to navigate to a new route.
+///
+/// Unless [NavigateOptions::resolve] is `false`, a relative path is resolved
+/// relative to the current route, like the `href` of an [A](crate::A). While
+/// rendering on the server, navigating redirects the response to the path
+/// instead, if the server integration has provided a way of doing that (see
+/// [provide_server_redirect](crate::provide_server_redirect)).
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// #[component]
+/// fn Logout(cx: Scope) -> impl IntoView {
+///     let navigate = use_navigate(cx);
+///     let on_click = move |_| {
+///         _ = navigate(
+///             "/login",
+///             NavigateOptions {
+///                 // going back shouldn't return to the logged-in page
+///                 replace: true,
+///                 state: State::from_serde(&"logged out").unwrap(),
+///                 ..Default::default()
+///             },
+///         );
+///     };
+///     view! { cx, <button on:click=on_click>"Log out"</button> }
+/// }
+/// ```
 pub fn use_navigate(
     cx: Scope,
 ) -> impl Fn(&str, NavigateOptions) -> Result<(), NavigationError> {
     let router = use_router(cx);
+    let route = use_route(cx);
+    let server_redirect = use_context::<ServerRedirectFunction>(cx);
     move |to, options| {
-        Rc::clone(&router.inner).navigate_from_route(to, &options)
+        let to = if options.resolve {
+            route
+                .resolve_path(to)
+                .ok_or_else(|| NavigationError::NotRoutable(to.to_string()))?
+        } else {
+            to.to_string()
+        };
+
+        // there's no history on the server, only the response
+        if let Some(server_redirect) = &server_redirect {
+            (server_redirect.f)(&to);
+            return Ok(());
+        }
+
+        Rc::clone(&router.inner).navigate_from_route(
+            &to,
+            &NavigateOptions {
+                resolve: false,
+                ..options
+            },
+        )
+    }
+}
+
+/// Saves the scroll position of an element, like the router does for the
+/// window, before navigating away from the page, and restores it when the
+/// user goes back or forward to the page. This is useful for elements that
+/// scroll on their own, like a sidebar.
+///
+/// The `key` tells elements apart, so that the position can be restored to
+/// the element that shows the same thing after its route is rendered again.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// #[component]
+/// fn Sidebar(cx: Scope) -> impl IntoView {
+///     let sidebar = create_node_ref::<html::Nav>(cx);
+///     use_scroll_restoration(cx, "sidebar", sidebar);
+///     view! { cx, <nav _ref=sidebar class="sidebar">"..."</nav> }
+/// }
+/// ```
+pub fn use_scroll_restoration<T>(
+    cx: Scope,
+    key: impl Into<String>,
+    node_ref: NodeRef<T>,
+) where
+    T: ElementDescriptor + Clone + 'static,
+{
+    cfg_if! {
+        if #[cfg(not(feature = "ssr"))] {
+            use crate::history::scroll;
+            use leptos::on_cleanup;
+
+            let key = key.into();
+            let id = scroll::register(key.clone(), move || {
+                let el = node_ref.get()?.into_any();
+                Some(web_sys::HtmlElement::clone(&el).into())
+            });
+            on_cleanup(cx, move || scroll::unregister(&key, id));
+        } else {
+            _ = (cx, key, node_ref);
+        }
     }
 }
@@ -185,6 +185,7 @@
 
 #![cfg_attr(not(feature = "stable"), feature(auto_traits))]
 #![cfg_attr(not(feature = "stable"), feature(negative_impls))]
+#![cfg_attr(not(feature = "stable"), feature(allocator_api))]
 #![cfg_attr(not(feature = "stable"), feature(type_name_of_val))]
 
 mod components;
@@ -1,38 +1,32 @@
 use std::borrow::Cow;
 
+// The same implementation is used in the browser and on the server, so that a
+// route is expanded, and matched, the same way by both of them.
+
+/// Expands a pattern with optional params into one pattern for every
+/// combination of them, e.g., `/users/:id?/:tab?` into `/users`,
+/// `/users/:id` and `/users/:id/:tab`.
 #[doc(hidden)]
-#[cfg(not(feature = "ssr"))]
 pub fn expand_optionals(pattern: &str) -> Vec<Cow<str>> {
-    use wasm_bindgen::JsValue;
-
-    #[allow(non_snake_case)]
-    let OPTIONAL_RE = js_sys::RegExp::new(OPTIONAL, "");
-    #[allow(non_snake_case)]
-    let OPTIONAL_RE_2 = js_sys::RegExp::new(OPTIONAL_2, "");
-
-    let captures = OPTIONAL_RE.exec(pattern);
-    match captures {
+    match find_optional(pattern) {
         None => vec![pattern.into()],
-        Some(matched) => {
-            let start: usize =
-                js_sys::Reflect::get(&matched, &JsValue::from_str("index"))
-                    .unwrap()
-                    .as_f64()
-                    .unwrap() as usize;
+        Some((start, len)) => {
             let mut prefix = pattern[0..start].to_string();
-            let mut suffix =
-                &pattern[start + matched.get(1).as_string().unwrap().len()..];
+            let mut suffix = &pattern[start + len..];
             let mut prefixes = vec![prefix.clone()];
 
-            prefix += &matched.get(1).as_string().unwrap();
+            prefix += &pattern[start..start + len];
             prefixes.push(prefix.clone());
 
-            while let Some(matched) =
-                OPTIONAL_RE_2.exec(suffix.trim_start_matches('?'))
+            // the optional params that directly follow it
+            while let Some(len) =
+                optional_at_start(suffix.trim_start_matches('?'))
             {
-                prefix += &matched.get(1).as_string().unwrap();
+                let trimmed =
+                    suffix.len() - suffix.trim_start_matches('?').len();
+                prefix += &suffix[trimmed..trimmed + len];
                 prefixes.push(prefix.clone());
-                suffix = &suffix[matched.get(0).as_string().unwrap().len()..];
+                suffix = &suffix[trimmed + len..];
             }
 
             expand_optionals(suffix).iter().fold(
@@ -50,50 +44,30 @@ pub fn expand_optionals(pattern: &str) -> Vec<Cow<str>> {
     }
 }
 
-#[doc(hidden)]
-#[cfg(feature = "ssr")]
-pub fn expand_optionals(pattern: &str) -> Vec<Cow<str>> {
-    use regex::Regex;
-
-    lazy_static::lazy_static! {
-        pub static ref OPTIONAL_RE: Regex = Regex::new(OPTIONAL).expect("could not compile OPTIONAL_RE");
-        pub static ref OPTIONAL_RE_2: Regex = Regex::new(OPTIONAL_2).expect("could not compile OPTIONAL_RE_2");
-    }
-
-    let captures = OPTIONAL_RE.find(pattern);
-    match captures {
-        None => vec![pattern.into()],
-        Some(matched) => {
-            let mut prefix = pattern[0..matched.start()].to_string();
-            let captures = OPTIONAL_RE.captures(pattern).unwrap();
-            let mut suffix = &pattern[matched.start() + captures[1].len()..];
-            let mut prefixes = vec![prefix.clone()];
-
-            prefix += &captures[1];
-            prefixes.push(prefix.clone());
-
-            while let Some(captures) =
-                OPTIONAL_RE_2.captures(suffix.trim_start_matches('?'))
-            {
-                prefix += &captures[1];
-                prefixes.push(prefix.clone());
-                suffix = &suffix[captures[0].len()..];
-            }
+// finds the first optional param, i.e., `:name?` with the `/` before it, if
+// there is one, and returns where it starts and its length without the `?`
+fn find_optional(pattern: &str) -> Option<(usize, usize)> {
+    pattern.match_indices(':').find_map(|(colon, _)| {
+        let len = param_len(&pattern[colon + 1..])?;
+        let start = if pattern[..colon].ends_with('/') {
+            colon - 1
+        } else {
+            colon
+        };
+        Some((start, colon + 1 + len - start))
+    })
+}
 
-            expand_optionals(suffix).iter().fold(
-                Vec::new(),
-                |mut results, expansion| {
-                    results.extend(prefixes.iter().map(|prefix| {
-                        Cow::Owned(
-                            prefix.clone() + expansion.trim_start_matches('?'),
-                        )
-                    }));
-                    results
-                },
-            )
-        }
-    }
+// the length of an optional param at the start of `pattern`, like `/:name?`,
+// without the `?`
+fn optional_at_start(pattern: &str) -> Option<usize> {
+    let rest = pattern.strip_prefix("/:")?;
+    param_len(rest).map(|len| len + 2)
 }
 
-const OPTIONAL: &str = r#"(/?:[^/]+)\?"#;
-const OPTIONAL_2: &str = r#"^(/:[^/]+)\?"#;
+// the length of a param's name, if it's followed by a `?` that makes it
+// optional; the name is everything up to the last `?` in its segment
+fn param_len(rest: &str) -> Option<usize> {
+    let segment = rest.split('/').next().unwrap_or_default();
+    segment.rfind('?').filter(|len| *len > 0)
+}
@@ -32,7 +32,8 @@ pub(crate) fn get_route_matches(
 pub struct Branch {
     /// All the routes contained in the branch.
     pub routes: Vec<RouteData>,
-    /// How closely this branch matches the current URL.
+    /// How closely this branch matches a URL, compared to the other
+    /// branches: the ones with higher scores are matched first.
     pub score: i32,
 }
 
@@ -1,4 +1,4 @@
-use crate::SsrMode;
+use crate::{Guard, Loader, SsrMode};
 use leptos::{leptos_dom::View, *};
 use std::rc::Rc;
 
@@ -17,6 +17,13 @@ pub struct RouteDefinition {
     pub view: Rc<dyn Fn(Scope) -> View>,
     /// The mode this route prefers during server-side rendering.
     pub ssr_mode: SsrMode,
+    /// Loads the data for this route while it's matched.
+    pub loader: Option<Loader>,
+    /// Decides whether this route is shown when it's matched.
+    pub guard: Option<Guard>,
+    /// Whether this is a [`<NotFound/>`](crate::NotFound) route, which is
+    /// matched after every other route next to it.
+    pub not_found: bool,
 }
 
 impl std::fmt::Debug for RouteDefinition {
@@ -25,6 +32,9 @@ impl std::fmt::Debug for RouteDefinition {
             .field("path", &self.path)
             .field("children", &self.children)
             .field("ssr_mode", &self.ssr_mode)
+            .field("loader", &self.loader)
+            .field("guard", &self.guard)
+            .field("not_found", &self.not_found)
             .finish()
     }
 }
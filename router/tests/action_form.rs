@@ -0,0 +1,136 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        pub struct Owner {
+            name: String,
+            email: Option<String>,
+        }
+
+        #[server(prefix = "/api", encoding = "Cbor")]
+        pub async fn rename_list(
+            id: usize,
+            owner: Owner,
+            title: String,
+        ) -> Result<String, ServerFnError> {
+            Ok(title)
+        }
+
+        #[server(encoding = "Multipart")]
+        pub async fn add_photos(
+            album: usize,
+            photos: Vec<UploadedFile>,
+        ) -> Result<usize, ServerFnError> {
+            Ok(photos.len())
+        }
+
+        // the fields of the arguments that the form doesn't ask for
+        #[derive(Serialize)]
+        struct List {
+            id: usize,
+            owner: Owner,
+        }
+
+        fn render(view: impl FnOnce(Scope) -> View + 'static) -> String {
+            let runtime = create_runtime();
+            let html = run_scope(runtime, move |cx| {
+                view(cx).render_to_string(cx).to_string()
+            });
+            runtime.dispose();
+            html
+        }
+
+        #[test]
+        fn the_form_posts_to_the_server_fn() {
+            let html = render(|cx| {
+                let rename = create_server_action::<RenameList>(cx);
+                view! { cx,
+                    <ActionForm action=rename class="rename">
+                        <input name="title"/>
+                    </ActionForm>
+                }
+                .into_view(cx)
+            });
+            assert!(html.contains(r#"method="post""#), "{html}");
+            assert!(
+                html.contains(&format!(r#"action="/api/{}""#, RenameList::url())),
+                "{html}"
+            );
+            // however the function's arguments are encoded
+            assert!(
+                html.contains(r#"enctype="application/x-www-form-urlencoded""#),
+                "{html}"
+            );
+            assert!(html.contains(r#"rename""#), "{html}");
+            assert!(!html.contains("aria-busy"), "{html}");
+            assert!(!html.contains("data-error"), "{html}");
+            assert!(html.contains(r#"name="title""#), "{html}");
+        }
+
+        #[test]
+        fn forms_with_files_are_posted_as_multipart() {
+            let html = render(|cx| {
+                let add = create_server_action::<AddPhotos>(cx);
+                view! { cx,
+                    <ActionForm action=add>
+                        <input type="file" name="photos" multiple/>
+                    </ActionForm>
+                }
+                .into_view(cx)
+            });
+            assert!(
+                html.contains(r#"enctype="multipart/form-data""#),
+                "{html}"
+            );
+        }
+
+        #[test]
+        fn the_hidden_arguments_are_rendered_as_hidden_fields() {
+            let html = render(|cx| {
+                let rename = create_server_action::<RenameList>(cx);
+                let list = List {
+                    id: 3,
+                    owner: Owner {
+                        name: "Greg".into(),
+                        email: None,
+                    },
+                };
+                view! { cx,
+                    <ActionForm action=rename hidden=list>
+                        <input name="title"/>
+                    </ActionForm>
+                }
+                .into_view(cx)
+            });
+            for field in [
+                r#"<input type="hidden" name="id" value="3""#,
+                r#"<input type="hidden" name="owner[name]" value="Greg""#,
+            ] {
+                assert!(html.contains(field), "{field} in {html}");
+            }
+            assert!(!html.contains("owner[email]"), "{html}");
+
+            // which the server reads back into the arguments
+            let fields = leptos::server_fn::form::to_fields(&List {
+                id: 3,
+                owner: Owner {
+                    name: "Greg".into(),
+                    email: None,
+                },
+            })
+            .unwrap()
+            .into_iter()
+            .chain([("title".to_string(), "Groceries".to_string())]);
+            let args: RenameList =
+                leptos::server_fn::form::from_fields(fields).unwrap();
+            assert_eq!(args.id, 3);
+            assert_eq!(args.owner.name, "Greg");
+            assert_eq!(args.title, "Groceries");
+        }
+    }
+}
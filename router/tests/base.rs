@@ -0,0 +1,165 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+        use std::{cell::RefCell, rc::Rc};
+
+        type Navigate = Rc<dyn Fn(&str, NavigateOptions) -> Result<(), NavigationError>>;
+
+        thread_local! {
+            // the navigate function of the matched route
+            static NAVIGATE: RefCell<Option<Navigate>> = Default::default();
+        }
+
+        #[derive(Clone)]
+        struct TestHistory(RwSignal<LocationChange>);
+
+        impl History for TestHistory {
+            fn location(&self, _cx: Scope) -> ReadSignal<LocationChange> {
+                self.0.read_only()
+            }
+
+            fn navigate(&self, loc: &LocationChange) {
+                self.0.set(loc.clone());
+            }
+        }
+
+        #[component]
+        fn Nav(cx: Scope) -> impl IntoView {
+            NAVIGATE.with(|n| *n.borrow_mut() = Some(Rc::new(use_navigate(cx))));
+            view! { cx,
+                <nav>
+                    <A href="/">"Home"</A>
+                    <A href="/users">"Users"</A>
+                    <A href="posts">"Posts"</A>
+                    <A href="https://leptos.dev/app">"Leptos"</A>
+                </nav>
+            }
+        }
+
+        fn app(cx: Scope, base: &'static str, mode: RouterMode, history: &TestHistory) -> View {
+            provide_context(cx, RouterIntegrationContext::new(history.clone()));
+            view! { cx,
+                <Router base mode fallback=|cx| view! { cx, <h1>"Not found"</h1> }.into_view(cx)>
+                    <Routes>
+                        <Route path="" view=|cx| view! { cx, <main>"Home"</main><Nav/> }/>
+                        <Route path="users/:id" view=|cx| view! { cx, <article>"User"</article><Nav/> }/>
+                    </Routes>
+                </Router>
+            }
+            .into_view(cx)
+        }
+
+        fn history_at(cx: Scope, path: &str) -> TestHistory {
+            TestHistory(create_rw_signal(
+                cx,
+                LocationChange {
+                    value: format!("http://leptos.dev{path}"),
+                    ..Default::default()
+                },
+            ))
+        }
+
+        fn render_at(base: &'static str, path: &str) -> String {
+            render_in(RouterMode::BrowserHistory, base, path)
+        }
+
+        fn render_in(mode: RouterMode, base: &'static str, path: &str) -> String {
+            let runtime = create_runtime();
+            let path = path.to_string();
+            let html = run_scope(runtime, move |cx| {
+                let history = history_at(cx, &path);
+                app(cx, base, mode, &history).render_to_string(cx).to_string()
+            });
+            runtime.dispose();
+            html
+        }
+
+        // the `href` of the link with the given text
+        fn href<'a>(html: &'a str, text: &str) -> &'a str {
+            let end = html.find(&format!(">{text}<")).unwrap_or_else(|| {
+                panic!("no link to {text} in {html}")
+            });
+            let link = &html[html[..end].rfind("<a").unwrap()..end];
+            link.split(r#"href=""#)
+                .nth(1)
+                .and_then(|rest| rest.split('"').next())
+                .unwrap()
+        }
+
+        #[test]
+        fn routes_are_matched_under_the_base() {
+            for base in ["/app", "app", "/app/"] {
+                let html = render_at(base, "/app");
+                assert!(html.contains("<main"), "{base}: {html}");
+                let html = render_at(base, "/app/");
+                assert!(html.contains("<main"), "{base}: {html}");
+                let html = render_at(base, "/app/users/1");
+                assert!(html.contains("<article"), "{base}: {html}");
+
+                // paths outside of the base aren't the app's
+                let html = render_at(base, "/users/1");
+                assert!(html.contains("Not found"), "{base}: {html}");
+                let html = render_at(base, "/application");
+                assert!(html.contains("Not found"), "{base}: {html}");
+            }
+        }
+
+        #[test]
+        fn links_include_the_base() {
+            let html = render_at("/app", "/app/users/1");
+            assert_eq!(href(&html, "Home"), "/app");
+            assert_eq!(href(&html, "Users"), "/app/users");
+            assert_eq!(href(&html, "Posts"), "/app/users/1/posts");
+            // full URLs are left alone
+            assert_eq!(href(&html, "Leptos"), "https://leptos.dev/app");
+        }
+
+        #[test]
+        fn hash_links_include_the_base() {
+            let html = render_in(RouterMode::Hash, "/app", "/app/users/1");
+            assert_eq!(href(&html, "Home"), "#/app");
+            assert_eq!(href(&html, "Users"), "#/app/users");
+            assert_eq!(href(&html, "Posts"), "#/app/users/1/posts");
+            assert_eq!(href(&html, "Leptos"), "https://leptos.dev/app");
+        }
+
+        #[test]
+        fn a_base_of_slash_is_the_same_as_none() {
+            for base in ["/", ""] {
+                let html = render_at(base, "/");
+                assert!(html.contains("<main"), "{base}: {html}");
+                let html = render_at(base, "/users/1");
+                assert!(html.contains("<article"), "{base}: {html}");
+                assert_eq!(href(&html, "Home"), "/");
+                assert_eq!(href(&html, "Users"), "/users");
+                assert_eq!(href(&html, "Posts"), "/users/1/posts");
+            }
+        }
+
+        #[test]
+        fn navigating_goes_to_paths_under_the_base() {
+            let runtime = create_runtime();
+            let (cx, disposer) = raw_scope_and_disposer(runtime);
+            // navigations are only handled in the browser
+            cx.set_render_mode(RenderMode::Csr);
+
+            let history = history_at(cx, "/app/users/1");
+            let _view = app(cx, "/app", RouterMode::BrowserHistory, &history);
+            let navigate = NAVIGATE.with(|n| n.borrow().clone()).unwrap();
+            let path = || history.0.with_untracked(|loc| loc.value.clone());
+
+            navigate("/users/2", Default::default()).unwrap();
+            assert_eq!(path(), "/app/users/2");
+
+            // the bare base is the app's root
+            navigate("/", Default::default()).unwrap();
+            assert_eq!(path(), "/app");
+
+            disposer.dispose();
+            runtime.dispose();
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+        use std::{cell::RefCell, rc::Rc};
+
+        thread_local! {
+            // every route view that's created or disposed, in order
+            static LOG: RefCell<Vec<String>> = Default::default();
+        }
+
+        fn log(cx: Scope, name: String) {
+            LOG.with(|log| log.borrow_mut().push(format!("+{name}")));
+            on_cleanup(cx, move || {
+                LOG.with(|log| log.borrow_mut().push(format!("-{name}")))
+            });
+        }
+
+        fn take_log() -> Vec<String> {
+            LOG.with(|log| log.take())
+        }
+
+        #[derive(Clone)]
+        struct TestHistory(RwSignal<LocationChange>);
+
+        impl History for TestHistory {
+            fn location(&self, _cx: Scope) -> ReadSignal<LocationChange> {
+                self.0.read_only()
+            }
+
+            fn navigate(&self, loc: &LocationChange) {
+                self.0.set(loc.clone());
+            }
+        }
+
+        impl TestHistory {
+            fn at(cx: Scope, path: &str) -> Self {
+                Self(create_rw_signal(
+                    cx,
+                    LocationChange {
+                        value: format!("http://leptos.dev{path}"),
+                        ..Default::default()
+                    },
+                ))
+            }
+
+            fn go(&self, path: &str) {
+                self.navigate(&LocationChange {
+                    value: format!("http://leptos.dev{path}"),
+                    ..Default::default()
+                });
+            }
+
+            fn path(&self) -> String {
+                self.0.with_untracked(|loc| loc.value.clone())
+            }
+        }
+
+        #[derive(Clone, Copy)]
+        struct LoggedIn(RwSignal<bool>);
+
+        fn must_be_logged_in(cx: Scope, _params: &ParamsMap) -> GuardResult {
+            let LoggedIn(logged_in) = use_context(cx).unwrap();
+            if logged_in.get() {
+                GuardResult::Allow
+            } else {
+                GuardResult::Redirect("/login".into())
+            }
+        }
+
+        fn not_hidden(_cx: Scope, params: &ParamsMap) -> GuardResult {
+            if params.get("id").map(String::as_str) == Some("hidden") {
+                GuardResult::Block
+            } else {
+                GuardResult::Allow
+            }
+        }
+
+        fn app(cx: Scope, history: &TestHistory, logged_in: bool) -> (View, RwSignal<bool>) {
+            let signal = create_rw_signal(cx, logged_in);
+            provide_context(cx, LoggedIn(signal));
+            provide_context(cx, RouterIntegrationContext::new(history.clone()));
+            let view = view! { cx,
+                <Router>
+                    <Routes>
+                        <Route
+                            path="account"
+                            view=|cx| {
+                                log(cx, "account".into());
+                                view! { cx, <section><Outlet/></section> }
+                            }
+                            guard=Guard::new(must_be_logged_in)
+                        >
+                            <Route
+                                path=":id"
+                                view=|cx| {
+                                    let id = use_params_map(cx)
+                                        .with_untracked(|p| p.get("id").cloned());
+                                    log(cx, format!("user {}", id.unwrap_or_default()));
+                                    view! { cx, <p>"User"</p> }
+                                }
+                                guard=Guard::new(not_hidden)
+                            />
+                            <Route path="" view=|_| ()/>
+                        </Route>
+                        <Route path="old" view=|cx| view! { cx, <Redirect path="/login"/> }/>
+                        <Route path="login" view=|cx| {
+                            log(cx, "login".into());
+                            view! { cx, <form>"Log in"</form> }
+                        }/>
+                    </Routes>
+                </Router>
+            }
+            .into_view(cx);
+            (view, signal)
+        }
+
+        // renders `path` on the server, with the redirects it makes
+        fn render(path: &str, logged_in: bool) -> (String, Vec<String>) {
+            let runtime = create_runtime();
+            let redirects = Rc::new(RefCell::new(Vec::new()));
+            let html = run_scope(runtime, {
+                let redirects = Rc::clone(&redirects);
+                let path = path.to_string();
+                move |cx| {
+                    // as the server integrations do, which set a 302 and a
+                    // `Location` header
+                    provide_server_redirect(cx, move |path| {
+                        redirects.borrow_mut().push(path.to_string())
+                    });
+                    let history = TestHistory::at(cx, &path);
+                    let (view, _) = app(cx, &history, logged_in);
+                    view.render_to_string(cx).to_string()
+                }
+            });
+            runtime.dispose();
+            let redirects = redirects.take();
+            (html, redirects)
+        }
+
+        #[test]
+        fn guards_redirect_the_response_on_the_server() {
+            let (html, redirects) = render("/account/1", false);
+            assert_eq!(redirects, ["/login"]);
+            // the guarded route isn't created at all
+            assert!(!html.contains("<section"), "{html}");
+            assert!(take_log().is_empty());
+
+            let (html, redirects) = render("/account/1", true);
+            assert!(redirects.is_empty());
+            assert!(html.contains("User"), "{html}");
+            assert_eq!(
+                take_log(),
+                ["+account", "+user 1", "-user 1", "-account"]
+            );
+        }
+
+        #[test]
+        fn redirect_redirects_the_response_on_the_server() {
+            let (_, redirects) = render("/old", true);
+            assert_eq!(redirects, ["/login"]);
+        }
+
+        #[test]
+        fn blocked_routes_are_not_shown() {
+            let (html, redirects) = render("/account/hidden", true);
+            assert!(redirects.is_empty());
+            // the parent is still shown, without the blocked route
+            assert!(html.contains("<section"), "{html}");
+            assert!(!html.contains("User"), "{html}");
+            assert_eq!(take_log(), ["+account", "-account"]);
+        }
+
+        #[test]
+        fn guards_run_again_when_their_signals_change() {
+            let runtime = create_runtime();
+            let (cx, disposer) = raw_scope_and_disposer(runtime);
+            cx.set_render_mode(RenderMode::Csr);
+            take_log();
+
+            let history = TestHistory::at(cx, "/account/1");
+            let (_view, logged_in) = app(cx, &history, true);
+            assert_eq!(take_log(), ["+account", "+user 1"]);
+
+            // logging out sends the user away from the protected page
+            logged_in.set(false);
+            assert_eq!(history.path(), "/login");
+            assert_eq!(take_log(), ["+login", "-user 1", "-account"]);
+
+            // and the guard keeps them from going back to it
+            history.go("/account/2");
+            assert_eq!(history.path(), "/login");
+            assert!(take_log().is_empty());
+
+            logged_in.set(true);
+            history.go("/account/2");
+            assert_eq!(take_log(), ["+account", "+user 2", "-login"]);
+
+            disposer.dispose();
+            runtime.dispose();
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+        use std::{cell::RefCell, rc::Rc};
+
+        thread_local! {
+            // every route view that's created or disposed, in order
+            static LOG: RefCell<Vec<String>> = Default::default();
+            static ROUTER: RefCell<Option<(RouterContext, Location)>> = Default::default();
+        }
+
+        fn take_log() -> Vec<String> {
+            LOG.with(|log| log.take())
+        }
+
+        // a History that moves through its stack of entries, like the browser's
+        #[derive(Clone)]
+        struct TestHistory {
+            location: RwSignal<LocationChange>,
+            entries: Rc<RefCell<(Vec<LocationChange>, usize)>>,
+        }
+
+        impl TestHistory {
+            fn at(cx: Scope, path: &str) -> Self {
+                let entry = LocationChange {
+                    value: format!("http://leptos.dev{path}"),
+                    ..Default::default()
+                };
+                Self {
+                    location: create_rw_signal(cx, entry.clone()),
+                    entries: Rc::new(RefCell::new((vec![entry], 0))),
+                }
+            }
+        }
+
+        impl History for TestHistory {
+            fn location(&self, _cx: Scope) -> ReadSignal<LocationChange> {
+                self.location.read_only()
+            }
+
+            fn navigate(&self, loc: &LocationChange) {
+                let mut entries = self.entries.borrow_mut();
+                let (entries, index) = &mut *entries;
+                if loc.replace {
+                    entries[*index] = loc.clone();
+                } else {
+                    entries.truncate(*index + 1);
+                    entries.push(loc.clone());
+                    *index += 1;
+                }
+                self.location.set(loc.clone());
+            }
+
+            fn go(&self, delta: i32) {
+                let entry = {
+                    let mut entries = self.entries.borrow_mut();
+                    let (entries, index) = &mut *entries;
+                    let next = *index as i32 + delta;
+                    if next < 0 || next as usize >= entries.len() {
+                        return;
+                    }
+                    *index = next as usize;
+                    entries[*index].clone()
+                };
+                self.location.set(entry);
+            }
+        }
+
+        fn router() -> (RouterContext, Location) {
+            ROUTER.with(|r| r.borrow().clone()).unwrap()
+        }
+
+        fn navigate(to: &str, options: NavigateOptions) {
+            let (router, _) = router();
+            let cx = router.base().cx();
+            use_navigate(cx)(to, options).unwrap();
+        }
+
+        #[component]
+        fn Page(cx: Scope) -> impl IntoView {
+            let id = use_params_map(cx)
+                .with_untracked(|p| p.get("id").cloned())
+                .unwrap_or_default();
+            LOG.with(|log| log.borrow_mut().push(format!("+page {id}")));
+            on_cleanup(cx, move || {
+                LOG.with(|log| log.borrow_mut().push(format!("-page {id}")))
+            });
+            view! { cx, <section id="top">"Page"</section> }
+        }
+
+        const MODES: [RouterMode; 2] = [RouterMode::BrowserHistory, RouterMode::Hash];
+
+        fn app(cx: Scope, mode: RouterMode, history: &TestHistory) -> View {
+            provide_context(cx, RouterIntegrationContext::new(history.clone()));
+            view! { cx,
+                <Router mode>
+                    {
+                        ROUTER.with(|r| {
+                            *r.borrow_mut() = Some((use_router(cx), use_location(cx)))
+                        });
+                    }
+                    <Routes>
+                        <Route path="pages/:id" view=|cx| view! { cx, <Page/> }/>
+                    </Routes>
+                </Router>
+            }
+            .into_view(cx)
+        }
+
+        fn parts(location: &Location) -> (String, String, String) {
+            (
+                location.pathname.get(),
+                location.search.get(),
+                location.hash.get(),
+            )
+        }
+
+        #[test]
+        fn the_location_follows_back_and_forward() {
+            for mode in MODES {
+                let runtime = create_runtime();
+                let (cx, disposer) = raw_scope_and_disposer(runtime);
+                // navigations are only handled in the browser
+                cx.set_render_mode(RenderMode::Csr);
+
+                let history = TestHistory::at(cx, "/pages/1");
+                let _view = app(cx, mode, &history);
+                let (router, location) = router();
+
+                // the state of an entry is kept by the browser, which the
+                // tests in examples/router/tests/history.rs check
+                navigate("/pages/2?tab=info#top", Default::default());
+                navigate("/pages/3", Default::default());
+                let third = parts(&location);
+                assert_eq!(third, ("/pages/3".into(), "".into(), "".into()));
+
+                router.back();
+                assert_eq!(
+                    parts(&location),
+                    ("/pages/2".into(), "?tab=info".into(), "#top".into())
+                );
+
+                router.go(-1);
+                assert_eq!(location.pathname.get(), "/pages/1");
+                // there's nothing before the first entry
+                router.back();
+                assert_eq!(location.pathname.get(), "/pages/1");
+
+                router.go(2);
+                assert_eq!(parts(&location), third);
+                router.forward();
+                assert_eq!(parts(&location), third);
+
+                disposer.dispose();
+                runtime.dispose();
+            }
+        }
+
+        #[test]
+        fn changing_only_the_hash_keeps_the_routes() {
+            for mode in MODES {
+                let runtime = create_runtime();
+                let (cx, disposer) = raw_scope_and_disposer(runtime);
+                cx.set_render_mode(RenderMode::Csr);
+                take_log();
+
+                let history = TestHistory::at(cx, "/pages/1");
+                let _view = app(cx, mode, &history);
+                let (router, location) = router();
+                assert_eq!(take_log(), ["+page 1"]);
+
+                navigate("/pages/1#top", Default::default());
+                assert_eq!(location.hash.get(), "#top");
+                assert_eq!(location.pathname.get(), "/pages/1");
+                router.back();
+                assert_eq!(location.hash.get(), "");
+                router.forward();
+                assert_eq!(location.hash.get(), "#top");
+                assert!(take_log().is_empty());
+
+                // but another path does recreate them
+                navigate("/pages/2#top", Default::default());
+                assert_eq!(take_log(), ["+page 2", "-page 1"]);
+
+                disposer.dispose();
+                runtime.dispose();
+            }
+        }
+    }
+}
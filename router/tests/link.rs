@@ -0,0 +1,127 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+
+        #[derive(Clone)]
+        struct TestHistory(RwSignal<LocationChange>);
+
+        impl History for TestHistory {
+            fn location(&self, _cx: Scope) -> ReadSignal<LocationChange> {
+                self.0.read_only()
+            }
+
+            fn navigate(&self, loc: &LocationChange) {
+                self.0.set(loc.clone());
+            }
+        }
+
+        #[component]
+        fn Nav(cx: Scope) -> impl IntoView {
+            view! { cx,
+                <nav>
+                    <A href="posts">"Posts"</A>
+                    <A href="/users" class="nav">"Users"</A>
+                    <A href="/users" exact=true>"All users"</A>
+                    <A href="/us">"Us"</A>
+                    <A href="" active_class="current">"This user"</A>
+                </nav>
+            }
+        }
+
+        const MODES: [RouterMode; 2] = [RouterMode::BrowserHistory, RouterMode::Hash];
+
+        fn render_at(mode: RouterMode, path: &str) -> String {
+            let runtime = create_runtime();
+            let url = format!("http://leptos.dev{path}");
+            let html = run_scope(runtime, move |cx| {
+                let history = TestHistory(create_rw_signal(
+                    cx,
+                    LocationChange {
+                        value: url,
+                        ..Default::default()
+                    },
+                ));
+                provide_context(cx, RouterIntegrationContext::new(history));
+                view! { cx,
+                    <Router mode>
+                        <Routes>
+                            <Route path="users/:id" view=|cx| view! { cx, <Nav/> }>
+                                <Route path="posts" view=|_| ()/>
+                                <Route path="" view=|_| ()/>
+                            </Route>
+                        </Routes>
+                    </Router>
+                }
+                .into_view(cx)
+                .render_to_string(cx)
+                .to_string()
+            });
+            runtime.dispose();
+            html
+        }
+
+        // the opening tag of the link with the given text
+        fn link<'a>(html: &'a str, text: &str) -> &'a str {
+            let end = html.find(&format!(">{text}<")).unwrap_or_else(|| {
+                panic!("no link to {text} in {html}")
+            });
+            &html[html[..end].rfind("<a").unwrap()..end]
+        }
+
+        fn classes(link: &str) -> Vec<&str> {
+            link.split(r#"class=""#)
+                .nth(1)
+                .and_then(|rest| rest.split('"').next())
+                .map(|class| class.split_whitespace().collect())
+                .unwrap_or_default()
+        }
+
+        // the `href` a link to `path` has in `mode`
+        fn href(mode: RouterMode, path: &str) -> String {
+            match mode {
+                RouterMode::BrowserHistory => format!(r#"href="{path}""#),
+                RouterMode::Hash => format!(r##"href="#{path}""##),
+            }
+        }
+
+        #[test]
+        fn links_resolve_relative_to_their_route() {
+            for mode in MODES {
+                let html = render_at(mode, "/users/1");
+                assert!(
+                    link(&html, "Posts").contains(&href(mode, "/users/1/posts")),
+                    "{html}"
+                );
+                assert!(link(&html, "This user").contains(&href(mode, "/users/1")));
+            }
+        }
+
+        #[test]
+        fn links_to_the_current_page_are_active() {
+            for mode in MODES {
+                let html = render_at(mode, "/users/1");
+
+                let this_user = link(&html, "This user");
+                assert!(this_user.contains(r#"aria-current="page""#), "{html}");
+                assert_eq!(classes(this_user), ["current"], "{html}");
+
+                // `/users/1` is nested in `/users`
+                let users = link(&html, "Users");
+                assert!(users.contains(r#"aria-current="page""#), "{html}");
+                assert_eq!(classes(users), ["nav", "active"], "{html}");
+
+                for text in ["All users", "Us", "Posts"] {
+                    let link = link(&html, text);
+                    assert!(!link.contains("aria-current"), "{html}");
+                    assert!(classes(link).is_empty(), "{html}");
+                }
+
+                let html = render_at(mode, "/users/1/posts/");
+                assert!(link(&html, "Posts").contains(r#"aria-current="page""#));
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use futures::StreamExt;
+        use leptos::*;
+        use leptos_router::*;
+        use std::cell::RefCell;
+
+        thread_local! {
+            // every loader that runs, and every route view that's created
+            // or disposed, in order
+            static LOG: RefCell<Vec<String>> = Default::default();
+        }
+
+        fn log(entry: String) {
+            LOG.with(|log| log.borrow_mut().push(entry));
+        }
+
+        fn take_log() -> Vec<String> {
+            LOG.with(|log| log.take())
+        }
+
+        // resources load in tasks on the server's local executor
+        fn run_local(fut: impl std::future::Future<Output = ()>) {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .build()
+                .unwrap();
+            tokio::task::LocalSet::new().block_on(&rt, fut);
+        }
+
+        async fn tick() {
+            for _ in 0..10 {
+                tokio::task::yield_now().await;
+            }
+        }
+
+        #[derive(Clone)]
+        struct TestHistory(RwSignal<LocationChange>);
+
+        impl History for TestHistory {
+            fn location(&self, _cx: Scope) -> ReadSignal<LocationChange> {
+                self.0.read_only()
+            }
+
+            fn navigate(&self, loc: &LocationChange) {
+                self.0.set(loc.clone());
+            }
+        }
+
+        fn go(history: &TestHistory, path: &str) {
+            history.navigate(&LocationChange {
+                value: format!("http://leptos.dev{path}"),
+                ..Default::default()
+            });
+        }
+
+        async fn load_users(_cx: Scope, _params: ParamsMap) -> usize {
+            log("load users".into());
+            2
+        }
+
+        async fn load_user(_cx: Scope, params: ParamsMap) -> String {
+            let id = params.get("id").cloned().unwrap_or_default();
+            log(format!("load user {id}"));
+            format!("User #{id}")
+        }
+
+        async fn load_about(_cx: Scope, _params: ParamsMap) -> String {
+            log("load about".into());
+            "About".into()
+        }
+
+        #[component]
+        fn Users(cx: Scope) -> impl IntoView {
+            log("+users".into());
+            on_cleanup(cx, || log("-users".into()));
+            let count = move || use_loader_data::<usize>(cx);
+            view! { cx,
+                <section>
+                    <Suspense fallback=|| ()>{move || count().map(|n| format!("{n} users"))}</Suspense>
+                    <Outlet/>
+                </section>
+            }
+        }
+
+        #[component]
+        fn User(cx: Scope) -> impl IntoView {
+            let name = move || use_loader_data::<String>(cx);
+            create_isomorphic_effect(cx, move |_| {
+                if let Some(name) = name() {
+                    log(format!("show {name}"));
+                }
+            });
+            view! { cx,
+                <Suspense fallback=|| "Loading...">
+                    <h1>{name}</h1>
+                </Suspense>
+            }
+        }
+
+        fn app(cx: Scope, history: &TestHistory) -> View {
+            provide_context(cx, RouterIntegrationContext::new(history.clone()));
+            view! { cx,
+                <Router>
+                    <Routes>
+                        <Route
+                            path="users"
+                            view=|cx| view! { cx, <Users/> }
+                            loader=Loader::new(load_users)
+                        >
+                            <Route
+                                path=":id"
+                                view=|cx| view! { cx, <User/> }
+                                loader=Loader::new(load_user)
+                            />
+                        </Route>
+                        <Route
+                            path="about"
+                            view=|_| ()
+                            loader=Loader::new(load_about)
+                        />
+                    </Routes>
+                </Router>
+            }
+            .into_view(cx)
+        }
+
+        #[test]
+        fn loaders_run_when_their_route_is_matched() {
+            run_local(async {
+                let runtime = create_runtime();
+                let (cx, disposer) = raw_scope_and_disposer(runtime);
+                cx.set_render_mode(RenderMode::Csr);
+
+                let history = TestHistory(create_rw_signal(cx, Default::default()));
+                go(&history, "/users/1");
+                let _view = app(cx, &history);
+                assert_eq!(take_log(), ["+users"]);
+                tick().await;
+                // `about` isn't matched, so it doesn't load anything
+                assert_eq!(
+                    take_log(),
+                    ["load users", "load user 1", "show User #1"]
+                );
+
+                // only the data of the segment whose params changed is
+                // loaded again; `users` keeps its scope and its data
+                go(&history, "/users/2");
+                tick().await;
+                assert_eq!(take_log(), ["load user 2", "show User #2"]);
+
+                go(&history, "/about");
+                tick().await;
+                assert_eq!(take_log(), ["-users", "load about"]);
+
+                disposer.dispose();
+                runtime.dispose();
+            });
+        }
+
+        #[test]
+        fn loaders_are_serialized_on_the_server() {
+            run_local(async {
+                let runtime = create_runtime();
+                let (cx, disposer) = raw_scope_and_disposer(runtime);
+                let history = TestHistory(create_rw_signal(cx, Default::default()));
+                go(&history, "/users/7");
+
+                let html = app(cx, &history).render_to_string(cx).to_string();
+                assert!(html.contains("<section"), "{html}");
+                assert_eq!(take_log(), ["+users"]);
+
+                // what the server sends along with the HTML, so the client
+                // can hydrate the data rather than loading it again
+                let serialized = cx
+                    .serialization_resolvers()
+                    .map(|(_, data)| data)
+                    .collect::<Vec<_>>()
+                    .await;
+                assert_eq!(serialized.len(), 2, "{serialized:?}");
+                assert!(
+                    serialized.iter().any(|data| data.contains("\"User #7\"")),
+                    "{serialized:?}"
+                );
+                assert!(
+                    serialized.iter().any(|data| data.ends_with('2')),
+                    "{serialized:?}"
+                );
+                assert_eq!(
+                    take_log(),
+                    ["load users", "load user 7", "show User #7"]
+                );
+
+                disposer.dispose();
+                runtime.dispose();
+            });
+        }
+    }
+}
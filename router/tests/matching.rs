@@ -0,0 +1,166 @@
+use cfg_if::cfg_if;
+
+// The server and the browser share the code that expands and matches route
+// patterns, and parse URLs into the same parts, so a route that's rendered on
+// the server is the same one the client hydrates.
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+
+        // patterns, with the params each of their expansions has
+        const PATTERNS: &[(&str, &[&[&str]])] = &[
+            ("/", &[&[]]),
+            ("/users", &[&[]]),
+            ("/users/:id", &[&["id"]]),
+            ("/users/:id?", &[&[], &["id"]]),
+            ("/users/:id/posts/:post", &[&["id", "post"]]),
+            ("/users/:id?/:tab?", &[&[], &["id"], &["id", "tab"]]),
+            (
+                "/shop/:category?/items/:item?",
+                &[&[], &["category"], &["item"], &["category", "item"]],
+            ),
+            ("/files/*path", &[&["path"]]),
+            ("/files/:drive?/*path", &[&["path"], &["drive", "path"]]),
+        ];
+
+        // a concrete path for a pattern, with a value for each of its params
+        fn concrete(pattern: &str) -> (String, ParamsMap) {
+            let mut params = ParamsMap::new();
+            let path = pattern
+                .split('/')
+                .map(|segment| {
+                    if let Some(name) = segment.strip_prefix(':') {
+                        let value = format!("{name}-1");
+                        params.insert(name.into(), value.clone());
+                        value
+                    } else if let Some(name) = segment.strip_prefix('*') {
+                        params.insert(name.into(), "a/b".into());
+                        "a/b".into()
+                    } else {
+                        segment.to_string()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("/");
+            (path, params)
+        }
+
+        #[test]
+        fn optional_params_expand_into_every_combination() {
+            for (pattern, expected) in PATTERNS {
+                let expansions = expand_optionals(pattern);
+                assert_eq!(expansions.len(), expected.len(), "{pattern}");
+                for (expansion, names) in expansions.iter().zip(*expected) {
+                    assert!(!expansion.contains('?'), "{pattern}: {expansion}");
+                    let (_, params) = concrete(expansion);
+                    let mut found = params.0.keys().collect::<Vec<_>>();
+                    let mut names = names.to_vec();
+                    found.sort();
+                    names.sort();
+                    assert_eq!(found, names, "{pattern}: {expansion}");
+                }
+            }
+        }
+
+        #[test]
+        fn expansions_match_their_concrete_paths() {
+            for (pattern, _) in PATTERNS {
+                for expansion in expand_optionals(pattern) {
+                    let (path, params) = concrete(&expansion);
+                    let matched = Matcher::new(&expansion)
+                        .test(&path)
+                        .unwrap_or_else(|| panic!("{expansion} on {path}"));
+                    assert_eq!(matched.params, params, "{expansion} on {path}");
+
+                    // ... and no longer paths, unless they end in a splat
+                    let longer = format!("{path}/extra");
+                    assert_eq!(
+                        Matcher::new(&expansion).test(&longer).is_some(),
+                        expansion.contains('*'),
+                        "{expansion} on {longer}"
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn server_urls_have_the_same_parts_as_in_the_browser() {
+            let url = Url::try_from("/users/1?tab=posts&tab=likes#top").unwrap();
+            assert_eq!(url.pathname, "/users/1");
+            assert_eq!(url.search, "?tab=posts&tab=likes");
+            assert_eq!(url.hash, "#top");
+            assert_eq!(
+                url.search_params.get_all("tab").collect::<Vec<_>>(),
+                ["posts", "likes"]
+            );
+
+            let url = Url::try_from("http://leptos.dev/users").unwrap();
+            assert_eq!(url.pathname, "/users");
+            assert_eq!(url.search, "");
+            assert_eq!(url.hash, "");
+        }
+
+        async fn load_user(_cx: Scope, _params: ParamsMap) -> String {
+            "User".into()
+        }
+
+        fn app(cx: Scope) -> impl IntoView {
+            view! { cx,
+                <Router>
+                    <Routes>
+                        <Route path="users" view=|cx| view! { cx, <Outlet/> }>
+                            <Route
+                                path=":id"
+                                view=|_| ()
+                                ssr=SsrMode::Async
+                                loader=Loader::new(load_user)
+                            />
+                        </Route>
+                        <Route path="search" view=|cx| {
+                            let query = use_query_map(cx);
+                            view! { cx,
+                                <p>"Results for " {move || query.with(|q| q.get("q").cloned())}</p>
+                            }
+                        }/>
+                    </Routes>
+                </Router>
+            }
+        }
+
+        #[test]
+        fn routes_are_collected_with_their_modes_and_loaders() {
+            let routes = collect_routes(app);
+            assert_eq!(
+                routes.iter().map(|route| &route.path).collect::<Vec<_>>(),
+                ["users", "search"]
+            );
+
+            let users = &routes[0];
+            assert_eq!(users.ssr_mode, SsrMode::OutOfOrder);
+            assert!(users.loader.is_none());
+            let user = &users.children[0];
+            assert_eq!(user.path, ":id");
+            assert_eq!(user.ssr_mode, SsrMode::Async);
+            assert!(user.loader.is_some());
+        }
+
+        #[test]
+        fn the_server_renders_the_path_and_query_of_the_request() {
+            let runtime = create_runtime();
+            let html = run_scope(runtime, |cx| {
+                provide_context(
+                    cx,
+                    RouterIntegrationContext::new(ServerIntegration {
+                        path: "http://leptos.dev/search?q=leptos".into(),
+                    }),
+                );
+                app(cx).into_view(cx).render_to_string(cx).to_string()
+            });
+            assert!(html.contains("Results for "), "{html}");
+            assert!(html.contains("Results for leptos") || html.contains("leptos<"), "{html}");
+            runtime.dispose();
+        }
+    }
+}
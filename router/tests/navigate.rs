@@ -0,0 +1,159 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+        use std::{cell::RefCell, rc::Rc};
+
+        type Navigate = Rc<dyn Fn(&str, NavigateOptions) -> Result<(), NavigationError>>;
+
+        thread_local! {
+            // the navigate function of the matched route
+            static NAVIGATE: RefCell<Option<Navigate>> = Default::default();
+        }
+
+        fn navigate(to: &str, options: NavigateOptions) {
+            let navigate = NAVIGATE.with(|n| n.borrow().clone()).unwrap();
+            navigate(to, options).unwrap();
+        }
+
+        // a History that keeps its stack of entries, like the browser's
+        #[derive(Clone)]
+        struct TestHistory {
+            location: RwSignal<LocationChange>,
+            entries: Rc<RefCell<Vec<(String, bool)>>>,
+        }
+
+        impl TestHistory {
+            fn at(cx: Scope, path: &str) -> Self {
+                let value = format!("http://leptos.dev{path}");
+                Self {
+                    location: create_rw_signal(
+                        cx,
+                        LocationChange {
+                            value: value.clone(),
+                            ..Default::default()
+                        },
+                    ),
+                    entries: Rc::new(RefCell::new(vec![(value, true)])),
+                }
+            }
+
+            fn paths(&self) -> Vec<String> {
+                self.entries.borrow().iter().map(|(path, _)| path.clone()).collect()
+            }
+        }
+
+        impl History for TestHistory {
+            fn location(&self, _cx: Scope) -> ReadSignal<LocationChange> {
+                self.location.read_only()
+            }
+
+            fn navigate(&self, loc: &LocationChange) {
+                let mut entries = self.entries.borrow_mut();
+                if loc.replace {
+                    entries.pop();
+                }
+                entries.push((loc.value.clone(), loc.scroll));
+                self.location.set(loc.clone());
+            }
+        }
+
+        #[component]
+        fn Account(cx: Scope) -> impl IntoView {
+            NAVIGATE.with(|n| *n.borrow_mut() = Some(Rc::new(use_navigate(cx))));
+            view! { cx, <Outlet/> }
+        }
+
+        #[component]
+        fn Private(cx: Scope) -> impl IntoView {
+            // e.g., a guard that sends users who aren't logged in away
+            let navigate = use_navigate(cx);
+            navigate("/login", Default::default()).unwrap();
+            view! { cx, <p>"Private"</p> }
+        }
+
+        fn app(cx: Scope, history: &TestHistory) -> View {
+            provide_context(cx, RouterIntegrationContext::new(history.clone()));
+            view! { cx,
+                <Router>
+                    <Routes>
+                        <Route path="account" view=|cx| view! { cx, <Account/> }>
+                            <Route path="private" view=|cx| view! { cx, <Private/> }/>
+                            <Route path="*any" view=|_| ()/>
+                        </Route>
+                        <Route path="*any" view=|_| ()/>
+                    </Routes>
+                </Router>
+            }
+            .into_view(cx)
+        }
+
+        #[test]
+        fn navigating_pushes_or_replaces_history_entries() {
+            let runtime = create_runtime();
+            let (cx, disposer) = raw_scope_and_disposer(runtime);
+            // navigations are only handled in the browser
+            cx.set_render_mode(RenderMode::Csr);
+
+            let history = TestHistory::at(cx, "/account");
+            let _view = app(cx, &history);
+            let start = "http://leptos.dev/account".to_string();
+
+            // relative to the route that called `use_navigate`
+            navigate("settings", Default::default());
+            assert_eq!(history.paths(), [start.clone(), "/account/settings".into()]);
+
+            navigate(
+                "/login",
+                NavigateOptions {
+                    replace: true,
+                    scroll: false,
+                    ..Default::default()
+                },
+            );
+            assert_eq!(
+                *history.entries.borrow(),
+                [(start.clone(), true), ("/login".into(), false)]
+            );
+
+            // a path that's already absolute isn't resolved again
+            navigate(
+                "/account/2",
+                NavigateOptions {
+                    resolve: false,
+                    ..Default::default()
+                },
+            );
+            assert_eq!(history.paths(), [start, "/login".into(), "/account/2".into()]);
+
+            disposer.dispose();
+            runtime.dispose();
+        }
+
+        #[test]
+        fn navigating_on_the_server_redirects_the_response() {
+            let runtime = create_runtime();
+            let redirects = Rc::new(RefCell::new(Vec::new()));
+            let (html, history) = run_scope(runtime, {
+                let redirects = Rc::clone(&redirects);
+                move |cx| {
+                    // as the server integrations do
+                    provide_server_redirect(cx, move |path| {
+                        redirects.borrow_mut().push(path.to_string())
+                    });
+                    let history = TestHistory::at(cx, "/account/private");
+                    let html = app(cx, &history).render_to_string(cx).to_string();
+                    (html, history)
+                }
+            });
+
+            assert!(html.contains("Private"), "{html}");
+            assert_eq!(*redirects.borrow(), ["/login"]);
+            // the history isn't touched
+            assert_eq!(history.paths(), ["http://leptos.dev/account/private"]);
+            runtime.dispose();
+        }
+    }
+}
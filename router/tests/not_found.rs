@@ -0,0 +1,154 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+        use std::cell::RefCell;
+
+        thread_local! {
+            // every route view that's created or disposed, in order
+            static LOG: RefCell<Vec<String>> = Default::default();
+        }
+
+        fn log(cx: Scope, name: &str) {
+            let name = name.to_string();
+            LOG.with(|log| log.borrow_mut().push(format!("+{name}")));
+            on_cleanup(cx, move || {
+                LOG.with(|log| log.borrow_mut().push(format!("-{name}")))
+            });
+        }
+
+        fn take_log() -> Vec<String> {
+            LOG.with(|log| log.take())
+        }
+
+        #[derive(Clone)]
+        struct TestHistory(RwSignal<LocationChange>);
+
+        impl History for TestHistory {
+            fn location(&self, _cx: Scope) -> ReadSignal<LocationChange> {
+                self.0.read_only()
+            }
+
+            fn navigate(&self, loc: &LocationChange) {
+                self.0.set(loc.clone());
+            }
+        }
+
+        fn go(history: &TestHistory, path: &str) {
+            history.navigate(&LocationChange {
+                value: format!("http://leptos.dev{path}"),
+                ..Default::default()
+            });
+        }
+
+        fn app(cx: Scope) -> impl IntoView {
+            view! { cx,
+                <Router fallback=|cx| view! { cx, <h1>"Outside of the app"</h1> }.into_view(cx)>
+                    <Routes>
+                        <Route path="app" view=|cx| {
+                            log(cx, "shell");
+                            view! { cx, <nav>"Menu"</nav><main><Outlet/></main> }
+                        }>
+                            <Route path="users" view=|cx| view! { cx, <Outlet/> }>
+                                <Route path=":id" view=|cx| {
+                                    log(cx, "user");
+                                    view! { cx, <p>"User"</p> }
+                                }/>
+                                <NotFound view=|cx| {
+                                    log(cx, "no such user page");
+                                    view! { cx, <p>"No such user page"</p> }
+                                }/>
+                            </Route>
+                            <Route path="" view=|cx| view! { cx, <p>"Home"</p> }/>
+                            <NotFound view=|cx| {
+                                log(cx, "not found");
+                                view! { cx, <p>"Not found"</p> }
+                            }/>
+                        </Route>
+                    </Routes>
+                </Router>
+            }
+        }
+
+        // the page at `path` on the server, and the status it responds with
+        fn render_at(path: &str) -> (String, Option<u16>) {
+            let runtime = create_runtime();
+            let path = path.to_string();
+            let rendered = run_scope(runtime, move |cx| {
+                let response = ResponseMetadata::default();
+                provide_context(cx, response.clone());
+                provide_context(
+                    cx,
+                    RouterIntegrationContext::new(ServerIntegration {
+                        path: format!("http://leptos.dev{path}"),
+                    }),
+                );
+                let html = app(cx).into_view(cx).render_to_string(cx).to_string();
+                (html, response.collect().status)
+            });
+            runtime.dispose();
+            take_log();
+            rendered
+        }
+
+        #[test]
+        fn the_not_found_page_is_shown_in_the_shell() {
+            let (html, status) = render_at("/app/nothing/here");
+            assert!(html.contains("Menu"), "{html}");
+            assert!(html.contains("Not found"), "{html}");
+            assert_eq!(status, Some(404));
+
+            let (html, status) = render_at("/app");
+            assert!(html.contains("Home"), "{html}");
+            assert!(!html.contains("Not found"), "{html}");
+            assert_eq!(status, None);
+        }
+
+        #[test]
+        fn the_deepest_not_found_page_is_shown() {
+            let (html, status) = render_at("/app/users/1/posts");
+            assert!(html.contains("Menu"), "{html}");
+            assert!(html.contains("No such user page"), "{html}");
+            assert!(!html.contains("Not found"), "{html}");
+            assert_eq!(status, Some(404));
+
+            let (html, status) = render_at("/app/users/1");
+            assert!(html.contains("User"), "{html}");
+            assert!(!html.contains("No such user page"), "{html}");
+            assert_eq!(status, None);
+        }
+
+        #[test]
+        fn paths_that_no_route_matches_are_a_404_too() {
+            let (html, status) = render_at("/elsewhere");
+            assert!(html.contains("Outside of the app"), "{html}");
+            assert!(!html.contains("Menu"), "{html}");
+            assert_eq!(status, Some(404));
+        }
+
+        #[test]
+        fn the_shell_stays_while_navigating_to_the_not_found_page() {
+            let runtime = create_runtime();
+            let (cx, disposer) = raw_scope_and_disposer(runtime);
+            // navigations are only handled in the browser
+            cx.set_render_mode(RenderMode::Csr);
+            take_log();
+
+            let history = TestHistory(create_rw_signal(cx, Default::default()));
+            go(&history, "/app/users/1");
+            provide_context(cx, RouterIntegrationContext::new(history.clone()));
+            let _view = app(cx).into_view(cx);
+            assert_eq!(take_log(), ["+shell", "+user"]);
+
+            go(&history, "/app/nothing");
+            assert_eq!(take_log(), ["+not found", "-user"]);
+            go(&history, "/app/users/1/posts");
+            assert_eq!(take_log(), ["+no such user page", "-not found"]);
+
+            disposer.dispose();
+            runtime.dispose();
+        }
+    }
+}
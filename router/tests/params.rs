@@ -0,0 +1,146 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+        use std::cell::RefCell;
+
+        #[derive(Params, Clone, Debug, PartialEq)]
+        struct UserParams {
+            id: usize,
+            tab: Option<String>,
+        }
+
+        #[test]
+        fn params_are_parsed_into_their_fields() {
+            let map = params_map! { "id" => "12", "tab" => "posts" };
+            assert_eq!(
+                UserParams::from_map(&map),
+                Ok(UserParams {
+                    id: 12,
+                    tab: Some("posts".into())
+                })
+            );
+        }
+
+        #[test]
+        fn optional_params_can_be_missing() {
+            let map = params_map! { "id" => "12" };
+            assert_eq!(
+                UserParams::from_map(&map),
+                Ok(UserParams { id: 12, tab: None })
+            );
+
+            let map = params_map! { "tab" => "posts" };
+            assert_eq!(
+                UserParams::from_map(&map),
+                Err(ParamsError::MissingParam("id".into()))
+            );
+        }
+
+        #[test]
+        fn params_that_cannot_be_parsed_are_errors() {
+            let map = params_map! { "id" => "twelve" };
+            let error = UserParams::from_map(&map).unwrap_err();
+            assert!(matches!(error, ParamsError::Params(_)), "{error:?}");
+            assert_eq!(
+                error.to_string(),
+                "failed to deserialize parameters: invalid digit found in \
+                 string"
+            );
+
+            // an optional param is only `None` if it's missing
+            let map = params_map! { "page" => "last" };
+            assert!(matches!(
+                <Option<u32> as IntoParam>::into_param(
+                    map.get("page").map(String::as_str),
+                    "page"
+                ),
+                Err(ParamsError::Params(_))
+            ));
+        }
+
+        thread_local! {
+            // what each user's view parsed from its params
+            static SEEN: RefCell<Vec<String>> = Default::default();
+        }
+
+        fn take_seen() -> Vec<String> {
+            SEEN.with(|seen| seen.take())
+        }
+
+        #[derive(Clone)]
+        struct TestHistory(RwSignal<LocationChange>);
+
+        impl History for TestHistory {
+            fn location(&self, _cx: Scope) -> ReadSignal<LocationChange> {
+                self.0.read_only()
+            }
+
+            fn navigate(&self, loc: &LocationChange) {
+                self.0.set(loc.clone());
+            }
+        }
+
+        fn go(history: &TestHistory, path: &str) {
+            history.navigate(&LocationChange {
+                value: format!("http://leptos.dev{path}"),
+                ..Default::default()
+            });
+        }
+
+        #[component]
+        fn User(cx: Scope) -> impl IntoView {
+            let params = use_params::<UserParams>(cx);
+            let id = use_param::<usize>(cx, "id");
+            create_isomorphic_effect(cx, move |_| {
+                let seen = match (params.get(), id.get()) {
+                    (Ok(params), Ok(id)) => {
+                        format!("{id} {:?}", params.tab)
+                    }
+                    (Err(e), Err(_)) => format!("error: {e}"),
+                    (params, id) => panic!("{params:?} but {id:?}"),
+                };
+                SEEN.with(|s| s.borrow_mut().push(seen));
+            });
+        }
+
+        #[test]
+        fn params_follow_navigation() {
+            let runtime = create_runtime();
+            let (cx, disposer) = raw_scope_and_disposer(runtime);
+            cx.set_render_mode(RenderMode::Csr);
+
+            let history = TestHistory(create_rw_signal(cx, Default::default()));
+            go(&history, "/users/1");
+            provide_context(cx, RouterIntegrationContext::new(history.clone()));
+            let _view = view! { cx,
+                <Router>
+                    <Routes>
+                        <Route path="users/:id/:tab?" view=|cx| view! { cx, <User/> }/>
+                    </Routes>
+                </Router>
+            }
+            .into_view(cx);
+            assert_eq!(take_seen(), ["1 None"]);
+
+            go(&history, "/users/2/posts");
+            assert_eq!(take_seen(), ["2 Some(\"posts\")"]);
+
+            // a bad param is an error the view can show, not a panic
+            go(&history, "/users/two");
+            assert_eq!(
+                take_seen(),
+                ["error: failed to deserialize parameters: invalid digit \
+                  found in string"]
+            );
+
+            go(&history, "/users/3");
+            assert_eq!(take_seen(), ["3 None"]);
+
+            disposer.dispose();
+            runtime.dispose();
+        }
+    }
+}
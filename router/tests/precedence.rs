@@ -0,0 +1,106 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+
+        // shows which route was matched, with its params
+        fn page(cx: Scope, pattern: &'static str) -> impl IntoView {
+            let mut params = use_params_map(cx)
+                .get_untracked()
+                .0
+                .into_iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect::<Vec<_>>();
+            params.sort();
+            let route = format!("{pattern} {}", params.join("&"));
+            view! { cx, <p data-route=route.trim_end().to_string()></p> }
+        }
+
+        // the routes are defined with the less specific ones first, so that
+        // they're matched in the order of their precedence, not of their
+        // definitions
+        fn app(cx: Scope) -> impl IntoView {
+            view! { cx,
+                <Router>
+                    <Routes>
+                        <NotFound view=|cx| page(cx, "not found")/>
+                        <Route path="docs/*rest" view=|cx| page(cx, "docs/*rest")/>
+                        <Route path=":page" view=|cx| page(cx, ":page")/>
+                        <Route path="users/*rest" view=|cx| page(cx, "users/*rest")/>
+                        <Route path="users/:id?" view=|cx| page(cx, "users/:id?")/>
+                        <Route path="users/:id" view=|cx| page(cx, "users/:id")/>
+                        <Route path="users" view=|cx| page(cx, "users")/>
+                        <Route path="users/new" view=|cx| page(cx, "users/new")/>
+                        <Route path="posts/*rest" view=|cx| page(cx, "posts/*rest")/>
+                        <Route path="posts/:id?" view=|cx| page(cx, "posts/:id?")/>
+                        <Route path="files/*path" view=|cx| page(cx, "files/*path")/>
+                        <Route path="tie/:first" view=|cx| page(cx, "tie/:first")/>
+                        <Route path="tie/:second" view=|cx| page(cx, "tie/:second")/>
+                        <Route path="" view=|cx| page(cx, "home")/>
+                    </Routes>
+                </Router>
+            }
+        }
+
+        // paths, and the route each of them matches
+        const PATHS: &[(&str, &str)] = &[
+            ("/", "home"),
+            // a static segment wins over an optional param that's left out,
+            // and over a catch-all with nothing left to catch
+            ("/users", "users"),
+            ("/users/", "users"),
+            // ... and over a param
+            ("/users/new", "users/new"),
+            // a param wins over an optional param
+            ("/users/1", "users/:id id=1"),
+            ("/users/1/", "users/:id id=1"),
+            ("/users/1/posts", "users/*rest rest=1/posts"),
+            // an optional param wins over a catch-all, whether it's there or
+            // not
+            ("/posts", "posts/:id?"),
+            ("/posts/1", "posts/:id? id=1"),
+            ("/posts/1/2", "posts/*rest rest=1/2"),
+            // a static segment followed by a catch-all wins over a param
+            ("/docs", "docs/*rest rest="),
+            ("/docs/", "docs/*rest rest="),
+            ("/docs/guide/routing", "docs/*rest rest=guide/routing"),
+            ("/about", ":page page=about"),
+            ("/files", "files/*path path="),
+            ("/files/a/b.txt", "files/*path path=a/b.txt"),
+            // routes that are just as specific are matched in the order
+            // they're defined in
+            ("/tie/1", "tie/:first first=1"),
+            // and a not found route matches whatever nothing else does
+            ("/about/team", "not found any=about/team"),
+        ];
+
+        fn render_at(path: &str) -> String {
+            let runtime = create_runtime();
+            let path = path.to_string();
+            let html = run_scope(runtime, move |cx| {
+                provide_context(
+                    cx,
+                    RouterIntegrationContext::new(ServerIntegration {
+                        path: format!("http://leptos.dev{path}"),
+                    }),
+                );
+                app(cx).into_view(cx).render_to_string(cx).to_string()
+            });
+            runtime.dispose();
+            html
+        }
+
+        #[test]
+        fn routes_are_matched_in_order_of_precedence() {
+            for (path, route) in PATHS {
+                let html = render_at(path);
+                assert!(
+                    html.contains(&format!(r#"data-route="{route}""#)),
+                    "{path} should match {route}: {html}"
+                );
+            }
+        }
+    }
+}
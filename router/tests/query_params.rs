@@ -2,11 +2,13 @@ use cfg_if::cfg_if;
 
 cfg_if! {
     if #[cfg(feature = "ssr")] {
-        use leptos_router::{Url, params_map};
+        use leptos::*;
+        use leptos_router::*;
+        use std::{cell::{Cell, RefCell}, rc::Rc};
 
         macro_rules! assert_params_map {
             ([$($key:expr => $val:expr),*] , $actual:expr) => (
-                assert_eq!(params_map!($($key => $val),*), $actual)
+                assert_eq!(QueryMap::from_iter([$(($key, $val)),*]), $actual)
             );
         }
 
@@ -48,5 +50,170 @@ cfg_if! {
                 url.search_params
             };
         }
+
+        #[test]
+        fn repeated_keys_keep_every_value_in_order() {
+            let url = Url::try_from("http://leptos.com?tag=a&page=1&tag=b").unwrap();
+            let query = url.search_params;
+            assert_eq!(query.get("tag"), Some(&"a".to_string()));
+            assert_eq!(query.get_all("tag").collect::<Vec<_>>(), ["a", "b"]);
+            assert_eq!(query.to_query_string(), "?tag=a&page=1&tag=b");
+        }
+
+        #[test]
+        fn updating_a_key_keeps_the_others() {
+            let mut query = QueryMap::from_iter([
+                ("tag", "a"),
+                ("page", "1"),
+                ("tag", "b"),
+            ]);
+            assert_eq!(query.insert("tag", "c"), Some("a".into()));
+            query.insert("sort", "new");
+            assert_eq!(query.to_query_string(), "?tag=c&page=1&sort=new");
+
+            query.append("tag", "d");
+            assert_eq!(query.remove("page"), Some("1".into()));
+            assert_eq!(query.to_query_string(), "?tag=c&sort=new&tag=d");
+
+            query.remove("tag");
+            query.remove("sort");
+            assert_eq!(query.to_query_string(), "");
+        }
+
+        #[derive(Params, Clone, Debug, PartialEq)]
+        struct Filters {
+            page: Option<usize>,
+            tag: Vec<String>,
+        }
+
+        fn round_trip(query: &QueryMap) -> QueryMap {
+            let url = format!("http://leptos.com/{}", query.to_query_string());
+            Url::try_from(url.as_str()).unwrap().search_params
+        }
+
+        #[test]
+        fn typed_queries_round_trip() {
+            let mut query = QueryMap::new();
+            query.append("tag", "a & b = c");
+            query.append("tag", "1+2 50%");
+            query.insert("page", "2");
+            let query = round_trip(&query);
+            assert_eq!(
+                Filters::from_query(&query),
+                Ok(Filters {
+                    page: Some(2),
+                    tag: vec!["a & b = c".into(), "1+2 50%".into()]
+                })
+            );
+
+            assert_eq!(
+                Filters::from_query(&QueryMap::new()),
+                Ok(Filters {
+                    page: None,
+                    tag: vec![]
+                })
+            );
+            assert!(matches!(
+                Filters::from_query(&QueryMap::from_iter([("page", "last")])),
+                Err(ParamsError::Params(_))
+            ));
+        }
+
+        // a History that keeps its stack of entries, like the browser's
+        #[derive(Clone)]
+        struct TestHistory {
+            location: RwSignal<LocationChange>,
+            entries: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl History for TestHistory {
+            fn location(&self, _cx: Scope) -> ReadSignal<LocationChange> {
+                self.location.read_only()
+            }
+
+            fn navigate(&self, loc: &LocationChange) {
+                let mut entries = self.entries.borrow_mut();
+                if loc.replace {
+                    entries.pop();
+                }
+                entries.push(loc.value.clone());
+                self.location.set(loc.clone());
+            }
+        }
+
+        thread_local! {
+            // the search route's scope, and its query
+            static SEARCH: Cell<Option<(Scope, Memo<Result<Filters, ParamsError>>)>> =
+                Default::default();
+        }
+
+        #[test]
+        fn updating_the_query_pushes_or_replaces_history_entries() {
+            let runtime = create_runtime();
+            let (cx, disposer) = raw_scope_and_disposer(runtime);
+            cx.set_render_mode(RenderMode::Csr);
+
+            let start = "http://leptos.dev/search?q=rust&tag=a&tag=b";
+            let history = TestHistory {
+                location: create_rw_signal(
+                    cx,
+                    LocationChange {
+                        value: start.into(),
+                        ..Default::default()
+                    },
+                ),
+                entries: Rc::new(RefCell::new(vec![start.into()])),
+            };
+            provide_context(cx, RouterIntegrationContext::new(history.clone()));
+
+            let _view = view! { cx,
+                <Router>
+                    <Routes>
+                        <Route path="search" view=|cx| {
+                            let filters = use_query::<Filters>(cx);
+                            SEARCH.with(|s| s.set(Some((cx, filters))));
+                        }/>
+                    </Routes>
+                </Router>
+            }
+            .into_view(cx);
+            let (search_cx, filters) = SEARCH.with(|s| s.get()).unwrap();
+            assert_eq!(
+                filters.get(),
+                Ok(Filters { page: None, tag: vec!["a".into(), "b".into()] })
+            );
+
+            update_query(search_cx, |q| {
+                q.insert("page", "2");
+            })
+            .unwrap();
+            assert_eq!(
+                filters.get(),
+                Ok(Filters { page: Some(2), tag: vec!["a".into(), "b".into()] })
+            );
+            assert_eq!(history.entries.borrow().len(), 2);
+
+            update_query_with_options(
+                search_cx,
+                NavigateOptions {
+                    replace: true,
+                    ..Default::default()
+                },
+                |q| {
+                    q.insert("page", "3");
+                    q.remove("tag");
+                },
+            )
+            .unwrap();
+            assert_eq!(filters.get(), Ok(Filters { page: Some(3), tag: vec![] }));
+            // the unrelated key is still there
+            assert_eq!(
+                *history.entries.borrow(),
+                [start, "/search?q=rust&page=3"]
+            );
+
+            disposer.dispose();
+            runtime.dispose();
+        }
     }
 }
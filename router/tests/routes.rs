@@ -0,0 +1,227 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+        use std::cell::RefCell;
+
+        thread_local! {
+            // every route view that's created or disposed, in order
+            static LOG: RefCell<Vec<String>> = Default::default();
+        }
+
+        fn log(cx: Scope, name: String) {
+            LOG.with(|log| log.borrow_mut().push(format!("+{name}")));
+            on_cleanup(cx, move || {
+                LOG.with(|log| log.borrow_mut().push(format!("-{name}")))
+            });
+        }
+
+        fn take_log() -> Vec<String> {
+            LOG.with(|log| log.take())
+        }
+
+        // a History whose location is set by the test
+        #[derive(Clone)]
+        struct TestHistory(RwSignal<LocationChange>);
+
+        impl History for TestHistory {
+            fn location(&self, _cx: Scope) -> ReadSignal<LocationChange> {
+                self.0.read_only()
+            }
+
+            fn navigate(&self, loc: &LocationChange) {
+                self.0.set(loc.clone());
+            }
+        }
+
+        // like the server integrations, with the whole URL
+        fn url(path: &str) -> String {
+            format!("http://leptos.dev{path}")
+        }
+
+        fn go(history: &TestHistory, path: &str) {
+            history.navigate(&LocationChange {
+                value: url(path),
+                ..Default::default()
+            });
+        }
+
+        #[derive(Clone, Copy)]
+        struct UserId(Memo<Option<String>>);
+
+        #[component]
+        fn Users(cx: Scope) -> impl IntoView {
+            log(cx, "users".into());
+            view! { cx, <section><Outlet/></section> }
+        }
+
+        #[component]
+        fn User(cx: Scope) -> impl IntoView {
+            let params = use_params_map(cx);
+            let id = params.with_untracked(|p| p.get("id").cloned());
+            log(cx, format!("user {}", id.unwrap_or_default()));
+            provide_context(
+                cx,
+                UserId(create_memo(cx, move |_| {
+                    params.with(|p| p.get("id").cloned())
+                })),
+            );
+            view! { cx, <article><Outlet/></article> }
+        }
+
+        #[component]
+        fn Posts(cx: Scope) -> impl IntoView {
+            // provided by the parent route's view
+            let UserId(id) = use_context(cx).expect("the user's id");
+            log(cx, format!("posts of {}", id.get().unwrap_or_default()));
+            view! { cx, <p>"Posts of " {move || id.get()}</p> }
+        }
+
+        #[component]
+        fn NewUser(cx: Scope) -> impl IntoView {
+            log(cx, "new user".into());
+            view! { cx, <form>"New user"</form> }
+        }
+
+        fn not_found(cx: Scope) -> View {
+            log(cx, "not found".into());
+            view! { cx, <h1>"Not found"</h1> }.into_view(cx)
+        }
+
+        // every test runs in each mode, which only changes how the browser
+        // integrations read and write the route
+        const MODES: [RouterMode; 2] = [RouterMode::BrowserHistory, RouterMode::Hash];
+
+        fn app(cx: Scope, mode: RouterMode, history: &TestHistory) -> View {
+            provide_context(cx, RouterIntegrationContext::new(history.clone()));
+
+            view! { cx,
+                <Router mode fallback=not_found>
+                    <Routes>
+                        <Route path="users" view=|cx| view! { cx, <Users/> }>
+                            <Route path=":id" view=|cx| view! { cx, <User/> }>
+                                <Route path="posts" view=|cx| view! { cx, <Posts/> }/>
+                                <Route path="" view=|_| ()/>
+                            </Route>
+                            <Route path="new" view=|cx| view! { cx, <NewUser/> }/>
+                        </Route>
+                    </Routes>
+                </Router>
+            }
+            .into_view(cx)
+        }
+
+        fn history_at(cx: Scope, path: &str) -> TestHistory {
+            TestHistory(create_rw_signal(
+                cx,
+                LocationChange {
+                    value: url(path),
+                    ..Default::default()
+                },
+            ))
+        }
+
+        #[test]
+        fn server_renders_the_matched_chain_of_routes() {
+            for mode in MODES {
+                let runtime = create_runtime();
+                let html = run_scope(runtime, move |cx| {
+                    let history = history_at(cx, "/users/1/posts");
+                    app(cx, mode, &history).render_to_string(cx).to_string()
+                });
+
+                assert!(html.contains("<section"), "{html}");
+                assert!(html.contains("<article"), "{html}");
+                assert!(html.contains("Posts of "), "{html}");
+                assert!(html.contains(">1<") || html.contains("1</p>"), "{html}");
+                take_log();
+                runtime.dispose();
+            }
+        }
+
+        #[test]
+        fn static_segments_take_precedence_over_params() {
+            for mode in MODES {
+                let runtime = create_runtime();
+                let html = run_scope(runtime, move |cx| {
+                    let history = history_at(cx, "/users/new");
+                    app(cx, mode, &history).render_to_string(cx).to_string()
+                });
+
+                assert!(html.contains("New user"), "{html}");
+                assert!(!html.contains("<article"), "{html}");
+                assert_eq!(
+                    take_log(),
+                    ["+users", "+new user", "-new user", "-users"]
+                );
+                runtime.dispose();
+            }
+        }
+
+        #[test]
+        fn unmatched_paths_render_the_fallback() {
+            for mode in MODES {
+                let runtime = create_runtime();
+                let html = run_scope(runtime, move |cx| {
+                    let history = history_at(cx, "/nowhere");
+                    app(cx, mode, &history).render_to_string(cx).to_string()
+                });
+
+                assert!(html.contains("Not found"), "{html}");
+                assert_eq!(take_log(), ["+not found", "-not found"]);
+                runtime.dispose();
+            }
+        }
+
+        #[test]
+        fn navigating_recreates_only_the_segments_that_changed() {
+            for mode in MODES {
+                let runtime = create_runtime();
+                let (cx, disposer) = raw_scope_and_disposer(runtime);
+                // navigations are only handled in the browser
+                cx.set_render_mode(RenderMode::Csr);
+                take_log();
+
+                let history = history_at(cx, "/users/1/posts");
+                let _view = app(cx, mode, &history);
+                assert_eq!(take_log(), ["+users", "+user 1", "+posts of 1"]);
+
+                // the `:id` segment and the one nested in it are created again,
+                // but `users` stays; the new ones are created before the old
+                // ones go away, which are shown until then
+                go(&history, "/users/2/posts");
+                assert_eq!(
+                    take_log(),
+                    ["+user 2", "+posts of 2", "-posts of 1", "-user 1"]
+                );
+
+                // the same path doesn't recreate anything
+                go(&history, "/users/2/posts");
+                assert!(take_log().is_empty());
+
+                // only the innermost segment changed
+                go(&history, "/users/2");
+                assert_eq!(take_log(), ["-posts of 2"]);
+
+                // a different route at the second level
+                go(&history, "/users/new");
+                assert_eq!(take_log(), ["+new user", "-user 2"]);
+
+                go(&history, "/nowhere");
+                assert_eq!(take_log(), ["+not found", "-new user", "-users"]);
+
+                let scopes = runtime.stats().scopes;
+                go(&history, "/users/3/posts");
+                go(&history, "/nowhere");
+                // the routes' own scopes went away with their views
+                assert_eq!(runtime.stats().scopes, scopes);
+                take_log();
+
+                disposer.dispose();
+                runtime.dispose();
+            }
+        }
+    }
+}
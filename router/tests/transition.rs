@@ -0,0 +1,233 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use futures::channel::oneshot;
+        use leptos::*;
+        use leptos_router::*;
+        use std::{cell::RefCell, collections::HashMap};
+
+        thread_local! {
+            // every route view that's created or disposed, in order
+            static LOG: RefCell<Vec<String>> = Default::default();
+            // the loads that are waiting, by the id of the user they load
+            static LOADS: RefCell<HashMap<String, oneshot::Sender<()>>> = Default::default();
+            static ROUTER: RefCell<Option<RouterContext>> = Default::default();
+        }
+
+        fn log(cx: Scope, name: String) {
+            LOG.with(|log| log.borrow_mut().push(format!("+{name}")));
+            on_cleanup(cx, move || {
+                LOG.with(|log| log.borrow_mut().push(format!("-{name}")))
+            });
+        }
+
+        fn take_log() -> Vec<String> {
+            LOG.with(|log| log.take())
+        }
+
+        // resources load in tasks on the server's local executor
+        fn run_local(fut: impl std::future::Future<Output = ()>) {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .build()
+                .unwrap();
+            tokio::task::LocalSet::new().block_on(&rt, fut);
+        }
+
+        async fn tick() {
+            for _ in 0..10 {
+                tokio::task::yield_now().await;
+            }
+        }
+
+        #[derive(Clone)]
+        struct TestHistory(RwSignal<LocationChange>);
+
+        impl History for TestHistory {
+            fn location(&self, _cx: Scope) -> ReadSignal<LocationChange> {
+                self.0.read_only()
+            }
+
+            fn navigate(&self, loc: &LocationChange) {
+                self.0.set(loc.clone());
+            }
+        }
+
+        fn go(history: &TestHistory, path: &str) {
+            history.navigate(&LocationChange {
+                value: format!("http://leptos.dev{path}"),
+                ..Default::default()
+            });
+        }
+
+        // a slow loader, which finishes once the test lets it
+        async fn load_user(_cx: Scope, params: ParamsMap) -> String {
+            let id = params.get("id").cloned().unwrap_or_default();
+            let (tx, rx) = oneshot::channel();
+            LOADS.with(|loads| loads.borrow_mut().insert(id.clone(), tx));
+            _ = rx.await;
+            format!("User #{id}")
+        }
+
+        async fn finish_loading(id: &str) {
+            let load = LOADS.with(|loads| loads.borrow_mut().remove(id));
+            load.unwrap_or_else(|| panic!("user {id} isn't loading"))
+                .send(())
+                .unwrap();
+            tick().await;
+        }
+
+        #[component]
+        fn User(cx: Scope) -> impl IntoView {
+            let id = use_params_map(cx)
+                .with_untracked(|p| p.get("id").cloned())
+                .unwrap_or_default();
+            log(cx, format!("user {id}"));
+            let name = move || use_loader_data::<String>(cx);
+            view! { cx,
+                <Suspense fallback=|| "Loading...">
+                    <h1>{name}</h1>
+                </Suspense>
+            }
+        }
+
+        fn app(cx: Scope, history: &TestHistory) -> View {
+            provide_context(cx, RouterIntegrationContext::new(history.clone()));
+            view! { cx,
+                <Router>
+                    {ROUTER.with(|r| *r.borrow_mut() = Some(use_router(cx)))}
+                    <Routes>
+                        <Route path="users" view=|cx| {
+                            log(cx, "users".into());
+                            view! { cx, <section><Outlet/></section> }
+                        }>
+                            <Route
+                                path=":id"
+                                view=|cx| view! { cx, <User/> }
+                                loader=Loader::new(load_user)
+                            />
+                            <Route path="" view=|_| ()/>
+                        </Route>
+                        <Route path="about" view=|cx| {
+                            log(cx, "about".into());
+                            view! { cx, <p>"About"</p> }
+                        }/>
+                    </Routes>
+                </Router>
+            }
+            .into_view(cx)
+        }
+
+        // the app, in the browser, at `path`
+        fn mount_at(cx: Scope, path: &str) -> (View, TestHistory, Signal<bool>) {
+            // navigations are only handled in the browser
+            cx.set_render_mode(RenderMode::Csr);
+            let history = TestHistory(create_rw_signal(cx, Default::default()));
+            go(&history, path);
+            let view = app(cx, &history);
+            let router = ROUTER.with(|r| r.borrow().clone()).unwrap();
+            (view, history, use_is_routing(router.base().cx()))
+        }
+
+        #[test]
+        fn the_old_page_stays_until_the_new_route_has_loaded() {
+            run_local(async {
+                let runtime = create_runtime();
+                let (cx, disposer) = raw_scope_and_disposer(runtime);
+                take_log();
+
+                let (_view, history, is_routing) = mount_at(cx, "/about");
+                assert_eq!(take_log(), ["+about"]);
+                assert!(!is_routing.get());
+
+                go(&history, "/users/1");
+                tick().await;
+                // the new routes are rendered, but not shown yet
+                assert_eq!(take_log(), ["+users", "+user 1"]);
+                assert!(is_routing.get());
+
+                // once the loader has finished, they replace the old page
+                finish_loading("1").await;
+                assert!(!is_routing.get());
+                assert_eq!(take_log(), ["-about"]);
+
+                // the same goes for the outlet of a route that stays
+                go(&history, "/users/2");
+                tick().await;
+                assert_eq!(take_log(), ["+user 2"]);
+                assert!(is_routing.get());
+                finish_loading("2").await;
+                assert_eq!(take_log(), ["-user 1"]);
+                assert!(!is_routing.get());
+
+                disposer.dispose();
+                runtime.dispose();
+            });
+        }
+
+        #[test]
+        fn routes_without_anything_to_load_are_shown_right_away() {
+            run_local(async {
+                let runtime = create_runtime();
+                let (cx, disposer) = raw_scope_and_disposer(runtime);
+                take_log();
+
+                let (_view, history, is_routing) = mount_at(cx, "/users/1");
+                // the first page is shown right away, while it loads
+                assert!(!is_routing.get());
+                tick().await;
+                finish_loading("1").await;
+                take_log();
+
+                go(&history, "/about");
+                assert_eq!(take_log(), ["+about", "-user 1", "-users"]);
+                assert!(!is_routing.get());
+
+                disposer.dispose();
+                runtime.dispose();
+            });
+        }
+
+        #[test]
+        fn a_newer_navigation_replaces_one_that_is_waiting() {
+            run_local(async {
+                let runtime = create_runtime();
+                let (cx, disposer) = raw_scope_and_disposer(runtime);
+                take_log();
+
+                let (_view, history, is_routing) = mount_at(cx, "/about");
+                go(&history, "/users/1");
+                tick().await;
+                go(&history, "/users/2");
+                tick().await;
+                // the route that stays waits for its new child instead
+                assert_eq!(take_log(), ["+about", "+users", "+user 1", "+user 2"]);
+                assert!(is_routing.get());
+
+                finish_loading("1").await;
+                assert!(is_routing.get());
+                finish_loading("2").await;
+                assert!(!is_routing.get());
+                // and the page is swapped once, without the user that was
+                // navigated away from
+                let mut disposed = take_log();
+                disposed.sort();
+                assert_eq!(disposed, ["-about", "-user 1"]);
+
+                // going back to the page that's shown matches it again
+                go(&history, "/users/3");
+                tick().await;
+                go(&history, "/users/2");
+                tick().await;
+                assert_eq!(take_log(), ["+user 3", "-user 3", "+user 2"]);
+                assert!(is_routing.get());
+                finish_loading("2").await;
+                assert_eq!(take_log(), ["-user 2"]);
+                assert!(!is_routing.get());
+
+                disposer.dispose();
+                runtime.dispose();
+            });
+        }
+    }
+}
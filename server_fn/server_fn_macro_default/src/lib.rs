@@ -40,14 +40,15 @@ use syn::__private::ToTokens;
 ///   function call.
 /// - **Server functions must return `Result<T, ServerFnError>`.** Even if the work being done
 ///   inside the function body can’t fail, the processes of serialization/deserialization and the
-///   network call are fallible.
+///   network call are fallible. They can also return errors of their own, as
+///   `Result<T, ServerFnError<MyAppError>>`, where `MyAppError` implements `ServerFnCustomError`.
 /// - **Return types must implement [Serialize](serde::Serialize).**
 ///   This should be fairly obvious: we have to serialize arguments to send them to the server, and we
 ///   need to deserialize the result to return it to the client.
 /// - **Arguments must be implement [`Serialize`](https://docs.rs/serde/latest/serde/trait.Serialize.html)
 ///   and [`DeserializeOwned`](https://docs.rs/serde/latest/serde/de/trait.DeserializeOwned.html).**
 ///   They are serialized as an `application/x-www-form-urlencoded`
-///   form data using [`serde_urlencoded`](https://docs.rs/serde_urlencoded/latest/serde_urlencoded/) or as `application/cbor`
+///   form data, as [`server_fn::form`](https://docs.rs/server_fn/latest/server_fn/form/index.html) describes, or as `application/cbor`
 ///   using [`cbor`](https://docs.rs/cbor/latest/cbor/).
 #[proc_macro_attribute]
 pub fn server(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
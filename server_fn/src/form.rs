@@ -0,0 +1,1024 @@
+//! Encodes the arguments of server functions as the fields of an HTML `<form>`,
+//! and decodes them from the fields a form submits.
+//!
+//! This is how [Encoding::Url](crate::Encoding::Url) arguments are sent, and
+//! how the server reads a form that's posted to any server function. A form
+//! can only name its fields, so the rest of the arguments' shape is written in
+//! those names:
+//! - each field of the arguments is named after it: `title=Nap`
+//! - the fields of a nested struct or map are named in brackets:
+//!   `author[name]=Greg`
+//! - the values of a `Vec` are the same field repeated, with or without `[]`:
+//!   `tags=home&tags=work`, or `tags[]=home&tags[]=work`; and if any of its
+//!   items has fields of its own, e.g., a struct or another `Vec`, they're all
+//!   numbered: `steps[0][title]=Nap&steps[1][title]=Wake`
+//! - an `Option` is `None` if its field is left out or empty, and an item of
+//!   a `Vec` that's `None` is empty: `scores=1&scores=&scores=3`
+//! - a `Vec` is empty if its field is left out, and a `bool` is `false`, as
+//!   an unchecked checkbox is; it's `true` if it's `true` or `on`
+//! - if a field is given more than once for a single value, the last wins
+//!
+//! The keys of a map can't be empty or contain brackets. Values that can't be
+//! told apart from others once they're fields, e.g., `Some` of an empty `Vec`,
+//! which would be read back as `None`, are errors to encode rather than
+//! being changed along the way.
+//!
+//! ```
+//! # use serde::{Deserialize, Serialize};
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Author {
+//!     name: String,
+//!     email: Option<String>,
+//! }
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct AddTodo {
+//!     title: String,
+//!     tags: Vec<String>,
+//!     author: Author,
+//!     done: bool,
+//! }
+//!
+//! let todo: AddTodo = server_fn::form::from_bytes(
+//!     b"title=Nap&tags[]=home&tags[]=sofa&author[name]=Greg&author[email]=",
+//! )
+//! .unwrap();
+//! assert_eq!(
+//!     todo,
+//!     AddTodo {
+//!         title: "Nap".into(),
+//!         tags: vec!["home".into(), "sofa".into()],
+//!         author: Author {
+//!             name: "Greg".into(),
+//!             email: None,
+//!         },
+//!         done: false,
+//!     }
+//! );
+//! assert_eq!(
+//!     server_fn::form::to_string(&todo).unwrap(),
+//!     "title=Nap&tags=home&tags=sofa&author%5Bname%5D=Greg&done=false"
+//! );
+//! ```
+
+use serde::{
+    de::{
+        self,
+        value::{MapDeserializer, SeqDeserializer},
+        DeserializeOwned, IntoDeserializer,
+    },
+    ser::{self, Impossible},
+    Deserialize, Serialize,
+};
+
+/// An error encoding or decoding the fields of a form.
+pub type Error = serde::de::value::Error;
+
+/// Encodes a struct or map as the fields of a form.
+pub fn to_fields<T: Serialize + ?Sized>(
+    value: &T,
+) -> Result<Vec<(String, String)>, Error> {
+    let mut fields = Vec::new();
+    value.serialize(FieldSerializer {
+        name: String::new(),
+        fields: &mut fields,
+        item: false,
+    })?;
+    Ok(fields)
+}
+
+/// Encodes a struct or map as a URL-encoded form body, or query string.
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String, Error> {
+    Ok(form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(to_fields(value)?)
+        .finish())
+}
+
+/// Decodes a value from the fields of a form, in the order they were given.
+pub fn from_fields<T: DeserializeOwned>(
+    fields: impl IntoIterator<Item = (String, String)>,
+) -> Result<T, Error> {
+    let mut root = Node::default();
+    for (name, value) in fields {
+        root.insert(&name, value);
+    }
+    T::deserialize(root)
+}
+
+/// Decodes a value from a URL-encoded form body, or query string.
+pub fn from_bytes<T: DeserializeOwned>(data: &[u8]) -> Result<T, Error> {
+    from_fields(
+        form_urlencoded::parse(data)
+            .map(|(name, value)| (name.into_owned(), value.into_owned())),
+    )
+}
+
+// the values that were given for a field, and its named subfields, in the
+// order they were first given; a field that wasn't given is empty
+#[derive(Debug, Default)]
+struct Node {
+    values: Vec<String>,
+    fields: Vec<(String, Node)>,
+}
+
+impl Node {
+    fn leaf(value: String) -> Self {
+        Self {
+            values: vec![value],
+            fields: Vec::new(),
+        }
+    }
+
+    // `steps[0][title]` is the `title` of the `0` of `steps`, and `tags[]` is
+    // just `tags`
+    fn insert(&mut self, name: &str, value: String) {
+        let (head, mut rest) = match name.find('[') {
+            Some(at) if name.ends_with(']') => (&name[..at], &name[at..]),
+            _ => (name, ""),
+        };
+        let mut node = self.field(head);
+        while let Some(segment) = rest.strip_prefix('[') {
+            let end = segment.find(']').unwrap_or(segment.len());
+            if end > 0 {
+                node = node.field(&segment[..end]);
+            }
+            rest = segment.get(end + 1..).unwrap_or_default();
+        }
+        node.values.push(value);
+    }
+
+    fn field(&mut self, name: &str) -> &mut Node {
+        match self.fields.iter().position(|(n, _)| n == name) {
+            Some(at) => &mut self.fields[at].1,
+            None => {
+                self.fields.push((name.to_string(), Node::default()));
+                &mut self.fields.last_mut().unwrap().1
+            }
+        }
+    }
+
+    fn take_field(&mut self, name: &str) -> Node {
+        match self.fields.iter().position(|(n, _)| n == name) {
+            Some(at) => self.fields.remove(at).1,
+            None => Node::default(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.values.is_empty() && self.fields.is_empty()
+    }
+
+    fn value(mut self) -> Result<String, Error> {
+        match self.values.pop() {
+            Some(value) if self.fields.is_empty() => Ok(value),
+            Some(_) => Err(de::Error::custom("expected a single value")),
+            None if self.fields.is_empty() => {
+                Err(de::Error::custom("missing value"))
+            }
+            None => Err(de::Error::custom("expected a value, not fields")),
+        }
+    }
+
+    fn parse<T>(self) -> Result<T, Error>
+    where
+        T: std::str::FromStr,
+        T::Err: std::fmt::Display,
+    {
+        let value = self.value()?;
+        value
+            .parse()
+            .map_err(|e| de::Error::custom(format!("{value:?}: {e}")))
+    }
+
+    // the nodes of a sequence: its numbered fields in order, or its values
+    fn items(self) -> Vec<Node> {
+        if self.fields.is_empty() {
+            self.values.into_iter().map(Node::leaf).collect()
+        } else {
+            let mut fields = self.fields;
+            fields.sort_by_key(|(name, _)| name.parse::<usize>().ok());
+            fields.into_iter().map(|(_, node)| node).collect()
+        }
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Node {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: de::Visitor<'de>>(
+                self,
+                visitor: V,
+            ) -> Result<V::Value, Error> {
+                visitor.$visit(self.parse()?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Node {
+    type Error = Error;
+
+    fn deserialize_any<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        if !self.fields.is_empty() {
+            self.deserialize_map(visitor)
+        } else if self.values.len() > 1 {
+            self.deserialize_seq(visitor)
+        } else if self.values.len() == 1 {
+            self.deserialize_string(visitor)
+        } else {
+            visitor.visit_unit()
+        }
+    }
+
+    fn deserialize_bool<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        if self.is_empty() {
+            return visitor.visit_bool(false);
+        }
+        match self.value()?.as_str() {
+            "true" | "on" => visitor.visit_bool(true),
+            "false" | "off" | "" => visitor.visit_bool(false),
+            value => Err(de::Error::custom(format!("{value:?} is not a bool"))),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_str<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_string(self.value()?)
+    }
+
+    fn deserialize_string<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_string(self.value()?)
+    }
+
+    fn deserialize_bytes<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_byte_buf(self.value()?.into_bytes())
+    }
+
+    fn deserialize_byte_buf<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_byte_buf(self.value()?.into_bytes())
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        if self.is_empty()
+            || (self.fields.is_empty()
+                && self.values.iter().all(String::is_empty))
+        {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let mut items = SeqDeserializer::new(self.items().into_iter());
+        let seq = visitor.visit_seq(&mut items)?;
+        items.end()?;
+        Ok(seq)
+    }
+
+    fn deserialize_tuple<V: de::Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let fields = self
+            .fields
+            .into_iter()
+            .map(|(name, node)| (Node::leaf(name), node));
+        visitor.visit_map(MapDeserializer::new(fields))
+    }
+
+    // every field of the struct is given, so that the ones that were left
+    // out of the form can be empty rather than missing
+    fn deserialize_struct<V: de::Visitor<'de>>(
+        mut self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        if !self.values.is_empty() {
+            return Err(de::Error::custom("expected fields, not a value"));
+        }
+        let fields = fields
+            .iter()
+            .map(|name| (*name, self.take_field(name)))
+            .collect::<Vec<_>>();
+        visitor.visit_map(MapDeserializer::new(fields.into_iter()))
+    }
+
+    // a unit variant is its name, and any other is a field named after it
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        mut self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        if self.fields.len() == 1 && self.values.is_empty() {
+            let (variant, node) = self.fields.pop().unwrap();
+            visitor.visit_enum(Variant(variant, node))
+        } else {
+            visitor.visit_enum(self.value()?.into_deserializer())
+        }
+    }
+
+    fn deserialize_identifier<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_ignored_any<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+}
+
+struct Variant(String, Node);
+
+impl<'de> de::EnumAccess<'de> for Variant {
+    type Error = Error;
+    type Variant = Node;
+
+    fn variant_seed<S: de::DeserializeSeed<'de>>(
+        self,
+        seed: S,
+    ) -> Result<(S::Value, Node), Error> {
+        let variant = seed.deserialize(Node::leaf(self.0))?;
+        Ok((variant, self.1))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Node {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<S: de::DeserializeSeed<'de>>(
+        self,
+        seed: S,
+    ) -> Result<S::Value, Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_struct(self, "", fields, visitor)
+    }
+}
+
+// serializes a value as the field `name`, and whatever fields it has
+struct FieldSerializer<'a> {
+    name: String,
+    fields: &'a mut Vec<(String, String)>,
+    // whether the value is an item of a sequence, so that it has to be given
+    // even if it's `None`, to keep its place
+    item: bool,
+}
+
+impl<'a> FieldSerializer<'a> {
+    fn push(self, value: impl ToString) -> Result<(), Error> {
+        let this = self.named()?;
+        this.fields.push((this.name, value.to_string()));
+        Ok(())
+    }
+
+    // only the fields of the arguments have names
+    fn named(self) -> Result<Self, Error> {
+        if self.name.is_empty() {
+            Err(ser::Error::custom(
+                "only structs and maps can be encoded as a form",
+            ))
+        } else {
+            Ok(self)
+        }
+    }
+
+    fn name_of(&self, field: &str) -> String {
+        if self.name.is_empty() {
+            field.to_string()
+        } else {
+            format!("{}[{field}]", self.name)
+        }
+    }
+
+    fn field(&mut self, field: &str) -> FieldSerializer<'_> {
+        FieldSerializer {
+            name: self.name_of(field),
+            fields: self.fields,
+            item: false,
+        }
+    }
+
+    fn into_field(self, field: &str) -> Self {
+        FieldSerializer {
+            name: self.name_of(field),
+            fields: self.fields,
+            item: false,
+        }
+    }
+
+    // nothing, unless an item of a sequence is, which is given empty
+    fn nothing(self) -> Result<(), Error> {
+        if self.item {
+            self.push("")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+macro_rules! serialize_display {
+    ($($method:ident($ty:ty),)*) => {
+        $(
+            fn $method(self, value: $ty) -> Result<(), Error> {
+                self.push(value)
+            }
+        )*
+    };
+}
+
+impl<'a> ser::Serializer for FieldSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = SeqSerializer<'a>;
+    type SerializeTuple = SeqSerializer<'a>;
+    type SerializeTupleStruct = SeqSerializer<'a>;
+    type SerializeTupleVariant = SeqSerializer<'a>;
+    type SerializeMap = MapSerializer<'a>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    serialize_display! {
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+    }
+
+    fn serialize_bytes(self, _value: &[u8]) -> Result<(), Error> {
+        Err(ser::Error::custom("bytes can't be encoded as a form"))
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        self.nothing()
+    }
+
+    // without any fields, it would be read back as `None`
+    fn serialize_some<T: Serialize + ?Sized>(
+        self,
+        value: &T,
+    ) -> Result<(), Error> {
+        let given = self.fields.len();
+        let fields = self.fields;
+        value.serialize(FieldSerializer {
+            name: self.name.clone(),
+            fields: &mut *fields,
+            item: false,
+        })?;
+        if fields.len() == given {
+            Err(ser::Error::custom(format!(
+                "`Some` without any fields can't be told apart from `None` in \
+                 {:?}",
+                self.name
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        self.nothing()
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        self.nothing()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.push(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self.into_field(variant))
+    }
+
+    fn serialize_seq(
+        self,
+        _len: Option<usize>,
+    ) -> Result<SeqSerializer<'a>, Error> {
+        Ok(SeqSerializer {
+            field: self.named()?,
+            items: Vec::new(),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer<'a>, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer<'a>, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer<'a>, Error> {
+        self.into_field(variant).serialize_seq(Some(len))
+    }
+
+    fn serialize_map(
+        self,
+        _len: Option<usize>,
+    ) -> Result<MapSerializer<'a>, Error> {
+        Ok(MapSerializer {
+            field: self,
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, Error> {
+        Ok(self.into_field(variant))
+    }
+}
+
+impl<'a> ser::SerializeStruct for FieldSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self.field(key))
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeStructVariant for FieldSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self.field(key))
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+// if each item is a single value, they're repeated as the field itself, and
+// otherwise they're all numbered, so that each item's fields stay together
+struct SeqSerializer<'a> {
+    field: FieldSerializer<'a>,
+    items: Vec<(String, Vec<(String, String)>)>,
+}
+
+impl<'a> ser::SerializeSeq for SeqSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), Error> {
+        let name = format!("{}[{}]", self.field.name, self.items.len());
+        let mut fields = Vec::new();
+        value.serialize(FieldSerializer {
+            name: name.clone(),
+            fields: &mut fields,
+            item: true,
+        })?;
+        // e.g., an empty `Vec`, which has no place to keep
+        if fields.is_empty() {
+            return Err(ser::Error::custom(format!(
+                "an item without any fields can't be encoded in {:?}",
+                self.field.name
+            )));
+        }
+        self.items.push((name, fields));
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Error> {
+        let single = self.items.iter().all(|(name, fields)| {
+            matches!(fields.as_slice(), [(item, _)] if item == name)
+        });
+        for (_, fields) in self.items {
+            if single {
+                let name = self.field.name.clone();
+                self.field.fields.extend(
+                    fields.into_iter().map(|(_, value)| (name.clone(), value)),
+                );
+            } else {
+                self.field.fields.extend(fields);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeTuple for SeqSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl<'a> ser::SerializeTupleStruct for SeqSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl<'a> ser::SerializeTupleVariant for SeqSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+struct MapSerializer<'a> {
+    field: FieldSerializer<'a>,
+    key: Option<String>,
+}
+
+impl<'a> ser::SerializeMap for MapSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(
+        &mut self,
+        key: &T,
+    ) -> Result<(), Error> {
+        let key = key.serialize(KeySerializer)?;
+        // which would be read back as other fields
+        if key.is_empty() || key.contains(['[', ']']) {
+            return Err(ser::Error::custom(format!(
+                "the key {key:?} can't be the name of a field"
+            )));
+        }
+        self.key = Some(key);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), Error> {
+        let key = self.key.take().unwrap_or_default();
+        value.serialize(self.field.field(&key))
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+// the keys of a map are the names of its fields, so they have to be strings
+// or numbers
+struct KeySerializer;
+
+macro_rules! serialize_key {
+    ($($method:ident($ty:ty),)*) => {
+        $(
+            fn $method(self, value: $ty) -> Result<String, Error> {
+                Ok(value.to_string())
+            }
+        )*
+    };
+}
+
+impl ser::Serializer for KeySerializer {
+    type Ok = String;
+    type Error = Error;
+    type SerializeSeq = Impossible<String, Error>;
+    type SerializeTuple = Impossible<String, Error>;
+    type SerializeTupleStruct = Impossible<String, Error>;
+    type SerializeTupleVariant = Impossible<String, Error>;
+    type SerializeMap = Impossible<String, Error>;
+    type SerializeStruct = Impossible<String, Error>;
+    type SerializeStructVariant = Impossible<String, Error>;
+
+    serialize_key! {
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+    }
+
+    fn serialize_bytes(self, _value: &[u8]) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_none(self) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(
+        self,
+        value: &T,
+    ) -> Result<String, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_unit_struct(
+        self,
+        _name: &'static str,
+    ) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<String, Error> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_seq(
+        self,
+        _len: Option<usize>,
+    ) -> Result<Self::SerializeSeq, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple(
+        self,
+        _len: usize,
+    ) -> Result<Self::SerializeTuple, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_map(
+        self,
+        _len: Option<usize>,
+    ) -> Result<Self::SerializeMap, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(key_error())
+    }
+}
+
+fn key_error() -> Error {
+    ser::Error::custom("the keys of a map must be strings or numbers")
+}
@@ -67,13 +67,15 @@
 //!   function call.
 //! - **Server functions must return `Result<T, ServerFnError>`.** Even if the work being done
 //!   inside the function body can’t fail, the processes of serialization/deserialization and the
-//!   network call are fallible.
+//!   network call are fallible. They can also return errors of their own, as
+//!   `Result<T, ServerFnError<MyAppError>>`, where `MyAppError` implements [ServerFnCustomError].
 //! - **Return types must implement [Serialize](serde::Serialize).**
 //!   This should be fairly obvious: we have to serialize arguments to send them to the server, and we
 //!   need to deserialize the result to return it to the client.
 //! - **Arguments must be implement [serde::Serialize].** They are serialized as an `application/x-www-form-urlencoded`
-//!   form data using [`serde_urlencoded`](https://docs.rs/serde_urlencoded/latest/serde_urlencoded/) or as `application/cbor`
-//!   using [`cbor`](https://docs.rs/cbor/latest/cbor/).
+//!   form data as [form] describes, as `application/json`,
+//!   or as `application/cbor` using [`cbor`](https://docs.rs/cbor/latest/cbor/). The results are
+//!   JSON or CBOR, whichever the function's [ServerFn::output_encoding] is.
 
 // used by the macro
 #[doc(hidden)]
@@ -91,6 +93,9 @@ use thiserror::Error;
 #[doc(hidden)]
 pub use xxhash_rust;
 
+pub mod form;
+pub mod multipart;
+
 #[cfg(any(feature = "ssr", doc))]
 /// Something that can register a server function.
 pub trait ServerFunctionRegistry<T> {
@@ -108,10 +113,7 @@ pub trait ServerFunctionRegistry<T> {
 }
 
 /// A server function that can be called from the client.
-pub type ServerFnTraitObj<T> = dyn Fn(
-        T,
-        &[u8],
-    ) -> Pin<Box<dyn Future<Output = Result<Payload, ServerFnError>>>>
+pub type ServerFnTraitObj<T> = dyn Fn(T, &[u8]) -> Pin<Box<dyn Future<Output = Result<Payload, ErrorPayload>>>>
     + Send
     + Sync;
 
@@ -126,6 +128,44 @@ pub enum Payload {
     Json(String),
 }
 
+impl Payload {
+    /// The MIME type of the payload.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Payload::Binary(_) => "application/cbor",
+            Payload::Url(_) => "application/x-www-form-urlencoded",
+            Payload::Json(_) => "application/json",
+        }
+    }
+    /// The encoded data.
+    pub fn into_bytes(self) -> Vec<u8> {
+        match self {
+            Payload::Binary(data) => data,
+            Payload::Url(data) | Payload::Json(data) => data.into_bytes(),
+        }
+    }
+}
+
+/// The error that a server function returned on the server, encoded to be
+/// sent to the client, which decodes it into the function's [ServerFnError].
+#[derive(Debug)]
+pub struct ErrorPayload {
+    /// The HTTP status code to respond with.
+    pub status: u16,
+    /// The error, encoded as the function's results are.
+    pub payload: Payload,
+    /// A description of the error, e.g., for the server's logs.
+    pub message: String,
+}
+
+impl std::fmt::Display for ErrorPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ErrorPayload {}
+
 /// Attempts to find a server function registered at the given path.
 ///
 /// This can be used by a server to handle the requests, as in the following example (using `actix-web`)
@@ -160,12 +200,14 @@ pub enum Payload {
 ///                 }
 ///             }
 ///             Err(e) => {
-///                 eprintln!("server function error: {e:#?}");
-///                 HttpResponse::InternalServerError().body(e.to_string())
+///                 eprintln!("server function error: {e}");
+///                 HttpResponse::build(StatusCode::from_u16(e.status).unwrap())
+///                     .content_type(e.payload.content_type())
+///                     .body(e.payload.into_bytes())
 ///             }
 ///         }
 ///     } else {
-///         HttpResponse::BadRequest().body(format!("Could not find a server function at that route."))
+///         HttpResponse::NotFound().body(format!("Could not find a server function at that route."))
 ///     }
 /// }
 /// ```
@@ -185,12 +227,18 @@ pub fn server_fns_by_path<T: 'static, R: ServerFunctionRegistry<T>>(
 
 /// Holds the current options for encoding types.
 /// More could be added, but they need to be serde
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Encoding {
     /// A Binary Encoding Scheme Called Cbor
     Cbor,
     /// The Default URL-encoded encoding method
     Url,
+    /// Encodes data as JSON
+    Json,
+    /// Encodes the arguments as `multipart/form-data`, so that they can
+    /// include [UploadedFile](multipart::UploadedFile)s, with the results
+    /// encoded as JSON
+    Multipart,
 }
 
 impl FromStr for Encoding {
@@ -200,6 +248,8 @@ impl FromStr for Encoding {
         match input {
             "URL" => Ok(Encoding::Url),
             "Cbor" => Ok(Encoding::Cbor),
+            "Json" => Ok(Encoding::Json),
+            "Multipart" => Ok(Encoding::Multipart),
             _ => Err(()),
         }
     }
@@ -210,6 +260,8 @@ impl quote::ToTokens for Encoding {
         let option: syn::Ident = match *self {
             Encoding::Cbor => parse_quote!(Cbor),
             Encoding::Url => parse_quote!(Url),
+            Encoding::Json => parse_quote!(Json),
+            Encoding::Multipart => parse_quote!(Multipart),
         };
         let expansion: syn::Ident = syn::parse_quote! {
           Encoding::#option
@@ -234,28 +286,60 @@ where
     /// The return type of the function.
     type Output: Serialize;
 
+    /// The error of the application's own that the function can return, as
+    /// [ServerFnError::Custom], or [NoCustomError].
+    type Error: ServerFnCustomError;
+
     /// URL prefix that should be prepended by the client to the generated URL.
     fn prefix() -> &'static str;
 
     /// The path at which the server function can be reached on the server.
     fn url() -> &'static str;
 
-    /// The path at which the server function can be reached on the server.
+    /// How the arguments of the function are encoded.
     fn encoding() -> Encoding;
 
+    /// How the results of the function are encoded: as CBOR if this is
+    /// [Encoding::Cbor], and as JSON otherwise.
+    ///
+    /// Defaults to CBOR for functions whose arguments are CBOR, and to JSON
+    /// for the rest.
+    fn output_encoding() -> Encoding {
+        match Self::encoding() {
+            Encoding::Cbor => Encoding::Cbor,
+            Encoding::Url | Encoding::Json | Encoding::Multipart => {
+                Encoding::Json
+            }
+        }
+    }
+
     /// Runs the function on the server.
     #[cfg(any(feature = "ssr", doc))]
+    #[allow(clippy::type_complexity)]
     fn call_fn(
         self,
         cx: T,
-    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, ServerFnError>>>>;
+    ) -> Pin<
+        Box<
+            dyn Future<
+                Output = Result<Self::Output, ServerFnError<Self::Error>>,
+            >,
+        >,
+    >;
 
     /// Runs the function on the client by sending an HTTP request to the server.
     #[cfg(any(not(feature = "ssr"), doc))]
+    #[allow(clippy::type_complexity)]
     fn call_fn_client(
         self,
         cx: T,
-    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, ServerFnError>>>>;
+    ) -> Pin<
+        Box<
+            dyn Future<
+                Output = Result<Self::Output, ServerFnError<Self::Error>>,
+            >,
+        >,
+    >;
 
     /// Registers the server function, allowing the server to query it by URL.
     #[cfg(any(feature = "ssr", doc))]
@@ -266,48 +350,24 @@ where
 
         let run_server_fn = Arc::new(|cx: T, data: &[u8]| {
             // decode the args
-            let value = match Self::encoding() {
-                Encoding::Url => serde_urlencoded::from_bytes(data)
-                    .map_err(|e| ServerFnError::Deserialization(e.to_string())),
-                Encoding::Cbor => ciborium::de::from_reader(data)
-                    .map_err(|e| ServerFnError::Deserialization(e.to_string())),
-            };
+            let value = decode_args(Self::encoding(), data);
             Box::pin(async move {
                 let value: Self = match value {
                     Ok(v) => v,
-                    Err(e) => return Err(e),
+                    Err(e) => return Err(encode_error::<Self, T>(e)),
                 };
 
                 // call the function
                 let result = match value.call_fn(cx).await {
                     Ok(r) => r,
-                    Err(e) => return Err(e),
+                    Err(e) => return Err(encode_error::<Self, T>(e)),
                 };
 
                 // serialize the output
-                let result = match Self::encoding() {
-                    Encoding::Url => match serde_json::to_string(&result)
-                        .map_err(|e| {
-                            ServerFnError::Serialization(e.to_string())
-                        }) {
-                        Ok(r) => Payload::Url(r),
-                        Err(e) => return Err(e),
-                    },
-                    Encoding::Cbor => {
-                        let mut buffer: Vec<u8> = Vec::new();
-                        match ciborium::ser::into_writer(&result, &mut buffer)
-                            .map_err(|e| {
-                                ServerFnError::Serialization(e.to_string())
-                            }) {
-                            Ok(_) => Payload::Binary(buffer),
-                            Err(e) => return Err(e),
-                        }
-                    }
-                };
-
-                Ok(result)
+                encode_output(Self::output_encoding(), &result)
+                    .map_err(encode_error::<Self, T>)
             })
-                as Pin<Box<dyn Future<Output = Result<Payload, ServerFnError>>>>
+                as Pin<Box<dyn Future<Output = Result<Payload, ErrorPayload>>>>
         });
 
         // store it in the hashmap
@@ -316,9 +376,90 @@ where
     }
 }
 
+// Decodes the arguments of a server function. Forms, and requests with the
+// arguments in their query string, are URL-encoded whatever the function's
+// encoding is, so those are accepted too.
+#[cfg(any(feature = "ssr", doc))]
+fn decode_args<T: DeserializeOwned, E>(
+    encoding: Encoding,
+    data: &[u8],
+) -> Result<T, ServerFnError<E>> {
+    let value = match encoding {
+        Encoding::Url => form::from_bytes(data)
+            .map_err(|e| ServerFnError::Args(e.to_string())),
+        Encoding::Json => serde_json::from_slice(data)
+            .map_err(|e| ServerFnError::Args(e.to_string())),
+        Encoding::Cbor => ciborium::de::from_reader(data)
+            .map_err(|e| ServerFnError::Args(e.to_string())),
+        Encoding::Multipart if data.len() > multipart::upload_limit() => {
+            return Err(ServerFnError::Args(format!(
+                "the upload is larger than the limit of {} bytes",
+                multipart::upload_limit()
+            )))
+        }
+        Encoding::Multipart => multipart::from_body(data)
+            .map_err(|e| ServerFnError::Args(e.to_string())),
+    };
+    match value {
+        Err(e) if encoding != Encoding::Url => {
+            form::from_bytes(data).map_err(|_| e)
+        }
+        value => value,
+    }
+}
+
+#[cfg(any(feature = "ssr", doc))]
+fn encode_output<E>(
+    encoding: Encoding,
+    value: &impl Serialize,
+) -> Result<Payload, ServerFnError<E>> {
+    match encoding {
+        Encoding::Cbor => {
+            let mut buffer: Vec<u8> = Vec::new();
+            ciborium::ser::into_writer(value, &mut buffer)
+                .map_err(|e| ServerFnError::Serialization(e.to_string()))?;
+            Ok(Payload::Binary(buffer))
+        }
+        Encoding::Url | Encoding::Json | Encoding::Multipart => {
+            serde_json::to_string(value)
+                .map(Payload::Json)
+                .map_err(|e| ServerFnError::Serialization(e.to_string()))
+        }
+    }
+}
+
+// errors are sent to the client encoded as the function's results are
+#[cfg(any(feature = "ssr", doc))]
+fn encode_error<F: ServerFn<T>, T: 'static>(
+    error: ServerFnError<F::Error>,
+) -> ErrorPayload {
+    let status = error.status();
+    let message = error.to_string();
+    let payload = encode_output(F::output_encoding(), &error)
+        .or_else(|e: ServerFnError| {
+            encode_output::<NoCustomError>(F::output_encoding(), &e)
+        })
+        .unwrap_or_else(|_| Payload::Json(String::new()));
+    ErrorPayload {
+        status,
+        payload,
+        message,
+    }
+}
+
 /// Type for errors that can occur when using server functions.
-#[derive(Error, Debug, Clone, Serialize, Deserialize)]
-pub enum ServerFnError {
+///
+/// Server functions can also return errors of the application's own, as
+/// [ServerFnError::Custom], by returning `Result<T, ServerFnError<MyAppError>>`
+/// where `MyAppError` implements [ServerFnCustomError]. Like the rest of the
+/// errors that occur on the server, they're sent to the client, and the client
+/// receives them as they were returned.
+///
+/// Naming the type as `ServerFnError` means `ServerFnError<NoCustomError>`,
+/// but where the type is only inferred, e.g., for an error that's returned
+/// from a closure, it may need to be named.
+#[derive(Error, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ServerFnError<E = NoCustomError> {
     /// Error while trying to register the server function (only occurs in case of poisoned RwLock).
     #[error("error while trying to register the server function: {0}")]
     Registration(String),
@@ -340,93 +481,442 @@ pub enum ServerFnError {
     /// Occurs on the server if there's a missing argument.
     #[error("missing argument {0}")]
     MissingArg(String),
+    /// Occurs when no server function is registered at the URL that's called.
+    #[error("no server function is registered at {0}")]
+    NotRegistered(String),
+    /// An error of the application's own, returned by the server function.
+    #[error("{0}")]
+    Custom(E),
+}
+
+impl<E: ServerFnCustomError> ServerFnError<E> {
+    /// The HTTP status code the error is sent to the client with.
+    pub fn status(&self) -> u16 {
+        match self {
+            ServerFnError::Args(_) | ServerFnError::MissingArg(_) => 400,
+            ServerFnError::NotRegistered(_) => 404,
+            ServerFnError::Custom(e) => e.status(),
+            _ => 500,
+        }
+    }
+}
+
+impl<E: ServerFnCustomError> From<E> for ServerFnError<E> {
+    fn from(error: E) -> Self {
+        ServerFnError::Custom(error)
+    }
+}
+
+// the errors of a transport, or of a function, which are the same whatever
+// the custom error is
+fn with_custom_error<E>(error: ServerFnError) -> ServerFnError<E> {
+    match error {
+        ServerFnError::Registration(e) => ServerFnError::Registration(e),
+        ServerFnError::Request(e) => ServerFnError::Request(e),
+        ServerFnError::ServerError(e) => ServerFnError::ServerError(e),
+        ServerFnError::Deserialization(e) => ServerFnError::Deserialization(e),
+        ServerFnError::Serialization(e) => ServerFnError::Serialization(e),
+        ServerFnError::Args(e) => ServerFnError::Args(e),
+        ServerFnError::MissingArg(e) => ServerFnError::MissingArg(e),
+        ServerFnError::NotRegistered(e) => ServerFnError::NotRegistered(e),
+        ServerFnError::Custom(e) => match e {},
+    }
+}
+
+/// An error of an application's own, which its server functions can return
+/// as [ServerFnError::Custom].
+///
+/// ```rust,ignore
+/// #[derive(Debug, Clone, Serialize, Deserialize, thiserror::Error)]
+/// pub enum TodoError {
+///     #[error("there's already a todo called {0}")]
+///     Duplicate(String),
+/// }
+///
+/// impl ServerFnCustomError for TodoError {
+///     fn status(&self) -> u16 {
+///         409
+///     }
+/// }
+///
+/// #[server]
+/// async fn add_todo(title: String) -> Result<(), ServerFnError<TodoError>> {
+///     Err(TodoError::Duplicate(title).into())
+/// }
+/// ```
+pub trait ServerFnCustomError:
+    Serialize + DeserializeOwned + std::error::Error + Send + Sync + 'static
+{
+    /// The HTTP status code the error is sent to the client with. Defaults
+    /// to `500 Internal Server Error`.
+    fn status(&self) -> u16 {
+        500
+    }
+}
+
+/// The custom error of server functions that don't return an error of their
+/// own. There are no values of this type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NoCustomError {}
+
+impl std::fmt::Display for NoCustomError {
+    fn fmt(&self, _f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {}
+    }
+}
+
+impl std::error::Error for NoCustomError {}
+
+impl ServerFnCustomError for NoCustomError {}
+
+/// A call to a server function that the client sends with a [ClientTransport].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientRequest {
+    /// The URL of the server function, including its prefix.
+    pub url: String,
+    /// How the arguments in the body are encoded.
+    pub encoding: Encoding,
+    /// The `Content-Type` of the body, which is the encoding's, along with
+    /// the boundary of a `multipart/form-data` body.
+    pub content_type: String,
+    /// How the server is asked to encode the result.
+    pub accept: Encoding,
+    /// The encoded arguments of the server function.
+    pub body: Vec<u8>,
+    /// Where to report how much of the body has been sent, if the call is
+    /// made within [with_upload_progress].
+    pub progress: Option<UploadProgress>,
+}
+
+/// Reports how much of the body of a call to a server function has been sent,
+/// from `0.0` to `1.0`, as a [ClientTransport] sends it.
+#[derive(Clone)]
+pub struct UploadProgress(std::rc::Rc<dyn Fn(f64)>);
+
+impl UploadProgress {
+    /// Reports the progress to `f`.
+    pub fn new(f: impl Fn(f64) + 'static) -> Self {
+        Self(std::rc::Rc::new(f))
+    }
+
+    /// Reports that the given fraction of the body has been sent.
+    pub fn report(&self, sent: f64) {
+        (self.0)(sent.clamp(0.0, 1.0))
+    }
+}
+
+impl std::fmt::Debug for UploadProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("UploadProgress")
+    }
+}
+
+impl PartialEq for UploadProgress {
+    fn eq(&self, other: &Self) -> bool {
+        std::rc::Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for UploadProgress {}
+
+thread_local! {
+    static UPLOAD_PROGRESS: std::cell::RefCell<Option<UploadProgress>> =
+        Default::default();
+}
+
+/// Runs a future, reporting the progress of the calls to server functions
+/// that it makes to `progress`, e.g., to show how much of a file has been
+/// uploaded. [Action](https://docs.rs/leptos/latest/leptos/struct.Action.html)s
+/// do this for each call they dispatch.
+pub fn with_upload_progress<F: Future>(
+    progress: UploadProgress,
+    fut: F,
+) -> impl Future<Output = F::Output> {
+    struct WithUploadProgress<F> {
+        progress: UploadProgress,
+        fut: Pin<Box<F>>,
+    }
+
+    impl<F: Future> Future for WithUploadProgress<F> {
+        type Output = F::Output;
+
+        fn poll(
+            mut self: Pin<&mut Self>,
+            cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<F::Output> {
+            let outer = UPLOAD_PROGRESS
+                .with(|p| p.replace(Some(self.progress.clone())));
+            let poll = self.fut.as_mut().poll(cx);
+            UPLOAD_PROGRESS.with(|p| *p.borrow_mut() = outer);
+            poll
+        }
+    }
+
+    WithUploadProgress {
+        progress,
+        fut: Box::pin(fut),
+    }
+}
+
+/// The response of the server to a call made with a [ClientTransport].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientResponse {
+    /// The HTTP status code.
+    pub status: u16,
+    /// The text that goes with the status code, e.g., `Internal Server Error`.
+    pub status_text: String,
+    /// The encoded result of the server function.
+    pub body: Vec<u8>,
+}
+
+/// Sends the calls that the client makes to server functions to the server.
+///
+/// In the browser, calls are `POST`ed with `fetch`. Another transport can be
+/// set with [set_client_transport], e.g., to call the server functions from
+/// a native client, or to call a registry directly in tests.
+pub trait ClientTransport {
+    /// Sends the encoded arguments of a server function to the server, and
+    /// returns the server's response.
+    fn send(
+        &self,
+        request: ClientRequest,
+    ) -> Pin<Box<dyn Future<Output = Result<ClientResponse, ServerFnError>>>>;
+}
+
+impl Encoding {
+    /// The MIME type of the arguments and results encoded this way.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Encoding::Url => "application/x-www-form-urlencoded",
+            Encoding::Cbor => "application/cbor",
+            Encoding::Json => "application/json",
+            Encoding::Multipart => "multipart/form-data",
+        }
+    }
+}
+
+/// Sends requests with `fetch`, which is how the client calls server
+/// functions unless another [ClientTransport] has been set. Calls whose
+/// [UploadProgress] is reported are sent with an `XMLHttpRequest` instead,
+/// which `fetch` can't report it for.
+#[cfg(not(feature = "ssr"))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BrowserTransport;
+
+#[cfg(not(feature = "ssr"))]
+impl ClientTransport for BrowserTransport {
+    fn send(
+        &self,
+        request: ClientRequest,
+    ) -> Pin<Box<dyn Future<Output = Result<ClientResponse, ServerFnError>>>>
+    {
+        use js_sys::Uint8Array;
+
+        let ClientRequest {
+            url,
+            encoding,
+            content_type,
+            accept,
+            body,
+            progress,
+        } = request;
+        if let Some(progress) = progress {
+            return send_with_progress(
+                url,
+                content_type,
+                accept,
+                body,
+                progress,
+            );
+        }
+        let request = gloo_net::http::Request::post(&url)
+            .header("Content-Type", &content_type)
+            .header("Accept", accept.content_type());
+        let request = match encoding {
+            Encoding::Url | Encoding::Json => String::from_utf8(body)
+                .map(|body| request.body(body))
+                .map_err(|e| ServerFnError::Serialization(e.to_string())),
+            Encoding::Cbor | Encoding::Multipart => {
+                let slice_ref: &[u8] = &body;
+                Ok(request.body(Uint8Array::from(slice_ref).buffer()))
+            }
+        };
+        Box::pin(async move {
+            let resp = request?
+                .send()
+                .await
+                .map_err(|e| ServerFnError::Request(e.to_string()))?;
+            let body = resp
+                .binary()
+                .await
+                .map_err(|e| ServerFnError::Deserialization(e.to_string()))?;
+            Ok(ClientResponse {
+                status: resp.status(),
+                status_text: resp.status_text(),
+                body,
+            })
+        })
+    }
+}
+
+#[cfg(not(feature = "ssr"))]
+fn send_with_progress(
+    url: String,
+    content_type: String,
+    accept: Encoding,
+    body: Vec<u8>,
+    progress: UploadProgress,
+) -> Pin<Box<dyn Future<Output = Result<ClientResponse, ServerFnError>>>> {
+    use wasm_bindgen::{closure::Closure, JsCast};
+    use web_sys::{ProgressEvent, XmlHttpRequest, XmlHttpRequestResponseType};
+
+    let request_error =
+        |e: wasm_bindgen::JsValue| ServerFnError::Request(format!("{e:?}"));
+    Box::pin(async move {
+        let xhr = XmlHttpRequest::new().map_err(request_error)?;
+        xhr.open("POST", &url).map_err(request_error)?;
+        xhr.set_request_header("Content-Type", &content_type)
+            .map_err(request_error)?;
+        xhr.set_request_header("Accept", accept.content_type())
+            .map_err(request_error)?;
+        xhr.set_response_type(XmlHttpRequestResponseType::Arraybuffer);
+
+        let on_progress =
+            Closure::<dyn Fn(ProgressEvent)>::new(move |ev: ProgressEvent| {
+                if ev.length_computable() && ev.total() > 0.0 {
+                    progress.report(ev.loaded() / ev.total());
+                }
+            });
+        xhr.upload()
+            .map_err(request_error)?
+            .set_onprogress(Some(on_progress.as_ref().unchecked_ref()));
+        let done = js_sys::Promise::new(&mut |resolve, reject| {
+            xhr.set_onload(Some(&resolve));
+            xhr.set_onerror(Some(&reject));
+        });
+        xhr.send_with_opt_u8_array(Some(&body))
+            .map_err(request_error)?;
+        wasm_bindgen_futures::JsFuture::from(done)
+            .await
+            .map_err(|_| ServerFnError::Request("the request failed".into()))?;
+        drop(on_progress);
+
+        let response = xhr.response().map_err(request_error)?;
+        Ok(ClientResponse {
+            status: xhr.status().map_err(request_error)?,
+            status_text: xhr.status_text().map_err(request_error)?,
+            body: js_sys::Uint8Array::new(&response).to_vec(),
+        })
+    })
+}
+
+#[cfg(not(feature = "ssr"))]
+thread_local! {
+    static CLIENT_TRANSPORT: std::cell::RefCell<Option<std::rc::Rc<dyn ClientTransport>>> =
+        Default::default();
+}
+
+/// Sets the [ClientTransport] that the client calls server functions
+/// with, instead of `fetch`.
+#[cfg(not(feature = "ssr"))]
+pub fn set_client_transport(transport: impl ClientTransport + 'static) {
+    CLIENT_TRANSPORT
+        .with(|t| *t.borrow_mut() = Some(std::rc::Rc::new(transport)));
 }
 
 /// Executes the HTTP call to call a server function from the client, given its URL and argument type.
+///
+/// This is what the client's version of a [`#[server]`](server) function
+/// calls, with the [ClientTransport] that's been set, or `fetch`.
 #[cfg(not(feature = "ssr"))]
-pub async fn call_server_fn<T, C: 'static>(
+pub async fn call_server_fn<T, C: 'static, F: ServerFn<C>>(
     url: &str,
-    args: impl ServerFn<C>,
+    args: F,
     enc: Encoding,
-) -> Result<T, ServerFnError>
+) -> Result<T, ServerFnError<F::Error>>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned + Sized,
+{
+    let transport = CLIENT_TRANSPORT
+        .with(|t| t.borrow().clone())
+        .unwrap_or_else(|| std::rc::Rc::new(BrowserTransport));
+    call_server_fn_with(&*transport, url, args, enc).await
+}
+
+/// Calls a server function with the given [ClientTransport]: serializes its
+/// arguments with `enc`, sends them to `url`, and deserializes the result,
+/// which is encoded as the function's [ServerFn::output_encoding].
+///
+/// The errors that the server responds with are decoded into the function's
+/// [ServerFnError] type.
+pub async fn call_server_fn_with<T, C: 'static, F: ServerFn<C>>(
+    transport: &dyn ClientTransport,
+    url: &str,
+    args: F,
+    enc: Encoding,
+) -> Result<T, ServerFnError<F::Error>>
 where
     T: serde::Serialize + serde::de::DeserializeOwned + Sized,
 {
     use ciborium::ser::into_writer;
-    use js_sys::Uint8Array;
-    use serde_json::Deserializer as JSONDeserializer;
 
-    #[derive(Debug)]
-    enum Payload {
-        Binary(Vec<u8>),
-        Url(String),
-    }
-    let args_encoded = match &enc {
-        Encoding::Url => Payload::Url(
-            serde_urlencoded::to_string(&args)
-                .map_err(|e| ServerFnError::Serialization(e.to_string()))?,
-        ),
+    let progress = UPLOAD_PROGRESS.with(|p| p.borrow().clone());
+    let mut content_type = enc.content_type().to_string();
+    let args_encoded = match enc {
+        Encoding::Url => form::to_string(&args)
+            .map_err(|e| ServerFnError::Serialization(e.to_string()))?
+            .into_bytes(),
+        Encoding::Json => serde_json::to_vec(&args)
+            .map_err(|e| ServerFnError::Serialization(e.to_string()))?,
         Encoding::Cbor => {
             let mut buffer: Vec<u8> = Vec::new();
             into_writer(&args, &mut buffer)
                 .map_err(|e| ServerFnError::Serialization(e.to_string()))?;
-            Payload::Binary(buffer)
+            buffer
         }
-    };
-
-    let content_type_header = match &enc {
-        Encoding::Url => "application/x-www-form-urlencoded",
-        Encoding::Cbor => "application/cbor",
-    };
-
-    let accept_header = match &enc {
-        Encoding::Url => "application/x-www-form-urlencoded",
-        Encoding::Cbor => "application/cbor",
-    };
-
-    let resp = match args_encoded {
-        Payload::Binary(b) => {
-            let slice_ref: &[u8] = &b;
-            let js_array = Uint8Array::from(slice_ref).buffer();
-            gloo_net::http::Request::post(url)
-                .header("Content-Type", content_type_header)
-                .header("Accept", accept_header)
-                .body(js_array)
-                .send()
-                .await
-                .map_err(|e| ServerFnError::Request(e.to_string()))?
+        Encoding::Multipart => {
+            let (multipart_type, body) = multipart::to_body(&args)
+                .map_err(|e| ServerFnError::Serialization(e.to_string()))?;
+            content_type = multipart_type;
+            body
         }
-        Payload::Url(s) => gloo_net::http::Request::post(url)
-            .header("Content-Type", content_type_header)
-            .header("Accept", accept_header)
-            .body(s)
-            .send()
-            .await
-            .map_err(|e| ServerFnError::Request(e.to_string()))?,
     };
 
-    // check for error status
-    let status = resp.status();
-    if (500..=599).contains(&status) {
-        return Err(ServerFnError::ServerError(resp.status_text()));
+    let accept = F::output_encoding();
+    let resp = transport
+        .send(ClientRequest {
+            url: url.to_string(),
+            encoding: enc,
+            content_type,
+            accept,
+            body: args_encoded,
+            progress,
+        })
+        .await
+        .map_err(with_custom_error)?;
+
+    // the server sends the errors it returns encoded, but anything else that
+    // fails along the way, e.g., a proxy, might not
+    if resp.status >= 400 {
+        return Err(decode_output(accept, &resp.body).unwrap_or_else(|_| {
+            match resp.status {
+                404 => ServerFnError::NotRegistered(url.to_string()),
+                _ => ServerFnError::ServerError(resp.status_text),
+            }
+        }));
     }
 
-    if enc == Encoding::Cbor {
-        let binary = resp
-            .binary()
-            .await
-            .map_err(|e| ServerFnError::Deserialization(e.to_string()))?;
+    decode_output(accept, &resp.body).map_err(ServerFnError::Deserialization)
+}
 
-        ciborium::de::from_reader(binary.as_slice())
-            .map_err(|e| ServerFnError::Deserialization(e.to_string()))
+fn decode_output<T: DeserializeOwned>(
+    encoding: Encoding,
+    body: &[u8],
+) -> Result<T, String> {
+    if encoding == Encoding::Cbor {
+        ciborium::de::from_reader(body).map_err(|e| e.to_string())
     } else {
-        let text = resp
-            .text()
-            .await
-            .map_err(|e| ServerFnError::Deserialization(e.to_string()))?;
-
-        let mut deserializer = JSONDeserializer::from_str(&text);
-        T::deserialize(&mut deserializer)
-            .map_err(|e| ServerFnError::Deserialization(e.to_string()))
+        let mut deserializer = serde_json::Deserializer::from_slice(body);
+        T::deserialize(&mut deserializer).map_err(|e| e.to_string())
     }
 }
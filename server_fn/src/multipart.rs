@@ -0,0 +1,423 @@
+//! Encodes the arguments of server functions as `multipart/form-data`, which
+//! is how [Encoding::Multipart](crate::Encoding::Multipart) arguments are
+//! sent, so that they can include [UploadedFile]s.
+//!
+//! Each file is a part of its own, named after its field as the rest of the
+//! fields are in [form](crate::form), and they're decoded back into the
+//! arguments along with them. A browser posts a `<form>` with
+//! `enctype="multipart/form-data"` the same way, so the files that are picked
+//! in its `<input type="file">`s reach the function without JavaScript too.
+//!
+//! ```
+//! # use serde::{Deserialize, Serialize};
+//! use server_fn::multipart::{self, UploadedFile};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Upload {
+//!     album: String,
+//!     photos: Vec<UploadedFile>,
+//! }
+//!
+//! let upload = Upload {
+//!     album: "Holidays".into(),
+//!     photos: vec![UploadedFile {
+//!         name: "beach.png".into(),
+//!         content_type: "image/png".into(),
+//!         bytes: vec![137, 80, 78, 71],
+//!     }],
+//! };
+//! let (content_type, body) = multipart::to_body(&upload).unwrap();
+//! assert!(content_type.starts_with("multipart/form-data; boundary="));
+//! assert_eq!(multipart::from_body::<Upload>(&body).unwrap(), upload);
+//! ```
+//!
+//! The server integrations read the whole body of a request before they call
+//! the function, so the files are buffered rather than streamed. They stop
+//! reading a body once it's larger than [set_upload_limit], and the function
+//! rejects it.
+
+use crate::form::{self, Error};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::cell::RefCell;
+
+/// A file that's uploaded to a server function, as one of its arguments.
+///
+/// Outside of `multipart/form-data`, e.g., when it's returned by a server
+/// function, it's serialized as a struct of its fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UploadedFile {
+    /// The name of the file on the client, e.g., `photo.png`.
+    pub name: String,
+    /// The MIME type of the file, e.g., `image/png`.
+    pub content_type: String,
+    /// The contents of the file.
+    pub bytes: Vec<u8>,
+}
+
+thread_local! {
+    // while arguments are encoded or decoded as multipart, the files among
+    // them, which are sent as parts of their own and stand in their fields
+    // as the index of their part
+    static FILES: RefCell<Option<Vec<UploadedFile>>> = Default::default();
+}
+
+const FILE_PREFIX: &str = "\0file:";
+
+// runs `f` with the files in `FILES`, and returns them along with its result
+fn with_files<T>(
+    files: Vec<UploadedFile>,
+    f: impl FnOnce() -> T,
+) -> (T, Vec<UploadedFile>) {
+    struct Reset(Option<Vec<UploadedFile>>);
+
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            FILES.with(|files| *files.borrow_mut() = self.0.take());
+        }
+    }
+
+    let reset = Reset(FILES.with(|slot| slot.replace(Some(files))));
+    let value = f();
+    let files = FILES.with(|files| files.take()).unwrap_or_default();
+    drop(reset);
+    (value, files)
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "UploadedFile")]
+struct FileFields {
+    name: String,
+    content_type: String,
+    bytes: Vec<u8>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FileRepr {
+    Part(String),
+    Fields(FileFields),
+}
+
+impl Serialize for UploadedFile {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let part = FILES.with(|files| {
+            let mut files = files.borrow_mut();
+            let files = files.as_mut()?;
+            files.push(self.clone());
+            Some(files.len() - 1)
+        });
+        match part {
+            Some(part) => {
+                serializer.serialize_str(&format!("{FILE_PREFIX}{part}"))
+            }
+            None => FileFields {
+                name: self.name.clone(),
+                content_type: self.content_type.clone(),
+                bytes: self.bytes.clone(),
+            }
+            .serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for UploadedFile {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        match FileRepr::deserialize(deserializer)? {
+            FileRepr::Fields(FileFields {
+                name,
+                content_type,
+                bytes,
+            }) => Ok(Self {
+                name,
+                content_type,
+                bytes,
+            }),
+            FileRepr::Part(part) => part
+                .strip_prefix(FILE_PREFIX)
+                .and_then(|part| part.parse::<usize>().ok())
+                .and_then(|part| {
+                    FILES.with(|files| {
+                        let mut files = files.borrow_mut();
+                        files.as_mut()?.get_mut(part).map(std::mem::take)
+                    })
+                })
+                .ok_or_else(|| de::Error::custom("expected an uploaded file")),
+        }
+    }
+}
+
+/// Encodes a struct or map as `multipart/form-data`, and returns the
+/// `Content-Type` to send it with, which includes its boundary, along with
+/// the body.
+pub fn to_body<T: Serialize + ?Sized>(
+    value: &T,
+) -> Result<(String, Vec<u8>), Error> {
+    let (fields, files) = with_files(Vec::new(), || form::to_fields(value));
+    let fields = fields?;
+    let parts = fields
+        .iter()
+        .map(|(name, value)| {
+            let file = value
+                .strip_prefix(FILE_PREFIX)
+                .and_then(|part| part.parse::<usize>().ok())
+                .and_then(|part| files.get(part));
+            let mut head = format!(
+                "Content-Disposition: form-data; name=\"{}\"",
+                escape(name)
+            );
+            match file {
+                Some(file) => {
+                    head.push_str(&format!(
+                        "; filename=\"{}\"\r\nContent-Type: {}",
+                        escape(&file.name),
+                        if file.content_type.is_empty() {
+                            "application/octet-stream"
+                        } else {
+                            &file.content_type
+                        }
+                    ));
+                    (head, file.bytes.as_slice())
+                }
+                None => (head, value.as_bytes()),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // the boundary can't appear in any of the parts
+    let mut seed = 0;
+    let boundary = loop {
+        let mut hash = seed;
+        for (head, body) in &parts {
+            hash = xxhash_rust::const_xxh64::xxh64(head.as_bytes(), hash);
+            hash = xxhash_rust::const_xxh64::xxh64(body, hash);
+        }
+        let boundary = format!("server-fn-{hash:016x}");
+        if !parts
+            .iter()
+            .any(|(_, body)| contains(body, boundary.as_bytes()))
+        {
+            break boundary;
+        }
+        seed += 1;
+    };
+
+    let mut body = Vec::new();
+    for (head, content) in parts {
+        body.extend_from_slice(
+            format!("--{boundary}\r\n{head}\r\n\r\n").as_bytes(),
+        );
+        body.extend_from_slice(content);
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(format!("--{boundary}--\r\n").as_bytes());
+    Ok((format!("multipart/form-data; boundary={boundary}"), body))
+}
+
+/// Decodes a value from a `multipart/form-data` body.
+///
+/// The boundary is read from the body, which starts with it. File inputs that
+/// were left empty are left out, so they're `None` or empty.
+pub fn from_body<T: de::DeserializeOwned>(body: &[u8]) -> Result<T, Error> {
+    let fields = parts(body)?
+        .into_iter()
+        .map(|part| {
+            let Part {
+                name,
+                filename,
+                content_type,
+                content,
+            } = part;
+            let field = match filename {
+                Some(filename) => Field::File(UploadedFile {
+                    name: filename,
+                    content_type: content_type
+                        .unwrap_or_else(|| "application/octet-stream".into()),
+                    bytes: content.to_vec(),
+                }),
+                None => Field::Text(
+                    String::from_utf8(content.to_vec()).map_err(|_| {
+                        <Error as de::Error>::custom(format!(
+                            "the field {name:?} isn't UTF-8"
+                        ))
+                    })?,
+                ),
+            };
+            Ok((name, field))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    from_fields(fields)
+}
+
+/// A field of a `multipart/form-data` body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Field {
+    /// The value of a field that isn't a file.
+    Text(String),
+    /// A file.
+    File(UploadedFile),
+}
+
+/// Decodes a value from the named fields of a `multipart/form-data` body,
+/// e.g., the entries of a browser's `FormData`, as [from_body] does.
+pub fn from_fields<T: de::DeserializeOwned>(
+    fields: impl IntoIterator<Item = (String, Field)>,
+) -> Result<T, Error> {
+    let mut text = Vec::new();
+    let mut files = Vec::new();
+    for (name, field) in fields {
+        match field {
+            // what a browser sends for a file input that was left empty
+            Field::File(file)
+                if file.name.is_empty() && file.bytes.is_empty() => {}
+            Field::File(file) => {
+                text.push((name, format!("{FILE_PREFIX}{}", files.len())));
+                files.push(file);
+            }
+            Field::Text(value) => text.push((name, value)),
+        }
+    }
+    with_files(files, || form::from_fields(text)).0
+}
+
+struct Part<'a> {
+    name: String,
+    filename: Option<String>,
+    content_type: Option<String>,
+    content: &'a [u8],
+}
+
+fn parts(body: &[u8]) -> Result<Vec<Part<'_>>, Error> {
+    let invalid = |reason: &str| -> Error {
+        de::Error::custom(format!("invalid multipart body: {reason}"))
+    };
+
+    let first_line =
+        find(body, b"\r\n").ok_or_else(|| invalid("no boundary"))?;
+    let boundary = body[..first_line]
+        .strip_prefix(b"--")
+        .ok_or_else(|| invalid("no boundary"))?;
+    // a body without any parts is just its closing boundary
+    if boundary.ends_with(b"--") && body[first_line + 2..].is_empty() {
+        return Ok(Vec::new());
+    }
+    let delimiter = [b"\r\n--", boundary].concat();
+
+    let mut parts = Vec::new();
+    let mut rest = &body[first_line + 2..];
+    loop {
+        let end = find(rest, &delimiter)
+            .ok_or_else(|| invalid("the last part isn't closed"))?;
+        let (part, after) = (&rest[..end], &rest[end + delimiter.len()..]);
+        let head_end = find(part, b"\r\n\r\n")
+            .ok_or_else(|| invalid("a part has no headers"))?;
+        let head = std::str::from_utf8(&part[..head_end])
+            .map_err(|_| invalid("a part's headers aren't UTF-8"))?;
+
+        let mut name = None;
+        let mut filename = None;
+        let mut content_type = None;
+        for header in head.split("\r\n") {
+            let Some((key, value)) = header.split_once(':') else {
+                continue;
+            };
+            if key.trim().eq_ignore_ascii_case("content-disposition") {
+                for (key, value) in params(value) {
+                    match key.as_str() {
+                        "name" => name = Some(value),
+                        "filename" => filename = Some(value),
+                        _ => {}
+                    }
+                }
+            } else if key.trim().eq_ignore_ascii_case("content-type") {
+                content_type = Some(value.trim().to_string());
+            }
+        }
+        parts.push(Part {
+            name: name.ok_or_else(|| invalid("a part has no name"))?,
+            filename,
+            content_type,
+            content: &part[head_end + 4..],
+        });
+
+        if after.starts_with(b"--") {
+            return Ok(parts);
+        }
+        rest = after.strip_prefix(b"\r\n").ok_or_else(|| {
+            invalid("a boundary isn't followed by a line break")
+        })?;
+    }
+}
+
+// the parameters of a `Content-Disposition`, e.g., `form-data; name="photo"`
+fn params(header: &str) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+    let mut rest = header;
+    while let Some(at) = rest.find(';') {
+        rest = rest[at + 1..].trim_start();
+        let Some((key, value)) = rest.split_once('=') else {
+            break;
+        };
+        let key = key.trim().to_ascii_lowercase();
+        let (value, after) = match value.strip_prefix('"') {
+            Some(quoted) => {
+                let end = quoted.find('"').unwrap_or(quoted.len());
+                (&quoted[..end], quoted.get(end + 1..).unwrap_or_default())
+            }
+            None => {
+                let end = value.find(';').unwrap_or(value.len());
+                (value[..end].trim(), &value[end..])
+            }
+        };
+        params.push((key, unescape(value)));
+        rest = after;
+    }
+    params
+}
+
+// as browsers do, quotes and line breaks are percent-encoded in names, and
+// the rest is sent as UTF-8
+fn escape(name: &str) -> String {
+    name.replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn unescape(name: &str) -> String {
+    name.replace("%22", "\"")
+        .replace("%0D", "\r")
+        .replace("%0A", "\n")
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    find(haystack, needle).is_some()
+}
+
+#[cfg(any(feature = "ssr", doc))]
+static UPLOAD_LIMIT: std::sync::atomic::AtomicUsize =
+    std::sync::atomic::AtomicUsize::new(10 * 1024 * 1024);
+
+/// Sets the largest `multipart/form-data` body, in bytes, that server
+/// functions accept. Defaults to 10 MiB.
+#[cfg(any(feature = "ssr", doc))]
+pub fn set_upload_limit(bytes: usize) {
+    UPLOAD_LIMIT.store(bytes, std::sync::atomic::Ordering::Relaxed);
+}
+
+/// The largest `multipart/form-data` body, in bytes, that server functions
+/// accept, as set with [set_upload_limit]. A server integration only needs to
+/// read one byte more than this before it calls the function.
+#[cfg(any(feature = "ssr", doc))]
+pub fn upload_limit() -> usize {
+    UPLOAD_LIMIT.load(std::sync::atomic::Ordering::Relaxed)
+}
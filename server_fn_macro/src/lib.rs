@@ -75,13 +75,30 @@ pub fn server_macro_impl(
         struct_name,
         prefix,
         encoding,
-        ..
+        output,
     } = syn::parse2::<ServerFnName>(args)?;
     let prefix = prefix.unwrap_or_else(|| Literal::string(""));
     let encoding = quote!(#server_fn_path::#encoding);
 
     let body = syn::parse::<ServerFnBody>(body.into())?;
     let fn_name = &body.ident;
+    // `add_todo` is described by `AddTodo`, unless it's given another name
+    let struct_name = struct_name.unwrap_or_else(|| {
+        let name = fn_name
+            .to_string()
+            .split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| {
+                        first.to_uppercase().chain(chars).collect::<String>()
+                    })
+                    .unwrap_or_default()
+            })
+            .collect::<String>();
+        Ident::new(&name, fn_name.span())
+    });
     let fn_name_as_str = body.ident.to_string();
     let vis = body.vis;
     let block = body.block;
@@ -195,6 +212,29 @@ pub fn server_macro_impl(
         );
     };
 
+    // `E` in `Result<T, ServerFnError<E>>`
+    let error_ty = match &return_ty {
+        syn::Type::Path(pat) => match &pat.path.segments[0].arguments {
+            PathArguments::AngleBracketed(args) => {
+                match args.args.iter().nth(1) {
+                    Some(GenericArgument::Type(syn::Type::Path(error))) => {
+                        error.path.segments.last().and_then(
+                            |error| match &error.arguments {
+                                PathArguments::AngleBracketed(args) => {
+                                    args.args.first()
+                                }
+                                _ => None,
+                            },
+                        )
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        },
+        _ => None,
+    };
+
     let server_ctx_path = if let Some(ctx) = &server_context {
         let path = &ctx.path;
         quote!(#path)
@@ -206,6 +246,18 @@ pub fn server_macro_impl(
         .map(|path| quote!(#path))
         .unwrap_or_else(|| quote! { server_fn });
 
+    let error_ty = error_ty
+        .map(|error| quote!(#error))
+        .unwrap_or_else(|| quote!(#server_fn_path::NoCustomError));
+
+    let output_encoding = output.map(|output| {
+        quote! {
+            fn output_encoding() -> #server_fn_path::Encoding {
+                #server_fn_path::#output
+            }
+        }
+    });
+
     Ok(quote::quote! {
         #[derive(Clone, Debug, ::serde::Serialize, ::serde::Deserialize)]
         pub struct #struct_name {
@@ -214,6 +266,7 @@ pub fn server_macro_impl(
 
         impl #server_fn_path::ServerFn<#server_ctx_path> for #struct_name {
             type Output = #output_ty;
+            type Error = #error_ty;
 
             fn prefix() -> &'static str {
                 #prefix
@@ -227,15 +280,17 @@ pub fn server_macro_impl(
                 #encoding
             }
 
+            #output_encoding
+
             #[cfg(feature = "ssr")]
-            fn call_fn(self, cx: #server_ctx_path) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Self::Output, server_fn::ServerFnError>>>> {
+            fn call_fn(self, cx: #server_ctx_path) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Self::Output, #server_fn_path::ServerFnError<Self::Error>>>>> {
                 let #struct_name { #(#field_names),* } = self;
                 #cx_assign_statement;
                 Box::pin(async move { #fn_name( #cx_fn_arg #(#field_names_2),*).await })
             }
 
             #[cfg(not(feature = "ssr"))]
-            fn call_fn_client(self, cx: #server_ctx_path) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Self::Output, server_fn::ServerFnError>>>> {
+            fn call_fn_client(self, cx: #server_ctx_path) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Self::Output, #server_fn_path::ServerFnError<Self::Error>>>>> {
                 let #struct_name { #(#field_names_3),* } = self;
                 Box::pin(async move { #fn_name( #cx_fn_arg #(#field_names_4),*).await })
             }
@@ -247,6 +302,7 @@ pub fn server_macro_impl(
         }
 
         #[cfg(not(feature = "ssr"))]
+        #[allow(unused_variables)]
         #vis async fn #fn_name(#(#fn_args_2),*) #output_arrow #return_ty {
             let prefix = #struct_name::prefix().to_string();
             let url = prefix + "/" + #struct_name::url();
@@ -256,34 +312,101 @@ pub fn server_macro_impl(
 }
 
 struct ServerFnName {
-    struct_name: Ident,
-    _comma: Option<Token![,]>,
+    struct_name: Option<Ident>,
     prefix: Option<Literal>,
-    _comma2: Option<Token![,]>,
     encoding: Path,
+    output: Option<Path>,
+}
+
+// `ReadPosts, "/api", "Cbor"`: the name comes first, if it's given, followed
+// by the prefix and the encoding, each of which can also be given by name,
+// along with the encoding of the results, e.g., `encoding = "Cbor"`
+enum ServerFnArg {
+    Name(Ident),
+    Value(Literal),
+    Named(Ident, Literal),
+}
+
+impl Parse for ServerFnArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Ident) && input.peek2(Token![=]) {
+            let key = input.parse()?;
+            input.parse::<Token![=]>()?;
+            Ok(Self::Named(key, input.parse()?))
+        } else if input.peek(Ident) {
+            Ok(Self::Name(input.parse()?))
+        } else {
+            Ok(Self::Value(input.parse()?))
+        }
+    }
+}
+
+fn parse_encoding(encoding: &Literal, allowed: &[&str]) -> Path {
+    let name = encoding.to_string();
+    let name = name.trim_matches('"');
+    if !allowed.contains(&name) {
+        abort!(encoding, "Encoding Not Found"; help = "expected one of {:?}", allowed);
+    }
+    let name = Ident::new(name, encoding.span());
+    syn::parse_quote!(Encoding::#name)
 }
 
 impl Parse for ServerFnName {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let struct_name = input.parse()?;
-        let _comma = input.parse()?;
-        let prefix = input.parse()?;
-        let _comma2 = input.parse()?;
-        let encoding = input
-            .parse::<Literal>()
-            .map(|encoding| match encoding.to_string().as_str() {
-                "\"Url\"" => syn::parse_quote!(Encoding::Url),
-                "\"Cbor\"" => syn::parse_quote!(Encoding::Cbor),
-                _ => abort!(encoding, "Encoding Not Found"),
-            })
-            .unwrap_or(syn::parse_quote!(Encoding::Url));
+        const ENCODINGS: &[&str] = &["Url", "Json", "Cbor", "Multipart"];
+        const OUTPUT_ENCODINGS: &[&str] = &["Json", "Cbor"];
+
+        let args =
+            Punctuated::<ServerFnArg, Token![,]>::parse_terminated(input)?;
+        let mut struct_name = None;
+        let mut prefix = None;
+        let mut encoding = None;
+        let mut output = None;
+        let mut values = 0;
+        for (index, arg) in args.into_iter().enumerate() {
+            match arg {
+                ServerFnArg::Name(name) if index == 0 => {
+                    struct_name = Some(name)
+                }
+                ServerFnArg::Name(name) => {
+                    abort!(
+                        name,
+                        "the name of the server function should come first"
+                    )
+                }
+                ServerFnArg::Value(value) => {
+                    match values {
+                        0 => prefix = Some(value),
+                        1 => encoding = Some(parse_encoding(&value, ENCODINGS)),
+                        _ => abort!(value, "unexpected argument"),
+                    }
+                    values += 1;
+                }
+                ServerFnArg::Named(key, value) => {
+                    match key.to_string().as_str() {
+                        "prefix" => prefix = Some(value),
+                        "encoding" => {
+                            encoding = Some(parse_encoding(&value, ENCODINGS))
+                        }
+                        "output" => {
+                            output =
+                                Some(parse_encoding(&value, OUTPUT_ENCODINGS))
+                        }
+                        _ => abort!(
+                            key,
+                            "expected `prefix`, `encoding` or `output`"
+                        ),
+                    }
+                }
+            }
+        }
 
         Ok(Self {
             struct_name,
-            _comma,
             prefix,
-            _comma2,
-            encoding,
+            encoding: encoding
+                .unwrap_or_else(|| syn::parse_quote!(Encoding::Url)),
+            output,
         })
     }
 }